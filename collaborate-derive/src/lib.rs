@@ -7,7 +7,7 @@ use proc_macro::TokenStream;
 use quote::{Tokens, ToTokens};
 use syn::*;
 
-#[proc_macro_derive(ColladaElement, attributes(name, attribute, child, text, optional_with_default, required))]
+#[proc_macro_derive(ColladaElement, attributes(name, attribute, any_attribute, child, text, optional_with_default, required, collada, text_type, write_with))]
 pub fn derive(input: TokenStream) -> TokenStream {
     // Parse the string representation.
     let ast = syn::parse_derive_input(&input.to_string()).unwrap();
@@ -28,6 +28,7 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
     // ----------------------------------------------------------------------------------
     let mut children = Vec::new();
     let mut attributes = Vec::new();
+    let mut any_attribute = None;
     let mut text_contents = None;
     let mut stub_me_out = false;
 
@@ -36,11 +37,15 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
             let variants = variants.drain(..)
                 .map(|variant| {
                     let name = variant.ident;
+                    let version = variant.attrs.iter()
+                        .filter(|attribute| attribute.name() == "collada")
+                        .map(|attribute| parse_version(&attribute.value))
+                        .last();
                     match variant.data {
                         VariantData::Tuple(mut fields) => {
                             assert!(fields.len() == 1, "Enum variants may only have a single type");
                             let inner_type = fields.pop().unwrap().ty;
-                            return EnumMemberVariant { name, inner_type };
+                            return EnumMemberVariant { name, inner_type, version };
                         }
 
                         _ => panic!("Only tuple variants with a single member are supported for enum variants"),
@@ -60,8 +65,10 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
         }
     };
 
-    // Process the top-level attributes on the type to find the `#[name = "foo"]` attribute.
+    // Process the top-level attributes on the type to find the `#[name = "foo"]` attribute, and
+    // the `#[write_with = "function"]` attribute for elements that write themselves by hand.
     // -------------------------------------------------------------------------------------
+    let mut write_with = None;
     let element_name = {
         let mut element_name = None;
 
@@ -70,6 +77,8 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
                 MetaItem::NameValue(attr_name, Lit::Str(value, _)) => {
                     if attr_name == "name" {
                         element_name = Some(value);
+                    } else if attr_name == "write_with" {
+                        write_with = Some(Ident::new(value));
                     }
                 }
 
@@ -84,6 +93,7 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
         enum MemberType {
             Child,
             Attribute,
+            AnyAttribute,
             Text,
         }
 
@@ -96,12 +106,35 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
         let mut member_type = None;
         let mut is_required = false;
         let mut optional_with_default = None;
+        let mut flatten = None;
+        let mut version = None;
+        let mut split_with = None;
+        let mut count_member = None;
+        let mut is_text_type = false;
 
         for attribute in field.attrs {
             match attribute.name() {
                 "child" => {
                     assert!(member_type.is_none(), "Member type may only be specified once");
                     member_type = Some(MemberType::Child);
+
+                    match attribute.value {
+                        MetaItem::Word(_) => {}
+
+                        MetaItem::List(_, ref items) => {
+                            for item in items {
+                                match *item {
+                                    NestedMetaItem::MetaItem(MetaItem::NameValue(ref item_name, Lit::Str(ref value, _))) if item_name == "flatten" => {
+                                        flatten = Some(value.clone());
+                                    }
+
+                                    _ => panic!(r#"Invalid usage of `#[child(...)]`, valid uses are `#[child]` or `#[child(flatten = "<wrapper>")]`"#),
+                                }
+                            }
+                        }
+
+                        _ => panic!(r#"Invalid usage of `#[child]`, valid uses are `#[child]` or `#[child(flatten = "<wrapper>")]`"#),
+                    }
                 }
 
                 "attribute" => {
@@ -109,13 +142,44 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
                     member_type = Some(MemberType::Attribute);
                 }
 
+                "any_attribute" => {
+                    assert!(member_type.is_none(), "Member type may only be specified once");
+                    member_type = Some(MemberType::AnyAttribute);
+                }
+
                 "text" => {
                     assert!(member_type.is_none(), "Member type may only be specified once");
                     member_type = Some(MemberType::Text);
+
+                    match attribute.value {
+                        MetaItem::Word(_) => {}
+
+                        MetaItem::List(_, ref items) => {
+                            for item in items {
+                                match *item {
+                                    NestedMetaItem::MetaItem(MetaItem::NameValue(ref item_name, Lit::Str(ref value, _))) if item_name == "split_with" => {
+                                        split_with = Some(Ident::new(value.clone()));
+                                    }
+
+                                    NestedMetaItem::MetaItem(MetaItem::NameValue(ref item_name, Lit::Str(ref value, _))) if item_name == "count" => {
+                                        count_member = Some(Ident::new(value.clone()));
+                                    }
+
+                                    _ => panic!(r#"Invalid usage of `#[text(...)]`, valid uses are `#[text]`, `#[text(split_with = "<function>")]`, or `#[text(count = "<member>")]`"#),
+                                }
+                            }
+                        }
+
+                        _ => panic!(r#"Invalid usage of `#[text]`, valid uses are `#[text]`, `#[text(split_with = "<function>")]`, or `#[text(count = "<member>")]`"#),
+                    }
                 }
 
                 "required" => { is_required = true; }
 
+                "text_type" => { is_text_type = true; }
+
+                "collada" => { version = Some(parse_version(&attribute.value)); }
+
                 "optional_with_default" => {
                     match attribute.value {
                         MetaItem::Word(_) => {
@@ -148,7 +212,20 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
             }
         }
 
-        let member_type = member_type.expect("Missing `#[child]`, `#[attribute]`, or `#[text]` attribute on member {:?}, one is required");
+        let member_type = member_type.expect("Missing `#[child]`, `#[attribute]`, `#[any_attribute]`, or `#[text]` attribute on member {:?}, one is required");
+
+        // The catch-all attribute member collects every attribute that isn't otherwise declared,
+        // so its type is a collection rather than a single value. We don't need to inspect the
+        // type any further, the collection just needs to impl `AnyAttributes`.
+        if let MemberType::AnyAttribute = member_type {
+            assert!(any_attribute.is_none(), "Only one member may have the `#[any_attribute]` attribute");
+            assert!(version.is_none(), "`#[any_attribute]` member may not be restricted to a version");
+            any_attribute = Some(AnyAttribute {
+                member_name,
+                ty: field.ty,
+            });
+            continue;
+        }
 
         // Determine the data type and occurrences for the member.
         let path = match field.ty.clone() {
@@ -194,6 +271,9 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
                 "Option" => {
                     (ChildOccurrences::Optional, inner_type)
                 }
+                "MaybeEmpty" => {
+                    (ChildOccurrences::MaybeEmpty, inner_type)
+                }
                 "Vec" => {
                     if is_required {
                         (ChildOccurrences::RequiredMany, inner_type)
@@ -201,14 +281,28 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
                         (ChildOccurrences::OptionalMany, inner_type)
                     }
                 }
-                _ => { return Err("Unexpected child type with parameters, only `Vec<T>` and `Option<T>` are allowed to have type parameters")?; }
+                // `RawArray<T>` is a single required value that holds the text for later decoding.
+                "RawArray" => {
+                    (ChildOccurrences::Required, field.ty)
+                }
+                _ => { return Err("Unexpected child type with parameters, only `Vec<T>`, `Option<T>`, `MaybeEmpty<T>`, and `RawArray<T>` are allowed to have type parameters")?; }
             }
         };
 
         // Determine the data type of the inner type. A specific set of known types are parsed
-        // automatically from text data. Any unknown type is assumed to impl `ColladaElement`,
-        // and so parsing defers to the types `ColladaElement` impl.
+        // automatically from text data, as are types marked with `#[text_type]`. Any unknown type
+        // is assumed to impl `ColladaElement`, and so parsing defers to the types
+        // `ColladaElement` impl.
         let data_type = match inner_type {
+            _ if is_text_type => {
+                match member_type {
+                    MemberType::Child => {}
+                    _ => { return Err("`#[text_type]` may only be used on `#[child]` members")?; }
+                }
+
+                DataType::TextData(inner_type.clone())
+            }
+
             Ty::Path(None, ref path) => {
                 let segment = path.segments.last().expect("Somehow got an empty path ?_?");
                 let type_ident = segment.ident.as_ref();
@@ -232,11 +326,30 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
         // has a `#[child]` or an `#[attribute]` attribute.
         match member_type {
             MemberType::Child => {
+                if occurrences == ChildOccurrences::MaybeEmpty {
+                    if let DataType::ColladaElement(_) = data_type {
+                        return Err("`MaybeEmpty<T>` may only be used for children containing text data")?;
+                    }
+                }
+
+                if flatten.is_some() {
+                    if let DataType::TextData(_) = data_type {
+                        return Err("`#[child(flatten = \"...\")]` may only be used with element types, not text data")?;
+                    }
+
+                    match occurrences {
+                        ChildOccurrences::Optional | ChildOccurrences::Required => {}
+                        _ => { return Err("`#[child(flatten = \"...\")]` may only be used with `T` or `Option<T>` members")?; }
+                    }
+                }
+
                 children.push(Child {
                     member_name: member_name.clone(),
                     element_name: special_name,
                     occurrences: occurrences,
                     data_type: data_type,
+                    flatten: flatten,
+                    version: version,
                 });
             }
 
@@ -250,6 +363,10 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
                     ChildOccurrences::OptionalMany | ChildOccurrences::RequiredMany => {
                         return Err("Attribute may not be repeating, meaning it may not be of type `Vec<T>`".into());
                     }
+
+                    ChildOccurrences::MaybeEmpty => {
+                        return Err("Attribute may not be of type `MaybeEmpty<T>`, use `Option<T>` instead".into());
+                    }
                 };
 
                 attributes.push(Attribute {
@@ -257,15 +374,38 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
                     attrib_name: special_name,
                     occurrences,
                     ty: inner_type,
+                    version,
                 });
             }
 
+            MemberType::AnyAttribute => { unreachable!(); }
+
             MemberType::Text => {
                 assert!(text_contents.is_none(), "Only one member may have the `#[text]` attribute");
+                assert!(version.is_none(), "`#[text]` member may not be restricted to a version");
+                assert!(occurrences != ChildOccurrences::MaybeEmpty, "`#[text]` member may not be of type `MaybeEmpty<T>`");
+
+                if count_member.is_some() {
+                    match occurrences {
+                        ChildOccurrences::OptionalMany | ChildOccurrences::RequiredMany => {}
+                        ChildOccurrences::Required if is_raw_array_type(&inner_type) => {}
+                        _ => { return Err("`#[text(count = \"...\")]` may only be used with members of type `Vec<T>` or `RawArray<T>`")?; }
+                    }
+                }
+
+                if split_with.is_some() {
+                    match occurrences {
+                        ChildOccurrences::OptionalMany | ChildOccurrences::RequiredMany => {}
+                        _ => { return Err("`#[text(split_with = \"...\")]` may only be used with list members of type `Vec<T>`")?; }
+                    }
+                }
+
                 text_contents = Some(TextContents {
                     member_name,
                     occurrences,
                     member_type: inner_type,
+                    split_with,
+                    count_member,
                 });
             }
         }
@@ -275,8 +415,10 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
         ident,
         element_name,
         attributes,
+        any_attribute,
         children,
         text_contents,
+        write_with,
 
         stub_me_out,
     }))
@@ -291,9 +433,14 @@ struct StructMember {
     ident: Ident,
     element_name: String,
     attributes: Vec<Attribute>,
+    any_attribute: Option<AnyAttribute>,
     children: Vec<Child>,
     text_contents: Option<TextContents>,
 
+    /// A function that writes the element in place of the generated code, for elements that are
+    /// written differently depending on the version of the specification.
+    write_with: Option<Ident>,
+
     /// Temporary flag to allow us to stub out elements until the entire spec is covered.
     stub_me_out: bool,
 }
@@ -306,6 +453,9 @@ struct EnumMember {
 struct EnumMemberVariant {
     name: Ident,
     inner_type: Ty,
+
+    /// The version(s) of the specification that allow the variant, if it's restricted.
+    version: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -320,6 +470,15 @@ struct Attribute {
     attrib_name: String,
     occurrences: AttributeOccurrences,
     ty: Ty,
+
+    /// The version(s) of the specification that allow the attribute, if it's restricted.
+    version: Option<String>,
+}
+
+/// A member that collects all attributes not otherwise declared on the element.
+struct AnyAttribute {
+    member_name: Ident,
+    ty: Ty,
 }
 
 enum DataType {
@@ -332,6 +491,13 @@ struct Child {
     element_name: String,
     occurrences: ChildOccurrences,
     data_type: DataType,
+
+    /// The name of the wrapper element (e.g. `technique_common`) that the child appears in, if
+    /// the wrapper is flattened away in the model.
+    flatten: Option<String>,
+
+    /// The version(s) of the specification that allow the child, if it's restricted.
+    version: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -346,7 +512,14 @@ enum ChildOccurrences {
     OptionalWithDefault(DefaultValue),
     Required,
     OptionalMany,
+
+    /// A repeating child that must appear at least once. For text data, every occurrence must
+    /// contain a value, the same as for `Required`.
     RequiredMany,
+
+    /// An optional text child stored as a `MaybeEmpty<T>`, which distinguishes an empty child
+    /// element from a missing one.
+    MaybeEmpty,
 }
 
 impl ToTokens for ChildOccurrences {
@@ -361,6 +534,8 @@ impl ToTokens for ChildOccurrences {
             ChildOccurrences::OptionalMany => { tokens.append("Many"); }
 
             ChildOccurrences::RequiredMany => { tokens.append("RequiredMany"); }
+
+            ChildOccurrences::MaybeEmpty => { tokens.append("Optional"); }
         }
     }
 }
@@ -369,6 +544,37 @@ struct TextContents {
     member_name: Ident,
     occurrences: ChildOccurrences,
     member_type: Ty,
+
+    /// The function used to split list contents into individual values. Lists are split on
+    /// whitespace if not specified.
+    split_with: Option<Ident>,
+
+    /// The attribute member holding the number of values the text is declared to contain, which
+    /// is checked if `ParseOptions::validate_counts` is set.
+    count_member: Option<Ident>,
+}
+
+/// Parses the `#[collada(version = "...")]` attribute that restricts a member to some versions of
+/// the specification.
+fn parse_version(value: &MetaItem) -> String {
+    let mut version = None;
+    match *value {
+        MetaItem::List(_, ref items) => {
+            for item in items {
+                match *item {
+                    NestedMetaItem::MetaItem(MetaItem::NameValue(ref item_name, Lit::Str(ref value, _))) if item_name == "version" => {
+                        version = Some(value.clone());
+                    }
+
+                    _ => panic!(r#"Invalid usage of `#[collada(...)]`, valid uses are `#[collada(version = "<version>")]`"#),
+                }
+            }
+        }
+
+        _ => panic!(r#"Invalid usage of `#[collada]`, valid uses are `#[collada(version = "<version>")]`"#),
+    }
+
+    version.expect(r#"Missing version in `#[collada(...)]`, valid uses are `#[collada(version = "<version>")]`"#)
 }
 
 fn generate_impl(derive_input: DeriveInput) -> Result<quote::Tokens, String> {
@@ -394,7 +600,7 @@ fn generate_enum_impl(config: EnumMember) -> Result<quote::Tokens, String> {
 
     let parse_variants = variants.iter()
         .fold(None, |joined, current| {
-            let &EnumMemberVariant { ref name, ref inner_type } = current;
+            let &EnumMemberVariant { ref name, ref inner_type, .. } = current;
             match joined {
                 None => Some(quote! {
                     if #inner_type::name_test(&*element_start.name.local_name) {
@@ -417,6 +623,49 @@ fn generate_enum_impl(config: EnumMember) -> Result<quote::Tokens, String> {
         .map(|variant| &variant.inner_type)
         .map(|ty| quote! { #ty::add_names(names); });
 
+    // Only the variants restricted to a version report one, the rest are allowed in every version.
+    let version_restrictions = variants.iter()
+        .filter_map(|variant| {
+            let ty = &variant.inner_type;
+            variant.version.as_ref().map(|version| quote! {
+                if #ty::name_test(name) {
+                    return Some(#version);
+                }
+            })
+        });
+
+    let add_coverage = variants.iter()
+        .map(|variant| {
+            let ty = &variant.inner_type;
+            match variant.version {
+                Some(ref version) => quote! {
+                    if coverage.allows(#version) {
+                        #ty::add_coverage(coverage);
+                    }
+                },
+
+                None => quote! { #ty::add_coverage(coverage); },
+            }
+        });
+
+    let write_variants = variants.iter()
+        .map(|variant| {
+            let name = &variant.name;
+            let ty = &variant.inner_type;
+            match variant.version {
+                Some(ref version) => quote! {
+                    #ident::#name(ref element) => {
+                        writer.check_version(::utils::element_name::<#ty>(), #version)?;
+                        ::utils::ColladaElement::write_element(element, writer)
+                    }
+                },
+
+                None => quote! {
+                    #ident::#name(ref element) => ::utils::ColladaElement::write_element(element, writer),
+                },
+            }
+        });
+
     Ok(quote! {
         impl ::utils::ColladaElement for #ident {
             fn name_test(name: &str) -> bool {
@@ -424,11 +673,11 @@ fn generate_enum_impl(config: EnumMember) -> Result<quote::Tokens, String> {
             }
 
             fn parse_element<R>(
-                reader: &mut ::xml::reader::EventReader<R>,
+                reader: &mut ::utils::DocumentReader<R>,
                 element_start: ::utils::ElementStart,
             ) -> Result<#ident>
             where
-                R: ::std::io::Read,
+                R: ::backend::XmlPull,
             {
                 #[allow(unused_imports)]
                 use ::xml::common::Position;
@@ -442,6 +691,22 @@ fn generate_enum_impl(config: EnumMember) -> Result<quote::Tokens, String> {
             fn add_names(names: &mut Vec<&'static str>) {
                 #( #add_names )*
             }
+
+            #[allow(unused_variables)]
+            fn version_restriction(name: &str) -> Option<&'static str> {
+                #( #version_restrictions )*
+                None
+            }
+
+            fn write_element<W: ::std::io::Write>(&self, writer: &mut ::writer::XmlWriter<W>) -> ::writer::Result<()> {
+                match *self {
+                    #( #write_variants )*
+                }
+            }
+
+            fn add_coverage(coverage: &mut ::coverage::ElementCoverage) {
+                #( #add_coverage )*
+            }
         }
     })
 }
@@ -451,8 +716,10 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
         ident,
         element_name,
         attributes,
+        any_attribute,
         children,
         text_contents,
+        write_with,
         stub_me_out
     } = config;
 
@@ -465,6 +732,13 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
                 quote! { let mut #ident = None; }
             });
 
+        let any_attrib = any_attribute.as_ref()
+            .map(|any_attribute| {
+                let AnyAttribute { ref member_name, ref ty } = *any_attribute;
+                quote! { let mut #member_name: #ty = ::std::default::Default::default(); }
+            })
+            .unwrap_or(Tokens::new());
+
         let childs = children.iter()
             .map(|child| {
                 let &Child { ref member_name, ref occurrences, .. } = child;
@@ -478,6 +752,10 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
                     ChildOccurrences::OptionalMany | ChildOccurrences::RequiredMany => {
                         quote! { let mut #member_name = Vec::new(); }
                     }
+
+                    ChildOccurrences::MaybeEmpty => {
+                        quote! { let mut #member_name = ::common::MaybeEmpty::Absent; }
+                    }
                 }
             });
 
@@ -494,12 +772,15 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
                     ChildOccurrences::OptionalMany | ChildOccurrences::RequiredMany => {
                         quote! { let mut #member_name = Vec::new(); }
                     }
+
+                    ChildOccurrences::MaybeEmpty => { unreachable!(); }
                 }
             })
             .unwrap_or(Tokens::new());
 
         quote! {
             #( #attribs )*
+            #any_attrib
             #( #childs )*
             #text
         }
@@ -507,19 +788,26 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
 
     // Generate code for parsing attributes.
     // -------------------------------------
-    let attributes_impl = if attributes.len() != 0 {
+    let attributes_impl = if attributes.len() != 0 || any_attribute.is_some() {
         let matches = attributes.iter()
             .map(|attrib| {
-                let &Attribute { ref member_name, ref attrib_name, ref ty, .. } = attrib;
-                quote! {
-                    #attrib_name => {
-                        let result = #ty::from_str(&*attribute.value)
-                            .map_err(|error| Error {
-                                position: reader.position(),
-                                kind: error.into(),
-                            })?;
-                        #member_name = Some(result);
-                    }
+                let &Attribute { ref member_name, ref attrib_name, ref ty, ref version, .. } = attrib;
+                match *version {
+                    Some(ref version) => quote! {
+                        #attrib_name => {
+                            if utils::check_version(reader, #element_name, #attrib_name, #version)? {
+                                let result: #ty = utils::parse_value(reader, &*attribute.value)?;
+                                #member_name = Some(result);
+                            }
+                        }
+                    },
+
+                    None => quote! {
+                        #attrib_name => {
+                            let result: #ty = utils::parse_value(reader, &*attribute.value)?;
+                            #member_name = Some(result);
+                        }
+                    },
                 }
             });
 
@@ -558,21 +846,37 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
                 }
             });
 
+        // Unknown attributes are either collected by the `#[any_attribute]` member or rejected.
+        let unknown_attrib = match any_attribute {
+            Some(AnyAttribute { ref member_name, .. }) => quote! {
+                _ => {
+                    reader.context_mut().report.record_fallback(concat!("unknown attribute on <", #element_name, ">"));
+                    utils::AnyAttributes::insert_attribute(&mut #member_name, attribute);
+                }
+            },
+
+            None => quote! {
+                attrib_name @ _ => {
+                    let error = Error {
+                        position: reader.position(),
+                        kind: ErrorKind::UnexpectedAttribute {
+                            element: #element_name,
+                            attribute: attrib_name.into(),
+                            expected: vec![ #( #attrib_names ),* ],
+                        },
+                    };
+                    reader.tolerate(error, ::Strictness::Lenient)?;
+                    reader.preserve_attribute(attribute);
+                }
+            },
+        };
+
         quote! {
             for attribute in element_start.attributes {
                 match &*attribute.name.local_name {
                     #( #matches )*
 
-                    attrib_name @ _ => {
-                        return Err(Error {
-                            position: reader.position(),
-                            kind: ErrorKind::UnexpectedAttribute {
-                                element: #element_name,
-                                attribute: attrib_name.into(),
-                                expected: vec![ #( #attrib_names ),* ],
-                            },
-                        })
-                    }
+                    #unknown_attrib
                 }
             }
 
@@ -589,9 +893,48 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
     let children_impl = {
         let decls = children.iter()
             .map(|child| {
-                let &Child { ref member_name, ref element_name, ref occurrences, ref data_type } = child;
+                let &Child { ref member_name, ref element_name, ref occurrences, ref data_type, ref flatten, ref version } = child;
+
+                // Children that only exist in some versions of the spec are checked against the
+                // document's version, as are the members of a group that are restricted to one.
+                let valid_in = match (version, data_type) {
+                    (&Some(ref version), _) => quote! {
+                        &|_| { Some(#version) }
+                    },
+
+                    (&None, &DataType::ColladaElement(ref ty)) if flatten.is_none() => quote! {
+                        &|test_name| { #ty::version_restriction(test_name) }
+                    },
+
+                    _ => quote! {
+                        &|_| { None }
+                    },
+                };
+
+                // Flattened children are parsed from inside of their wrapper element.
+                let parse_child = match *data_type {
+                    DataType::ColladaElement(ref ident) if flatten.is_some() => {
+                        let wrapper = flatten.as_ref().unwrap();
+                        quote! {
+                            utils::parse_flattened::<_, #ident>(reader, element_start, #wrapper)
+                        }
+                    }
+
+                    DataType::ColladaElement(ref ident) => quote! {
+                        #ident::parse_element(reader, element_start)
+                    },
+
+                    DataType::TextData(_) => Tokens::new(),
+                };
 
                 let name = match *data_type {
+                    _ if flatten.is_some() => {
+                        let wrapper = flatten.as_ref().unwrap();
+                        quote! {
+                            &mut |test_name| { test_name == #wrapper }
+                        }
+                    }
+
                     DataType::TextData(_) => {
                         quote! {
                             &mut |test_name| { test_name == #element_name }
@@ -606,6 +949,13 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
                 };
 
                 let add_names = match *data_type {
+                    _ if flatten.is_some() => {
+                        let wrapper = flatten.as_ref().unwrap();
+                        quote! {
+                            &|names| { names.push(#wrapper); }
+                        }
+                    }
+
                     DataType::TextData(_) => {
                         quote! {
                             &|names| { names.push(#element_name); }
@@ -646,7 +996,7 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
                         quote! {
                             utils::verify_attributes(reader, #element_name, element_start.attributes)?;
                             if let Some(result) = utils::optional_text_contents(reader, #element_name)? {
-                                #member_name.push(result.parse()?);
+                                #member_name.push(result);
                             }
                         }
                     }
@@ -654,43 +1004,54 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
                     (&ChildOccurrences::RequiredMany, &DataType::TextData(_)) => {
                         quote! {
                             utils::verify_attributes(reader, #element_name, element_start.attributes)?;
-                            if let Some(result) = utils::optional_text_contents(reader, #element_name)? {
-                                #member_name.push(result.parse()?);
-                            }
+                            let result = utils::required_text_contents(reader, #element_name)?;
+                            #member_name.push(result);
                         }
                     }
 
-                    (&ChildOccurrences::Optional, &DataType::ColladaElement(ref ident)) => {
+                    (&ChildOccurrences::MaybeEmpty, &DataType::TextData(_)) => {
                         quote! {
-                            let result = #ident::parse_element(reader, element_start)?;
+                            utils::verify_attributes(reader, #element_name, element_start.attributes)?;
+                            #member_name = match utils::optional_text_contents(reader, #element_name)? {
+                                Some(result) => ::common::MaybeEmpty::Value(result),
+                                None => ::common::MaybeEmpty::Empty,
+                            };
+                        }
+                    }
+
+                    (&ChildOccurrences::MaybeEmpty, &DataType::ColladaElement(_)) => { unreachable!(); }
+
+                    (&ChildOccurrences::Optional, &DataType::ColladaElement(_)) => {
+                        quote! {
+                            let result = #parse_child?;
                             #member_name = Some(result);
                         }
                     }
 
-                    (&ChildOccurrences::OptionalWithDefault(_), &DataType::ColladaElement(ref ident)) => {
+                    (&ChildOccurrences::OptionalWithDefault(_), &DataType::ColladaElement(_)) => {
                         quote! {
-                            let result = #ident::parse_element(reader, element_start)?;
+                            let result = #parse_child?;
                             #member_name = Some(result);
                         }
                     }
 
-                    (&ChildOccurrences::Required, &DataType::ColladaElement(ref ident)) => {
+                    (&ChildOccurrences::Required, &DataType::ColladaElement(_)) => {
                         quote! {
-                            let result = #ident::parse_element(reader, element_start)?;
+                            let result = #parse_child?;
                             #member_name = Some(result);
                         }
                     }
 
-                    (&ChildOccurrences::OptionalMany, &DataType::ColladaElement(ref ident)) => {
+                    (&ChildOccurrences::OptionalMany, &DataType::ColladaElement(_)) => {
                         quote! {
-                            let result = #ident::parse_element(reader, element_start)?;
+                            let result = #parse_child?;
                             #member_name.push(result);
                         }
                     }
 
-                    (&ChildOccurrences::RequiredMany, &DataType::ColladaElement(ref ident)) => {
+                    (&ChildOccurrences::RequiredMany, &DataType::ColladaElement(_)) => {
                         quote! {
-                            let result = #ident::parse_element(reader, element_start)?;
+                            let result = #parse_child?;
                             #member_name.push(result);
                         }
                     }
@@ -707,6 +1068,7 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
                         },
 
                         add_names: #add_names,
+                        valid_in: #valid_in,
                     }
                 }
             });
@@ -717,15 +1079,55 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
                     ref member_name,
                     ref occurrences,
                     ref member_type,
+                    ref split_with,
+                    ref count_member,
                 } = *text_contents;
 
+                let declared_count = match *count_member {
+                    Some(ref count_member) => quote! { Some(#count_member) },
+                    None => quote! { None },
+                };
+
+                // The splitting function takes the text as a `&str` and returns anything that can
+                // be iterated over as `&str`s.
+                let split = match *split_with {
+                    Some(ref split_with) => quote! {
+                        ::std::iter::IntoIterator::into_iter(#split_with(&*text))
+                    },
+                    None => quote! { text.split_whitespace() },
+                };
+
                 match *occurrences {
+                    // Raw arrays hold on to the text and decode it later, so they need the
+                    // position of the text for reporting errors.
+                    ChildOccurrences::Required if is_raw_array_type(member_type) => {
+                        quote! {
+                            Some(&mut |reader, text| {
+                                #member_name = Some(::utils::raw_array(reader, #element_name, text, #declared_count)?);
+                                Ok(())
+                            })
+                        }
+                    }
+
                     ChildOccurrences::Optional |
                     ChildOccurrences::OptionalWithDefault(_) |
                     ChildOccurrences::Required => {
                         quote! {
-                            Some(&mut |_, text| {
-                                #member_name = Some(text.parse()?);
+                            Some(&mut |reader, text| {
+                                #member_name = Some(::utils::parse_value(reader, &*text)?);
+                                Ok(())
+                            })
+                        }
+                    }
+
+                    // Lists of floats can be huge, so they get a dedicated parser unless the
+                    // member has asked for custom splitting.
+                    ChildOccurrences::OptionalMany | ChildOccurrences::RequiredMany
+                    if split_with.is_none() && is_float_type(member_type) => {
+                        quote! {
+                            Some(&mut |reader, text| {
+                                #member_name = ::utils::float_list::<_, #member_type>(reader, &*text)?;
+                                ::utils::check_list_len::<_, #member_type>(reader, #element_name, #member_name.len(), #declared_count)?;
                                 Ok(())
                             })
                         }
@@ -734,7 +1136,7 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
                     ChildOccurrences::OptionalMany | ChildOccurrences::RequiredMany => {
                         quote! {
                             Some(&mut |reader, text| {
-                                #member_name = text.split_whitespace()
+                                #member_name = #split
                                     .map(|word| word.parse::<#member_type>())
                                     .collect::<::std::result::Result<Vec<_>, _>>()
                                     .map_err(|err| {
@@ -743,21 +1145,64 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
                                             kind: err.into(),
                                         }
                                     })?;
+                                ::utils::check_list_len::<_, #member_type>(reader, #element_name, #member_name.len(), #declared_count)?;
                                 Ok(())
                             })
                         }
                     }
+
+                    ChildOccurrences::MaybeEmpty => { unreachable!(); }
                 }
             })
             .unwrap_or(quote! { None });
 
+        // `parse_children` doesn't report a required-many child that never appears at all (e.g. an
+        // `<extra>` with no `<technique>`), so the required children are checked for once all of
+        // them have been parsed. The position is only looked up when the error is reported.
         let required_childs = children.iter()
             .filter_map(|child| {
-                let &Child { ref member_name, ref occurrences, .. } = child;
+                let &Child { ref member_name, element_name: ref child_name, ref occurrences, ref data_type, ref flatten, .. } = child;
+
+                let missing_child = {
+                    let add_names = match *data_type {
+                        _ if flatten.is_some() => {
+                            let wrapper = flatten.as_ref().unwrap();
+                            quote! { expected.push(#wrapper); }
+                        }
+                        DataType::TextData(_) => quote! { expected.push(#child_name); },
+                        DataType::ColladaElement(ref ty) => quote! { #ty::add_names(&mut expected); },
+                    };
+
+                    quote! {
+                        {
+                            let mut expected = Vec::new();
+                            #add_names
+                            Error {
+                                position: reader.position(),
+                                kind: ErrorKind::MissingRequiredChild {
+                                    parent: #element_name,
+                                    expected: expected,
+                                },
+                            }
+                        }
+                    }
+                };
+
                 match *occurrences {
                     ChildOccurrences::Required => {
                         Some(quote! {
-                            let #member_name = #member_name.expect("Required child was `None`");
+                            let #member_name = match #member_name {
+                                Some(value) => value,
+                                None => return Err(#missing_child),
+                            };
+                        })
+                    }
+
+                    ChildOccurrences::RequiredMany => {
+                        Some(quote! {
+                            if #member_name.is_empty() {
+                                return Err(#missing_child);
+                            }
                         })
                     }
 
@@ -792,18 +1237,26 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
             })
             .unwrap_or(Tokens::new());
 
-        quote! {
-            ElementConfiguration {
-                name: #element_name,
-                children: &mut [
-                    #( #decls ),*
-                ],
-                text_contents: #text_contents_impl,
-            }.parse_children(reader)?;
-
-            #( #required_childs )*
-
-            #unwrap_text_contents
+        if children.is_empty() && text_contents.is_none() {
+            // Leaf elements only need to check for their end tag, so skip the full
+            // `ElementConfiguration` machinery for them.
+            quote! {
+                parse_leaf_element(reader, #element_name)?;
+            }
+        } else {
+            quote! {
+                ElementConfiguration {
+                    name: #element_name,
+                    children: &mut [
+                        #( #decls ),*
+                    ],
+                    text_contents: #text_contents_impl,
+                }.parse_children(reader)?;
+
+                #( #required_childs )*
+
+                #unwrap_text_contents
+            }
         }
     };
 
@@ -815,6 +1268,12 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
                 let ident = &attrib.member_name;
                 quote! { #ident }
             });
+        let any_attrib = any_attribute.as_ref()
+            .map(|any_attribute| {
+                let ident = &any_attribute.member_name;
+                quote! { #ident, }
+            })
+            .unwrap_or(Tokens::new());
         let childs = children.iter()
             .map(|child| {
                 let ident = &child.member_name;
@@ -830,6 +1289,7 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
         quote! {
             Ok(#ident {
                 #( #attribs, )*
+                #any_attrib
                 #( #childs, )*
                 #text
             })
@@ -839,11 +1299,11 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
     let body = if stub_me_out {
         quote! {
             #[allow(unused_imports)]
-            fn parse_element<R: ::std::io::Read>(
-                reader: &mut ::xml::reader::EventReader<R>,
-                _: ::utils::ElementStart,
+            fn parse_element<R: ::backend::XmlPull>(
+                reader: &mut ::utils::DocumentReader<R>,
+                element_start: ::utils::ElementStart,
             ) -> Result<Self> {
-                ::utils::stub_out(reader, #element_name)?;
+                ::utils::stub_out(reader, element_start, #element_name)?;
 
                 Ok(Self {})
             }
@@ -851,8 +1311,8 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
     } else {
         quote! {
             #[allow(unused_imports)]
-            fn parse_element<R: ::std::io::Read>(
-                reader: &mut ::xml::reader::EventReader<R>,
+            fn parse_element<R: ::backend::XmlPull>(
+                reader: &mut ::utils::DocumentReader<R>,
                 element_start: ::utils::ElementStart,
             ) -> Result<Self> {
                 use std::str::FromStr;
@@ -872,6 +1332,198 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
         }
     };
 
+    // Generate code for writing the element.
+    // --------------------------------------
+    let write_impl = if let Some(write_with) = write_with {
+        quote! {
+            fn write_element<W: ::std::io::Write>(&self, writer: &mut ::writer::XmlWriter<W>) -> ::writer::Result<()> {
+                #write_with(self, writer)
+            }
+        }
+    } else if stub_me_out {
+        // Stubbed elements don't keep their contents, so there's nothing to write inside of them.
+        quote! {
+            fn write_element<W: ::std::io::Write>(&self, writer: &mut ::writer::XmlWriter<W>) -> ::writer::Result<()> {
+                writer.empty_element(#element_name)
+            }
+        }
+    } else {
+        let write_attribs = attributes.iter()
+            .map(|attrib| {
+                let &Attribute { ref member_name, ref attrib_name, ref occurrences, ref version, .. } = attrib;
+                let check_version = match *version {
+                    Some(ref version) => quote! { writer.check_version(#attrib_name, #version)?; },
+                    None => Tokens::new(),
+                };
+
+                match *occurrences {
+                    AttributeOccurrences::Optional => quote! {
+                        if let Some(ref value) = self.#member_name {
+                            #check_version
+                            writer.attribute(#attrib_name, value)?;
+                        }
+                    },
+
+                    AttributeOccurrences::OptionalWithDefault(_) | AttributeOccurrences::Required => quote! {
+                        #check_version
+                        writer.attribute(#attrib_name, &self.#member_name)?;
+                    },
+                }
+            });
+
+        let write_any_attrib = any_attribute.as_ref()
+            .map(|any_attribute| {
+                let member_name = &any_attribute.member_name;
+                quote! { ::utils::AnyAttributes::write_attributes(&self.#member_name, writer)?; }
+            })
+            .unwrap_or(Tokens::new());
+
+        let write_childs = children.iter()
+            .map(|child| {
+                let &Child { ref member_name, ref element_name, ref occurrences, ref data_type, ref flatten, ref version } = child;
+
+                // Children restricted to a version are only checked when they're present.
+                let check_version = match (version, data_type) {
+                    (&Some(ref version), &DataType::TextData(_)) => quote! {
+                        writer.check_version(#element_name, #version)?;
+                    },
+
+                    (&Some(ref version), &DataType::ColladaElement(ref ty)) => quote! {
+                        writer.check_version(::utils::element_name::<#ty>(), #version)?;
+                    },
+
+                    (&None, _) => Tokens::new(),
+                };
+
+                let write_child = match *data_type {
+                    DataType::TextData(_) => quote! {
+                        #check_version
+                        writer.text_element(#element_name, value)?;
+                    },
+
+                    DataType::ColladaElement(_) => match *flatten {
+                        Some(ref wrapper) => quote! {
+                            #check_version
+                            writer.start_element(#wrapper)?;
+                            ::utils::ColladaElement::write_element(value, writer)?;
+                            writer.end_element(#wrapper)?;
+                        },
+
+                        None => quote! {
+                            #check_version
+                            ::utils::ColladaElement::write_element(value, writer)?;
+                        },
+                    },
+                };
+
+                match *occurrences {
+                    ChildOccurrences::Optional => quote! {
+                        if let Some(ref value) = self.#member_name {
+                            #write_child
+                        }
+                    },
+
+                    ChildOccurrences::OptionalWithDefault(_) | ChildOccurrences::Required => quote! {
+                        {
+                            let value = &self.#member_name;
+                            #write_child
+                        }
+                    },
+
+                    ChildOccurrences::OptionalMany | ChildOccurrences::RequiredMany => quote! {
+                        for value in &self.#member_name {
+                            #write_child
+                        }
+                    },
+
+                    ChildOccurrences::MaybeEmpty => quote! {
+                        match self.#member_name {
+                            ::common::MaybeEmpty::Absent => {}
+                            ::common::MaybeEmpty::Empty => {
+                                #check_version
+                                writer.empty_element(#element_name)?;
+                            }
+                            ::common::MaybeEmpty::Value(ref value) => { #write_child }
+                        }
+                    },
+                }
+            });
+
+        let write_text = text_contents.as_ref()
+            .map(|text_contents| {
+                let TextContents { ref member_name, ref occurrences, .. } = *text_contents;
+                match *occurrences {
+                    ChildOccurrences::Optional => quote! {
+                        if let Some(ref value) = self.#member_name {
+                            writer.text(value)?;
+                        }
+                    },
+
+                    _ => quote! {
+                        writer.text(&self.#member_name)?;
+                    },
+                }
+            })
+            .unwrap_or(Tokens::new());
+
+        quote! {
+            fn write_element<W: ::std::io::Write>(&self, writer: &mut ::writer::XmlWriter<W>) -> ::writer::Result<()> {
+                writer.start_element(#element_name)?;
+                #( #write_attribs )*
+                #write_any_attrib
+                #( #write_childs )*
+                #write_text
+                writer.end_element(#element_name)
+            }
+        }
+    };
+
+    // Generate code for reporting schema coverage.
+    // --------------------------------------------
+    let coverage_impl = {
+        let support = if stub_me_out { quote! { Stubbed } } else { quote! { Implemented } };
+
+        let childs = children.iter()
+            .map(|child| {
+                let wrapper = child.flatten.as_ref()
+                    .map(|wrapper| quote! { coverage.insert_name(#wrapper, ::coverage::Support::Implemented); })
+                    .unwrap_or(Tokens::new());
+
+                let add_coverage = match child.data_type {
+                    DataType::TextData(_) => {
+                        let element_name = &child.element_name;
+                        quote! { coverage.insert_name(#element_name, ::coverage::Support::Implemented); }
+                    }
+
+                    DataType::ColladaElement(ref ty) => {
+                        quote! {
+                            #wrapper
+                            #ty::add_coverage(coverage);
+                        }
+                    }
+                };
+
+                // Children from other versions of the spec aren't part of this version's schema.
+                match child.version {
+                    Some(ref version) => quote! {
+                        if coverage.allows(#version) {
+                            #add_coverage
+                        }
+                    },
+
+                    None => add_coverage,
+                }
+            });
+
+        quote! {
+            fn add_coverage(coverage: &mut ::coverage::ElementCoverage) {
+                if coverage.insert::<Self>(#element_name, ::coverage::Support::#support) {
+                    #( #childs )*
+                }
+            }
+        }
+    };
+
     // Put all the pieces together.
     // ----------------------------
     Ok(quote! {
@@ -885,6 +1537,31 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
             fn add_names(names: &mut Vec<&'static str>) {
                 names.push(#element_name);
             }
+
+            #write_impl
+
+            #coverage_impl
         }
     })
 }
+
+/// Returns `true` if `ty` is `f32` or `f64`.
+fn is_float_type(ty: &Ty) -> bool {
+    match *ty {
+        Ty::Path(None, ref path) if path.segments.len() == 1 => {
+            let ident = &path.segments[0].ident;
+            ident == "f32" || ident == "f64"
+        }
+        _ => false,
+    }
+}
+
+/// Returns `true` if `ty` is `RawArray<T>`.
+fn is_raw_array_type(ty: &Ty) -> bool {
+    match *ty {
+        Ty::Path(None, ref path) => {
+            path.segments.last().map_or(false, |segment| segment.ident == "RawArray")
+        }
+        _ => false,
+    }
+}
@@ -1,5 +1,6 @@
 extern crate collaborate;
 
+use ::collaborate::common::Semantic;
 use ::collaborate::v1_4::*;
 
 static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
@@ -36,8 +37,8 @@ fn main() {
                 // Retrieve the raw data for each attribute that matches the attribute's offset.
                 for input in polylist.inputs_for_offset(attribute.offset) {
                     // Handle the input based on its semantic.
-                    match input.semantic.as_ref() {
-                        "VERTEX" => {
+                    match input.semantic {
+                        Semantic::Vertex => {
                             // The "VERTEX" semantic means that this input indexes into all of
                             // sources specified in the `vertices` member of the host mesh.
 
@@ -53,7 +54,7 @@ fn main() {
                             // Find the input that corresponds to the "POSITION" semantic. The
                             // COLLADA spec requires that there be one in a `<vertices>` element.
                             let input = mesh.vertices.inputs.iter()
-                                .find(|input| input.semantic == "POSITION")
+                                .find(|input| input.semantic == Semantic::Position)
                                 .expect("Vertices had no input with the \"POSITION\" semantic");
 
                             // Find the mesh source identified by the input's `source` within the
@@ -96,7 +97,7 @@ fn main() {
                             ])
                         }
 
-                        "NORMAL" => {
+                        Semantic::Normal => {
                             // Find the mesh source identified by the input's `source` within the
                             // parent `Mesh` object.
                             let source = mesh.find_source(input.source.id())
@@ -138,7 +139,7 @@ fn main() {
                         }
 
                         // Ignore any unknown semantics.
-                        semantic @ _ => { println!("Ignoring unknown semantic {:?}", semantic); }
+                        ref semantic => { println!("Ignoring unknown semantic {}", semantic); }
                     }
                 }
             }
@@ -0,0 +1,155 @@
+//! Parsing documents from asynchronous sources.
+//!
+//! Loading a document from a socket or an async file handle with [`Collada::read`] would block
+//! the executor thread while waiting on I/O. [`Collada::read_async`] instead reads the document
+//! from any [`AsyncRead`] source without blocking, and then parses it once it has been fully
+//! received. [`read_async_with`] does the same for documents of any supported version, parsing
+//! them with the given [`ParseOptions`].
+//!
+//! Only the I/O is asynchronous: parsing happens all at once when the last of the document has
+//! been read, in the task that polls the future. For very large documents it may still be worth
+//! moving parsing onto a blocking thread pool.
+//!
+//! Only available with the `futures-io` feature enabled. Tokio users can use the `compat`
+//! adapters from `tokio-util` to get a `futures-io` compatible reader.
+//!
+//! [`Collada::read`]: ../v1_4/struct.Collada.html#method.read
+//! [`Collada::read_async`]: ../v1_4/struct.Collada.html#method.read_async
+//! [`read_async_with`]: fn.read_async_with.html
+//! [`ParseOptions`]: ../struct.ParseOptions.html
+//! [`AsyncRead`]: https://docs.rs/futures-io/0.3/futures_io/trait.AsyncRead.html
+
+use {ParseOptions, ParseReport, Result, VersionedDocument, XmlError};
+use futures_io::AsyncRead;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use v1_4::Collada;
+
+/// The number of bytes read from the source each time it's polled.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+impl Collada {
+    /// Reads and parses a COLLADA document from an asynchronous source.
+    ///
+    /// See the [module documentation](../aio/index.html) for more information.
+    ///
+    /// # Errors
+    ///
+    /// The returned future resolves to `Err` if reading from `reader` fails, or if the document is
+    /// invalid or malformed in some way, the same as [`read`](#method.read).
+    pub fn read_async<R: AsyncRead + Unpin>(reader: R) -> ReadAsync<R> {
+        ReadAsync {
+            source: ReadToEnd::new(reader),
+        }
+    }
+}
+
+/// Reads and parses a COLLADA document of any supported version from an asynchronous source,
+/// using the given options.
+///
+/// The returned future resolves to the document and the report for the parse, the same as
+/// [`VersionedDocument::read_with`].
+///
+/// # Errors
+///
+/// The returned future resolves to `Err` if reading from `reader` fails, or if the document is
+/// invalid or malformed in a way that `options` doesn't allow, the same as
+/// [`VersionedDocument::read_with`].
+///
+/// [`VersionedDocument::read_with`]: ../enum.VersionedDocument.html#method.read_with
+pub fn read_async_with<R: AsyncRead + Unpin>(reader: R, options: ParseOptions) -> ReadAsyncWith<R> {
+    ReadAsyncWith {
+        source: ReadToEnd::new(reader),
+        options: Some(options),
+    }
+}
+
+/// A future that reads and parses a COLLADA document.
+///
+/// Created with [`Collada::read_async`].
+///
+/// [`Collada::read_async`]: ../v1_4/struct.Collada.html#method.read_async
+#[derive(Debug)]
+pub struct ReadAsync<R> {
+    source: ReadToEnd<R>,
+}
+
+impl<R: AsyncRead + Unpin> Future for ReadAsync<R> {
+    type Output = Result<Collada>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<Result<Collada>> {
+        let this = self.get_mut();
+        match this.source.poll_read(context) {
+            Poll::Ready(Ok(source)) => Poll::Ready(Collada::read(&*source)),
+            Poll::Ready(Err(error)) => Poll::Ready(Err(error)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// A future that reads and parses a COLLADA document with the given options.
+///
+/// Created with [`read_async_with`](fn.read_async_with.html).
+#[derive(Debug)]
+pub struct ReadAsyncWith<R> {
+    source: ReadToEnd<R>,
+    options: Option<ParseOptions>,
+}
+
+impl<R: AsyncRead + Unpin> Future for ReadAsyncWith<R> {
+    type Output = Result<(VersionedDocument, ParseReport)>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<Result<(VersionedDocument, ParseReport)>> {
+        let this = self.get_mut();
+        match this.source.poll_read(context) {
+            Poll::Ready(Ok(source)) => {
+                let options = this.options.take().expect("`ReadAsyncWith` polled after completion");
+                Poll::Ready(VersionedDocument::read_with(&*source, options))
+            }
+            Poll::Ready(Err(error)) => Poll::Ready(Err(error)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Reads the whole of an asynchronous source into memory.
+#[derive(Debug)]
+struct ReadToEnd<R> {
+    reader: R,
+    source: Vec<u8>,
+}
+
+impl<R: AsyncRead + Unpin> ReadToEnd<R> {
+    fn new(reader: R) -> ReadToEnd<R> {
+        ReadToEnd {
+            reader,
+            source: Vec::new(),
+        }
+    }
+
+    /// Reads from the source until it's exhausted, returning everything that was read.
+    fn poll_read(&mut self, context: &mut Context) -> Poll<Result<Vec<u8>>> {
+        loop {
+            let len = self.source.len();
+            self.source.resize(len + CHUNK_SIZE, 0);
+            let result = Pin::new(&mut self.reader).poll_read(context, &mut self.source[len..]);
+            let read = match result {
+                Poll::Ready(Ok(read)) => read,
+                Poll::Ready(Err(error)) => {
+                    self.source.truncate(len);
+                    return Poll::Ready(Err(XmlError::from(error).into()));
+                }
+                Poll::Pending => {
+                    self.source.truncate(len);
+                    return Poll::Pending;
+                }
+            };
+
+            self.source.truncate(len + read);
+            if read == 0 {
+                return Poll::Ready(Ok(::std::mem::take(&mut self.source)));
+            }
+        }
+    }
+}
@@ -0,0 +1,535 @@
+//! The XML parsers used to read documents.
+//!
+//! Parsing code only depends on the small [`XmlPull`] interface, which pulls one xml-rs
+//! `XmlEvent` at a time. xml-rs is used by default, and a much faster quick-xml based reader is
+//! used instead when the `quick-xml` feature is enabled. Either way, the events (and positions)
+//! produced match what xml-rs produces with `utils::PARSER_CONFIG`.
+
+use {ParseOptions, Result};
+use dtd::{self, DtdFilter};
+use encoding::{self, Transcode};
+use std::fmt::{self, Debug, Formatter};
+use std::io::Read;
+use std::mem;
+use xml::common::Position;
+use xml::reader::{self, EventReader, XmlEvent};
+
+/// A source of XML events.
+///
+/// This is implemented for xml-rs's `EventReader`, so that helpers like [`skip_element`] can be
+/// used with documents read directly with xml-rs.
+///
+/// [`skip_element`]: ./fn.skip_element.html
+pub trait XmlPull: Position {
+    /// Pulls the next event from the document.
+    fn next(&mut self) -> reader::Result<XmlEvent>;
+}
+
+impl<R: Read> XmlPull for EventReader<R> {
+    fn next(&mut self) -> reader::Result<XmlEvent> {
+        EventReader::next(self)
+    }
+}
+
+/// The backend used by default for parsing documents.
+#[cfg(not(feature = "quick-xml"))]
+pub type DefaultBackend<R> = EventReader<R>;
+
+/// The backend used by default for parsing documents.
+#[cfg(feature = "quick-xml")]
+pub type DefaultBackend<R> = self::quick::QuickXmlReader<'static, R>;
+
+/// Creates the default backend for reading from `source`.
+#[cfg(not(feature = "quick-xml"))]
+pub fn open<R: Read>(source: R) -> Result<DefaultBackend<R>> {
+    Ok(EventReader::new_with_config(source, ::utils::PARSER_CONFIG.clone()))
+}
+
+/// Creates the default backend for reading from `source`.
+#[cfg(feature = "quick-xml")]
+pub fn open<R: Read>(source: R) -> Result<DefaultBackend<R>> {
+    self::quick::QuickXmlReader::new(source)
+}
+
+/// The source that documents are read from, after being decoded and having their DTD handled.
+pub type Source<R> = DtdFilter<Transcode<R>>;
+
+/// Creates the default backend for reading a document from `source`, decoding it to UTF-8 first
+/// if it uses some other encoding.
+pub fn open_encoded<R: Read>(source: R, options: &ParseOptions) -> Result<DefaultBackend<Source<R>>> {
+    open_transcoded(Transcode::new(source), options)
+}
+
+/// Creates the default backend for reading a document from `source`, handling its DTD and
+/// entities according to `options`.
+pub fn open_transcoded<R: Read>(source: Transcode<R>, options: &ParseOptions) -> Result<DefaultBackend<Source<R>>> {
+    open(DtdFilter::new(source, options)?)
+}
+
+/// The backend used for parsing documents that are already in memory.
+#[cfg(not(feature = "quick-xml"))]
+pub type SliceBackend<'a> = EventReader<&'a [u8]>;
+
+/// The backend used for parsing documents that are already in memory.
+#[cfg(feature = "quick-xml")]
+pub type SliceBackend<'a> = self::quick::QuickXmlReader<'a, ()>;
+
+/// Creates the backend for reading a document directly from memory.
+///
+/// `source` must already be UTF-8 and have had its DTD handled, e.g. by [`parse_utf8`].
+///
+/// [`parse_utf8`]: ./fn.parse_utf8.html
+#[cfg(not(feature = "quick-xml"))]
+pub fn open_slice(source: &[u8]) -> SliceBackend<'_> {
+    EventReader::new_with_config(source, ::utils::PARSER_CONFIG.clone())
+}
+
+/// Creates the backend for reading a document directly from memory.
+///
+/// `source` must already be UTF-8 and have had its DTD handled, e.g. by [`parse_utf8`].
+///
+/// [`parse_utf8`]: ./fn.parse_utf8.html
+#[cfg(feature = "quick-xml")]
+pub fn open_slice(source: &[u8]) -> SliceBackend<'_> {
+    self::quick::QuickXmlReader::from_slice(source)
+}
+
+/// Parses a document in memory with `parse`, decoding it to UTF-8 first if it uses some other
+/// encoding.
+///
+/// Unlike [`open_encoded`], the document isn't read through `io::Read`, and is only copied if it
+/// needs to be decoded or filtered.
+///
+/// [`open_encoded`]: ./fn.open_encoded.html
+pub fn parse_bytes<T, F>(source: &[u8], options: ParseOptions, parse: F) -> Result<T>
+    where F: FnOnce(SliceBackend, ParseOptions) -> Result<T>
+{
+    parse_utf8(&encoding::decode_slice(source), options, parse)
+}
+
+/// Parses a document in memory that's already UTF-8, e.g. from a `&str`, with `parse`.
+pub fn parse_utf8<T, F>(source: &[u8], options: ParseOptions, parse: F) -> Result<T>
+    where F: FnOnce(SliceBackend, ParseOptions) -> Result<T>
+{
+    let source = dtd::filter_slice(source, &options)?;
+    parse(open_slice(&source), options)
+}
+
+/// Scratch buffers used while reading a document, which can be reused for the next document.
+#[derive(Default)]
+pub struct Buffers {
+    /// The buffers used for decoding documents that aren't UTF-8.
+    transcode: (Vec<u8>, Vec<u8>),
+
+    /// The buffers used for handling DTDs and entities.
+    dtd: (Vec<u8>, Vec<u8>),
+
+    /// The buffers holding the whole document and each event for the `quick-xml` backend.
+    #[cfg(feature = "quick-xml")]
+    quick: (Vec<u8>, Vec<u8>),
+}
+
+impl Debug for Buffers {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        // The buffers can hold whole documents, so their contents are left out.
+        formatter.write_str("Buffers { .. }")
+    }
+}
+
+/// Creates the default backend for reading a document from `source` the same as
+/// [`open_encoded`], reusing `buffers` from a previous document.
+///
+/// Pass the backend to [`recycle`] once the document has been parsed to get the buffers back.
+///
+/// [`open_encoded`]: ./fn.open_encoded.html
+/// [`recycle`]: ./fn.recycle.html
+pub fn open_reusing<R: Read>(source: R, options: &ParseOptions, buffers: &mut Buffers) -> Result<DefaultBackend<Source<R>>> {
+    let source = Transcode::with_buffers(source, mem::take(&mut buffers.transcode));
+    let source = DtdFilter::with_buffers(source, options, mem::take(&mut buffers.dtd))?;
+    open_backend_reusing(source, buffers)
+}
+
+#[cfg(not(feature = "quick-xml"))]
+fn open_backend_reusing<R: Read>(source: R, _buffers: &mut Buffers) -> Result<DefaultBackend<R>> {
+    open(source)
+}
+
+#[cfg(feature = "quick-xml")]
+fn open_backend_reusing<R: Read>(source: R, buffers: &mut Buffers) -> Result<DefaultBackend<R>> {
+    self::quick::QuickXmlReader::with_buffers(source, mem::take(&mut buffers.quick))
+}
+
+/// Takes back the buffers from a backend created with [`open_reusing`].
+///
+/// [`open_reusing`]: ./fn.open_reusing.html
+pub fn recycle<R: Read>(backend: DefaultBackend<Source<R>>, buffers: &mut Buffers) {
+    let source = close_backend(backend, buffers);
+    let (source, dtd) = source.into_parts();
+    let (_, transcode) = source.into_parts();
+    buffers.dtd = dtd;
+    buffers.transcode = transcode;
+}
+
+#[cfg(not(feature = "quick-xml"))]
+fn close_backend<R: Read>(backend: DefaultBackend<R>, _buffers: &mut Buffers) -> R {
+    backend.into_inner()
+}
+
+#[cfg(feature = "quick-xml")]
+fn close_backend<R: Read>(backend: DefaultBackend<R>, buffers: &mut Buffers) -> R {
+    let (source, quick) = backend.into_parts();
+    buffers.quick = quick;
+    source
+}
+
+#[cfg(feature = "quick-xml")]
+mod quick {
+    use {Result, XmlError};
+    use quick_xml;
+    use quick_xml::events::{BytesStart, Event};
+    use std::borrow::Cow;
+    use std::cell::Cell;
+    use std::collections::VecDeque;
+    use std::io::{Cursor, Read};
+    use xml::attribute::OwnedAttribute;
+    use xml::common::{Position, TextPosition, XmlVersion};
+    use xml::name::OwnedName;
+    use xml::namespace::{Namespace, NamespaceStack};
+    use xml::reader::{self, XmlEvent};
+    use super::XmlPull;
+
+    /// Reads events with quick-xml, converting them to the equivalent xml-rs events.
+    ///
+    /// The whole document is read into memory up front (or borrowed, if it's already in memory),
+    /// so that line and column positions can be recovered from byte offsets the same way xml-rs
+    /// reports them.
+    pub struct QuickXmlReader<'a, R> {
+        reader: quick_xml::Reader<Cursor<Cow<'a, [u8]>>>,
+        buffer: Vec<u8>,
+
+        namespaces: NamespaceStack,
+        started: bool,
+
+        /// The number of elements that are currently open, and whether the root element has been
+        /// seen, so that the end of the document is only reported once the root element is closed.
+        depth: usize,
+        found_root: bool,
+
+        /// Events that have been read but not yet returned, and their offsets in the source.
+        pending: VecDeque<(XmlEvent, usize)>,
+
+        /// The start of the last event returned, used for reporting positions.
+        event_offset: usize,
+
+        /// The last position computed and the offset it's for, so that computing the position of
+        /// each event only needs to scan the bytes since the previous one.
+        position: Cell<(usize, TextPosition)>,
+
+        /// The source the document was read from, kept so that its buffers can be reused.
+        source: R,
+    }
+
+    impl<R: Read> QuickXmlReader<'static, R> {
+        pub fn new(source: R) -> Result<QuickXmlReader<'static, R>> {
+            QuickXmlReader::with_buffers(source, (Vec::new(), Vec::new()))
+        }
+
+        /// Creates a reader that reuses the document and event buffers from a previous one.
+        pub fn with_buffers(mut source: R, buffers: (Vec<u8>, Vec<u8>)) -> Result<QuickXmlReader<'static, R>> {
+            let (mut bytes, mut buffer) = buffers;
+            bytes.clear();
+            buffer.clear();
+
+            source.read_to_end(&mut bytes).map_err(XmlError::from)?;
+            Ok(QuickXmlReader::with_document(Cow::Owned(bytes), buffer, source))
+        }
+    }
+
+    impl<'a> QuickXmlReader<'a, ()> {
+        /// Creates a reader for a document that's already in memory, without copying it.
+        pub fn from_slice(source: &'a [u8]) -> QuickXmlReader<'a, ()> {
+            QuickXmlReader::with_document(Cow::Borrowed(source), Vec::new(), ())
+        }
+    }
+
+    impl<'a, R> QuickXmlReader<'a, R> {
+        fn with_document(document: Cow<'a, [u8]>, buffer: Vec<u8>, source: R) -> QuickXmlReader<'a, R> {
+            let mut reader = quick_xml::Reader::from_reader(Cursor::new(document));
+            reader.trim_text(false);
+            reader.expand_empty_elements(false);
+
+            QuickXmlReader {
+                reader,
+                buffer,
+                namespaces: NamespaceStack::default(),
+                started: false,
+                depth: 0,
+                found_root: false,
+                pending: VecDeque::new(),
+                event_offset: 0,
+                position: Cell::new((0, TextPosition::new())),
+                source,
+            }
+        }
+
+        /// Returns the source along with the document and event buffers, so that the buffers can
+        /// be reused with `with_buffers`.
+        pub fn into_parts(self) -> (R, (Vec<u8>, Vec<u8>)) {
+            let document = match self.reader.into_inner().into_inner() {
+                Cow::Owned(document) => document,
+                Cow::Borrowed(_) => Vec::new(),
+            };
+            (self.source, (document, self.buffer))
+        }
+
+        /// Reads the next event from quick-xml, converting it to an xml-rs event.
+        ///
+        /// Returns `None` for events that xml-rs is configured to skip.
+        fn read_raw(&mut self) -> reader::Result<(Option<XmlEvent>, usize)> {
+            let offset = self.reader.buffer_position();
+            self.event_offset = offset;
+            self.buffer.clear();
+
+            let event = match self.reader.read_event_into(&mut self.buffer) {
+                Ok(event) => event.into_owned(),
+                Err(error) => { return Err(XmlError::from((&*self, error.to_string()))); }
+            };
+
+            let event = match event {
+                Event::Decl(decl) => {
+                    let version = match decl.version() {
+                        Ok(ref version) if &**version == b"1.1" => XmlVersion::Version11,
+                        _ => XmlVersion::Version10,
+                    };
+                    let encoding = match decl.encoding() {
+                        Some(Ok(encoding)) => String::from_utf8_lossy(&encoding).into_owned(),
+                        _ => "UTF-8".into(),
+                    };
+                    let standalone = match decl.standalone() {
+                        Some(Ok(standalone)) => Some(&*standalone == b"yes"),
+                        _ => None,
+                    };
+                    Some(XmlEvent::StartDocument { version, encoding, standalone })
+                }
+
+                Event::Start(start) => {
+                    self.depth += 1;
+                    Some(self.start_element(&start)?)
+                }
+
+                Event::Empty(start) => {
+                    // xml-rs reports empty elements as a start and an end event, so queue up the
+                    // end event to be returned next.
+                    let event = self.start_element(&start)?;
+                    let name = match event {
+                        XmlEvent::StartElement { ref name, .. } => name.clone(),
+                        _ => unreachable!(),
+                    };
+                    self.namespaces.pop();
+                    self.pending.push_back((XmlEvent::EndElement { name }, offset));
+                    Some(event)
+                }
+
+                Event::End(end) => {
+                    let name = String::from_utf8_lossy(end.name().as_ref()).into_owned();
+                    let name = self.resolve(&name, true);
+                    self.namespaces.pop();
+                    self.depth = self.depth.saturating_sub(1);
+                    Some(XmlEvent::EndElement { name })
+                }
+
+                Event::Text(text) => {
+                    let text = text.unescape().map_err(|error| XmlError::from((&*self, error.to_string())))?;
+                    Some(XmlEvent::Characters(text.into_owned()))
+                }
+
+                Event::CData(data) => {
+                    Some(XmlEvent::Characters(String::from_utf8_lossy(&data.into_inner()).into_owned()))
+                }
+
+                Event::PI(instruction) => {
+                    let content = String::from_utf8_lossy(&instruction).into_owned();
+                    let mut parts = content.splitn(2, char::is_whitespace);
+                    let name = parts.next().unwrap_or("").into();
+                    let data = parts.next().map(|data| data.trim().to_owned()).filter(|data| !data.is_empty());
+                    Some(XmlEvent::ProcessingInstruction { name, data })
+                }
+
+                // quick-xml doesn't check that the document is complete, so report a truncated
+                // document with the same errors as xml-rs.
+                Event::Eof if !self.found_root => {
+                    return Err(XmlError::from((&*self, "Unexpected end of stream: no root element found")));
+                }
+                Event::Eof if self.depth > 0 => {
+                    return Err(XmlError::from((&*self, "Unexpected end of stream: still inside the root element")));
+                }
+                Event::Eof => Some(XmlEvent::EndDocument),
+
+                Event::Comment(_) | Event::DocType(_) => None,
+            };
+
+            Ok((event, offset))
+        }
+
+        fn start_element(&mut self, start: &BytesStart) -> reader::Result<XmlEvent> {
+            self.found_root = true;
+            let mut namespace = Namespace::empty();
+            let mut raw_attributes = Vec::new();
+            for attribute in start.attributes() {
+                let attribute = attribute.map_err(|error| XmlError::from((&*self, error.to_string())))?;
+                let key = String::from_utf8_lossy(attribute.key.as_ref()).into_owned();
+                let value = attribute.unescape_value()
+                    .map_err(|error| XmlError::from((&*self, error.to_string())))?
+                    .into_owned();
+
+                if key == "xmlns" {
+                    namespace.put("", value);
+                } else if let Some(prefix) = key.strip_prefix("xmlns:") {
+                    namespace.put(prefix, value);
+                } else {
+                    raw_attributes.push((key, value));
+                }
+            }
+
+            self.namespaces.push_empty();
+            for (prefix, uri) in &namespace {
+                self.namespaces.put(prefix, uri);
+            }
+
+            let name = String::from_utf8_lossy(start.name().as_ref()).into_owned();
+            let name = self.resolve(&name, true);
+            let attributes = raw_attributes.into_iter()
+                .map(|(key, value)| OwnedAttribute {
+                    name: self.resolve(&key, false),
+                    value,
+                })
+                .collect();
+
+            Ok(XmlEvent::StartElement {
+                name,
+                attributes,
+                namespace: self.namespaces.squash(),
+            })
+        }
+
+        /// Resolves the namespace of a qualified name. Unprefixed attributes don't belong to the
+        /// default namespace.
+        fn resolve(&self, name: &str, use_default: bool) -> OwnedName {
+            let (prefix, local_name) = match name.find(':') {
+                Some(colon) => (Some(&name[..colon]), &name[colon + 1..]),
+                None => (None, name),
+            };
+
+            let namespace = match prefix {
+                Some(prefix) => self.namespaces.get(prefix),
+                None if use_default => self.namespaces.get("").filter(|uri| !uri.is_empty()),
+                None => None,
+            };
+
+            OwnedName {
+                local_name: local_name.into(),
+                namespace: namespace.map(Into::into),
+                prefix: prefix.map(Into::into),
+            }
+        }
+
+        /// Reads the next event, coalescing adjacent text and trimming whitespace to match xml-rs.
+        fn next_event(&mut self) -> reader::Result<XmlEvent> {
+            let (event, offset) = self.next_significant()?;
+            if self.started {
+                self.event_offset = offset;
+                return Ok(event);
+            }
+
+            self.started = true;
+            if let XmlEvent::StartDocument { .. } = event {
+                self.event_offset = offset;
+                return Ok(event);
+            }
+
+            // Documents without an XML declaration still begin with `StartDocument`.
+            self.pending.push_front((event, offset));
+            self.event_offset = 0;
+            Ok(XmlEvent::StartDocument {
+                version: XmlVersion::Version10,
+                encoding: "UTF-8".into(),
+                standalone: None,
+            })
+        }
+
+        /// Returns the next event that isn't whitespace, merging adjacent text into a single
+        /// trimmed `Characters` event.
+        fn next_significant(&mut self) -> reader::Result<(XmlEvent, usize)> {
+            loop {
+                let (event, offset) = self.next_unskipped()?;
+                let mut text = match event {
+                    XmlEvent::Characters(text) => text,
+                    event => { return Ok((event, offset)); }
+                };
+
+                loop {
+                    let (event, next_offset) = self.next_unskipped()?;
+                    match event {
+                        XmlEvent::Characters(more) => { text.push_str(&more); }
+                        event => {
+                            self.pending.push_front((event, next_offset));
+                            break;
+                        }
+                    }
+                }
+
+                let trimmed = text.trim();
+                if !trimmed.is_empty() {
+                    return Ok((XmlEvent::Characters(trimmed.into()), offset));
+                }
+            }
+        }
+
+        /// Returns the next event that isn't skipped.
+        fn next_unskipped(&mut self) -> reader::Result<(XmlEvent, usize)> {
+            if let Some(pending) = self.pending.pop_front() {
+                return Ok(pending);
+            }
+
+            loop {
+                if let (Some(event), offset) = self.read_raw()? {
+                    return Ok((event, offset));
+                }
+            }
+        }
+    }
+
+    impl<'a, R> Position for QuickXmlReader<'a, R> {
+        fn position(&self) -> TextPosition {
+            // Positions are requested for events in document order, so the scan picks up from the
+            // last position computed. Reading ahead can move `event_offset` past an event that's
+            // returned later, so only the positions that are actually requested are cached.
+            let source = self.reader.get_ref().get_ref();
+            let end = self.event_offset.min(source.len());
+
+            let (mut start, mut position) = self.position.get();
+            if start > end {
+                start = 0;
+                position = TextPosition::new();
+            }
+
+            for &byte in &source[start..end] {
+                if byte == b'\n' {
+                    position.new_line();
+                } else if byte & 0b1100_0000 != 0b1000_0000 {
+                    // Columns count characters, so UTF-8 continuation bytes are skipped.
+                    position.advance(1);
+                }
+            }
+
+            self.position.set((end, position));
+            position
+        }
+    }
+
+    impl<'a, R> XmlPull for QuickXmlReader<'a, R> {
+        fn next(&mut self) -> reader::Result<XmlEvent> {
+            self.next_event()
+        }
+    }
+}
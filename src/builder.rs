@@ -0,0 +1,391 @@
+//! Constructing documents from scratch.
+//!
+//! [`DocumentBuilder`] assembles a complete [`v1_4::Collada`] document out of the parts an
+//! exporter typically has on hand: an asset, some geometry, and the visual scenes that place
+//! it. The builder takes care of the bookkeeping that makes the document valid, i.e. generating
+//! unique ids for the elements that need them, grouping elements into libraries, and pointing
+//! the document's `<scene>` at a visual scene. The result can be modified further like any parsed
+//! document, and written with [`Collada::write`].
+//!
+//! # Examples
+//!
+//! ```
+//! use std::fs::File;
+//! use collaborate::builder::DocumentBuilder;
+//! use collaborate::v1_4::Collada;
+//! use collaborate::writer::WriteOptions;
+//!
+//! # let file = File::open("resources/blender_cube.dae").unwrap();
+//! # let cube = Collada::read(file).unwrap();
+//! # let mesh = cube.geometry("Cube-mesh").unwrap().geometric_element.as_mesh().unwrap().clone();
+//! let document = DocumentBuilder::new()
+//!     .add_geometry(mesh)
+//!     .build();
+//!
+//! // The geometry is instantiated by a node in the main scene.
+//! let scene = document.main_scene().unwrap();
+//! let url = &scene.nodes[0].instance_geometries[0].url;
+//! assert_eq!(Some("geometry-0"), url.local_id());
+//! assert!(document.geometry("geometry-0").is_some());
+//!
+//! let mut output = Vec::new();
+//! document.write(&mut output, WriteOptions::default()).unwrap();
+//! ```
+//!
+//! The builder always produces a 1.4.1 document, which can be written as 1.5.0 by setting
+//! [`WriteOptions::version`]:
+//!
+//! ```
+//! use collaborate::Version;
+//! use collaborate::builder::DocumentBuilder;
+//! use collaborate::writer::WriteOptions;
+//!
+//! let document = DocumentBuilder::new().build();
+//! let options = WriteOptions {
+//!     version: Some(Version::V1_5_0),
+//!     ..WriteOptions::default()
+//! };
+//! let mut output = Vec::new();
+//! document.write(&mut output, options).unwrap();
+//! assert!(String::from_utf8(output).unwrap().contains(r#"version="1.5.0""#));
+//! ```
+//!
+//! [`DocumentBuilder`]: ./struct.DocumentBuilder.html
+//! [`v1_4::Collada`]: ../v1_4/struct.Collada.html
+//! [`Collada::write`]: ../v1_4/struct.Collada.html#method.write
+//! [`WriteOptions::version`]: ../writer/struct.WriteOptions.html#structfield.version
+
+use chrono::{FixedOffset, UTC};
+use common::{AnyUri, DateTime, MaybeEmpty, Technique};
+use std::collections::{HashMap, HashSet};
+use v1_4::*;
+use Version;
+use xml::reader::XmlEvent;
+
+/// Builds a [`Collada`](../v1_4/struct.Collada.html) document, see the
+/// [module documentation](./index.html) for details.
+///
+/// Ids are assigned when the document is built. Geometries, visual scenes, and nodes that don't
+/// have an id are given one that's unique within the document, e.g. `"geometry-0"`, avoiding
+/// every id used anywhere inside of the geometries and visual scenes (such as those of their
+/// sources, arrays, and extras). Elements that already have an id keep it, so give a geometry an
+/// id if it needs to be instantiated by the nodes of a visual scene passed to
+/// [`add_visual_scene`](#method.add_visual_scene).
+#[derive(Debug, Clone, Default)]
+pub struct DocumentBuilder {
+    asset: Option<Asset>,
+    geometries: Vec<Geometry>,
+    visual_scenes: Vec<VisualScene>,
+}
+
+impl DocumentBuilder {
+    /// Creates a builder for an empty document.
+    pub fn new() -> DocumentBuilder {
+        DocumentBuilder::default()
+    }
+
+    /// Sets the document's `<asset>`.
+    ///
+    /// If no asset is set, the document is given one that was created and modified at the time
+    /// it's built.
+    pub fn set_asset(&mut self, asset: Asset) -> &mut DocumentBuilder {
+        self.asset = Some(asset);
+        self
+    }
+
+    /// Adds a geometry to the document, e.g. a [`Mesh`](../v1_4/struct.Mesh.html).
+    pub fn add_geometry<G: Into<Geometry>>(&mut self, geometry: G) -> &mut DocumentBuilder {
+        self.geometries.push(geometry.into());
+        self
+    }
+
+    /// Adds a visual scene to the document.
+    ///
+    /// The first visual scene added is the one instantiated by the document's `<scene>`. If no
+    /// visual scene is added, the document is given one containing a node for each geometry.
+    pub fn add_visual_scene(&mut self, visual_scene: VisualScene) -> &mut DocumentBuilder {
+        self.visual_scenes.push(visual_scene);
+        self
+    }
+
+    /// Builds the document.
+    ///
+    /// The builder can be used again afterwards, e.g. to build several documents that share
+    /// their geometry.
+    ///
+    /// The document is always a 1.4.1 document, see the [module documentation](./index.html) for
+    /// writing it as 1.5.0.
+    pub fn build(&self) -> Collada {
+        let mut ids = IdGenerator::default();
+        for geometry in &self.geometries {
+            ids.reserve_geometry(geometry);
+        }
+        for visual_scene in &self.visual_scenes {
+            ids.reserve(&visual_scene.id);
+            ids.reserve_nodes(&visual_scene.nodes);
+            ids.reserve_extras(&visual_scene.extras);
+        }
+
+        let mut geometries = self.geometries.clone();
+        for geometry in &mut geometries {
+            ids.assign(&mut geometry.id, "geometry");
+        }
+
+        let mut visual_scenes = self.visual_scenes.clone();
+        if visual_scenes.is_empty() && !geometries.is_empty() {
+            let nodes = geometries.iter()
+                .map(|geometry| Node {
+                    id: None,
+                    name: geometry.name.clone(),
+                    sid: None,
+                    node_type: NodeType::Node,
+                    layer: None,
+                    other_attributes: Vec::new(),
+                    asset: None,
+                    transforms: Vec::new(),
+                    instance_cameras: Vec::new(),
+                    instance_controllers: Vec::new(),
+                    instance_geometries: vec![InstanceGeometry {
+                        url: local_uri(geometry.id.as_ref().unwrap()),
+                        sid: None,
+                        name: None,
+                        bind_material: None,
+                        extras: Vec::new(),
+                    }],
+                    instance_lights: Vec::new(),
+                    instance_nodes: Vec::new(),
+                    nodes: Vec::new(),
+                    extras: Vec::new(),
+                })
+                .collect();
+
+            visual_scenes.push(VisualScene {
+                id: None,
+                name: None,
+                asset: None,
+                nodes,
+                evaluate_scenes: Vec::new(),
+                extras: Vec::new(),
+            });
+        }
+        for visual_scene in &mut visual_scenes {
+            ids.assign(&mut visual_scene.id, "visual_scene");
+            ids.assign_nodes(&mut visual_scene.nodes);
+        }
+
+        let scene = visual_scenes.first().map(|visual_scene| Scene {
+            instance_visual_scene: Some(InstanceVisualScene {
+                url: local_uri(visual_scene.id.as_ref().unwrap()),
+                sid: None,
+                name: None,
+                extras: Vec::new(),
+            }),
+            ..Scene::default()
+        });
+
+        // Libraries must have at least one element in them, so empty ones are left out.
+        let mut libraries = Vec::new();
+        if !geometries.is_empty() {
+            libraries.push(Library::Geometries(LibraryGeometries {
+                id: None,
+                name: None,
+                asset: None,
+                geometries,
+                extras: Vec::new(),
+            }));
+        }
+        if !visual_scenes.is_empty() {
+            libraries.push(Library::VisualScenes(LibraryVisualScenes {
+                id: None,
+                name: None,
+                asset: None,
+                visual_scenes,
+                extras: Vec::new(),
+            }));
+        }
+
+        Collada {
+            version: Version::V1_4_1.as_str().into(),
+            xmlns: None,
+            base_uri: None,
+            asset: self.asset.clone().unwrap_or_else(default_asset),
+            libraries,
+            scene,
+            extras: Vec::new(),
+        }
+    }
+}
+
+/// Hands out ids that aren't used anywhere else in the document.
+#[derive(Debug, Default)]
+struct IdGenerator {
+    used: HashSet<String>,
+
+    /// The next number to try for each prefix.
+    next: HashMap<&'static str, usize>,
+}
+
+impl IdGenerator {
+    fn reserve(&mut self, id: &Option<String>) {
+        if let Some(ref id) = *id {
+            self.used.insert(id.clone());
+        }
+    }
+
+    fn reserve_geometry(&mut self, geometry: &Geometry) {
+        self.reserve(&geometry.id);
+        match geometry.geometric_element {
+            GeometricElement::ConvexMesh(ref mesh) => {
+                self.reserve_sources(&mesh.sources);
+                if let Some(ref vertices) = mesh.vertices {
+                    self.reserve_vertices(vertices);
+                }
+                self.reserve_primitives(&mesh.primitives);
+                self.reserve_extras(&mesh.extras);
+            }
+
+            GeometricElement::Mesh(ref mesh) => {
+                self.reserve_sources(&mesh.sources);
+                self.reserve_vertices(&mesh.vertices);
+                self.reserve_primitives(&mesh.primitives);
+                self.reserve_extras(&mesh.extras);
+            }
+
+            GeometricElement::Brep(_) | GeometricElement::Spline(_) => {}
+        }
+        self.reserve_extras(&geometry.extras);
+    }
+
+    fn reserve_sources(&mut self, sources: &[Source]) {
+        for source in sources {
+            self.used.insert(source.id.clone());
+            if let Some(id) = source.array.as_ref().and_then(Array::id) {
+                self.used.insert(id.into());
+            }
+            self.reserve_techniques(&source.techniques);
+        }
+    }
+
+    fn reserve_vertices(&mut self, vertices: &Vertices) {
+        self.used.insert(vertices.id.clone());
+        self.reserve_extras(&vertices.extras);
+    }
+
+    fn reserve_primitives(&mut self, primitives: &[Primitive]) {
+        for primitive in primitives {
+            let extras = match *primitive {
+                Primitive::Lines(ref lines) => &lines.extras,
+                Primitive::Linestrips(ref linestrips) => &linestrips.extras,
+                Primitive::Polygons(ref polygons) => &polygons.extras,
+                Primitive::Polylist(ref polylist) => &polylist.extras,
+                Primitive::Triangles(ref triangles) => &triangles.extras,
+                Primitive::Trifans(ref trifans) => &trifans.extras,
+                Primitive::Tristrips(ref tristrips) => &tristrips.extras,
+            };
+            self.reserve_extras(extras);
+        }
+    }
+
+    fn reserve_nodes(&mut self, nodes: &[Node]) {
+        for node in nodes {
+            self.reserve(&node.id);
+            for instance in &node.instance_cameras {
+                self.reserve_extras(&instance.extras);
+            }
+            for instance in &node.instance_controllers {
+                self.reserve_bind_material(&instance.bind_material);
+                self.reserve_extras(&instance.extras);
+            }
+            for instance in &node.instance_geometries {
+                self.reserve_bind_material(&instance.bind_material);
+                self.reserve_extras(&instance.extras);
+            }
+            for instance in &node.instance_lights {
+                self.reserve_extras(&instance.extras);
+            }
+            for instance in &node.instance_nodes {
+                self.reserve_extras(&instance.extras);
+            }
+            self.reserve_nodes(&node.nodes);
+            self.reserve_extras(&node.extras);
+        }
+    }
+
+    fn reserve_bind_material(&mut self, bind_material: &Option<BindMaterial>) {
+        if let Some(ref bind_material) = *bind_material {
+            for instance in &bind_material.technique_common.instance_materials {
+                self.reserve_extras(&instance.extras);
+            }
+            self.reserve_techniques(&bind_material.techniques);
+            self.reserve_extras(&bind_material.extras);
+        }
+    }
+
+    fn reserve_extras(&mut self, extras: &[Extra]) {
+        for extra in extras {
+            self.reserve(&extra.id);
+            self.reserve_techniques(&extra.techniques);
+        }
+    }
+
+    /// Reserves the ids of the elements inside of techniques, which are only kept as XML.
+    fn reserve_techniques(&mut self, techniques: &[Technique]) {
+        for technique in techniques {
+            for event in &technique.data {
+                if let XmlEvent::StartElement { ref attributes, .. } = *event {
+                    let ids = attributes.iter()
+                        .filter(|attribute| attribute.name.local_name == "id" && attribute.name.prefix.is_none())
+                        .map(|attribute| attribute.value.clone());
+                    self.used.extend(ids);
+                }
+            }
+        }
+    }
+
+    /// Gives `id` a unique value starting with `prefix`, unless it already has one.
+    fn assign(&mut self, id: &mut Option<String>, prefix: &'static str) {
+        if id.is_some() {
+            return;
+        }
+
+        let next = self.next.entry(prefix).or_insert(0);
+        loop {
+            let candidate = format!("{}-{}", prefix, next);
+            *next += 1;
+            if self.used.insert(candidate.clone()) {
+                *id = Some(candidate);
+                return;
+            }
+        }
+    }
+
+    fn assign_nodes(&mut self, nodes: &mut [Node]) {
+        for node in nodes {
+            self.assign(&mut node.id, "node");
+            self.assign_nodes(&mut node.nodes);
+        }
+    }
+}
+
+/// Returns a URI referencing the element with the given id in the same document.
+fn local_uri(id: &str) -> AnyUri {
+    format!("#{}", id).parse().unwrap()
+}
+
+/// Returns an asset for a document created right now.
+fn default_asset() -> Asset {
+    let now = DateTime::Utc(UTC::now().with_timezone(&FixedOffset::east(0)));
+    Asset {
+        contributors: Vec::new(),
+        coverage: None,
+        created: now,
+        keywords: MaybeEmpty::Absent,
+        modified: now,
+        revision: MaybeEmpty::Absent,
+        subject: MaybeEmpty::Absent,
+        title: MaybeEmpty::Absent,
+        unit: None,
+        up_axis: None,
+        extras: Vec::new(),
+    }
+}
@@ -1,12 +1,17 @@
 //! Type definitions common to all supported COLLADA specifications.
 
-use {Error, ErrorKind, Result};
-use std::io::Read;
+use {Error, ErrorKind, Result, Strictness};
+use backend::XmlPull;
+use raw::RawElement;
+use std::io::Write;
+use std::ops::Deref;
 use std::str::FromStr;
+use std::sync::OnceLock;
 use utils;
 use utils::*;
-use xml::common::Position;
-use xml::reader::{EventReader, XmlEvent};
+use writer::{self, XmlWriter};
+use xml::common::{Position, TextPosition};
+use xml::reader::XmlEvent;
 
 /// A URI in the COLLADA document.
 ///
@@ -16,6 +21,23 @@ use xml::reader::{EventReader, XmlEvent};
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AnyUri(String);
 
+impl AnyUri {
+    /// Returns the URI as a string.
+    pub fn as_str(&self) -> &str { self.0.as_ref() }
+
+    /// Returns the ID of the element referenced by the URI, if it refers to an element in the
+    /// same document.
+    ///
+    /// URIs referencing an element in the same document are of the form `"#id"`.
+    pub fn local_id(&self) -> Option<&str> {
+        if self.0.starts_with("#") {
+            Some(&self.0[1..])
+        } else {
+            None
+        }
+    }
+}
+
 // TODO: Actually parse the string and verify that it's a valid URI.
 impl ::std::str::FromStr for AnyUri {
     type Err = ::std::string::ParseError;
@@ -25,6 +47,84 @@ impl ::std::str::FromStr for AnyUri {
     }
 }
 
+/// The unit of distance and up axis in effect for an element.
+///
+/// Any element with an `<asset>` may override the unit and up axis of the element containing it,
+/// so the coordinate system of an element depends on the assets of all of its ancestors. The
+/// `coordinate_system` methods of a document (e.g.
+/// [`Collada::node_coordinate_system`][node_coordinate_system]) take care of this.
+///
+/// [node_coordinate_system]: ../v1_4/struct.Collada.html#method.node_coordinate_system
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CoordinateSystem {
+    /// The unit of distance used by the element.
+    pub unit: Unit,
+
+    /// The up axis used by the element.
+    pub up_axis: UpAxis,
+}
+
+impl CoordinateSystem {
+    /// Returns the coordinate system of an element within this one that declares the given unit
+    /// and up axis, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::common::{CoordinateSystem, Unit, UpAxis};
+    /// let document = CoordinateSystem { unit: Unit::default(), up_axis: UpAxis::Z };
+    /// let centimeter = Unit { meter: 0.01, name: "centimeter".into() };
+    ///
+    /// let node = document.with_overrides(Some(&centimeter), None);
+    /// assert_eq!(centimeter, node.unit);
+    /// assert_eq!(UpAxis::Z, node.up_axis);
+    /// ```
+    pub fn with_overrides(&self, unit: Option<&Unit>, up_axis: Option<UpAxis>) -> CoordinateSystem {
+        CoordinateSystem {
+            unit: unit.unwrap_or(&self.unit).clone(),
+            up_axis: up_axis.unwrap_or(self.up_axis),
+        }
+    }
+
+    /// Returns the factor that distances in this coordinate system are multiplied by to convert
+    /// them to `target`.
+    pub fn scale_to(&self, target: &CoordinateSystem) -> f64 {
+        self.unit.scale_to(&target.unit)
+    }
+
+    /// Returns the transform that converts coordinates in this coordinate system to coordinates
+    /// in the `target` coordinate system, as a 4x4 matrix in row-major order.
+    ///
+    /// The matrix rotates between the two up axes (see [`UpAxis::rotation_to`]) and scales
+    /// between the two units.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::common::{CoordinateSystem, Unit, UpAxis};
+    /// let centimeters = CoordinateSystem {
+    ///     unit: Unit { meter: 0.01, name: "centimeter".into() },
+    ///     up_axis: UpAxis::Z,
+    /// };
+    /// let matrix = centimeters.conversion_to(&CoordinateSystem::default());
+    ///
+    /// // 100 centimeters up is 1 meter up.
+    /// assert!((matrix[2 + 4] * 100.0 - 1.0).abs() < 1e-6);
+    /// ```
+    ///
+    /// [`UpAxis::rotation_to`]: ./enum.UpAxis.html#method.rotation_to
+    pub fn conversion_to(&self, target: &CoordinateSystem) -> [f32; 16] {
+        let scale = self.scale_to(target) as f32;
+        let mut matrix = self.up_axis.rotation_to(target.up_axis);
+        for row in 0..3 {
+            for column in 0..3 {
+                matrix[row * 4 + column] *= scale;
+            }
+        }
+        matrix
+    }
+}
+
 /// A datetime value, with or without a timezone.
 ///
 /// Timestamps in a COLLADA document adhere to [ISO 8601][ISO 8601], which specifies a standard
@@ -60,9 +160,812 @@ impl FromStr for DateTime {
     }
 }
 
+/// Binary data encoded as hexadecimal text.
+///
+/// Represents the [`xs:hexBinary`][hexBinary] XML data type, where each byte is written as two
+/// hexadecimal digits. Whitespace is ignored, so a whitespace-separated list of `xs:hexBinary`
+/// values decodes to all of their bytes in order.
+///
+/// # Examples
+///
+/// ```
+/// use collaborate::common::HexBinary;
+///
+/// let data: HexBinary = "89504e47 0d0A1a0a".parse().unwrap();
+/// assert_eq!(b"\x89PNG\r\n\x1a\n", &*data);
+///
+/// assert!("89504".parse::<HexBinary>().is_err());
+/// ```
+///
+/// [hexBinary]: http://www.datypic.com/sc/xsd/t-xsd_hexBinary.html
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct HexBinary(Vec<u8>);
+
+impl HexBinary {
+    /// Returns the decoded bytes.
+    pub fn as_bytes(&self) -> &[u8] { &*self.0 }
+
+    /// Returns the decoded bytes, consuming the `HexBinary`.
+    pub fn into_bytes(self) -> Vec<u8> { self.0 }
+}
+
+impl ::std::ops::Deref for HexBinary {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] { &*self.0 }
+}
+
+impl FromStr for HexBinary {
+    type Err = HexBinaryParseError;
+
+    fn from_str(source: &str) -> ::std::result::Result<HexBinary, HexBinaryParseError> {
+        let mut digits = source.chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c.to_digit(16).map(|digit| digit as u8).ok_or(HexBinaryParseError));
+
+        let mut bytes = Vec::with_capacity(source.len() / 2);
+        while let Some(high) = digits.next() {
+            let low = digits.next().unwrap_or(Err(HexBinaryParseError));
+            bytes.push(high? << 4 | low?);
+        }
+
+        Ok(HexBinary(bytes))
+    }
+}
+
+/// An error when parsing [`HexBinary`] data.
+///
+/// Parsing fails if the text contains anything other than hexadecimal digits and whitespace, or
+/// if there's an odd number of digits. This type carries no other information.
+///
+/// [`HexBinary`]: ./struct.HexBinary.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HexBinaryParseError;
+
+impl ::std::fmt::Display for HexBinaryParseError {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
+        write!(formatter, "Binary data was not an even number of hexadecimal digits")
+    }
+}
+
+/// The text data of an optional element, distinguishing an empty element from a missing one.
+///
+/// For most optional text data, an empty element (e.g. `<title/>` or `<title></title>`) means the
+/// same thing as a missing one, and so both are represented as `None` when using `Option<T>`.
+/// Where the difference matters, such as when the document will be written back out and should
+/// match the original, `MaybeEmpty<T>` can be used instead. It's equivalent to an
+/// `Option<Option<T>>`, and can be converted to and from one.
+///
+/// Note that `<title/>` and `<title></title>` are both represented as `MaybeEmpty::Empty`, as
+/// the two forms are equivalent in XML.
+///
+/// # Examples
+///
+/// ```
+/// use collaborate::common::MaybeEmpty;
+///
+/// let title = MaybeEmpty::Value(String::from("Model of a thing"));
+/// assert_eq!(Some("Model of a thing"), title.value().map(|title| &**title));
+///
+/// let empty: MaybeEmpty<String> = MaybeEmpty::Empty;
+/// assert!(empty.is_present());
+/// assert_eq!(None, empty.value());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MaybeEmpty<T> {
+    /// The element was not present in the document.
+    Absent,
+
+    /// The element was present but did not contain any text data.
+    Empty,
+
+    /// The element was present and contained a value.
+    Value(T),
+}
+
+impl<T> MaybeEmpty<T> {
+    /// Returns `true` if the element was present in the document, whether or not it was empty.
+    pub fn is_present(&self) -> bool {
+        match *self {
+            MaybeEmpty::Absent => false,
+            MaybeEmpty::Empty | MaybeEmpty::Value(_) => true,
+        }
+    }
+
+    /// Returns `true` if the element was present in the document but did not contain any data.
+    pub fn is_empty(&self) -> bool {
+        match *self {
+            MaybeEmpty::Empty => true,
+            MaybeEmpty::Absent | MaybeEmpty::Value(_) => false,
+        }
+    }
+
+    /// Returns a reference to the contained value, if there is one.
+    pub fn value(&self) -> Option<&T> {
+        match *self {
+            MaybeEmpty::Value(ref value) => Some(value),
+            MaybeEmpty::Absent | MaybeEmpty::Empty => None,
+        }
+    }
+
+    /// Returns a dereferenced reference to the contained value, if there is one, e.g. an
+    /// `Option<&str>` for a `MaybeEmpty<String>`.
+    pub fn as_deref(&self) -> Option<&T::Target> where T: ::std::ops::Deref {
+        self.value().map(|value| &**value)
+    }
+
+    /// Converts the `MaybeEmpty<T>` into an `Option<T>`, discarding the distinction between
+    /// an empty element and a missing one.
+    pub fn into_value(self) -> Option<T> {
+        match self {
+            MaybeEmpty::Value(value) => Some(value),
+            MaybeEmpty::Absent | MaybeEmpty::Empty => None,
+        }
+    }
+}
+
+impl<T> Default for MaybeEmpty<T> {
+    fn default() -> MaybeEmpty<T> { MaybeEmpty::Absent }
+}
+
+impl<T> From<Option<Option<T>>> for MaybeEmpty<T> {
+    fn from(from: Option<Option<T>>) -> MaybeEmpty<T> {
+        match from {
+            None => MaybeEmpty::Absent,
+            Some(None) => MaybeEmpty::Empty,
+            Some(Some(value)) => MaybeEmpty::Value(value),
+        }
+    }
+}
+
+impl<T> From<MaybeEmpty<T>> for Option<Option<T>> {
+    fn from(from: MaybeEmpty<T>) -> Option<Option<T>> {
+        match from {
+            MaybeEmpty::Absent => None,
+            MaybeEmpty::Empty => Some(None),
+            MaybeEmpty::Value(value) => Some(Some(value)),
+        }
+    }
+}
+
+/// The contents of a large array element, such as `<float_array>`.
+///
+/// `RawArray<T>` dereferences to the decoded values, so it can be used like a `&[T]`. By default
+/// the values are decoded while the document is parsed, and a malformed value is an error from
+/// the parse.
+///
+/// Array elements often make up the bulk of a document though, and decoding them is most of the
+/// cost of parsing. When [`ParseOptions::defer_arrays`] is set, the element's text is kept as-is
+/// while the document is parsed, and only decoded into a list of `T` when the array is first
+/// accessed. The decoded values are cached, so later accesses are free. Applications that only
+/// need the structure of the scene (or a single mesh) never pay to decode the arrays they don't
+/// look at.
+///
+/// A malformed value in a deferred array isn't reported when the document is parsed, but is
+/// instead returned from [`get`]. The error's position still points at the array in the original
+/// document. Dereferencing a deferred array that fails to decode panics, so use [`get`] when
+/// decoding is deferred.
+///
+/// # Examples
+///
+/// ```
+/// use std::fs::File;
+/// use collaborate::ParseOptions;
+/// use collaborate::v1_4::{Array, Collada};
+///
+/// let options = ParseOptions {
+///     defer_arrays: true,
+///     ..ParseOptions::default()
+/// };
+///
+/// let file = File::open("resources/blender_cube.dae").unwrap();
+/// let (document, _) = Collada::read_with(file, options).unwrap();
+/// let library = document.libraries[5].as_library_geometries().unwrap();
+/// let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+/// let array = mesh.sources[0].array.as_ref().and_then(Array::as_float_array).unwrap();
+/// assert!(!array.data.is_decoded());
+///
+/// // The array's contents are only decoded here.
+/// let positions = array.data.get().unwrap();
+/// assert_eq!(24, positions.len());
+/// ```
+///
+/// [`ParseOptions::defer_arrays`]: ../struct.ParseOptions.html#structfield.defer_arrays
+/// [`get`]: #method.get
+#[derive(Debug, Clone)]
+pub struct RawArray<T> {
+    /// The text of the array element, if it was kept for decoding later.
+    text: Option<String>,
+    position: TextPosition,
+    decoded: OnceLock<Vec<T>>,
+}
+
+impl<T: ArrayValue> RawArray<T> {
+    /// Creates an array that's decoded from `text` when it's first accessed.
+    pub(crate) fn deferred(text: String, position: TextPosition) -> RawArray<T> {
+        RawArray {
+            text: Some(text),
+            position,
+            decoded: OnceLock::new(),
+        }
+    }
+
+    /// Returns the decoded values, decoding them if this is the first time they're accessed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if decoding was deferred and any of the values in the array are malformed.
+    /// Failed decodes aren't cached, so every call will return the same error.
+    pub fn get(&self) -> Result<&[T]> {
+        if let Some(values) = self.decoded.get() {
+            return Ok(values);
+        }
+
+        let text = self.text.as_ref().map_or("", String::as_str);
+        let values = T::parse_list(text)
+            .map_err(|kind| Error {
+                position: self.position,
+                kind,
+            })?;
+        Ok(self.decoded.get_or_init(|| values))
+    }
+
+    /// Returns `true` if the values have already been decoded.
+    pub fn is_decoded(&self) -> bool {
+        self.decoded.get().is_some()
+    }
+
+    /// Returns the raw text contents of the array element.
+    ///
+    /// The text is only kept when decoding was deferred, otherwise this returns `None`.
+    pub fn raw_text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+}
+
+impl<T> From<Vec<T>> for RawArray<T> {
+    fn from(values: Vec<T>) -> RawArray<T> {
+        let decoded = OnceLock::new();
+        let _ = decoded.set(values);
+        RawArray {
+            text: None,
+            position: TextPosition { row: 0, column: 0 },
+            decoded,
+        }
+    }
+}
+
+/// # Panics
+///
+/// Panics if decoding was deferred and the values in the array are malformed. Use
+/// [`get`](#method.get) to handle the error instead.
+impl<T: ArrayValue> Deref for RawArray<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match self.get() {
+            Ok(values) => values,
+            Err(error) => panic!("Failed to decode array: {}", error),
+        }
+    }
+}
+
+/// Arrays are compared by their values, decoding them if needed. Arrays that fail to decode are
+/// equal if their text is.
+impl<T: ArrayValue + PartialEq> PartialEq for RawArray<T> {
+    fn eq(&self, other: &RawArray<T>) -> bool {
+        match (self.get(), other.get()) {
+            (Ok(values), Ok(other_values)) => values == other_values,
+            (Err(_), Err(_)) => self.text == other.text,
+            _ => false,
+        }
+    }
+}
+
+/// A value type that can be stored in a [`RawArray`].
+///
+/// [`RawArray`]: ./struct.RawArray.html
+pub trait ArrayValue: Sized {
+    /// Decodes a whitespace-separated list of values.
+    fn parse_list(text: &str) -> ::std::result::Result<Vec<Self>, ErrorKind>;
+
+    /// Reformats a list of values written for another locale (e.g. with decimal commas) so that
+    /// it can be decoded, if that applies to this type. Returns `None` if nothing needs fixing.
+    fn normalize_locale(_text: &str) -> Option<String> { None }
+}
+
+macro_rules! float_array_value {
+    ($($ty:ty),*) => {
+        $(
+            impl ArrayValue for $ty {
+                fn parse_list(text: &str) -> ::std::result::Result<Vec<$ty>, ErrorKind> {
+                    utils::parse_float_list(text).map_err(Into::into)
+                }
+
+                fn normalize_locale(text: &str) -> Option<String> {
+                    utils::normalize_locale_list(text)
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! int_array_value {
+    ($($ty:ty),*) => {
+        $(
+            impl ArrayValue for $ty {
+                fn parse_list(text: &str) -> ::std::result::Result<Vec<$ty>, ErrorKind> {
+                    text.split_whitespace()
+                        .map(str::parse)
+                        .collect::<::std::result::Result<Vec<_>, _>>()
+                        .map_err(Into::into)
+                }
+            }
+        )*
+    }
+}
+
+float_array_value!(f32, f64);
+int_array_value!(i32, i64, u32, u64, usize);
+
+impl ArrayValue for bool {
+    /// Decodes a list of `xs:boolean` values, which may be written as either `true`/`false` or
+    /// `1`/`0`.
+    fn parse_list(text: &str) -> ::std::result::Result<Vec<bool>, ErrorKind> {
+        text.split_whitespace()
+            .map(|value| match value {
+                "1" => Ok(true),
+                "0" => Ok(false),
+                _ => value.parse(),
+            })
+            .collect::<::std::result::Result<Vec<_>, _>>()
+            .map_err(Into::into)
+    }
+}
+
+/// A value type that can be parsed from the text contents of an element.
+///
+/// `#[derive(ColladaElement)]` recognizes children of the built-in text types (`String`,
+/// `DateTime`, `AnyUri`, `f32`, `f64`, and `usize`) by name. Children of any other type that
+/// implements `TextValue` must be marked with `#[text_type]` to be parsed as text data, otherwise
+/// the type is assumed to be an element.
+pub trait TextValue: Sized {
+    /// Parses a value from the text contents of an element.
+    fn parse_text(text: &str) -> ::std::result::Result<Self, ErrorKind>;
+}
+
+macro_rules! from_str_text_value {
+    ($($ty:ty),*) => {
+        $(
+            impl TextValue for $ty {
+                fn parse_text(text: &str) -> ::std::result::Result<$ty, ErrorKind> {
+                    text.parse().map_err(Into::into)
+                }
+            }
+        )*
+    }
+}
+
+from_str_text_value!(String, DateTime, AnyUri, f32, f64, usize);
+
+/// The meaning of an input connection, e.g. the `semantic` of a
+/// [`SharedInput`](../v1_4/struct.SharedInput.html).
+///
+/// The semantics defined by the COLLADA specification each have their own variant, any other
+/// semantic is kept as `Other`. Some exporters number their texture coordinate and color sets
+/// directly in the semantic (e.g. `"TEXCOORD1"`) rather than using the input's `set` attribute, so
+/// `TexCoord` and `Color` keep that number if it's present.
+///
+/// A `Semantic` can be compared directly against a string, which compares it with the semantic
+/// as it's written in the document.
+///
+/// # Examples
+///
+/// ```
+/// # use collaborate::common::Semantic;
+/// let semantic: Semantic = "TEXCOORD1".parse().unwrap();
+/// assert_eq!(Semantic::TexCoord(Some(1)), semantic);
+/// assert_eq!(Some(1), semantic.set_index());
+/// assert!(semantic == "TEXCOORD1");
+/// assert!(semantic != "TEXCOORD");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Semantic {
+    /// Geometric binormal (bitangent) vector.
+    Binormal,
+
+    /// Color coordinate vector, with the number of the color set if it's part of the semantic.
+    /// Color inputs are RGB.
+    Color(Option<usize>),
+
+    /// Continuity constraint at the control vertex of a spline.
+    Continuity,
+
+    /// Raster or MIP-level input.
+    Image,
+
+    /// Sampler input, e.g. the keyframe times of an animation.
+    Input,
+
+    /// Tangent vector for the preceding control point of a curve.
+    InTangent,
+
+    /// Sampler interpolation type.
+    Interpolation,
+
+    /// Inverse of the location-to-world matrix of a joint.
+    InvBindMatrix,
+
+    /// Skin influence identifier.
+    Joint,
+
+    /// Number of piece-wise linear approximation steps to use for the spline segment that follows
+    /// a control vertex.
+    LinearSteps,
+
+    /// Morph targets for mesh morphing.
+    MorphTarget,
+
+    /// Weights for mesh morphing.
+    MorphWeight,
+
+    /// Normal vector.
+    Normal,
+
+    /// Sampler output, e.g. the keyframe values of an animation.
+    Output,
+
+    /// Tangent vector for the succeeding control point of a curve.
+    OutTangent,
+
+    /// Geometric coordinate vector.
+    Position,
+
+    /// Geometric tangent vector.
+    Tangent,
+
+    /// Texture binormal (bitangent) vector.
+    TexBinormal,
+
+    /// Texture coordinate vector, with the number of the texture coordinate set if it's part of
+    /// the semantic.
+    TexCoord(Option<usize>),
+
+    /// Texture tangent vector.
+    TexTangent,
+
+    /// Generic parameter vector.
+    Uv,
+
+    /// Mesh vertex, i.e. all of the inputs of the mesh's `<vertices>`.
+    Vertex,
+
+    /// Skin influence weighting value.
+    Weight,
+
+    /// A semantic not defined by the COLLADA specification.
+    Other(String),
+}
+
+impl Semantic {
+    /// Returns the name of the semantic without any set number, e.g. `"TEXCOORD"`.
+    pub fn name(&self) -> &str {
+        match *self {
+            Semantic::Binormal => "BINORMAL",
+            Semantic::Color(_) => "COLOR",
+            Semantic::Continuity => "CONTINUITY",
+            Semantic::Image => "IMAGE",
+            Semantic::Input => "INPUT",
+            Semantic::InTangent => "IN_TANGENT",
+            Semantic::Interpolation => "INTERPOLATION",
+            Semantic::InvBindMatrix => "INV_BIND_MATRIX",
+            Semantic::Joint => "JOINT",
+            Semantic::LinearSteps => "LINEAR_STEPS",
+            Semantic::MorphTarget => "MORPH_TARGET",
+            Semantic::MorphWeight => "MORPH_WEIGHT",
+            Semantic::Normal => "NORMAL",
+            Semantic::Output => "OUTPUT",
+            Semantic::OutTangent => "OUT_TANGENT",
+            Semantic::Position => "POSITION",
+            Semantic::Tangent => "TANGENT",
+            Semantic::TexBinormal => "TEXBINORMAL",
+            Semantic::TexCoord(_) => "TEXCOORD",
+            Semantic::TexTangent => "TEXTANGENT",
+            Semantic::Uv => "UV",
+            Semantic::Vertex => "VERTEX",
+            Semantic::Weight => "WEIGHT",
+            Semantic::Other(ref semantic) => semantic,
+        }
+    }
+
+    /// Returns the set number written as part of a `TEXCOORD` or `COLOR` semantic, e.g. `1` for
+    /// `"TEXCOORD1"`.
+    ///
+    /// Most documents use the input's `set` attribute instead, which is preferred by
+    /// `SharedInput::set_index`.
+    pub fn set_index(&self) -> Option<usize> {
+        match *self {
+            Semantic::Color(set) | Semantic::TexCoord(set) => set,
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Semantic {
+    type Err = ::std::string::ParseError;
+
+    fn from_str(source: &str) -> ::std::result::Result<Semantic, ::std::string::ParseError> {
+        let semantic = match source {
+            "BINORMAL" => Semantic::Binormal,
+            "COLOR" => Semantic::Color(None),
+            "CONTINUITY" => Semantic::Continuity,
+            "IMAGE" => Semantic::Image,
+            "INPUT" => Semantic::Input,
+            "IN_TANGENT" => Semantic::InTangent,
+            "INTERPOLATION" => Semantic::Interpolation,
+            "INV_BIND_MATRIX" => Semantic::InvBindMatrix,
+            "JOINT" => Semantic::Joint,
+            "LINEAR_STEPS" => Semantic::LinearSteps,
+            "MORPH_TARGET" => Semantic::MorphTarget,
+            "MORPH_WEIGHT" => Semantic::MorphWeight,
+            "NORMAL" => Semantic::Normal,
+            "OUTPUT" => Semantic::Output,
+            "OUT_TANGENT" => Semantic::OutTangent,
+            "POSITION" => Semantic::Position,
+            "TANGENT" => Semantic::Tangent,
+            "TEXBINORMAL" => Semantic::TexBinormal,
+            "TEXCOORD" => Semantic::TexCoord(None),
+            "TEXTANGENT" => Semantic::TexTangent,
+            "UV" => Semantic::Uv,
+            "VERTEX" => Semantic::Vertex,
+            "WEIGHT" => Semantic::Weight,
+            _ => {
+                // Only treat the suffix as a set number if it's written the same way it would be
+                // written back out, so that the semantic round-trips exactly.
+                let numbered = |prefix: &str| -> Option<usize> {
+                    let suffix = source.get(prefix.len()..).filter(|_| source.starts_with(prefix))?;
+                    let set: usize = suffix.parse().ok()?;
+                    if set.to_string() == suffix { Some(set) } else { None }
+                };
+
+                if let Some(set) = numbered("TEXCOORD") {
+                    Semantic::TexCoord(Some(set))
+                } else if let Some(set) = numbered("COLOR") {
+                    Semantic::Color(Some(set))
+                } else {
+                    Semantic::Other(source.into())
+                }
+            }
+        };
+
+        Ok(semantic)
+    }
+}
+
+impl<'a> From<&'a str> for Semantic {
+    fn from(from: &'a str) -> Semantic {
+        match from.parse() {
+            Ok(semantic) => semantic,
+            Err(error) => match error {},
+        }
+    }
+}
+
+impl ::std::fmt::Display for Semantic {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
+        formatter.write_str(self.name())?;
+        match self.set_index() {
+            Some(set) => write!(formatter, "{}", set),
+            None => Ok(()),
+        }
+    }
+}
+
+impl PartialEq<str> for Semantic {
+    fn eq(&self, other: &str) -> bool {
+        match self.set_index() {
+            Some(set) => {
+                other.starts_with(self.name()) && other[self.name().len()..] == *set.to_string()
+            }
+            None => self.name() == other,
+        }
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Semantic {
+    fn eq(&self, other: &&'a str) -> bool {
+        *self == **other
+    }
+}
+
+/// A parsed COLLADA address, which targets a value in the document through a path of ids and
+/// sids, e.g. the `target` of an animation [`Channel`].
+///
+/// An address starts with the id of an element, or with `.` to start from the element the
+/// address appears in. It's followed by the sids of the elements within it, separated by `/`,
+/// and can end with a selector picking out part of the targeted value:
+///
+/// | Address                 | Targets                                                   |
+/// | ----------------------- | --------------------------------------------------------- |
+/// | `"Cube/location"`       | The `location` transform of the `Cube` node.              |
+/// | `"Cube/location.X"`     | The x value of that transform.                            |
+/// | `"joint/rotateY.ANGLE"` | The angle of the `rotateY` transform of the `joint` node. |
+/// | `"Box/transform(3)(2)"` | Row 3, column 2 of the `transform` matrix of `Box`.       |
+/// | `"./translate"`         | The `translate` transform of the current element.         |
+///
+/// Addresses are resolved against a document with `Collada::resolve_sid_path`.
+///
+/// # Examples
+///
+/// ```
+/// # use collaborate::common::{SidRef, SidSelector};
+/// let address: SidRef = "Armature/Bone/rotateY.ANGLE".parse().unwrap();
+/// assert_eq!(Some("Armature".into()), address.root);
+/// assert_eq!(vec!["Bone", "rotateY"], address.sids);
+/// assert_eq!(Some(SidSelector::Member("ANGLE".into())), address.selector);
+/// assert_eq!("Armature/Bone/rotateY.ANGLE", address.to_string());
+/// ```
+///
+/// [`Channel`]: ../v1_4/struct.Channel.html
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SidRef {
+    /// The id of the element the address starts from, or `None` if the address starts from the
+    /// element it appears in.
+    pub root: Option<String>,
+
+    /// The sids of the elements to follow from the root, in order.
+    pub sids: Vec<String>,
+
+    /// The part of the targeted value that's selected, if only part of it is.
+    pub selector: Option<SidSelector>,
+}
+
+impl FromStr for SidRef {
+    type Err = SidRefParseError;
+
+    fn from_str(source: &str) -> ::std::result::Result<SidRef, SidRefParseError> {
+        // The selector can only appear at the end of the last segment, so split it off first.
+        let last_start = source.rfind('/').map(|index| index + 1).unwrap_or(0);
+        let last = &source[last_start..];
+        let (path, selector) = match last.find(['.', '(']) {
+            // A leading `.` is the relative root, not a member selector.
+            Some(0) if last_start == 0 && last.starts_with('.') => (source, None),
+            Some(index) => {
+                let selector = SidSelector::parse(&last[index..]).ok_or(SidRefParseError)?;
+                (&source[..last_start + index], Some(selector))
+            }
+            None => (source, None),
+        };
+
+        let mut segments = path.split('/');
+        let root = match segments.next() {
+            Some(".") => None,
+            Some(id) if !id.is_empty() => Some(id.into()),
+            _ => return Err(SidRefParseError),
+        };
+        let sids = segments.map(String::from).collect::<Vec<_>>();
+        if sids.iter().any(String::is_empty) || (root.is_none() && sids.is_empty()) {
+            return Err(SidRefParseError);
+        }
+
+        Ok(SidRef {
+            root,
+            sids,
+            selector,
+        })
+    }
+}
+
+impl ::std::fmt::Display for SidRef {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
+        formatter.write_str(self.root.as_deref().unwrap_or("."))?;
+        for sid in &self.sids {
+            write!(formatter, "/{}", sid)?;
+        }
+
+        match self.selector {
+            Some(ref selector) => write!(formatter, "{}", selector),
+            None => Ok(()),
+        }
+    }
+}
+
+/// An error when parsing a [`SidRef`].
+///
+/// Parsing fails if the address has an empty segment, or if its selector isn't a member name or
+/// one or two array indices. Since there's nothing else to report, this type carries no other
+/// information.
+///
+/// [`SidRef`]: ./struct.SidRef.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SidRefParseError;
+
+impl ::std::fmt::Display for SidRefParseError {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
+        write!(formatter, "invalid SID address")
+    }
+}
+
+/// Selects part of the value targeted by a [`SidRef`].
+///
+/// [`SidRef`]: ./struct.SidRef.html
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SidSelector {
+    /// A named member, e.g. `X` in `"Cube/location.X"`.
+    Member(String),
+
+    /// An index into a vector, e.g. `(2)`.
+    Index(usize),
+
+    /// A row and column of a matrix, e.g. `(3)(2)`.
+    Index2(usize, usize),
+}
+
+impl SidSelector {
+    /// Returns the index of the selected value within a list of values, where `columns` is the
+    /// number of columns in each row of a matrix.
+    ///
+    /// Members are mapped according to the specification: `X`, `R`, `U`, and `S` select the first
+    /// value, `Y`, `G`, `V`, and `T` the second, `Z`, `B`, and `P` the third, and `W`, `A`, `Q`,
+    /// and `ANGLE` the fourth. Returns `None` for any other member.
+    pub fn index(&self, columns: usize) -> Option<usize> {
+        match *self {
+            SidSelector::Member(ref member) => match &**member {
+                "X" | "R" | "U" | "S" => Some(0),
+                "Y" | "G" | "V" | "T" => Some(1),
+                "Z" | "B" | "P" => Some(2),
+                "W" | "A" | "Q" | "ANGLE" => Some(3),
+                _ => None,
+            },
+            SidSelector::Index(index) => Some(index),
+            SidSelector::Index2(row, column) => Some(row * columns + column),
+        }
+    }
+
+    /// Parses a selector including its leading `.` or `(`.
+    fn parse(source: &str) -> Option<SidSelector> {
+        if let Some(member) = source.strip_prefix('.') {
+            if member.is_empty() || member.contains(['.', '(', ')']) {
+                return None;
+            }
+            return Some(SidSelector::Member(member.into()));
+        }
+
+        // Each index is written as `(n)`, so splitting on `)` leaves a trailing empty string.
+        let mut indices = Vec::new();
+        let mut parts = source.split(')');
+        let trailing = parts.next_back();
+        for part in parts {
+            if !part.starts_with('(') {
+                return None;
+            }
+            indices.push(part[1..].parse::<usize>().ok()?);
+        }
+
+        match (trailing, &*indices) {
+            (Some(""), &[index]) => Some(SidSelector::Index(index)),
+            (Some(""), &[row, column]) => Some(SidSelector::Index2(row, column)),
+            _ => None,
+        }
+    }
+}
+
+impl ::std::fmt::Display for SidSelector {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
+        match *self {
+            SidSelector::Member(ref member) => write!(formatter, ".{}", member),
+            SidSelector::Index(index) => write!(formatter, "({})", index),
+            SidSelector::Index2(row, column) => write!(formatter, "({})({})", row, column),
+        }
+    }
+}
+
 /// Arbitrary additional information represented as XML events.
 ///
-/// > TODO: Provide more information about processing techniques.
+/// Each technique holds the data for one profile, usually the name of the application that wrote
+/// it (e.g. `"blender"`, `"FCOLLADA"`, `"OpenCOLLADA"`, or `"GOOGLEEARTH"`). The contents aren't
+/// interpreted in any way, they're kept exactly as they appear in the document so that no
+/// information is lost when a document is loaded and written back out.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Technique {
     /// A vendor-defined string that indicates the platform or capability target for the technique.
@@ -83,17 +986,26 @@ pub struct Technique {
     pub data: Vec<XmlEvent>,
 }
 
+impl Technique {
+    /// Returns the elements contained within the technique as a tree of
+    /// [`RawElement`](../raw/struct.RawElement.html)s, which is usually easier to work with than
+    /// the raw events in `data`.
+    pub fn elements(&self) -> Vec<RawElement> {
+        RawElement::from_events(&self.data)
+    }
+}
+
 impl ColladaElement for Technique {
     fn name_test(name: &str) -> bool {
         name == "technique"
     }
 
     fn parse_element<R>(
-        reader: &mut EventReader<R>,
+        reader: &mut DocumentReader<R>,
         element_start: ElementStart,
     ) -> Result<Technique>
     where
-        R: Read,
+        R: XmlPull,
     {
         let mut profile = None;
         let mut xmlns = None;
@@ -116,14 +1028,16 @@ impl ColladaElement for Technique {
                 }
 
                 _ => {
-                    return Err(Error {
+                    let error = Error {
                         position: reader.position(),
                         kind: ErrorKind::UnexpectedAttribute {
                             element: "technique",
                             attribute: attribute.name.local_name.clone(),
                             expected: vec!["profile", "xmlns"],
                         },
-                    });
+                    };
+                    reader.tolerate(error, Strictness::Lenient)?;
+                    reader.preserve_attribute(attribute);
                 }
             }
         }
@@ -172,6 +1086,18 @@ impl ColladaElement for Technique {
     fn add_names(names: &mut Vec<&'static str>) {
         names.push("technique");
     }
+
+    fn write_element<W: Write>(&self, writer: &mut XmlWriter<W>) -> writer::Result<()> {
+        writer.start_element("technique")?;
+        writer.attribute("profile", &self.profile)?;
+        if let Some(ref xmlns) = self.xmlns {
+            writer.attribute("xmlns", xmlns)?;
+        }
+        for event in &self.data {
+            writer.event(event)?;
+        }
+        writer.end_element("technique")
+    }
 }
 
 /// Defines the unit of distance for an [`Asset`][Asset].
@@ -184,18 +1110,52 @@ impl ColladaElement for Technique {
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
 #[name = "unit"]
 pub struct Unit {
-    /// The name of the distance unit. For example, “meter”, “centimeter”, “inch”, or “parsec”.
-    /// This can be the name of a real measurement, or an imaginary name. Defaults to `1.0`.
+    /// How many real-world meters in one distance unit as a floating-point number. For example,
+    /// 1.0 for the name "meter"; 1000 for the name "kilometer"; 0.3048 for the name
+    /// "foot". Defaults to `1.0`.
     #[attribute]
+    #[optional_with_default = "1.0"]
     pub meter: f64,
 
-    /// How many real-world meters in one distance unit as a floating-point number. For example,
-    /// 1.0 for the name "meter"; 1000 for the name "kilometer"; 0.3048 for the name
-    /// "foot". Defaults to "meter".
+    /// The name of the distance unit. For example, “meter”, “centimeter”, “inch”, or “parsec”.
+    /// This can be the name of a real measurement, or an imaginary name. Defaults to "meter".
     #[attribute]
+    #[optional_with_default = r#""meter".into()"#]
     pub name: String,
 }
 
+impl Unit {
+    /// Converts a distance in this unit to meters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::common::Unit;
+    /// let centimeter = Unit { meter: 0.01, name: "centimeter".into() };
+    /// assert_eq!(2.5, centimeter.to_meters(250.0));
+    /// ```
+    pub fn to_meters(&self, distance: f64) -> f64 {
+        distance * self.meter
+    }
+
+    /// Returns the factor that distances in this unit are multiplied by to convert them to
+    /// `target`.
+    ///
+    /// Only the `meter` values are used, the names of the units are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::common::Unit;
+    /// let inch = Unit { meter: 0.0254, name: "inch".into() };
+    /// let centimeter = Unit { meter: 0.01, name: "centimeter".into() };
+    /// assert!((inch.scale_to(&centimeter) - 2.54).abs() < 1e-9);
+    /// ```
+    pub fn scale_to(&self, target: &Unit) -> f64 {
+        self.meter / target.meter
+    }
+}
+
 impl Default for Unit {
     fn default() -> Unit {
         Unit {
@@ -224,45 +1184,88 @@ pub enum UpAxis {
     Z,
 }
 
-impl ColladaElement for UpAxis {
-    fn name_test(name: &str) -> bool {
-        name == "up_axis"
+impl TextValue for UpAxis {
+    fn parse_text(text: &str) -> ::std::result::Result<UpAxis, ErrorKind> {
+        match text {
+            "X_UP" => Ok(UpAxis::X),
+            "Y_UP" => Ok(UpAxis::Y),
+            "Z_UP" => Ok(UpAxis::Z),
+            _ => Err(ErrorKind::InvalidValue {
+                element: "up_axis",
+                value: text.into(),
+            }),
+        }
     }
+}
 
-    fn parse_element<R>(
-        reader: &mut EventReader<R>,
-        element_start: ElementStart,
-    ) -> Result<UpAxis>
-    where
-        R: Read,
-    {
-        utils::verify_attributes(reader, "up_axis", element_start.attributes)?;
-        let text: String = utils::optional_text_contents(reader, "up_axis")?.unwrap_or_default();
-        let parsed = match &*text {
-            "X_UP" => { UpAxis::X }
-            "Y_UP" => { UpAxis::Y }
-            "Z_UP" => { UpAxis::Z }
-            _ => {
-                return Err(Error {
-                    position: reader.position(),
-                    kind: ErrorKind::InvalidValue {
-                        element: "up_axis".into(),
-                        value: text,
-                    },
-                });
-            }
-        };
+impl Default for UpAxis {
+    fn default() -> UpAxis { UpAxis::Y }
+}
 
-        Ok(parsed)
+impl UpAxis {
+    /// Returns the value of the axis as it's written in a document, e.g. `"Y_UP"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UpAxis::X => "X_UP",
+            UpAxis::Y => "Y_UP",
+            UpAxis::Z => "Z_UP",
+        }
     }
 
-    fn add_names(names: &mut Vec<&'static str>) {
-        names.push("up_axis");
+    /// Returns the unit vector pointing up in this coordinate system.
+    pub fn up_vector(self) -> [f32; 3] {
+        match self {
+            UpAxis::X => [1.0, 0.0, 0.0],
+            UpAxis::Y => [0.0, 1.0, 0.0],
+            UpAxis::Z => [0.0, 0.0, 1.0],
+        }
+    }
+
+    /// Returns the rotation that converts coordinates in this coordinate system to coordinates in
+    /// the `target` coordinate system, as a 4x4 matrix in row-major order.
+    ///
+    /// The right, up, and in axes of the two coordinate systems (see the table above) are
+    /// mapped onto each other, so no mirroring is involved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::common::UpAxis;
+    /// let matrix = UpAxis::Z.rotation_to(UpAxis::Y);
+    ///
+    /// // Up in a Z-up document is up in a Y-up document too.
+    /// let up = [matrix[2], matrix[6], matrix[10]];
+    /// assert_eq!([0.0, 1.0, 0.0], up);
+    /// ```
+    pub fn rotation_to(self, target: UpAxis) -> [f32; 16] {
+        // Compose the rotation from this system to Y-up with the inverse (i.e. the transpose)
+        // of the rotation from the target system to Y-up.
+        let from = self.rotation_to_y_up();
+        let to = target.rotation_to_y_up();
+        let mut matrix = [0.0; 16];
+        for row in 0..3 {
+            for column in 0..3 {
+                matrix[row * 4 + column] = (0..3).map(|index| to[index][row] * from[index][column]).sum();
+            }
+        }
+        matrix[15] = 1.0;
+        matrix
+    }
+
+    /// Returns the rotation from this coordinate system to the Y-up coordinate system.
+    fn rotation_to_y_up(self) -> [[f32; 3]; 3] {
+        match self {
+            UpAxis::X => [[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]],
+            UpAxis::Y => [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            UpAxis::Z => [[1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, -1.0, 0.0]],
+        }
     }
 }
 
-impl Default for UpAxis {
-    fn default() -> UpAxis { UpAxis::Y }
+impl ::std::fmt::Display for UpAxis {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
+        formatter.write_str(self.as_str())
+    }
 }
 
 /// Represents the final fragment portion of a URI.
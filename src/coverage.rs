@@ -0,0 +1,161 @@
+//! Reporting on how much of the COLLADA schema is supported.
+//!
+//! Not every element in the COLLADA specification is fully supported yet. Elements that aren't
+//! supported are still accepted in a document, but their contents are skipped during parsing
+//! (they are "stubbed out"). [`schema_coverage`] reports which elements reachable from the root
+//! `<COLLADA>` element are implemented and which are stubbed, for each supported version of the
+//! specification. The report is generated from the same type definitions used for parsing, so it
+//! is always in sync with what the parser actually does.
+//!
+//! # Examples
+//!
+//! ```
+//! use collaborate::coverage::{self, Support};
+//!
+//! let coverage = coverage::schema_coverage();
+//! assert_eq!(Some(Support::Implemented), coverage.v1_4.support("asset"));
+//!
+//! for (element, support) in coverage.v1_5.elements() {
+//!     println!("<{}>: {:?}", element, support);
+//! }
+//! ```
+//!
+//! [`schema_coverage`]: ./fn.schema_coverage.html
+
+use std::any::TypeId;
+use std::collections::{BTreeMap, HashSet};
+use std::collections::btree_map;
+use utils::ColladaElement;
+use {v1_4, v1_5, Version};
+
+/// Returns the schema coverage for all supported versions of the COLLADA specification.
+pub fn schema_coverage() -> SchemaCoverage {
+    SchemaCoverage {
+        v1_4: ElementCoverage::for_root::<v1_4::Collada>(Version::V1_4_1),
+        v1_5: ElementCoverage::for_root::<v1_5::Collada>(Version::V1_5_0),
+    }
+}
+
+/// Schema coverage for each supported version of the COLLADA specification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaCoverage {
+    /// Coverage for `1.4.0` and `1.4.1` documents.
+    pub v1_4: ElementCoverage,
+
+    /// Coverage for `1.5.0` documents.
+    pub v1_5: ElementCoverage,
+}
+
+/// The level of support for a single element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Support {
+    /// The element is accepted, but its contents are skipped.
+    Stubbed,
+
+    /// The element and its contents are parsed.
+    Implemented,
+}
+
+/// The set of elements reachable from the root of a document, along with their level of support.
+#[derive(Debug, Clone, Default, Eq)]
+pub struct ElementCoverage {
+    elements: BTreeMap<&'static str, Support>,
+    visited: HashSet<TypeId>,
+    version: Option<Version>,
+}
+
+impl ElementCoverage {
+    fn for_root<T: ColladaElement + 'static>(version: Version) -> ElementCoverage {
+        let mut coverage = ElementCoverage {
+            version: Some(version),
+            ..ElementCoverage::default()
+        };
+        T::add_coverage(&mut coverage);
+        coverage
+    }
+
+    /// Returns the level of support for the element `name`.
+    ///
+    /// Returns `None` if the element isn't known to the parser. If multiple elements share the
+    /// same name (e.g. `<input>` is used both with and without an `offset`), the element is
+    /// reported as implemented if any of them are implemented.
+    pub fn support(&self, name: &str) -> Option<Support> {
+        self.elements.get(name).cloned()
+    }
+
+    /// Returns an iterator over all known elements, in alphabetical order.
+    pub fn elements(&self) -> Elements<'_> {
+        Elements { iter: self.elements.iter() }
+    }
+
+    /// Returns an iterator over the names of the elements that are implemented.
+    pub fn implemented<'a>(&'a self) -> Box<Iterator<Item = &'static str> + 'a> {
+        Box::new(self.elements().filter(|&(_, support)| support == Support::Implemented).map(|(name, _)| name))
+    }
+
+    /// Returns an iterator over the names of the elements that are stubbed out.
+    pub fn stubbed<'a>(&'a self) -> Box<Iterator<Item = &'static str> + 'a> {
+        Box::new(self.elements().filter(|&(_, support)| support == Support::Stubbed).map(|(name, _)| name))
+    }
+
+    /// Records the type `T` as providing the element `name`.
+    ///
+    /// Returns `false` if `T` has already been visited, in which case its children don't need to
+    /// be visited again. This is what keeps recursive elements (e.g. `<node>`) from recursing
+    /// forever.
+    #[doc(hidden)]
+    pub fn insert<T: 'static>(&mut self, name: &'static str, support: Support) -> bool {
+        if !self.visited.insert(TypeId::of::<T>()) {
+            return false;
+        }
+
+        self.insert_name(name, support);
+        true
+    }
+
+    /// Whether members restricted to `valid_in` versions of the specification are part of the
+    /// schema being covered.
+    ///
+    /// Members that were added or removed in other versions can still be parsed from documents
+    /// with `Strictness::Permissive`, but aren't reported as part of this version's schema.
+    #[doc(hidden)]
+    pub fn allows(&self, valid_in: &str) -> bool {
+        match self.version {
+            Some(version) => version.is_compatible(valid_in),
+            None => true,
+        }
+    }
+
+    /// Records an element that only contains text data, and so has no type of its own.
+    #[doc(hidden)]
+    pub fn insert_name(&mut self, name: &'static str, support: Support) {
+        let entry = self.elements.entry(name).or_insert(support);
+        if support > *entry {
+            *entry = support;
+        }
+    }
+}
+
+// Only the elements are compared, the types visited along the way and the version they were
+// collected for are an implementation detail.
+impl PartialEq for ElementCoverage {
+    fn eq(&self, other: &ElementCoverage) -> bool {
+        self.elements == other.elements
+    }
+}
+
+/// An iterator over the elements in an [`ElementCoverage`].
+///
+/// [`ElementCoverage`]: ./struct.ElementCoverage.html
+#[derive(Debug, Clone)]
+pub struct Elements<'a> {
+    iter: btree_map::Iter<'a, &'static str, Support>,
+}
+
+impl<'a> Iterator for Elements<'a> {
+    type Item = (&'static str, Support);
+
+    fn next(&mut self) -> Option<(&'static str, Support)> {
+        self.iter.next().map(|(&name, &support)| (name, support))
+    }
+}
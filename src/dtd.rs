@@ -0,0 +1,501 @@
+//! Handling for document type declarations and custom entities.
+//!
+//! Neither XML backend understands DTDs: xml-rs can't read a DTD with an internal subset, and both
+//! backends fail on references to entities other than XML's predefined ones. [`DtdFilter`] deals
+//! with both before the document reaches the backend, as configured by the [`ParseOptions`].
+
+use {Error, ErrorKind, Result, XmlError};
+use encoding::copy_from;
+use options::{DtdHandling, ParseOptions};
+use progress::ProgressCallback;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+use std::str;
+use xml::common::TextPosition;
+
+/// The number of bytes read from the source at a time.
+const CHUNK_LEN: usize = 8 * 1024;
+
+/// The longest entity name that will be expanded.
+///
+/// References with longer names are passed through to the backend unchanged, so that a stray `&`
+/// doesn't cause the rest of the document to be buffered while looking for a `;`.
+const MAX_ENTITY_LEN: usize = 256;
+
+/// Reads a document, skipping or rejecting its DTD and expanding any custom entities.
+///
+/// The document's prolog (everything before the root element) is read when the filter is
+/// created, so that a rejected DTD is reported up front. After that the document is passed
+/// through unchanged, unless there are entities to expand.
+pub struct DtdFilter<R> {
+    source: R,
+    entities: BTreeMap<String, String>,
+    progress: Option<ProgressCallback>,
+    markup: Markup,
+    eof: bool,
+
+    /// Bytes read from `source` that haven't been used yet, starting at `input_start`.
+    input: Vec<u8>,
+    input_start: usize,
+
+    /// Filtered bytes that haven't been returned yet, starting at `output_start`.
+    output: Vec<u8>,
+    output_start: usize,
+}
+
+/// The kind of markup that the filter is currently in, i.e. where entities may appear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Markup {
+    /// Text content and tags, where entity references are expanded.
+    Content,
+    Comment,
+    CData,
+    ProcessingInstruction,
+}
+
+impl Markup {
+    /// The text that ends this kind of markup.
+    fn end(self) -> &'static [u8] {
+        match self {
+            Markup::Content => b"",
+            Markup::Comment => b"-->",
+            Markup::CData => b"]]>",
+            Markup::ProcessingInstruction => b"?>",
+        }
+    }
+}
+
+impl<R: Read> DtdFilter<R> {
+    /// Begins filtering `source`, reading its prolog.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the document has a DTD and `options.dtd` is [`DtdHandling::Reject`], or if
+    /// reading the prolog fails.
+    pub fn new(source: R, options: &ParseOptions) -> Result<DtdFilter<R>> {
+        DtdFilter::with_buffers(source, options, (Vec::new(), Vec::new()))
+    }
+
+    /// Begins filtering `source` the same as [`new`](#method.new), reusing `buffers` from a
+    /// previous filter.
+    ///
+    /// See [`into_parts`](#method.into_parts).
+    pub fn with_buffers(source: R, options: &ParseOptions, buffers: (Vec<u8>, Vec<u8>)) -> Result<DtdFilter<R>> {
+        let (mut input, mut output) = buffers;
+        input.clear();
+        output.clear();
+
+        let mut filter = DtdFilter {
+            source,
+            entities: options.entities.clone(),
+            progress: options.progress.clone(),
+            markup: Markup::Content,
+            eof: false,
+            input,
+            input_start: 0,
+            output,
+            output_start: 0,
+        };
+
+        if let Some(ref progress) = filter.progress {
+            progress.start();
+        }
+
+        // The prolog has been dealt with once it's read, so it can be passed through as-is.
+        let prolog_len = filter.read_prolog(options.dtd)?;
+        filter.output.extend(filter.input.drain(..prolog_len));
+        Ok(filter)
+    }
+
+    /// Returns the source along with the buffers used for filtering it, so that the buffers can
+    /// be reused with [`with_buffers`](#method.with_buffers).
+    pub fn into_parts(self) -> (R, (Vec<u8>, Vec<u8>)) {
+        (self.source, (self.input, self.output))
+    }
+
+    /// Reads up to the start of the root element, handling any DTD found along the way.
+    ///
+    /// Returns the length of the prolog.
+    fn read_prolog(&mut self, dtd: DtdHandling) -> Result<usize> {
+        let mut offset = 0;
+        loop {
+            let (start, len) = match next_in_prolog(&self.input, offset, self.eof) {
+                PrologPart::Incomplete => {
+                    self.fill().map_err(XmlError::from)?;
+                    continue;
+                }
+
+                PrologPart::End(end) => return Ok(end),
+
+                PrologPart::Markup { start, len, doctype: false } => {
+                    offset = start + len;
+                    continue;
+                }
+
+                PrologPart::Markup { start, len, doctype: true } => (start, len),
+            };
+
+            match dtd {
+                DtdHandling::Skip => {
+                    let blanked = blank(&self.input[start..start + len]);
+                    offset = start + blanked.len();
+                    self.input.splice(start..start + len, blanked);
+                }
+
+                DtdHandling::Reject => {
+                    return Err(Error {
+                        position: position_at(&self.input, start),
+                        kind: ErrorKind::DtdNotAllowed,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Reads the next chunk of the source onto the end of the buffered input.
+    fn fill(&mut self) -> io::Result<()> {
+        self.input.drain(..self.input_start);
+        self.input_start = 0;
+
+        let len = self.input.len();
+        self.input.resize(len + CHUNK_LEN, 0);
+        loop {
+            match self.source.read(&mut self.input[len..]) {
+                Ok(read) => {
+                    self.input.truncate(len + read);
+                    self.eof = read == 0;
+                    self.record_progress(read);
+                    return Ok(());
+                }
+
+                Err(ref error) if error.kind() == io::ErrorKind::Interrupted => {}
+
+                Err(error) => {
+                    self.input.truncate(len);
+                    return Err(error);
+                }
+            }
+        }
+    }
+
+    fn record_progress(&self, read: usize) {
+        if let Some(ref progress) = self.progress {
+            progress.add_bytes(read);
+        }
+    }
+
+    /// Expands entity references in as much of the buffered input as possible, writing the
+    /// result to `output`.
+    ///
+    /// Stops early if more input is needed to tell what comes next.
+    fn expand(&mut self) {
+        let mut offset = self.input_start;
+        while offset < self.input.len() {
+            let rest = &self.input[offset..];
+            if self.markup != Markup::Content {
+                // Entities aren't expanded in comments, CDATA, or processing instructions, so
+                // skip ahead to the end of the markup.
+                let end = self.markup.end();
+                match find(rest, end) {
+                    Some(index) => {
+                        self.output.extend_from_slice(&rest[..index + end.len()]);
+                        offset += index + end.len();
+                        self.markup = Markup::Content;
+                        continue;
+                    }
+
+                    None => {
+                        // Hold back enough to recognize the end if it's split between chunks.
+                        let len = if self.eof { rest.len() } else { rest.len().saturating_sub(end.len() - 1) };
+                        self.output.extend_from_slice(&rest[..len]);
+                        offset += len;
+                        break;
+                    }
+                }
+            }
+
+            let index = match rest.iter().position(|&byte| byte == b'<' || byte == b'&') {
+                Some(index) => index,
+                None => {
+                    self.output.extend_from_slice(rest);
+                    offset += rest.len();
+                    break;
+                }
+            };
+            self.output.extend_from_slice(&rest[..index]);
+            offset += index;
+
+            let rest = &rest[index..];
+            if rest[0] == b'<' {
+                if rest.len() < CDATA_START.len() && !self.eof {
+                    break;
+                }
+
+                let (markup, len) = if rest.starts_with(b"<!--") {
+                    (Markup::Comment, 4)
+                } else if rest.starts_with(CDATA_START) {
+                    (Markup::CData, CDATA_START.len())
+                } else if rest.starts_with(b"<?") {
+                    (Markup::ProcessingInstruction, 2)
+                } else {
+                    (Markup::Content, 1)
+                };
+                self.output.extend_from_slice(&rest[..len]);
+                offset += len;
+                self.markup = markup;
+                continue;
+            }
+
+            match rest.iter().take(MAX_ENTITY_LEN + 2).position(|&byte| byte == b';') {
+                Some(end) => {
+                    let entities = &self.entities;
+                    let value = str::from_utf8(&rest[1..end])
+                        .ok()
+                        .filter(|name| !is_predefined(name))
+                        .and_then(|name| entities.get(name));
+                    match value {
+                        Some(value) => escape_into(value, &mut self.output),
+                        None => self.output.extend_from_slice(&rest[..end + 1]),
+                    }
+                    offset += end + 1;
+                }
+
+                None if rest.len() < MAX_ENTITY_LEN + 2 && !self.eof => break,
+
+                None => {
+                    self.output.push(b'&');
+                    offset += 1;
+                }
+            }
+        }
+
+        self.input_start = offset;
+    }
+}
+
+impl<R: Read> Read for DtdFilter<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if self.output_start < self.output.len() {
+                return Ok(copy_from(&self.output, &mut self.output_start, buf));
+            }
+
+            // Without any entities to expand, the rest of the document is passed straight through.
+            if self.entities.is_empty() {
+                if self.input_start < self.input.len() {
+                    return Ok(copy_from(&self.input, &mut self.input_start, buf));
+                }
+                let read = self.source.read(buf)?;
+                self.record_progress(read);
+                return Ok(read);
+            }
+
+            self.output.clear();
+            self.output_start = 0;
+            self.expand();
+
+            if self.output.is_empty() {
+                if self.eof && self.input_start == self.input.len() {
+                    return Ok(0);
+                }
+                self.fill()?;
+            }
+        }
+    }
+}
+
+/// Handles the DTD and entities of a whole document in memory, the same as reading it through a
+/// [`DtdFilter`].
+///
+/// The document is borrowed rather than copied unless it has a DTD to skip or entities to expand.
+pub fn filter_slice<'a>(source: &'a [u8], options: &ParseOptions) -> Result<Cow<'a, [u8]>> {
+    if !options.entities.is_empty() {
+        let mut filtered = Vec::with_capacity(source.len());
+        DtdFilter::new(source, options)?.read_to_end(&mut filtered).map_err(XmlError::from)?;
+        return Ok(Cow::Owned(filtered));
+    }
+
+    // The whole document counts as read up front, the same as with the `quick-xml` backend.
+    if let Some(ref progress) = options.progress {
+        progress.start();
+        progress.add_bytes(source.len());
+    }
+
+    let mut source = Cow::Borrowed(source);
+    let mut offset = 0;
+    loop {
+        let (start, len) = match next_in_prolog(&source, offset, true) {
+            PrologPart::Incomplete | PrologPart::End(_) => return Ok(source),
+            PrologPart::Markup { start, len, doctype: false } => {
+                offset = start + len;
+                continue;
+            }
+            PrologPart::Markup { start, len, doctype: true } => (start, len),
+        };
+
+        match options.dtd {
+            DtdHandling::Skip => {
+                let blanked = blank(&source[start..start + len]);
+                offset = start + blanked.len();
+                source.to_mut().splice(start..start + len, blanked);
+            }
+
+            DtdHandling::Reject => {
+                return Err(Error {
+                    position: position_at(&source, start),
+                    kind: ErrorKind::DtdNotAllowed,
+                });
+            }
+        }
+    }
+}
+
+/// The next part of a document's prolog, as found by [`next_in_prolog`].
+enum PrologPart {
+    /// More of the document needs to be read to tell what comes next.
+    Incomplete,
+
+    /// A comment, processing instruction, or DTD (if `doctype` is `true`) of `len` bytes, starting
+    /// at `start`.
+    Markup { start: usize, len: usize, doctype: bool },
+
+    /// The prolog ends at the given offset, where the root element begins.
+    End(usize),
+}
+
+/// Finds the next part of the prolog in `input`, skipping whitespace from `offset`.
+///
+/// `eof` is whether `input` holds the rest of the document.
+fn next_in_prolog(input: &[u8], offset: usize, eof: bool) -> PrologPart {
+    let whitespace = input[offset..]
+        .iter()
+        .take_while(|byte| byte.is_ascii_whitespace())
+        .count();
+    let start = offset + whitespace;
+
+    // Make sure there's enough to recognize `<!DOCTYPE` before looking at what's next.
+    let rest = &input[start..];
+    if rest.len() < DOCTYPE.len() && !eof {
+        return PrologPart::Incomplete;
+    }
+
+    let doctype = rest.starts_with(DOCTYPE);
+    let len = if rest.starts_with(b"<?") {
+        find(rest, b"?>").map(|end| end + 2)
+    } else if rest.starts_with(b"<!--") {
+        find(rest, b"-->").map(|end| end + 3)
+    } else if doctype {
+        doctype_len(rest)
+    } else {
+        // Anything else is the root element (or something the backend will reject).
+        return PrologPart::End(start);
+    };
+
+    match len {
+        Some(len) => PrologPart::Markup { start, len, doctype },
+
+        // Leave unterminated markup for the backend to report.
+        None if eof => PrologPart::End(input.len()),
+
+        None => PrologPart::Incomplete,
+    }
+}
+
+const DOCTYPE: &[u8] = b"<!DOCTYPE";
+const CDATA_START: &[u8] = b"<![CDATA[";
+
+/// Returns the index of the first occurrence of `needle` in `haystack`.
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+/// Returns the length of the `<!DOCTYPE ...>` declaration at the start of `doctype`, or `None` if
+/// it doesn't end within `doctype`.
+fn doctype_len(doctype: &[u8]) -> Option<usize> {
+    let mut quote = None;
+    let mut in_subset = false;
+    let mut index = DOCTYPE.len();
+    while index < doctype.len() {
+        let byte = doctype[index];
+        match quote {
+            Some(open) => {
+                if byte == open {
+                    quote = None;
+                }
+            }
+
+            // Comments in the internal subset may contain anything, including quotes and `>`.
+            None if doctype[index..].starts_with(b"<!--") => {
+                index += find(&doctype[index..], b"-->")? + 2;
+            }
+
+            None => match byte {
+                b'"' | b'\'' => quote = Some(byte),
+                b'[' => in_subset = true,
+                b']' => in_subset = false,
+                b'>' if !in_subset => return Some(index + 1),
+                _ => {}
+            },
+        }
+
+        index += 1;
+    }
+
+    None
+}
+
+/// Replaces every character in `text` with a space, except for line breaks.
+///
+/// Positions are reported as a line and a character offset within the line, so this keeps the
+/// positions of everything following the blanked text unchanged.
+fn blank(text: &[u8]) -> Vec<u8> {
+    text.iter()
+        .filter(|&&byte| byte & 0b1100_0000 != 0b1000_0000)
+        .map(|&byte| if byte == b'\n' { b'\n' } else { b' ' })
+        .collect()
+}
+
+/// Returns the position of the byte at `offset` in `source`, the same as the backends report it.
+fn position_at(source: &[u8], offset: usize) -> TextPosition {
+    let mut position = TextPosition::new();
+    for &byte in &source[..offset] {
+        if byte == b'\n' {
+            position.new_line();
+        } else if byte & 0b1100_0000 != 0b1000_0000 {
+            position.advance(1);
+        }
+    }
+
+    position
+}
+
+/// Returns `true` for the entities that XML itself defines, including character references.
+fn is_predefined(name: &str) -> bool {
+    match name {
+        "lt" | "gt" | "amp" | "apos" | "quot" => true,
+        _ => name.starts_with('#'),
+    }
+}
+
+/// Writes `value` to `output` escaped as XML text, so that it's never treated as markup.
+///
+/// Line breaks are written as character references so that the lines of the document, and so the
+/// positions reported in errors, aren't changed by expanding an entity.
+fn escape_into(value: &str, output: &mut Vec<u8>) {
+    for character in value.chars() {
+        match character {
+            '&' => output.extend_from_slice(b"&amp;"),
+            '<' => output.extend_from_slice(b"&lt;"),
+            '>' => output.extend_from_slice(b"&gt;"),
+            '"' => output.extend_from_slice(b"&quot;"),
+            '\'' => output.extend_from_slice(b"&apos;"),
+            '\n' => output.extend_from_slice(b"&#10;"),
+            '\r' => output.extend_from_slice(b"&#13;"),
+            _ => {
+                let mut buffer = [0; 4];
+                output.extend_from_slice(character.encode_utf8(&mut buffer).as_bytes());
+            }
+        }
+    }
+}
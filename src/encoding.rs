@@ -0,0 +1,255 @@
+//! Transcoding for documents that aren't encoded as UTF-8.
+//!
+//! Both XML backends assume that documents are UTF-8 and ignore the encoding named in the XML
+//! declaration, but files from older tools are sometimes ISO-8859-1 or UTF-16 encoded. [`Transcode`]
+//! detects a document's encoding from its byte order mark or XML declaration and decodes it to
+//! UTF-8 before it reaches the backend.
+
+use encoding_rs::{CoderResult, Decoder, Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use std::borrow::Cow;
+use std::io::{self, Read};
+
+/// The number of bytes read up front when looking for the document's encoding.
+///
+/// This only needs to be enough to cover the XML declaration.
+const SNIFF_LEN: usize = 1024;
+
+/// The number of bytes read from the source at a time when decoding.
+const CHUNK_LEN: usize = 8 * 1024;
+
+/// Reads a document, decoding it to UTF-8.
+///
+/// UTF-8 documents are passed through unchanged (minus any byte order mark), so invalid UTF-8 is
+/// still reported by the backend. For other encodings, malformed byte sequences are replaced with
+/// U+FFFD REPLACEMENT CHARACTER.
+pub struct Transcode<R> {
+    source: R,
+    state: State,
+
+    /// Bytes read from `source` that haven't been used yet, starting at `input_start`.
+    input: Vec<u8>,
+    input_start: usize,
+
+    /// Decoded bytes that haven't been returned yet, starting at `output_start`.
+    output: Vec<u8>,
+    output_start: usize,
+}
+
+enum State {
+    /// The encoding hasn't been detected yet.
+    Sniffing,
+
+    /// The document is UTF-8, so bytes are passed through as they're read.
+    Utf8,
+
+    /// The document is being decoded from another encoding.
+    Decoding { decoder: Decoder, finished: bool },
+}
+
+impl<R: Read> Transcode<R> {
+    pub fn new(source: R) -> Transcode<R> {
+        Transcode::with_buffers(source, (Vec::new(), Vec::new()))
+    }
+
+    /// Creates a `Transcode` that reuses `buffers` from a previous one.
+    ///
+    /// See [`into_parts`](#method.into_parts).
+    pub fn with_buffers(source: R, buffers: (Vec<u8>, Vec<u8>)) -> Transcode<R> {
+        let (mut input, mut output) = buffers;
+        input.clear();
+        output.clear();
+
+        Transcode {
+            source,
+            state: State::Sniffing,
+            input,
+            input_start: 0,
+            output,
+            output_start: 0,
+        }
+    }
+
+    /// Reads a source that's already known to be UTF-8, without detecting its encoding.
+    ///
+    /// This is used for text that has already been decoded, whose XML declaration may still name
+    /// the encoding it was decoded from.
+    pub fn utf8(source: R) -> Transcode<R> {
+        Transcode {
+            state: State::Utf8,
+            ..Transcode::new(source)
+        }
+    }
+
+    /// Returns the source along with the buffers used for reading it, so that the buffers can be
+    /// reused with [`with_buffers`](#method.with_buffers).
+    pub fn into_parts(self) -> (R, (Vec<u8>, Vec<u8>)) {
+        (self.source, (self.input, self.output))
+    }
+
+    /// Reads the start of the document and detects its encoding from it.
+    fn sniff(&mut self) -> io::Result<()> {
+        self.input.resize(SNIFF_LEN, 0);
+        let mut len = 0;
+        while len < SNIFF_LEN {
+            match self.source.read(&mut self.input[len..]) {
+                Ok(0) => break,
+                Ok(read) => len += read,
+                Err(ref error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+        self.input.truncate(len);
+
+        let (encoding, bom_len) = detect_encoding(&self.input);
+        self.input_start = bom_len;
+        self.state = if encoding == UTF_8 {
+            State::Utf8
+        } else {
+            State::Decoding {
+                decoder: encoding.new_decoder_without_bom_handling(),
+                finished: false,
+            }
+        };
+
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for Transcode<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if let State::Sniffing = self.state {
+            self.sniff()?;
+        }
+
+        match self.state {
+            State::Sniffing => unreachable!(),
+
+            State::Utf8 => {
+                // Finish off what was read while sniffing before reading any more of the source.
+                if self.input_start < self.input.len() {
+                    Ok(copy_from(&self.input, &mut self.input_start, buf))
+                } else {
+                    self.source.read(buf)
+                }
+            }
+
+            State::Decoding { ref mut decoder, ref mut finished } => {
+                loop {
+                    if self.output_start < self.output.len() {
+                        return Ok(copy_from(&self.output, &mut self.output_start, buf));
+                    }
+
+                    if *finished {
+                        return Ok(0);
+                    }
+
+                    let mut last = false;
+                    if self.input_start == self.input.len() {
+                        self.input.resize(CHUNK_LEN, 0);
+                        let read = match self.source.read(&mut self.input) {
+                            Ok(read) => read,
+                            Err(error) => {
+                                self.input.clear();
+                                return Err(error);
+                            }
+                        };
+                        self.input.truncate(read);
+                        self.input_start = 0;
+                        last = read == 0;
+                    }
+
+                    self.output.resize(CHUNK_LEN * 3, 0);
+                    let (result, read, written, _) = decoder.decode_to_utf8(
+                        &self.input[self.input_start..],
+                        &mut self.output,
+                        last,
+                    );
+                    self.input_start += read;
+                    self.output.truncate(written);
+                    self.output_start = 0;
+
+                    if last && result == CoderResult::InputEmpty {
+                        *finished = true;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Decodes a whole document to UTF-8, the same as reading it through [`Transcode`].
+///
+/// UTF-8 documents are borrowed rather than copied.
+pub fn decode_slice(source: &[u8]) -> Cow<'_, [u8]> {
+    let (encoding, bom_len) = detect_encoding(&source[..source.len().min(SNIFF_LEN)]);
+    let source = &source[bom_len..];
+    if encoding == UTF_8 {
+        return Cow::Borrowed(source);
+    }
+
+    let decoded = encoding.decode_without_bom_handling(source).0;
+    Cow::Owned(decoded.into_owned().into_bytes())
+}
+
+/// Copies as much of `source[*start..]` into `buf` as will fit, advancing `start` past it.
+pub fn copy_from(source: &[u8], start: &mut usize, buf: &mut [u8]) -> usize {
+    let len = buf.len().min(source.len() - *start);
+    buf[..len].copy_from_slice(&source[*start..*start + len]);
+    *start += len;
+    len
+}
+
+/// Detects the encoding of a document from its first few bytes.
+///
+/// Returns the encoding along with the length of the byte order mark, if there is one.
+fn detect_encoding(start: &[u8]) -> (&'static Encoding, usize) {
+    if let Some(detected) = Encoding::for_bom(start) {
+        return detected;
+    }
+
+    // A UTF-16 document without a byte order mark can still be recognized by the `<?` that begins
+    // its XML declaration.
+    if start.starts_with(b"<\0?\0") {
+        return (UTF_16LE, 0);
+    }
+    if start.starts_with(b"\0<\0?") {
+        return (UTF_16BE, 0);
+    }
+
+    // Everything else is ASCII-compatible, so a declaration naming UTF-16 is wrong and is ignored.
+    match declared_encoding(start) {
+        Some(encoding) if encoding != UTF_16LE && encoding != UTF_16BE => (encoding, 0),
+        _ => (UTF_8, 0),
+    }
+}
+
+/// Finds the encoding named in the document's XML declaration, if it has one that's recognized.
+fn declared_encoding(start: &[u8]) -> Option<&'static Encoding> {
+    let start = trim_start(start);
+    if !start.starts_with(b"<?xml") {
+        return None;
+    }
+
+    let end = start.windows(2).position(|window| window == b"?>")?;
+    let declaration = &start[..end];
+    let attribute = declaration.windows(8).position(|window| window == b"encoding")?;
+
+    let rest = trim_start(&declaration[attribute + 8..]);
+    if rest.first() != Some(&b'=') {
+        return None;
+    }
+
+    let rest = trim_start(&rest[1..]);
+    let quote = *rest.first()?;
+    if quote != b'"' && quote != b'\'' {
+        return None;
+    }
+
+    let len = rest[1..].iter().position(|&byte| byte == quote)?;
+    Encoding::for_label(&rest[1..len + 1])
+}
+
+fn trim_start(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|byte| !byte.is_ascii_whitespace()).unwrap_or(bytes.len());
+    &bytes[start..]
+}
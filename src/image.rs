@@ -0,0 +1,341 @@
+//! Reading the format and size of image files from their headers.
+//!
+//! Pipelines often need to check textures against a budget, e.g. a maximum size or channel count,
+//! without decoding every image in a scene. [`sniff`] reads just the header of a PNG, JPEG, DDS,
+//! TGA, or OpenEXR file and returns its [`ImageInfo`]: the format, dimensions, and number of
+//! channels.
+//!
+//! Images embedded in a document can be sniffed directly with `Image::info` from the [`v1_4`] or
+//! [`v1_5`] module. For images stored in a separate file, `Image::uri` gives the location of the
+//! file, which the application can load and pass to [`sniff`].
+//!
+//! # Examples
+//!
+//! ```
+//! use collaborate::image::{self, ImageFileFormat};
+//!
+//! // The header of a 2x1 RGBA PNG.
+//! let header = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x02\0\0\0\x01\x08\x06\0\0\0";
+//!
+//! let info = image::sniff(header).unwrap();
+//! assert_eq!(ImageFileFormat::Png, info.format);
+//! assert_eq!("image/png", info.format.mime_type());
+//! assert_eq!((2, 1), (info.width, info.height));
+//! assert_eq!(Some(4), info.channels);
+//! ```
+//!
+//! [`sniff`]: ./fn.sniff.html
+//! [`ImageInfo`]: ./struct.ImageInfo.html
+//! [`v1_4`]: ../v1_4/struct.Image.html#method.info
+//! [`v1_5`]: ../v1_5/struct.Image.html#method.info
+
+/// The format and size of an image, as read from its header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImageInfo {
+    /// The file format of the image.
+    pub format: ImageFileFormat,
+
+    /// The width of the image in pixels.
+    pub width: u32,
+
+    /// The height of the image in pixels.
+    pub height: u32,
+
+    /// The number of channels in each pixel, e.g. 4 for RGBA, if the header determines it.
+    ///
+    /// Palette images count the channels of their palette entries.
+    pub channels: Option<u8>,
+}
+
+/// An image file format that can be recognized by [`sniff`](./fn.sniff.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageFileFormat {
+    /// Portable Network Graphics.
+    Png,
+
+    /// JPEG, including JFIF and Exif files.
+    Jpeg,
+
+    /// DirectDraw Surface.
+    Dds,
+
+    /// Truevision TGA.
+    Tga,
+
+    /// OpenEXR.
+    Exr,
+}
+
+impl ImageFileFormat {
+    /// Returns the MIME type for the format, e.g. `"image/png"`.
+    ///
+    /// Formats without a registered MIME type use the `x-` type that's in common use.
+    pub fn mime_type(&self) -> &'static str {
+        match *self {
+            ImageFileFormat::Png => "image/png",
+            ImageFileFormat::Jpeg => "image/jpeg",
+            ImageFileFormat::Dds => "image/vnd-ms.dds",
+            ImageFileFormat::Tga => "image/x-tga",
+            ImageFileFormat::Exr => "image/x-exr",
+        }
+    }
+}
+
+/// Reads the format, dimensions, and channel count of an image from the start of its file.
+///
+/// `bytes` only needs to include the image's header, though for JPEG files that can be well into
+/// the file, since the size comes after any embedded metadata. TGA files have no signature, so
+/// they're only recognized if the header is consistent with a TGA image and the data isn't any of
+/// the other formats.
+///
+/// Returns `None` if the format isn't recognized, or if the header is truncated or malformed.
+pub fn sniff(bytes: &[u8]) -> Option<ImageInfo> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        sniff_png(bytes)
+    } else if bytes.starts_with(b"\xff\xd8") {
+        sniff_jpeg(bytes)
+    } else if bytes.starts_with(b"DDS ") {
+        sniff_dds(bytes)
+    } else if bytes.starts_with(b"\x76\x2f\x31\x01") {
+        sniff_exr(bytes)
+    } else {
+        sniff_tga(bytes)
+    }
+}
+
+fn sniff_png(bytes: &[u8]) -> Option<ImageInfo> {
+    // The `IHDR` chunk is always first, right after the signature.
+    if bytes.get(12..16)? != b"IHDR" {
+        return None;
+    }
+
+    let channels = match *bytes.get(25)? {
+        0 => 1,
+        2 | 3 => 3,
+        4 => 2,
+        6 => 4,
+        _ => return None,
+    };
+
+    Some(ImageInfo {
+        format: ImageFileFormat::Png,
+        width: read_u32_be(bytes, 16)?,
+        height: read_u32_be(bytes, 20)?,
+        channels: Some(channels),
+    })
+}
+
+fn sniff_jpeg(bytes: &[u8]) -> Option<ImageInfo> {
+    let mut offset = 2;
+    loop {
+        if *bytes.get(offset)? != 0xff {
+            return None;
+        }
+
+        let marker = *bytes.get(offset + 1)?;
+        match marker {
+            // Fill bytes before a marker.
+            0xff => {
+                offset += 1;
+                continue;
+            }
+
+            // Markers without a segment.
+            0x01 | 0xd0..=0xd7 => {
+                offset += 2;
+                continue;
+            }
+
+            // The start of the image data, so there was no frame header.
+            0xd9 | 0xda => return None,
+
+            _ => {}
+        }
+
+        let len = read_u16_be(bytes, offset + 2)? as usize;
+
+        // Every start of frame marker except those for DHT, JPG, and DAC.
+        if let 0xc0..=0xcf = marker {
+            if marker != 0xc4 && marker != 0xc8 && marker != 0xcc {
+                return Some(ImageInfo {
+                    format: ImageFileFormat::Jpeg,
+                    width: u32::from(read_u16_be(bytes, offset + 7)?),
+                    height: u32::from(read_u16_be(bytes, offset + 5)?),
+                    channels: Some(*bytes.get(offset + 9)?),
+                });
+            }
+        }
+
+        offset += 2 + len;
+    }
+}
+
+fn sniff_dds(bytes: &[u8]) -> Option<ImageInfo> {
+    const ALPHA_PIXELS: u32 = 0x1;
+    const ALPHA: u32 = 0x2;
+    const FOUR_CC: u32 = 0x4;
+    const RGB: u32 = 0x40;
+    const LUMINANCE: u32 = 0x20000;
+
+    // The header follows the magic number, and starts with its own size.
+    if read_u32_le(bytes, 4)? != 124 {
+        return None;
+    }
+
+    let flags = read_u32_le(bytes, 80)?;
+    let alpha = if flags & ALPHA_PIXELS != 0 { 1 } else { 0 };
+    let channels = if flags & FOUR_CC != 0 {
+        match bytes.get(84..88)? {
+            b"DXT1" => Some(3),
+            b"DXT2" | b"DXT3" | b"DXT4" | b"DXT5" => Some(4),
+            b"ATI1" | b"BC4U" | b"BC4S" => Some(1),
+            b"ATI2" | b"BC5U" | b"BC5S" => Some(2),
+            _ => None,
+        }
+    } else if flags & RGB != 0 {
+        Some(3 + alpha)
+    } else if flags & LUMINANCE != 0 {
+        Some(1 + alpha)
+    } else if flags & ALPHA != 0 {
+        Some(1)
+    } else {
+        None
+    };
+
+    Some(ImageInfo {
+        format: ImageFileFormat::Dds,
+        width: read_u32_le(bytes, 16)?,
+        height: read_u32_le(bytes, 12)?,
+        channels,
+    })
+}
+
+fn sniff_exr(bytes: &[u8]) -> Option<ImageInfo> {
+    // The header is a list of attributes following the magic number and version, each made up of
+    // a name, a type name, a size, and a value, and ended by an empty name.
+    let mut offset = 8;
+    let mut size = None;
+    let mut channels = None;
+    loop {
+        let name = read_str(bytes, offset)?;
+        if name.is_empty() {
+            break;
+        }
+        offset += name.len() + 1;
+
+        let attribute_type = read_str(bytes, offset)?;
+        offset += attribute_type.len() + 1;
+
+        let len = read_u32_le(bytes, offset)? as usize;
+        offset += 4;
+        let value = bytes.get(offset..offset + len)?;
+        offset += len;
+
+        match (name, attribute_type) {
+            (b"dataWindow", b"box2i") => {
+                let x_min = read_u32_le(value, 0)? as i32;
+                let y_min = read_u32_le(value, 4)? as i32;
+                let x_max = read_u32_le(value, 8)? as i32;
+                let y_max = read_u32_le(value, 12)? as i32;
+                let width = i64::from(x_max) - i64::from(x_min) + 1;
+                let height = i64::from(y_max) - i64::from(y_min) + 1;
+                if width <= 0 || height <= 0 {
+                    return None;
+                }
+                size = Some((width as u32, height as u32));
+            }
+
+            // Each channel is a name followed by 16 bytes describing it, and the list is ended by
+            // an empty name.
+            (b"channels", b"chlist") => {
+                let mut count = 0u8;
+                let mut channel = 0;
+                loop {
+                    let channel_name = read_str(value, channel)?;
+                    if channel_name.is_empty() {
+                        break;
+                    }
+                    channel += channel_name.len() + 1 + 16;
+                    count = count.checked_add(1)?;
+                }
+                channels = Some(count);
+            }
+
+            _ => {}
+        }
+    }
+
+    let (width, height) = size?;
+    Some(ImageInfo {
+        format: ImageFileFormat::Exr,
+        width,
+        height,
+        channels,
+    })
+}
+
+fn sniff_tga(bytes: &[u8]) -> Option<ImageInfo> {
+    let color_map_type = *bytes.get(1)?;
+    let image_type = *bytes.get(2)?;
+    let depth = *bytes.get(16)?;
+    let alpha_bits = *bytes.get(17)? & 0x0f;
+    if color_map_type > 1 {
+        return None;
+    }
+
+    let channels = match (image_type, depth) {
+        // Color mapped.
+        (1, 8) | (9, 8) if color_map_type == 1 => 3,
+
+        // True color, where 15 and 16 bit pixels may have a single bit of alpha.
+        (2, 15) | (10, 15) | (2, 16) | (10, 16) => if alpha_bits == 0 { 3 } else { 4 },
+        (2, 24) | (10, 24) => 3,
+        (2, 32) | (10, 32) => 4,
+
+        // Grayscale.
+        (3, 8) | (11, 8) => 1,
+        (3, 16) | (11, 16) => 2,
+
+        _ => return None,
+    };
+
+    let width = read_u16_le(bytes, 12)?;
+    let height = read_u16_le(bytes, 14)?;
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    Some(ImageInfo {
+        format: ImageFileFormat::Tga,
+        width: u32::from(width),
+        height: u32::from(height),
+        channels: Some(channels),
+    })
+}
+
+fn read_u16_be(bytes: &[u8], offset: usize) -> Option<u16> {
+    let bytes = bytes.get(offset..offset + 2)?;
+    Some(u16::from(bytes[0]) << 8 | u16::from(bytes[1]))
+}
+
+fn read_u16_le(bytes: &[u8], offset: usize) -> Option<u16> {
+    let bytes = bytes.get(offset..offset + 2)?;
+    Some(u16::from(bytes[1]) << 8 | u16::from(bytes[0]))
+}
+
+fn read_u32_be(bytes: &[u8], offset: usize) -> Option<u32> {
+    let bytes = bytes.get(offset..offset + 4)?;
+    Some(bytes.iter().fold(0, |value, &byte| value << 8 | u32::from(byte)))
+}
+
+fn read_u32_le(bytes: &[u8], offset: usize) -> Option<u32> {
+    let bytes = bytes.get(offset..offset + 4)?;
+    Some(bytes.iter().rev().fold(0, |value, &byte| value << 8 | u32::from(byte)))
+}
+
+/// Reads a null-terminated string, without the terminator.
+fn read_str(bytes: &[u8], offset: usize) -> Option<&[u8]> {
+    let rest = bytes.get(offset..)?;
+    let len = rest.iter().position(|&byte| byte == 0)?;
+    Some(&rest[..len])
+}
@@ -0,0 +1,230 @@
+//! Reparsing a document incrementally as it's edited.
+//!
+//! Tools like editors and inspectors change a document a little at a time, and reparsing the whole
+//! file after every keystroke gets slow for large scenes. [`IncrementalDocument`] keeps track of
+//! where each library (and each element with an `id` directly inside of a library) is in the
+//! source, the same as a [`LazyDocument`]. When part of the source is replaced, only the smallest
+//! of those elements containing the edit is reparsed and patched into the document.
+//!
+//! Incremental parsing is currently only supported for `1.4.0` and `1.4.1` documents.
+//!
+//! # Examples
+//!
+//! ```
+//! use collaborate::incremental::{IncrementalDocument, Reparsed};
+//!
+//! let source = std::fs::read_to_string("resources/blender_cube.dae").unwrap();
+//! let mut document = IncrementalDocument::from_str(&source).unwrap();
+//!
+//! // Rename the cube's mesh, which only needs the one `<geometry>` to be reparsed.
+//! let start = source.find(r#"name="Cube""#).unwrap() + 6;
+//! let reparsed = document.edit(start..start + 4, "Box").unwrap();
+//! assert_eq!(Reparsed::Geometry { library: 5, index: 0 }, reparsed);
+//! ```
+//!
+//! [`IncrementalDocument`]: ./struct.IncrementalDocument.html
+//! [`LazyDocument`]: ../lazy/struct.LazyDocument.html
+
+use {ParseOptions, Result};
+use encoding::Transcode;
+use lazy::LazyDocument;
+use std::io::Read;
+use std::ops::Range;
+use std::str;
+use v1_4::{Collada, Library};
+
+/// A parsed document along with its source, which can be edited without reparsing all of it.
+///
+/// Edits are given as byte ranges in [`source`](#method.source), which is the document decoded to
+/// UTF-8 with any DTD blanked out. For documents that were already UTF-8 the offsets are the same
+/// as in the original file.
+#[derive(Debug, Clone)]
+pub struct IncrementalDocument {
+    lazy: LazyDocument,
+    document: Collada,
+}
+
+/// The part of a document that was reparsed after an edit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reparsed {
+    /// A single `<geometry>`, at `index` in the `<library_geometries>` at `library` in the
+    /// document's libraries.
+    Geometry {
+        library: usize,
+        index: usize,
+    },
+
+    /// The library at the given index in the document's libraries.
+    Library(usize),
+
+    /// The whole document, because the edit wasn't contained within a single library, or it
+    /// changed where the libraries or their elements begin and end.
+    Document,
+}
+
+impl IncrementalDocument {
+    /// Parses a document from a string.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the document is invalid or malformed in some way, or if it isn't a
+    /// `1.4.0` or `1.4.1` document.
+    pub fn from_str(source: &str) -> Result<IncrementalDocument> {
+        IncrementalDocument::from_lazy(LazyDocument::from_source(Transcode::utf8(source.as_bytes()), ParseOptions::default())?)
+    }
+
+    /// Reads and parses a document, detecting its encoding the same as
+    /// [`Collada::read`](../v1_4/struct.Collada.html#method.read).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` for the same reasons as [`from_str`](#method.from_str).
+    pub fn from_reader<R: Read>(reader: R) -> Result<IncrementalDocument> {
+        IncrementalDocument::from_lazy(LazyDocument::from_source(Transcode::new(reader), ParseOptions::default())?)
+    }
+
+    fn from_lazy(lazy: LazyDocument) -> Result<IncrementalDocument> {
+        let document = Collada::read_utf8(&lazy.source)?;
+        Ok(IncrementalDocument { lazy, document })
+    }
+
+    /// Returns the parsed document.
+    pub fn document(&self) -> &Collada {
+        &self.document
+    }
+
+    /// Returns the parsed document, discarding the source.
+    pub fn into_document(self) -> Collada {
+        self.document
+    }
+
+    /// Returns the source of the document, including any edits.
+    pub fn source(&self) -> &str {
+        // The source is checked to be UTF-8 before it's parsed, and edits are always UTF-8.
+        str::from_utf8(&self.lazy.source).expect("Document source wasn't UTF-8")
+    }
+
+    /// Replaces the bytes of the source in `range` with `replacement`, and reparses the part of
+    /// the document affected by the edit.
+    ///
+    /// An edit contained entirely within a `<geometry>` with an `id` only reparses that geometry
+    /// (provided every geometry in its library has an `id`), and one contained entirely within a
+    /// library only reparses that library. Anything else reparses the whole document.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the edited document fails to parse. The edit is discarded in that case,
+    /// leaving both the source and the document as they were.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds, or doesn't start and end on character boundaries.
+    pub fn edit(&mut self, range: Range<usize>, replacement: &str) -> Result<Reparsed> {
+        assert!(range.start <= range.end, "Edit range starts after it ends");
+        assert!(self.source().is_char_boundary(range.start), "Edit range doesn't start on a character boundary");
+        assert!(self.source().is_char_boundary(range.end), "Edit range doesn't end on a character boundary");
+
+        let mut source = Vec::with_capacity(self.lazy.source.len() - range.len() + replacement.len());
+        source.extend_from_slice(&self.lazy.source[..range.start]);
+        source.extend_from_slice(replacement.as_bytes());
+        source.extend_from_slice(&self.lazy.source[range.end..]);
+        let edited = LazyDocument::from_filtered(source, ParseOptions::default())?;
+
+        let reparsed = self.reparsed_for(&edited, &range, replacement.len());
+        match reparsed {
+            Reparsed::Geometry { library, index } => {
+                let element = edited.elements.iter()
+                    .filter(|element| element.library == library && element.name == "geometry")
+                    .nth(index)
+                    .expect("Geometry span went missing");
+                let geometry = edited.parse_element(element)?;
+                match self.document.libraries[library] {
+                    Library::Geometries(ref mut geometries) => geometries.geometries[index] = geometry,
+                    _ => unreachable!("Geometry wasn't in a geometry library"),
+                }
+            }
+
+            Reparsed::Library(index) => {
+                if let Some(library) = edited.parse_library(&edited.libraries[index])? {
+                    self.document.libraries[index] = library;
+                }
+            }
+
+            Reparsed::Document => {
+                self.document = Collada::read_utf8(&edited.source)?;
+            }
+        }
+
+        self.lazy = edited;
+        Ok(reparsed)
+    }
+
+    /// Works out how much of the document needs to be reparsed for an edit replacing `range` with
+    /// `inserted` bytes, which resulted in `edited`.
+    fn reparsed_for(&self, edited: &LazyDocument, range: &Range<usize>, inserted: usize) -> Reparsed {
+        // Nothing outside of the edit has changed, so if every library and element is exactly
+        // where it's expected to be after the edit then only the one containing the edit needs
+        // to be reparsed. Otherwise the edit changed the structure of the document.
+        let moved = |span: &Range<usize>| moved(span, range, inserted);
+        let libraries_match = self.lazy.libraries.len() == edited.libraries.len()
+            && self.lazy.libraries.iter().zip(&edited.libraries).all(|(old, new)| {
+                old.name == new.name
+                    && moved(&old.range) == Some(new.range.clone())
+                    && moved(&old.start_tag) == Some(new.start_tag.clone())
+            });
+        let elements_match = self.lazy.elements.len() == edited.elements.len()
+            && self.lazy.elements.iter().zip(&edited.elements).all(|(old, new)| {
+                old.name == new.name
+                    && old.library == new.library
+                    && moved(&old.range) == Some(new.range.clone())
+            });
+        if !libraries_match || !elements_match {
+            return Reparsed::Document;
+        }
+
+        let library = match self.lazy.libraries.iter().position(|library| contains(&library.range, range)) {
+            Some(library) => library,
+            None => { return Reparsed::Document; }
+        };
+
+        let element = self.lazy.elements.iter()
+            .find(|element| element.name == "geometry" && contains(&element.range, range));
+        let geometries = match self.document.libraries[library] {
+            Library::Geometries(ref library) => &library.geometries,
+            _ => { return Reparsed::Library(library); }
+        };
+        if let Some(element) = element {
+            // Geometries are matched up with their spans by position, which only works if every
+            // geometry in the library has a span.
+            let spans = self.lazy.elements.iter()
+                .filter(|element| element.library == library && element.name == "geometry");
+            if spans.clone().count() == geometries.len() {
+                let index = spans.take_while(|span| span.range != element.range).count();
+                return Reparsed::Geometry { library, index };
+            }
+        }
+
+        Reparsed::Library(library)
+    }
+}
+
+/// Returns `true` if `edit` is strictly inside of `span`, i.e. it doesn't touch the first or last
+/// byte of the element.
+fn contains(span: &Range<usize>, edit: &Range<usize>) -> bool {
+    span.start < edit.start && edit.end < span.end
+}
+
+/// Returns where `span` should be after replacing `edit` with `inserted` bytes, or `None` if the
+/// edit overlaps the start or end of the span.
+fn moved(span: &Range<usize>, edit: &Range<usize>, inserted: usize) -> Option<Range<usize>> {
+    let shift = |offset: usize| offset - edit.len() + inserted;
+    if span.end <= edit.start {
+        Some(span.clone())
+    } else if span.start >= edit.end {
+        Some(shift(span.start)..shift(span.end))
+    } else if contains(span, edit) {
+        Some(span.start..shift(span.end))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,597 @@
+//! On-demand parsing of a document's libraries.
+//!
+//! Most of the data in a COLLADA document lives in its libraries, and many tools only need a few
+//! of them (e.g. a scene viewer might not care about physics data). [`LazyDocument`] quickly
+//! skims the document to find where each library is, without parsing any of them, and then only
+//! parses a library when it's requested.
+//!
+//! The skim also indexes the elements directly inside each library that have an `id`, so that a
+//! single element (e.g. one `<geometry>`) can be parsed by id without parsing the rest of its
+//! library. This suits tools like editors that only ever look at a few elements of a large file.
+//!
+//! Lazy loading is currently only supported for `1.4.0` and `1.4.1` documents.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::fs::File;
+//! use collaborate::lazy::LazyDocument;
+//!
+//! let file = File::open("resources/blender_cube.dae").unwrap();
+//! let document = LazyDocument::from_reader(file).unwrap();
+//!
+//! // Only `<library_geometries>` gets parsed.
+//! for library in document.geometries().unwrap() {
+//!     println!("Geometries: {:?}", library.geometries);
+//! }
+//! ```
+//!
+//!
+//! Parsing a single geometry by id:
+//!
+//! ```
+//! use collaborate::lazy::LazyDocument;
+//!
+//! let document = LazyDocument::open("resources/blender_cube.dae").unwrap();
+//! let cube = document.geometry("Cube-mesh").unwrap().unwrap();
+//! assert_eq!(Some("Cube"), cube.name.as_deref());
+//! ```
+//!
+//! [`LazyDocument`]: ./struct.LazyDocument.html
+
+use {Error, ParseOptions, Result, Version, XmlError};
+use dtd::DtdFilter;
+use encoding::Transcode;
+use std::fs::File;
+use std::io::{self, Read};
+use std::ops::Range;
+use std::path::Path;
+use stream::DocumentReader as StreamReader;
+use backend::XmlPull;
+use utils::{self, ColladaElement, ElementStart, ParseContext};
+use v1_4::{Asset, Geometry, Library, LibraryGeometries, LibraryVisualScenes};
+#[cfg(feature = "rayon")]
+use {parallel, MultiError};
+#[cfg(feature = "rayon")]
+use v1_4::Collada;
+use xml::common::TextPosition;
+use xml::reader::EventReader;
+
+/// A document whose libraries are parsed on demand.
+///
+/// Loading a `LazyDocument` parses the document's `<asset>` and records the location of each
+/// library. Libraries are parsed each time they're requested, so callers that access the same
+/// library repeatedly should hold on to the result.
+///
+/// Because libraries aren't parsed until they're requested, errors within a library are only
+/// reported when that library is accessed. Libraries are parsed with the options the document was
+/// loaded with, but there's no [`ParseReport`] for them, so any warnings are discarded.
+///
+/// [`ParseReport`]: ../report/struct.ParseReport.html
+#[derive(Debug, Clone)]
+pub struct LazyDocument {
+    pub(crate) source: Vec<u8>,
+    options: ParseOptions,
+    version: Version,
+    asset: Asset,
+    root_tag: Range<usize>,
+    pub(crate) libraries: Vec<LibrarySpan>,
+    pub(crate) elements: Vec<ElementSpan>,
+}
+
+/// The location of a single library within the document source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LibrarySpan {
+    pub(crate) name: String,
+    pub(crate) range: Range<usize>,
+
+    /// The library's start tag.
+    pub(crate) start_tag: Range<usize>,
+}
+
+/// The location of an element with an `id` directly inside of a library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ElementSpan {
+    pub(crate) name: String,
+    pub(crate) id: String,
+    pub(crate) range: Range<usize>,
+
+    /// The index of the library containing the element.
+    pub(crate) library: usize,
+}
+
+impl LazyDocument {
+    /// Reads a document and indexes its libraries.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the document doesn't begin with a valid `<COLLADA>` element and `<asset>`,
+    /// if the document isn't a `1.4.0` or `1.4.1` document, or if its elements aren't properly
+    /// nested (e.g. if the document is truncated). Errors within the libraries aren't reported
+    /// until the library is accessed.
+    pub fn from_reader<R: Read>(reader: R) -> Result<LazyDocument> {
+        LazyDocument::from_reader_with(reader, ParseOptions::default())
+    }
+
+    /// Reads a document and indexes its libraries, parsing it with `options`.
+    ///
+    /// The options are used for the `<asset>` and for every library or element that's parsed
+    /// later on.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` for the same reasons as [`from_reader`](#method.from_reader).
+    pub fn from_reader_with<R: Read>(reader: R, options: ParseOptions) -> Result<LazyDocument> {
+        LazyDocument::from_source(Transcode::new(reader), options)
+    }
+
+    /// Reads and indexes a document that's being decoded by `reader`.
+    pub(crate) fn from_source<R: Read>(reader: Transcode<R>, options: ParseOptions) -> Result<LazyDocument> {
+        // The DTD is blanked out up front, since the skim below doesn't understand them.
+        let mut source = Vec::new();
+        DtdFilter::new(reader, &options)?
+            .read_to_end(&mut source)
+            .map_err(XmlError::from)?;
+        LazyDocument::from_filtered(source, options)
+    }
+
+    /// Indexes a document that has already been decoded to UTF-8 and had its DTD blanked out.
+    pub(crate) fn from_filtered(source: Vec<u8>, options: ParseOptions) -> Result<LazyDocument> {
+        let (version, asset) = {
+            let stream = StreamReader::from_utf8(&*source, options.clone())?;
+            (stream.version(), stream.asset().clone())
+        };
+
+        let skim = skim(&source, Library::name_test)?;
+        Ok(LazyDocument {
+            options,
+            version,
+            asset,
+            root_tag: skim.root_tag,
+            libraries: skim.libraries,
+            elements: skim.elements,
+            source,
+        })
+    }
+
+    /// Opens the document at `path` and indexes its libraries.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the file can't be read, or for the same reasons as
+    /// [`from_reader`](#method.from_reader).
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<LazyDocument> {
+        let file = File::open(path).map_err(XmlError::from)?;
+        LazyDocument::from_reader(file)
+    }
+
+    /// Returns the version of the specification used by the document.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Returns the document's `<asset>`.
+    pub fn asset(&self) -> &Asset {
+        &self.asset
+    }
+
+    /// Returns an iterator over the element names of the document's libraries, in document order.
+    pub fn library_names<'a>(&'a self) -> Box<Iterator<Item = &'a str> + 'a> {
+        Box::new(self.libraries.iter().map(|library| &*library.name))
+    }
+
+    /// Returns an iterator over the ids of the elements directly inside of the document's
+    /// libraries, in document order.
+    pub fn element_ids<'a>(&'a self) -> Box<Iterator<Item = &'a str> + 'a> {
+        Box::new(self.elements.iter().map(|element| &*element.id))
+    }
+
+    /// Parses all of the libraries in the document.
+    pub fn libraries(&self) -> Result<Vec<Library>> {
+        self.parse_libraries(self.libraries.iter())
+    }
+
+    /// Parses all of the libraries with the element name `name` (e.g. `"library_geometries"`).
+    pub fn libraries_named(&self, name: &str) -> Result<Vec<Library>> {
+        self.parse_libraries(self.libraries.iter().filter(|library| library.name == name))
+    }
+
+    /// Parses all of the `<library_geometries>` elements in the document.
+    pub fn geometries(&self) -> Result<Vec<LibraryGeometries>> {
+        let libraries = self.libraries_named("library_geometries")?;
+        Ok(libraries.into_iter()
+            .filter_map(|library| match library {
+                Library::Geometries(library) => Some(library),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Parses all of the `<library_visual_scenes>` elements in the document.
+    pub fn visual_scenes(&self) -> Result<Vec<LibraryVisualScenes>> {
+        let libraries = self.libraries_named("library_visual_scenes")?;
+        Ok(libraries.into_iter()
+            .filter_map(|library| match library {
+                Library::VisualScenes(library) => Some(library),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Parses the `<geometry>` with the given id, if the document has one.
+    ///
+    /// Only the geometry itself is parsed, not the rest of its library.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the geometry fails to parse.
+    pub fn geometry(&self, id: &str) -> Result<Option<Geometry>> {
+        self.parse_element_with_id("geometry", id)
+    }
+
+    /// Parses the entire document, parsing the libraries in parallel.
+    ///
+    /// Each library is parsed on a separate [rayon] task, and the results are assembled into a
+    /// complete [`Collada`] in document order. The rest of the document (everything other than the
+    /// libraries) is parsed alongside them. This is the same as parsing the document with
+    /// [`ParseOptions::parallel`] set, except that the errors from every library that fails to
+    /// parse are returned, rather than just the first one.
+    ///
+    /// Only available with the `rayon` feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` with the errors from every part of the document that failed to parse, in
+    /// document order. The first error is the same one that would be returned when parsing the
+    /// document sequentially.
+    ///
+    /// [rayon]: https://docs.rs/rayon
+    /// [`Collada`]: ../v1_4/struct.Collada.html
+    /// [`ParseOptions::parallel`]: ../options/struct.ParseOptions.html#structfield.parallel
+    #[cfg(feature = "rayon")]
+    pub fn parse_parallel(&self) -> ::std::result::Result<Collada, MultiError> {
+        let (document, report) = parallel::parse::<Collada>(&self.source, self.options.clone())?;
+        let mut errors = report.errors;
+        match document {
+            Ok(document) if errors.is_empty() => Ok(document),
+            Ok(_) => Err(MultiError { errors }),
+            Err(error) => {
+                errors.insert(0, error);
+                Err(MultiError { errors })
+            }
+        }
+    }
+
+    /// Parses each of `libraries`, leaving out any from other versions of the specification that
+    /// were skipped.
+    fn parse_libraries<'a, I>(&self, libraries: I) -> Result<Vec<Library>>
+    where
+        I: Iterator<Item = &'a LibrarySpan>,
+    {
+        let mut parsed = Vec::new();
+        for library in libraries {
+            parsed.extend(self.parse_library(library)?);
+        }
+        Ok(parsed)
+    }
+
+    /// Parses a single library, returning `None` if it's from a different version of the
+    /// specification and was skipped.
+    pub(crate) fn parse_library(&self, library: &LibrarySpan) -> Result<Option<Library>> {
+        // The library is parsed as a standalone document consisting of the original root tag (so
+        // that namespace declarations still apply) and the library itself. Everything between the
+        // two is replaced with blank space, so that positions in errors still match the original
+        // document.
+        let source = (&self.source[..self.root_tag.end])
+            .chain(Blank::new(&self.source[self.root_tag.end..library.range.start]))
+            .chain(&self.source[library.range.clone()])
+            .chain(&b"</COLLADA>"[..]);
+
+        let mut events = EventReader::new_with_config(source, utils::PARSER_CONFIG.clone());
+        utils::get_document_start(&mut events)?;
+
+        let mut reader = self.reader(events);
+        let element_start = next_element(&mut reader, "COLLADA")?;
+        utils::parse_group_member(&mut reader, "COLLADA", element_start)
+    }
+
+    fn parse_element_with_id<T: ColladaElement>(&self, name: &str, id: &str) -> Result<Option<T>> {
+        match self.elements.iter().find(|element| element.id == id && element.name == name) {
+            Some(element) => self.parse_element(element).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) fn parse_element<T: ColladaElement>(&self, element: &ElementSpan) -> Result<T> {
+        let library = &self.libraries[element.library];
+
+        // Like with `parse_library`, the element is parsed as a standalone document, but this
+        // time it's nested in its library's start tag too. Parsing stops at the end of the
+        // element, so the enclosing tags never need to be closed.
+        let source = (&self.source[..self.root_tag.end])
+            .chain(Blank::new(&self.source[self.root_tag.end..library.start_tag.start]))
+            .chain(&self.source[library.start_tag.clone()])
+            .chain(Blank::new(&self.source[library.start_tag.end..element.range.start]))
+            .chain(&self.source[element.range.clone()]);
+
+        let mut events = EventReader::new_with_config(source, utils::PARSER_CONFIG.clone());
+        utils::get_document_start(&mut events)?;
+
+        let mut reader = self.reader(events);
+        next_element(&mut reader, "COLLADA")?;
+        let element_start = next_element(&mut reader, "library")?;
+        T::parse_element(&mut reader, element_start)
+    }
+
+    /// Returns a reader for parsing part of the document with the document's options.
+    fn reader<R: XmlPull>(&self, events: R) -> utils::DocumentReader<R> {
+        utils::DocumentReader::new(events, ParseContext::with_options(self.version, self.options.clone()))
+    }
+}
+
+/// Reads the start of the next child of `parent`, which the skim found to be there.
+fn next_element<R: XmlPull>(reader: &mut utils::DocumentReader<R>, parent: &'static str) -> Result<ElementStart> {
+    match utils::start_element(reader, parent)? {
+        Some(element_start) => Ok(element_start),
+        None => {
+            let error = XmlError::from((&*reader, "Expected an element"));
+            Err(error.into())
+        }
+    }
+}
+
+/// Reads the source text with every character replaced by a space, except for line breaks.
+///
+/// Positions are reported as a line and a character offset within the line, so this keeps the
+/// positions of everything following the blanked text unchanged.
+struct Blank<'a> {
+    source: &'a [u8],
+}
+
+impl<'a> Blank<'a> {
+    fn new(source: &'a [u8]) -> Blank<'a> {
+        Blank { source }
+    }
+}
+
+impl<'a> Read for Blank<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut written = 0;
+        while written < buf.len() && !self.source.is_empty() {
+            let byte = self.source[0];
+            self.source = &self.source[1..];
+
+            // Skip UTF-8 continuation bytes so that each character becomes a single space.
+            if byte & 0b1100_0000 == 0b1000_0000 {
+                continue;
+            }
+
+            buf[written] = if byte == b'\n' { b'\n' } else { b' ' };
+            written += 1;
+        }
+
+        Ok(written)
+    }
+}
+
+/// The locations found by skimming a document.
+pub(crate) struct Skim {
+    pub(crate) root_tag: Range<usize>,
+    pub(crate) libraries: Vec<LibrarySpan>,
+    pub(crate) elements: Vec<ElementSpan>,
+
+    /// The names of the root element's children, in document order.
+    #[cfg(feature = "rayon")]
+    pub(crate) children: Vec<String>,
+}
+
+/// Finds the root tag, the libraries, and the elements with ids inside the libraries in the
+/// document without fully parsing it.
+///
+/// This only tracks enough of the XML syntax to find where elements start and end. Other than
+/// checking that start and end tags balance out, it doesn't validate anything. The document has
+/// already been partially validated by the time this is run, and any errors within a library will
+/// be reported when the library is parsed.
+///
+/// `is_library` tests whether the name of a child of the root element is a library.
+///
+/// # Errors
+///
+/// Returns `Err` if an end tag doesn't have a matching start tag, if there's more than one root
+/// element, or if the document ends before the root element is closed.
+pub(crate) fn skim(source: &[u8], is_library: fn(&str) -> bool) -> Result<Skim> {
+    let mut root_tag = 0..0;
+    let mut libraries = Vec::new();
+    let mut elements = Vec::new();
+    #[cfg(feature = "rayon")]
+    let mut children = Vec::new();
+
+    // The library and the element within it currently being skimmed, if any.
+    let mut current: Option<LibrarySpan> = None;
+    let mut current_element: Option<ElementSpan> = None;
+    let mut depth: usize = 0;
+    let mut found_root = false;
+    let mut index = 0;
+
+    while let Some(offset) = find(&source[index..], b"<") {
+        let start = index + offset;
+        let rest = &source[start..];
+
+        // Skip over everything that isn't an element, none of it affects nesting.
+        let skip_to = if rest.starts_with(b"<!--") {
+            Some(b"-->" as &[u8])
+        } else if rest.starts_with(b"<![CDATA[") {
+            Some(b"]]>" as &[u8])
+        } else if rest.starts_with(b"<?") {
+            Some(b"?>" as &[u8])
+        } else if rest.starts_with(b"<!") {
+            Some(b">" as &[u8])
+        } else {
+            None
+        };
+
+        if let Some(terminator) = skip_to {
+            index = match find(rest, terminator) {
+                Some(end) => start + end + terminator.len(),
+                None => return Err(malformed(source, source.len(), "Unexpected end of document")),
+            };
+            continue;
+        }
+
+        let end = match find_tag_end(rest) {
+            Some(end) => start + end,
+            None => return Err(malformed(source, source.len(), "Unexpected end of document")),
+        };
+        index = end;
+
+        if rest.starts_with(b"</") {
+            if depth == 0 {
+                return Err(malformed(source, start, "Unexpected end tag without a matching start tag"));
+            }
+
+            depth -= 1;
+            if depth == 1 {
+                if let Some(mut library) = current.take() {
+                    library.range.end = end;
+                    libraries.push(library);
+                }
+            } else if depth == 2 {
+                if let Some(mut element) = current_element.take() {
+                    element.range.end = end;
+                    elements.push(element);
+                }
+            }
+            continue;
+        }
+
+        let is_empty = source[end - 2] == b'/';
+        if depth == 0 {
+            if found_root {
+                return Err(malformed(source, start, "Unexpected element after the root element"));
+            }
+
+            found_root = true;
+            root_tag = start..end;
+        } else if depth == 1 {
+            // Anything that isn't a library is left for the rest of the document to report.
+            let name = tag_name(&rest[1..]);
+            #[cfg(feature = "rayon")]
+            children.push(name.clone());
+            if is_library(&name) {
+                let library = LibrarySpan { name, range: start..end, start_tag: start..end };
+                if is_empty {
+                    libraries.push(library);
+                } else {
+                    current = Some(library);
+                }
+            }
+        } else if depth == 2 && current.is_some() {
+            if let Some(id) = tag_attribute(rest, "id") {
+                let element = ElementSpan {
+                    name: tag_name(&rest[1..]),
+                    id,
+                    range: start..end,
+                    library: libraries.len(),
+                };
+                if is_empty {
+                    elements.push(element);
+                } else {
+                    current_element = Some(element);
+                }
+            }
+        }
+
+        if !is_empty {
+            depth += 1;
+        }
+    }
+
+    if !found_root || depth != 0 {
+        return Err(malformed(source, source.len(), "Unexpected end of document"));
+    }
+
+    Ok(Skim {
+        root_tag,
+        libraries,
+        elements,
+        #[cfg(feature = "rayon")]
+        children,
+    })
+}
+
+/// Returns an error for malformed XML at `offset` in `source`.
+///
+/// The position is the line and character offset within the line, the same as the parser would
+/// report it.
+fn malformed(source: &[u8], offset: usize, message: &'static str) -> Error {
+    let before = &source[..offset];
+    let line_start = before.iter().rposition(|&byte| byte == b'\n').map_or(0, |index| index + 1);
+    let position = TextPosition {
+        row: before.iter().filter(|&&byte| byte == b'\n').count() as u64,
+
+        // UTF-8 continuation bytes are skipped so that each character is counted once.
+        column: before[line_start..].iter().filter(|&&byte| byte & 0b1100_0000 != 0b1000_0000).count() as u64,
+    };
+    XmlError::from((&position, message)).into()
+}
+
+/// Finds the end of the tag starting at the beginning of `source`, taking care to ignore any `>`
+/// characters inside of quoted attribute values.
+fn find_tag_end(source: &[u8]) -> Option<usize> {
+    let mut quote = None;
+    for (index, &byte) in source.iter().enumerate() {
+        match quote {
+            Some(quote_byte) => if byte == quote_byte { quote = None; },
+            None => match byte {
+                b'"' | b'\'' => { quote = Some(byte); }
+                b'>' => { return Some(index + 1); }
+                _ => {}
+            },
+        }
+    }
+
+    None
+}
+
+/// Returns the local name of the tag at the beginning of `source`.
+fn tag_name(source: &[u8]) -> String {
+    let len = source.iter()
+        .position(|&byte| byte.is_ascii_whitespace() || byte == b'/' || byte == b'>')
+        .unwrap_or(source.len());
+    let name = String::from_utf8_lossy(&source[..len]);
+    match name.rfind(':') {
+        Some(colon) => name[colon + 1..].into(),
+        None => name.into_owned(),
+    }
+}
+
+/// Returns the value of the attribute `name` in the tag at the beginning of `source`, if it has
+/// one. Entities in the value aren't expanded.
+fn tag_attribute(source: &[u8], name: &str) -> Option<String> {
+    let tag = &source[..find_tag_end(source)?];
+    let name = name.as_bytes();
+
+    // Skip over the tag name, then look at each `name="value"` pair in turn.
+    let mut index = tag.iter().position(u8::is_ascii_whitespace)?;
+    loop {
+        while index < tag.len() && tag[index].is_ascii_whitespace() {
+            index += 1;
+        }
+
+        let name_end = index + tag[index..].iter().position(|&byte| byte == b'=')?;
+        let attribute_name = tag[index..name_end].trim_ascii_end();
+        let quote_start = name_end + 1 + tag[name_end + 1..].iter().position(|&byte| byte == b'"' || byte == b'\'')?;
+        let quote = tag[quote_start];
+        let value_end = quote_start + 1 + tag[quote_start + 1..].iter().position(|&byte| byte == quote)?;
+
+        if attribute_name == name {
+            return Some(String::from_utf8_lossy(&tag[quote_start + 1..value_end]).into_owned());
+        }
+
+        index = value_end + 1;
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
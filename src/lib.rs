@@ -68,6 +68,10 @@
 //! COLLABORATE makes no effort to unify incompatible versions of the specification, so users of
 //! COLLABORATE will have to handle both versions separately if they wish to do so.
 //!
+//! Elements and attributes that only exist in one version of the specification are rejected from
+//! documents declaring the other with an [`ErrorKind::VersionMismatch`] error, e.g. a `1.4.1`
+//! document containing a `<library_kinematics_models>`.
+//!
 //! # 3rd Party Extensions
 //!
 //! The COLLADA format allows for semi-arbitrary extensions to the standard, allowing applications
@@ -76,11 +80,34 @@
 //! in the document. This library attempts to directly support common 3rd party extensions,
 //! primarily those for Blender and Maya. In the case that the 3rd party extension is not
 //! directly supported, the underlying XML will be preserved so that the client code can attempt
-//! to still use the data.
+//! to still use the data. The [`raw`] module can turn that XML into a simple tree of elements,
+//! and the [`profiles`] module decodes the extensions written by several popular exporters.
+//!
+//! # XML Backends
+//!
+//! Documents are parsed with [xml-rs] by default. Enabling the `quick-xml` cargo feature switches
+//! `read` and `from_str` over to a backend built on [quick-xml], which is considerably faster for
+//! large documents. Both backends produce the same documents and report errors at the same
+//! positions, though the `quick-xml` backend reads the whole document into memory before parsing
+//! it. The [`stream`] module always uses xml-rs, so that memory use stays bounded.
+//!
+//! Enabling the `rayon` cargo feature adds [`ParseOptions::parallel`], which parses each of the
+//! document's libraries in parallel and can greatly reduce load times for large scenes.
+//!
+//! Enabling the `mathml` cargo feature adds the [`mathml`] module, which interprets the MathML
+//! expressions used by `1.5.0` kinematics formulas.
 //!
 //! [COLLADA]: https://www.khronos.org/collada/
 //! [FBX]: https://en.wikipedia.org/wiki/FBX
+//! [xml-rs]: https://crates.io/crates/xml-rs
+//! [quick-xml]: https://crates.io/crates/quick-xml
+//! [`stream`]: ./stream/index.html
+//! [`raw`]: ./raw/index.html
+//! [`profiles`]: ./profiles/index.html
+//! [`mathml`]: ./mathml/index.html
+//! [`ParseOptions::parallel`]: ./options/struct.ParseOptions.html#structfield.parallel
 //! [`VersionedDocument`]: ./enum.VersionedDocument.html
+//! [`ErrorKind::VersionMismatch`]: ./enum.ErrorKind.html#variant.VersionMismatch
 //! [`VersionedDocument::read`]: ./enum.VersionedDocument.html#method.read
 //! [`v1_4`]: ./v1_4/index.html
 //! [`v1_5`]: ./v1_5/index.html
@@ -90,23 +117,66 @@
 pub extern crate chrono;
 #[macro_use]
 extern crate collaborate_derive;
+extern crate encoding_rs;
+extern crate fast_float;
 extern crate xml;
 
+#[cfg(feature = "futures-io")]
+extern crate futures_io;
+#[cfg(feature = "quick-xml")]
+extern crate quick_xml;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+
+pub use backend::XmlPull;
+pub use builder::DocumentBuilder;
+pub use coverage::schema_coverage;
+pub use options::{DtdHandling, ParseOptions, Strictness};
+pub use parser::Parser;
+pub use progress::{Progress, ProgressCallback};
+pub use report::{ParseReport, SourceSpan, Warning};
+pub use utils::skip_element;
 pub use xml::common::TextPosition;
 pub use xml::reader::{Error as XmlError, XmlEvent};
 
-use common::UriFragmentParseError;
+use common::{HexBinaryParseError, UriFragmentParseError};
 use std::fmt::{self, Display, Formatter};
-use std::io::Read;
+use std::io::{Read, Write};
 use std::num::{ParseFloatError, ParseIntError};
-use utils::{ColladaElement, StringListDisplay};
+use std::str::{FromStr, ParseBoolError};
+use utils::{ColladaElement, DocumentReader, ParseContext, StringListDisplay};
+use writer::WriteOptions;
 use xml::common::Position;
 use xml::reader::EventReader;
 
+#[cfg(feature = "futures-io")]
+pub mod aio;
+pub mod builder;
 pub mod common;
+pub mod coverage;
+pub mod image;
+pub mod incremental;
+pub mod lazy;
+#[cfg(feature = "mathml")]
+pub mod mathml;
+pub mod options;
+pub mod parser;
+pub mod preserve;
+pub mod profiles;
+pub mod progress;
+pub mod raw;
+pub mod report;
+pub mod shared;
+pub mod stream;
 pub mod v1_4;
 pub mod v1_5;
+pub mod writer;
 
+mod backend;
+mod dtd;
+mod encoding;
+#[cfg(feature = "rayon")]
+mod parallel;
 mod utils;
 
 /// A helper type for parsing documents without knowing the version ahead of time.
@@ -181,8 +251,34 @@ impl VersionedDocument {
     /// COLLADA versions, 3rd party extensions, and any other details that could influence how
     /// a document is parsed see the [crate-level documentation](./index.html).
     pub fn from_str(source: &str) -> Result<VersionedDocument> {
-        let reader = EventReader::new_with_config(source.as_bytes(), utils::PARSER_CONFIG.clone());
-        Self::parse(reader)
+        Self::from_str_with_report(source).map(|(document, _)| document)
+    }
+
+    /// Read a COLLADA document from a byte slice.
+    ///
+    /// The document's encoding is detected the same as with [`read`](#method.read), but since the
+    /// whole document is already in memory it's parsed directly from the slice rather than through
+    /// `io::Read`, and is only copied if it needs to be decoded. This is useful when documents
+    /// arrive as a buffer, e.g. from a network request or when running in WebAssembly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![allow(unused_variables)]
+    /// use collaborate::VersionedDocument;
+    ///
+    /// let bytes = std::fs::read("resources/blender_cube.dae").unwrap();
+    /// let document = VersionedDocument::from_bytes(&bytes).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the document is invalid or malformed in some way, the same as
+    /// [`read`](#method.read).
+    pub fn from_bytes(source: &[u8]) -> Result<VersionedDocument> {
+        backend::parse_bytes(source, ParseOptions::default(), |reader, options| {
+            Self::parse_with_report(reader, options)
+        }).map(|(document, _)| document)
     }
 
     /// Attempts to parse the contents of a COLLADA document.
@@ -214,42 +310,175 @@ impl VersionedDocument {
     ///
     /// [crate]: index.html
     pub fn read<R: Read>(reader: R) -> Result<VersionedDocument> {
-        let reader = EventReader::new_with_config(reader, utils::PARSER_CONFIG.clone());
-        Self::parse(reader)
+        let options = ParseOptions::default();
+        let reader = backend::open_encoded(reader, &options)?;
+        Self::parse_with_report(reader, options).map(|(document, _)| document)
+    }
+
+    /// Read a COLLADA document from a string, reporting any workarounds needed to parse it.
+    ///
+    /// See the [`report`](./report/index.html) module for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the document is invalid or malformed in some way, the same as
+    /// [`from_str`](#method.from_str).
+    pub fn from_str_with_report(source: &str) -> Result<(VersionedDocument, ParseReport)> {
+        backend::parse_utf8(source.as_bytes(), ParseOptions::default(), |reader, options| {
+            Self::parse_with_report(reader, options)
+        })
+    }
+
+    /// Attempts to parse the contents of a COLLADA document, reporting any workarounds needed to
+    /// parse it.
+    ///
+    /// See the [`report`](./report/index.html) module for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the document is invalid or malformed in some way, the same as
+    /// [`read`](#method.read).
+    pub fn read_with_report<R: Read>(reader: R) -> Result<(VersionedDocument, ParseReport)> {
+        let options = ParseOptions::default();
+        let reader = backend::open_encoded(reader, &options)?;
+        Self::parse_with_report(reader, options)
+    }
+
+    /// Attempts to parse the contents of a COLLADA document using the given options.
+    ///
+    /// Returns the parsed document along with a report of any workarounds that were needed to
+    /// parse it, including any problems that were tolerated because of `options.strictness`. See
+    /// the [`options`](./options/index.html) module for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the document is invalid or malformed in a way that `options` doesn't
+    /// allow, or if it exceeds one of the limits set in `options`.
+    pub fn read_with<R: Read>(reader: R, options: ParseOptions) -> Result<(VersionedDocument, ParseReport)> {
+        #[cfg(feature = "rayon")]
+        {
+            if options.parallel {
+                let source = parallel::read_source(reader, &options)?;
+                let (document, report) = match parallel::version(&source)? {
+                    Version::V1_4_0 | Version::V1_4_1 => {
+                        let (document, report) = parallel::parse::<v1_4::Collada>(&source, options)?;
+                        (document.map(Into::into), report)
+                    }
+
+                    Version::V1_5_0 => {
+                        let (document, report) = parallel::parse::<v1_5::Collada>(&source, options)?;
+                        (document.map(Into::into), report)
+                    }
+                };
+                return Ok((document?, report));
+            }
+        }
+
+        let reader = backend::open_encoded(reader, &options)?;
+        Self::parse_with_report(reader, options)
+    }
+
+    /// Attempts to parse the contents of a COLLADA document, collecting any recoverable problems
+    /// as warnings instead of failing.
+    ///
+    /// The document is parsed with [`Strictness::Lenient`], and the resulting report is flattened
+    /// into a list of [`Warning`]s. Use [`read_with`](#method.read_with) for more control over how
+    /// the document is parsed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the document is invalid or malformed in a way that can't be recovered
+    /// from.
+    ///
+    /// [`Strictness::Lenient`]: ./options/enum.Strictness.html#variant.Lenient
+    /// [`Warning`]: ./report/enum.Warning.html
+    pub fn read_with_warnings<R: Read>(reader: R) -> Result<(VersionedDocument, Vec<Warning>)> {
+        let options = ParseOptions {
+            strictness: Strictness::Lenient,
+            ..ParseOptions::default()
+        };
+        let (document, report) = Self::read_with(reader, options)?;
+        Ok((document, report.into_warnings()))
+    }
+
+    /// Attempts to parse the contents of a COLLADA document, collecting every error found instead
+    /// of stopping at the first one.
+    ///
+    /// The document is parsed with [`ParseOptions::recover`] set, so elements that fail to parse
+    /// are skipped and parsing continues with the rest of the document. This is useful for
+    /// triaging broken exports, where fixing one error at a time would be tedious.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` with all of the errors found, in the order they were found, if any part of
+    /// the document failed to parse. The document itself is discarded in that case.
+    ///
+    /// [`ParseOptions::recover`]: ./options/struct.ParseOptions.html#structfield.recover
+    pub fn read_recovering<R: Read>(reader: R) -> ::std::result::Result<VersionedDocument, MultiError> {
+        let options = ParseOptions {
+            recover: true,
+            ..ParseOptions::default()
+        };
+        let (document, report) = backend::open_encoded(reader, &options)
+            .and_then(|reader| Self::parse_document(reader, options))?;
+        MultiError::collect(document, report.errors)
+    }
+
+    /// Writes the document to `writer` as COLLADA XML, using the version of the specification
+    /// that the document was parsed with unless [`WriteOptions::version`] requests another one.
+    ///
+    /// See the [`writer`](./writer/index.html) module for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if writing to `writer` fails, or if the document can't be represented in
+    /// the version requested by `options`.
+    ///
+    /// [`WriteOptions::version`]: ./writer/struct.WriteOptions.html#structfield.version
+    pub fn write<W: Write>(&self, writer: W, options: WriteOptions) -> writer::Result<()> {
+        match *self {
+            VersionedDocument::V1_4(ref document) => document.write(writer, options),
+            VersionedDocument::V1_5(ref document) => document.write(writer, options),
+        }
     }
 
-    pub fn parse<R: Read>(mut reader: EventReader<R>) -> Result<VersionedDocument> {
+    pub fn parse<R: Read>(reader: EventReader<R>) -> Result<VersionedDocument> {
+        Self::parse_with_report(reader, ParseOptions::default()).map(|(document, _)| document)
+    }
+
+    fn parse_with_report<P: XmlPull>(reader: P, options: ParseOptions) -> Result<(VersionedDocument, ParseReport)> {
+        let (document, report) = Self::parse_document(reader, options)?;
+        Ok((document?, report))
+    }
+
+    /// Parses a document, returning the report for it even if the document fails to parse.
+    ///
+    /// The outer `Result` is `Err` if the document can't be parsed at all, e.g. because its
+    /// version isn't supported.
+    fn parse_document<P: XmlPull>(reader: P, options: ParseOptions) -> Result<(Result<VersionedDocument>, ParseReport)> {
+        Self::parse_events(reader, options).map(|(document, report, _)| (document, report))
+    }
+
+    /// Parses a document the same as `parse_document`, also returning the event source once it's
+    /// finished with so that it can be reused.
+    fn parse_events<P: XmlPull>(mut reader: P, options: ParseOptions) -> Result<(Result<VersionedDocument>, ParseReport, P)> {
         // Get the opening `<COLLADA>` tag and find the "version" attribute.
         let element_start = utils::get_document_start(&mut reader)?;
-        let version = element_start.attributes.iter()
-            .find(|attrib| attrib.name.local_name == "version")
-            .map(|attrib| attrib.value.clone())
-            .ok_or(Error {
-                position: reader.position(),
-                kind: ErrorKind::MissingAttribute {
-                    element: "COLLADA",
-                    attribute: "version",
-                },
-            })?;
-
-        match &*version {
-            "1.4.0" | "1.4.1" => {
+        let version = utils::get_document_version(&reader, &element_start)?;
+        let mut reader = DocumentReader::new(reader, ParseContext::with_options(version, options));
+
+        let document = match version {
+            Version::V1_4_0 | Version::V1_4_1 => {
                 v1_4::Collada::parse_element(&mut reader, element_start).map(Into::into)
             }
 
-            "1.5.0" => {
+            Version::V1_5_0 => {
                 v1_5::Collada::parse_element(&mut reader, element_start).map(Into::into)
             }
+        };
 
-            _ => {
-                Err(Error {
-                    position: reader.position(),
-                    kind: ErrorKind::UnsupportedVersion {
-                        version: version,
-                    },
-                })
-            }
-        }
+        let (events, context) = reader.into_parts();
+        Ok((document, context.report, events))
     }
 }
 
@@ -265,6 +494,79 @@ impl From<v1_5::Collada> for VersionedDocument {
     }
 }
 
+/// A version of the COLLADA specification supported by this library.
+///
+/// # Examples
+///
+/// ```
+/// use collaborate::Version;
+///
+/// let version: Version = "1.4.1".parse().unwrap();
+/// assert_eq!(Version::V1_4_1, version);
+/// assert!(version.is_compatible("1.4"));
+/// assert!(!version.is_compatible("1.5"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[allow(non_camel_case_types)]
+pub enum Version {
+    /// Version `1.4.0`.
+    V1_4_0,
+
+    /// Version `1.4.1`.
+    V1_4_1,
+
+    /// Version `1.5.0`.
+    V1_5_0,
+}
+
+impl Version {
+    /// Returns the version string as it appears in the `version` attribute of a document.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Version::V1_4_0 => "1.4.0",
+            Version::V1_4_1 => "1.4.1",
+            Version::V1_5_0 => "1.5.0",
+        }
+    }
+
+    /// Returns the XML namespace of the COLLADA schema for the version.
+    pub fn namespace(&self) -> &'static str {
+        match *self {
+            Version::V1_4_0 | Version::V1_4_1 => "http://www.collada.org/2005/11/COLLADASchema",
+            Version::V1_5_0 => "http://www.collada.org/2008/03/COLLADASchema",
+        }
+    }
+
+    /// Checks if the version matches `spec`, which may be either a full version string or a
+    /// prefix of one.
+    ///
+    /// Prefixes only match complete version components, so `"1.4"` matches both `1.4.0` and
+    /// `1.4.1`, but `"1.5"` doesn't match a hypothetical `1.50.0`.
+    pub fn is_compatible(&self, spec: &str) -> bool {
+        let version = self.as_str();
+        version == spec || (version.starts_with(spec) && version[spec.len()..].starts_with('.'))
+    }
+}
+
+impl FromStr for Version {
+    type Err = ErrorKind;
+
+    fn from_str(source: &str) -> ::std::result::Result<Version, ErrorKind> {
+        match source {
+            "1.4.0" => Ok(Version::V1_4_0),
+            "1.4.1" => Ok(Version::V1_4_1),
+            "1.5.0" => Ok(Version::V1_5_0),
+            _ => Err(ErrorKind::UnsupportedVersion { version: source.into() }),
+        }
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, formatter: &mut Formatter) -> ::std::result::Result<(), fmt::Error> {
+        formatter.write_str(self.as_str())
+    }
+}
+
 /// A COLLADA parsing error.
 ///
 /// Contains where in the document the error occurred (i.e. line number and column), and
@@ -275,6 +577,140 @@ pub struct Error {
     pub kind: ErrorKind,
 }
 
+impl Error {
+    /// Renders the error as a diagnostic report, quoting the offending line of `source`.
+    ///
+    /// `source` must be the text of the document that produced the error. The report shows the
+    /// error message, the location of the error, an excerpt of the line with the offending element
+    /// or attribute underlined, and a note listing the names that were expected, if any. This is
+    /// intended for displaying errors directly to users, e.g. in an importer UI or a log.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use collaborate::VersionedDocument;
+    ///
+    /// static DOCUMENT: &'static str = r#"<COLLADA version="1.4.1">
+    ///     <asset>
+    ///         <created>2017-02-07T20:44:30Z</created>
+    ///         <modified>2017-02-07T20:44:30Z</modified>
+    ///         <gadget />
+    ///     </asset>
+    /// </COLLADA>"#;
+    ///
+    /// let error = VersionedDocument::from_str(DOCUMENT).unwrap_err();
+    /// println!("{}", error.render(DOCUMENT));
+    /// ```
+    pub fn render(&self, source: &str) -> String {
+        let mut report = format!("error: {}\n", self.kind);
+
+        let row = self.position.row as usize;
+        let column = self.position.column as usize;
+        let line_number = (row + 1).to_string();
+        let gutter = " ".repeat(line_number.len());
+
+        report.push_str(&format!("{}--> {}\n", gutter, self.position));
+
+        if let Some(line) = source.lines().nth(row) {
+            let (start, len) = self.highlight(line, column);
+
+            // Keep tabs in the padding so that the carets line up with the quoted line.
+            let padding = line.chars()
+                .take(start)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect::<String>();
+
+            report.push_str(&format!("{} |\n", gutter));
+            report.push_str(&format!("{} | {}\n", line_number, line));
+            report.push_str(&format!("{} | {}{}\n", gutter, padding, "^".repeat(len)));
+        }
+
+        if let Some(note) = self.note() {
+            report.push_str(&format!("{} |\n", gutter));
+            report.push_str(&format!("{} = note: {}\n", gutter, note));
+        }
+
+        report
+    }
+
+    /// Finds the span of `line` to underline, as a character offset and length.
+    ///
+    /// Errors are reported at the start of an element's tag, so the span covers the tag name or,
+    /// for attribute errors, the name of the offending attribute within the tag.
+    fn highlight(&self, line: &str, column: usize) -> (usize, usize) {
+        let rest = line.chars().skip(column).collect::<String>();
+
+        let attribute = match self.kind {
+            ErrorKind::UnexpectedAttribute { ref attribute, .. } => Some(&**attribute),
+            ErrorKind::VersionMismatch { ref member, .. } if !rest.starts_with(&*format!("<{}", member)) => Some(&**member),
+            _ => None,
+        };
+
+        if let Some(attribute) = attribute {
+            let mut search = &*rest;
+            while let Some(index) = search.find(attribute) {
+                let before = search[..index].chars().next_back();
+                let after = search[index + attribute.len()..].trim_start();
+                if before.map(char::is_whitespace).unwrap_or(false) && after.starts_with('=') {
+                    let offset = rest.len() - search.len() + index;
+                    return (column + rest[..offset].chars().count(), attribute.chars().count());
+                }
+
+                search = &search[index + attribute.len()..];
+            }
+        }
+
+        if rest.starts_with('<') {
+            let len = rest.chars()
+                .take_while(|&c| !c.is_whitespace() && c != '>' && c != '/')
+                .count();
+            return (column, len.max(1));
+        }
+
+        (column, 1)
+    }
+
+    /// Returns additional information to display with the rendered error, if any.
+    fn note(&self) -> Option<String> {
+        fn expected_list(expected: &[&'static str]) -> String {
+            if expected.len() == 1 {
+                format!("expected {}", expected[0])
+            } else {
+                format!("expected one of {}", StringListDisplay(expected))
+            }
+        }
+
+        match self.kind {
+            ErrorKind::MissingElement { ref expected, .. } |
+            ErrorKind::MissingRequiredChild { ref expected, .. } if !expected.is_empty() => {
+                Some(expected_list(expected))
+            }
+
+            ErrorKind::UnexpectedAttribute { ref element, ref expected, .. } => {
+                if expected.is_empty() {
+                    Some(format!("<{}> doesn't allow any attributes", element))
+                } else {
+                    Some(expected_list(expected))
+                }
+            }
+
+            ErrorKind::UnexpectedElement { ref parent, ref expected, .. } => {
+                if expected.is_empty() {
+                    Some(format!("<{}> doesn't allow any child elements", parent))
+                } else {
+                    Some(expected_list(expected))
+                }
+            }
+
+            ErrorKind::VersionMismatch { ref member, ref valid_in, .. } => {
+                Some(format!("\"{}\" is only allowed in COLLADA {}", member, valid_in))
+            }
+
+            _ => None,
+        }
+    }
+}
+
 impl From<xml::reader::Error> for Error {
     fn from(from: xml::reader::Error) -> Error {
         Error {
@@ -290,6 +726,48 @@ impl Display for Error {
     }
 }
 
+/// All of the errors found while parsing a document with [`ParseOptions::recover`] set.
+///
+/// [`ParseOptions::recover`]: ./options/struct.ParseOptions.html#structfield.recover
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiError {
+    /// The errors, in the order they were found. There's always at least one.
+    pub errors: Vec<Error>,
+}
+
+impl MultiError {
+    /// Combines the result of parsing a document with the errors recovered from along the way.
+    fn collect<T>(document: Result<T>, mut errors: Vec<Error>) -> ::std::result::Result<T, MultiError> {
+        match document {
+            Ok(document) if errors.is_empty() => Ok(document),
+            Ok(_) => Err(MultiError { errors }),
+            Err(error) => {
+                errors.push(error);
+                Err(MultiError { errors })
+            }
+        }
+    }
+}
+
+impl From<Error> for MultiError {
+    fn from(from: Error) -> MultiError {
+        MultiError { errors: vec![from] }
+    }
+}
+
+impl Display for MultiError {
+    fn fmt(&self, formatter: &mut Formatter) -> ::std::result::Result<(), fmt::Error> {
+        for (index, error) in self.errors.iter().enumerate() {
+            if index > 0 {
+                writeln!(formatter)?;
+            }
+            write!(formatter, "{}", error)?;
+        }
+
+        Ok(())
+    }
+}
+
 /// The specific error variant.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ErrorKind {
@@ -321,6 +799,20 @@ pub enum ErrorKind {
         expected: Vec<&'static str>,
     },
 
+    /// An element was missing a required child once it was fully parsed.
+    ///
+    /// This is reported when a child that must appear at least once (either exactly once or
+    /// repeating) never appeared. Most missing children are reported as `MissingElement` as soon
+    /// as the parser reaches a point where the child can no longer appear, this is returned if a
+    /// missing child is only detected after the element has been parsed.
+    MissingRequiredChild {
+        /// The element that was missing a child.
+        parent: &'static str,
+
+        /// The names that the missing child could have had.
+        expected: Vec<&'static str>,
+    },
+
     /// An element was missing required text data.
     ///
     /// Some elements in the COLLADA document are required to contain some kind of data. If such
@@ -329,6 +821,12 @@ pub enum ErrorKind {
         element: &'static str,
     },
 
+    /// A boolean value was formatted incorrectly.
+    ///
+    /// Boolean values are parsed according to Rust's [standard handling for booleans](https://doc.rust-lang.org/std/primitive.bool.html#impl-FromStr-for-bool),
+    /// i.e. they must be either `true` or `false`.
+    ParseBoolError(ParseBoolError),
+
     /// A floating point value was formatted incorrectly.
     ///
     /// Floating point values are parsed according to Rust's [standard handling for floating point
@@ -423,6 +921,34 @@ pub enum ErrorKind {
         value: String,
     },
 
+    /// The document exceeded one of the limits set in its [`ParseOptions`].
+    ///
+    /// [`ParseOptions`]: ./struct.ParseOptions.html
+    LimitExceeded {
+        /// The name of the limit that was exceeded, e.g. `"max_depth"`.
+        limit: &'static str,
+
+        /// The configured value of the limit.
+        max: usize,
+    },
+
+    /// An array element contained a different number of values than its `count` attribute
+    /// declared.
+    ///
+    /// Only checked if [`ParseOptions::validate_counts`] is set.
+    ///
+    /// [`ParseOptions::validate_counts`]: ./struct.ParseOptions.html#structfield.validate_counts
+    CountMismatch {
+        /// The array element.
+        element: &'static str,
+
+        /// The number of values declared by the `count` attribute.
+        expected: usize,
+
+        /// The number of values the element actually contained.
+        actual: usize,
+    },
+
     /// The COLLADA document specified an unsupported version of the specification.
     ///
     /// The root `<COLLADA>` element of every COLLADA document must have a `version` attribute
@@ -433,6 +959,37 @@ pub enum ErrorKind {
         version: String,
     },
 
+    /// An element or attribute was used that isn't allowed by the document's version of the
+    /// specification.
+    ///
+    /// Some elements and attributes were added or removed between versions of the COLLADA
+    /// specification. If one appears in a document declaring a version that doesn't support it,
+    /// this error is returned.
+    VersionMismatch {
+        /// The element containing the version-specific member.
+        element: &'static str,
+
+        /// The name of the child element or attribute.
+        member: String,
+
+        /// The version(s) of the specification that allow the member.
+        valid_in: &'static str,
+
+        /// The version declared by the document.
+        version: Version,
+    },
+
+    /// The document had a document type declaration (`<!DOCTYPE ...>`), which isn't allowed.
+    ///
+    /// Only returned if [`ParseOptions::dtd`] is [`DtdHandling::Reject`].
+    ///
+    /// [`ParseOptions::dtd`]: ./struct.ParseOptions.html#structfield.dtd
+    /// [`DtdHandling::Reject`]: ./options/enum.DtdHandling.html#variant.Reject
+    DtdNotAllowed,
+
+    /// There was invalid hex-encoded binary data in the document.
+    HexBinaryParseError(HexBinaryParseError),
+
     /// There was an invalid URI fragment in the document.
     UriFragmentParseError(UriFragmentParseError),
 
@@ -448,6 +1005,12 @@ impl From<::chrono::format::ParseError> for ErrorKind {
     }
 }
 
+impl From<::std::str::ParseBoolError> for ErrorKind {
+    fn from(from: ::std::str::ParseBoolError) -> ErrorKind {
+        ErrorKind::ParseBoolError(from)
+    }
+}
+
 impl From<::std::num::ParseFloatError> for ErrorKind {
     fn from(from: ::std::num::ParseFloatError) -> ErrorKind {
         ErrorKind::ParseFloatError(from)
@@ -466,6 +1029,12 @@ impl From<::std::string::ParseError> for ErrorKind {
     }
 }
 
+impl From<HexBinaryParseError> for ErrorKind {
+    fn from(from: HexBinaryParseError) -> ErrorKind {
+        ErrorKind::HexBinaryParseError(from)
+    }
+}
+
 impl From<UriFragmentParseError> for ErrorKind {
     fn from(from: UriFragmentParseError) -> ErrorKind {
         ErrorKind::UriFragmentParseError(from)
@@ -491,10 +1060,23 @@ impl Display for ErrorKind {
                 }
             }
 
+            ErrorKind::MissingRequiredChild { ref parent, ref expected } => {
+                write!(
+                    formatter,
+                    "<{}> is missing a required child element, expected at least one of: {}",
+                    parent,
+                    StringListDisplay(&*expected),
+                )
+            }
+
             ErrorKind::MissingValue { element } => {
                 write!(formatter, "<{}> is missing required text data", element)
             }
 
+            ErrorKind::ParseBoolError(ref error) => {
+                error.fmt(formatter)
+            }
+
             ErrorKind::ParseFloatError(ref error) => {
                 error.fmt(formatter)
             }
@@ -539,10 +1121,37 @@ impl Display for ErrorKind {
                 write!(formatter, "<{}> contained an unexpected value {:?}", element, value)
             }
 
+            ErrorKind::LimitExceeded { limit, max } => {
+                write!(formatter, "Document exceeded the {} limit of {}", limit, max)
+            }
+
+            ErrorKind::CountMismatch { element, expected, actual } => {
+                write!(formatter, "<{}> declared {} values but contained {}", element, expected, actual)
+            }
+
             ErrorKind::UnsupportedVersion { ref version } => {
                 write!(formatter, "Unsupported COLLADA version {:?}, supported versions are \"1.4.0\", \"1.4.1\", \"1.5.0\"", version)
             }
 
+            ErrorKind::VersionMismatch { ref element, ref member, ref valid_in, ref version } => {
+                write!(
+                    formatter,
+                    "<{}> had \"{}\" which is only allowed in COLLADA {}, but the document uses version {}",
+                    element,
+                    member,
+                    valid_in,
+                    version,
+                )
+            }
+
+            ErrorKind::DtdNotAllowed => {
+                write!(formatter, "Document had a document type declaration, which isn't allowed")
+            }
+
+            ErrorKind::HexBinaryParseError(ref error) => {
+                error.fmt(formatter)
+            }
+
             ErrorKind::UriFragmentParseError(ref error) => {
                 error.fmt(formatter)
             }
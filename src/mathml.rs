@@ -0,0 +1,165 @@
+//! Interpreting the content MathML used by kinematics formulas.
+//!
+//! A `<formula>` defines its value with a MathML expression, which is kept as a tree of
+//! [`RawElement`]s by [`FormulaTechniqueCommon`]. This module turns that tree into an
+//! [`Expression`] that can be inspected or evaluated. Only content MathML is supported, since
+//! presentation MathML doesn't describe a computation.
+//!
+//! Only available with the `mathml` feature enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate collaborate;
+//! # extern crate xml;
+//! use collaborate::mathml::Expression;
+//! use collaborate::raw::RawElement;
+//! use xml::reader::EventReader;
+//!
+//! # fn main() {
+//! let source = r#"
+//!     <math xmlns="http://www.w3.org/1998/Math/MathML">
+//!         <apply><times/><cn>2</cn><ci>joint0</ci></apply>
+//!     </math>
+//! "#;
+//! let math = RawElement::parse(&mut EventReader::from_str(source)).unwrap();
+//! let expression = Expression::from_math(&math).unwrap();
+//!
+//! let value = expression.evaluate(&|name| if name == "joint0" { Some(45.0) } else { None });
+//! assert_eq!(Some(90.0), value);
+//! # }
+//! ```
+//!
+//! [`RawElement`]: ../raw/struct.RawElement.html
+//! [`FormulaTechniqueCommon`]: ../v1_5/struct.FormulaTechniqueCommon.html
+//! [`Expression`]: ./enum.Expression.html
+
+use raw::RawElement;
+
+/// A content MathML expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    /// A literal number, from a `<cn>` element or a constant such as `<pi/>`.
+    ///
+    /// The boolean constants `<true/>` and `<false/>` are represented as 1 and 0.
+    Number(f64),
+
+    /// A variable, from a `<ci>` element.
+    ///
+    /// In a formula this is usually the sid of one of the formula's parameters.
+    Identifier(String),
+
+    /// A symbol defined outside of the expression, from a `<csymbol>` element.
+    ///
+    /// COLLADA documents use these to refer to joints and other parts of the document.
+    Symbol(String),
+
+    /// An operator applied to its arguments, from an `<apply>` element.
+    ///
+    /// The operator is the name of the MathML element that names it, e.g. `"plus"`.
+    Apply(String, Vec<Expression>),
+}
+
+impl Expression {
+    /// Parses the expression in a `<math>` element.
+    ///
+    /// Returns `None` if `math` doesn't contain exactly one expression, or if the expression
+    /// isn't valid content MathML.
+    pub fn from_math(math: &RawElement) -> Option<Expression> {
+        if math.name.local_name != "math" || math.children.len() != 1 {
+            return None;
+        }
+
+        Expression::from_element(&math.children[0])
+    }
+
+    /// Parses a single content MathML element, such as an `<apply>` or a `<ci>`.
+    ///
+    /// Returns `None` if the element isn't valid content MathML.
+    pub fn from_element(element: &RawElement) -> Option<Expression> {
+        match &*element.name.local_name {
+            "cn" => element.text.trim().parse().ok().map(Expression::Number),
+            "ci" => Some(Expression::Identifier(element.text.trim().into())),
+            "csymbol" => Some(Expression::Symbol(element.text.trim().into())),
+            "pi" => Some(Expression::Number(::std::f64::consts::PI)),
+            "exponentiale" => Some(Expression::Number(::std::f64::consts::E)),
+            "true" => Some(Expression::Number(1.0)),
+            "false" => Some(Expression::Number(0.0)),
+
+            "apply" => {
+                let (operator, arguments) = element.children.split_first()?;
+                if !operator.children.is_empty() {
+                    return None;
+                }
+
+                let arguments = arguments.iter()
+                    .map(Expression::from_element)
+                    .collect::<Option<Vec<_>>>()?;
+                Some(Expression::Apply(operator.name.local_name.clone(), arguments))
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Evaluates the expression, looking up the value of each identifier and symbol with
+    /// `lookup`.
+    ///
+    /// Trigonometric functions work in radians. Comparisons and logical operators produce 1 for
+    /// true and 0 for false.
+    ///
+    /// Returns `None` if `lookup` doesn't know one of the variables, or if the expression uses an
+    /// operator that isn't supported or gives it the wrong number of arguments.
+    pub fn evaluate(&self, lookup: &Fn(&str) -> Option<f64>) -> Option<f64> {
+        let (operator, arguments) = match *self {
+            Expression::Number(value) => return Some(value),
+            Expression::Identifier(ref name) | Expression::Symbol(ref name) => return lookup(name),
+            Expression::Apply(ref operator, ref arguments) => (operator, arguments),
+        };
+
+        let values = arguments.iter()
+            .map(|argument| argument.evaluate(lookup))
+            .collect::<Option<Vec<_>>>()?;
+        let truth = |value: bool| if value { 1.0 } else { 0.0 };
+
+        let value = match (&**operator, &*values) {
+            ("plus", _) => values.iter().sum(),
+            ("times", _) => values.iter().product(),
+            ("max", &[first, ref rest @ ..]) => rest.iter().fold(first, |max, &value| max.max(value)),
+            ("min", &[first, ref rest @ ..]) => rest.iter().fold(first, |min, &value| min.min(value)),
+            ("and", _) => truth(values.iter().all(|&value| value != 0.0)),
+            ("or", _) => truth(values.iter().any(|&value| value != 0.0)),
+
+            ("minus", &[value]) => -value,
+            ("minus", &[left, right]) => left - right,
+            ("divide", &[left, right]) => left / right,
+            ("power", &[left, right]) => left.powf(right),
+            ("rem", &[left, right]) => left % right,
+            ("quotient", &[left, right]) => (left / right).trunc(),
+            ("root", &[value]) => value.sqrt(),
+            ("eq", &[left, right]) => truth(left == right),
+            ("neq", &[left, right]) => truth(left != right),
+            ("lt", &[left, right]) => truth(left < right),
+            ("leq", &[left, right]) => truth(left <= right),
+            ("gt", &[left, right]) => truth(left > right),
+            ("geq", &[left, right]) => truth(left >= right),
+
+            ("not", &[value]) => truth(value == 0.0),
+            ("abs", &[value]) => value.abs(),
+            ("floor", &[value]) => value.floor(),
+            ("ceiling", &[value]) => value.ceil(),
+            ("exp", &[value]) => value.exp(),
+            ("ln", &[value]) => value.ln(),
+            ("sin", &[value]) => value.sin(),
+            ("cos", &[value]) => value.cos(),
+            ("tan", &[value]) => value.tan(),
+            ("arcsin", &[value]) => value.asin(),
+            ("arccos", &[value]) => value.acos(),
+            ("arctan", &[value]) => value.atan(),
+
+            _ => return None,
+        };
+
+        Some(value)
+    }
+}
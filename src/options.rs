@@ -0,0 +1,242 @@
+//! Options for controlling how documents are parsed.
+//!
+//! By default documents are parsed strictly: anything that doesn't match the COLLADA schema is an
+//! error. Real exporter output is rarely schema-perfect though, so [`ParseOptions`] allows the
+//! parser to be more forgiving, as well as to limit how much work it will do for a document.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::fs::File;
+//! use collaborate::{ParseOptions, Strictness, VersionedDocument};
+//!
+//! let options = ParseOptions {
+//!     strictness: Strictness::Lenient,
+//!     max_depth: Some(64),
+//!     ..ParseOptions::default()
+//! };
+//!
+//! let file = File::open("resources/blender_cube.dae").unwrap();
+//! let (_document, report) = VersionedDocument::read_with(file, options).unwrap();
+//! for warning in &report.warnings {
+//!     println!("Warning: {}", warning);
+//! }
+//! ```
+//!
+//! When parsing documents from untrusted sources, set all of the limits in `ParseOptions`. Together
+//! they bound the size of the document that gets built, so a malicious document fails with a
+//! `LimitExceeded` error instead of exhausting memory.
+//!
+//! [`ParseOptions`]: ./struct.ParseOptions.html
+
+use progress::{Progress, ProgressCallback};
+use std::collections::BTreeMap;
+
+/// Options for parsing a document.
+///
+/// New options may be added in the future, so construct `ParseOptions` using
+/// `..ParseOptions::default()` to fill in any options you don't set explicitly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// How strictly the document is required to conform to the specification.
+    ///
+    /// Defaults to [`Strictness::Strict`](./enum.Strictness.html#variant.Strict).
+    pub strictness: Strictness,
+
+    /// The maximum nesting depth of elements in the document, if any.
+    ///
+    /// Documents that nest elements more deeply fail with a `LimitExceeded` error. Defaults to
+    /// `None`.
+    pub max_depth: Option<usize>,
+
+    /// The maximum number of values in any single array or list, if any.
+    ///
+    /// Documents with a longer array fail with a `LimitExceeded` error. Defaults to `None`.
+    pub max_array_len: Option<usize>,
+
+    /// The maximum length in bytes of the text contents of any single element, if any.
+    ///
+    /// Documents with longer text fail with a `LimitExceeded` error. Defaults to `None`.
+    pub max_text_len: Option<usize>,
+
+    /// The maximum number of child elements that any single element may have, if any.
+    ///
+    /// Documents with an element that has more children fail with a `LimitExceeded` error.
+    /// Children that are skipped in lenient modes still count towards the limit. Defaults to
+    /// `None`.
+    pub max_children: Option<usize>,
+
+    /// The maximum total size in bytes of all the arrays and lists decoded from the document, if
+    /// any.
+    ///
+    /// Documents that decode to more data fail with a `LimitExceeded` error. Defaults to `None`.
+    pub max_array_bytes: Option<usize>,
+
+    /// Whether to check that each array element (e.g. `<float_array>`) contains the number of
+    /// values declared by its `count` attribute.
+    ///
+    /// A mismatch is a `CountMismatch` error, or a warning in lenient modes. Defaults to `false`.
+    pub validate_counts: bool,
+
+    /// Whether to defer decoding the contents of array elements (e.g. `<float_array>`) until
+    /// they're first accessed.
+    ///
+    /// Malformed values in a deferred array are reported when it's accessed instead of failing the
+    /// parse. See [`RawArray`](../common/struct.RawArray.html) for details. Defaults to `false`.
+    pub defer_arrays: bool,
+
+    /// Whether to record where in the document each element with an `id` attribute is.
+    ///
+    /// The spans are available from [`ParseReport::span`](../report/struct.ParseReport.html#method.span).
+    /// Defaults to `false`.
+    pub track_spans: bool,
+
+    /// Whether to record the position in document order of each element with an `id` attribute.
+    ///
+    /// Elements are numbered in the order their start tags appear, so the original order of
+    /// elements can be recovered even after the parsed document has been rearranged, e.g. to tell
+    /// elements that were moved from ones that were edited. The indices are available from
+    /// [`ParseReport::document_index`](../report/struct.ParseReport.html#method.document_index).
+    /// Defaults to `false`.
+    pub track_order: bool,
+
+    /// Whether to keep the content that the document model doesn't have a place for, so that it
+    /// can be written back out.
+    ///
+    /// When set, unexpected elements and attributes that are skipped in lenient modes, and the
+    /// contents of stubbed elements, are kept in
+    /// [`ParseReport::preserved`](../report/struct.ParseReport.html#structfield.preserved). See
+    /// the [`preserve`](../preserve/index.html) module for details. Only used when parsing a whole
+    /// document. Defaults to `false`.
+    pub preserve_unknown: bool,
+
+    /// Whether to keep parsing after an element fails to parse.
+    ///
+    /// When set, an error in an optional or repeatable element is recorded in the
+    /// [`ParseReport`](../report/struct.ParseReport.html#structfield.errors) and the rest of the
+    /// element is skipped, so that as many problems as possible are found in a single pass.
+    /// Malformed XML and exceeded limits are never recovered from. Defaults to `false`.
+    pub recover: bool,
+
+    /// Whether to accept numbers formatted for locales other than English, as written by some
+    /// exporters.
+    ///
+    /// When set, a floating point value that fails to parse is parsed again with commas treated
+    /// as decimal separators and thousands separators removed, e.g. `"0,5"` is read as `0.5` and
+    /// `"1.234,5"` as `1234.5`. Each time this is needed it's recorded as a fallback in the
+    /// [`ParseReport`](../report/struct.ParseReport.html). This is always enabled when parsing
+    /// with [`Strictness::Lenient`](./enum.Strictness.html#variant.Lenient) or above. Defaults to
+    /// `false`.
+    pub locale_numbers: bool,
+
+    /// How to handle a document type declaration (`<!DOCTYPE ...>`) in the document.
+    ///
+    /// Defaults to [`DtdHandling::Skip`](./enum.DtdHandling.html#variant.Skip).
+    pub dtd: DtdHandling,
+
+    /// Replacement text for entities referenced by the document, beyond XML's predefined ones.
+    ///
+    /// Some exporters reference entities declared in their DTD, e.g. `&copy;`. DTDs are never
+    /// read, so those entities need to be provided here, keyed by name (without the `&` and `;`).
+    /// The replacement text is inserted as plain text, never as markup. References to entities
+    /// that aren't in the map are still errors. Defaults to empty.
+    ///
+    /// Errors reported on the same line as an expanded entity may have their column offset by the
+    /// difference in length between the reference and its replacement.
+    pub entities: BTreeMap<String, String>,
+
+    /// A callback to report the progress of the parse to, if any.
+    ///
+    /// Usually set with [`progress`](#method.progress). See the
+    /// [`progress`](../progress/index.html) module for details. Defaults to `None`.
+    pub progress: Option<ProgressCallback>,
+
+    /// Whether to parse the document's libraries in parallel, which can greatly reduce load times
+    /// for large scenes.
+    ///
+    /// The whole document is read into memory, and then each library is parsed on a separate
+    /// [rayon](https://docs.rs/rayon) task, alongside the rest of the document. The document and
+    /// report are the same as when parsing sequentially, and if more than one library fails to
+    /// parse, the error that occurs earliest in the document is returned. Documents are still
+    /// parsed sequentially if `progress`, `track_order`, `preserve_unknown`, `max_children`, or
+    /// `max_array_bytes` is set, since those apply to the document as a whole. Only available with
+    /// the `rayon` feature enabled. Defaults to `false`.
+    #[cfg(feature = "rayon")]
+    pub parallel: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> ParseOptions {
+        ParseOptions {
+            strictness: Strictness::Strict,
+            max_depth: None,
+            max_array_len: None,
+            max_text_len: None,
+            max_children: None,
+            max_array_bytes: None,
+            validate_counts: false,
+            defer_arrays: false,
+            track_spans: false,
+            track_order: false,
+            preserve_unknown: false,
+            recover: false,
+            locale_numbers: false,
+            dtd: DtdHandling::Skip,
+            entities: BTreeMap::new(),
+            progress: None,
+            #[cfg(feature = "rayon")]
+            parallel: false,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Sets a callback that's called periodically with the progress of the parse.
+    ///
+    /// See the [`progress`](../progress/index.html) module for details.
+    pub fn progress<F: FnMut(Progress) + Send + 'static>(mut self, callback: F) -> ParseOptions {
+        self.progress = Some(ProgressCallback::new(callback));
+        self
+    }
+
+    pub(crate) fn allows_locale_numbers(&self) -> bool {
+        self.locale_numbers || self.strictness >= Strictness::Lenient
+    }
+}
+
+/// How strictly a document is required to conform to the specification.
+///
+/// In the lenient modes, problems that the parser can recover from are collected as warnings in
+/// the [`ParseReport`] for the document instead of being returned as errors. Each mode tolerates
+/// everything that the modes before it tolerate.
+///
+/// [`ParseReport`]: ../report/struct.ParseReport.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Strictness {
+    /// Any deviation from the specification is an error.
+    Strict,
+
+    /// Unexpected attributes and elements are skipped, as are elements that appear out of order.
+    /// Array count mismatches are also reported as warnings.
+    Lenient,
+
+    /// Elements and attributes that aren't allowed by the document's version of the specification
+    /// are accepted anyway.
+    Permissive,
+}
+
+/// How to handle a document type declaration (DTD) in a document.
+///
+/// COLLADA documents don't need a DTD, but some exporters include one anyway, sometimes with an
+/// internal subset declaring custom entities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DtdHandling {
+    /// The DTD is ignored, along with any declarations in it.
+    Skip,
+
+    /// Documents with a DTD fail to parse with a `DtdNotAllowed` error.
+    ///
+    /// DTDs are never processed, so this isn't needed for safety, but it's useful for rejecting
+    /// documents from untrusted sources that attempt to use one.
+    Reject,
+}
@@ -0,0 +1,246 @@
+//! Parsing a document's libraries in parallel, see [`ParseOptions::parallel`].
+//!
+//! The document is read into memory and skimmed to find its libraries, the same as for a
+//! [`LazyDocument`]. Each library is then parsed on its own rayon task as a standalone document
+//! consisting of the original root tag and the library, with everything in between blanked out so
+//! that positions in errors still match the original document. The rest of the document is parsed
+//! alongside them with the libraries blanked out. Every part is parsed with the default backend and
+//! the caller's options.
+//!
+//! [`ParseOptions::parallel`]: ../options/struct.ParseOptions.html#structfield.parallel
+//! [`LazyDocument`]: ../lazy/struct.LazyDocument.html
+
+use {Error, ParseOptions, ParseReport, Result, Version, XmlError};
+use backend::{self, XmlPull};
+use dtd::DtdFilter;
+use encoding::Transcode;
+use lazy::{self, LibrarySpan, Skim};
+use rayon::prelude::*;
+use report::SourceSpan;
+use std::io::Read;
+use utils::{self, ColladaElement, DocumentReader, ParseContext};
+use xml::common::Position;
+
+/// A version of the document model whose libraries can be parsed separately from the rest of the
+/// document.
+pub(crate) trait SplitDocument: Sized + Send {
+    /// The group of library elements that can appear in the document.
+    type Library: ColladaElement + Send;
+
+    /// Parses a whole document in order, returning the report for it even if the document fails
+    /// to parse.
+    fn parse_document<P: XmlPull>(reader: P, options: ParseOptions) -> Result<(Result<Self>, ParseReport)>;
+
+    /// Replaces the document's libraries with the ones that were parsed separately.
+    fn set_libraries(&mut self, libraries: Vec<Self::Library>);
+}
+
+/// Reads a whole document into memory, decoding it to UTF-8 and handling its DTD according to
+/// `options`.
+pub(crate) fn read_source<R: Read>(reader: R, options: &ParseOptions) -> Result<Vec<u8>> {
+    let mut source = Vec::new();
+    DtdFilter::new(Transcode::new(reader), options)?
+        .read_to_end(&mut source)
+        .map_err(XmlError::from)?;
+    Ok(source)
+}
+
+/// Returns the version of the specification declared by a document read with `read_source`.
+pub(crate) fn version(source: &[u8]) -> Result<Version> {
+    let mut events = backend::open_slice(source);
+    let element_start = utils::get_document_start(&mut events)?;
+    utils::get_document_version(&events, &element_start)
+}
+
+/// Parses a document read with `read_source`, parsing its libraries in parallel.
+///
+/// The results match parsing the document in order. If more than one part of the document fails
+/// to parse, the error that occurs earliest in the document is returned, and the others are
+/// recorded in the report's `errors` along with any that were recovered from.
+///
+/// The document is parsed in order instead if `options` can't be applied to the libraries one at
+/// a time, or if the libraries aren't all directly after the `<asset>`, since only the full parse
+/// reports misplaced elements the same way.
+pub(crate) fn parse<D: SplitDocument>(source: &[u8], options: ParseOptions) -> Result<(Result<D>, ParseReport)> {
+    // Malformed documents are parsed in order too, so that the error is reported the same way.
+    let skim = match lazy::skim(source, D::Library::name_test) {
+        Ok(skim) => skim,
+        Err(_) => return D::parse_document(backend::open_slice(source), options),
+    };
+    if !can_split(&options) || !libraries_in_place::<D>(&skim) {
+        return D::parse_document(backend::open_slice(source), options);
+    }
+
+    let version = version(source)?;
+    let (document, libraries) = rayon::join(
+        || {
+            let skeleton = skeleton(source, &skim);
+            D::parse_document(backend::open_slice(&skeleton), options.clone())
+        },
+        || skim.libraries.par_iter()
+            .map(|library| parse_library::<D::Library>(source, &skim, library, version, options.clone()))
+            .collect::<Vec<_>>(),
+    );
+
+    let (document, mut report) = document?;
+    let mut failures = Vec::new();
+    let mut parsed = Vec::with_capacity(libraries.len());
+    for (library, library_report) in libraries {
+        report.merge(&library_report);
+        report.spans.extend(library_report.spans);
+        match library {
+            Ok(Some(library)) => parsed.push(library),
+            Ok(None) => {}
+            Err(error) => failures.push(error),
+        }
+    }
+
+    let document = match document {
+        Ok(document) => Some(document),
+        Err(error) => {
+            failures.push(error);
+            None
+        }
+    };
+
+    failures.sort_by_key(position_key);
+    let mut failures = failures.into_iter();
+    let document = match (failures.next(), document) {
+        (Some(error), _) => Err(error),
+        (None, Some(mut document)) => {
+            document.set_libraries(parsed);
+            Ok(document)
+        }
+        (None, None) => unreachable!("Document failed to parse without an error"),
+    };
+
+    report.errors.extend(failures);
+    report.errors.sort_by_key(position_key);
+    report.warnings.sort_by_key(position_key);
+    Ok((document, report))
+}
+
+/// Whether `options` can be applied to each part of a document separately.
+///
+/// Progress, document order, and preserved content are tracked through the document as a whole.
+/// The limits on children and array sizes count the libraries towards the root element's children
+/// and the document's total, which the separate parts can't see.
+fn can_split(options: &ParseOptions) -> bool {
+    options.progress.is_none()
+        && !options.track_order
+        && !options.preserve_unknown
+        && options.max_children.is_none()
+        && options.max_array_bytes.is_none()
+}
+
+/// Whether all of the libraries in the document are where the specification expects them, i.e.
+/// directly after the `<asset>` and before anything else.
+fn libraries_in_place<D: SplitDocument>(skim: &Skim) -> bool {
+    let mut rest = skim.children.iter()
+        .skip(1)
+        .skip_while(|name| D::Library::name_test(name));
+    skim.children.first().map(|name| &**name) == Some("asset")
+        && !rest.any(|name| D::Library::name_test(name))
+}
+
+/// Returns the document with every library blanked out.
+fn skeleton(source: &[u8], skim: &Skim) -> Vec<u8> {
+    let mut skeleton = Vec::with_capacity(source.len());
+    let mut start = 0;
+    for library in &skim.libraries {
+        skeleton.extend_from_slice(&source[start..library.range.start]);
+        blank(&mut skeleton, &source[library.range.clone()]);
+        start = library.range.end;
+    }
+    skeleton.extend_from_slice(&source[start..]);
+    skeleton
+}
+
+/// Parses a single library as a standalone document, returning the report for it.
+///
+/// The library is `None` if it failed to parse, but the error was recovered from.
+fn parse_library<L: ColladaElement>(
+    source: &[u8],
+    skim: &Skim,
+    library: &LibrarySpan,
+    version: Version,
+    options: ParseOptions,
+) -> (Result<Option<L>>, ParseReport) {
+    let mut document = Vec::with_capacity(skim.root_tag.end + library.range.len() + 16);
+    document.extend_from_slice(&source[..skim.root_tag.end]);
+    blank(&mut document, &source[skim.root_tag.end..library.range.start]);
+    document.extend_from_slice(&source[library.range.clone()]);
+    document.extend_from_slice(b"</COLLADA>");
+
+    let mut events = backend::open_slice(&document);
+    if let Err(error) = utils::get_document_start(&mut events) {
+        return (Err(error), ParseReport::default());
+    }
+
+    // Quirks of the document's version are reported by the rest of the document, and the library
+    // is nested inside of the root element.
+    let mut context = ParseContext::with_options(version, options);
+    context.report.quirks.clear();
+    context.depth = 1;
+
+    let mut reader = DocumentReader::new(events, context);
+    let library = parse_library_element(&mut reader);
+    (library, reader.into_context().report)
+}
+
+/// Parses the library inside of the root element, recovering from errors and recording its span
+/// the same as when it's parsed as a child of the root element.
+fn parse_library_element<R: XmlPull, L: ColladaElement>(reader: &mut DocumentReader<R>) -> Result<Option<L>> {
+    let element_start = utils::start_element(reader, "COLLADA")?
+        .expect("Library span didn't contain an element");
+
+    let id = if reader.context().options.track_spans {
+        element_start.attributes.iter()
+            .find(|attribute| attribute.name.local_name == "id" && attribute.name.prefix.is_none())
+            .map(|attribute| attribute.value.clone())
+    } else {
+        None
+    };
+    let start = reader.position();
+    let open_elements = reader.open_elements();
+
+    let library = match utils::parse_group_member(reader, "COLLADA", element_start) {
+        Ok(library) => library,
+        Err(error) => {
+            reader.recover(error, open_elements)?;
+            None
+        }
+    };
+
+    if let Some(id) = id {
+        let span = SourceSpan { start, end: reader.position() };
+        reader.context_mut().report.record_span(&id, span);
+    }
+
+    Ok(library)
+}
+
+/// Appends whitespace to `into` that ends at the same line and column as `text`.
+///
+/// Positions are reported as a line and a character offset within the line, so this keeps the
+/// positions of everything following the blanked text unchanged.
+fn blank(into: &mut Vec<u8>, text: &[u8]) {
+    let last_line = match text.iter().rposition(|&byte| byte == b'\n') {
+        Some(index) => {
+            let lines = text[..=index].iter().filter(|&&byte| byte == b'\n').count();
+            let len = into.len() + lines;
+            into.resize(len, b'\n');
+            &text[index + 1..]
+        }
+        None => text,
+    };
+
+    // UTF-8 continuation bytes are skipped so that each character becomes a single space.
+    let chars = last_line.iter().filter(|&&byte| byte & 0b1100_0000 != 0b1000_0000).count();
+    let len = into.len() + chars;
+    into.resize(len, b' ');
+}
+
+fn position_key(error: &Error) -> (u64, u64) {
+    (error.position.row, error.position.column)
+}
@@ -0,0 +1,76 @@
+//! Parsing many documents in a batch.
+//!
+//! See [`Parser`](./struct.Parser.html) for details.
+
+use {ParseOptions, ParseReport, Result, VersionedDocument};
+use backend::{self, Buffers};
+use std::io::Read;
+
+/// Parses documents with the same options, reusing buffers between them.
+///
+/// Reading a document needs a number of internal buffers, e.g. for decoding documents that aren't
+/// UTF-8 or for holding the whole document with the `quick-xml` backend. When parsing thousands
+/// of documents in an asset pipeline, allocating those buffers for each document adds up. A
+/// `Parser` keeps the buffers from one document to use for the next.
+///
+/// # Examples
+///
+/// ```
+/// use std::fs::File;
+/// use collaborate::Parser;
+///
+/// let mut parser = Parser::new();
+/// for path in &["resources/blender_cube.dae", "resources/v1_5_minimal.dae"] {
+///     let document = parser.parse(File::open(path).unwrap()).unwrap();
+///     println!("{}: {:?}", path, document);
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct Parser {
+    options: ParseOptions,
+    buffers: Buffers,
+}
+
+impl Parser {
+    /// Creates a parser that parses documents with the default options.
+    pub fn new() -> Parser {
+        Parser::with_options(ParseOptions::default())
+    }
+
+    /// Creates a parser that parses documents with `options`.
+    pub fn with_options(options: ParseOptions) -> Parser {
+        Parser {
+            options,
+            buffers: Buffers::default(),
+        }
+    }
+
+    /// Returns the options that documents are parsed with.
+    pub fn options(&self) -> &ParseOptions {
+        &self.options
+    }
+
+    /// Parses a document, the same as [`VersionedDocument::read_with`] with the parser's options.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` for the same reasons as [`VersionedDocument::read_with`]. The parser can
+    /// still be used for other documents afterwards.
+    ///
+    /// [`VersionedDocument::read_with`]: ../enum.VersionedDocument.html#method.read_with
+    pub fn parse<R: Read>(&mut self, reader: R) -> Result<VersionedDocument> {
+        self.parse_with_report(reader).map(|(document, _)| document)
+    }
+
+    /// Parses a document, also returning a report of any workarounds needed to parse it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` for the same reasons as [`parse`](#method.parse).
+    pub fn parse_with_report<R: Read>(&mut self, reader: R) -> Result<(VersionedDocument, ParseReport)> {
+        let events = backend::open_reusing(reader, &self.options, &mut self.buffers)?;
+        let (document, report, events) = VersionedDocument::parse_events(events, self.options.clone())?;
+        backend::recycle(events, &mut self.buffers);
+        Ok((document?, report))
+    }
+}
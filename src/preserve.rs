@@ -0,0 +1,215 @@
+//! Preserving content that isn't part of the document model, so that it can be written back out.
+//!
+//! Parsing a document normally loses anything that the document model doesn't have a place for:
+//! unexpected elements and attributes that are skipped in lenient modes, and the contents of
+//! elements that are only stubbed out (see the [`coverage`] module). When a document is parsed
+//! with [`ParseOptions::preserve_unknown`] set, this content is kept in
+//! [`ParseReport::preserved`] instead. Passing it to the writer with
+//! [`WriteOptions::preserved`] puts it back where it came from, so that parsing and writing a
+//! document doesn't lose anything, e.g. when using the crate to rewrite `.dae` files.
+//!
+//! # Examples
+//!
+//! ```
+//! use collaborate::{ParseOptions, Strictness, VersionedDocument};
+//! use collaborate::writer::WriteOptions;
+//!
+//! static DOCUMENT: &'static str = r#"
+//! <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+//!     <asset>
+//!         <created>2017-02-07T20:44:30Z</created>
+//!         <modified>2017-02-07T20:44:30Z</modified>
+//!         <exporter_notes draft="true">Not in the schema</exporter_notes>
+//!     </asset>
+//! </COLLADA>
+//! "#;
+//!
+//! let options = ParseOptions {
+//!     strictness: Strictness::Lenient,
+//!     preserve_unknown: true,
+//!     ..ParseOptions::default()
+//! };
+//! let (document, report) = VersionedDocument::read_with(DOCUMENT.as_bytes(), options).unwrap();
+//!
+//! let options = WriteOptions {
+//!     preserved: Some(report.preserved),
+//!     ..WriteOptions::default()
+//! };
+//! let mut output = Vec::new();
+//! document.write(&mut output, options).unwrap();
+//!
+//! let written = String::from_utf8(output).unwrap();
+//! assert!(written.contains(r#"<exporter_notes draft="true">Not in the schema</exporter_notes>"#));
+//! ```
+//!
+//! # Placement
+//!
+//! Elements that are part of the document model are always written in the order the
+//! specification gives them, which matches the original document for any document that conforms
+//! to the specification. Preserved elements are written immediately after the sibling they
+//! followed in the original document, or at the start of their parent if they came first.
+//! Preserved attributes are written after the element's own attributes.
+//!
+//! Preserved elements are kept as [`RawElement`]s with their full names, so they're written with
+//! the same namespace prefixes they were read with. Prefixed attributes of elements in the
+//! document model keep their prefixes too, e.g. the `xsi:schemaLocation` that many exporters put
+//! on the root element.
+//!
+//! Preserved content is located by the path to its parent element, so it should only be written
+//! with the document it was parsed with. Content whose parent isn't written is dropped, and
+//! content in a parent whose earlier children were removed may end up in a different position.
+//!
+//! [`coverage`]: ../coverage/index.html
+//! [`RawElement`]: ../raw/struct.RawElement.html
+//! [`ParseOptions::preserve_unknown`]: ../options/struct.ParseOptions.html#structfield.preserve_unknown
+//! [`ParseReport::preserved`]: ../report/struct.ParseReport.html#structfield.preserved
+//! [`WriteOptions::preserved`]: ../writer/struct.WriteOptions.html#structfield.preserved
+
+use raw::RawElement;
+use std::collections::HashMap;
+use xml::name::OwnedName;
+
+/// Content from a document that isn't part of the document model, see the
+/// [module documentation](./index.html) for details.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreservedContent {
+    /// The preserved content inside of each element, keyed by the element's path.
+    elements: HashMap<String, PreservedElement>,
+}
+
+/// The preserved content inside of a single element.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct PreservedElement {
+    attributes: Vec<(OwnedName, String)>,
+    children: Vec<PreservedChild>,
+}
+
+/// A preserved element, and where to put it within its parent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PreservedChild {
+    /// The path segment of the sibling that the element followed, or `None` if it came before
+    /// all of the siblings that are part of the document model.
+    pub after: Option<String>,
+
+    pub element: RawElement,
+}
+
+impl PreservedContent {
+    /// Returns `true` if nothing was preserved.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns the number of attributes and elements that were preserved.
+    ///
+    /// Elements are counted once, regardless of how many elements are inside of them.
+    pub fn len(&self) -> usize {
+        self.elements.values()
+            .map(|element| element.attributes.len() + element.children.len())
+            .sum()
+    }
+
+    pub(crate) fn add_attribute(&mut self, path: &str, name: OwnedName, value: String) {
+        self.element_mut(path).attributes.push((name, value));
+    }
+
+    pub(crate) fn add_child(&mut self, path: &str, after: Option<String>, element: RawElement) {
+        self.element_mut(path).children.push(PreservedChild { after, element });
+    }
+
+    /// Returns the preserved attributes of the element at `path`.
+    pub(crate) fn attributes(&self, path: &str) -> &[(OwnedName, String)] {
+        self.elements.get(path).map(|element| &*element.attributes).unwrap_or(&[])
+    }
+
+    /// Returns the preserved children of the element at `path`, in document order.
+    pub(crate) fn children(&self, path: &str) -> &[PreservedChild] {
+        self.elements.get(path).map(|element| &*element.children).unwrap_or(&[])
+    }
+
+    fn element_mut(&mut self, path: &str) -> &mut PreservedElement {
+        self.elements.entry(path.into()).or_default()
+    }
+}
+
+/// Tracks the path to the current element while a document is read or written.
+///
+/// Each element is identified by its name and its index among the siblings with the same name,
+/// e.g. `COLLADA[0]/library_geometries[1]/geometry[0]`. Unlike a plain index among all siblings,
+/// this stays the same when the writer puts elements in a different order than the original
+/// document, or writes elements that were left out of it because they have default values.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ElementPath {
+    path: String,
+    frames: Vec<PathFrame>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct PathFrame {
+    /// The length of the path to the element's parent.
+    parent_len: usize,
+
+    /// The number of children started so far with each name.
+    counts: HashMap<String, usize>,
+
+    /// The path segment of the last child that's part of the document model, not counting
+    /// `current`.
+    last_known: Option<String>,
+
+    /// The path segment of the most recently started child, unless it was preserved.
+    current: Option<String>,
+}
+
+impl ElementPath {
+    pub fn new() -> ElementPath {
+        ElementPath::default()
+    }
+
+    /// Returns the path to the current element.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Enters a child of the current element.
+    pub fn start(&mut self, name: &str) {
+        let segment = match self.frames.last_mut() {
+            Some(frame) => {
+                let count = frame.counts.entry(name.into()).or_insert(0);
+                let segment = format!("{}[{}]", name, count);
+                *count += 1;
+
+                if let Some(previous) = frame.current.take() {
+                    frame.last_known = Some(previous);
+                }
+                frame.current = Some(segment.clone());
+                segment
+            }
+
+            None => format!("{}[0]", name),
+        };
+
+        let parent_len = self.path.len();
+        if !self.path.is_empty() {
+            self.path.push('/');
+        }
+        self.path.push_str(&segment);
+        self.frames.push(PathFrame { parent_len, ..PathFrame::default() });
+    }
+
+    /// Leaves the current element, returning its path segment within its parent.
+    pub fn end(&mut self) -> Option<String> {
+        let frame = self.frames.pop()?;
+        let segment = self.path[frame.parent_len..].trim_start_matches('/').to_owned();
+        self.path.truncate(frame.parent_len);
+        Some(segment)
+    }
+
+    /// Marks the current element as preserved rather than part of the document model, returning
+    /// the path segment of the sibling it follows.
+    pub fn preserve_current(&mut self) -> Option<String> {
+        let parent_index = self.frames.len().checked_sub(2)?;
+        let parent = &mut self.frames[parent_index];
+        parent.current = None;
+        parent.last_known.clone()
+    }
+}
@@ -0,0 +1,195 @@
+//! Typed decoders for well-known vendor extensions.
+//!
+//! Exporters store application-specific data in `<extra>` elements, in a `<technique>` named
+//! after the exporter's profile. The contents of those techniques have no fixed schema, but a few
+//! of them are common enough to be worth decoding: the double sided flags written by Blender,
+//! OpenCOLLADA, and SketchUp, and the extra texture slots written by 3ds Max and Feeling
+//! Software's exporters.
+//!
+//! Each supported profile is a type implementing [`ExtraProfile`], which can be decoded from any
+//! `<extra>` with `Extra::decode`. Values that are missing or that can't be parsed are left as
+//! `None`, so decoding never fails outright.
+//!
+//! # Examples
+//!
+//! ```
+//! use collaborate::profiles::GoogleEarthProfile;
+//! use collaborate::v1_4::Collada;
+//!
+//! let source = r#"
+//!     <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+//!         <asset>
+//!             <created>2017-02-07T20:44:30Z</created>
+//!             <modified>2017-02-07T20:44:30Z</modified>
+//!         </asset>
+//!         <extra>
+//!             <technique profile="GOOGLEEARTH">
+//!                 <double_sided>1</double_sided>
+//!             </technique>
+//!         </extra>
+//!     </COLLADA>
+//! "#;
+//! let document = Collada::from_str(source).unwrap();
+//!
+//! let google_earth = document.extras[0].decode::<GoogleEarthProfile>().unwrap();
+//! assert_eq!(Some(true), google_earth.double_sided);
+//! ```
+//!
+//! [`ExtraProfile`]: ./trait.ExtraProfile.html
+
+use raw::RawElement;
+
+/// The contents of a `<technique>` for a specific profile.
+pub trait ExtraProfile: Sized {
+    /// The value of the `profile` attribute for techniques holding this data.
+    fn profile() -> &'static str;
+
+    /// Decodes the elements inside of a technique with the matching profile.
+    fn decode(elements: &[RawElement]) -> Self;
+}
+
+/// A texture referenced by a vendor extension, e.g. a bump map.
+///
+/// This mirrors the `<texture>` element of the common profile: `texture` is the sid of a
+/// sampler parameter in the effect, and `texcoord` is the texture coordinate semantic that is
+/// bound when the material is instantiated.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExtensionTexture {
+    /// The sid of the sampler parameter used to sample the texture.
+    pub texture: String,
+
+    /// The texture coordinate semantic to sample with.
+    pub texcoord: String,
+}
+
+/// Extensions written by Blender's COLLADA exporter, with the `"blender"` profile.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BlenderProfile {
+    /// Whether both sides of the faces should be rendered.
+    pub double_sided: Option<bool>,
+}
+
+impl ExtraProfile for BlenderProfile {
+    fn profile() -> &'static str { "blender" }
+
+    fn decode(elements: &[RawElement]) -> BlenderProfile {
+        BlenderProfile {
+            double_sided: bool_value(elements, "double_sided"),
+        }
+    }
+}
+
+/// Extensions written by the OpenCOLLADA exporters, with the `"OpenCOLLADA"` profile.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OpenColladaProfile {
+    /// Whether both sides of the faces should be rendered.
+    pub double_sided: Option<bool>,
+}
+
+impl ExtraProfile for OpenColladaProfile {
+    fn profile() -> &'static str { "OpenCOLLADA" }
+
+    fn decode(elements: &[RawElement]) -> OpenColladaProfile {
+        OpenColladaProfile {
+            double_sided: bool_value(elements, "double_sided"),
+        }
+    }
+}
+
+/// Extensions written by the 3ds Max exporters, with the `"MAX3D"` profile.
+///
+/// These usually appear in the `<extra>` of an effect's technique, and add texture slots that
+/// the common profile doesn't have.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Max3dProfile {
+    /// Whether the ambient color is locked to the diffuse color.
+    pub ambient_diffuse_lock: Option<bool>,
+
+    /// Whether the ambient texture is locked to the diffuse texture.
+    pub ambient_diffuse_texture_lock: Option<bool>,
+
+    /// The texture used for the ambient color.
+    pub ambient: Option<ExtensionTexture>,
+
+    /// The texture used as a bump map.
+    pub bump: Option<ExtensionTexture>,
+}
+
+impl ExtraProfile for Max3dProfile {
+    fn profile() -> &'static str { "MAX3D" }
+
+    fn decode(elements: &[RawElement]) -> Max3dProfile {
+        Max3dProfile {
+            ambient_diffuse_lock: bool_value(elements, "ambient_diffuse_lock"),
+            ambient_diffuse_texture_lock: bool_value(elements, "ambient_diffuse_texture_lock"),
+            ambient: texture_value(elements, "ambient"),
+            bump: texture_value(elements, "bump"),
+        }
+    }
+}
+
+/// Extensions written by Feeling Software's exporters, with the `"FCOLLADA"` profile.
+///
+/// Like [`Max3dProfile`], these add texture slots to an effect's technique.
+///
+/// [`Max3dProfile`]: ./struct.Max3dProfile.html
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FColladaProfile {
+    /// The texture used for the ambient color.
+    pub ambient: Option<ExtensionTexture>,
+
+    /// The texture used as a bump map.
+    pub bump: Option<ExtensionTexture>,
+}
+
+impl ExtraProfile for FColladaProfile {
+    fn profile() -> &'static str { "FCOLLADA" }
+
+    fn decode(elements: &[RawElement]) -> FColladaProfile {
+        FColladaProfile {
+            ambient: texture_value(elements, "ambient"),
+            bump: texture_value(elements, "bump"),
+        }
+    }
+}
+
+/// Extensions written by Google Earth and SketchUp, with the `"GOOGLEEARTH"` profile.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GoogleEarthProfile {
+    /// Whether both sides of the faces should be rendered.
+    pub double_sided: Option<bool>,
+}
+
+impl ExtraProfile for GoogleEarthProfile {
+    fn profile() -> &'static str { "GOOGLEEARTH" }
+
+    fn decode(elements: &[RawElement]) -> GoogleEarthProfile {
+        GoogleEarthProfile {
+            double_sided: bool_value(elements, "double_sided"),
+        }
+    }
+}
+
+/// Finds the element named `name` in `elements`.
+fn find<'a>(elements: &'a [RawElement], name: &str) -> Option<&'a RawElement> {
+    elements.iter().find(|element| element.name.local_name == name)
+}
+
+/// Parses the text of the element named `name` as a boolean, which exporters write as either
+/// `1`/`0` or `true`/`false`.
+fn bool_value(elements: &[RawElement], name: &str) -> Option<bool> {
+    match find(elements, name)?.text.trim() {
+        "1" | "true" => Some(true),
+        "0" | "false" => Some(false),
+        _ => None,
+    }
+}
+
+/// Reads the `<texture>` inside of the element named `name`.
+fn texture_value(elements: &[RawElement], name: &str) -> Option<ExtensionTexture> {
+    let texture = find(elements, name)?.child("texture")?;
+    Some(ExtensionTexture {
+        texture: texture.attribute("texture")?.into(),
+        texcoord: texture.attribute("texcoord").unwrap_or("").into(),
+    })
+}
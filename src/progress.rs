@@ -0,0 +1,154 @@
+//! Progress reporting for long parses.
+//!
+//! Large documents can take several seconds to parse. Setting a callback with
+//! [`ParseOptions::progress`] lets applications show how far along parsing is, e.g. with a progress
+//! bar in an importer's UI.
+//!
+//! The callback is called as each library in the document begins, and after every 64 KiB of the
+//! document that's read.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::fs::File;
+//! use collaborate::{ParseOptions, Progress, VersionedDocument};
+//!
+//! let file = File::open("resources/blender_cube.dae").unwrap();
+//! let options = ParseOptions::default().progress(|progress: Progress| {
+//!     println!("{} bytes read, in {}", progress.bytes_read, progress.path.join("/"));
+//! });
+//! let _document = VersionedDocument::read_with(file, options).unwrap();
+//! ```
+//!
+//! To also report the size of the document, construct the [`ProgressCallback`] directly:
+//!
+//! ```
+//! use std::fs::File;
+//! use collaborate::{ParseOptions, Progress, ProgressCallback, VersionedDocument};
+//!
+//! let file = File::open("resources/blender_cube.dae").unwrap();
+//! let total = file.metadata().unwrap().len();
+//! let callback = ProgressCallback::new(|progress: Progress| {
+//!     let percent = 100 * progress.bytes_read / progress.total_bytes.unwrap();
+//!     println!("{}% done", percent);
+//! });
+//!
+//! let options = ParseOptions {
+//!     progress: Some(callback.with_total_bytes(total)),
+//!     ..ParseOptions::default()
+//! };
+//! let _document = VersionedDocument::read_with(file, options).unwrap();
+//! ```
+//!
+//! [`ParseOptions::progress`]: ../options/struct.ParseOptions.html#method.progress
+//! [`ProgressCallback`]: ./struct.ProgressCallback.html
+
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// How many bytes are read between progress reports.
+pub(crate) const REPORT_INTERVAL: u64 = 64 * 1024;
+
+/// How far parsing a document has gotten.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Progress {
+    /// The number of bytes of the document that have been read so far.
+    ///
+    /// Documents in other encodings are counted after being decoded to UTF-8. With the
+    /// `quick-xml` feature enabled, the whole document is read before parsing begins, so this
+    /// reaches the size of the document right away.
+    pub bytes_read: u64,
+
+    /// The size of the document in bytes, if it was given with
+    /// [`ProgressCallback::with_total_bytes`](./struct.ProgressCallback.html#method.with_total_bytes).
+    pub total_bytes: Option<u64>,
+
+    /// The names of the elements currently being parsed, starting with `COLLADA`.
+    pub path: Vec<String>,
+}
+
+/// A progress callback, set with [`ParseOptions::progress`].
+///
+/// Clones of a `ProgressCallback` share the same callback, so parsing several documents at once
+/// with the same options will interleave their progress.
+///
+/// [`ParseOptions::progress`]: ../options/struct.ParseOptions.html#method.progress
+#[derive(Clone)]
+pub struct ProgressCallback {
+    shared: Arc<Shared>,
+    total_bytes: Option<u64>,
+}
+
+struct Shared {
+    callback: Mutex<Box<FnMut(Progress) + Send>>,
+    bytes_read: AtomicU64,
+}
+
+impl ProgressCallback {
+    /// Wraps `callback` so that it can be set in [`ParseOptions`].
+    ///
+    /// [`ParseOptions`]: ../options/struct.ParseOptions.html
+    pub fn new<F: FnMut(Progress) + Send + 'static>(callback: F) -> ProgressCallback {
+        ProgressCallback {
+            shared: Arc::new(Shared {
+                callback: Mutex::new(Box::new(callback)),
+                bytes_read: AtomicU64::new(0),
+            }),
+            total_bytes: None,
+        }
+    }
+
+    /// Sets the size of the document, to be reported as [`Progress::total_bytes`].
+    ///
+    /// The size of a document can't be determined while reading it, so use this when it's known
+    /// ahead of time, e.g. from the size of the file the document is read from.
+    ///
+    /// [`Progress::total_bytes`]: ./struct.Progress.html#structfield.total_bytes
+    pub fn with_total_bytes(mut self, total_bytes: u64) -> ProgressCallback {
+        self.total_bytes = Some(total_bytes);
+        self
+    }
+
+    /// Resets the progress at the start of a document.
+    pub(crate) fn start(&self) {
+        self.shared.bytes_read.store(0, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_bytes(&self, bytes: usize) {
+        self.shared.bytes_read.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn bytes_read(&self) -> u64 {
+        self.shared.bytes_read.load(Ordering::Relaxed)
+    }
+
+    /// Calls the callback with the current progress.
+    ///
+    /// If the callback has panicked before, it isn't called again.
+    pub(crate) fn report(&self, path: &[String]) {
+        let progress = Progress {
+            bytes_read: self.bytes_read(),
+            total_bytes: self.total_bytes,
+            path: path.to_vec(),
+        };
+
+        if let Ok(mut callback) = self.shared.callback.lock() {
+            (*callback)(progress);
+        }
+    }
+}
+
+impl Debug for ProgressCallback {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("ProgressCallback")
+    }
+}
+
+impl PartialEq for ProgressCallback {
+    fn eq(&self, other: &ProgressCallback) -> bool {
+        Arc::ptr_eq(&self.shared, &other.shared) && self.total_bytes == other.total_bytes
+    }
+}
+
+impl Eq for ProgressCallback {}
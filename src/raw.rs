@@ -0,0 +1,192 @@
+//! Capturing arbitrary XML as a tree of elements.
+//!
+//! Not everything in a COLLADA document is modeled by this crate: `<extra>` and
+//! `<technique profile="...">` elements hold application-specific data with no fixed schema, and
+//! newer exporters may produce elements that aren't supported yet. [`RawElement`] captures any
+//! such subtree as plain names, attributes, and text, so that it can still be inspected or
+//! preserved.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate collaborate;
+//! # extern crate xml;
+//! use collaborate::raw::RawElement;
+//! use xml::reader::EventReader;
+//!
+//! # fn main() {
+//! let source = r#"<layer name="Base"><color>1 0 0</color><visible /></layer>"#;
+//! let element = RawElement::parse(&mut EventReader::from_str(source)).unwrap();
+//!
+//! assert_eq!("layer", element.name.local_name);
+//! assert_eq!(Some("Base"), element.attribute("name"));
+//! assert_eq!(Some("1 0 0"), element.child("color").map(|color| &*color.text));
+//! # }
+//! ```
+//!
+//! [`RawElement`]: ./struct.RawElement.html
+
+use {Result, XmlError, XmlPull};
+use xml::attribute::OwnedAttribute;
+use xml::name::OwnedName;
+use xml::reader::XmlEvent;
+
+/// An XML element and everything inside of it, without any interpretation.
+///
+/// Names are kept in full, including their prefix and namespace, so that the element can be
+/// written back out as it was read. Looking up attributes and children by name compares local
+/// names, i.e. ignores any prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawElement {
+    /// The name of the element.
+    pub name: OwnedName,
+
+    /// The element's attributes as `(name, value)` pairs, in the order they appear in the
+    /// document.
+    pub attributes: Vec<(OwnedName, String)>,
+
+    /// The elements directly inside of this element, in document order.
+    pub children: Vec<RawElement>,
+
+    /// All of the text directly inside of this element (not including the text inside of its
+    /// children), concatenated together.
+    pub text: String,
+}
+
+impl RawElement {
+    /// Reads the next element from `events`, along with everything inside of it.
+    ///
+    /// Any events before the element begins (e.g. the start of the document) are skipped. Parsing
+    /// stops after the element's end tag, so the rest of the document can still be read from
+    /// `events` afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the document is malformed, or if it ends (or the enclosing element ends)
+    /// before an element is found.
+    pub fn parse<R: XmlPull>(events: &mut R) -> Result<RawElement> {
+        let mut builder = TreeBuilder::default();
+        loop {
+            let event = events.next()?;
+            match event {
+                XmlEvent::StartElement { .. } => {
+                    builder.push(event);
+                    break;
+                }
+
+                XmlEvent::EndElement { .. } | XmlEvent::EndDocument => {
+                    let error = XmlError::from((&*events, "Expected the start of an element"));
+                    return Err(error.into());
+                }
+
+                _ => {}
+            }
+        }
+
+        while builder.is_open() {
+            let event = events.next()?;
+            if let XmlEvent::EndDocument = event {
+                let error = XmlError::from((&*events, "Unexpected end of document"));
+                return Err(error.into());
+            }
+
+            builder.push(event);
+        }
+
+        Ok(builder.finished.pop().expect("Element wasn't finished"))
+    }
+
+    /// Builds the elements represented by a sequence of XML events, such as the contents of a
+    /// [`Technique`](../common/struct.Technique.html).
+    ///
+    /// Text outside of any element is ignored, as are any elements that aren't closed.
+    pub fn from_events<'a, I>(events: I) -> Vec<RawElement>
+    where I: IntoIterator<Item = &'a XmlEvent>
+    {
+        let mut builder = TreeBuilder::default();
+        for event in events {
+            builder.push(event.clone());
+        }
+        builder.finished
+    }
+
+    /// Returns the value of the attribute `name`, if the element has one.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter()
+            .find(|attribute| attribute.0.local_name == name)
+            .map(|attribute| &*attribute.1)
+    }
+
+    /// Returns the first child element named `name`, if there is one.
+    pub fn child(&self, name: &str) -> Option<&RawElement> {
+        self.children.iter().find(|child| child.name.local_name == name)
+    }
+
+    /// Returns an iterator over the child elements named `name`.
+    pub fn children_named<'a>(&'a self, name: &'a str) -> Box<Iterator<Item = &'a RawElement> + 'a> {
+        Box::new(self.children.iter().filter(move |child| child.name.local_name == name))
+    }
+
+    fn new(name: OwnedName, attributes: Vec<OwnedAttribute>) -> RawElement {
+        RawElement {
+            name,
+            attributes: attributes.into_iter()
+                .map(|attribute| (attribute.name, attribute.value))
+                .collect(),
+            children: Vec::new(),
+            text: String::new(),
+        }
+    }
+}
+
+impl Default for RawElement {
+    fn default() -> RawElement {
+        RawElement {
+            name: OwnedName::local(""),
+            attributes: Vec::new(),
+            children: Vec::new(),
+            text: String::new(),
+        }
+    }
+}
+
+/// Assembles elements from XML events as they're read.
+#[derive(Default)]
+struct TreeBuilder {
+    /// The elements that have been started but not yet ended, innermost last.
+    open: Vec<RawElement>,
+
+    /// The outermost elements that have ended.
+    finished: Vec<RawElement>,
+}
+
+impl TreeBuilder {
+    fn is_open(&self) -> bool {
+        !self.open.is_empty()
+    }
+
+    fn push(&mut self, event: XmlEvent) {
+        match event {
+            XmlEvent::StartElement { name, attributes, .. } => {
+                self.open.push(RawElement::new(name, attributes));
+            }
+
+            XmlEvent::EndElement { .. } => {
+                if let Some(element) = self.open.pop() {
+                    match self.open.last_mut() {
+                        Some(parent) => parent.children.push(element),
+                        None => self.finished.push(element),
+                    }
+                }
+            }
+
+            XmlEvent::Characters(text) | XmlEvent::CData(text) => {
+                if let Some(element) = self.open.last_mut() {
+                    element.text.push_str(&text);
+                }
+            }
+
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,250 @@
+//! Reporting on which workarounds were needed to parse a document.
+//!
+//! Documents in the wild don't always conform to the COLLADA specification, and not every part of
+//! the specification is supported yet. Rather than rejecting such documents outright, the parser
+//! will sometimes fall back to more lenient handling, paper over known exporter quirks, or skip
+//! the contents of elements it doesn't support (see the [`coverage`] module). A [`ParseReport`]
+//! records every time one of these workarounds was exercised while parsing a document, making it
+//! possible to quantify how much of an asset corpus relies on them.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::fs::File;
+//! use collaborate::VersionedDocument;
+//!
+//! let file = File::open("resources/blender_cube.dae").unwrap();
+//! let (_document, report) = VersionedDocument::read_with_report(file).unwrap();
+//! for (element, count) in &report.stubbed_elements {
+//!     println!("Skipped {} <{}> element(s)", count, element);
+//! }
+//! ```
+//!
+//! [`coverage`]: ../coverage/index.html
+//! [`ParseReport`]: ./struct.ParseReport.html
+
+use {Error, TextPosition};
+use preserve::PreservedContent;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+/// The workarounds exercised while parsing a single document.
+///
+/// Each category maps a description of the workaround to the number of times it was needed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseReport {
+    /// Elements whose contents were skipped because they aren't supported yet, keyed by element
+    /// name.
+    pub stubbed_elements: BTreeMap<String, usize>,
+
+    /// Places where content that doesn't conform to the specification was accepted anyway.
+    pub fallbacks: BTreeMap<String, usize>,
+
+    /// Known deviations between exporters (or versions of the specification) that were smoothed
+    /// over.
+    pub quirks: BTreeMap<String, usize>,
+
+    /// Problems that would have been errors, but were tolerated because of the
+    /// [`Strictness`](../options/enum.Strictness.html) the document was parsed with.
+    pub warnings: Vec<Error>,
+
+    /// Errors in elements that were skipped so that parsing could continue, if the document was
+    /// parsed with [`ParseOptions::recover`](../options/struct.ParseOptions.html#structfield.recover)
+    /// set.
+    pub errors: Vec<Error>,
+
+    /// The location in the document of each element with an `id` attribute, keyed by id.
+    ///
+    /// Only recorded if the document was parsed with
+    /// [`ParseOptions::track_spans`](../options/struct.ParseOptions.html#structfield.track_spans)
+    /// set. Spans aren't workarounds, so they're ignored by `is_clean` and `merge`.
+    pub spans: BTreeMap<String, SourceSpan>,
+
+    /// The index in document order of each element with an `id` attribute, keyed by id.
+    ///
+    /// Every element is counted, starting from 0 for the root `<COLLADA>` element, including any
+    /// that were skipped. Only recorded if the document was parsed with
+    /// [`ParseOptions::track_order`](../options/struct.ParseOptions.html#structfield.track_order)
+    /// set. Like spans, the order is ignored by `is_clean` and `merge`.
+    pub document_order: BTreeMap<String, usize>,
+
+    /// The content of the document that isn't part of the document model.
+    ///
+    /// Only recorded if the document was parsed with
+    /// [`ParseOptions::preserve_unknown`](../options/struct.ParseOptions.html#structfield.preserve_unknown)
+    /// set. The content is still reported by the other categories (e.g. as warnings), so it's
+    /// ignored by `is_clean` and `merge`.
+    pub preserved: PreservedContent,
+}
+
+impl ParseReport {
+    /// Returns `true` if the document was parsed without needing any workarounds.
+    pub fn is_clean(&self) -> bool {
+        self.stubbed_elements.is_empty()
+            && self.fallbacks.is_empty()
+            && self.quirks.is_empty()
+            && self.warnings.is_empty()
+            && self.errors.is_empty()
+    }
+
+    /// Adds the counts, warnings, and errors from `other` to this report.
+    ///
+    /// This is useful for aggregating the reports for many documents.
+    pub fn merge(&mut self, other: &ParseReport) {
+        fn merge_counts(into: &mut BTreeMap<String, usize>, from: &BTreeMap<String, usize>) {
+            for (key, count) in from {
+                *into.entry(key.clone()).or_insert(0) += *count;
+            }
+        }
+
+        merge_counts(&mut self.stubbed_elements, &other.stubbed_elements);
+        merge_counts(&mut self.fallbacks, &other.fallbacks);
+        merge_counts(&mut self.quirks, &other.quirks);
+        self.warnings.extend(other.warnings.iter().cloned());
+        self.errors.extend(other.errors.iter().cloned());
+    }
+
+    /// Returns the location in the document of the element with the given id, if spans were
+    /// tracked and the document contains such an element.
+    pub fn span(&self, id: &str) -> Option<SourceSpan> {
+        self.spans.get(id).cloned()
+    }
+
+    /// Returns the index in document order of the element with the given id, if the order was
+    /// tracked and the document contains such an element.
+    pub fn document_index(&self, id: &str) -> Option<usize> {
+        self.document_order.get(id).cloned()
+    }
+
+    /// Returns the ids of the elements whose order was tracked, in the order they appear in the
+    /// document.
+    pub fn ids_in_document_order(&self) -> Vec<&str> {
+        let mut ids = self.document_order.iter().collect::<Vec<_>>();
+        ids.sort_by_key(|&(_, &index)| index);
+        ids.into_iter().map(|(id, _)| &**id).collect()
+    }
+
+    /// Converts the report into a flat list of warnings.
+    ///
+    /// Recovered errors come first, then tolerated problems, each in the order they were
+    /// encountered, followed by the stubbed elements, fallbacks, and quirks.
+    pub fn into_warnings(self) -> Vec<Warning> {
+        let mut warnings = self.errors.into_iter()
+            .map(Warning::Recovered)
+            .collect::<Vec<_>>();
+        warnings.extend(self.warnings.into_iter().map(Warning::Tolerated));
+        warnings.extend(self.stubbed_elements.into_iter()
+            .map(|(element, count)| Warning::Stubbed { element, count }));
+        warnings.extend(self.fallbacks.into_iter()
+            .map(|(description, count)| Warning::Fallback { description, count }));
+        warnings.extend(self.quirks.into_iter()
+            .map(|(description, count)| Warning::Quirk { description, count }));
+        warnings
+    }
+
+    pub(crate) fn record_stubbed(&mut self, element: &str) {
+        increment(&mut self.stubbed_elements, element);
+    }
+
+    pub(crate) fn record_fallback(&mut self, description: &str) {
+        increment(&mut self.fallbacks, description);
+    }
+
+    pub(crate) fn record_quirk(&mut self, description: &str) {
+        increment(&mut self.quirks, description);
+    }
+
+    pub(crate) fn record_warning(&mut self, warning: Error) {
+        self.warnings.push(warning);
+    }
+
+    pub(crate) fn record_error(&mut self, error: Error) {
+        self.errors.push(error);
+    }
+
+    pub(crate) fn record_span(&mut self, id: &str, span: SourceSpan) {
+        // Ids are supposed to be unique, but if they aren't the first element is the one that
+        // references resolve to.
+        if !self.spans.contains_key(id) {
+            self.spans.insert(id.into(), span);
+        }
+    }
+
+    pub(crate) fn record_document_index(&mut self, id: &str, index: usize) {
+        // The same as with spans, the first element with a duplicated id is the one recorded.
+        if !self.document_order.contains_key(id) {
+            self.document_order.insert(id.into(), index);
+        }
+    }
+}
+
+/// The location of an element in its document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceSpan {
+    /// The position of the element's start tag.
+    pub start: TextPosition,
+
+    /// The position of the element's end tag.
+    pub end: TextPosition,
+}
+
+/// A single recoverable issue encountered while parsing a document.
+///
+/// Created with [`ParseReport::into_warnings`](./struct.ParseReport.html#method.into_warnings).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    /// A problem that would have been an error, but was tolerated because of the strictness the
+    /// document was parsed with.
+    Tolerated(Error),
+
+    /// An element failed to parse, and was skipped so that parsing could continue.
+    Recovered(Error),
+
+    /// Elements whose contents were skipped because they aren't supported yet.
+    Stubbed {
+        /// The name of the skipped element.
+        element: String,
+
+        /// The number of times the element was skipped.
+        count: usize,
+    },
+
+    /// Content that doesn't conform to the specification was accepted anyway.
+    Fallback {
+        description: String,
+        count: usize,
+    },
+
+    /// A known deviation between exporters (or versions of the specification) was smoothed over.
+    Quirk {
+        description: String,
+        count: usize,
+    },
+}
+
+impl Display for Warning {
+    fn fmt(&self, formatter: &mut Formatter) -> ::std::result::Result<(), fmt::Error> {
+        match *self {
+            Warning::Tolerated(ref error) => write!(formatter, "{}", error),
+            Warning::Recovered(ref error) => write!(formatter, "Skipped element: {}", error),
+            Warning::Stubbed { ref element, count } => {
+                write!(formatter, "Skipped unsupported <{}> element ({} times)", element, count)
+            }
+            Warning::Fallback { ref description, count } => {
+                write!(formatter, "Fallback: {} ({} times)", description, count)
+            }
+            Warning::Quirk { ref description, count } => {
+                write!(formatter, "Quirk: {} ({} times)", description, count)
+            }
+        }
+    }
+}
+
+fn increment(counts: &mut BTreeMap<String, usize>, key: &str) {
+    if let Some(count) = counts.get_mut(key) {
+        *count += 1;
+        return;
+    }
+
+    counts.insert(key.into(), 1);
+}
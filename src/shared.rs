@@ -0,0 +1,137 @@
+//! Sharing a parsed document between threads.
+//!
+//! Parsed documents are plain data, so they're `Send` and `Sync` and can already be shared with
+//! an `Arc`. [`SharedDocument`] wraps that up with cheap clones, and hands out [`SharedLibrary`]
+//! handles that each keep the whole document alive, so that e.g. a renderer thread and an asset
+//! conditioning thread can each hold on to just the libraries they work with.
+//!
+//! Shared documents are currently only supported for `1.4.0` and `1.4.1` documents.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::fs::File;
+//! use std::thread;
+//! use collaborate::shared::SharedDocument;
+//! use collaborate::v1_4::Collada;
+//!
+//! let file = File::open("resources/blender_cube.dae").unwrap();
+//! let document = SharedDocument::new(Collada::read(file).unwrap());
+//!
+//! let workers = document.geometries()
+//!     .map(|library| thread::spawn(move || {
+//!         let geometries = library.as_library_geometries().unwrap();
+//!         geometries.geometries.len()
+//!     }))
+//!     .collect::<Vec<_>>();
+//!
+//! for worker in workers {
+//!     println!("Found {} geometries", worker.join().unwrap());
+//! }
+//! ```
+//!
+//! [`SharedDocument`]: ./struct.SharedDocument.html
+//! [`SharedLibrary`]: ./struct.SharedLibrary.html
+
+use std::ops::Deref;
+use std::sync::Arc;
+use v1_4::{Collada, Library};
+
+/// A parsed document that can be cheaply cloned and shared between threads.
+///
+/// Dereferences to the [`Collada`](../v1_4/struct.Collada.html) document.
+#[derive(Debug, Clone)]
+pub struct SharedDocument {
+    document: Arc<Collada>,
+}
+
+impl SharedDocument {
+    /// Wraps `document` so that it can be shared.
+    pub fn new(document: Collada) -> SharedDocument {
+        SharedDocument {
+            document: Arc::new(document),
+        }
+    }
+
+    /// Returns a handle to the library at `index` in document order, if there is one.
+    pub fn library(&self, index: usize) -> Option<SharedLibrary> {
+        if index < self.document.libraries.len() {
+            Some(SharedLibrary {
+                document: self.clone(),
+                index,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over handles to all of the libraries in the document, in document
+    /// order.
+    pub fn libraries<'a>(&'a self) -> Box<Iterator<Item = SharedLibrary> + 'a> {
+        Box::new((0..self.document.libraries.len()).map(move |index| SharedLibrary {
+            document: self.clone(),
+            index,
+        }))
+    }
+
+    /// Returns an iterator over handles to all of the `<library_geometries>` elements in the
+    /// document.
+    pub fn geometries<'a>(&'a self) -> Box<Iterator<Item = SharedLibrary> + 'a> {
+        Box::new(self.libraries().filter(|library| library.as_library_geometries().is_some()))
+    }
+
+    /// Returns the document, if this is the only handle to it (including any
+    /// [`SharedLibrary`](./struct.SharedLibrary.html) handles).
+    ///
+    /// # Errors
+    ///
+    /// Returns the `SharedDocument` back if there are other handles to the document.
+    pub fn try_unwrap(self) -> ::std::result::Result<Collada, SharedDocument> {
+        Arc::try_unwrap(self.document).map_err(|document| SharedDocument { document })
+    }
+}
+
+impl Deref for SharedDocument {
+    type Target = Collada;
+
+    fn deref(&self) -> &Collada {
+        &self.document
+    }
+}
+
+impl From<Collada> for SharedDocument {
+    fn from(document: Collada) -> SharedDocument {
+        SharedDocument::new(document)
+    }
+}
+
+/// A handle to a single library in a [`SharedDocument`], which keeps the document alive.
+///
+/// Dereferences to the [`Library`](../v1_4/enum.Library.html).
+///
+/// [`SharedDocument`]: ./struct.SharedDocument.html
+#[derive(Debug, Clone)]
+pub struct SharedLibrary {
+    document: SharedDocument,
+    index: usize,
+}
+
+impl SharedLibrary {
+    /// Returns the document that the library is in.
+    pub fn document(&self) -> &SharedDocument {
+        &self.document
+    }
+
+    /// Returns the index of the library in document order.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl Deref for SharedLibrary {
+    type Target = Library;
+
+    fn deref(&self) -> &Library {
+        &self.document.libraries[self.index]
+    }
+}
@@ -0,0 +1,275 @@
+//! Incremental parsing for large documents.
+//!
+//! Parsing a document with [`v1_4::Collada::read`] builds the entire document in memory at once,
+//! which can be prohibitive for very large files when only part of the data is needed. The types
+//! in this module instead pull data from the document as it's requested, so that only the
+//! library (or geometry) currently being processed needs to be held in memory.
+//!
+//! Streaming is currently only supported for `1.4.0` and `1.4.1` documents.
+//!
+//! # Examples
+//!
+//! Process each library in a document one at a time:
+//!
+//! ```
+//! use std::fs::File;
+//! use collaborate::stream::DocumentReader;
+//!
+//! let file = File::open("resources/blender_cube.dae").unwrap();
+//! let mut reader = DocumentReader::new(file).unwrap();
+//! while let Some(library) = reader.next_library().unwrap() {
+//!     println!("Library: {:?}", library);
+//! }
+//! ```
+//!
+//! Process each geometry in a document one at a time, skipping all other libraries:
+//!
+//! ```
+//! use std::fs::File;
+//! use collaborate::stream::DocumentReader;
+//!
+//! let file = File::open("resources/blender_cube.dae").unwrap();
+//! for geometry in DocumentReader::new(file).unwrap().into_geometries() {
+//!     let geometry = geometry.unwrap();
+//!     println!("Geometry: {:?}", geometry.id);
+//! }
+//! ```
+//!
+//! [`v1_4::Collada::read`]: ../v1_4/struct.Collada.html#method.read
+
+use {Error, ErrorKind, ParseOptions, Result, Version};
+use backend::Source;
+use dtd::DtdFilter;
+use encoding::Transcode;
+use std::io::Read;
+use utils::{self, ColladaElement, ElementStart, ParseContext};
+use v1_4::{Asset, Geometry, Library};
+use xml::common::Position;
+use xml::reader::EventReader;
+
+/// Reads the libraries of a COLLADA document one at a time.
+///
+/// Creating a `DocumentReader` parses the opening `<COLLADA>` tag and the document's `<asset>`,
+/// and then each call to [`next_library`] parses the next library in the document. Once the
+/// libraries have been exhausted the rest of the document (i.e. the `<scene>` and any `<extra>`
+/// elements) is skipped.
+///
+/// [`next_library`]: #method.next_library
+pub struct DocumentReader<R: Read> {
+    reader: utils::DocumentReader<EventReader<Source<R>>>,
+    asset: Asset,
+    finished: bool,
+}
+
+impl<R: Read> DocumentReader<R> {
+    /// Begins reading a document, parsing everything up to the first library.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the document doesn't begin with a valid `<COLLADA>` element and `<asset>`,
+    /// or if the document isn't a `1.4.0` or `1.4.1` document.
+    pub fn new(reader: R) -> Result<DocumentReader<R>> {
+        DocumentReader::with_source(Transcode::new(reader), ParseOptions::default())
+    }
+
+    /// Begins reading a document that's already known to be UTF-8.
+    pub(crate) fn from_utf8(reader: R, options: ParseOptions) -> Result<DocumentReader<R>> {
+        DocumentReader::with_source(Transcode::utf8(reader), options)
+    }
+
+    fn with_source(source: Transcode<R>, options: ParseOptions) -> Result<DocumentReader<R>> {
+        let source = DtdFilter::new(source, &options)?;
+        let mut events = EventReader::new_with_config(source, utils::PARSER_CONFIG.clone());
+        let element_start = utils::get_document_start(&mut events)?;
+        let version = utils::get_document_version(&events, &element_start)?;
+        if !version.is_compatible("1.4") {
+            return Err(Error {
+                position: events.position(),
+                kind: ErrorKind::UnsupportedVersion {
+                    version: version.to_string(),
+                },
+            });
+        }
+
+        // Missing children are reported at the position of their parent, the same as when parsing
+        // the whole document.
+        let root_position = events.position();
+        let mut reader = utils::DocumentReader::new(events, ParseContext::with_options(version, options));
+        let asset = match utils::start_element(&mut reader, "COLLADA")? {
+            Some(element_start) if Asset::name_test(&*element_start.name.local_name) => {
+                Asset::parse_element(&mut reader, element_start)?
+            }
+
+            _ => {
+                return Err(Error {
+                    position: root_position,
+                    kind: ErrorKind::MissingElement {
+                        parent: "COLLADA",
+                        expected: vec!["asset"],
+                    },
+                });
+            }
+        };
+
+        Ok(DocumentReader {
+            reader,
+            asset,
+            finished: false,
+        })
+    }
+
+    /// Returns the version of the specification used by the document.
+    pub fn version(&self) -> Version {
+        self.reader.context().version
+    }
+
+    /// Returns the document's `<asset>`.
+    pub fn asset(&self) -> &Asset {
+        &self.asset
+    }
+
+    /// Parses the next library in the document.
+    ///
+    /// Returns `Ok(None)` once all libraries have been read.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the library is invalid or malformed in some way. No more libraries are
+    /// returned after an error.
+    pub fn next_library(&mut self) -> Result<Option<Library>> {
+        loop {
+            let result = match self.next_library_start() {
+                Ok(Some(element_start)) => utils::parse_group_member(&mut self.reader, "COLLADA", element_start),
+                Ok(None) => return Ok(None),
+                Err(error) => Err(error),
+            };
+
+            match result {
+                // Libraries from other versions of the specification are skipped in lenient mode.
+                Ok(None) => continue,
+                Ok(Some(library)) => return Ok(Some(library)),
+                Err(error) => {
+                    self.finished = true;
+                    return Err(error);
+                }
+            }
+        }
+    }
+
+    /// Converts the reader into an iterator over the geometries in the document.
+    ///
+    /// All libraries other than `<library_geometries>` are skipped without being parsed.
+    pub fn into_geometries(self) -> GeometryIter<R> {
+        GeometryIter {
+            reader: self,
+            in_library: false,
+        }
+    }
+
+    /// Reads up to the start of the next library, skipping the rest of the document once the
+    /// libraries are exhausted.
+    fn next_library_start(&mut self) -> Result<Option<ElementStart>> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        if let Some(element_start) = utils::start_element(&mut self.reader, "COLLADA")? {
+            if Library::name_test(&*element_start.name.local_name) {
+                return Ok(Some(element_start));
+            }
+
+            if element_start.name.local_name != "scene" && element_start.name.local_name != "extra" {
+                let mut expected = Vec::new();
+                Library::add_names(&mut expected);
+                expected.push("scene");
+                expected.push("extra");
+
+                self.finished = true;
+                return Err(Error {
+                    position: self.reader.position(),
+                    kind: ErrorKind::UnexpectedElement {
+                        parent: "COLLADA",
+                        element: element_start.name.local_name,
+                        expected,
+                    },
+                });
+            }
+
+            // We've reached the end of the libraries, skip the remaining elements.
+            utils::skip_element(&mut self.reader)?;
+            while let Some(_) = utils::start_element(&mut self.reader, "COLLADA")? {
+                utils::skip_element(&mut self.reader)?;
+            }
+        }
+
+        self.finished = true;
+        Ok(None)
+    }
+}
+
+/// An iterator over the geometries in a document.
+///
+/// Created with [`DocumentReader::into_geometries`]. Geometries are parsed one at a time as the
+/// iterator advances. Iteration stops after the first error.
+///
+/// [`DocumentReader::into_geometries`]: ./struct.DocumentReader.html#method.into_geometries
+pub struct GeometryIter<R: Read> {
+    reader: DocumentReader<R>,
+    in_library: bool,
+}
+
+impl<R: Read> GeometryIter<R> {
+    /// Returns the document's `<asset>`.
+    pub fn asset(&self) -> &Asset {
+        self.reader.asset()
+    }
+
+    fn next_geometry(&mut self) -> Result<Option<Geometry>> {
+        loop {
+            if !self.in_library {
+                match self.reader.next_library_start()? {
+                    Some(element_start) => {
+                        if element_start.name.local_name == "library_geometries" {
+                            self.in_library = true;
+                        } else {
+                            utils::skip_element(&mut self.reader.reader)?;
+                        }
+                    }
+
+                    None => { return Ok(None); }
+                }
+
+                continue;
+            }
+
+            match utils::start_element(&mut self.reader.reader, "library_geometries")? {
+                Some(element_start) => {
+                    if Geometry::name_test(&*element_start.name.local_name) {
+                        return Geometry::parse_element(&mut self.reader.reader, element_start).map(Some);
+                    }
+
+                    // Only the geometries are streamed, the library's `<asset>` and `<extra>`
+                    // elements are skipped.
+                    utils::skip_element(&mut self.reader.reader)?;
+                }
+
+                None => { self.in_library = false; }
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for GeometryIter<R> {
+    type Item = Result<Geometry>;
+
+    fn next(&mut self) -> Option<Result<Geometry>> {
+        match self.next_geometry() {
+            Ok(geometry) => geometry.map(Ok),
+            Err(error) => {
+                self.reader.finished = true;
+                self.in_library = false;
+                Some(Err(error))
+            }
+        }
+    }
+}
@@ -1,13 +1,25 @@
-use {Result, Error, ErrorKind};
+use {Result, Error, ErrorKind, ParseOptions, Strictness, Version, XmlError};
+use backend::XmlPull;
+use common::{ArrayValue, RawArray, TextValue};
+use coverage::{ElementCoverage, Support};
+use preserve::ElementPath;
+use fast_float::{self, FastFloat};
+use progress::{ProgressCallback, REPORT_INTERVAL};
+use raw::RawElement;
+use report::{ParseReport, SourceSpan};
 use self::ChildOccurrences::*;
+use std::collections::HashMap;
 use std::fmt::{self, Display, Formatter};
-use std::io::Read;
+use std::io::Write;
+use std::num::ParseFloatError;
 use std::str::FromStr;
 use xml::attribute::OwnedAttribute;
-use xml::common::Position;
+use xml::common::{Position, TextPosition};
 use xml::name::OwnedName;
-use xml::reader::{EventReader, ParserConfig};
+use xml::namespace::Namespace;
+use xml::reader::{self, ParserConfig, XmlEvent};
 use xml::reader::XmlEvent::*;
+use writer::{self, XmlWriter};
 
 pub static PARSER_CONFIG: ParserConfig = ParserConfig {
     trim_whitespace: true,
@@ -17,6 +29,260 @@ pub static PARSER_CONFIG: ParserConfig = ParserConfig {
     coalesce_characters: true,
 };
 
+/// Wraps the XML event stream along with any state needed while parsing a document.
+///
+/// Generated parsing code only ever pulls events with `next` and queries the current position,
+/// so this mirrors the `XmlPull` API of the underlying backend.
+pub struct DocumentReader<R: XmlPull> {
+    events: R,
+    context: ParseContext,
+
+    /// The number of elements started through this reader that haven't been ended yet.
+    open_elements: usize,
+
+    /// The number of elements started through this reader, which is also the index in document
+    /// order of the last one started, since the root element is started before the reader is
+    /// created.
+    started_elements: usize,
+
+    /// Tracks progress through the document, if a progress callback was set.
+    progress: Option<ProgressTracker>,
+
+    /// The path to the current element, if content that isn't part of the document model is being
+    /// preserved.
+    preserve: Option<ElementPath>,
+}
+
+impl<R: XmlPull> DocumentReader<R> {
+    pub fn new(events: R, context: ParseContext) -> DocumentReader<R> {
+        let progress = context.options.progress.clone().map(ProgressTracker::new);
+        // The root element is started before the reader is created.
+        let preserve = if context.options.preserve_unknown {
+            let mut path = ElementPath::new();
+            path.start("COLLADA");
+            Some(path)
+        } else {
+            None
+        };
+
+        DocumentReader { events, context, open_elements: 0, started_elements: 0, progress, preserve }
+    }
+
+    pub fn next(&mut self) -> reader::Result<XmlEvent> {
+        let event = self.events.next()?;
+        match event {
+            StartElement { ref name, .. } => {
+                self.open_elements += 1;
+                self.started_elements += 1;
+                if let Some(ref mut path) = self.preserve {
+                    path.start(&name.local_name);
+                }
+            }
+            EndElement { .. } => {
+                self.open_elements = self.open_elements.saturating_sub(1);
+                if let Some(ref mut path) = self.preserve {
+                    path.end();
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(ref mut progress) = self.progress {
+            progress.update(&event);
+        }
+
+        Ok(event)
+    }
+
+    /// The number of elements started through this reader that haven't been ended yet.
+    ///
+    /// This only counts relative to wherever the reader started, so it's only useful for
+    /// comparing against itself.
+    pub fn open_elements(&self) -> usize {
+        self.open_elements
+    }
+
+    /// The index in document order of the last element started, counting the root element as 0.
+    ///
+    /// Like `open_elements`, this is relative to wherever the reader started.
+    pub fn element_index(&self) -> usize {
+        self.started_elements
+    }
+
+    pub fn context(&self) -> &ParseContext {
+        &self.context
+    }
+
+    pub fn context_mut(&mut self) -> &mut ParseContext {
+        &mut self.context
+    }
+
+    pub fn into_context(self) -> ParseContext {
+        self.context
+    }
+
+    pub fn into_parts(self) -> (R, ParseContext) {
+        (self.events, self.context)
+    }
+
+    /// Handles a problem that the parser can recover from.
+    ///
+    /// If the document is being parsed with at least `tolerated_at` strictness the error is
+    /// recorded as a warning and parsing continues, otherwise the error is returned.
+    pub fn tolerate(&mut self, error: Error, tolerated_at: Strictness) -> Result<()> {
+        if self.context.options.strictness < tolerated_at {
+            return Err(error);
+        }
+
+        self.context.report.record_warning(error);
+        Ok(())
+    }
+
+    /// Skips the rest of an element that isn't part of the document model, keeping it in the report
+    /// if the document is being parsed with `ParseOptions::preserve_unknown` set.
+    ///
+    /// `element_start` is the start of the element, which must have just been read.
+    pub fn preserve_element(&mut self, element_start: ElementStart) -> Result<()> {
+        let after = match self.preserve {
+            Some(ref mut path) => path.preserve_current(),
+            None => return skip_element(self),
+        };
+
+        let element = parse_raw_element(self, element_start)?;
+        if let Some(ref path) = self.preserve {
+            self.context.report.preserved.add_child(path.path(), after, element);
+        }
+
+        Ok(())
+    }
+
+    /// Keeps an attribute of the current element that isn't part of the document model in the
+    /// report, if the document is being parsed with `ParseOptions::preserve_unknown` set.
+    pub fn preserve_attribute(&mut self, attribute: OwnedAttribute) {
+        if let Some(ref path) = self.preserve {
+            self.context.report.preserved.add_attribute(path.path(), attribute.name, attribute.value);
+        }
+    }
+
+    /// Handles an error in an element, recovering from it if the document is being parsed with
+    /// `ParseOptions::recover` set.
+    ///
+    /// `open_elements` is the value of `open_elements()` just after the failed element was
+    /// started. If the error can be recovered from, it's recorded in the report and the rest of
+    /// the element is skipped, otherwise the error is returned.
+    pub fn recover(&mut self, error: Error, open_elements: usize) -> Result<()> {
+        let recoverable = match error.kind {
+            ErrorKind::XmlError(_) | ErrorKind::LimitExceeded { .. } => false,
+            _ => self.context.options.recover,
+        };
+        if !recoverable {
+            return Err(error);
+        }
+
+        while self.open_elements >= open_elements {
+            if let EndDocument = self.next()? {
+                return Err(error);
+            }
+        }
+
+        self.context.report.record_error(error);
+        Ok(())
+    }
+}
+
+/// Keeps track of when to report progress, and where in the document parsing is.
+struct ProgressTracker {
+    callback: ProgressCallback,
+
+    /// The names of the currently open elements, starting with the root element.
+    path: Vec<String>,
+
+    /// Progress is next reported once this many bytes have been read.
+    next_report: u64,
+}
+
+impl ProgressTracker {
+    /// Starts tracking progress just after the root `<COLLADA>` element has been started.
+    fn new(callback: ProgressCallback) -> ProgressTracker {
+        ProgressTracker {
+            callback,
+            path: vec!["COLLADA".into()],
+            next_report: REPORT_INTERVAL,
+        }
+    }
+
+    /// Updates the progress for `event`, reporting it if a library is starting or enough of the
+    /// document has been read since the last report.
+    fn update(&mut self, event: &XmlEvent) {
+        let mut library_start = false;
+        match *event {
+            StartElement { ref name, .. } => {
+                library_start = self.path.len() == 1 && name.local_name.starts_with("library_");
+                self.path.push(name.local_name.clone());
+            }
+
+            EndElement { .. } => { self.path.pop(); }
+
+            _ => {}
+        }
+
+        let bytes_read = self.callback.bytes_read();
+        if library_start || bytes_read >= self.next_report {
+            self.callback.report(&self.path);
+            self.next_report = bytes_read + REPORT_INTERVAL;
+        }
+    }
+}
+
+impl<R: XmlPull> Position for DocumentReader<R> {
+    fn position(&self) -> TextPosition {
+        self.events.position()
+    }
+}
+
+impl<R: XmlPull> XmlPull for DocumentReader<R> {
+    fn next(&mut self) -> reader::Result<XmlEvent> {
+        DocumentReader::next(self)
+    }
+}
+
+/// Document-wide information made available to elements while they're being parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseContext {
+    /// The version of the specification declared by the document's root element.
+    pub version: Version,
+
+    /// The workarounds that have been needed to parse the document so far.
+    pub report: ParseReport,
+
+    /// The options the document is being parsed with.
+    pub options: ParseOptions,
+
+    /// The number of elements currently being parsed, i.e. the nesting depth of the current
+    /// element.
+    pub depth: usize,
+
+    /// The total size in bytes of the arrays and lists decoded so far.
+    pub array_bytes: usize,
+}
+
+impl ParseContext {
+    pub fn with_options(version: Version, options: ParseOptions) -> ParseContext {
+        let mut report = ParseReport::default();
+        if version == Version::V1_4_0 {
+            report.record_quirk("1.4.0 document parsed as 1.4.1");
+        }
+
+        ParseContext {
+            version,
+            report,
+            options,
+            depth: 0,
+            array_bytes: 0,
+        }
+    }
+}
+
 /// Helper trait for handling parsing. This can be derived for most types with the
 /// `collaborate-derive` crate.
 pub trait ColladaElement: Sized {
@@ -29,17 +295,101 @@ pub trait ColladaElement: Sized {
     ///
     /// Implementation should panic if `element_start` isn't valid for the current element.
     fn parse_element<R>(
-        reader: &mut EventReader<R>,
+        reader: &mut DocumentReader<R>,
         element_start: ElementStart,
     ) -> Result<Self>
     where
-        R: Read;
+        R: XmlPull;
 
     /// Adds all valid names for the current element to `names`.
     ///
     /// This allows both single elements and element groups to add their name(s) to the list of
     /// expected names when returning an error message.
     fn add_names(names: &mut Vec<&'static str>);
+
+    /// Returns the versions of the specification that allow the element `name`, if only some of
+    /// them do.
+    ///
+    /// This is how enum groups report members marked with `#[collada(version = "...")]`, e.g. the
+    /// libraries added in 1.5, so that the element containing the group can check them against
+    /// the document's version.
+    fn version_restriction(_name: &str) -> Option<&'static str> {
+        None
+    }
+
+    /// Writes the element, including its attributes and everything inside of it, to `writer`.
+    fn write_element<W: Write>(&self, writer: &mut XmlWriter<W>) -> writer::Result<()>;
+
+    /// Records the current element, and all elements that may appear within it, in `coverage`.
+    ///
+    /// By default all valid names for the element are recorded as implemented, which is correct
+    /// for hand-written implementations that don't have children of their own.
+    fn add_coverage(coverage: &mut ElementCoverage) where Self: 'static {
+        let mut names = Vec::new();
+        Self::add_names(&mut names);
+        for name in names {
+            coverage.insert_name(name, Support::Implemented);
+        }
+    }
+}
+
+/// Returns the name of the element `T`, e.g. for reporting it in an error.
+///
+/// For a group, this is the name of its first member.
+#[doc(hidden)]
+pub fn element_name<T: ColladaElement>() -> &'static str {
+    let mut names = Vec::new();
+    T::add_names(&mut names);
+    names[0]
+}
+
+/// A collection that can hold the attributes of an element that aren't part of the COLLADA
+/// specification.
+///
+/// Exporters frequently add their own attributes to standard elements. Members marked with
+/// `#[any_attribute]` collect these instead of rejecting the element with an
+/// `UnexpectedAttribute` error.
+pub trait AnyAttributes: Default {
+    /// Adds an unknown attribute to the collection.
+    fn insert_attribute(&mut self, attribute: OwnedAttribute);
+
+    /// Writes every attribute in the collection to the element that was just started.
+    fn write_attributes<W: Write>(&self, writer: &mut XmlWriter<W>) -> writer::Result<()>;
+}
+
+/// Attributes are keyed by their qualified name (e.g. `"xsi:schemaLocation"`).
+///
+/// The attributes are written sorted by name, so that the output doesn't depend on the order of
+/// the map. The namespaces of their prefixes aren't kept, so they're only declared if an
+/// enclosing element already declares them.
+impl AnyAttributes for HashMap<String, String> {
+    fn insert_attribute(&mut self, attribute: OwnedAttribute) {
+        self.insert(attribute.name.borrow().to_repr(), attribute.value);
+    }
+
+    fn write_attributes<W: Write>(&self, writer: &mut XmlWriter<W>) -> writer::Result<()> {
+        let mut attributes: Vec<_> = self.iter().collect();
+        attributes.sort();
+        for (name, value) in attributes {
+            writer.attribute(name, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Attributes are kept in document order with their full namespace information, so that the
+/// namespaces of prefixed attributes can be declared when they're written.
+impl AnyAttributes for Vec<(OwnedName, String)> {
+    fn insert_attribute(&mut self, attribute: OwnedAttribute) {
+        self.push((attribute.name, attribute.value));
+    }
+
+    fn write_attributes<W: Write>(&self, writer: &mut XmlWriter<W>) -> writer::Result<()> {
+        for (name, value) in self {
+            writer.qualified_attribute(name, value)?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -57,14 +407,24 @@ pub enum ChildOccurrences {
     RequiredMany,
 }
 
-pub struct ElementConfiguration<'a, R: 'a + Read> {
+pub struct ElementConfiguration<'a, R: 'a + XmlPull> {
     pub name: &'static str,
     pub children: &'a mut [ChildConfiguration<'a, R>],
-    pub text_contents: Option<&'a mut FnMut(&mut EventReader<R>, String) -> Result<()>>,
+    pub text_contents: Option<&'a mut FnMut(&mut DocumentReader<R>, String) -> Result<()>>,
 }
 
-impl<'a, R: 'a + Read> ElementConfiguration<'a, R> {
-    pub fn parse_children(self, reader: &mut EventReader<R>) -> Result<()> {
+impl<'a, R: 'a + XmlPull> ElementConfiguration<'a, R> {
+    pub fn parse_children(self, reader: &mut DocumentReader<R>) -> Result<()> {
+        let depth = reader.context().depth + 1;
+        check_depth(reader, depth)?;
+
+        reader.context_mut().depth = depth;
+        let result = self.parse_children_at_depth(reader);
+        reader.context_mut().depth = depth - 1;
+        result
+    }
+
+    fn parse_children_at_depth(self, reader: &mut DocumentReader<R>) -> Result<()> {
         // Keep track of the text position for the root element so that it can be used for error
         // messages.
         let root_position = reader.position();
@@ -82,15 +442,70 @@ impl<'a, R: 'a + Read> ElementConfiguration<'a, R> {
         // for `RequiredMany` children to ensure they are found at least once.
         let mut has_encountered_child = false;
 
+        // The number of child elements encountered so far, including any that were skipped.
+        let mut child_count = 0;
+
         'elements: while let Some(element) = start_element(reader, self.name)? {
+            child_count += 1;
+            check_child_count(reader, child_count)?;
+
+            // Remember where we were, so that we can pick up from here again if the element is
+            // skipped.
+            let resume_at = (current_child, has_encountered_child);
+
             while current_child < self.children.len() {
                 let child = &mut self.children[current_child];
 
                 if (child.name)(&*element.name.local_name) {
+                    // Children that only exist in some versions of the spec are skipped, unless
+                    // the document is being parsed permissively.
+                    if let Some(valid_in) = (child.valid_in)(&element.name.local_name) {
+                        let name = element.name.local_name.clone();
+                        if !check_version(reader, self.name, &name, valid_in)? {
+                            reader.preserve_element(element)?;
+                            current_child = resume_at.0;
+                            has_encountered_child = resume_at.1;
+                            continue 'elements;
+                        }
+                    }
+
                     has_encountered_child = true;
 
-                    // We've found a valid child, hooray! Allow it to run its parsing code.
-                    (child.action)(reader, element)?;
+                    let (track_spans, track_order) = {
+                        let options = &reader.context().options;
+                        (options.track_spans, options.track_order)
+                    };
+                    let id = if track_spans || track_order {
+                        element.attributes.iter()
+                            .find(|attribute| attribute.name.local_name == "id" && attribute.name.prefix.is_none())
+                            .map(|attribute| attribute.value.clone())
+                    } else {
+                        None
+                    };
+                    let start = reader.position();
+                    let index = reader.element_index();
+                    let open_elements = reader.open_elements();
+
+                    // We've found a valid child, hooray! Allow it to run its parsing code. If it
+                    // fails, the error may be recovered from by skipping the child, unless the
+                    // child is required (in which case this element can't be parsed either).
+                    if let Err(error) = (child.action)(reader, element) {
+                        if child.occurrences == Required {
+                            return Err(error);
+                        }
+
+                        reader.recover(error, open_elements)?;
+                    }
+
+                    if let Some(id) = id {
+                        if track_spans {
+                            let span = SourceSpan { start, end: reader.position() };
+                            reader.context_mut().report.record_span(&id, span);
+                        }
+                        if track_order {
+                            reader.context_mut().report.record_document_index(&id, index);
+                        }
+                    }
 
                     // Either advance `current_child` or don't, depending on if it's allowed to repeat.
                     match child.occurrences {
@@ -116,22 +531,26 @@ impl<'a, R: 'a + Read> ElementConfiguration<'a, R> {
                 current_child += 1;
             }
 
-            return Err(Error {
+            let error = Error {
                 position: reader.position(),
                 kind: ErrorKind::UnexpectedElement {
                     parent: self.name,
-                    element: element.name.local_name,
-                    expected: self.collect_expected_children(),
+                    element: element.name.local_name.clone(),
+                    expected: self.collect_expected_children(reader.context().version),
                 },
-            });
+            };
+            reader.tolerate(error, Strictness::Lenient)?;
+            reader.preserve_element(element)?;
+
+            current_child = resume_at.0;
+            has_encountered_child = resume_at.1;
         }
 
         // No more child elements are present, and none of the children we encountered were invalid.
         // Verify that there are no remaining required children.
         for child in &self.children[current_child..] {
             if child.occurrences == ChildOccurrences::Required {
-                let mut expected = Vec::new();
-                (child.add_names)(&mut expected);
+                let expected = child.names_in(reader.context().version);
 
                 return Err(Error {
                     position: root_position,
@@ -146,30 +565,112 @@ impl<'a, R: 'a + Read> ElementConfiguration<'a, R> {
         Ok(())
     }
 
-    fn collect_expected_children(&self) -> Vec<&'static str> {
+    /// Collects the names of the children allowed in documents using `version`.
+    fn collect_expected_children(&self, version: Version) -> Vec<&'static str> {
         let mut names = Vec::with_capacity(self.children.len());
         for child in self.children.iter() {
-            (child.add_names)(&mut names);
+            names.extend(child.names_in(version));
         }
         names
     }
 }
 
-pub struct ChildConfiguration<'a, R: 'a + Read> {
+/// Parses the rest of an element that has no child elements or text, i.e. just its end tag.
+///
+/// This is equivalent to calling `parse_children` for the element with no children, but leaf
+/// elements are common enough that skipping the setup of an `ElementConfiguration` for each of
+/// them is worthwhile.
+pub fn parse_leaf_element<R: XmlPull>(reader: &mut DocumentReader<R>, name: &'static str) -> Result<()> {
+    check_depth(reader, reader.context().depth + 1)?;
+
+    // Any child elements are unexpected, but may be tolerated in lenient modes.
+    let mut child_count = 0;
+    while let Some(element) = start_element(reader, name)? {
+        child_count += 1;
+        check_child_count(reader, child_count)?;
+
+        let error = Error {
+            position: reader.position(),
+            kind: ErrorKind::UnexpectedElement {
+                parent: name,
+                element: element.name.local_name.clone(),
+                expected: vec![],
+            },
+        };
+        reader.tolerate(error, Strictness::Lenient)?;
+        reader.preserve_element(element)?;
+    }
+
+    Ok(())
+}
+
+/// Checks the nesting depth of an element against the limits in the document's parse options.
+fn check_depth<R: XmlPull>(reader: &DocumentReader<R>, depth: usize) -> Result<()> {
+    if let Some(max) = reader.context().options.max_depth {
+        if depth > max {
+            return Err(Error {
+                position: reader.position(),
+                kind: ErrorKind::LimitExceeded {
+                    limit: "max_depth",
+                    max,
+                },
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the number of children of an element against the limits in the document's parse
+/// options.
+fn check_child_count<R: XmlPull>(reader: &DocumentReader<R>, count: usize) -> Result<()> {
+    if let Some(max) = reader.context().options.max_children {
+        if count > max {
+            return Err(Error {
+                position: reader.position(),
+                kind: ErrorKind::LimitExceeded {
+                    limit: "max_children",
+                    max,
+                },
+            });
+        }
+    }
+
+    Ok(())
+}
+
+pub struct ChildConfiguration<'a, R: 'a + XmlPull> {
     pub name: &'a Fn(&str) -> bool,
     pub occurrences: ChildOccurrences,
-    pub action: &'a mut FnMut(&mut EventReader<R>, ElementStart) -> Result<()>,
+    pub action: &'a mut FnMut(&mut DocumentReader<R>, ElementStart) -> Result<()>,
     pub add_names: &'a Fn(&mut Vec<&'static str>),
+
+    /// Returns the versions of the specification that allow the child with a given name, if
+    /// only some of them do.
+    pub valid_in: &'a Fn(&str) -> Option<&'static str>,
 }
 
-pub fn get_document_start<R: Read>(reader: &mut EventReader<R>) -> Result<ElementStart> {
+impl<'a, R: 'a + XmlPull> ChildConfiguration<'a, R> {
+    /// Returns the names of the child that are allowed in the given version of the specification.
+    fn names_in(&self, version: Version) -> Vec<&'static str> {
+        let mut names = Vec::new();
+        (self.add_names)(&mut names);
+        names.retain(|name| match (self.valid_in)(name) {
+            Some(valid_in) => version.is_compatible(valid_in),
+            None => true,
+        });
+        names
+    }
+}
+
+pub fn get_document_start<R: XmlPull>(reader: &mut R) -> Result<ElementStart> {
     // Eat the `StartDocument` event. It has no useful information for our purposes, but it
     // will always be the first event emitted, even if there's no XML declaration at the
     // beginning of the document. This is defined as part of the xml-rs API as of v0.3.5,
     // but it's possible this can will change in the future.
     match reader.next()? {
         StartDocument { .. } => {},
-        _ => panic!("First event from EventReader wasn't StartDocument"),
+        _ => panic!("First event from the XML backend wasn't StartDocument"),
     }
 
     // The next element will always be the `<COLLADA>` tag. This will specify what version of
@@ -202,8 +703,82 @@ pub fn get_document_start<R: Read>(reader: &mut EventReader<R>) -> Result<Elemen
     Ok(element_start)
 }
 
-pub fn start_element<R: Read>(
-    reader: &mut EventReader<R>,
+/// Reads the `version` attribute from the opening `<COLLADA>` tag.
+pub fn get_document_version<R: Position>(reader: &R, element_start: &ElementStart) -> Result<Version> {
+    let version = element_start.attributes.iter()
+        .find(|attrib| attrib.name.local_name == "version")
+        .ok_or(Error {
+            position: reader.position(),
+            kind: ErrorKind::MissingAttribute {
+                element: "COLLADA",
+                attribute: "version",
+            },
+        })?;
+
+    version.value.parse()
+        .map_err(|kind| Error {
+            position: reader.position(),
+            kind,
+        })
+}
+
+/// Verifies that a member restricted to `valid_in` versions of the specification is allowed in the
+/// current document.
+///
+/// `valid_in` may be a full version (e.g. `"1.4.1"`) or a prefix of one (e.g. `"1.5"`). Returns
+/// whether the member should be parsed: members from the wrong version are an error in strict
+/// mode, skipped in lenient mode, and only accepted in permissive mode.
+pub fn check_version<R: XmlPull>(
+    reader: &mut DocumentReader<R>,
+    element: &'static str,
+    member: &str,
+    valid_in: &'static str,
+) -> Result<bool> {
+    let version = reader.context().version;
+    if version.is_compatible(valid_in) {
+        return Ok(true);
+    }
+
+    let error = Error {
+        position: reader.position(),
+        kind: ErrorKind::VersionMismatch {
+            element,
+            member: member.into(),
+            valid_in,
+            version,
+        },
+    };
+    reader.tolerate(error, Strictness::Lenient)?;
+    Ok(reader.context().options.strictness >= Strictness::Permissive)
+}
+
+/// Parses a single member of the group `T` outside of the element containing it, e.g. a library
+/// that's parsed on its own.
+///
+/// The member is checked against the document's version the same way the containing element
+/// would check it. Returns `None` if it belongs to a different version and was skipped.
+pub(crate) fn parse_group_member<T, R>(
+    reader: &mut DocumentReader<R>,
+    parent: &'static str,
+    element_start: ElementStart,
+) -> Result<Option<T>>
+where
+    T: ColladaElement,
+    R: XmlPull,
+{
+    if let Some(valid_in) = T::version_restriction(&element_start.name.local_name) {
+        let name = element_start.name.local_name.clone();
+        if !check_version(reader, parent, &name, valid_in)? {
+            reader.preserve_element(element_start)?;
+            return Ok(None);
+        }
+    }
+
+    T::parse_element(reader, element_start).map(Some)
+}
+
+pub fn start_element<R: XmlPull>(
+    reader: &mut DocumentReader<R>,
     parent: &'static str,
 ) -> Result<Option<ElementStart>> {
     match reader.next()? {
@@ -235,21 +810,17 @@ pub fn start_element<R: Read>(
 }
 
 pub fn required_text_contents<R, T>(
-    reader: &mut EventReader<R>,
+    reader: &mut DocumentReader<R>,
     parent: &'static str,
 ) -> Result<T>
     where
-    R: Read,
-    T: FromStr,
-    ErrorKind: From<<T as FromStr>::Err>,
+    R: XmlPull,
+    T: TextValue,
 {
     match reader.next()? {
         Characters(data) => {
-            let result = T::from_str(&*data)
-                .map_err(|error| Error {
-                    position: reader.position(),
-                    kind: error.into(),
-                })?;
+            check_text_len(reader, &data)?;
+            let result = parse_with_locale_fallback(reader, &data, T::parse_text)?;
             end_element(reader, parent)?;
             return Ok(result);
         }
@@ -281,21 +852,17 @@ pub fn required_text_contents<R, T>(
 }
 
 pub fn optional_text_contents<R, T>(
-    reader: &mut EventReader<R>,
+    reader: &mut DocumentReader<R>,
     parent: &'static str,
 ) -> Result<Option<T>>
     where
-    R: Read,
-    T: FromStr,
-    ErrorKind: From<<T as FromStr>::Err>
+    R: XmlPull,
+    T: TextValue,
 {
     match reader.next()? {
         Characters(data) => {
-            let result = T::from_str(&*data)
-                .map_err(|error| Error {
-                    position: reader.position(),
-                    kind: error.into(),
-                })?;
+            check_text_len(reader, &data)?;
+            let result = parse_with_locale_fallback(reader, &data, T::parse_text)?;
             end_element(reader, parent)?;
             return Ok(Some(result));
         }
@@ -321,7 +888,295 @@ pub fn optional_text_contents<R, T>(
     }
 }
 
-pub fn end_element<R: Read>(reader: &mut EventReader<R>, parent: &'static str) -> Result<()> {
+/// Checks the length of the text contents of an element against the limits in the document's parse
+/// options.
+pub fn check_text_len<R: XmlPull>(reader: &mut DocumentReader<R>, text: &str) -> Result<()> {
+    if let Some(max) = reader.context().options.max_text_len {
+        if text.len() > max {
+            return Err(Error {
+                position: reader.position(),
+                kind: ErrorKind::LimitExceeded {
+                    limit: "max_text_len",
+                    max,
+                },
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks a list of `len` values of type `T` against the limits in the document's parse options,
+/// and adds its size to the total size of the document's arrays.
+///
+/// `declared` is the number of values the element declared it contains (i.e. its `count`
+/// attribute), if it has one, which is checked if `ParseOptions::validate_counts` is set.
+pub fn check_list_len<R: XmlPull, T>(
+    reader: &mut DocumentReader<R>,
+    element: &'static str,
+    len: usize,
+    declared: Option<usize>,
+) -> Result<()> {
+    if let Some(max) = reader.context().options.max_array_len {
+        if len > max {
+            return Err(Error {
+                position: reader.position(),
+                kind: ErrorKind::LimitExceeded {
+                    limit: "max_array_len",
+                    max,
+                },
+            });
+        }
+    }
+
+    let array_bytes = reader.context().array_bytes
+        .saturating_add(len.saturating_mul(::std::mem::size_of::<T>()));
+    if let Some(max) = reader.context().options.max_array_bytes {
+        if array_bytes > max {
+            return Err(Error {
+                position: reader.position(),
+                kind: ErrorKind::LimitExceeded {
+                    limit: "max_array_bytes",
+                    max,
+                },
+            });
+        }
+    }
+    reader.context_mut().array_bytes = array_bytes;
+
+    if let Some(declared) = declared {
+        if reader.context().options.validate_counts && declared != len {
+            let error = Error {
+                position: reader.position(),
+                kind: ErrorKind::CountMismatch {
+                    element,
+                    expected: declared,
+                    actual: len,
+                },
+            };
+            reader.tolerate(error, Strictness::Lenient)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates a `RawArray` from the text contents of an array element.
+///
+/// The values are decoded right away unless `defer_arrays` is set. Deferred values are still
+/// counted if that's needed to check the array against the document's parse options, and the
+/// array counts towards `max_array_bytes` as if it had been decoded.
+pub fn raw_array<R: XmlPull, T: ArrayValue>(
+    reader: &mut DocumentReader<R>,
+    element: &'static str,
+    text: String,
+    declared: Option<usize>,
+) -> Result<RawArray<T>> {
+    let position = reader.position();
+
+    let needs_len = {
+        let options = &reader.context().options;
+        options.max_array_len.is_some()
+            || options.max_array_bytes.is_some()
+            || (options.validate_counts && declared.is_some())
+    };
+    if needs_len {
+        let len = text.split_whitespace().count();
+        check_list_len::<R, T>(reader, element, len, declared)?;
+    }
+
+    // The values aren't parsed yet, so numbers in other locales need to be fixed up ahead of time.
+    let mut text = text;
+    if reader.context().options.allows_locale_numbers() {
+        if let Some(normalized) = T::normalize_locale(&text) {
+            reader.context_mut().report.record_fallback(LOCALE_NUMBER_FALLBACK);
+            text = normalized;
+        }
+    }
+
+    if reader.context().options.defer_arrays {
+        return Ok(RawArray::deferred(text, position));
+    }
+
+    T::parse_list(&text)
+        .map(RawArray::from)
+        .map_err(|kind| Error { position, kind })
+}
+
+/// The fallback recorded when a number formatted for another locale is accepted.
+const LOCALE_NUMBER_FALLBACK: &str = "number formatted for another locale";
+
+/// Parses a value from text, such as an attribute value.
+///
+/// If the value is a number formatted for another locale and the document's parse options allow
+/// it, it's reformatted and parsed again.
+pub fn parse_value<R, T>(reader: &mut DocumentReader<R>, text: &str) -> Result<T>
+    where
+    R: XmlPull,
+    T: FromStr,
+    ErrorKind: From<<T as FromStr>::Err>,
+{
+    parse_with_locale_fallback(reader, text, |text| T::from_str(text).map_err(Into::into))
+}
+
+/// Parses a whitespace-separated list of floats from the text contents of an element.
+///
+/// Like `parse_value`, lists containing numbers formatted for another locale are reformatted and
+/// parsed again if the document's parse options allow it.
+pub fn float_list<R, T>(reader: &mut DocumentReader<R>, text: &str) -> Result<Vec<T>>
+    where
+    R: XmlPull,
+    T: FastFloat + FromStr<Err = ParseFloatError>,
+{
+    let error = match parse_float_list(text) {
+        Ok(values) => { return Ok(values); }
+        Err(error) => error,
+    };
+
+    if reader.context().options.allows_locale_numbers() {
+        if let Some(values) = normalize_locale_list(text).and_then(|text| parse_float_list(&text).ok()) {
+            reader.context_mut().report.record_fallback(LOCALE_NUMBER_FALLBACK);
+            return Ok(values);
+        }
+    }
+
+    Err(Error {
+        position: reader.position(),
+        kind: error.into(),
+    })
+}
+
+fn parse_with_locale_fallback<R, T, F>(reader: &mut DocumentReader<R>, text: &str, parse: F) -> Result<T>
+    where
+    R: XmlPull,
+    F: Fn(&str) -> ::std::result::Result<T, ErrorKind>,
+{
+    let kind = match parse(text) {
+        Ok(value) => { return Ok(value); }
+        Err(kind) => kind,
+    };
+
+    if let ErrorKind::ParseFloatError(_) = kind {
+        if reader.context().options.allows_locale_numbers() {
+            if let Some(value) = normalize_locale_number(text).and_then(|text| parse(&text).ok()) {
+                reader.context_mut().report.record_fallback(LOCALE_NUMBER_FALLBACK);
+                return Ok(value);
+            }
+        }
+    }
+
+    Err(Error {
+        position: reader.position(),
+        kind,
+    })
+}
+
+/// Reformats a number written with another locale's separators, e.g. `"1.234,5"`, so that it can
+/// be parsed. Returns `None` if the number doesn't contain a comma, since then there's nothing to
+/// fix, or if its separators don't make sense for any locale.
+///
+/// If the number contains both commas and periods, whichever comes last is the decimal separator
+/// and the other is the thousands separator. Otherwise, a single comma is a decimal separator and
+/// multiple commas are thousands separators. Thousands separators must separate groups of three
+/// digits, so that e.g. a comma-separated list like `"1,2,3"` isn't mistaken for a number.
+pub fn normalize_locale_number(text: &str) -> Option<String> {
+    let last_comma = text.rfind(',')?;
+    match text.rfind('.') {
+        Some(last_period) if last_period > last_comma => {
+            let integer = remove_thousands_separators(&text[..last_period], ',')?;
+            Some(integer + &text[last_period..])
+        }
+
+        Some(_) => {
+            let integer = remove_thousands_separators(&text[..last_comma], '.')?;
+            Some(integer + "." + &text[last_comma + 1..])
+        }
+
+        None if text.matches(',').count() == 1 => Some(text.replace(',', ".")),
+
+        None => remove_thousands_separators(text, ','),
+    }
+}
+
+fn remove_thousands_separators(text: &str, separator: char) -> Option<String> {
+    let mut groups = text.split(separator);
+    let mut result = String::from(groups.next()?);
+    for group in groups {
+        if group.len() != 3 || !group.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+        result.push_str(group);
+    }
+
+    Some(result)
+}
+
+/// Applies `normalize_locale_number` to each value in a whitespace-separated list. Returns `None`
+/// if none of the values needed to be reformatted.
+pub fn normalize_locale_list(text: &str) -> Option<String> {
+    if !text.contains(',') {
+        return None;
+    }
+
+    let values = text.split_whitespace()
+        .map(|value| normalize_locale_number(value).unwrap_or_else(|| value.into()))
+        .collect::<Vec<_>>();
+    Some(values.join(" "))
+}
+
+/// Splits an `xs:list` of names, references or tokens, as found in `<IDREF_array>` and
+/// `<Name_array>`.
+///
+/// Only the XML whitespace characters separate items, so names containing other Unicode
+/// whitespace, e.g. a non-breaking space, are kept whole instead of being split the way
+/// `str::split_whitespace` would split them.
+pub fn split_xml_list(text: &str) -> Vec<&str> {
+    text.split([' ', '\t', '\n', '\r'])
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Parses a whitespace-separated list of floats, as found in `<float_array>` and similar elements.
+///
+/// Large documents can contain millions of floats, so this parses directly from the text with
+/// `fast_float` instead of splitting it into tokens and parsing each with `str::parse`. If a value
+/// fails to parse, the token is reparsed with `str::parse` to produce the error.
+pub fn parse_float_list<T>(text: &str) -> ::std::result::Result<Vec<T>, ParseFloatError>
+    where
+    T: FastFloat + FromStr<Err = ParseFloatError>,
+{
+    let bytes = text.as_bytes();
+    let mut values = Vec::new();
+    let mut index = 0;
+    loop {
+        while index < bytes.len() && bytes[index].is_ascii_whitespace() {
+            index += 1;
+        }
+
+        if index == bytes.len() {
+            return Ok(values);
+        }
+
+        let end = match fast_float::parse_partial::<T, _>(&bytes[index..]) {
+            Ok((value, len)) if bytes.get(index + len).map_or(true, u8::is_ascii_whitespace) => {
+                values.push(value);
+                index + len
+            }
+
+            _ => {
+                let len = bytes[index..].iter()
+                    .position(u8::is_ascii_whitespace)
+                    .unwrap_or(bytes.len() - index);
+                values.push(text[index..index + len].parse()?);
+                index + len
+            }
+        };
+
+        index = end;
+    }
+}
+
+pub fn end_element<R: XmlPull>(reader: &mut DocumentReader<R>, parent: &'static str) -> Result<()> {
     match reader.next()? {
         EndElement { .. } => {
             return Ok(());
@@ -355,35 +1210,164 @@ pub fn end_element<R: Read>(reader: &mut EventReader<R>, parent: &'static str) -
 }
 
 /// Meaning, of course, "verify that there are no attributes".
-pub fn verify_attributes<R: Read>(reader: &EventReader<R>, name: &'static str, attributes: Vec<OwnedAttribute>) -> Result<()> {
+pub fn verify_attributes<R: XmlPull>(reader: &mut DocumentReader<R>, name: &'static str, attributes: Vec<OwnedAttribute>) -> Result<()> {
     // Make sure the child element has no attributes.
-    if attributes.len() != 0 {
-        return Err(Error {
+    for attribute in attributes {
+        let error = Error {
             position: reader.position(),
             kind: ErrorKind::UnexpectedAttribute {
                 element: name,
-                attribute: attributes[0].name.local_name.clone(),
+                attribute: attribute.name.local_name.clone(),
                 expected: vec![],
             },
-        })
+        };
+        reader.tolerate(error, Strictness::Lenient)?;
+        reader.preserve_attribute(attribute);
+    }
+
+    Ok(())
+}
+
+/// Parses a wrapper element (e.g. `<technique_common>`) that contains nothing but a single `T`,
+/// returning the `T`.
+///
+/// This is used for `#[child(flatten = "...")]` members, so that the model doesn't need a struct
+/// for every wrapper element.
+pub fn parse_flattened<R, T>(reader: &mut DocumentReader<R>, element_start: ElementStart, wrapper: &'static str) -> Result<T>
+where
+    R: XmlPull,
+    T: ColladaElement,
+{
+    verify_attributes(reader, wrapper, element_start.attributes)?;
+
+    let mut result = None;
+    ElementConfiguration {
+        name: wrapper,
+        children: &mut [
+            ChildConfiguration {
+                name: &T::name_test,
+                occurrences: ChildOccurrences::Required,
+                action: &mut |reader, element_start| {
+                    result = Some(T::parse_element(reader, element_start)?);
+                    Ok(())
+                },
+                add_names: &T::add_names,
+                valid_in: &T::version_restriction,
+            },
+        ],
+        text_contents: None,
+    }.parse_children(reader)?;
+
+    Ok(result.expect("Required child was `None`"))
+}
+
+/// Skips over the remainder of the current element, including all of its children.
+///
+/// The `StartElement` event for the element must already have been consumed. Events are read up
+/// to and including the matching `EndElement` event, keeping track of nested elements (even ones
+/// with the same name as the skipped element). Any text, CDATA, and processing instructions
+/// inside the element are skipped too.
+///
+/// This works with any [`XmlPull`] source, including xml-rs's `EventReader`, so it can be used to
+/// skip content that a caller doesn't care about without reimplementing the event loop.
+///
+/// # Errors
+///
+/// Returns `Err` if the document is malformed, or if it ends before the element is closed.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate collaborate;
+/// # extern crate xml;
+/// use collaborate::{skip_element, XmlEvent};
+/// use xml::reader::EventReader;
+///
+/// # fn main() {
+/// let source = "<extra><technique><extra /></technique></extra><next />";
+/// let mut events = EventReader::from_str(source);
+///
+/// // Consume the `StartDocument` event and the start of the `<extra>` element.
+/// events.next().unwrap();
+/// events.next().unwrap();
+///
+/// skip_element(&mut events).unwrap();
+/// match events.next().unwrap() {
+///     XmlEvent::StartElement { name, .. } => assert_eq!(name.local_name, "next"),
+///     event => panic!("Unexpected event: {:?}", event),
+/// }
+/// # }
+/// ```
+///
+/// [`XmlPull`]: ./trait.XmlPull.html
+pub fn skip_element<R: XmlPull>(events: &mut R) -> Result<()> {
+    let mut depth = 1;
+    while depth > 0 {
+        match events.next()? {
+            StartElement { .. } => { depth += 1; }
+            EndElement { .. } => { depth -= 1; }
+            EndDocument => {
+                let error = XmlError::from((&*events, "Unexpected end of document"));
+                return Err(error.into());
+            }
+            _ => {}
+        }
     }
 
     Ok(())
 }
 
+/// Captures the element that was just started, along with everything inside of it, without
+/// interpreting it.
+///
+/// This is used for the parts of the schema that are too open-ended to be worth modeling, e.g. the
+/// render states of a shader pass.
+pub fn parse_raw_element<R: XmlPull>(reader: &mut DocumentReader<R>, element_start: ElementStart) -> Result<RawElement> {
+    let mut events = vec![StartElement {
+        name: element_start.name,
+        attributes: element_start.attributes,
+        namespace: Namespace::empty(),
+    }];
+
+    let mut depth = 1;
+    while depth > 0 {
+        let event = reader.next()?;
+        match event {
+            StartElement { .. } => { depth += 1; }
+            EndElement { .. } => { depth -= 1; }
+            EndDocument => {
+                let error = XmlError::from((&*reader, "Unexpected end of document"));
+                return Err(error.into());
+            }
+            _ => {}
+        }
+
+        events.push(event);
+    }
+
+    Ok(RawElement::from_events(&events).remove(0))
+}
+
 // TODO: This is a temporary helper to allow us to ignore COLLADA elements that we don't care
 // about parsing yet. This should be removed once we've implemented the full COLLADA spec.
-pub fn stub_out<R>(reader: &mut EventReader<R>, stubbed_name: &str) -> Result<()> where R: Read {
-    let mut depth = 1;
+pub fn stub_out<R>(reader: &mut DocumentReader<R>, element_start: ElementStart, stubbed_name: &str) -> Result<()> where R: XmlPull {
+    reader.context_mut().report.record_stubbed(stubbed_name);
+
+    for attribute in element_start.attributes {
+        reader.preserve_attribute(attribute);
+    }
+
     loop {
         match reader.next()? {
-            StartElement { name, attributes: _, namespace: _ } => {
-                if name.local_name == stubbed_name { depth += 1; }
+            StartElement { name, attributes, namespace: _ } => {
+                reader.preserve_element(ElementStart { name, attributes })?;
             }
 
-            EndElement { name } => {
-                if name.local_name == stubbed_name { depth -= 1; }
-                if depth == 0 { break; }
+            EndElement { .. } => { break; }
+
+            EndDocument => {
+                let error = XmlError::from((&*reader, "Unexpected end of document"));
+                return Err(error.into());
             }
 
             _ => {}
@@ -4,13 +4,138 @@
 //! documents are still accurately represented by the types in this module. Users of COLLABORATE
 //! don't need to distinguish between `1.4.0` and `1.4.1` documents.
 
-use {Error, ErrorKind, Result};
+use {Error, ErrorKind, MultiError, ParseOptions, ParseReport, Result, Strictness, Version, Warning};
+use backend::{self, XmlPull};
 use common::*;
+use encoding::Transcode;
+use image::{self, ImageInfo};
+#[cfg(feature = "rayon")]
+use parallel;
+use profiles::ExtraProfile;
+use raw::RawElement;
+use std::collections::{HashMap, HashSet};
 use std::io::Read;
+use std::io::Write;
 use utils;
 use utils::*;
-use xml::common::Position;
-use xml::reader::EventReader;
+use writer::{self, WriteOptions, WriteValue, XmlWriter};
+use xml::name::OwnedName;
+
+/// Declares an element containing a single float that can be targeted by an animation through its
+/// `sid`, e.g. `<znear sid="znear">0.1</znear>`.
+macro_rules! targetable_float {
+    ($(#[$attr:meta])* pub struct $ty:ident, $name:tt) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, ColladaElement)]
+        #[name = $name]
+        pub struct $ty {
+            /// An identifier for the value, so that it can be targeted by animations.
+            #[attribute]
+            pub sid: Option<String>,
+
+            #[text]
+            pub value: f32,
+        }
+    }
+}
+
+/// Declares an element containing a fixed number of floats that can be targeted by an animation
+/// through its `sid`, e.g. `<gravity sid="gravity">0 -9.8 0</gravity>`.
+macro_rules! targetable_vector {
+    ($(#[$attr:meta])* pub struct $ty:ident, $name:tt) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, ColladaElement)]
+        #[name = $name]
+        pub struct $ty {
+            /// An identifier for the value, so that it can be targeted by animations.
+            #[attribute]
+            pub sid: Option<String>,
+
+            #[text]
+            pub values: Vec<f32>,
+        }
+    }
+}
+
+/// Declares an element containing a single boolean that can be targeted by an animation through
+/// its `sid`, e.g. `<dynamic sid="dynamic">true</dynamic>`.
+macro_rules! targetable_bool {
+    ($(#[$attr:meta])* pub struct $ty:ident, $name:tt) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, ColladaElement)]
+        #[name = $name]
+        pub struct $ty {
+            /// An identifier for the value, so that it can be targeted by animations.
+            #[attribute]
+            pub sid: Option<String>,
+
+            #[text]
+            pub value: bool,
+        }
+    }
+}
+
+/// Declares a sampler parameter type, e.g. `<sampler3D>`, along with the texture coordinates it
+/// wraps.
+macro_rules! sampler {
+    ($(#[$attr:meta])* pub struct $ty:ident, $name:tt, [$($wrap:ident),*]) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, ColladaElement)]
+        #[name = $name]
+        pub struct $ty {
+            /// The sid of the [`Surface`] parameter to sample from.
+            ///
+            /// [`Surface`]: ./struct.Surface.html
+            #[child]
+            #[collada(version = "1.4")]
+            pub source: String,
+
+            $(
+                /// How texture coordinates outside of `0.0` to `1.0` are handled along this axis.
+                #[child]
+                #[text_type]
+                #[optional_with_default]
+                pub $wrap: WrapMode,
+            )*
+
+            /// The filter used when the texture is minified.
+            #[child]
+            #[text_type]
+            #[optional_with_default]
+            pub minfilter: FilterMode,
+
+            /// The filter used when the texture is magnified.
+            #[child]
+            #[text_type]
+            #[optional_with_default]
+            pub magfilter: FilterMode,
+
+            /// The filter used between mipmap levels.
+            #[child]
+            #[text_type]
+            #[optional_with_default]
+            pub mipfilter: FilterMode,
+
+            #[child]
+            pub border_color: Option<BorderColor>,
+
+            /// The highest mipmap level to sample from.
+            #[child]
+            pub mipmap_maxlevel: Option<usize>,
+
+            /// The bias added to the mipmap level that's sampled from.
+            #[child]
+            pub mipmap_bias: Option<f32>,
+
+            /// Arbitrary additional information about this sampler.
+            ///
+            /// For more information about 3rd-party extensions, see the
+            /// [crate-level documentation](../index.html#3rd-party-extensions).
+            #[child]
+            pub extras: Vec<Extra>,
+        }
+    }
+}
 
 /// Represents a complete COLLADA document.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
@@ -87,8 +212,33 @@ impl Collada {
     /// COLLADA versions, 3rd party extensions, and any other details that could influence how
     /// a document is parsed see the [crate-level documentation](../index.html)
     pub fn from_str(source: &str) -> Result<Collada> {
-        let reader = EventReader::new_with_config(source.as_bytes(), utils::PARSER_CONFIG.clone());
-        Self::parse(reader)
+        Self::read_utf8(source.as_bytes())
+    }
+
+    /// Read a COLLADA document from a byte slice.
+    ///
+    /// The document's encoding is detected the same as with [`read`](#method.read), but the
+    /// document is parsed directly from the slice rather than through `io::Read`. See
+    /// [`VersionedDocument::from_bytes`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![allow(unused_variables)]
+    /// use collaborate::v1_4::Collada;
+    ///
+    /// let bytes = std::fs::read("resources/blender_cube.dae").unwrap();
+    /// let collada = Collada::from_bytes(&bytes).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the document is invalid or malformed in some way, the same as
+    /// [`read`](#method.read).
+    ///
+    /// [`VersionedDocument::from_bytes`]: ../enum.VersionedDocument.html#method.from_bytes
+    pub fn from_bytes(source: &[u8]) -> Result<Collada> {
+        Self::read_bytes(source)
     }
 
     /// Attempts to parse the contents of a COLLADA document.
@@ -110,8 +260,124 @@ impl Collada {
     /// COLLADA versions, 3rd party extensions, and any other details that could influence how
     /// a document is parsed see the [crate-level documentation](../index.html).
     pub fn read<R: Read>(reader: R) -> Result<Collada> {
-        let reader = EventReader::new_with_config(reader, utils::PARSER_CONFIG.clone());
-        Self::parse(reader)
+        Self::read_source(Transcode::new(reader))
+    }
+
+    /// Attempts to parse the contents of a COLLADA document using the given options.
+    ///
+    /// Returns the parsed document along with a report of any workarounds that were needed to
+    /// parse it, including any problems that were tolerated because of `options.strictness`. See
+    /// the [`options`](../options/index.html) module for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the document is invalid or malformed in a way that `options` doesn't
+    /// allow, or if it exceeds one of the limits set in `options`.
+    pub fn read_with<R: Read>(reader: R, options: ParseOptions) -> Result<(Collada, ParseReport)> {
+        #[cfg(feature = "rayon")]
+        {
+            if options.parallel {
+                let source = parallel::read_source(reader, &options)?;
+                let (document, report) = parallel::parse::<Collada>(&source, options)?;
+                return Ok((document?, report));
+            }
+        }
+
+        let reader = backend::open_encoded(reader, &options)?;
+        Self::parse_events(reader, options)
+    }
+
+    /// Attempts to parse the contents of a COLLADA document, collecting any recoverable problems
+    /// as warnings instead of failing.
+    ///
+    /// The document is parsed with [`Strictness::Lenient`], and the resulting report is flattened
+    /// into a list of [`Warning`]s. Use [`read_with`](#method.read_with) for more control over how
+    /// the document is parsed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the document is invalid or malformed in a way that can't be recovered
+    /// from.
+    ///
+    /// [`Strictness::Lenient`]: ../options/enum.Strictness.html#variant.Lenient
+    /// [`Warning`]: ../report/enum.Warning.html
+    pub fn read_with_warnings<R: Read>(reader: R) -> Result<(Collada, Vec<Warning>)> {
+        let options = ParseOptions {
+            strictness: Strictness::Lenient,
+            ..ParseOptions::default()
+        };
+        let (document, report) = Self::read_with(reader, options)?;
+        Ok((document, report.into_warnings()))
+    }
+
+    /// Attempts to parse the contents of a COLLADA document, collecting every error found instead
+    /// of stopping at the first one.
+    ///
+    /// The document is parsed with [`ParseOptions::recover`] set, so elements that fail to parse
+    /// are skipped and parsing continues with the rest of the document. This is useful for
+    /// triaging broken exports, where fixing one error at a time would be tedious.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` with all of the errors found, in the order they were found, if any part of
+    /// the document failed to parse. The document itself is discarded in that case.
+    ///
+    /// [`ParseOptions::recover`]: ../options/struct.ParseOptions.html#structfield.recover
+    pub fn read_recovering<R: Read>(reader: R) -> ::std::result::Result<Collada, MultiError> {
+        let options = ParseOptions {
+            recover: true,
+            ..ParseOptions::default()
+        };
+        let (document, report) = backend::open_encoded(reader, &options)
+            .and_then(|reader| Self::parse_document(reader, options))?;
+        MultiError::collect(document, report.errors)
+    }
+
+    /// Writes the document to `writer` as COLLADA XML.
+    ///
+    /// See the [`writer`](../writer/index.html) module for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use collaborate::v1_4::Collada;
+    /// use collaborate::writer::WriteOptions;
+    ///
+    /// let document = Collada::read(File::open("resources/blender_cube.dae").unwrap()).unwrap();
+    /// let mut output = Vec::new();
+    /// document.write(&mut output, WriteOptions::default()).unwrap();
+    /// ```
+    ///
+    /// The document can also be written as COLLADA 1.5:
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// use collaborate::{Version, VersionedDocument};
+    /// # use collaborate::v1_4::Collada;
+    /// # use collaborate::writer::WriteOptions;
+    /// # let document = Collada::read(File::open("resources/blender_cube.dae").unwrap()).unwrap();
+    ///
+    /// let options = WriteOptions {
+    ///     version: Some(Version::V1_5_0),
+    ///     ..WriteOptions::default()
+    /// };
+    /// let mut output = Vec::new();
+    /// document.write(&mut output, options).unwrap();
+    ///
+    /// match VersionedDocument::from_str(&String::from_utf8(output).unwrap()).unwrap() {
+    ///     VersionedDocument::V1_5(written) => assert_eq!("1.5.0", written.version),
+    ///     _ => panic!("Expected a 1.5 document"),
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if writing to `writer` fails, or if the document can't be represented in
+    /// the version requested by `options`.
+    pub fn write<W: Write>(&self, writer: W, options: WriteOptions) -> writer::Result<()> {
+        let version = self.version.parse().unwrap_or(Version::V1_4_1);
+        writer::write_document(self, writer, options, version)
     }
 
     /// Returns an iterator over all the libraries in the document.
@@ -133,505 +399,9102 @@ impl Collada {
         self.libraries.iter()
     }
 
-    /// Helper method that handles the bulk of the parsing work.
-    ///
-    /// `from_str` and `read` just create the `EventReader<R>` instance and then defer to `parse`.
-    fn parse<R: Read>(mut reader: EventReader<R>) -> Result<Collada> {
-        // Get the opening `<COLLADA>` tag and find the "version" attribute.
-        let element_start = utils::get_document_start(&mut reader)?;
-        let version = element_start.attributes.iter()
-            .find(|attrib| attrib.name.local_name == "version")
-            .map(|attrib| attrib.value.clone())
-            .ok_or(Error {
-                position: reader.position(),
-                kind: ErrorKind::MissingAttribute {
-                    element: "COLLADA",
-                    attribute: "version",
-                },
-            })?;
+    /// Returns the image with the given id, if it's in one of the document's
+    /// `<library_images>` elements.
+    pub fn image(&self, id: &str) -> Option<&Image> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_images)
+            .flat_map(|library| library.images.iter())
+            .find(|image| image.id.as_deref() == Some(id))
+    }
 
-        if version != "1.4.0" && version != "1.4.1" {
-            return Err(Error {
-                position: reader.position(),
-                kind: ErrorKind::UnsupportedVersion {
-                    version: version,
-                },
-            });
-        }
+    /// Returns the animation with the given id, if it's in one of the document's
+    /// `<library_animations>` elements.
+    ///
+    /// Animations nested within other animations are found as well.
+    pub fn animation(&self, id: &str) -> Option<&Animation> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_animations)
+            .flat_map(|library| library.animations.iter())
+            .filter_map(|animation| animation.find_animation(id))
+            .next()
+    }
 
-        Collada::parse_element(&mut reader, element_start)
+    /// Returns the animation clip with the given id, if it's in one of the document's
+    /// `<library_animation_clips>` elements.
+    pub fn animation_clip(&self, id: &str) -> Option<&AnimationClip> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_animation_clips)
+            .filter_map(|library| library.animation_clip(id))
+            .next()
     }
-}
 
-/// Describes a stream of values from an array data source.
-///
-/// An accessor declares an access pattern into an array of source data. The arrays can be
-/// arranged in either an interleaved or noninterleaved manner, depending on the `offset` and
-/// `stride` values.
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "accessor"]
-pub struct Accessor {
-    /// The number of times the array is accessed.
-    #[attribute]
-    pub count: usize,
+    /// Returns an iterator over all of the animation clips in the document.
+    pub fn animation_clips<'a>(&'a self) -> Box<Iterator<Item = &'a AnimationClip> + 'a> {
+        Box::new(self.libraries.iter()
+            .filter_map(Library::as_library_animation_clips)
+            .flat_map(|library| library.animation_clips.iter()))
+    }
 
-    /// The index of the first value to be read from the array.
-    #[attribute]
-    #[optional_with_default = "0"]
-    pub offset: usize,
+    /// Returns the animation referenced by `instance`, if it's defined in this document.
+    pub fn instance_animation(&self, instance: &InstanceAnimation) -> Option<&Animation> {
+        instance.url.local_id().and_then(|id| self.animation(id))
+    }
 
-    /// The location of the array to access.
+    /// Returns the animations that make up `clip`, in the order they're instantiated.
     ///
-    /// This may refer to a COLLADA array element or to an array data source outside the scope
-    /// of the instance document; The source does not need to be a COLLADA document.
-    #[attribute]
-    pub source: AnyUri,
+    /// Instances that reference animations outside of this document are skipped.
+    pub fn clip_animations<'a>(&'a self, clip: &'a AnimationClip) -> Box<Iterator<Item = &'a Animation> + 'a> {
+        Box::new(clip.instances.iter().filter_map(move |instance| self.instance_animation(instance)))
+    }
 
-    /// The number of values that are to be considered a unit during each access to the array.
-    #[attribute]
-    #[optional_with_default = "1"]
-    pub stride: usize,
+    /// Returns the geometry with the given id, if it's in one of the document's
+    /// `<library_geometries>` elements.
+    pub fn geometry(&self, id: &str) -> Option<&Geometry> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_geometries)
+            .filter_map(|library| library.geometry(id))
+            .next()
+    }
 
-    #[child]
-    pub params: Vec<Param>,
-}
+    /// Returns the geometry that `mesh` is the convex hull of, if it's given by `convex_hull_of`
+    /// and the geometry is in this document.
+    pub fn convex_hull_of(&self, mesh: &ConvexMesh) -> Option<&Geometry> {
+        mesh.convex_hull_of.as_ref()
+            .and_then(AnyUri::local_id)
+            .and_then(|id| self.geometry(id))
+    }
 
-impl Accessor {
-    /// Access a source array using the accessor.
+    /// Resolves a texture input of `effect` to the URI of the image it samples.
     ///
-    /// Returns a sub-slice of `array` containing the
-    pub fn access<'a, 'b, T>(&'a self, array: &'b [T], index: usize) -> &'b [T] {
-        let start = self.offset + self.stride * index;
-        let end = start + self.stride;
-        &array[start..end]
+    /// The texture names a `<sampler2D>` parameter of the effect, which names a `<surface>`
+    /// parameter, which is initialized from an image. The image is looked up in the effect first,
+    /// and then in the document's `<library_images>`.
+    ///
+    /// Returns `None` if any step of the lookup fails, or if the image's data is embedded in the
+    /// document rather than referenced by URI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use collaborate::v1_4::Collada;
+    /// # let file = File::open("resources/blender_cube.dae").unwrap();
+    /// let document = Collada::read(file).unwrap();
+    /// let effects = document.libraries()
+    ///     .filter_map(|library| library.as_library_effects())
+    ///     .flat_map(|library| &library.effects);
+    /// for effect in effects {
+    ///     let texture = effect.common_shading()
+    ///         .and_then(|shading| shading.diffuse())
+    ///         .and_then(|diffuse| diffuse.as_texture());
+    ///     if let Some(texture) = texture {
+    ///         println!("{}: {:?}", effect.id, document.texture_image_uri(effect, texture));
+    ///     }
+    /// }
+    /// ```
+    pub fn texture_image_uri<'a>(&'a self, effect: &'a Effect, texture: &Texture) -> Option<&'a AnyUri> {
+        let id = effect.texture_surface(texture).and_then(Surface::image)?;
+        effect.image(id)
+            .or_else(|| self.image(id))
+            .and_then(Image::uri)
     }
-}
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-pub enum Array {
-    Idref(IdrefArray),
-    Name(NameArray),
-    Bool(BoolArray),
-    Float(FloatArray),
-    Int(IntArray),
-}
+    /// Returns the effect with the given id, if it's in one of the document's
+    /// `<library_effects>` elements.
+    pub fn effect(&self, id: &str) -> Option<&Effect> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_effects)
+            .filter_map(|library| library.effect(id))
+            .next()
+    }
 
-impl Array {
-    pub fn as_float_array(&self) -> Option<&FloatArray> {
-        match *self {
-            Array::Float(ref float_array) => Some(float_array),
-            _ => None,
-        }
+    /// Returns the material with the given id, if it's in one of the document's
+    /// `<library_materials>` elements.
+    pub fn material(&self, id: &str) -> Option<&Material> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_materials)
+            .filter_map(|library| library.material(id))
+            .next()
     }
-}
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "asset"]
-pub struct Asset {
-    #[child]
-    pub contributors: Vec<Contributor>,
+    /// Returns the effect that `material` instantiates, if it's in this document.
+    pub fn material_effect(&self, material: &Material) -> Option<&Effect> {
+        material.instance_effect.url.local_id().and_then(|id| self.effect(id))
+    }
 
-    #[child]
-    pub created: DateTime,
+    /// Returns the value of the effect parameter `sid` as used by `material`.
+    ///
+    /// A value set by the material's `<setparam>` elements overrides the value declared by the
+    /// effect.
+    pub fn material_param<'a>(&'a self, material: &'a Material, sid: &str) -> Option<&'a ParamValue> {
+        material.instance_effect.set_param(sid)
+            .map(|param| &param.value)
+            .or_else(|| {
+                self.material_effect(material)
+                    .and_then(|effect| effect.new_param(sid))
+                    .map(|param| &param.value)
+            })
+    }
 
-    #[child]
-    pub keywords: Option<String>,
+    /// Returns the material that `instance` binds to its symbol, if it's in this document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use collaborate::v1_4::Collada;
+    /// # let file = File::open("resources/blender_cube.dae").unwrap();
+    /// let document = Collada::read(file).unwrap();
+    /// let cube = document.visual_scene("Scene").and_then(|scene| scene.node("Cube")).unwrap();
+    /// let instance = cube.instance_geometries[0].instance_material("Material-material").unwrap();
+    /// let material = document.bound_material(instance).unwrap();
+    /// assert_eq!(Some("Material"), material.name.as_deref());
+    /// ```
+    pub fn bound_material(&self, instance: &InstanceMaterial) -> Option<&Material> {
+        instance.target.local_id().and_then(|id| self.material(id))
+    }
 
-    #[child]
-    pub modified: DateTime,
+    /// Returns the node with the given id, if it's anywhere in one of the document's
+    /// `<library_nodes>` or `<library_visual_scenes>` elements.
+    ///
+    /// The shared nodes in `<library_nodes>` are searched first.
+    pub fn node(&self, id: &str) -> Option<&Node> {
+        let shared = self.libraries.iter()
+            .filter_map(Library::as_library_nodes)
+            .filter_map(|library| library.node(id))
+            .next();
+        shared.or_else(|| {
+            self.libraries.iter()
+                .filter_map(Library::as_library_visual_scenes)
+                .flat_map(|library| library.visual_scenes.iter())
+                .filter_map(|visual_scene| visual_scene.node(id))
+                .next()
+        })
+    }
 
-    #[child]
-    pub revision: Option<String>,
+    /// Returns the camera with the given id, if it's in one of the document's
+    /// `<library_cameras>` elements.
+    pub fn camera(&self, id: &str) -> Option<&Camera> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_cameras)
+            .filter_map(|library| library.camera(id))
+            .next()
+    }
 
-    #[child]
-    pub subject: Option<String>,
+    /// Returns the light with the given id, if it's in one of the document's `<library_lights>`
+    /// elements.
+    pub fn light(&self, id: &str) -> Option<&Light> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_lights)
+            .filter_map(|library| library.light(id))
+            .next()
+    }
 
-    #[child]
-    pub title: Option<String>,
+    /// Finds the value targeted by the address `path`.
+    ///
+    /// The address starts from the node, camera, light, or effect with the id given by
+    /// `path.root`. If the address is relative (i.e. it starts with `./`), it starts from `root`
+    /// instead. Each sid in the path is then searched for within the previous element, see e.g.
+    /// [`Node::find_sid`](./struct.Node.html#method.find_sid).
+    ///
+    /// Returns `None` if any part of the path can't be found, or if the selector doesn't fit the
+    /// targeted value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use collaborate::common::SidRef;
+    /// # use collaborate::v1_4::{Collada, SidTarget};
+    /// # let file = File::open("resources/blender_cube.dae").unwrap();
+    /// let document = Collada::read(file).unwrap();
+    ///
+    /// // Select the x translation from the camera's transform matrix.
+    /// let path: SidRef = "Camera/transform(0)(3)".parse().unwrap();
+    /// let resolved = document.resolve_sid_path(None, &path).unwrap();
+    /// match resolved.target {
+    ///     SidTarget::Transform(transform) => assert_eq!(Some("transform"), transform.sid()),
+    ///     ref target => panic!("Expected a transform, got {:?}", target),
+    /// }
+    /// assert_eq!(Some(3), resolved.component);
+    /// assert_eq!(Some(&[7.481132][..]), resolved.values());
+    /// ```
+    pub fn resolve_sid_path<'a>(&'a self, root: Option<&'a Node>, path: &SidRef) -> Option<ResolvedSid<'a>> {
+        let mut target = match path.root {
+            Some(ref id) => self.node(id).map(SidTarget::Node)
+                .or_else(|| self.camera(id).map(SidTarget::Camera))
+                .or_else(|| self.light(id).map(SidTarget::Light))
+                .or_else(|| self.effect(id).map(SidTarget::Effect))?,
+            None => SidTarget::Node(root?),
+        };
 
-    #[child]
-    #[optional_with_default]
-    pub unit: Unit,
+        for sid in &path.sids {
+            target = match target {
+                SidTarget::Camera(camera) => camera.find_sid(sid)?,
+                SidTarget::Effect(effect) => effect.find_sid(sid)?,
+                SidTarget::Light(light) => light.find_sid(sid)?,
+                SidTarget::Node(node) => node.find_sid(sid)?,
+                _ => return None,
+            };
+        }
 
-    #[child]
-    #[optional_with_default]
-    pub up_axis: UpAxis,
-}
+        let component = match path.selector {
+            Some(ref selector) => {
+                let (values, columns) = match target {
+                    SidTarget::Color(color) => (&*color.values, color.values.len()),
+                    SidTarget::Transform(Transform::Matrix(matrix)) => (&*matrix.values, 4),
+                    SidTarget::Transform(transform) => (transform.values(), transform.values().len()),
+                    _ => return None,
+                };
+                let index = selector.index(columns)?;
+                if index >= values.len() {
+                    return None;
+                }
+                Some(index)
+            }
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "bool_array"]
-pub struct BoolArray;
+            None => None,
+        };
 
-#[derive(Debug, Clone, Default, PartialEq, Eq, ColladaElement)]
-#[name = "contributor"]
-pub struct Contributor {
-    #[child]
-    pub author: Option<String>,
+        Some(ResolvedSid { target, component })
+    }
 
-    #[child]
-    pub authoring_tool: Option<String>,
+    /// Finds the value animated by `channel`.
+    ///
+    /// Returns `None` if the channel's target isn't a valid address, or if it can't be resolved
+    /// with [`resolve_sid_path`](#method.resolve_sid_path).
+    pub fn resolve_channel<'a>(&'a self, channel: &Channel) -> Option<ResolvedSid<'a>> {
+        channel.target_ref().ok().and_then(|path| self.resolve_sid_path(None, &path))
+    }
 
-    #[child]
-    pub comments: Option<String>,
+    /// Renames the joints of the document's skins, replacing each name that's a key in `names`
+    /// with its value.
+    ///
+    /// See [`remap_joint_names_with`](#method.remap_joint_names_with) for what gets renamed.
+    pub fn remap_joint_names(&mut self, names: &HashMap<String, String>) {
+        self.remap_joint_names_with(|name| names.get(name).cloned());
+    }
 
-    #[child]
-    pub copyright: Option<String>,
+    /// Renames the joints of the document's skins, e.g. to strip the prefix an exporter puts on
+    /// every bone, or to match the names an existing rig uses.
+    ///
+    /// `rename` is called with each name and returns the new name, or `None` to keep the name as
+    /// it is. A joint is renamed everywhere it's referenced, so that the skins, animations, and
+    /// scene stay consistent:
+    ///
+    /// - The joint names or ids in the `"JOINT"` source of each skin.
+    /// - The id, sid, and name of each joint node. A node is a joint if its type is `JOINT` or if
+    ///   a skin lists its id or sid as a joint.
+    /// - The ids and sids of renamed nodes in the targets of animation channels. Targets that
+    ///   aren't valid addresses are left as they are.
+    /// - The skeleton roots of controller instances that reference a renamed node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use collaborate::v1_4::Collada;
+    ///
+    /// static DOCUMENT: &'static str = r#"
+    ///     <?xml version="1.0" encoding="utf-8"?>
+    ///     <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+    ///         <asset>
+    ///             <created>2017-02-07T20:44:30Z</created>
+    ///             <modified>2017-02-07T20:44:30Z</modified>
+    ///         </asset>
+    ///         <library_visual_scenes>
+    ///             <visual_scene id="Scene">
+    ///                 <node id="mixamorig_Hips" sid="mixamorig_Hips" type="JOINT"/>
+    ///             </visual_scene>
+    ///         </library_visual_scenes>
+    ///     </COLLADA>
+    /// "#;
+    ///
+    /// let mut document = Collada::from_str(DOCUMENT).unwrap();
+    /// document.remap_joint_names_with(|name| name.strip_prefix("mixamorig_").map(String::from));
+    ///
+    /// let hips = document.node("Hips").unwrap();
+    /// assert_eq!(Some("Hips"), hips.sid.as_deref());
+    /// ```
+    pub fn remap_joint_names_with<F>(&mut self, rename: F) where F: FnMut(&str) -> Option<String> {
+        let mut renames = JointRenames {
+            rename,
+            joints: HashSet::new(),
+            ids: HashMap::new(),
+            sids: HashMap::new(),
+        };
 
+        // Skins are renamed first, since their joints determine which nodes are joints.
+        for library in &mut self.libraries {
+            if let Library::Controllers(ref mut library) = *library {
+                for controller in &mut library.controllers {
+                    if let ControlElement::Skin(ref mut skin) = controller.control_element {
+                        renames.rename_skin(skin);
+                    }
+                }
+            }
+        }
+
+        for library in &mut self.libraries {
+            match *library {
+                Library::Nodes(ref mut library) => renames.rename_nodes(&mut library.nodes),
+                Library::VisualScenes(ref mut library) => {
+                    for visual_scene in &mut library.visual_scenes {
+                        renames.rename_nodes(&mut visual_scene.nodes);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for library in &mut self.libraries {
+            match *library {
+                Library::Animations(ref mut library) => renames.rename_channels(&mut library.animations),
+                Library::Nodes(ref mut library) => renames.rename_skeletons(&mut library.nodes),
+                Library::VisualScenes(ref mut library) => {
+                    for visual_scene in &mut library.visual_scenes {
+                        renames.rename_skeletons(&mut visual_scene.nodes);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the node that `instance` places in the scene, if it's in this document.
+    ///
+    /// The instantiated node and its children are placed in the scene as children of the node
+    /// containing `instance`.
+    pub fn instance_node(&self, instance: &InstanceNode) -> Option<&Node> {
+        instance.url.local_id().and_then(|id| self.node(id))
+    }
+
+    /// Returns the node containing the camera that `render` renders from, if it's defined in this
+    /// document.
+    pub fn render_camera(&self, render: &Render) -> Option<&Node> {
+        render.camera_node.local_id().and_then(|id| self.node(id))
+    }
+
+    /// Returns the force field with the given id, if it's in one of the document's
+    /// `<library_force_fields>` elements.
+    pub fn force_field(&self, id: &str) -> Option<&ForceField> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_force_fields)
+            .filter_map(|library| library.force_field(id))
+            .next()
+    }
+
+    /// Returns the force field that `instance` applies, if it's in this document.
+    pub fn instance_force_field(&self, instance: &InstanceForceField) -> Option<&ForceField> {
+        instance.url.local_id().and_then(|id| self.force_field(id))
+    }
+
+    /// Returns the physics material with the given id, if it's in one of the document's
+    /// `<library_physics_materials>` elements.
+    pub fn physics_material(&self, id: &str) -> Option<&PhysicsMaterial> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_physics_materials)
+            .filter_map(|library| library.physics_material(id))
+            .next()
+    }
+
+    /// Returns the physics model with the given id, if it's in one of the document's
+    /// `<library_physics_models>` elements.
+    pub fn physics_model(&self, id: &str) -> Option<&PhysicsModel> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_physics_models)
+            .filter_map(|library| library.physics_model(id))
+            .next()
+    }
+
+    /// Returns the physics scene with the given id, if it's in one of the document's
+    /// `<library_physics_scenes>` elements.
+    pub fn physics_scene(&self, id: &str) -> Option<&PhysicsScene> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_physics_scenes)
+            .filter_map(|library| library.physics_scene(id))
+            .next()
+    }
+
+    /// Returns the visual scene with the given id, if it's in one of the document's
+    /// `<library_visual_scenes>` elements.
+    pub fn visual_scene(&self, id: &str) -> Option<&VisualScene> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_visual_scenes)
+            .filter_map(|library| library.visual_scene(id))
+            .next()
+    }
+
+    /// Returns the visual scene referenced by `instance`, if it's defined in this document.
+    pub fn instance_visual_scene(&self, instance: &InstanceVisualScene) -> Option<&VisualScene> {
+        instance.url.local_id().and_then(|id| self.visual_scene(id))
+    }
+
+    /// Returns the physics scene referenced by `instance`, if it's defined in this document.
+    pub fn instance_physics_scene(&self, instance: &InstancePhysicsScene) -> Option<&PhysicsScene> {
+        instance.url.local_id().and_then(|id| self.physics_scene(id))
+    }
+
+    /// Returns the visual scene instantiated by the document's `<scene>`, which is the scene an
+    /// application loading the document should display.
+    ///
+    /// Returns `None` if the document has no `<scene>`, if the scene doesn't instantiate a visual
+    /// scene, or if the visual scene isn't defined in this document.
+    pub fn main_scene(&self) -> Option<&VisualScene> {
+        self.scene.as_ref()
+            .and_then(|scene| scene.instance_visual_scene.as_ref())
+            .and_then(|instance| self.instance_visual_scene(instance))
+    }
+
+    /// Returns the unit of distance and up axis declared by the document's root `<asset>`.
+    ///
+    /// Libraries, geometries, nodes, and other elements may override these with their own
+    /// `<asset>`, see [`library_coordinate_system`](#method.library_coordinate_system),
+    /// [`geometry_coordinate_system`](#method.geometry_coordinate_system), and
+    /// [`node_coordinate_system`](#method.node_coordinate_system).
+    pub fn coordinate_system(&self) -> CoordinateSystem {
+        self.asset.coordinate_system(&CoordinateSystem::default())
+    }
+
+    /// Returns the coordinate system of the elements within `library`, taking into account the
+    /// library's own `<asset>`.
+    pub fn library_coordinate_system(&self, library: &Library) -> CoordinateSystem {
+        nested_coordinate_system(self.coordinate_system(), vec![library.asset()])
+    }
+
+    /// Returns the coordinate system of `geometry`, taking into account the `<asset>` of the
+    /// geometry and the library containing it.
+    ///
+    /// Returns `None` if `geometry` isn't borrowed from this document's
+    /// `<library_geometries>`.
+    pub fn geometry_coordinate_system(&self, geometry: &Geometry) -> Option<CoordinateSystem> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_geometries)
+            .find(|library| library.geometries.iter().any(|other| ::std::ptr::eq(other, geometry)))
+            .map(|library| {
+                nested_coordinate_system(
+                    self.coordinate_system(),
+                    vec![library.asset.as_ref(), geometry.asset.as_ref()],
+                )
+            })
+    }
+
+    /// Returns the coordinate system of `node`, taking into account the `<asset>` of the node,
+    /// of each of its ancestors, and of the visual scene and library containing it.
+    ///
+    /// Returns `None` if `node` isn't borrowed from this document's `<library_nodes>` or
+    /// `<library_visual_scenes>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::common::UpAxis;
+    /// # use collaborate::v1_4::Collada;
+    /// static DOCUMENT: &'static str = r#"
+    ///     <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+    ///         <asset>
+    ///             <created>2017-02-07T20:44:30Z</created>
+    ///             <modified>2017-02-07T20:44:30Z</modified>
+    ///             <up_axis>Z_UP</up_axis>
+    ///         </asset>
+    ///         <library_nodes>
+    ///             <node id="Prop">
+    ///                 <asset>
+    ///                     <created>2017-02-07T20:44:30Z</created>
+    ///                     <modified>2017-02-07T20:44:30Z</modified>
+    ///                     <unit meter="0.01" name="centimeter" />
+    ///                 </asset>
+    ///             </node>
+    ///         </library_nodes>
+    ///     </COLLADA>
+    /// "#;
+    ///
+    /// let document = Collada::from_str(DOCUMENT).unwrap();
+    /// let system = document.node_coordinate_system(document.node("Prop").unwrap()).unwrap();
+    /// assert_eq!(0.01, system.unit.meter);
+    /// assert_eq!(UpAxis::Z, system.up_axis);
+    /// ```
+    pub fn node_coordinate_system(&self, node: &Node) -> Option<CoordinateSystem> {
+        for library in &self.libraries {
+            let roots: Vec<(Option<&Asset>, &[Node])> = match *library {
+                Library::Nodes(ref nodes) => vec![(None, &nodes.nodes)],
+                Library::VisualScenes(ref scenes) => scenes.visual_scenes.iter()
+                    .map(|scene| (scene.asset.as_ref(), &*scene.nodes))
+                    .collect(),
+                _ => continue,
+            };
+
+            for (scene_asset, nodes) in roots {
+                let mut path = Vec::new();
+                if nodes.iter().any(|root| root.path_to(node, &mut path)) {
+                    let assets = vec![library.asset(), scene_asset].into_iter()
+                        .chain(path.into_iter().map(|node| node.asset.as_ref()));
+                    return Some(nested_coordinate_system(self.coordinate_system(), assets));
+                }
+            }
+        }
+
+        None
+    }
+
+    fn read_source<R: Read>(source: Transcode<R>) -> Result<Collada> {
+        let options = ParseOptions::default();
+        let reader = backend::open_transcoded(source, &options)?;
+        Self::parse_events(reader, options).map(|(document, _)| document)
+    }
+
+    pub(crate) fn read_utf8(source: &[u8]) -> Result<Collada> {
+        backend::parse_utf8(source, ParseOptions::default(), |reader, options| {
+            Self::parse_events(reader, options)
+        }).map(|(document, _)| document)
+    }
+
+    fn read_bytes(source: &[u8]) -> Result<Collada> {
+        backend::parse_bytes(source, ParseOptions::default(), |reader, options| {
+            Self::parse_events(reader, options)
+        }).map(|(document, _)| document)
+    }
+
+    /// Helper method that handles the bulk of the parsing work.
+    ///
+    /// `from_str`, `read`, and `read_with` just open the XML backend and then defer to
+    /// `parse_events`.
+    fn parse_events<P: XmlPull>(reader: P, options: ParseOptions) -> Result<(Collada, ParseReport)> {
+        let (document, report) = Self::parse_document(reader, options)?;
+        Ok((document?, report))
+    }
+
+    /// Parses a document, returning the report for it even if the document fails to parse.
+    ///
+    /// The outer `Result` is `Err` if the document can't be parsed at all, e.g. because it's for
+    /// a different version of the specification.
+    fn parse_document<P: XmlPull>(mut reader: P, options: ParseOptions) -> Result<(Result<Collada>, ParseReport)> {
+        // Get the opening `<COLLADA>` tag and find the "version" attribute.
+        let element_start = utils::get_document_start(&mut reader)?;
+        let version = utils::get_document_version(&reader, &element_start)?;
+        if !version.is_compatible("1.4") {
+            return Err(Error {
+                position: reader.position(),
+                kind: ErrorKind::UnsupportedVersion {
+                    version: version.to_string(),
+                },
+            });
+        }
+
+        let mut reader = DocumentReader::new(reader, ParseContext::with_options(version, options));
+        let document = Collada::parse_element(&mut reader, element_start);
+        Ok((document, reader.into_context().report))
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl parallel::SplitDocument for Collada {
+    type Library = Library;
+
+    fn parse_document<P: XmlPull>(reader: P, options: ParseOptions) -> Result<(Result<Collada>, ParseReport)> {
+        Collada::parse_document(reader, options)
+    }
+
+    fn set_libraries(&mut self, libraries: Vec<Library>) {
+        self.libraries = libraries;
+    }
+}
+
+/// Describes a stream of values from an array data source.
+///
+/// An accessor declares an access pattern into an array of source data. The arrays can be
+/// arranged in either an interleaved or noninterleaved manner, depending on the `offset` and
+/// `stride` values.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "accessor"]
+pub struct Accessor {
+    /// The number of times the array is accessed.
+    #[attribute]
+    pub count: usize,
+
+    /// The index of the first value to be read from the array.
+    #[attribute]
+    #[optional_with_default = "0"]
+    pub offset: usize,
+
+    /// The location of the array to access.
+    ///
+    /// This may refer to a COLLADA array element or to an array data source outside the scope
+    /// of the instance document; The source does not need to be a COLLADA document.
+    #[attribute]
+    pub source: AnyUri,
+
+    /// The number of values that are to be considered a unit during each access to the array.
+    #[attribute]
+    #[optional_with_default = "1"]
+    pub stride: usize,
+
+    #[child]
+    pub params: Vec<Param>,
+}
+
+impl Accessor {
+    /// Access a source array using the accessor.
+    ///
+    /// Returns a sub-slice of `array` containing the
+    pub fn access<'a, 'b, T>(&'a self, array: &'b [T], index: usize) -> &'b [T] {
+        let start = self.offset + self.stride * index;
+        let end = start + self.stride;
+        &array[start..end]
+    }
+}
+
+/// A light that lights everything in the scene equally, regardless of position or orientation.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "ambient"]
+pub struct Ambient {
+    /// The color of the light.
+    #[child]
+    pub color: Color,
+}
+
+/// The ancestors of a node visited by [`VisualScene::iter_nodes`].
+///
+/// [`VisualScene::iter_nodes`]: ./struct.VisualScene.html#method.iter_nodes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ancestry<'a> {
+    nodes: Vec<&'a Node>,
+}
+
+impl<'a> Ancestry<'a> {
+    /// Returns the node's ancestors, starting with the root node of the scene and ending with the
+    /// node's parent.
+    pub fn nodes(&self) -> &[&'a Node] {
+        &self.nodes
+    }
+
+    /// Returns the node's parent, or `None` if the node is one of the scene's root nodes.
+    pub fn parent(&self) -> Option<&'a Node> {
+        self.nodes.last().cloned()
+    }
+
+    /// Returns the number of ancestors the node has, i.e. 0 for the scene's root nodes.
+    pub fn depth(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the transforms of the node's ancestors combined into a single 4x4 matrix in
+    /// row-major order, which transforms from the node's parent's space into the scene's.
+    ///
+    /// The matrix is computed each time this is called. Returns `None` if any of the ancestors'
+    /// transforms is malformed.
+    pub fn matrix(&self) -> Option<[f32; 16]> {
+        let mut matrix = IDENTITY;
+        for node in &self.nodes {
+            matrix = multiply(&matrix, &node.local_matrix()?);
+        }
+        Some(matrix)
+    }
+
+    /// Returns the matrix that transforms the contents of `node` into the scene's space, where
+    /// `node` is the node visited along with this ancestry.
+    ///
+    /// Returns `None` if any of the transforms of `node` or its ancestors is malformed.
+    pub fn world_matrix(&self, node: &Node) -> Option<[f32; 16]> {
+        Some(multiply(&self.matrix()?, &node.local_matrix()?))
+    }
+}
+
+/// A spring acting on the rotation of the attachment of a rigid constraint.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "angular"]
+pub struct AngularSpring {
+    /// The stiffness of the spring, which defaults to 1.
+    #[child]
+    pub stiffness: Option<Stiffness>,
+
+    /// The damping of the spring, which defaults to 1.
+    #[child]
+    pub damping: Option<Damping>,
+
+    /// The resting position of the spring, which defaults to 0.
+    #[child]
+    pub target_value: Option<TargetValue>,
+}
+
+targetable_vector! {
+    /// The initial angular velocity of an
+    /// [`InstanceRigidBody`](./struct.InstanceRigidBody.html), in degrees per second around each axis.
+    pub struct AngularVelocity, "angular_velocity"
+}
+
+/// Declares animation information.
+///
+/// The keyframe data for an animation is held in its [`Source`]s. Each [`Sampler`] combines
+/// those sources into an animation curve, and each [`Channel`] applies the output of a sampler to
+/// a value elsewhere in the document. Animations can also contain other animations, which is
+/// used to group related animations together (e.g. all of the animations for one character).
+///
+/// [`Source`]: ./struct.Source.html
+/// [`Sampler`]: ./struct.Sampler.html
+/// [`Channel`]: ./struct.Channel.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "animation"]
+pub struct Animation {
+    /// A unique identifier for the animation.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this animation.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about the animation and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The keyframe data used by the animation's samplers.
+    #[child]
+    pub sources: Vec<Source>,
+
+    /// The animation curves built from the animation's sources.
+    #[child]
+    pub samplers: Vec<Sampler>,
+
+    /// The values in the document that are driven by the animation's samplers.
+    #[child]
+    pub channels: Vec<Channel>,
+
+    /// Animations grouped within this animation.
+    #[child]
+    pub animations: Vec<Animation>,
+
+    /// Arbitrary additional information about this animation and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Animation {
+    /// Returns the source in this animation with the given id, if there is one.
+    pub fn source(&self, id: &str) -> Option<&Source> {
+        self.sources.iter().find(|source| source.id == id)
+    }
+
+    /// Returns the sampler in this animation with the given id, if there is one.
+    pub fn sampler(&self, id: &str) -> Option<&Sampler> {
+        self.samplers.iter().find(|sampler| sampler.id.as_deref() == Some(id))
+    }
+
+    /// Returns the sampler that drives `channel`, if it's in this animation.
+    pub fn channel_sampler(&self, channel: &Channel) -> Option<&Sampler> {
+        self.sampler(channel.source.id())
+    }
+
+    /// Returns this animation or the animation nested within it with the given id, if there is
+    /// one.
+    pub fn find_animation(&self, id: &str) -> Option<&Animation> {
+        if self.id.as_deref() == Some(id) {
+            return Some(self);
+        }
+
+        self.animations.iter().filter_map(|animation| animation.find_animation(id)).next()
+    }
+
+    /// Returns the source connected to the input of `sampler` with the given semantic (e.g.
+    /// `"OUTPUT"`), if the sampler has such an input and the source is in this animation.
+    pub fn sampler_source(&self, sampler: &Sampler, semantic: &str) -> Option<&Source> {
+        sampler.input(semantic).and_then(|input| self.source(input.source.id()))
+    }
+}
+
+/// A named section of the document's animations, e.g. a character's walk cycle.
+///
+/// Exporters often write every animation for a model into a single document. A clip groups the
+/// animations that belong together and gives the time span they play over, so that the clips can
+/// be separated back out again. The animations themselves are referenced by `instances`, and can
+/// be resolved with [`Collada::clip_animations`].
+///
+/// [`Collada::clip_animations`]: ./struct.Collada.html#method.clip_animations
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "animation_clip"]
+pub struct AnimationClip {
+    /// A unique identifier for the clip.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this clip, e.g. `"walk"`.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The time in seconds at which the clip starts. Defaults to `0.0`.
+    #[attribute]
+    #[optional_with_default = "0.0"]
+    pub start: f32,
+
+    /// The time in seconds at which the clip ends. If not specified, the clip plays until its
+    /// animations end.
+    #[attribute]
+    pub end: Option<f32>,
+
+    /// Metadata about the clip.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The animations that make up the clip. There will always be at least one animation.
+    #[child]
+    #[required]
+    pub instances: Vec<InstanceAnimation>,
+
+    /// Arbitrary additional information about this clip.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl AnimationClip {
+    /// Returns the length of the clip in seconds, if it has an end time.
+    pub fn duration(&self) -> Option<f32> {
+        self.end.map(|end| end - self.start)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "annotate"]
+pub struct Annotate;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum Array {
+    Idref(IdrefArray),
+    Name(NameArray),
+    Bool(BoolArray),
+    Float(FloatArray),
+    Int(IntArray),
+}
+
+impl Array {
+    pub fn as_bool_array(&self) -> Option<&BoolArray> {
+        match *self {
+            Array::Bool(ref bool_array) => Some(bool_array),
+            _ => None,
+        }
+    }
+
+    pub fn as_float_array(&self) -> Option<&FloatArray> {
+        match *self {
+            Array::Float(ref float_array) => Some(float_array),
+            _ => None,
+        }
+    }
+
+    pub fn as_int_array(&self) -> Option<&IntArray> {
+        match *self {
+            Array::Int(ref int_array) => Some(int_array),
+            _ => None,
+        }
+    }
+
+    pub fn as_idref_array(&self) -> Option<&IdrefArray> {
+        match *self {
+            Array::Idref(ref idref_array) => Some(idref_array),
+            _ => None,
+        }
+    }
+
+    pub fn as_name_array(&self) -> Option<&NameArray> {
+        match *self {
+            Array::Name(ref name_array) => Some(name_array),
+            _ => None,
+        }
+    }
+
+    /// Returns the ID of the array, if it has one.
+    pub fn id(&self) -> Option<&str> {
+        match *self {
+            Array::Bool(ref bool_array) => bool_array.id.as_ref().map(AsRef::as_ref),
+            Array::Float(ref float_array) => float_array.id.as_ref().map(AsRef::as_ref),
+            Array::Idref(ref idref_array) => idref_array.id.as_ref().map(AsRef::as_ref),
+            Array::Int(ref int_array) => int_array.id.as_ref().map(AsRef::as_ref),
+            Array::Name(ref name_array) => name_array.id.as_ref().map(AsRef::as_ref),
+        }
+    }
+
+    /// Returns the number of values in the array, as declared by its `count` attribute.
+    pub fn count(&self) -> usize {
+        match *self {
+            Array::Bool(ref bool_array) => bool_array.count,
+            Array::Float(ref float_array) => float_array.count,
+            Array::Idref(ref idref_array) => idref_array.count,
+            Array::Int(ref int_array) => int_array.count,
+            Array::Name(ref name_array) => name_array.count,
+        }
+    }
+}
+
+/// Describes how one or more sources read from a single array.
+///
+/// Exporters will sometimes store multiple vertex attributes in a single array, interleaving the
+/// values for each attribute (e.g. `x y z nx ny nz x y z nx ny nz ...`), and then declare a
+/// separate [`Source`] for each attribute, all of which access the same array at different
+/// offsets. Extracting the data for each source separately would copy the array once per
+/// attribute, so `ArrayLayout` groups together all of the sources that view the same array in
+/// order to allow the array to be used directly.
+///
+/// `ArrayLayout`s are created with [`Mesh::array_layouts`].
+///
+/// [`Source`]: ./struct.Source.html
+/// [`Mesh::array_layouts`]: ./struct.Mesh.html#method.array_layouts
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArrayLayout<'a> {
+    /// The URI of the array being accessed.
+    pub array_uri: &'a AnyUri,
+
+    /// The array being accessed, if it's one of the arrays within the mesh.
+    pub array: Option<&'a Array>,
+
+    /// The accessors that read from the array, along with the source each one belongs to.
+    ///
+    /// Views are listed in the same order as the mesh's sources.
+    pub views: Vec<ArrayView<'a>>,
+}
+
+impl<'a> ArrayLayout<'a> {
+    /// Returns `true` if more than one source reads from the array.
+    pub fn is_shared(&self) -> bool {
+        self.views.len() > 1
+    }
+
+    /// Returns the stride of the array if it's interleaved, or `None` if it is not.
+    ///
+    /// The array is considered interleaved if it is viewed by more than one accessor and all
+    /// accessors have the same stride and count, and each accessor reads a distinct,
+    /// non-overlapping range of values within each stride. In that case, the array can be
+    /// treated as a single array of `count` elements of the returned stride, with each view
+    /// reading the values from `offset` to `offset + width` within each element.
+    pub fn interleaved_stride(&self) -> Option<usize> {
+        if !self.is_shared() {
+            return None;
+        }
+
+        let first = self.views[0].accessor;
+        let uniform = self.views.iter()
+            .all(|view| view.accessor.stride == first.stride && view.accessor.count == first.count);
+        if !uniform {
+            return None;
+        }
+
+        let mut ranges = self.views.iter()
+            .map(|view| (view.accessor.offset, view.accessor.offset + view.width()))
+            .collect::<Vec<_>>();
+        ranges.sort();
+
+        let in_bounds = ranges.iter().all(|&(_, end)| end <= first.stride);
+        let disjoint = ranges.windows(2).all(|pair| pair[0].1 <= pair[1].0);
+        if in_bounds && disjoint {
+            Some(first.stride)
+        } else {
+            None
+        }
+    }
+}
+
+/// A single source's view into an array, as part of an [`ArrayLayout`].
+///
+/// [`ArrayLayout`]: ./struct.ArrayLayout.html
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArrayView<'a> {
+    /// The source declaring the view.
+    pub source: &'a Source,
+
+    /// The accessor describing how the array is read.
+    pub accessor: &'a Accessor,
+}
+
+impl<'a> ArrayView<'a> {
+    /// Returns the number of values read by each access to the array.
+    ///
+    /// This is the number of `<param>` elements declared by the accessor, or the accessor's stride
+    /// if it doesn't declare any params.
+    pub fn width(&self) -> usize {
+        if self.accessor.params.is_empty() {
+            self.accessor.stride
+        } else {
+            self.accessor.params.len()
+        }
+    }
+}
+
+/// # COLLADA Versions
+///
+/// `coverage` and `extras` were added in COLLADA version `1.5.0`. They're only parsed from
+/// `1.4.x` documents with [`Strictness::Permissive`], and can't be written as `1.4.x`.
+///
+/// [`Strictness::Permissive`]: ../options/enum.Strictness.html#variant.Permissive
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "asset"]
+pub struct Asset {
+    #[child]
+    pub contributors: Vec<Contributor>,
+
+    /// The location of the visual scene in physical space.
+    #[child]
+    #[collada(version = "1.5")]
+    pub coverage: Option<::v1_5::Coverage>,
+
+    #[child]
+    pub created: DateTime,
+
+    #[child]
+    pub keywords: MaybeEmpty<String>,
+
+    #[child]
+    pub modified: DateTime,
+
+    #[child]
+    pub revision: MaybeEmpty<String>,
+
+    #[child]
+    pub subject: MaybeEmpty<String>,
+
+    #[child]
+    pub title: MaybeEmpty<String>,
+
+    /// The unit of distance used by the asset's parent element and the elements within it.
+    ///
+    /// `None` if the asset doesn't override the unit of the element containing its parent. Use
+    /// [`unit`](#method.unit) to get the unit with the default applied.
+    #[child]
+    pub unit: Option<Unit>,
+
+    /// The up axis used by the asset's parent element and the elements within it.
+    ///
+    /// `None` if the asset doesn't override the up axis of the element containing its parent.
+    /// Use [`up_axis`](#method.up_axis) to get the up axis with the default applied.
+    #[child]
+    #[text_type]
+    pub up_axis: Option<UpAxis>,
+
+    /// Arbitrary additional information about the asset.
+    #[child]
+    #[collada(version = "1.5")]
+    pub extras: Vec<Extra>,
+}
+
+impl Asset {
+    /// Returns the unit of distance declared by the asset, or one meter if it doesn't declare
+    /// one.
+    pub fn unit(&self) -> Unit {
+        self.unit.clone().unwrap_or_default()
+    }
+
+    /// Returns the up axis declared by the asset, or the Y axis if it doesn't declare one.
+    pub fn up_axis(&self) -> UpAxis {
+        self.up_axis.unwrap_or_default()
+    }
+
+    /// Returns the coordinate system of the asset's parent element, given the coordinate system
+    /// of the element containing it.
+    pub fn coordinate_system(&self, enclosing: &CoordinateSystem) -> CoordinateSystem {
+        enclosing.with_overrides(self.unit.as_ref(), self.up_axis)
+    }
+
+    /// Returns an iterator over the individual keywords in `keywords`.
+    ///
+    /// Keywords are separated by whitespace.
+    pub fn keywords<'a>(&'a self) -> Box<Iterator<Item = &'a str> + 'a> {
+        match self.keywords.value() {
+            Some(keywords) => Box::new(keywords.split_whitespace()),
+            None => Box::new(::std::iter::empty()),
+        }
+    }
+}
+
+targetable_float! {
+    /// The aspect ratio of a camera's field of view, i.e. its width divided by its height.
+    pub struct AspectRatio, "aspect_ratio"
+}
+
+/// The frame of the second rigid body connected by a [`RigidConstraint`], which is constrained
+/// relative to the frame of its `ref_attachment`.
+///
+/// [`RigidConstraint`]: ./struct.RigidConstraint.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "attachment"]
+pub struct Attachment {
+    /// The location of the rigid body, or of a node, that the frame is relative to.
+    #[attribute]
+    pub rigid_body: AnyUri,
+
+    /// The transforms positioning the frame, in the order they appear in the document.
+    #[child]
+    pub transforms: Vec<RigidTransform>,
+
+    /// Arbitrary additional information about this attachment and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Binds a parameter of an instantiated material to a value in the scene.
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+#[name = "bind"]
+pub struct Bind {
+    /// The semantic of the parameter being bound.
+    #[attribute]
+    pub semantic: String,
+
+    /// The location of the value the parameter is bound to.
+    #[attribute]
+    pub target: String,
+}
+
+/// Binds the material symbols used by an instantiated geometry or controller to concrete
+/// materials.
+///
+/// The primitives of a [`Mesh`] name their material with a symbol, e.g. the `material`
+/// attribute of a [`Polylist`]. Each instance of the mesh then decides which [`Material`] that
+/// symbol refers to with an [`InstanceMaterial`], so the same mesh can be placed in the scene
+/// more than once with different materials.
+///
+/// [`Mesh`]: ./struct.Mesh.html
+/// [`Polylist`]: ./struct.Polylist.html
+/// [`Material`]: ./struct.Material.html
+/// [`InstanceMaterial`]: ./struct.InstanceMaterial.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "bind_material"]
+pub struct BindMaterial {
+    /// Parameters that can be targeted by animations to change the bound materials.
+    #[child]
+    pub params: Vec<Param>,
+
+    /// The material bindings, which every application is expected to support.
+    #[child]
+    pub technique_common: BindMaterialTechniqueCommon,
+
+    /// Application-specific material bindings.
+    #[child]
+    pub techniques: Vec<Technique>,
+
+    /// Arbitrary additional information about the bindings.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl BindMaterial {
+    /// Returns the material instance bound to `symbol`, if there is one.
+    pub fn instance_material(&self, symbol: &str) -> Option<&InstanceMaterial> {
+        self.technique_common.instance_materials.iter().find(|instance| instance.symbol == symbol)
+    }
+}
+
+/// The common technique of a [`BindMaterial`](./struct.BindMaterial.html), containing the
+/// material bindings.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "technique_common"]
+pub struct BindMaterialTechniqueCommon {
+    /// The materials bound to each symbol.
+    #[child]
+    #[required]
+    pub instance_materials: Vec<InstanceMaterial>,
+}
+
+/// The transform applied to a skinned mesh before it's bound to its joints.
+///
+/// Contains the 16 values of a 4x4 matrix in row-major order.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "bind_shape_matrix"]
+pub struct BindShapeMatrix {
+    #[text]
+    data: Vec<f32>,
+}
+
+impl ::std::ops::Deref for BindShapeMatrix {
+    type Target = [f32];
+
+    fn deref(&self) -> &[f32] { &*self.data }
+}
+
+/// Binds a vertex input of a mesh to an effect parameter, e.g. to pick which texture coordinate
+/// set a texture is sampled with.
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+#[name = "bind_vertex_input"]
+pub struct BindVertexInput {
+    /// The semantic of the effect parameter, e.g. the `texcoord` of a
+    /// [`Texture`](./struct.Texture.html).
+    #[attribute]
+    pub semantic: String,
+
+    /// The semantic of the mesh input being bound, e.g. `Semantic::TexCoord(None)`.
+    #[attribute]
+    pub input_semantic: Semantic,
+
+    /// The set of the mesh input being bound.
+    #[attribute]
+    pub input_set: Option<usize>,
+}
+
+/// How a surface is combined with whatever has already been drawn behind it.
+///
+/// This is computed from the `transparent` and `transparency` inputs of a shading model by
+/// [`ShadingModel::blend`]. Each opacity is the weight given to the surface's color, with the
+/// color behind the surface weighted by `1.0 - opacity`.
+///
+/// [`ShadingModel::blend`]: ./enum.ShadingModel.html#method.blend
+#[derive(Debug, Clone, PartialEq)]
+pub enum Blend<'a> {
+    /// The surface is fully opaque and doesn't need to be blended.
+    Opaque,
+
+    /// The surface is blended using a constant opacity for each of the red, green, and blue
+    /// channels.
+    Constant([f32; 3]),
+
+    /// The opacity varies across the surface and is sampled from a texture.
+    ///
+    /// The opacity of each sample can be computed with [`Opaque::opacity`].
+    ///
+    /// [`Opaque::opacity`]: ./enum.Opaque.html#method.opacity
+    Texture {
+        /// The texture containing the transparency.
+        texture: &'a Texture,
+
+        /// How the transparency is encoded in the texture.
+        opaque: Opaque,
+
+        /// The scalar transparency that each sample is multiplied by.
+        transparency: f32,
+    },
+}
+
+impl<'a> Blend<'a> {
+    /// Returns `true` if the surface doesn't need to be blended.
+    pub fn is_opaque(&self) -> bool {
+        *self == Blend::Opaque
+    }
+
+    /// Returns a single alpha value for the surface, where `1.0` is opaque.
+    ///
+    /// Per-channel opacities are reduced to their luminance. Returns `None` if the opacity
+    /// comes from a texture.
+    pub fn alpha(&self) -> Option<f32> {
+        match *self {
+            Blend::Opaque => Some(1.0),
+            Blend::Constant(opacity) => Some(luminance(opacity)),
+            Blend::Texture { .. } => None,
+        }
+    }
+}
+
+/// Produces a shaded surface using the Blinn-Torrance-Sparrow lighting model.
+///
+/// Has the same inputs as [`Phong`](./struct.Phong.html), but `shininess` is used as the
+/// exponent of the half-angle term rather than of the reflection term.
+#[derive(Debug, Clone, Default, PartialEq, ColladaElement)]
+#[name = "blinn"]
+pub struct Blinn {
+    /// The amount of light emitted from the surface.
+    #[child(flatten = "emission")]
+    pub emission: Option<ColorOrTexture>,
+
+    /// The amount of ambient light reflected from the surface.
+    #[child(flatten = "ambient")]
+    pub ambient: Option<ColorOrTexture>,
+
+    /// The amount of light diffusely reflected from the surface.
+    #[child(flatten = "diffuse")]
+    pub diffuse: Option<ColorOrTexture>,
+
+    /// The color of light reflected specularly from the surface.
+    #[child(flatten = "specular")]
+    pub specular: Option<ColorOrTexture>,
+
+    /// The sharpness of the specular highlight.
+    #[child(flatten = "shininess")]
+    pub shininess: Option<FloatOrParam>,
+
+    /// The color of a perfect mirror reflection.
+    #[child(flatten = "reflective")]
+    pub reflective: Option<ColorOrTexture>,
+
+    /// The amount of perfect mirror reflection to be added to the reflected light.
+    #[child(flatten = "reflectivity")]
+    pub reflectivity: Option<FloatOrParam>,
+
+    /// The color of perfectly refracted light.
+    #[child]
+    pub transparent: Option<Transparent>,
+
+    /// The amount of perfectly refracted light added to the reflected color.
+    #[child(flatten = "transparency")]
+    pub transparency: Option<FloatOrParam>,
+
+    /// The index of refraction for perfectly refracted light.
+    #[child(flatten = "index_of_refraction")]
+    pub index_of_refraction: Option<FloatOrParam>,
+}
+
+/// A parameter value of a single boolean.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "bool"]
+pub struct Bool {
+    #[text]
+    pub value: bool,
+}
+
+/// A parameter value of two booleans.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "bool2"]
+pub struct Bool2 {
+    #[text]
+    pub values: Vec<bool>,
+}
+
+/// A parameter value of three booleans.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "bool3"]
+pub struct Bool3 {
+    #[text]
+    pub values: Vec<bool>,
+}
+
+/// A parameter value of four booleans.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "bool4"]
+pub struct Bool4 {
+    #[text]
+    pub values: Vec<bool>,
+}
+
+/// An array of boolean values, e.g. a `<source>` of joint or visibility flags.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "bool_array"]
+pub struct BoolArray {
+    #[attribute]
+    pub count: usize,
+
+    #[attribute]
+    pub id: Option<String>,
+
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The values in the array.
+    ///
+    /// Values may be written as either `true`/`false` or `1`/`0`. Decoding the values can be
+    /// deferred until they're first accessed, see [`RawArray`] for details.
+    ///
+    /// [`RawArray`]: ../common/struct.RawArray.html
+    #[text(count = "count")]
+    pub data: RawArray<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "border_color"]
+pub struct BorderColor;
+
+/// An axis-aligned box shape, centered on the origin.
+///
+/// Named `BoxShape` rather than `Box` to avoid clashing with the standard library.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "box"]
+pub struct BoxShape {
+    /// Half of the width, height, and depth of the box.
+    #[child]
+    pub half_extents: HalfExtents,
+
+    /// Arbitrary additional information about this shape and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Declares a view into the scene, which can be instantiated by a node to place it in the scene.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "camera"]
+pub struct Camera {
+    /// A unique identifier for the camera.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this camera.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about the camera and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The projection of the camera.
+    #[child]
+    pub optics: Optics,
+
+    #[child]
+    pub imager: Option<Imager>,
+
+    /// Arbitrary additional information about this camera and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// A capsule shape, i.e. a cylinder with hemispherical ends, centered on the origin and aligned
+/// with the local y axis.
+impl Camera {
+    /// Returns the value within the camera with the given sid, e.g. its `xfov`, if there is one.
+    pub fn find_sid<'a>(&'a self, sid: &str) -> Option<SidTarget<'a>> {
+        let values = match self.optics.projection {
+            Projection::Orthographic(ref orthographic) => vec![
+                orthographic.xmag.as_ref().map(|xmag| (&xmag.sid, &xmag.value)),
+                orthographic.ymag.as_ref().map(|ymag| (&ymag.sid, &ymag.value)),
+                orthographic.aspect_ratio.as_ref().map(|aspect_ratio| (&aspect_ratio.sid, &aspect_ratio.value)),
+                Some((&orthographic.znear.sid, &orthographic.znear.value)),
+                Some((&orthographic.zfar.sid, &orthographic.zfar.value)),
+            ],
+            Projection::Perspective(ref perspective) => vec![
+                perspective.xfov.as_ref().map(|xfov| (&xfov.sid, &xfov.value)),
+                perspective.yfov.as_ref().map(|yfov| (&yfov.sid, &yfov.value)),
+                perspective.aspect_ratio.as_ref().map(|aspect_ratio| (&aspect_ratio.sid, &aspect_ratio.value)),
+                Some((&perspective.znear.sid, &perspective.znear.value)),
+                Some((&perspective.zfar.sid, &perspective.zfar.value)),
+            ],
+        };
+
+        values.into_iter()
+            .flatten()
+            .find(|(value_sid, _)| value_sid.as_deref() == Some(sid))
+            .map(|(_, value)| SidTarget::Float(value))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "capsule"]
+pub struct Capsule {
+    /// The height of the capsule along its local y axis.
+    #[child]
+    pub height: Height,
+
+    /// The radii of the capsule along its local x and z axes.
+    #[child]
+    pub radius: Radius,
+
+    /// Arbitrary additional information about this shape and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Applies the output of an animation's [`Sampler`] to a value in the document.
+///
+/// [`Sampler`]: ./struct.Sampler.html
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+#[name = "channel"]
+pub struct Channel {
+    /// The sampler that provides the animated values.
+    #[attribute]
+    pub source: UriFragment,
+
+    /// The value being animated, as a path of ids and sids (e.g. `"Cube/location.X"`).
+    ///
+    /// Use [`target_ref`](#method.target_ref) to parse the path, or
+    /// [`Collada::resolve_channel`](./struct.Collada.html#method.resolve_channel) to find the
+    /// value it targets.
+    #[attribute]
+    pub target: String,
+}
+
+impl Channel {
+    /// Parses the path of the value being animated.
+    pub fn target_ref(&self) -> ::std::result::Result<SidRef, SidRefParseError> {
+        self.target.parse()
+    }
+}
+
+/// A block of shader source code embedded in a [`ProfileGlsl`] or [`ProfileCg`].
+///
+/// [`ProfileGlsl`]: ./struct.ProfileGlsl.html
+/// [`ProfileCg`]: ./struct.ProfileCg.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "code"]
+pub struct Code {
+    /// An identifier for the code that's unique within its parent element, which shaders use to
+    /// refer to it.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The source code.
+    #[text]
+    pub source: String,
+}
+
+/// A literal color value in an effect, e.g. the diffuse color of a [`Phong`] surface.
+///
+/// [`Phong`]: ./struct.Phong.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "color"]
+pub struct Color {
+    /// An identifier for the color, so that it can be targeted by animations.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The components of the color, which should be red, green, blue, and alpha in that order.
+    #[text]
+    pub values: Vec<f32>,
+}
+
+impl Color {
+    /// Returns the color as red, green, blue, and alpha components.
+    ///
+    /// The alpha defaults to `1.0` if the color only has three components. Returns `None` if the
+    /// color has any other number of components.
+    pub fn rgba(&self) -> Option<[f32; 4]> {
+        match *self.values {
+            [r, g, b] => Some([r, g, b, 1.0]),
+            [r, g, b, a] => Some([r, g, b, a]),
+            _ => None,
+        }
+    }
+}
+
+/// The value of a color input to a shading model, such as `<diffuse>` or `<emission>`.
+///
+/// The value is either given directly, taken from a parameter of the effect, or sampled from a
+/// texture.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum ColorOrTexture {
+    Color(Color),
+    Param(ParamRef),
+    Texture(Texture),
+}
+
+impl ColorOrTexture {
+    /// Returns the inner [`Color`](./struct.Color.html) if the value is given directly.
+    pub fn as_color(&self) -> Option<&Color> {
+        match *self {
+            ColorOrTexture::Color(ref color) => Some(color),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner [`Texture`](./struct.Texture.html) if the value is sampled from a
+    /// texture.
+    pub fn as_texture(&self) -> Option<&Texture> {
+        match *self {
+            ColorOrTexture::Texture(ref texture) => Some(texture),
+            _ => None,
+        }
+    }
+}
+
+/// Connects a parameter of a Cg shader to another parameter, so that setting one sets the
+/// other.
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+#[name = "connect_param"]
+pub struct ConnectParam {
+    /// The sid of the parameter to connect to.
+    #[attribute]
+    #[name = "ref"]
+    pub reference: String,
+}
+
+/// Produces a constantly shaded surface that is independent of lighting.
+///
+/// The resulting color is the sum of the emissive color and any reflected or refracted light.
+#[derive(Debug, Clone, Default, PartialEq, ColladaElement)]
+#[name = "constant"]
+pub struct Constant {
+    /// The amount of light emitted from the surface.
+    #[child(flatten = "emission")]
+    pub emission: Option<ColorOrTexture>,
+
+    /// The color of a perfect mirror reflection.
+    #[child(flatten = "reflective")]
+    pub reflective: Option<ColorOrTexture>,
+
+    /// The amount of perfect mirror reflection to be added to the reflected light.
+    #[child(flatten = "reflectivity")]
+    pub reflectivity: Option<FloatOrParam>,
+
+    /// The color of perfectly refracted light.
+    #[child]
+    pub transparent: Option<Transparent>,
+
+    /// The amount of perfectly refracted light added to the reflected color.
+    #[child(flatten = "transparency")]
+    pub transparency: Option<FloatOrParam>,
+
+    /// The index of refraction for perfectly refracted light.
+    #[child(flatten = "index_of_refraction")]
+    pub index_of_refraction: Option<FloatOrParam>,
+}
+
+targetable_float! {
+    /// The constant factor in the attenuation of a [`Point`](./struct.Point.html) or
+    /// [`Spot`](./struct.Spot.html) light.
+    pub struct ConstantAttenuation, "constant_attenuation"
+}
+
+/// # COLLADA Versions
+///
+/// `author_email` and `author_website` were added in COLLADA version `1.5.0`. They're only parsed
+/// from `1.4.x` documents with [`Strictness::Permissive`], and can't be written as `1.4.x`.
+///
+/// [`Strictness::Permissive`]: ../options/enum.Strictness.html#variant.Permissive
+#[derive(Debug, Clone, Default, PartialEq, Eq, ColladaElement)]
+#[name = "contributor"]
+pub struct Contributor {
+    #[child]
+    pub author: MaybeEmpty<String>,
+
+    #[child]
+    #[collada(version = "1.5")]
+    pub author_email: MaybeEmpty<String>,
+
+    #[child]
+    #[collada(version = "1.5")]
+    pub author_website: Option<AnyUri>,
+
+    #[child]
+    pub authoring_tool: MaybeEmpty<String>,
+
+    #[child]
+    pub comments: MaybeEmpty<String>,
+
+    #[child]
+    pub copyright: MaybeEmpty<String>,
+
+    #[child]
+    pub source_data: Option<AnyUri>,
+}
+
+/// Declares a control element, which modifies the data of a mesh, e.g. by deforming it with a
+/// skeleton.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "controller"]
+pub struct Controller {
+    /// A unique identifier for the controller.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this controller.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about the controller and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The skin or morph that the controller applies.
+    #[child]
+    pub control_element: ControlElement,
+
+    /// Arbitrary additional information about this controller and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// A control element of unknown type.
+///
+/// Each variant wraps a single value containing a given type of control data. See the
+/// documentation for each of the possible control types for more information.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum ControlElement {
+    Morph(Morph),
+    Skin(Skin),
+}
+
+impl ControlElement {
+    /// Returns the inner [`Skin`](./struct.Skin.html) if this is a skin controller.
+    pub fn as_skin(&self) -> Option<&Skin> {
+        match *self {
+            ControlElement::Skin(ref skin) => Some(skin),
+            _ => None,
+        }
+    }
+}
+
+/// Describes a convex mesh, usually the collision hull of a rigid body.
+///
+/// A convex mesh is either described in full, in the same way as a [`Mesh`], or as the convex
+/// hull of another geometry in the document named by `convex_hull_of`. In the latter case the
+/// hull has to be computed by the application, and `sources`, `vertices`, and `primitives` will
+/// usually be empty. Use [`Collada::convex_hull_of`] to find the geometry being referenced.
+///
+/// [`Mesh`]: ./struct.Mesh.html
+/// [`Collada::convex_hull_of`]: ./struct.Collada.html#method.convex_hull_of
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "convex_mesh"]
+pub struct ConvexMesh {
+    /// The location of the geometry that this mesh is the convex hull of.
+    #[attribute]
+    pub convex_hull_of: Option<AnyUri>,
+
+    /// The [`Source`] instances containing the raw mesh data.
+    ///
+    /// Will be empty if the mesh is given by `convex_hull_of`.
+    ///
+    /// [`Source`]: ./struct.Source.html
+    #[child]
+    pub sources: Vec<Source>,
+
+    /// Describes the mesh's vertex attributes.
+    ///
+    /// Will only be `None` if the mesh is given by `convex_hull_of`.
+    #[child]
+    pub vertices: Option<Vertices>,
+
+    /// Geometric primitives that assemble values from the inputs into vertex attribute data.
+    #[child]
+    pub primitives: Vec<Primitive>,
+
+    /// Arbitrary additional information about this convex mesh and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl ConvexMesh {
+    /// Returns the source which matches `id`, or `None` if no sources match.
+    pub fn find_source<'a>(&'a self, id: &str) -> Option<&'a Source> {
+        self.sources.iter().find(|source| source.id == id)
+    }
+
+    /// Returns an iterator over the primitives in the mesh.
+    pub fn primitives<'a>(&'a self) -> ::std::slice::Iter<'a, Primitive> {
+        self.primitives.iter()
+    }
+}
+
+/// A cylinder shape, centered on the origin and aligned with the local y axis.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "cylinder"]
+pub struct Cylinder {
+    /// The height of the cylinder along its local y axis.
+    #[child]
+    pub height: Height,
+
+    /// The radii of the cylinder along its local x and z axes.
+    #[child]
+    pub radius: Radius,
+
+    /// Arbitrary additional information about this shape and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+targetable_float! {
+    /// The damping of a spring, which slows it down over time.
+    pub struct Damping, "damping"
+}
+
+targetable_float! {
+    /// The density of a shape, used to work out its mass from its volume.
+    pub struct Density, "density"
+}
+
+/// A light that shines in a single direction from infinitely far away, e.g. sunlight.
+///
+/// The light shines down the local negative z axis of the node that instantiates it.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "directional"]
+pub struct Directional {
+    /// The color of the light.
+    #[child]
+    pub color: Color,
+}
+
+targetable_bool! {
+    /// Whether a rigid body is moved by the simulation, rather than being static or animated.
+    pub struct Dynamic, "dynamic"
+}
+
+targetable_float! {
+    /// The friction of a [`PhysicsMaterial`](./struct.PhysicsMaterial.html) while it slides
+    /// against another surface.
+    pub struct DynamicFriction, "dynamic_friction"
+}
+
+/// Describes how a surface is rendered, e.g. its shading model and the colors and textures used
+/// as inputs to it.
+///
+/// An effect may provide several profiles describing the same look for different platforms:
+/// [`ProfileCommon`] uses fixed-function shading models that every application should support,
+/// while [`ProfileGlsl`] and [`ProfileCg`] provide programmable shaders.
+///
+/// [`ProfileCommon`]: ./struct.ProfileCommon.html
+/// [`ProfileGlsl`]: ./struct.ProfileGlsl.html
+/// [`ProfileCg`]: ./struct.ProfileCg.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "effect"]
+pub struct Effect {
+    /// A unique identifier for the effect.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: String,
+
+    /// The human-friendly name for this effect.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about the effect and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    #[child]
+    pub annotations: Vec<Annotate>,
+
+    #[child]
+    pub images: Vec<Image>,
+
+    #[child]
+    pub new_params: Vec<NewParam>,
+
+    /// The platform-specific descriptions of the effect.
+    ///
+    /// There will always be at least one profile in an `Effect`.
+    #[child]
+    #[required]
+    pub profiles: Vec<Profile>,
+
+    /// Arbitrary additional information about this effect and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Effect {
+    /// Returns the effect's [`ProfileCommon`](./struct.ProfileCommon.html), if it has one.
+    pub fn profile_common(&self) -> Option<&ProfileCommon> {
+        self.profiles.iter().filter_map(Profile::as_common).next()
+    }
+
+    /// Returns the effect's [`ProfileGlsl`](./struct.ProfileGlsl.html), if it has one.
+    pub fn profile_glsl(&self) -> Option<&ProfileGlsl> {
+        self.profiles.iter().filter_map(Profile::as_glsl).next()
+    }
+
+    /// Returns the effect's [`ProfileCg`](./struct.ProfileCg.html), if it has one.
+    pub fn profile_cg(&self) -> Option<&ProfileCg> {
+        self.profiles.iter().filter_map(Profile::as_cg).next()
+    }
+
+    /// Returns the shading model of the effect's
+    /// [`ProfileCommon`](./struct.ProfileCommon.html), if it has one.
+    pub fn common_shading(&self) -> Option<&ShadingModel> {
+        self.profile_common().map(|profile| &profile.technique.shading)
+    }
+
+    /// Returns the color or float with the given sid in the effect's common shading model (e.g.
+    /// `<color sid="emission">`), if there is one.
+    pub fn find_sid<'a>(&'a self, sid: &str) -> Option<SidTarget<'a>> {
+        let shading = self.common_shading()?;
+        let colors = vec![
+            shading.emission(),
+            shading.ambient(),
+            shading.diffuse(),
+            shading.specular(),
+            shading.reflective(),
+            shading.transparent().map(|transparent| &transparent.value),
+        ];
+        let color = colors.into_iter()
+            .flatten()
+            .filter_map(ColorOrTexture::as_color)
+            .find(|color| color.sid.as_deref() == Some(sid));
+        if let Some(color) = color {
+            return Some(SidTarget::Color(color));
+        }
+
+        let floats = vec![
+            shading.shininess(),
+            shading.reflectivity(),
+            shading.transparency(),
+            shading.index_of_refraction(),
+        ];
+        floats.into_iter()
+            .flatten()
+            .filter_map(|float| match *float {
+                FloatOrParam::Float(ref float) => Some(float),
+                _ => None,
+            })
+            .find(|float| float.sid.as_deref() == Some(sid))
+            .map(|float| SidTarget::Float(&float.value))
+    }
+
+    /// Returns the parameter with the given sid, if the effect declares one.
+    ///
+    /// Parameters declared in the effect's [`ProfileCommon`](./struct.ProfileCommon.html) take
+    /// precedence over those declared in the effect itself.
+    pub fn new_param(&self, sid: &str) -> Option<&NewParam> {
+        self.profile_common()
+            .into_iter()
+            .flat_map(|profile| profile.new_params.iter())
+            .chain(&self.new_params)
+            .find(|param| param.sid == sid)
+    }
+
+    /// Returns the image with the given id, if it's declared within the effect.
+    pub fn image(&self, id: &str) -> Option<&Image> {
+        let profile_images = self.profile_common()
+            .into_iter()
+            .flat_map(|profile| profile.technique.images.iter().chain(&profile.images));
+        profile_images
+            .chain(&self.images)
+            .find(|image| image.id.as_deref() == Some(id))
+    }
+
+    /// Returns the sampler that `texture` samples through, if it's a parameter of the effect.
+    pub fn texture_sampler(&self, texture: &Texture) -> Option<&Sampler2D> {
+        self.new_param(&texture.texture).and_then(|param| param.value.as_sampler_2d())
+    }
+
+    /// Returns the surface that `texture` samples, following its sampler parameter to the surface
+    /// parameter that the sampler reads from.
+    pub fn texture_surface(&self, texture: &Texture) -> Option<&Surface> {
+        let sampler = self.texture_sampler(texture)?;
+        self.new_param(&sampler.source).and_then(|param| param.value.as_surface())
+    }
+}
+
+targetable_bool! {
+    /// Whether a rigid constraint is enabled.
+    pub struct Enabled, "enabled"
+}
+
+/// A parameter value naming an enumerated state, e.g. `<enum>LINEAR_MIPMAP_LINEAR</enum>`.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "enum"]
+pub struct Enum {
+    #[text]
+    pub value: String,
+}
+
+targetable_vector! {
+    /// The coefficients `a`, `b`, `c`, and `d` of the plane equation `ax + by + cz + d = 0` of a
+    /// [`Plane`](./struct.Plane.html).
+    pub struct Equation, "equation"
+}
+
+/// Describes how to render a [`VisualScene`], as a series of rendering passes.
+///
+/// Each [`Render`] is one pass, rendered in order. A visual scene without an `EvaluateScene` is
+/// rendered in a single pass from an application-defined camera.
+///
+/// [`VisualScene`]: ./struct.VisualScene.html
+/// [`Render`]: ./struct.Render.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "evaluate_scene"]
+pub struct EvaluateScene {
+    /// The human-friendly name for this evaluation.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The rendering passes, in the order they should be rendered. There will always be at least
+    /// one render.
+    #[child]
+    #[required]
+    pub renders: Vec<Render>,
+}
+
+/// Provides arbitrary additional information about an element.
+///
+/// COLLADA allows for applications to provide extra information about any given piece of data,
+/// including application-specific information that's not part of the COLLADA specification. This
+/// data can be any syntactically valid XML data, and is not parsed as part of this library, save
+/// for a few specific 3rd party applications that are directly supported.
+///
+/// # Choosing a Technique
+///
+/// There may be more than one [`Technique`][Technique] provided in `techniques`, but generally
+/// only one is used by the consuming application. The application should pick a technique
+/// with a supported profile. If there are multiple techniques with supported profiles the
+/// application is free to pick whichever technique is preferred.
+///
+/// [Technique]: struct.Technique.html
+#[derive(Debug, Clone, Default, PartialEq, ColladaElement)]
+#[name = "extra"]
+pub struct Extra {
+    /// The identifier of the element, if present. Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// A hint as to the type of information this element represents, if present. Must be
+    /// must be understood by the consuming application.
+    #[attribute]
+    #[name = "type"]
+    pub type_hint: Option<String>,
+
+    /// Asset-management information for this element, if present.
+    ///
+    /// While this is technically allowed in all `<extra>` elements, it is likely only present in
+    /// elements that describe a new "asset" of some kind, rather than in `<extra>` elements that
+    /// provide application-specific information about an existing one.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The arbitrary additional information, containing unprocessed XML events. There will always
+    /// be at least one item in `techniques`.
+    #[child]
+    #[required]
+    pub techniques: Vec<Technique>,
+}
+
+impl Extra {
+    /// Returns the technique with the specified profile, if present.
+    ///
+    /// Profile names are case-sensitive, e.g. `"blender"` or `"FCOLLADA"`.
+    pub fn technique(&self, profile: &str) -> Option<&Technique> {
+        self.techniques.iter().find(|technique| technique.profile == profile)
+    }
+
+    /// Searches a list of `<extra>` elements for the first technique with the specified profile.
+    ///
+    /// Every element that supports 3rd party extensions has an `extras` field, so this can be
+    /// used to look up application-specific data on any of them.
+    pub fn find_technique<'a>(extras: &'a [Extra], profile: &str) -> Option<&'a Technique> {
+        extras.iter().filter_map(|extra| extra.technique(profile)).next()
+    }
+
+    /// Decodes the technique for a well-known vendor profile, if present.
+    ///
+    /// See the [`profiles`](../profiles/index.html) module for the supported profiles.
+    pub fn decode<P: ExtraProfile>(&self) -> Option<P> {
+        self.technique(P::profile()).map(|technique| P::decode(&technique.elements()))
+    }
+}
+
+targetable_float! {
+    /// The angle in degrees at which the cone of a [`Spot`](./struct.Spot.html) light falls off.
+    pub struct FalloffAngle, "falloff_angle"
+}
+
+targetable_float! {
+    /// How quickly the light of a [`Spot`](./struct.Spot.html) light falls off towards the edge
+    /// of its cone.
+    pub struct FalloffExponent, "falloff_exponent"
+}
+
+/// How a [`Sampler2D`] filters a texture when it's minified, magnified, or between mipmap
+/// levels.
+///
+/// [`Sampler2D`]: ./struct.Sampler2D.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FilterMode {
+    None,
+    Nearest,
+    Linear,
+    NearestMipmapNearest,
+    LinearMipmapNearest,
+    NearestMipmapLinear,
+    LinearMipmapLinear,
+}
+
+impl TextValue for FilterMode {
+    fn parse_text(text: &str) -> ::std::result::Result<FilterMode, ErrorKind> {
+        match text {
+            "NONE" => Ok(FilterMode::None),
+            "NEAREST" => Ok(FilterMode::Nearest),
+            "LINEAR" => Ok(FilterMode::Linear),
+            "NEAREST_MIPMAP_NEAREST" => Ok(FilterMode::NearestMipmapNearest),
+            "LINEAR_MIPMAP_NEAREST" => Ok(FilterMode::LinearMipmapNearest),
+            "NEAREST_MIPMAP_LINEAR" => Ok(FilterMode::NearestMipmapLinear),
+            "LINEAR_MIPMAP_LINEAR" => Ok(FilterMode::LinearMipmapLinear),
+            _ => Err(ErrorKind::InvalidValue {
+                element: "sampler2D",
+                value: text.into(),
+            }),
+        }
+    }
+}
+
+impl WriteValue for FilterMode {
+    fn write_value(&self, output: &mut String, _: &WriteOptions) {
+        output.push_str(match *self {
+            FilterMode::None => "NONE",
+            FilterMode::Nearest => "NEAREST",
+            FilterMode::Linear => "LINEAR",
+            FilterMode::NearestMipmapNearest => "NEAREST_MIPMAP_NEAREST",
+            FilterMode::LinearMipmapNearest => "LINEAR_MIPMAP_NEAREST",
+            FilterMode::NearestMipmapLinear => "NEAREST_MIPMAP_LINEAR",
+            FilterMode::LinearMipmapLinear => "LINEAR_MIPMAP_LINEAR",
+        });
+    }
+}
+
+impl Default for FilterMode {
+    fn default() -> FilterMode { FilterMode::None }
+}
+
+/// A literal scalar value in an effect, e.g. the shininess of a [`Phong`] surface.
+///
+/// [`Phong`]: ./struct.Phong.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "float"]
+pub struct Float {
+    /// An identifier for the value, so that it can be targeted by animations.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub value: f32,
+}
+
+/// A parameter value of two floats.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "float2"]
+pub struct Float2 {
+    #[text]
+    pub values: Vec<f32>,
+}
+
+/// A parameter value of three floats.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "float3"]
+pub struct Float3 {
+    #[text]
+    pub values: Vec<f32>,
+}
+
+/// A parameter value of four floats, e.g. a color.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "float4"]
+pub struct Float4 {
+    #[text]
+    pub values: Vec<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "float_array"]
+pub struct FloatArray {
+    #[attribute]
+    pub count: usize,
+
+    #[attribute]
+    pub id: Option<String>,
+
+    #[attribute]
+    pub name: Option<String>,
+
+    #[attribute]
+    #[optional_with_default = "6"]
+    pub digits: usize,
+
+    #[attribute]
+    #[optional_with_default = "38"]
+    pub magnitude: usize,
+
+    /// The values in the array.
+    ///
+    /// Decoding the values can be deferred until they're first accessed, see [`RawArray`] for
+    /// details.
+    ///
+    /// [`RawArray`]: ../common/struct.RawArray.html
+    #[text(count = "count")]
+    pub data: RawArray<f32>,
+}
+
+/// A parameter value holding a matrix of floats, e.g. `<float4x4>`.
+///
+/// Any size from `float1x1` to `float4x4` is supported. The values are kept in row-major order,
+/// the order they're written in the document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloatMatrix {
+    /// The number of rows in the matrix.
+    pub rows: usize,
+
+    /// The number of columns in the matrix.
+    pub columns: usize,
+
+    /// The values of the matrix in row-major order.
+    pub values: Vec<f32>,
+}
+
+impl FloatMatrix {
+    const NAMES: &'static [&'static str] = &[
+        "float1x1", "float1x2", "float1x3", "float1x4", "float2x1", "float2x2", "float2x3",
+        "float2x4", "float3x1", "float3x2", "float3x3", "float3x4", "float4x1", "float4x2",
+        "float4x3", "float4x4",
+    ];
+
+    /// Returns the name of the matrix type, e.g. `"float4x4"`.
+    pub fn type_name(&self) -> &'static str {
+        FloatMatrix::NAMES[(self.rows - 1) * 4 + self.columns - 1]
+    }
+
+    /// Returns the value at the given row and column, or `None` if it's outside of the matrix.
+    pub fn get(&self, row: usize, column: usize) -> Option<f32> {
+        if row >= self.rows || column >= self.columns {
+            return None;
+        }
+
+        self.values.get(row * self.columns + column).cloned()
+    }
+}
+
+impl ColladaElement for FloatMatrix {
+    fn name_test(name: &str) -> bool {
+        FloatMatrix::NAMES.contains(&name)
+    }
+
+    fn parse_element<R>(
+        reader: &mut DocumentReader<R>,
+        element_start: ElementStart,
+    ) -> Result<FloatMatrix>
+    where
+        R: XmlPull,
+    {
+        let index = FloatMatrix::NAMES.iter()
+            .position(|&name| name == element_start.name.local_name)
+            .expect("Float matrix parsed from an element with an unexpected name");
+        let name = FloatMatrix::NAMES[index];
+        let rows = index / 4 + 1;
+        let columns = index % 4 + 1;
+
+        utils::verify_attributes(reader, name, element_start.attributes)?;
+        let text: String = utils::required_text_contents(reader, name)?;
+        let values = utils::float_list(reader, &text)?;
+        utils::check_list_len::<_, f32>(reader, name, values.len(), Some(rows * columns))?;
+
+        Ok(FloatMatrix { rows, columns, values })
+    }
+
+    fn add_names(names: &mut Vec<&'static str>) {
+        names.extend_from_slice(FloatMatrix::NAMES);
+    }
+
+    fn write_element<W: Write>(&self, writer: &mut XmlWriter<W>) -> writer::Result<()> {
+        writer.text_element(self.type_name(), &self.values)
+    }
+}
+
+/// The value of a scalar input to a shading model, such as `<shininess>` or `<transparency>`.
+///
+/// The value is either given directly or taken from a parameter of the effect.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum FloatOrParam {
+    Float(Float),
+    Param(ParamRef),
+}
+
+impl FloatOrParam {
+    /// Returns the value if it's given directly.
+    pub fn as_float(&self) -> Option<f32> {
+        match *self {
+            FloatOrParam::Float(ref float) => Some(float.value),
+            _ => None,
+        }
+    }
+}
+
+/// Declares a force, such as wind or a vortex, that affects the rigid bodies in a physics scene.
+///
+/// The specification doesn't define a common technique for force fields, so they're entirely
+/// described by application-specific techniques. A force field is applied to a
+/// [`PhysicsScene`] or an [`InstancePhysicsModel`] through an [`InstanceForceField`].
+///
+/// [`PhysicsScene`]: ./struct.PhysicsScene.html
+/// [`InstancePhysicsModel`]: ./struct.InstancePhysicsModel.html
+/// [`InstanceForceField`]: ./struct.InstanceForceField.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "force_field"]
+pub struct ForceField {
+    /// A unique identifier for the force field.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this force field.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about the force field and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The application-specific descriptions of the force field.
+    ///
+    /// There will always be at least one technique.
+    #[child]
+    #[required]
+    pub techniques: Vec<Technique>,
+
+    /// Arbitrary additional information about this force field and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "format_hint"]
+pub struct FormatHint;
+
+/// A geometric element of unknown type.
+///
+/// Each variant wraps a single value containing a given type of geometric data. See the
+/// documentation for each of the possible geometric types for more information.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum GeometricElement {
+    /// A boundary representation, which was added in COLLADA version `1.5.0`.
+    #[collada(version = "1.5")]
+    Brep(::v1_5::Brep),
+    ConvexMesh(ConvexMesh),
+    Mesh(Mesh),
+    Spline(Spline),
+}
+
+impl GeometricElement {
+    /// Attempts to downcast the geometric element to a [`ConvexMesh`].
+    ///
+    /// Returns a reference to the inner [`ConvexMesh`] if there is one, returns `None` otherwise.
+    /// This is useful if you have a `GeometricElement` but only want to use it if it represents a
+    /// [`ConvexMesh`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ::collaborate::v1_4::*;
+    /// # static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+    /// # let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
+    /// # let document = Collada::from_str(&*source).unwrap();
+    /// # let library_geometries = document.libraries[5].as_library_geometries().unwrap();
+    /// let geometry = &library_geometries.geometries[0];
+    /// if let Some(mesh) = geometry.geometric_element.as_convex_mesh() {
+    ///     // Do something with `mesh`.
+    /// }
+    /// ```
+    ///
+    /// [`ConvexMesh`]: ./struct.ConvexMesh.html
+    pub fn as_convex_mesh(&self) -> Option<&ConvexMesh> {
+        match *self {
+            GeometricElement::ConvexMesh(ref mesh) => Some(mesh),
+            _ => None,
+        }
+    }
+
+    /// Attempts to downcast the geometric element to a [`Mesh`].
+    ///
+    /// Returns a reference to the inner [`Mesh`] if there is one, returns `None` otherwise. This
+    /// is useful if you have a `GeometricElement` but only want to use it if it represents a
+    /// [`Mesh`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ::collaborate::v1_4::*;
+    /// # static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+    /// # let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
+    /// # let document = Collada::from_str(&*source).unwrap();
+    /// # let library_geometries = document.libraries[5].as_library_geometries().unwrap();
+    /// let geometry = &library_geometries.geometries[0];
+    /// if let Some(mesh) = geometry.geometric_element.as_mesh() {
+    ///     // Do something with `mesh`.
+    /// }
+    /// ```
+    ///
+    /// [`Mesh`]: ./struct.Mesh.html
+    pub fn as_mesh(&self) -> Option<&Mesh> {
+        match *self {
+            GeometricElement::Mesh(ref mesh) => Some(mesh),
+            _ => None,
+        }
+    }
+
+    /// Attempts to downcast the geometric element to a [`Spline`].
+    ///
+    /// Returns a reference to the inner [`Spline`] if there is one, returns `None` otherwise. This
+    /// is useful if you have a `GeometricElement` but only want to use it if it represents a
+    /// [`Spline`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ::collaborate::v1_4::*;
+    /// # static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+    /// # let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
+    /// # let document = Collada::from_str(&*source).unwrap();
+    /// # let library_geometries = document.libraries[5].as_library_geometries().unwrap();
+    /// let geometry = &library_geometries.geometries[0];
+    /// if let Some(spline) = geometry.geometric_element.as_spline() {
+    ///     // Do something with `spline`.
+    /// }
+    /// ```
+    ///
+    /// [`Spline`]: ./struct.Spline.html
+    pub fn as_spline(&self) -> Option<&Spline> {
+        match *self {
+            GeometricElement::Spline(ref mesh) => Some(mesh),
+            _ => None,
+        }
+    }
+}
+
+/// An iterator over groups of vertices that all have the same size, e.g. the triangles of a
+/// [`Triangles`](./struct.Triangles.html).
+pub struct FixedPolygonIter<'a> {
+    chunks: Option<::std::slice::Chunks<'a, usize>>,
+    stride: usize,
+}
+
+impl<'a> FixedPolygonIter<'a> {
+    fn new(primitives: Option<&'a Primitives>, vertices: usize, stride: usize) -> FixedPolygonIter<'a> {
+        FixedPolygonIter {
+            chunks: primitives.map(|primitives| primitives.chunks(vertices * stride)),
+            stride,
+        }
+    }
+}
+
+impl<'a> ::std::iter::Iterator for FixedPolygonIter<'a> {
+    type Item = Polygon<'a>;
+
+    fn next(&mut self) -> Option<Polygon<'a>> {
+        let stride = self.stride;
+        self.chunks.as_mut()
+            .and_then(|chunks| chunks.next())
+            .map(|indices| Polygon::from_indices(indices, stride))
+    }
+}
+
+/// Describes the visual shape and appearance of an object in a scene.
+///
+/// The primary purpose of `Geometry` is to provide access to a [`GeometricElement`], via its
+/// `geographic_element` member. It contains miscellaneous additional data, such as asset
+/// metadata, but otherwise does not directly contain any geometric data.
+///
+/// # Examples
+///
+/// ```
+/// # use ::collaborate::v1_4::*;
+/// # static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+/// # let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
+/// # let document = Collada::from_str(&*source).unwrap();
+/// # let library_geometries = document.libraries[5].as_library_geometries().unwrap();
+/// let geometry = &library_geometries.geometries[0];
+/// match geometry.geometric_element {
+///     GeometricElement::Brep(ref brep) => {
+///         // Only found in documents parsed with `Strictness::Permissive`.
+///     }
+///
+///     GeometricElement::ConvexMesh(ref mesh) => {
+///         // Do something with `mesh`.
+///     }
+///
+///     GeometricElement::Mesh(ref mesh) => {
+///         // Do something with `mesh`.
+///     }
+///
+///     GeometricElement::Spline(ref spline) => {
+///         // Do something with `spline`.
+///     }
+/// }
+/// ```
+///
+/// [`GeometricElement`]: ./enum.GeometricElement.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "geometry"]
+pub struct Geometry {
+    /// A unique identifier for the geometry instance.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this geometry instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about this geometry instance and the data it contains.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The actual data for the geometry instance.
+    #[child]
+    pub geometric_element: GeometricElement,
+
+    /// Arbitrary additional information about this geometry instance and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl From<Mesh> for Geometry {
+    /// Wraps `mesh` in a geometry with no id or name.
+    fn from(mesh: Mesh) -> Geometry {
+        Geometry {
+            id: None,
+            name: None,
+            asset: None,
+            geometric_element: GeometricElement::Mesh(mesh),
+            extras: Vec::new(),
+        }
+    }
+}
+
+targetable_vector! {
+    /// The gravity applied to every rigid body in a
+    /// [`PhysicsScene`](./struct.PhysicsScene.html).
+    pub struct Gravity, "gravity"
+}
+
+targetable_vector! {
+    /// Half of the width, height, and depth of a [`BoxShape`](./struct.BoxShape.html).
+    pub struct HalfExtents, "half_extents"
+}
+
+targetable_float! {
+    /// The height of a cylinder or capsule shape along its local y axis.
+    pub struct Height, "height"
+}
+
+/// The vertex attribute indices of a hole in a [`PolygonHole`].
+///
+/// [`PolygonHole`]: ./struct.PolygonHole.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "h"]
+pub struct Hole {
+    #[text]
+    data: Vec<usize>,
+}
+
+impl ::std::ops::Deref for Hole {
+    type Target = [usize];
+
+    fn deref(&self) -> &[usize] { &*self.data }
+}
+
+targetable_bool! {
+    /// Whether a shape is hollow, which affects how its mass is distributed.
+    pub struct Hollow, "hollow"
+}
+
+/// An array of references to the ids of other elements in the document, e.g. the joints of a
+/// skin given as node ids.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "IDREF_array"]
+pub struct IdrefArray {
+    #[attribute]
+    pub count: usize,
+
+    #[attribute]
+    pub id: Option<String>,
+
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The referenced ids.
+    #[text(count = "count", split_with = "::utils::split_xml_list")]
+    pub data: Vec<String>,
+}
+
+/// Declares an image used by an effect, e.g. as the source of a texture.
+///
+/// The image data is usually stored in a separate file referenced by `init_from`, but it may
+/// also be embedded in the document.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "image"]
+#[write_with = "write_image"]
+pub struct Image {
+    /// A unique identifier for the image.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this image.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The format of the image data, if it isn't determined by the data itself.
+    #[attribute]
+    pub format: Option<String>,
+
+    /// The height of the image in pixels.
+    #[attribute]
+    pub height: Option<usize>,
+
+    /// The width of the image in pixels.
+    #[attribute]
+    pub width: Option<usize>,
+
+    /// The depth of the image in pixels, which is `1` for 2D images.
+    #[attribute]
+    #[optional_with_default = "1"]
+    pub depth: usize,
+
+    /// Metadata about the image and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The image data, if it's embedded in the document.
+    #[child]
+    pub data: Option<ImageData>,
+
+    /// The location of the image data, if it's stored separately from the document.
+    #[child]
+    pub init_from: Option<AnyUri>,
+
+    /// Arbitrary additional information about this image and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Image {
+    /// Returns the location of the image data, if it's stored separately from the document.
+    pub fn uri(&self) -> Option<&AnyUri> {
+        self.init_from.as_ref()
+    }
+
+    /// Returns the image data, if it's embedded in the document.
+    pub fn embedded_data(&self) -> Option<&[u8]> {
+        self.data.as_ref().map(|data| data.data.as_bytes())
+    }
+
+    /// Returns the format, dimensions, and channel count of the image data embedded in the
+    /// document, read from the data's header.
+    ///
+    /// Returns `None` if the image data isn't embedded, or if it isn't in one of the formats
+    /// recognized by [`image::sniff`](../image/fn.sniff.html). For image data stored in a separate
+    /// file, load the file at [`uri`](#method.uri) and pass it to `image::sniff` instead.
+    pub fn info(&self) -> Option<ImageInfo> {
+        self.embedded_data().and_then(image::sniff)
+    }
+}
+
+/// Writes an [`Image`], translating it to the 1.5 form of `<image>` when writing 1.5.
+///
+/// In 1.5 the source of an image is wrapped in `<ref>` or `<hex>` inside of `<init_from>`, and the
+/// format of embedded data moves to `<hex>`. The size of an image can only be given by creating
+/// it with `<create_2d>` or `<create_3d>`, which has no equivalent in 1.4, so images with a size
+/// can't be written as 1.5.
+///
+/// [`Image`]: ./struct.Image.html
+fn write_image<W: Write>(image: &Image, writer: &mut XmlWriter<W>) -> writer::Result<()> {
+    let as_v1_5 = writer.version().is_some_and(|version| version.is_compatible("1.5"));
+
+    writer.start_element("image")?;
+    if let Some(ref id) = image.id {
+        writer.attribute("id", id)?;
+    }
+    if let Some(ref name) = image.name {
+        writer.attribute("name", name)?;
+    }
+
+    if as_v1_5 {
+        if image.height.is_some() {
+            writer.check_version("height", "1.4")?;
+        }
+        if image.width.is_some() {
+            writer.check_version("width", "1.4")?;
+        }
+        if image.depth != 1 {
+            writer.check_version("depth", "1.4")?;
+        }
+
+        // Only embedded data has somewhere to keep its format.
+        if image.format.is_some() && image.data.is_none() {
+            writer.check_version("format", "1.4")?;
+        }
+
+        if let Some(ref asset) = image.asset {
+            asset.write_element(writer)?;
+        }
+
+        if let Some(ref data) = image.data {
+            let format = match image.format {
+                Some(ref format) => format,
+                None => return writer.check_version("data", "1.4"),
+            };
+
+            writer.start_element("init_from")?;
+            writer.start_element("hex")?;
+            writer.attribute("format", format)?;
+            writer.text(&data.data)?;
+            writer.end_element("hex")?;
+            writer.end_element("init_from")?;
+        }
+
+        if let Some(ref init_from) = image.init_from {
+            writer.start_element("init_from")?;
+            writer.text_element("ref", init_from)?;
+            writer.end_element("init_from")?;
+        }
+    } else {
+        if let Some(ref format) = image.format {
+            writer.attribute("format", format)?;
+        }
+        if let Some(ref height) = image.height {
+            writer.attribute("height", height)?;
+        }
+        if let Some(ref width) = image.width {
+            writer.attribute("width", width)?;
+        }
+        writer.attribute("depth", &image.depth)?;
+
+        if let Some(ref asset) = image.asset {
+            asset.write_element(writer)?;
+        }
+        if let Some(ref data) = image.data {
+            data.write_element(writer)?;
+        }
+        if let Some(ref init_from) = image.init_from {
+            writer.text_element("init_from", init_from)?;
+        }
+    }
+
+    for extra in &image.extras {
+        extra.write_element(writer)?;
+    }
+    writer.end_element("image")
+}
+
+/// Image data embedded in a document, e.g. the contents of a PNG file.
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+#[name = "data"]
+pub struct ImageData {
+    /// The bytes of the image.
+    #[text]
+    pub data: HexBinary,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "imager"]
+pub struct Imager;
+
+/// Includes shader source code from an external file in a [`ProfileGlsl`] or [`ProfileCg`].
+///
+/// [`ProfileGlsl`]: ./struct.ProfileGlsl.html
+/// [`ProfileCg`]: ./struct.ProfileCg.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "include"]
+pub struct Include {
+    /// An identifier for the code that's unique within its parent element, which shaders use to
+    /// refer to it.
+    #[attribute]
+    pub sid: String,
+
+    /// The location of the source code.
+    #[attribute]
+    pub url: AnyUri,
+}
+
+targetable_vector! {
+    /// The diagonal of the inertia tensor of a rigid body, in the frame given by its `mass_frame`.
+    pub struct Inertia, "inertia"
+}
+
+/// A single joint's influence on a vertex of a skinned mesh.
+///
+/// Created with [`Skin::influences`](./struct.Skin.html#method.influences).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Influence {
+    /// The index of the joint in the skin's `"JOINT"` source, or `None` if the vertex is
+    /// influenced by the bind shape itself.
+    pub joint: Option<usize>,
+
+    /// How strongly the joint influences the vertex.
+    pub weight: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "init_as_null"]
+pub struct InitAsNull;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "init_as_target"]
+pub struct InitAsTarget;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "init_cube"]
+pub struct InitCube;
+
+/// Initializes part of a [`Surface`] from an [`Image`].
+///
+/// [`Surface`]: ./struct.Surface.html
+/// [`Image`]: ./struct.Image.html
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+#[name = "init_from"]
+pub struct InitFrom {
+    /// The mipmap level of the surface to initialize.
+    #[attribute]
+    #[optional_with_default = "0"]
+    pub mip: usize,
+
+    /// The slice of a 3D surface to initialize.
+    #[attribute]
+    #[optional_with_default = "0"]
+    pub slice: usize,
+
+    /// The face of a cube map to initialize, e.g. `"POSITIVE_X"`, which is the default.
+    #[attribute]
+    pub face: Option<String>,
+
+    /// The id of the image to initialize the surface from.
+    #[text]
+    pub image: String,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "init_planar"]
+pub struct InitPlanar;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "init_volume"]
+pub struct InitVolume;
+
+#[derive(Debug, Clone)]
+pub struct InputsForOffset<'a> {
+    inputs: ::std::slice::Iter<'a, SharedInput>,
+    offset: usize,
+}
+
+impl<'a> Iterator for InputsForOffset<'a> {
+    type Item = &'a SharedInput;
+
+    fn next(&mut self) -> Option<&'a SharedInput> {
+        while let Some(input) = self.inputs.next() {
+            if input.offset == self.offset {
+                return Some(input);
+            }
+        }
+
+        None
+    }
+}
+
+/// Instantiates an [`Animation`] as part of an [`AnimationClip`].
+///
+/// [`Animation`]: ./struct.Animation.html
+/// [`AnimationClip`]: ./struct.AnimationClip.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_animation"]
+pub struct InstanceAnimation {
+    /// The location of the animation to instantiate.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// An identifier for the instance that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Instantiates a [`Camera`] within a [`Node`], placing it in the scene.
+///
+/// [`Camera`]: ./struct.Camera.html
+/// [`Node`]: ./struct.Node.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_camera"]
+pub struct InstanceCamera {
+    /// The location of the camera to instantiate.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// An identifier for the instance that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Instantiates a [`Controller`] within a [`Node`], placing the controlled geometry in the scene.
+///
+/// [`Controller`]: ./struct.Controller.html
+/// [`Node`]: ./struct.Node.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_controller"]
+pub struct InstanceController {
+    /// The location of the controller to instantiate.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// An identifier for the instance that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The locations of the nodes where the controller starts searching for its joints.
+    #[child]
+    #[name = "skeleton"]
+    pub skeletons: Vec<AnyUri>,
+
+    /// Binds the materials used by the controlled geometry.
+    #[child]
+    pub bind_material: Option<BindMaterial>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl InstanceController {
+    /// Returns the material instance bound to `symbol`, if there is one.
+    pub fn instance_material(&self, symbol: &str) -> Option<&InstanceMaterial> {
+        self.bind_material.as_ref().and_then(|bind_material| bind_material.instance_material(symbol))
+    }
+}
+
+/// Instantiates an [`Effect`] for a [`Material`], optionally overriding its parameters.
+///
+/// [`Effect`]: ./struct.Effect.html
+/// [`Material`]: ./struct.Material.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_effect"]
+pub struct InstanceEffect {
+    /// The location of the effect to instantiate.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// An identifier for the instance that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Hints for which of the effect's techniques to use on a given platform.
+    #[child]
+    pub technique_hints: Vec<TechniqueHint>,
+
+    /// Values for the effect's parameters that override the values declared by the effect.
+    #[child]
+    pub set_params: Vec<SetParam>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl InstanceEffect {
+    /// Returns the `<setparam>` that overrides the effect parameter `sid`, if there is one.
+    pub fn set_param(&self, sid: &str) -> Option<&SetParam> {
+        self.set_params.iter().find(|param| param.reference == sid)
+    }
+}
+
+/// Instantiates a force field within a [`PhysicsScene`] or an [`InstancePhysicsModel`].
+///
+/// [`PhysicsScene`]: ./struct.PhysicsScene.html
+/// [`InstancePhysicsModel`]: ./struct.InstancePhysicsModel.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_force_field"]
+pub struct InstanceForceField {
+    /// The location of the force field to instantiate.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// An identifier for the instance that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Instantiates a [`Geometry`] within a [`Node`], placing it in the scene.
+///
+/// [`Geometry`]: ./struct.Geometry.html
+/// [`Node`]: ./struct.Node.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_geometry"]
+pub struct InstanceGeometry {
+    /// The location of the geometry to instantiate.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// An identifier for the instance that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Binds the materials used by the geometry.
+    #[child]
+    pub bind_material: Option<BindMaterial>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl InstanceGeometry {
+    /// Returns the material instance bound to `symbol`, if there is one.
+    pub fn instance_material(&self, symbol: &str) -> Option<&InstanceMaterial> {
+        self.bind_material.as_ref().and_then(|bind_material| bind_material.instance_material(symbol))
+    }
+}
+
+/// Instantiates a [`Light`] within a [`Node`], placing it in the scene.
+///
+/// [`Light`]: ./struct.Light.html
+/// [`Node`]: ./struct.Node.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_light"]
+pub struct InstanceLight {
+    /// The location of the light to instantiate.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// An identifier for the instance that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Instantiates a [`Material`] for a material symbol of an instantiated geometry or controller.
+///
+/// [`Material`]: ./struct.Material.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_material"]
+pub struct InstanceMaterial {
+    /// The material symbol being bound, as used by the primitives of the geometry.
+    #[attribute]
+    pub symbol: String,
+
+    /// The location of the material to instantiate.
+    #[attribute]
+    pub target: AnyUri,
+
+    /// An identifier for the instance that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Bindings from the material's parameters to values in the scene.
+    #[child]
+    pub binds: Vec<Bind>,
+
+    /// Bindings from the material's parameters to the vertex inputs of the geometry.
+    #[child]
+    pub bind_vertex_inputs: Vec<BindVertexInput>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl InstanceMaterial {
+    /// Returns the vertex input binding for the effect parameter `semantic`, if there is one.
+    pub fn bind_vertex_input(&self, semantic: &str) -> Option<&BindVertexInput> {
+        self.bind_vertex_inputs.iter().find(|input| input.semantic == semantic)
+    }
+}
+
+/// Instantiates a [`Node`] and its children within another node.
+///
+/// The node is usually declared in a `<library_nodes>`, which allows the same hierarchy to be
+/// placed in the scene more than once.
+///
+/// [`Node`]: ./struct.Node.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_node"]
+pub struct InstanceNode {
+    /// The location of the node to instantiate.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// An identifier for the instance that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+targetable_bool! {
+    /// Whether the rigid bodies connected by a constraint may pass through each other.
+    pub struct Interpenetrate, "interpenetrate"
+}
+
+/// Associates joint names with their inverse bind matrices for a [`Skin`].
+///
+/// [`Skin`]: ./struct.Skin.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "joints"]
+pub struct Joints {
+    /// The sources for the joints, generally with the semantics `"JOINT"` (the names of the
+    /// joints) and `"INV_BIND_MATRIX"` (the inverse bind matrix for each joint).
+    ///
+    /// There will always be at least one input.
+    #[child]
+    #[required]
+    pub inputs: Vec<UnsharedInput>,
+
+    /// Arbitrary additional information about the joints.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Joints {
+    /// Returns the input with the given semantic, if there is one.
+    pub fn input(&self, semantic: &str) -> Option<&UnsharedInput> {
+        self.inputs.iter().find(|input| input.semantic == semantic)
+    }
+}
+
+/// The state of [`Collada::remap_joint_names_with`] as it works through the document.
+///
+/// [`Collada::remap_joint_names_with`]: ./struct.Collada.html#method.remap_joint_names_with
+struct JointRenames<F> {
+    rename: F,
+
+    /// The names the skins give their joints, before they're renamed.
+    joints: HashSet<String>,
+
+    /// The new ids of renamed nodes, by their old ids.
+    ids: HashMap<String, String>,
+
+    /// The new sids of renamed nodes, by their old sids.
+    sids: HashMap<String, String>,
+}
+
+impl<F> JointRenames<F> where F: FnMut(&str) -> Option<String> {
+    fn rename_skin(&mut self, skin: &mut Skin) {
+        let id = match skin.joints.input("JOINT") {
+            Some(input) => input.source.id().to_owned(),
+            None => return,
+        };
+        let names = match skin.sources.iter_mut().find(|source| source.id == id).and_then(|source| source.array.as_mut()) {
+            Some(&mut Array::Name(ref mut array)) => &mut array.data,
+            Some(&mut Array::Idref(ref mut array)) => &mut array.data,
+            _ => return,
+        };
+
+        for name in names {
+            self.joints.insert(name.clone());
+            if let Some(new_name) = (self.rename)(name) {
+                *name = new_name;
+            }
+        }
+    }
+
+    fn rename_nodes(&mut self, nodes: &mut [Node]) {
+        for node in nodes {
+            let is_joint = node.node_type == NodeType::Joint
+                || node.id.iter().chain(&node.sid).any(|name| self.joints.contains(name));
+            if is_joint {
+                if let Some(ref mut id) = node.id {
+                    if let Some(new_id) = (self.rename)(id) {
+                        self.ids.insert(::std::mem::replace(id, new_id.clone()), new_id);
+                    }
+                }
+                if let Some(ref mut sid) = node.sid {
+                    if let Some(new_sid) = (self.rename)(sid) {
+                        self.sids.insert(::std::mem::replace(sid, new_sid.clone()), new_sid);
+                    }
+                }
+                if let Some(ref mut name) = node.name {
+                    if let Some(new_name) = (self.rename)(name) {
+                        *name = new_name;
+                    }
+                }
+            }
+
+            self.rename_nodes(&mut node.nodes);
+        }
+    }
+
+    fn rename_channels(&self, animations: &mut [Animation]) {
+        for animation in animations {
+            for channel in &mut animation.channels {
+                let mut path = match channel.target_ref() {
+                    Ok(path) => path,
+                    Err(_) => continue,
+                };
+
+                // The last sid is the animated value, which belongs to a node rather than being one.
+                let mut renamed = false;
+                if let Some(new_id) = path.root.as_ref().and_then(|id| self.ids.get(id)) {
+                    path.root = Some(new_id.clone());
+                    renamed = true;
+                }
+                let nodes = path.sids.len().saturating_sub(1);
+                for sid in &mut path.sids[..nodes] {
+                    if let Some(new_sid) = self.sids.get(sid) {
+                        *sid = new_sid.clone();
+                        renamed = true;
+                    }
+                }
+
+                if renamed {
+                    channel.target = path.to_string();
+                }
+            }
+
+            self.rename_channels(&mut animation.animations);
+        }
+    }
+
+    fn rename_skeletons(&self, nodes: &mut [Node]) {
+        for node in nodes {
+            for instance in &mut node.instance_controllers {
+                for skeleton in &mut instance.skeletons {
+                    let new_id = skeleton.local_id().and_then(|id| self.ids.get(id));
+                    if let Some(new_id) = new_id {
+                        *skeleton = format!("#{}", new_id).parse().unwrap();
+                    }
+                }
+            }
+
+            self.rename_skeletons(&mut node.nodes);
+        }
+    }
+}
+
+/// Instantiates a [`PhysicsMaterial`] for a rigid body or shape.
+///
+/// [`PhysicsMaterial`]: ./struct.PhysicsMaterial.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_physics_material"]
+pub struct InstancePhysicsMaterial {
+    /// The location of the physics material to instantiate.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// An identifier for the instance that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Instantiates a [`PhysicsModel`], either within a [`PhysicsScene`] or as part of another
+/// physics model.
+///
+/// The rigid bodies of the model are bound to the nodes they simulate by the instance's
+/// `instance_rigid_bodies`.
+///
+/// [`PhysicsModel`]: ./struct.PhysicsModel.html
+/// [`PhysicsScene`]: ./struct.PhysicsScene.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_physics_model"]
+pub struct InstancePhysicsModel {
+    /// The location of the physics model to instantiate.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// An identifier for the instance that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The location of the node that the instantiated model is positioned relative to.
+    ///
+    /// If `None`, the model is positioned relative to the world.
+    #[attribute]
+    pub parent: Option<AnyUri>,
+
+    /// Force fields that only affect this instance of the model.
+    #[child]
+    pub instance_force_fields: Vec<InstanceForceField>,
+
+    /// Bindings from the model's rigid bodies to the nodes they simulate.
+    #[child]
+    pub instance_rigid_bodies: Vec<InstanceRigidBody>,
+
+    /// The rigid constraints of the model that are used by this instance.
+    #[child]
+    pub instance_rigid_constraints: Vec<InstanceRigidConstraint>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl InstancePhysicsModel {
+    /// Returns the instance of the rigid body with the given sid, if there is one.
+    pub fn instance_rigid_body(&self, body: &str) -> Option<&InstanceRigidBody> {
+        self.instance_rigid_bodies.iter().find(|instance| instance.body == body)
+    }
+}
+
+/// Instantiates a [`PhysicsScene`] within the document's [`Scene`].
+///
+/// [`PhysicsScene`]: ./struct.PhysicsScene.html
+/// [`Scene`]: ./struct.Scene.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_physics_scene"]
+pub struct InstancePhysicsScene {
+    /// The location of the physics scene to instantiate.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// An identifier for the instance that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Binds a [`RigidBody`] of an instantiated physics model to the node it simulates.
+///
+/// [`RigidBody`]: ./struct.RigidBody.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_rigid_body"]
+pub struct InstanceRigidBody {
+    /// The sid of the rigid body within the instantiated physics model.
+    #[attribute]
+    pub body: String,
+
+    /// An identifier for the instance that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The location of the node that the rigid body simulates.
+    #[attribute]
+    pub target: AnyUri,
+
+    /// The initial state of the body, which overrides the values declared by the rigid body.
+    #[child]
+    pub technique_common: InstanceRigidBodyTechniqueCommon,
+
+    /// Application-specific information about the instance.
+    #[child]
+    pub techniques: Vec<Technique>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl InstanceRigidBody {
+    /// Returns whether the body is moved by the simulation, where `body` is the rigid body that
+    /// this instantiates.
+    ///
+    /// The instance's `<dynamic>` takes precedence over the one declared by `body`. If neither
+    /// of them has one, the body is dynamic.
+    pub fn is_dynamic(&self, body: &RigidBody) -> bool {
+        match self.technique_common.dynamic {
+            Some(ref dynamic) => dynamic.value,
+            None => body.is_dynamic(),
+        }
+    }
+
+    /// Returns the initial linear velocity of the body.
+    ///
+    /// Bodies without a `<velocity>` start at rest, so this is zero if one wasn't given. Returns
+    /// `None` if the velocity doesn't have exactly three values.
+    pub fn initial_velocity(&self) -> Option<[f32; 3]> {
+        initial_vector(self.technique_common.velocity.as_ref().map(|velocity| &*velocity.values))
+    }
+
+    /// Returns the initial angular velocity of the body, in degrees per second around each axis.
+    ///
+    /// Bodies without an `<angular_velocity>` start without spinning, so this is zero if one
+    /// wasn't given. Returns `None` if the angular velocity doesn't have exactly three values.
+    pub fn initial_angular_velocity(&self) -> Option<[f32; 3]> {
+        initial_vector(self.technique_common.angular_velocity.as_ref().map(|velocity| &*velocity.values))
+    }
+}
+
+/// Reads an initial velocity, which defaults to zero.
+fn initial_vector(values: Option<&[f32]>) -> Option<[f32; 3]> {
+    match values {
+        Some(&[x, y, z]) => Some([x, y, z]),
+        Some(_) => None,
+        None => Some([0.0; 3]),
+    }
+}
+
+/// The common technique of an [`InstanceRigidBody`](./struct.InstanceRigidBody.html).
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "technique_common"]
+pub struct InstanceRigidBodyTechniqueCommon {
+    /// The initial angular velocity of the body.
+    #[child]
+    pub angular_velocity: Option<AngularVelocity>,
+
+    /// The initial linear velocity of the body.
+    #[child]
+    pub velocity: Option<Velocity>,
+
+    /// Whether the body is moved by the simulation, which defaults to `true`.
+    #[child]
+    pub dynamic: Option<Dynamic>,
+
+    /// The total mass of the body.
+    ///
+    /// If `None`, the mass is worked out from the density and volume of the body's shapes.
+    #[child]
+    pub mass: Option<Mass>,
+
+    /// The center of mass and principal axes of inertia of the body, relative to the node it
+    /// simulates.
+    #[child]
+    pub mass_frame: Option<MassFrame>,
+
+    /// The inertia tensor of the body, in the frame given by `mass_frame`.
+    #[child]
+    pub inertia: Option<Inertia>,
+
+    /// The physics material of the body, used by any shapes that don't have their own.
+    #[child]
+    pub physics_material: Option<PhysicsMaterialSource>,
+
+    /// Additional shapes for the body.
+    #[child]
+    pub shapes: Vec<Shape>,
+}
+
+/// Enables a [`RigidConstraint`] of an instantiated physics model.
+///
+/// [`RigidConstraint`]: ./struct.RigidConstraint.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_rigid_constraint"]
+pub struct InstanceRigidConstraint {
+    /// The sid of the rigid constraint within the instantiated physics model.
+    #[attribute]
+    pub constraint: String,
+
+    /// An identifier for the instance that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Instantiates a [`VisualScene`] as the document's [`Scene`].
+///
+/// [`VisualScene`]: ./struct.VisualScene.html
+/// [`Scene`]: ./struct.Scene.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_visual_scene"]
+pub struct InstanceVisualScene {
+    /// The location of the visual scene to instantiate.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// An identifier for the instance that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// A parameter value of a single integer.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "int"]
+pub struct Int {
+    #[text]
+    pub value: i64,
+}
+
+/// A parameter value of two integers.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "int2"]
+pub struct Int2 {
+    #[text]
+    pub values: Vec<i64>,
+}
+
+/// A parameter value of three integers.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "int3"]
+pub struct Int3 {
+    #[text]
+    pub values: Vec<i64>,
+}
+
+/// A parameter value of four integers.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "int4"]
+pub struct Int4 {
+    #[text]
+    pub values: Vec<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "int_array"]
+pub struct IntArray {
+    #[attribute]
+    pub count: usize,
+
+    #[attribute]
+    pub id: Option<String>,
+
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The smallest value that can appear in the array.
+    #[attribute]
+    #[name = "minInclusive"]
+    #[optional_with_default = "-2147483648"]
+    pub min_inclusive: i64,
+
+    /// The largest value that can appear in the array.
+    #[attribute]
+    #[name = "maxInclusive"]
+    #[optional_with_default = "2147483647"]
+    pub max_inclusive: i64,
+
+    /// The values in the array.
+    ///
+    /// Decoding the values can be deferred until they're first accessed, see [`RawArray`] for
+    /// details.
+    ///
+    /// [`RawArray`]: ../common/struct.RawArray.html
+    #[text(count = "count")]
+    pub data: RawArray<i64>,
+}
+
+/// Produces a diffusely shaded surface using Lambertian reflectance.
+///
+/// Lambert surfaces have no specular highlights, so there are no `specular` or `shininess`
+/// inputs.
+#[derive(Debug, Clone, Default, PartialEq, ColladaElement)]
+#[name = "lambert"]
+pub struct Lambert {
+    /// The amount of light emitted from the surface.
+    #[child(flatten = "emission")]
+    pub emission: Option<ColorOrTexture>,
+
+    /// The amount of ambient light reflected from the surface.
+    #[child(flatten = "ambient")]
+    pub ambient: Option<ColorOrTexture>,
+
+    /// The amount of light diffusely reflected from the surface.
+    #[child(flatten = "diffuse")]
+    pub diffuse: Option<ColorOrTexture>,
+
+    /// The color of a perfect mirror reflection.
+    #[child(flatten = "reflective")]
+    pub reflective: Option<ColorOrTexture>,
+
+    /// The amount of perfect mirror reflection to be added to the reflected light.
+    #[child(flatten = "reflectivity")]
+    pub reflectivity: Option<FloatOrParam>,
+
+    /// The color of perfectly refracted light.
+    #[child]
+    pub transparent: Option<Transparent>,
+
+    /// The amount of perfectly refracted light added to the reflected color.
+    #[child(flatten = "transparency")]
+    pub transparency: Option<FloatOrParam>,
+
+    /// The index of refraction for perfectly refracted light.
+    #[child(flatten = "index_of_refraction")]
+    pub index_of_refraction: Option<FloatOrParam>,
+}
+
+/// A single library of unknown type.
+///
+/// Each variant wraps a single value containing the library data. See the documentation for
+/// each of the possible library types for more information on what data each can contain.
+///
+/// # COLLADA Versions
+///
+/// The articulated system, formula, joint, and kinematics libraries were added in COLLADA version
+/// `1.5.0`, and use the `1.5.0` document model. They're only parsed from `1.4.x` documents with
+/// [`Strictness::Permissive`], and can't be written as `1.4.x`.
+///
+/// [`Strictness::Permissive`]: ../options/enum.Strictness.html#variant.Permissive
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum Library {
+    Animations(LibraryAnimations),
+    AnimationClips(LibraryAnimationClips),
+    #[collada(version = "1.5")]
+    ArticulatedSystems(::v1_5::LibraryArticulatedSystems),
+    Cameras(LibraryCameras),
+    Controllers(LibraryControllers),
+    Effects(LibraryEffects),
+    ForceFields(LibraryForceFields),
+    #[collada(version = "1.5")]
+    Formulas(::v1_5::LibraryFormulas),
+    Geometries(LibraryGeometries),
+    Images(LibraryImages),
+    #[collada(version = "1.5")]
+    Joints(::v1_5::LibraryJoints),
+    #[collada(version = "1.5")]
+    KinematicsModels(::v1_5::LibraryKinematicsModels),
+    #[collada(version = "1.5")]
+    KinematicsScenes(::v1_5::LibraryKinematicsScenes),
+    Lights(LibraryLights),
+    Materials(LibraryMaterials),
+    Nodes(LibraryNodes),
+    PhysicsMaterials(LibraryPhysicsMaterials),
+    PhysicsModels(LibraryPhysicsModels),
+    PhysicsScenes(LibraryPhysicsScenes),
+    VisualScenes(LibraryVisualScenes),
+}
+
+impl Library {
+    /// Returns the asset information of the library, if it has any.
+    ///
+    /// Always `None` for the libraries from COLLADA `1.5.0`, since their asset information uses
+    /// the `1.5.0` model.
+    pub fn asset(&self) -> Option<&Asset> {
+        match *self {
+            Library::ArticulatedSystems(_)
+            | Library::Formulas(_)
+            | Library::Joints(_)
+            | Library::KinematicsModels(_)
+            | Library::KinematicsScenes(_) => None,
+            Library::Animations(ref library) => library.asset.as_ref(),
+            Library::AnimationClips(ref library) => library.asset.as_ref(),
+            Library::Cameras(ref library) => library.asset.as_ref(),
+            Library::Controllers(ref library) => library.asset.as_ref(),
+            Library::Effects(ref library) => library.asset.as_ref(),
+            Library::ForceFields(ref library) => library.asset.as_ref(),
+            Library::Geometries(ref library) => library.asset.as_ref(),
+            Library::Images(ref library) => library.asset.as_ref(),
+            Library::Lights(ref library) => library.asset.as_ref(),
+            Library::Materials(ref library) => library.asset.as_ref(),
+            Library::Nodes(ref library) => library.asset.as_ref(),
+            Library::PhysicsMaterials(ref library) => library.asset.as_ref(),
+            Library::PhysicsModels(ref library) => library.asset.as_ref(),
+            Library::PhysicsScenes(ref library) => library.asset.as_ref(),
+            Library::VisualScenes(ref library) => library.asset.as_ref(),
+        }
+    }
+
+    pub fn as_library_animations(&self) -> Option<&LibraryAnimations> {
+        match *self {
+            Library::Animations(ref library_animations) => Some(library_animations),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_animation_clips(&self) -> Option<&LibraryAnimationClips> {
+        match *self {
+            Library::AnimationClips(ref library_animation_clips) => Some(library_animation_clips),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_cameras(&self) -> Option<&LibraryCameras> {
+        match *self {
+            Library::Cameras(ref library_cameras) => Some(library_cameras),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_controllers(&self) -> Option<&LibraryControllers> {
+        match *self {
+            Library::Controllers(ref library_controllers) => Some(library_controllers),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_effects(&self) -> Option<&LibraryEffects> {
+        match *self {
+            Library::Effects(ref library_effects) => Some(library_effects),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_force_fields(&self) -> Option<&LibraryForceFields> {
+        match *self {
+            Library::ForceFields(ref library_force_fields) => Some(library_force_fields),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_geometries(&self) -> Option<&LibraryGeometries> {
+        match *self {
+            Library::Geometries(ref library_geometries) => Some(library_geometries),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_images(&self) -> Option<&LibraryImages> {
+        match *self {
+            Library::Images(ref library_images) => Some(library_images),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_lights(&self) -> Option<&LibraryLights> {
+        match *self {
+            Library::Lights(ref library_lights) => Some(library_lights),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_materials(&self) -> Option<&LibraryMaterials> {
+        match *self {
+            Library::Materials(ref library_materials) => Some(library_materials),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_nodes(&self) -> Option<&LibraryNodes> {
+        match *self {
+            Library::Nodes(ref library_nodes) => Some(library_nodes),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_physics_materials(&self) -> Option<&LibraryPhysicsMaterials> {
+        match *self {
+            Library::PhysicsMaterials(ref library_physics_materials) => Some(library_physics_materials),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_physics_models(&self) -> Option<&LibraryPhysicsModels> {
+        match *self {
+            Library::PhysicsModels(ref library_physics_models) => Some(library_physics_models),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_physics_scenes(&self) -> Option<&LibraryPhysicsScenes> {
+        match *self {
+            Library::PhysicsScenes(ref library_physics_scenes) => Some(library_physics_scenes),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_visual_scenes(&self) -> Option<&LibraryVisualScenes> {
+        match *self {
+            Library::VisualScenes(ref library_visual_scenes) => Some(library_visual_scenes),
+            _ => None,
+        }
+    }
+}
+
+/// Contains animation data for the document.
+///
+/// The animation data is contained in `animations` by one or more [`Animation`] instances,
+/// `LibraryAnimations` is only a container and does not represent any animation data itself.
+///
+/// [`Animation`]: ./struct.Animation.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_animations"]
+pub struct LibraryAnimations {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The animations contained within this library instance.
+    ///
+    /// There will always be at least one animation in a `LibraryAnimations`.
+    #[child]
+    #[required]
+    pub animations: Vec<Animation>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Contains animation clips for the document.
+///
+/// The clips are contained in `animation_clips` by one or more [`AnimationClip`] instances,
+/// `LibraryAnimationClips` is only a container and does not represent any animation data itself.
+///
+/// [`AnimationClip`]: ./struct.AnimationClip.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_animation_clips"]
+pub struct LibraryAnimationClips {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The animation clips contained within this library instance.
+    ///
+    /// There will always be at least one clip in a `LibraryAnimationClips`.
+    #[child]
+    #[required]
+    pub animation_clips: Vec<AnimationClip>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl LibraryAnimationClips {
+    /// Returns the animation clip with the given id, if it's in this library.
+    pub fn animation_clip(&self, id: &str) -> Option<&AnimationClip> {
+        self.animation_clips.iter().find(|clip| clip.id.as_deref() == Some(id))
+    }
+}
+
+/// Contains cameras for the document.
+///
+/// The cameras are contained in `cameras` by one or more [`Camera`] instances, `LibraryCameras`
+/// is only a container and does not represent any camera data itself.
+///
+/// [`Camera`]: ./struct.Camera.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_cameras"]
+pub struct LibraryCameras {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The cameras contained within this library instance.
+    ///
+    /// An empty `<library_cameras>` is accepted even though the specification requires at least
+    /// one camera, so this may be empty.
+    #[child]
+    pub cameras: Vec<Camera>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl LibraryCameras {
+    /// Returns the camera with the given id, if it's in this library.
+    pub fn camera(&self, id: &str) -> Option<&Camera> {
+        self.cameras.iter().find(|camera| camera.id.as_deref() == Some(id))
+    }
+}
+
+/// Contains controllers for the document.
+///
+/// The controllers are contained in `controllers` by one or more [`Controller`] instances,
+/// `LibraryControllers` is only a container and does not represent any control data itself.
+///
+/// [`Controller`]: ./struct.Controller.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_controllers"]
+pub struct LibraryControllers {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The controllers contained within this library instance.
+    ///
+    /// The specification requires at least one controller, but exporters commonly write empty
+    /// `<library_controllers>` elements, so this may be empty.
+    #[child]
+    pub controllers: Vec<Controller>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Contains effects for the document.
+///
+/// The effects are contained in `effects` by one or more [`Effect`] instances, `LibraryEffects`
+/// is only a container and does not represent any effect data itself.
+///
+/// [`Effect`]: ./struct.Effect.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_effects"]
+pub struct LibraryEffects {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The effects contained within this library instance.
+    ///
+    /// There will always be at least one effect in a `LibraryEffects`.
+    #[child]
+    #[required]
+    pub effects: Vec<Effect>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl LibraryEffects {
+    /// Returns the effect with the given id, if it's in this library.
+    pub fn effect(&self, id: &str) -> Option<&Effect> {
+        self.effects.iter().find(|effect| effect.id == id)
+    }
+}
+
+/// Contains force fields for the document.
+///
+/// The force fields are contained in `force_fields` by one or more [`ForceField`] instances, `LibraryForceFields`
+/// is only a container and does not represent any force field data itself.
+///
+/// [`ForceField`]: ./struct.ForceField.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_force_fields"]
+pub struct LibraryForceFields {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The force fields contained within this library instance.
+    ///
+    /// An empty `<library_force_fields>` is accepted even though the specification requires at least
+    /// one force field, so this may be empty.
+    #[child]
+    pub force_fields: Vec<ForceField>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl LibraryForceFields {
+    /// Returns the force field with the given id, if it's in this library.
+    pub fn force_field(&self, id: &str) -> Option<&ForceField> {
+        self.force_fields.iter().find(|item| item.id.as_deref() == Some(id))
+    }
+}
+
+/// Contains geometric data for the document.
+///
+/// The geometric data is contained in `geometries` by one or more [`Geometry`] instances,
+/// `LibraryGeometries` is only a container and does not represent any geometric data itself.
+///
+/// [`Geometry`]: ./struct.Geometry.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_geometries"]
+pub struct LibraryGeometries {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The geometric data contained within this library instance.
+    ///
+    /// There will always be at least one geometric element in a `LibraryGeometries`.
+    #[child]
+    #[required]
+    pub geometries: Vec<Geometry>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl LibraryGeometries {
+    /// Returns an iterator over all the [`Geometry`] objects contained in this library.
+    ///
+    /// [`Geometry`]: ./struct.Geometry.html
+    pub fn geometries<'a>(&'a self) -> ::std::slice::Iter<'a, Geometry> {
+        self.geometries.iter()
+    }
+
+    /// Returns the geometry with the given id, if it's in this library.
+    pub fn geometry(&self, id: &str) -> Option<&Geometry> {
+        self.geometries.iter().find(|geometry| geometry.id.as_deref() == Some(id))
+    }
+}
+
+/// Contains images for the document.
+///
+/// The images are contained in `images` by zero or more [`Image`] instances, `LibraryImages` is
+/// only a container and does not represent any image data itself.
+///
+/// [`Image`]: ./struct.Image.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_images"]
+pub struct LibraryImages {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The images contained within this library instance.
+    ///
+    /// Documents without any textures often still have an empty `<library_images>`, so this may
+    /// be empty.
+    #[child]
+    pub images: Vec<Image>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Contains lights for the document.
+///
+/// The lights are contained in `lights` by one or more [`Light`] instances, `LibraryLights` is
+/// only a container and does not represent any light data itself.
+///
+/// [`Light`]: ./struct.Light.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_lights"]
+pub struct LibraryLights {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The lights contained within this library instance.
+    ///
+    /// An empty `<library_lights>` is accepted even though the specification requires at least
+    /// one light, so this may be empty.
+    #[child]
+    pub lights: Vec<Light>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl LibraryLights {
+    /// Returns the light with the given id, if it's in this library.
+    pub fn light(&self, id: &str) -> Option<&Light> {
+        self.lights.iter().find(|light| light.id.as_deref() == Some(id))
+    }
+}
+
+/// Contains materials for the document.
+///
+/// The materials are contained in `materials` by one or more [`Material`] instances,
+/// `LibraryMaterials` is only a container and does not represent any material data itself.
+///
+/// [`Material`]: ./struct.Material.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_materials"]
+pub struct LibraryMaterials {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The materials contained within this library instance.
+    ///
+    /// There will always be at least one material in a `LibraryMaterials`.
+    #[child]
+    #[required]
+    pub materials: Vec<Material>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl LibraryMaterials {
+    /// Returns the material with the given id, if it's in this library.
+    pub fn material(&self, id: &str) -> Option<&Material> {
+        self.materials.iter().find(|material| material.id.as_deref() == Some(id))
+    }
+}
+
+/// Contains nodes that can be shared between scenes.
+///
+/// The nodes in a `LibraryNodes` aren't part of any scene themselves, they're placed in a scene
+/// by an [`InstanceNode`] within one of the scene's nodes. This allows a hierarchy that's used
+/// more than once (e.g. a piece of furniture) to only be declared once.
+///
+/// [`InstanceNode`]: ./struct.InstanceNode.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_nodes"]
+pub struct LibraryNodes {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The root nodes contained within this library instance.
+    ///
+    /// An empty `<library_nodes>` is accepted even though the specification requires at least
+    /// one node, so this may be empty.
+    #[child]
+    pub nodes: Vec<Node>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl LibraryNodes {
+    /// Returns the node with the given id, searching the whole hierarchy of each node in the
+    /// library.
+    pub fn node(&self, id: &str) -> Option<&Node> {
+        self.nodes.iter().filter_map(|node| node.find(id)).next()
+    }
+}
+
+/// Contains physics materials for the document.
+///
+/// The physics materials are contained in `physics_materials` by one or more [`PhysicsMaterial`] instances, `LibraryPhysicsMaterials`
+/// is only a container and does not represent any physics material data itself.
+///
+/// [`PhysicsMaterial`]: ./struct.PhysicsMaterial.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_physics_materials"]
+pub struct LibraryPhysicsMaterials {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The physics materials contained within this library instance.
+    ///
+    /// An empty `<library_physics_materials>` is accepted even though the specification requires at least
+    /// one physics material, so this may be empty.
+    #[child]
+    pub physics_materials: Vec<PhysicsMaterial>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl LibraryPhysicsMaterials {
+    /// Returns the physics material with the given id, if it's in this library.
+    pub fn physics_material(&self, id: &str) -> Option<&PhysicsMaterial> {
+        self.physics_materials.iter().find(|item| item.id.as_deref() == Some(id))
+    }
+}
+
+/// Contains physics models for the document.
+///
+/// The physics models are contained in `physics_models` by one or more [`PhysicsModel`] instances, `LibraryPhysicsModels`
+/// is only a container and does not represent any physics model data itself.
+///
+/// [`PhysicsModel`]: ./struct.PhysicsModel.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_physics_models"]
+pub struct LibraryPhysicsModels {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The physics models contained within this library instance.
+    ///
+    /// An empty `<library_physics_models>` is accepted even though the specification requires at least
+    /// one physics model, so this may be empty.
+    #[child]
+    pub physics_models: Vec<PhysicsModel>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl LibraryPhysicsModels {
+    /// Returns the physics model with the given id, if it's in this library.
+    pub fn physics_model(&self, id: &str) -> Option<&PhysicsModel> {
+        self.physics_models.iter().find(|item| item.id.as_deref() == Some(id))
+    }
+}
+
+/// Contains physics scenes for the document.
+///
+/// The physics scenes are contained in `physics_scenes` by one or more [`PhysicsScene`] instances, `LibraryPhysicsScenes`
+/// is only a container and does not represent any physics scene data itself.
+///
+/// [`PhysicsScene`]: ./struct.PhysicsScene.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_physics_scenes"]
+pub struct LibraryPhysicsScenes {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The physics scenes contained within this library instance.
+    ///
+    /// An empty `<library_physics_scenes>` is accepted even though the specification requires at least
+    /// one physics scene, so this may be empty.
+    #[child]
+    pub physics_scenes: Vec<PhysicsScene>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl LibraryPhysicsScenes {
+    /// Returns the physics scene with the given id, if it's in this library.
+    pub fn physics_scene(&self, id: &str) -> Option<&PhysicsScene> {
+        self.physics_scenes.iter().find(|item| item.id.as_deref() == Some(id))
+    }
+}
+
+/// Contains visual scenes for the document.
+///
+/// The visual scenes are contained in `visual_scenes` by one or more [`VisualScene`] instances,
+/// `LibraryVisualScenes` is only a container and does not represent any scene data itself.
+///
+/// [`VisualScene`]: ./struct.VisualScene.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_visual_scenes"]
+pub struct LibraryVisualScenes {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The visual scenes contained within this library instance.
+    ///
+    /// An empty `<library_visual_scenes>` is accepted even though the specification requires at
+    /// least one visual scene, so this may be empty.
+    #[child]
+    pub visual_scenes: Vec<VisualScene>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl LibraryVisualScenes {
+    /// Returns the visual scene with the given id, if it's in this library.
+    pub fn visual_scene(&self, id: &str) -> Option<&VisualScene> {
+        self.visual_scenes.iter().find(|visual_scene| visual_scene.id.as_deref() == Some(id))
+    }
+}
+
+/// Declares a light source, which can be instantiated by a node to place it in the scene.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "light"]
+pub struct Light {
+    /// A unique identifier for the light.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this light.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about the light and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The type of light and its parameters, which every application is expected to support.
+    #[child(flatten = "technique_common")]
+    pub kind: LightKind,
+
+    /// Application-specific descriptions of the light.
+    #[child]
+    pub techniques: Vec<Technique>,
+
+    /// Arbitrary additional information about this light and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Light {
+    /// Returns the value within the light with the given sid, e.g. its `color`, if there is one.
+    pub fn find_sid<'a>(&'a self, sid: &str) -> Option<SidTarget<'a>> {
+        let color = self.kind.color();
+        if color.sid.as_deref() == Some(sid) {
+            return Some(SidTarget::Color(color));
+        }
+
+        let values = match self.kind {
+            LightKind::Point(ref point) => vec![
+                point.constant_attenuation.as_ref().map(|value| (&value.sid, &value.value)),
+                point.linear_attenuation.as_ref().map(|value| (&value.sid, &value.value)),
+                point.quadratic_attenuation.as_ref().map(|value| (&value.sid, &value.value)),
+            ],
+            LightKind::Spot(ref spot) => vec![
+                spot.constant_attenuation.as_ref().map(|value| (&value.sid, &value.value)),
+                spot.linear_attenuation.as_ref().map(|value| (&value.sid, &value.value)),
+                spot.quadratic_attenuation.as_ref().map(|value| (&value.sid, &value.value)),
+                spot.falloff_angle.as_ref().map(|value| (&value.sid, &value.value)),
+                spot.falloff_exponent.as_ref().map(|value| (&value.sid, &value.value)),
+            ],
+            _ => Vec::new(),
+        };
+
+        values.into_iter()
+            .flatten()
+            .find(|(value_sid, _)| value_sid.as_deref() == Some(sid))
+            .map(|(_, value)| SidTarget::Float(value))
+    }
+}
+
+/// The type of a [`Light`](./struct.Light.html).
+///
+/// Each variant wraps a single value containing the parameters of a given type of light.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum LightKind {
+    Ambient(Ambient),
+    Directional(Directional),
+    Point(Point),
+    Spot(Spot),
+}
+
+impl LightKind {
+    /// Returns the inner [`Ambient`](./struct.Ambient.html) if this is an ambient light.
+    pub fn as_ambient(&self) -> Option<&Ambient> {
+        match *self {
+            LightKind::Ambient(ref ambient) => Some(ambient),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner [`Directional`](./struct.Directional.html) if this is a directional
+    /// light.
+    pub fn as_directional(&self) -> Option<&Directional> {
+        match *self {
+            LightKind::Directional(ref directional) => Some(directional),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner [`Point`](./struct.Point.html) if this is a point light.
+    pub fn as_point(&self) -> Option<&Point> {
+        match *self {
+            LightKind::Point(ref point) => Some(point),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner [`Spot`](./struct.Spot.html) if this is a spot light.
+    pub fn as_spot(&self) -> Option<&Spot> {
+        match *self {
+            LightKind::Spot(ref spot) => Some(spot),
+            _ => None,
+        }
+    }
+
+    /// Returns the color of the light, which every type of light has.
+    pub fn color(&self) -> &Color {
+        match *self {
+            LightKind::Ambient(ref ambient) => &ambient.color,
+            LightKind::Directional(ref directional) => &directional.color,
+            LightKind::Point(ref point) => &point.color,
+            LightKind::Spot(ref spot) => &spot.color,
+        }
+    }
+
+    /// Returns the constant, linear, and quadratic attenuation factors of the light.
+    ///
+    /// Ambient and directional lights aren't attenuated, so this returns `None` for them.
+    pub fn attenuation(&self) -> Option<(f32, f32, f32)> {
+        match *self {
+            LightKind::Point(ref point) => Some(point.attenuation()),
+            LightKind::Spot(ref spot) => Some(spot.attenuation()),
+            _ => None,
+        }
+    }
+}
+
+/// The limits of a [`RigidConstraint`].
+///
+/// The attachment can move freely along or around any axis where the lower limit is smaller than
+/// the upper limit, and is locked along or around any axis where they're equal.
+///
+/// [`RigidConstraint`]: ./struct.RigidConstraint.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "limits"]
+pub struct Limits {
+    /// The angular limits, in degrees.
+    #[child]
+    pub swing_cone_and_twist: Option<SwingConeAndTwist>,
+
+    /// The linear limits.
+    #[child]
+    pub linear: Option<LinearLimits>,
+}
+
+/// A single line segment of a [`Lines`] or [`Linestrips`], connecting two vertices.
+///
+/// [`Lines`]: ./struct.Lines.html
+/// [`Linestrips`]: ./struct.Linestrips.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineSegment<'a> {
+    /// The vertex the segment starts at.
+    pub start: Vertex<'a>,
+
+    /// The vertex the segment ends at.
+    pub end: Vertex<'a>,
+}
+
+impl<'a> LineSegment<'a> {
+    /// Returns the indices of the segment's two vertices for the inputs at `offset`, e.g. the
+    /// indices into the positions of a mesh.
+    ///
+    /// Returns `None` if the vertices don't have an attribute at `offset`.
+    pub fn indices(&self, offset: usize) -> Option<[usize; 2]> {
+        Some([self.start.index(offset)?, self.end.index(offset)?])
+    }
+}
+
+/// An iterator over the line segments of a [`Lines`] or [`Linestrips`], yielded as
+/// [`LineSegment`]s.
+///
+/// [`Lines`]: ./struct.Lines.html
+/// [`Linestrips`]: ./struct.Linestrips.html
+/// [`LineSegment`]: ./struct.LineSegment.html
+#[derive(Debug, Clone)]
+pub struct LineSegmentIter<'a> {
+    strips: ::std::slice::Iter<'a, Primitives>,
+    current: &'a [usize],
+    stride: usize,
+
+    /// The number of vertices between the start of one segment and the start of the next, 2
+    /// for unconnected segments and 1 for strips.
+    step: usize,
+}
+
+impl<'a> ::std::iter::Iterator for LineSegmentIter<'a> {
+    type Item = LineSegment<'a>;
+
+    fn next(&mut self) -> Option<LineSegment<'a>> {
+        loop {
+            if self.current.len() >= 2 * self.stride {
+                let start = &self.current[..self.stride];
+                let end = &self.current[self.stride..2 * self.stride];
+                self.current = &self.current[self.step * self.stride..];
+                return Some(LineSegment {
+                    start: Vertex { attributes: start },
+                    end: Vertex { attributes: end },
+                });
+            }
+
+            self.current = &**self.strips.next()?;
+        }
+    }
+}
+
+targetable_float! {
+    /// The linear factor in the attenuation of a [`Point`](./struct.Point.html) or
+    /// [`Spot`](./struct.Spot.html) light.
+    pub struct LinearAttenuation, "linear_attenuation"
+}
+
+/// The limits of how far the attachment of a rigid constraint can move along each axis.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "linear"]
+pub struct LinearLimits {
+    /// The lower limits, which default to 0.
+    #[child]
+    pub min: Option<Min>,
+
+    /// The upper limits, which default to 0.
+    #[child]
+    pub max: Option<Max>,
+}
+
+/// A spring acting on the position of the attachment of a rigid constraint.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "linear"]
+pub struct LinearSpring {
+    /// The stiffness of the spring, which defaults to 1.
+    #[child]
+    pub stiffness: Option<Stiffness>,
+
+    /// The damping of the spring, which defaults to 1.
+    #[child]
+    pub damping: Option<Damping>,
+
+    /// The resting position of the spring, which defaults to 0.
+    #[child]
+    pub target_value: Option<TargetValue>,
+}
+
+/// A list of individual, unconnected line segments.
+///
+/// Each line segment is made up of two vertices, which are stored one after the other in
+/// `primitives`.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "lines"]
+pub struct Lines {
+    /// A human-friendly name for this line list.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The number of line primitives in the line list.
+    #[attribute]
+    pub count: usize,
+
+    /// The name of the material associated with this line list.
+    ///
+    /// This name is bound to a material at the time of instantiaion. See [`InstanceGeometry`]
+    /// and [`BindMaterial`].
+    ///
+    /// If `None`, then the lighting and shading results are appplication-defined.
+    ///
+    /// [`InstanceGeometry`]: ./struct.InstanceGeometry.html
+    /// [`BindMaterial`]: ./struct.BindMaterial.html
+    #[attribute]
+    pub material: Option<String>,
+
+    /// The input data for the line list.
+    #[child]
+    pub inputs: Vec<SharedInput>,
+
+    /// A list of integers that specify the vertex attributes as indexes into the inputs.
+    #[child]
+    pub primitives: Option<Primitives>,
+
+    /// Arbitrary additional information about this line list and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Lines {
+    /// Returns an iterator over the line primitives in the line list, each yielded as a
+    /// [`Polygon`](./struct.Polygon.html) with two vertices.
+    pub fn iter<'a>(&'a self) -> FixedPolygonIter<'a> {
+        FixedPolygonIter::new(self.primitives.as_ref(), 2, vertex_stride(&self.inputs))
+    }
+
+    /// Returns an iterator over the line primitives in the line list, each yielded as a
+    /// [`LineSegment`](./struct.LineSegment.html).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::v1_4::*;
+    /// # static DOCUMENT: &'static str = r##"
+    /// #     <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+    /// #         <asset>
+    /// #             <created>2017-02-07T20:44:30Z</created>
+    /// #             <modified>2017-02-07T20:44:30Z</modified>
+    /// #         </asset>
+    /// #         <library_geometries>
+    /// #             <geometry id="Outline">
+    /// #                 <mesh>
+    /// #                     <source id="positions">
+    /// #                         <float_array id="positions-array" count="9">0 0 0 1 0 0 1 1 0</float_array>
+    /// #                     </source>
+    /// #                     <vertices id="vertices">
+    /// #                         <input semantic="POSITION" source="#positions" />
+    /// #                     </vertices>
+    /// #                     <lines count="2">
+    /// #                         <input semantic="VERTEX" source="#vertices" offset="0" />
+    /// #                         <p>0 1 1 2</p>
+    /// #                     </lines>
+    /// #                 </mesh>
+    /// #             </geometry>
+    /// #         </library_geometries>
+    /// #     </COLLADA>
+    /// # "##;
+    /// # let document = Collada::from_str(DOCUMENT).unwrap();
+    /// # let mesh = document.geometry("Outline").unwrap().geometric_element.as_mesh().unwrap();
+    /// let lines = mesh.primitives[0].as_lines().unwrap();
+    /// let pairs: Vec<_> = lines.segments()
+    ///     .filter_map(|segment| segment.indices(0))
+    ///     .collect();
+    /// assert_eq!(vec![[0, 1], [1, 2]], pairs);
+    /// ```
+    pub fn segments<'a>(&'a self) -> LineSegmentIter<'a> {
+        LineSegmentIter {
+            strips: self.primitives.as_slice().iter(),
+            current: &[],
+            stride: vertex_stride(&self.inputs),
+            step: 2,
+        }
+    }
+
+    /// Returns an iterator over all of the vertices in the line list, in document order.
+    ///
+    /// Vertices shared by more than one line are yielded once for each line, so this is mostly
+    /// useful for treating the lines as a point cloud.
+    pub fn vertices<'a>(&'a self) -> Box<Iterator<Item = Vertex<'a>> + 'a> {
+        Box::new(self.iter().flatten())
+    }
+
+    /// Returns the number of line primitives in the line list.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns an iterator yielding all inputs that match `offset`.
+    ///
+    /// See [`Polylist::inputs_for_offset`](./struct.Polylist.html#method.inputs_for_offset) for
+    /// more information.
+    pub fn inputs_for_offset<'a>(&'a self, offset: usize) -> InputsForOffset<'a> {
+        InputsForOffset {
+            inputs: self.inputs.iter(),
+            offset,
+        }
+    }
+}
+
+impl<'a> ::std::iter::IntoIterator for &'a Lines {
+    type Item = Polygon<'a>;
+    type IntoIter = FixedPolygonIter<'a>;
+
+    fn into_iter(self) -> FixedPolygonIter<'a> {
+        self.iter()
+    }
+}
+
+/// A list of connected line strips.
+///
+/// Each line strip is stored in its own `<p>` element. A strip with `n` vertices is made up of
+/// `n - 1` line segments, each connecting a vertex to the one after it.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "linestrips"]
+pub struct Linestrips {
+    /// A human-friendly name for this line strip list.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The number of line strips in the line strip list.
+    #[attribute]
+    pub count: usize,
+
+    /// The name of the material associated with this line strip list.
+    ///
+    /// This name is bound to a material at the time of instantiaion. See [`InstanceGeometry`]
+    /// and [`BindMaterial`].
+    ///
+    /// If `None`, then the lighting and shading results are appplication-defined.
+    ///
+    /// [`InstanceGeometry`]: ./struct.InstanceGeometry.html
+    /// [`BindMaterial`]: ./struct.BindMaterial.html
+    #[attribute]
+    pub material: Option<String>,
+
+    /// The input data for the line strip list.
+    #[child]
+    pub inputs: Vec<SharedInput>,
+
+    /// One list of vertex attribute indices for each line strip.
+    #[child]
+    pub primitives: Vec<Primitives>,
+
+    /// Arbitrary additional information about this line strip list and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Linestrips {
+    /// Returns an iterator over the line strips, each yielded as a
+    /// [`Polygon`](./struct.Polygon.html) containing the connected vertices of the strip.
+    pub fn iter<'a>(&'a self) -> PrimitivesIter<'a> {
+        PrimitivesIter {
+            iter: self.primitives.iter(),
+            stride: vertex_stride(&self.inputs),
+        }
+    }
+
+    /// Returns an iterator over the line segments of all of the strips, each yielded as a
+    /// [`LineSegment`](./struct.LineSegment.html).
+    ///
+    /// Each strip with `n` vertices is expanded into its `n - 1` segments, so consecutive
+    /// segments of a strip share a vertex. Strips with fewer than two vertices don't yield any
+    /// segments.
+    pub fn segments<'a>(&'a self) -> LineSegmentIter<'a> {
+        LineSegmentIter {
+            strips: self.primitives.iter(),
+            current: &[],
+            stride: vertex_stride(&self.inputs),
+            step: 1,
+        }
+    }
+
+    /// Returns an iterator over all of the vertices in the strips, in document order.
+    ///
+    /// Vertices shared by more than one strip are yielded once for each strip, so this is mostly
+    /// useful for treating the strips as a point cloud.
+    pub fn vertices<'a>(&'a self) -> Box<Iterator<Item = Vertex<'a>> + 'a> {
+        Box::new(self.iter().flatten())
+    }
+
+    /// Returns the number of line strips in the line strip list.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns an iterator yielding all inputs that match `offset`.
+    ///
+    /// See [`Polylist::inputs_for_offset`](./struct.Polylist.html#method.inputs_for_offset) for
+    /// more information.
+    pub fn inputs_for_offset<'a>(&'a self, offset: usize) -> InputsForOffset<'a> {
+        InputsForOffset {
+            inputs: self.inputs.iter(),
+            offset,
+        }
+    }
+}
+
+impl<'a> ::std::iter::IntoIterator for &'a Linestrips {
+    type Item = Polygon<'a>;
+    type IntoIter = PrimitivesIter<'a>;
+
+    fn into_iter(self) -> PrimitivesIter<'a> {
+        self.iter()
+    }
+}
+
+/// Positions and orients a node so that it looks from one point towards another.
+///
+/// Contains 9 values: the position of the eye, the point of interest being looked at, and the
+/// direction that's up, in that order.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "lookat"]
+pub struct LookAt {
+    /// An identifier for the transform, so that it can be targeted by animations.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The eye position, interest point, and up direction.
+    #[text]
+    pub values: Vec<f32>,
+}
+
+impl LookAt {
+    /// Returns the eye position, interest point, and up direction.
+    ///
+    /// Returns `None` if the element doesn't contain exactly 9 values.
+    pub fn parts(&self) -> Option<([f32; 3], [f32; 3], [f32; 3])> {
+        match *self.values {
+            [ex, ey, ez, ix, iy, iz, ux, uy, uz] => Some(([ex, ey, ez], [ix, iy, iz], [ux, uy, uz])),
+            _ => None,
+        }
+    }
+
+    /// Returns the transform as a 4x4 matrix in row-major order.
+    ///
+    /// The node is placed at the eye position with its local negative z axis pointing towards
+    /// the interest point.
+    pub fn matrix(&self) -> Option<[f32; 16]> {
+        let (eye, interest, up) = self.parts()?;
+        let forward = normalize(sub(interest, eye));
+        let side = normalize(cross(forward, up));
+        let up = cross(side, forward);
+        Some([
+            side[0], up[0], -forward[0], eye[0],
+            side[1], up[1], -forward[1], eye[1],
+            side[2], up[2], -forward[2], eye[2],
+            0.0, 0.0, 0.0, 1.0,
+        ])
+    }
+}
+
+targetable_float! {
+    /// The total mass of a rigid body or shape.
+    pub struct Mass, "mass"
+}
+
+/// The center of mass and principal axes of inertia of a rigid body.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "mass_frame"]
+pub struct MassFrame {
+    /// The transforms positioning the frame, in the order they appear in the document.
+    ///
+    /// There will always be at least one transform.
+    #[child]
+    #[required]
+    pub transforms: Vec<RigidTransform>,
+}
+
+/// Describes the look of a surface by instantiating an [`Effect`].
+///
+/// Geometry refers to materials by symbol, and the symbols are bound to materials when the
+/// geometry is instantiated in a scene.
+///
+/// [`Effect`]: ./struct.Effect.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "material"]
+pub struct Material {
+    /// A unique identifier for the material.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this material.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about the material and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The effect that the material instantiates.
+    #[child]
+    pub instance_effect: InstanceEffect,
+
+    /// Arbitrary additional information about this material and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// A transform given directly as a 4x4 matrix.
+///
+/// Contains the 16 values of the matrix in row-major order.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "matrix"]
+pub struct Matrix {
+    /// An identifier for the transform, so that it can be targeted by animations.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The values of the matrix.
+    #[text]
+    pub values: Vec<f32>,
+}
+
+impl Matrix {
+    /// Returns the transform as a 4x4 matrix in row-major order.
+    ///
+    /// Returns `None` if the element doesn't contain exactly 16 values.
+    pub fn matrix(&self) -> Option<[f32; 16]> {
+        if self.values.len() != 16 {
+            return None;
+        }
+
+        let mut matrix = [0.0; 16];
+        matrix.copy_from_slice(&self.values);
+        Some(matrix)
+    }
+}
+
+targetable_vector! {
+    /// The upper limits of a rigid constraint's degrees of freedom along or around each axis.
+    pub struct Max, "max"
+}
+
+/// Describes basic geometric meshes using vertex and primitive information.
+///
+/// Meshes embody a general form of geometric description that primarily includes vertex and
+/// primitive information. Vertex information is the set of attributes associated with a poin on
+/// the surface of the mesh. Each vertex includes data for attributes such as:
+///
+/// * Vertex position
+/// * Vertex color
+/// * Vertex normal
+/// * Vertex texture coordinate
+///
+/// The mesh also includes a description of how the vertices are organized to form the geometric
+/// shape of the mesh. The mesh vertices are collated into geometric primitives such as polygons,
+/// triangles, or lines.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "mesh"]
+pub struct Mesh {
+    /// One or more [`Source`] instances containing the raw mesh data.
+    ///
+    /// These contain the raw data used to specify the vertex attributes of the vertices in the
+    /// mesh. The primitives in `primitives` will index into these sources to specify the mesh.
+    ///
+    /// [`Source`]: ./struct.Source.html
+    #[child]
+    #[required]
+    pub sources: Vec<Source>,
+
+    /// Describes the mesh's vertex attributes.
+    ///
+    /// `vertices` will have the [`UnsharedInput`] which specifies the "POSITION" attribute for
+    /// the mesh's vertices. It may also specify other mesh attributes.
+    ///
+    /// [`UnsharedInput`]: ./struct.UnsharedInput.html
+    #[child]
+    pub vertices: Vertices,
+
+    /// Geometric primitives that assemble values from the inputs into vertex attribute data.
+    #[child]
+    pub primitives: Vec<Primitive>,
+
+    /// Arbitrary additional information about this geometry instance and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Mesh {
+    /// Returns the source which matches `id`, or `None` if no sources match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![allow(unused_variables)]
+    /// # use std::fs::File;
+    /// # use collaborate::v1_4::Collada;
+    /// # let file = File::open("resources/blender_cube.dae").unwrap();
+    /// # let document = Collada::read(file).unwrap();
+    /// # let library = document.libraries[5].as_library_geometries().unwrap();
+    /// let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    /// let positions_source = mesh.find_source("Cube-mesh-positions");
+    /// assert!(positions_source.is_some());
+    /// ```
+    pub fn find_source<'a>(&'a self, id: &str) -> Option<&'a Source> {
+        self.sources.iter().find(|source| source.id == id)
+    }
+
+    /// Returns an iterator over the primitives in the mesh.
+    pub fn primitives<'a>(&'a self) -> ::std::slice::Iter<'a, Primitive> {
+        self.primitives.iter()
+    }
+
+    /// Groups the mesh's sources by the array they access.
+    ///
+    /// Returns one [`ArrayLayout`] for each distinct array accessed by the sources in the mesh,
+    /// in the order the arrays are first accessed. Use [`ArrayLayout::interleaved_stride`] to
+    /// detect when multiple sources access a single interleaved array. Sources that don't have a
+    /// common accessor are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use collaborate::v1_4::Collada;
+    /// # let file = File::open("resources/blender_cube.dae").unwrap();
+    /// # let document = Collada::read(file).unwrap();
+    /// # let library = document.libraries[5].as_library_geometries().unwrap();
+    /// let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    /// for layout in mesh.array_layouts() {
+    ///     if let Some(stride) = layout.interleaved_stride() {
+    ///         println!("{:?} is interleaved with stride {}", layout.array_uri, stride);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`ArrayLayout`]: ./struct.ArrayLayout.html
+    /// [`ArrayLayout::interleaved_stride`]: ./struct.ArrayLayout.html#method.interleaved_stride
+    pub fn array_layouts<'a>(&'a self) -> Vec<ArrayLayout<'a>> {
+        let mut layouts: Vec<ArrayLayout<'a>> = Vec::new();
+        for source in &self.sources {
+            let accessor = match source.common_accessor() {
+                Some(accessor) => accessor,
+                None => continue,
+            };
+
+            let view = ArrayView { source, accessor };
+            if let Some(layout) = layouts.iter_mut().find(|layout| *layout.array_uri == accessor.source) {
+                layout.views.push(view);
+                continue;
+            }
+
+            let array = accessor.source.local_id()
+                .and_then(|id| {
+                    self.sources.iter()
+                        .filter_map(|source| source.array.as_ref())
+                        .find(|array| array.id() == Some(id))
+                });
+
+            layouts.push(ArrayLayout {
+                array_uri: &accessor.source,
+                array,
+                views: vec![view],
+            });
+        }
+
+        layouts
+    }
+
+    /// Returns the inputs of `primitive`, with its `VERTEX` input replaced by the inputs of the
+    /// mesh's [`Vertices`].
+    ///
+    /// The inputs taken from `<vertices>` (e.g. `POSITION`, but also any `NORMAL` or `COLOR`
+    /// bound at the vertex level) are read with the `VERTEX` input's offset. If the primitive
+    /// declares an input with the same semantic and set as one of the vertex-level inputs, the
+    /// primitive's input takes precedence and the vertex-level input is left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use collaborate::common::Semantic;
+    /// # use collaborate::v1_4::Collada;
+    /// # let file = File::open("resources/blender_cube.dae").unwrap();
+    /// # let document = Collada::read(file).unwrap();
+    /// # let library = document.libraries[5].as_library_geometries().unwrap();
+    /// let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    /// let inputs = mesh.resolved_inputs(&mesh.primitives[0]);
+    /// let semantics: Vec<_> = inputs.iter().map(|input| input.semantic).collect();
+    /// assert_eq!(vec![&Semantic::Position, &Semantic::Normal], semantics);
+    /// assert!(inputs[0].per_vertex);
+    /// ```
+    ///
+    /// [`Vertices`]: ./struct.Vertices.html
+    pub fn resolved_inputs<'a>(&'a self, primitive: &'a Primitive) -> Vec<ResolvedInput<'a>> {
+        let inputs = primitive.inputs();
+        let mut resolved = Vec::new();
+        for input in inputs {
+            if input.semantic != Semantic::Vertex {
+                resolved.push(ResolvedInput {
+                    semantic: &input.semantic,
+                    offset: input.offset,
+                    set: input.set_index(),
+                    source: &input.source,
+                    per_vertex: false,
+                });
+                continue;
+            }
+
+            if input.source.id() != self.vertices.id {
+                continue;
+            }
+
+            for vertex_input in &self.vertices.inputs {
+                let set = vertex_input.semantic.set_index().or(input.set);
+                let overridden = inputs.iter()
+                    .any(|other| other.semantic == vertex_input.semantic && other.set_index() == set);
+                if overridden {
+                    continue;
+                }
+
+                resolved.push(ResolvedInput {
+                    semantic: &vertex_input.semantic,
+                    offset: input.offset,
+                    set,
+                    source: &vertex_input.source,
+                    per_vertex: true,
+                });
+            }
+        }
+
+        resolved
+    }
+
+    /// Reads the values of each of the [`resolved_inputs`](#method.resolved_inputs) of
+    /// `primitive` for every vertex of the primitive, in the order given by
+    /// [`Primitive::vertices`](./enum.Primitive.html#method.vertices).
+    ///
+    /// This produces one flat stream per input (positions, normals, texture coordinates, and so
+    /// on) that can be uploaded to a GPU directly, with vertex-level and primitive-level inputs
+    /// merged.
+    ///
+    /// Returns `None` if the source of any input isn't in this mesh, isn't a float array with a
+    /// common accessor, or fails to decode, or if any vertex index is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use collaborate::v1_4::Collada;
+    /// # let file = File::open("resources/blender_cube.dae").unwrap();
+    /// # let document = Collada::read(file).unwrap();
+    /// # let library = document.libraries[5].as_library_geometries().unwrap();
+    /// let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    /// let streams = mesh.vertex_streams(&mesh.primitives[0]).unwrap();
+    /// let positions = &streams[0];
+    /// assert_eq!(3, positions.width);
+    /// assert_eq!(positions.values.len() / 3, mesh.primitives[0].vertices().count());
+    /// ```
+    pub fn vertex_streams<'a>(&'a self, primitive: &'a Primitive) -> Option<Vec<VertexStream<'a>>> {
+        self.resolved_inputs(primitive)
+            .into_iter()
+            .map(|input| {
+                let source = self.find_source(input.source.id())?;
+                let accessor = source.common_accessor()?;
+                let array_id = accessor.source.local_id()?;
+                let array = self.sources.iter()
+                    .filter_map(|source| source.array.as_ref())
+                    .find(|array| array.id() == Some(array_id))?
+                    .as_float_array()?
+                    .data
+                    .get()
+                    .ok()?;
+
+                let width = ArrayView { source, accessor }.width();
+                let mut values = Vec::new();
+                for vertex in primitive.vertices() {
+                    let index = vertex.index(input.offset)?;
+                    if index >= accessor.count {
+                        return None;
+                    }
+
+                    let start = accessor.offset + accessor.stride * index;
+                    values.extend_from_slice(array.get(start..start + width)?);
+                }
+
+                Some(VertexStream { input, width, values })
+            })
+            .collect()
+    }
+}
+
+targetable_vector! {
+    /// The lower limits of a rigid constraint's degrees of freedom along or around each axis.
+    pub struct Min, "min"
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "mipmap_generate"]
+pub struct MipmapGenerate;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "morph"]
+pub struct Morph;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "Name_array"]
+pub struct NameArray {
+    #[attribute]
+    pub count: usize,
+
+    #[attribute]
+    pub id: Option<String>,
+
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The names in the array.
+    #[text(count = "count", split_with = "::utils::split_xml_list")]
+    pub data: Vec<String>,
+}
+
+/// Declares a parameter of an effect, which can then be referenced by sid, e.g. by a
+/// [`ParamRef`] or a [`Texture`].
+///
+/// [`ParamRef`]: ./struct.ParamRef.html
+/// [`Texture`]: ./struct.Texture.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "newparam"]
+pub struct NewParam {
+    /// The identifier of the parameter within the effect.
+    #[attribute]
+    pub sid: String,
+
+    #[child]
+    pub annotations: Vec<Annotate>,
+
+    /// A semantic for binding the parameter to a value provided by the application, e.g.
+    /// `"WORLDVIEWPROJECTION"`.
+    #[child]
+    pub semantic: Option<String>,
+
+    /// A storage modifier for the parameter, e.g. `"CONST"`.
+    #[child]
+    pub modifier: Option<String>,
+
+    /// The type and value of the parameter.
+    #[child]
+    pub value: ParamValue,
+}
+
+/// A point in the scene graph of a [`VisualScene`].
+///
+/// A node places the cameras, controllers, geometry, and lights it instantiates into the scene,
+/// transformed by its `transforms` and by the transforms of its ancestors. Child nodes are
+/// nested within their parent, forming a hierarchy.
+///
+/// [`VisualScene`]: ./struct.VisualScene.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "node"]
+pub struct Node {
+    /// A unique identifier for the node.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this node.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// An identifier for the node that's unique within its parent.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// Whether the node is a regular node or a joint of a skeleton.
+    #[attribute]
+    #[name = "type"]
+    #[optional_with_default]
+    pub node_type: NodeType,
+
+    /// The names of the layers the node belongs to, separated by whitespace.
+    ///
+    /// Use [`layers`](#method.layers) to iterate over the individual names.
+    #[attribute]
+    pub layer: Option<String>,
+
+    /// Attributes that aren't part of the specification, in the order they appear, e.g. the
+    /// handles some exporters use to match nodes back up with the objects in their scenes.
+    ///
+    /// These are written back out along with the declarations of their namespaces.
+    #[any_attribute]
+    pub other_attributes: Vec<(OwnedName, String)>,
+
+    /// Metadata about the node and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The transforms of the node, in the order they appear in the document.
+    ///
+    /// Use [`local_matrix`](#method.local_matrix) to combine them into a single matrix.
+    #[child]
+    pub transforms: Vec<Transform>,
+
+    /// The cameras placed in the scene by this node.
+    #[child]
+    pub instance_cameras: Vec<InstanceCamera>,
+
+    /// The controllers placed in the scene by this node.
+    #[child]
+    pub instance_controllers: Vec<InstanceController>,
+
+    /// The geometry placed in the scene by this node.
+    #[child]
+    pub instance_geometries: Vec<InstanceGeometry>,
+
+    /// The lights placed in the scene by this node.
+    #[child]
+    pub instance_lights: Vec<InstanceLight>,
+
+    /// The nodes from elsewhere in the document that are instantiated as children of this node.
+    #[child]
+    pub instance_nodes: Vec<InstanceNode>,
+
+    /// The child nodes of this node.
+    #[child]
+    pub nodes: Vec<Node>,
+
+    /// Arbitrary additional information about this node and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Node {
+    /// Returns the transform or node within this node with the given sid, if there is one.
+    ///
+    /// As the specification requires, the search is breadth-first: the transforms and child
+    /// nodes of this node are searched before those of its descendants.
+    pub fn find_sid<'a>(&'a self, sid: &str) -> Option<SidTarget<'a>> {
+        let mut level = vec![self];
+        while !level.is_empty() {
+            for node in &level {
+                if let Some(transform) = node.transforms.iter().find(|transform| transform.sid() == Some(sid)) {
+                    return Some(SidTarget::Transform(transform));
+                }
+
+                if let Some(child) = node.nodes.iter().find(|child| child.sid.as_deref() == Some(sid)) {
+                    return Some(SidTarget::Node(child));
+                }
+            }
+
+            level = level.into_iter().flat_map(|node| node.nodes.iter()).collect();
+        }
+
+        None
+    }
+
+    /// Returns an iterator over the names of the layers the node belongs to.
+    pub fn layers<'a>(&'a self) -> ::std::str::SplitWhitespace<'a> {
+        self.layer.as_deref().unwrap_or("").split_whitespace()
+    }
+
+    /// Returns the node's transforms combined into a single 4x4 matrix in row-major order.
+    ///
+    /// The transforms are applied in document order, so the last transform is the one applied
+    /// to the node's contents first. A node without any transforms has the identity matrix.
+    ///
+    /// Returns `None` if any of the transforms is malformed.
+    pub fn local_matrix(&self) -> Option<[f32; 16]> {
+        let mut matrix = IDENTITY;
+        for transform in &self.transforms {
+            matrix = multiply(&matrix, &transform.matrix()?);
+        }
+        Some(matrix)
+    }
+
+    /// Returns this node or one of its descendants with the given id.
+    ///
+    /// Only the nodes nested within this node are searched, nodes instantiated with
+    /// `<instance_node>` are not followed.
+    pub fn find(&self, id: &str) -> Option<&Node> {
+        if self.id.as_deref() == Some(id) {
+            return Some(self);
+        }
+
+        self.nodes.iter().filter_map(|node| node.find(id)).next()
+    }
+
+    /// Pushes the nodes from this node down to `target` onto `path`, if `target` is this node or
+    /// one of its descendants.
+    ///
+    /// Nodes are compared by address rather than by value, so `target` must be borrowed from the
+    /// same document.
+    fn path_to<'a>(&'a self, target: &Node, path: &mut Vec<&'a Node>) -> bool {
+        path.push(self);
+        if ::std::ptr::eq(self, target) || self.nodes.iter().any(|node| node.path_to(target, path)) {
+            return true;
+        }
+
+        path.pop();
+        false
+    }
+}
+
+/// An iterator over the nodes of a [`VisualScene`] and their ancestors.
+///
+/// See [`VisualScene::iter_nodes`](./struct.VisualScene.html#method.iter_nodes).
+///
+/// [`VisualScene`]: ./struct.VisualScene.html
+#[derive(Debug, Clone)]
+pub struct NodeIter<'a> {
+    /// The nodes left to visit at each level of the hierarchy, starting with the root nodes.
+    levels: Vec<::std::slice::Iter<'a, Node>>,
+
+    /// The ancestors of the nodes at the innermost level.
+    ancestors: Vec<&'a Node>,
+}
+
+impl<'a> Iterator for NodeIter<'a> {
+    type Item = (&'a Node, Ancestry<'a>);
+
+    fn next(&mut self) -> Option<(&'a Node, Ancestry<'a>)> {
+        loop {
+            match self.levels.last_mut()?.next() {
+                Some(node) => {
+                    let ancestry = Ancestry { nodes: self.ancestors.clone() };
+                    self.ancestors.push(node);
+                    self.levels.push(node.nodes.iter());
+                    return Some((node, ancestry));
+                }
+
+                None => {
+                    self.levels.pop();
+                    self.ancestors.pop();
+                }
+            }
+        }
+    }
+}
+
+/// The type of a [`Node`](./struct.Node.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeType {
+    /// A regular node in the scene.
+    Node,
+
+    /// A joint of a skeleton, which can be bound to a skinned mesh.
+    Joint,
+}
+
+impl Default for NodeType {
+    fn default() -> NodeType { NodeType::Node }
+}
+
+impl ::std::str::FromStr for NodeType {
+    type Err = ErrorKind;
+
+    fn from_str(source: &str) -> ::std::result::Result<NodeType, ErrorKind> {
+        match source {
+            "NODE" => Ok(NodeType::Node),
+            "JOINT" => Ok(NodeType::Joint),
+            _ => Err(ErrorKind::InvalidValue {
+                element: "node",
+                value: source.into(),
+            }),
+        }
+    }
+}
+
+impl WriteValue for NodeType {
+    fn write_value(&self, output: &mut String, _: &WriteOptions) {
+        output.push_str(match *self {
+            NodeType::Node => "NODE",
+            NodeType::Joint => "JOINT",
+        });
+    }
+}
+
+/// How the transparency of a [`Transparent`] color or texture is encoded.
+///
+/// [`Transparent`]: ./struct.Transparent.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opaque {
+    /// Transparency is taken from the alpha channel, where `1.0` is opaque.
+    AOne,
+
+    /// Transparency is taken from the red, green, and blue channels, where `0.0` is opaque.
+    RgbZero,
+
+    /// Transparency is taken from the alpha channel, where `0.0` is opaque.
+    AZero,
+
+    /// Transparency is taken from the red, green, and blue channels, where `1.0` is opaque.
+    RgbOne,
+}
+
+impl Opaque {
+    /// Returns the string used to represent this mode in a COLLADA document.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Opaque::AOne => "A_ONE",
+            Opaque::RgbZero => "RGB_ZERO",
+            Opaque::AZero => "A_ZERO",
+            Opaque::RgbOne => "RGB_ONE",
+        }
+    }
+
+    /// Returns `true` if the transparency is taken from the alpha channel.
+    pub fn uses_alpha(self) -> bool {
+        match self {
+            Opaque::AOne | Opaque::AZero => true,
+            Opaque::RgbZero | Opaque::RgbOne => false,
+        }
+    }
+
+    /// Computes the opacity of the red, green, and blue channels of a surface.
+    ///
+    /// `transparent` is the red, green, blue, and alpha components of the transparent color
+    /// (or of a sample of the transparent texture), and `transparency` is the scalar
+    /// transparency of the shading model. Each resulting opacity is the weight given to the
+    /// surface's color when blending, where `1.0` is opaque.
+    ///
+    /// ```
+    /// use collaborate::v1_4::Opaque;
+    ///
+    /// assert_eq!([0.25; 3], Opaque::AOne.opacity([0.0, 0.0, 0.0, 0.5], 0.5));
+    /// assert_eq!([0.75; 3], Opaque::AZero.opacity([0.0, 0.0, 0.0, 0.5], 0.5));
+    /// assert_eq!([1.0, 0.5, 0.0], Opaque::RgbZero.opacity([0.0, 0.5, 1.0, 1.0], 1.0));
+    /// assert_eq!([0.0, 0.5, 1.0], Opaque::RgbOne.opacity([0.0, 0.5, 1.0, 1.0], 1.0));
+    /// ```
+    pub fn opacity(self, transparent: [f32; 4], transparency: f32) -> [f32; 3] {
+        let alpha = transparent[3] * transparency;
+        let rgb = [
+            transparent[0] * transparency,
+            transparent[1] * transparency,
+            transparent[2] * transparency,
+        ];
+        match self {
+            Opaque::AOne => [alpha; 3],
+            Opaque::AZero => [1.0 - alpha; 3],
+            Opaque::RgbZero => [1.0 - rgb[0], 1.0 - rgb[1], 1.0 - rgb[2]],
+            Opaque::RgbOne => rgb,
+        }
+    }
+}
+
+impl Default for Opaque {
+    fn default() -> Opaque { Opaque::AOne }
+}
+
+impl ::std::str::FromStr for Opaque {
+    type Err = ErrorKind;
+
+    fn from_str(source: &str) -> ::std::result::Result<Opaque, ErrorKind> {
+        match source {
+            "A_ONE" => Ok(Opaque::AOne),
+            "RGB_ZERO" => Ok(Opaque::RgbZero),
+            "A_ZERO" => Ok(Opaque::AZero),
+            "RGB_ONE" => Ok(Opaque::RgbOne),
+            _ => Err(ErrorKind::InvalidValue {
+                element: "transparent",
+                value: source.into(),
+            }),
+        }
+    }
+}
+
+impl WriteValue for Opaque {
+    fn write_value(&self, output: &mut String, _: &WriteOptions) {
+        output.push_str(self.as_str());
+    }
+}
+
+/// Describes the field of view and viewing frustum of a [`Camera`].
+///
+/// [`Camera`]: ./struct.Camera.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "optics"]
+pub struct Optics {
+    /// The projection of the camera, which every application is expected to support.
+    #[child(flatten = "technique_common")]
+    pub projection: Projection,
+
+    /// Application-specific descriptions of the camera's optics.
+    #[child]
+    pub techniques: Vec<Technique>,
+
+    /// Arbitrary additional information about the optics.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// An orthographic projection, which has no perspective distortion.
+///
+/// At most two of `xmag`, `ymag`, and `aspect_ratio` are given. Use
+/// [`magnification`](#method.magnification) to get both magnifications regardless of which were
+/// given.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "orthographic"]
+pub struct Orthographic {
+    /// The horizontal magnification of the view.
+    #[child]
+    pub xmag: Option<Xmag>,
+
+    /// The vertical magnification of the view.
+    #[child]
+    pub ymag: Option<Ymag>,
+
+    /// The aspect ratio of the view.
+    #[child]
+    pub aspect_ratio: Option<AspectRatio>,
+
+    /// The distance to the near clipping plane.
+    #[child]
+    pub znear: Znear,
+
+    /// The distance to the far clipping plane.
+    #[child]
+    pub zfar: Zfar,
+}
+
+impl Orthographic {
+    /// Returns the horizontal and vertical magnification of the view, working out whichever one
+    /// is missing from the aspect ratio.
+    ///
+    /// Returns `None` if the magnifications can't be worked out from the values given.
+    pub fn magnification(&self) -> Option<(f32, f32)> {
+        let aspect_ratio = self.aspect_ratio.as_ref().map(|aspect_ratio| aspect_ratio.value);
+        match (self.xmag.as_ref(), self.ymag.as_ref(), aspect_ratio) {
+            (Some(xmag), Some(ymag), _) => Some((xmag.value, ymag.value)),
+            (Some(xmag), None, Some(aspect_ratio)) => Some((xmag.value, xmag.value / aspect_ratio)),
+            (None, Some(ymag), Some(aspect_ratio)) => Some((ymag.value * aspect_ratio, ymag.value)),
+            _ => None,
+        }
+    }
+}
+
+/// Declares parametric information for its parent element.
+///
+/// A functional or programmatical format requires a means for users to specify parametric
+/// information. This information represents function parameter (argument) data.
+///
+/// Material shader programs may contain code representing vertex or pixel programs. These
+/// programs require parameters as part of their state information.
+///
+/// The basic declaration of a parameter describes the name, data type, and value data of the
+/// parameter. That parameter name identifies it to the function or program. The parameter type
+/// indicates the encoding of its value.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "param"]
+pub struct Param {
+    /// The name of the parameter.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The subidentifier of this parameter.
+    ///
+    /// This value is unique within the scope of the parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The type of the value data.
+    ///
+    /// Must be understood by the application consuming the COLLADA document.
+    #[attribute]
+    #[name = "type"]
+    pub data_type: Option<String>,
+
+    /// The user-defined meaning of the parameter.
+    #[attribute]
+    pub semantic: Option<String>,
+}
+
+/// A reference to a parameter of an effect, used in place of a literal value.
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+#[name = "param"]
+pub struct ParamRef {
+    /// The sid of the parameter.
+    #[attribute]
+    #[name = "ref"]
+    pub reference: String,
+}
+
+/// The type and value of an effect parameter, e.g. of a [`NewParam`] or a [`ShaderNewParam`].
+///
+/// Each variant wraps a single value of a given parameter type. The same set of types is used by
+/// every profile, though the specification only allows some of them in a [`ProfileCommon`].
+/// Types that are rarely used outside of Cg (e.g. `half3`, `bool2x2`, and `usertype`) are kept
+/// as a [`RawParamValue`].
+///
+/// [`NewParam`]: ./struct.NewParam.html
+/// [`ShaderNewParam`]: ./struct.ShaderNewParam.html
+/// [`ProfileCommon`]: ./struct.ProfileCommon.html
+/// [`RawParamValue`]: ./struct.RawParamValue.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum ParamValue {
+    Bool(Bool),
+    Bool2(Bool2),
+    Bool3(Bool3),
+    Bool4(Bool4),
+    Int(Int),
+    Int2(Int2),
+    Int3(Int3),
+    Int4(Int4),
+    Float(Float),
+    Float2(Float2),
+    Float3(Float3),
+    Float4(Float4),
+    FloatMatrix(FloatMatrix),
+    Sampler1D(Sampler1D),
+    Sampler2D(Sampler2D),
+    Sampler3D(Sampler3D),
+    SamplerCube(SamplerCube),
+    SamplerRect(SamplerRect),
+    SamplerDepth(SamplerDepth),
+    #[collada(version = "1.4")]
+    Surface(Surface),
+    Enum(Enum),
+    Other(RawParamValue),
+}
+
+impl ParamValue {
+    /// Returns the name of the value's type, e.g. `"float4x4"`.
+    pub fn type_name(&self) -> &str {
+        match *self {
+            ParamValue::Bool(_) => "bool",
+            ParamValue::Bool2(_) => "bool2",
+            ParamValue::Bool3(_) => "bool3",
+            ParamValue::Bool4(_) => "bool4",
+            ParamValue::Int(_) => "int",
+            ParamValue::Int2(_) => "int2",
+            ParamValue::Int3(_) => "int3",
+            ParamValue::Int4(_) => "int4",
+            ParamValue::Float(_) => "float",
+            ParamValue::Float2(_) => "float2",
+            ParamValue::Float3(_) => "float3",
+            ParamValue::Float4(_) => "float4",
+            ParamValue::FloatMatrix(ref matrix) => matrix.type_name(),
+            ParamValue::Sampler1D(_) => "sampler1D",
+            ParamValue::Sampler2D(_) => "sampler2D",
+            ParamValue::Sampler3D(_) => "sampler3D",
+            ParamValue::SamplerCube(_) => "samplerCUBE",
+            ParamValue::SamplerRect(_) => "samplerRECT",
+            ParamValue::SamplerDepth(_) => "samplerDEPTH",
+            ParamValue::Surface(_) => "surface",
+            ParamValue::Enum(_) => "enum",
+            ParamValue::Other(ref other) => other.type_name(),
+        }
+    }
+
+    /// Interprets the value as a list of numbers, e.g. for a `float3` or `int2`.
+    ///
+    /// Returns `None` if the value isn't a scalar, vector, or matrix of numbers.
+    pub fn float_values(&self) -> Option<Vec<f32>> {
+        match *self {
+            ParamValue::Int(ref int) => Some(vec![int.value as f32]),
+            ParamValue::Int2(Int2 { ref values }) |
+            ParamValue::Int3(Int3 { ref values }) |
+            ParamValue::Int4(Int4 { ref values }) => {
+                Some(values.iter().map(|&value| value as f32).collect())
+            }
+            ParamValue::Float(ref float) => Some(vec![float.value]),
+            ParamValue::Float2(Float2 { ref values }) |
+            ParamValue::Float3(Float3 { ref values }) |
+            ParamValue::Float4(Float4 { ref values }) |
+            ParamValue::FloatMatrix(FloatMatrix { ref values, .. }) => Some(values.clone()),
+            ParamValue::Other(ref other) => other.float_values(),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner [`Sampler2D`](./struct.Sampler2D.html) if the parameter is a sampler.
+    pub fn as_sampler_2d(&self) -> Option<&Sampler2D> {
+        match *self {
+            ParamValue::Sampler2D(ref sampler) => Some(sampler),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner [`Surface`](./struct.Surface.html) if the parameter is a surface.
+    pub fn as_surface(&self) -> Option<&Surface> {
+        match *self {
+            ParamValue::Surface(ref surface) => Some(surface),
+            _ => None,
+        }
+    }
+}
+
+/// A single rendering pass of a [`ShaderTechnique`].
+///
+/// A pass selects its render targets, sets up the render states, and binds the shaders used to
+/// draw the geometry.
+///
+/// [`ShaderTechnique`]: ./struct.ShaderTechnique.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "pass"]
+pub struct Pass {
+    /// An identifier for the pass that's unique within its technique.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[child]
+    pub annotations: Vec<Annotate>,
+
+    /// The render targets, buffer clears, and draw instruction of the pass.
+    #[child]
+    pub setup: Vec<PassSetup>,
+
+    /// The render states and shaders of the pass, in document order.
+    #[child]
+    pub states: Vec<PassState>,
+
+    /// Arbitrary additional information about this pass and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Pass {
+    /// Returns an iterator over the render states set by the pass.
+    pub fn render_states<'a>(&'a self) -> Box<Iterator<Item = &'a RenderState> + 'a> {
+        Box::new(self.states.iter().filter_map(PassState::as_render_state))
+    }
+
+    /// Returns the render state with the given name (e.g. `"blend_enable"`), if the pass sets it.
+    ///
+    /// If the state is set more than once, the last value is returned.
+    pub fn render_state(&self, name: &str) -> Option<&RenderState> {
+        self.render_states().filter(|state| state.name == name).last()
+    }
+
+    /// Returns the shader bound to `stage`, if there is one.
+    pub fn shader(&self, stage: ShaderStage) -> Option<&Shader> {
+        self.states.iter()
+            .filter_map(PassState::as_shader)
+            .find(|shader| shader.stage == stage)
+    }
+}
+
+/// A render target, buffer clear, or draw instruction of a [`Pass`], e.g. a `<color_target>`.
+///
+/// These are kept as [`RawElement`]s, since they're rarely used outside of specialized tools.
+///
+/// [`Pass`]: ./struct.Pass.html
+/// [`RawElement`]: ../raw/struct.RawElement.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassSetup {
+    /// The element, e.g. `<color_target index="1">Normals</color_target>`.
+    pub element: RawElement,
+}
+
+impl PassSetup {
+    const NAMES: &'static [&'static str] = &[
+        "color_target", "depth_target", "stencil_target", "color_clear", "depth_clear",
+        "stencil_clear", "draw",
+    ];
+
+    /// The kind of setup, e.g. `"color_target"` or `"draw"`.
+    pub fn name(&self) -> &str {
+        &self.element.name.local_name
+    }
+
+    /// The value of the setup, e.g. the sid of the surface for a render target.
+    pub fn value(&self) -> &str {
+        self.element.text.trim()
+    }
+}
+
+impl ColladaElement for PassSetup {
+    fn name_test(name: &str) -> bool {
+        PassSetup::NAMES.contains(&name)
+    }
+
+    fn parse_element<R>(
+        reader: &mut DocumentReader<R>,
+        element_start: ElementStart,
+    ) -> Result<PassSetup>
+    where
+        R: XmlPull,
+    {
+        utils::parse_raw_element(reader, element_start).map(|element| PassSetup { element })
+    }
+
+    fn add_names(names: &mut Vec<&'static str>) {
+        names.extend_from_slice(PassSetup::NAMES);
+    }
+
+    fn write_element<W: Write>(&self, writer: &mut XmlWriter<W>) -> writer::Result<()> {
+        writer.raw_element(&self.element)
+    }
+}
+
+/// A render state or shader of a [`Pass`](./struct.Pass.html).
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum PassState {
+    RenderState(RenderState),
+    Shader(Shader),
+}
+
+impl PassState {
+    /// Returns the inner [`RenderState`](./struct.RenderState.html) if this is a render state.
+    pub fn as_render_state(&self) -> Option<&RenderState> {
+        match *self {
+            PassState::RenderState(ref state) => Some(state),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner [`Shader`](./struct.Shader.html) if this is a shader.
+    pub fn as_shader(&self) -> Option<&Shader> {
+        match *self {
+            PassState::Shader(ref shader) => Some(shader),
+            _ => None,
+        }
+    }
+}
+
+/// A perspective projection, where objects further from the camera appear smaller.
+///
+/// At most two of `xfov`, `yfov`, and `aspect_ratio` are given. Use
+/// [`field_of_view`](#method.field_of_view) to get both fields of view regardless of which were
+/// given.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "perspective"]
+pub struct Perspective {
+    /// The horizontal field of view in degrees.
+    #[child]
+    pub xfov: Option<Xfov>,
+
+    /// The vertical field of view in degrees.
+    #[child]
+    pub yfov: Option<Yfov>,
+
+    /// The aspect ratio of the field of view.
+    #[child]
+    pub aspect_ratio: Option<AspectRatio>,
+
+    /// The distance to the near clipping plane.
+    #[child]
+    pub znear: Znear,
+
+    /// The distance to the far clipping plane.
+    #[child]
+    pub zfar: Zfar,
+}
+
+impl Perspective {
+    /// Returns the horizontal and vertical field of view in degrees, working out whichever one is
+    /// missing from the aspect ratio.
+    ///
+    /// The aspect ratio is the ratio of the width to the height of the view, so the fields of
+    /// view are related through their tangents rather than directly.
+    ///
+    /// Returns `None` if the fields of view can't be worked out from the values given.
+    pub fn field_of_view(&self) -> Option<(f32, f32)> {
+        let aspect_ratio = self.aspect_ratio.as_ref().map(|aspect_ratio| aspect_ratio.value);
+        let scale = |fov: f32, scale: f32| ((fov.to_radians() / 2.0).tan() * scale).atan().to_degrees() * 2.0;
+        match (self.xfov.as_ref(), self.yfov.as_ref(), aspect_ratio) {
+            (Some(xfov), Some(yfov), _) => Some((xfov.value, yfov.value)),
+            (Some(xfov), None, Some(aspect_ratio)) => Some((xfov.value, scale(xfov.value, 1.0 / aspect_ratio))),
+            (None, Some(yfov), Some(aspect_ratio)) => Some((scale(yfov.value, aspect_ratio), yfov.value)),
+            _ => None,
+        }
+    }
+}
+
+/// Produces a shaded surface using the Phong lighting model, with specular highlights.
+#[derive(Debug, Clone, Default, PartialEq, ColladaElement)]
+#[name = "phong"]
+pub struct Phong {
+    /// The amount of light emitted from the surface.
+    #[child(flatten = "emission")]
+    pub emission: Option<ColorOrTexture>,
+
+    /// The amount of ambient light reflected from the surface.
+    #[child(flatten = "ambient")]
+    pub ambient: Option<ColorOrTexture>,
+
+    /// The amount of light diffusely reflected from the surface.
+    #[child(flatten = "diffuse")]
+    pub diffuse: Option<ColorOrTexture>,
+
+    /// The color of light reflected specularly from the surface.
+    #[child(flatten = "specular")]
+    pub specular: Option<ColorOrTexture>,
+
+    /// The specular exponent, i.e. the sharpness of the specular highlight.
+    #[child(flatten = "shininess")]
+    pub shininess: Option<FloatOrParam>,
+
+    /// The color of a perfect mirror reflection.
+    #[child(flatten = "reflective")]
+    pub reflective: Option<ColorOrTexture>,
+
+    /// The amount of perfect mirror reflection to be added to the reflected light.
+    #[child(flatten = "reflectivity")]
+    pub reflectivity: Option<FloatOrParam>,
+
+    /// The color of perfectly refracted light.
+    #[child]
+    pub transparent: Option<Transparent>,
+
+    /// The amount of perfectly refracted light added to the reflected color.
+    #[child(flatten = "transparency")]
+    pub transparency: Option<FloatOrParam>,
+
+    /// The index of refraction for perfectly refracted light.
+    #[child(flatten = "index_of_refraction")]
+    pub index_of_refraction: Option<FloatOrParam>,
+}
+
+/// Declares the surface properties of a rigid body or shape, used when simulating collisions.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "physics_material"]
+pub struct PhysicsMaterial {
+    /// A unique identifier for the physics material.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this physics material.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about the physics material and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The surface properties, which every application is expected to support.
+    #[child]
+    pub technique_common: PhysicsMaterialTechniqueCommon,
+
+    /// Application-specific surface properties.
+    #[child]
+    pub techniques: Vec<Technique>,
+
+    /// Arbitrary additional information about this physics material and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl PhysicsMaterial {
+    /// Returns the dynamic friction, which defaults to 0.
+    pub fn dynamic_friction(&self) -> f32 {
+        self.technique_common.dynamic_friction.as_ref().map_or(0.0, |friction| friction.value)
+    }
+
+    /// Returns the restitution, which defaults to 0.
+    pub fn restitution(&self) -> f32 {
+        self.technique_common.restitution.as_ref().map_or(0.0, |restitution| restitution.value)
+    }
+
+    /// Returns the static friction, which defaults to 0.
+    pub fn static_friction(&self) -> f32 {
+        self.technique_common.static_friction.as_ref().map_or(0.0, |friction| friction.value)
+    }
+}
+
+/// The physics material of a rigid body or shape.
+///
+/// The material is either instantiated from a `<library_physics_materials>` or declared in place.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum PhysicsMaterialSource {
+    Instance(InstancePhysicsMaterial),
+    Declared(PhysicsMaterial),
+}
+
+/// The common technique of a [`PhysicsMaterial`](./struct.PhysicsMaterial.html).
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "technique_common"]
+pub struct PhysicsMaterialTechniqueCommon {
+    /// The friction while sliding against another surface.
+    #[child]
+    pub dynamic_friction: Option<DynamicFriction>,
+
+    /// How much energy is kept in a collision.
+    #[child]
+    pub restitution: Option<Restitution>,
+
+    /// The friction that has to be overcome before sliding against another surface.
+    #[child]
+    pub static_friction: Option<StaticFriction>,
+}
+
+/// Declares a collection of rigid bodies and the constraints between them, which can be
+/// instantiated in a [`PhysicsScene`].
+///
+/// [`PhysicsScene`]: ./struct.PhysicsScene.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "physics_model"]
+pub struct PhysicsModel {
+    /// A unique identifier for the physics model.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this physics model.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about the physics model and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The rigid bodies of the model.
+    #[child]
+    pub rigid_bodies: Vec<RigidBody>,
+
+    /// The constraints between the model's rigid bodies.
+    #[child]
+    pub rigid_constraints: Vec<RigidConstraint>,
+
+    /// Other physics models that are part of this model.
+    #[child]
+    pub instance_physics_models: Vec<InstancePhysicsModel>,
+
+    /// Arbitrary additional information about this physics model and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl PhysicsModel {
+    /// Returns the rigid body with the given sid, if it's in this model.
+    pub fn rigid_body(&self, sid: &str) -> Option<&RigidBody> {
+        self.rigid_bodies.iter().find(|rigid_body| rigid_body.sid == sid)
+    }
+
+    /// Returns the rigid constraint with the given sid, if it's in this model.
+    pub fn rigid_constraint(&self, sid: &str) -> Option<&RigidConstraint> {
+        self.rigid_constraints.iter().find(|rigid_constraint| rigid_constraint.sid == sid)
+    }
+}
+
+/// Declares a physics simulation, made up of instantiated physics models and force fields.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "physics_scene"]
+pub struct PhysicsScene {
+    /// A unique identifier for the physics scene.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this physics scene.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about the physics scene and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// Force fields that affect every physics model in the scene.
+    #[child]
+    pub instance_force_fields: Vec<InstanceForceField>,
+
+    /// The physics models being simulated.
+    #[child]
+    pub instance_physics_models: Vec<InstancePhysicsModel>,
+
+    /// The simulation parameters, which every application is expected to support.
+    #[child]
+    pub technique_common: PhysicsSceneTechniqueCommon,
+
+    /// Application-specific simulation parameters.
+    #[child]
+    pub techniques: Vec<Technique>,
+
+    /// Arbitrary additional information about this physics scene and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl PhysicsScene {
+    /// Returns the gravity applied to the scene, if one was given.
+    pub fn gravity(&self) -> Option<[f32; 3]> {
+        match self.technique_common.gravity.as_ref().map(|gravity| &*gravity.values) {
+            Some(&[x, y, z]) => Some([x, y, z]),
+            _ => None,
+        }
+    }
+
+    /// Returns the length of time simulated by each step, if one was given.
+    pub fn time_step(&self) -> Option<f32> {
+        self.technique_common.time_step.as_ref().map(|time_step| time_step.value)
+    }
+}
+
+/// The common technique of a [`PhysicsScene`](./struct.PhysicsScene.html).
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "technique_common"]
+pub struct PhysicsSceneTechniqueCommon {
+    /// The gravity applied to every rigid body in the scene.
+    #[child]
+    pub gravity: Option<Gravity>,
+
+    /// The length of time simulated by each step.
+    #[child]
+    pub time_step: Option<TimeStep>,
+}
+
+/// An infinite plane shape.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "plane"]
+pub struct Plane {
+    /// The coefficients of the plane equation.
+    #[child]
+    pub equation: Equation,
+
+    /// Arbitrary additional information about this shape and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// A light that shines equally in all directions from a single point, e.g. a light bulb.
+///
+/// The intensity of the light is attenuated by its distance `d` from the light:
+/// `1 / (constant_attenuation + linear_attenuation * d + quadratic_attenuation * d * d)`.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "point"]
+pub struct Point {
+    /// The color of the light.
+    #[child]
+    pub color: Color,
+
+    /// The constant attenuation factor, which defaults to 1.
+    #[child]
+    pub constant_attenuation: Option<ConstantAttenuation>,
+
+    /// The linear attenuation factor, which defaults to 0.
+    #[child]
+    pub linear_attenuation: Option<LinearAttenuation>,
+
+    /// The quadratic attenuation factor, which defaults to 0.
+    #[child]
+    pub quadratic_attenuation: Option<QuadraticAttenuation>,
+}
+
+impl Point {
+    /// Returns the constant, linear, and quadratic attenuation factors, filling in the defaults
+    /// for any that weren't given.
+    pub fn attenuation(&self) -> (f32, f32, f32) {
+        (
+            self.constant_attenuation.as_ref().map_or(1.0, |constant| constant.value),
+            self.linear_attenuation.as_ref().map_or(0.0, |linear| linear.value),
+            self.quadratic_attenuation.as_ref().map_or(0.0, |quadratic| quadratic.value),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Polygon<'a> {
+    len: usize,
+    chunks: ::std::slice::Chunks<'a, usize>,
+}
+
+impl<'a> Polygon<'a> {
+    fn from_indices(indices: &'a [usize], stride: usize) -> Polygon<'a> {
+        Polygon {
+            len: indices.len() / stride,
+            chunks: indices.chunks(stride),
+        }
+    }
+
+    pub fn iter(&self) -> PolygonIter<'a> {
+        PolygonIter { chunks: self.chunks.clone() }
+    }
+
+    /// Returns the number of vertices in this polygon.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a> ::std::iter::IntoIterator for Polygon<'a> {
+    type Item = Vertex<'a>;
+    type IntoIter = PolygonIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        PolygonIter { chunks: self.chunks }
+    }
+}
+
+impl<'a> ::std::iter::IntoIterator for &'a Polygon<'a> {
+    type Item = Vertex<'a>;
+    type IntoIter = PolygonIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        PolygonIter { chunks: self.chunks.clone() }
+    }
+}
+
+pub struct PolygonIter<'a> {
+    chunks: ::std::slice::Chunks<'a, usize>,
+}
+
+impl<'a> ::std::iter::Iterator for PolygonIter<'a> {
+    type Item = Vertex<'a>;
+
+    fn next(&mut self) -> Option<Vertex<'a>> {
+        self.chunks.next().map(|attributes| Vertex { attributes })
+    }
+}
+
+/// A list of polygons, which may contain holes.
+///
+/// Each polygon is stored in its own `<p>` element, or in a `<ph>` element along with its holes.
+/// Use [`Polylist`](./struct.Polylist.html) for polygons without holes where possible, as it
+/// stores them more compactly.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "polygons"]
+pub struct Polygons {
+    /// A human-friendly name for this polygon list.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The number of polygon primitives in the polygon list.
+    #[attribute]
+    pub count: usize,
+
+    /// The name of the material associated with this polygon list.
+    ///
+    /// This name is bound to a material at the time of instantiaion. See [`InstanceGeometry`]
+    /// and [`BindMaterial`].
+    ///
+    /// If `None`, then the lighting and shading results are appplication-defined.
+    ///
+    /// [`InstanceGeometry`]: ./struct.InstanceGeometry.html
+    /// [`BindMaterial`]: ./struct.BindMaterial.html
+    #[attribute]
+    pub material: Option<String>,
+
+    /// The input data for the polygon list.
+    #[child]
+    pub inputs: Vec<SharedInput>,
+
+    /// The polygons, in the order they appear in the document.
+    #[child]
+    pub polygons: Vec<PolygonsEntry>,
+
+    /// Arbitrary additional information about this polygon list and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Polygons {
+    /// Returns an iterator over the polygons, each yielded as a [`Polygon`](./struct.Polygon.html).
+    ///
+    /// Only the outline of a polygon with holes is yielded. The holes are available through
+    /// the [`PolygonHole`](./struct.PolygonHole.html) in `polygons`.
+    pub fn iter<'a>(&'a self) -> PolygonsIter<'a> {
+        PolygonsIter {
+            iter: self.polygons.iter(),
+            stride: vertex_stride(&self.inputs),
+        }
+    }
+
+    /// Returns the number of polygon primitives in the polygon list.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns an iterator yielding all inputs that match `offset`.
+    ///
+    /// See [`Polylist::inputs_for_offset`](./struct.Polylist.html#method.inputs_for_offset) for
+    /// more information.
+    pub fn inputs_for_offset<'a>(&'a self, offset: usize) -> InputsForOffset<'a> {
+        InputsForOffset {
+            inputs: self.inputs.iter(),
+            offset,
+        }
+    }
+}
+
+impl<'a> ::std::iter::IntoIterator for &'a Polygons {
+    type Item = Polygon<'a>;
+    type IntoIter = PolygonsIter<'a>;
+
+    fn into_iter(self) -> PolygonsIter<'a> {
+        self.iter()
+    }
+}
+
+/// A single polygon of a [`Polygons`](./struct.Polygons.html).
+///
+/// Each variant wraps a list of vertex attribute indices describing the polygon.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum PolygonsEntry {
+    /// A polygon without any holes, stored in a `<p>` element.
+    Polygon(Primitives),
+
+    /// A polygon with holes, stored in a `<ph>` element.
+    PolygonHole(PolygonHole),
+}
+
+impl PolygonsEntry {
+    /// Returns the vertex attribute indices of the outline of the polygon.
+    pub fn outline(&self) -> &Primitives {
+        match *self {
+            PolygonsEntry::Polygon(ref primitives) => primitives,
+            PolygonsEntry::PolygonHole(ref polygon_hole) => &polygon_hole.outline,
+        }
+    }
+
+    /// Returns the holes of the polygon, which is empty for a polygon stored in a `<p>` element.
+    pub fn holes(&self) -> &[Hole] {
+        match *self {
+            PolygonsEntry::Polygon(_) => &[],
+            PolygonsEntry::PolygonHole(ref polygon_hole) => &polygon_hole.holes,
+        }
+    }
+}
+
+pub struct PolygonsIter<'a> {
+    iter: ::std::slice::Iter<'a, PolygonsEntry>,
+    stride: usize,
+}
+
+impl<'a> ::std::iter::Iterator for PolygonsIter<'a> {
+    type Item = Polygon<'a>;
+
+    fn next(&mut self) -> Option<Polygon<'a>> {
+        let stride = self.stride;
+        self.iter.next().map(|entry| Polygon::from_indices(entry.outline(), stride))
+    }
+}
+
+/// A polygon of a [`Polygons`] with one or more holes cut out of it.
+///
+/// [`Polygons`]: ./struct.Polygons.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "ph"]
+pub struct PolygonHole {
+    /// The vertex attribute indices of the outline of the polygon.
+    #[child]
+    pub outline: Primitives,
+
+    /// The vertex attribute indices of each hole in the polygon.
+    ///
+    /// There will always be at least one hole.
+    #[child]
+    #[required]
+    pub holes: Vec<Hole>,
+}
+
+/// A list of polygons that are not necessarily triangles.
+///
+/// Provides the information needed for a mesh to bind vertex attributes together and then
+/// organize those vertices into individual polygons. `Polylist` provides functionality for
+/// iterating over the polygons it represents.
+///
+/// # Examples
+///
+/// Iterate over all of the polygons in a polylist, then iterate over each vertex in each polygon:
+///
+/// ```
+/// # #![allow(unused_variables)]
+/// # use std::fs::File;
+/// # use collaborate::v1_4::Collada;
+/// # let file = File::open("resources/blender_cube.dae").unwrap();
+/// # let document = Collada::read(file).unwrap();
+/// # let library = document.libraries[5].as_library_geometries().unwrap();
+/// # let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+/// let polylist = mesh.primitives[0].as_polylist().unwrap();
+/// for polygon in polylist {
+///     println!("Vertices in polygon: {}", polygon.len());
+///     for vertex in polygon {
+///         println!("{:?}", vertex);
+///         for attribute in vertex {
+///             for input in polylist.inputs_for_offset(attribute.offset) {
+///                 println!(
+///                     "Attribute {:?} indexes into {:?}",
+///                     attribute,
+///                     input,
+///                 );
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "polylist"]
+pub struct Polylist {
+    /// A human-friendly name for this polylist.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The number of polygon primitives in the polylist.
+    #[attribute]
+    pub count: usize,
+
+    /// The name of the material associated with this polylist.
+    ///
+    /// This name is bound to a material at the time of instantiaion. See [`InstanceGeometry`]
+    /// and [`BindMaterial`].
+    ///
+    /// If `None`, then the lighting and shading results are appplication-defined.
+    ///
+    /// [`InstanceGeometry`]: ./struct.InstanceGeometry.html
+    /// [`BindMaterial`]: ./struct.BindMaterial.html
+    #[attribute]
+    pub material: Option<String>,
+
+    /// The input data for the polylist.
+    #[child]
+    pub inputs: Vec<SharedInput>,
+
+    /// A list of integers, each specifying the number of vertices for one polygon in the polylist.
+    #[child]
+    pub vcount: Option<VCount>,
+
+    /// A list of integers that specify the vertex attributes as indexes into the inputs.
+    #[child]
+    pub primitives: Option<Primitives>,
+
+    /// Arbitrary additional information about this polylist and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Polylist {
+    /// Returns an iterator over the polygons in the polylist.
+    pub fn iter<'a>(&'a self) -> PolylistIter<'a> {
+        // Determine the number of indices that are used for each vertex. Generally, we expect this to
+        // be the same as the number of inputs (e.g. if there's an input for position and an input
+        // for normal, then we'd expect there to be 2 indices for each vertex), but the COLLADA spec
+        // allows multiple inputs to share an offset, effectively reducing the number of indices
+        // needed for each vertex. To account for this, we look for the largest offset used by the
+        // inputs, which should tell us consistently how many unique offsets there are.
+        // TODO: How do we handle a polylist with no inputs? Probably return no polygons.
+        let largest_offset = self.inputs.iter()
+            .map(|input| input.offset)
+            .max()
+            .unwrap();
+
+        PolylistIter {
+            polylist: self,
+            num_indices_per_vertex: largest_offset + 1,
+            vcount_iter: self.vcount.as_ref().unwrap().iter(),
+            verts_so_far: 0,
+        }
+    }
+
+    /// Returns the number of polygons in the polylist.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns an iterator yielding all inputs that match `offset`.
+    ///
+    /// When matching a vertex attribute to an input, the attribute's offset is matched against
+    /// the input's offset. It's possible for multiple inputs to share the same offset, so this
+    /// method provides an easy way to iterate over all inputs with a given offset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![allow(unused_variables)]
+    /// # use std::fs::File;
+    /// # use collaborate::v1_4::Collada;
+    /// # let file = File::open("resources/blender_cube.dae").unwrap();
+    /// # let document = Collada::read(file).unwrap();
+    /// # let library = document.libraries[5].as_library_geometries().unwrap();
+    /// # let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    /// let polylist = mesh.primitives[0].as_polylist().unwrap();
+    /// for polygon in polylist {
+    ///     println!("Vertices in polygon: {}", polygon.len());
+    ///     for vertex in polygon {
+    ///         println!("{:?}", vertex);
+    ///         for attribute in vertex {
+    ///             for input in polylist.inputs_for_offset(attribute.offset) {
+    ///                 println!(
+    ///                     "Attribute {:?} indexes into {:?}",
+    ///                     attribute,
+    ///                     input,
+    ///                 );
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn inputs_for_offset<'a>(&'a self, offset: usize) -> InputsForOffset<'a> {
+        InputsForOffset {
+            inputs: self.inputs.iter(),
+            offset,
+        }
+    }
+}
+
+impl<'a> ::std::iter::IntoIterator for &'a Polylist {
+    type Item = Polygon<'a>;
+    type IntoIter = PolylistIter<'a>;
+
+    fn into_iter(self) -> PolylistIter<'a> {
+        self.iter()
+    }
+}
+
+pub struct PolylistIter<'a> {
+    polylist: &'a Polylist,
+    num_indices_per_vertex: usize,
+    vcount_iter: ::std::slice::Iter<'a, usize>,
+    verts_so_far: usize,
+}
+
+impl<'a> ::std::iter::Iterator for PolylistIter<'a> {
+    type Item = Polygon<'a>;
+
+    fn next(&mut self) -> Option<Polygon<'a>> {
+        let primitives = match self.polylist.primitives {
+            Some(ref primitives) => primitives,
+            None => return None,
+        };
+
+        self.vcount_iter.next()
+            .map(|&num_verts| {
+                let indices = &primitives[self.verts_so_far * self.num_indices_per_vertex .. (self.verts_so_far + num_verts) * self.num_indices_per_vertex];
+                self.verts_so_far += num_verts;
+                Polygon {
+                    len: num_verts,
+                    chunks: indices.chunks(self.num_indices_per_vertex),
+                }
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum Primitive {
+    Lines(Lines),
+    Linestrips(Linestrips),
+    Polygons(Polygons),
+    Polylist(Polylist),
+    Triangles(Triangles),
+    Trifans(Trifans),
+    Tristrips(Tristrips),
+}
+
+impl Primitive {
+    pub fn as_lines(&self) -> Option<&Lines> {
+        match *self {
+            Primitive::Lines(ref lines) => Some(lines),
+            _ => None,
+        }
+    }
+
+    pub fn as_linestrips(&self) -> Option<&Linestrips> {
+        match *self {
+            Primitive::Linestrips(ref linestrips) => Some(linestrips),
+            _ => None,
+        }
+    }
+
+    pub fn as_polygons(&self) -> Option<&Polygons> {
+        match *self {
+            Primitive::Polygons(ref polygons) => Some(polygons),
+            _ => None,
+        }
+    }
+
+    pub fn as_polylist(&self) -> Option<&Polylist> {
+        match *self {
+            Primitive::Polylist(ref polylist) => Some(polylist),
+            _ => None,
+        }
+    }
+
+    pub fn as_triangles(&self) -> Option<&Triangles> {
+        match *self {
+            Primitive::Triangles(ref triangles) => Some(triangles),
+            _ => None,
+        }
+    }
+
+    pub fn as_trifans(&self) -> Option<&Trifans> {
+        match *self {
+            Primitive::Trifans(ref trifans) => Some(trifans),
+            _ => None,
+        }
+    }
+
+    pub fn as_tristrips(&self) -> Option<&Tristrips> {
+        match *self {
+            Primitive::Tristrips(ref tristrips) => Some(tristrips),
+            _ => None,
+        }
+    }
+
+    /// Returns the material symbol of the primitive, which is bound to a material when the
+    /// geometry is instantiated.
+    pub fn material(&self) -> Option<&str> {
+        match *self {
+            Primitive::Lines(ref lines) => lines.material.as_deref(),
+            Primitive::Linestrips(ref linestrips) => linestrips.material.as_deref(),
+            Primitive::Polygons(ref polygons) => polygons.material.as_deref(),
+            Primitive::Polylist(ref polylist) => polylist.material.as_deref(),
+            Primitive::Triangles(ref triangles) => triangles.material.as_deref(),
+            Primitive::Trifans(ref trifans) => trifans.material.as_deref(),
+            Primitive::Tristrips(ref tristrips) => tristrips.material.as_deref(),
+        }
+    }
+
+    /// Returns the inputs of the primitive.
+    pub fn inputs(&self) -> &[SharedInput] {
+        match *self {
+            Primitive::Lines(ref lines) => &lines.inputs,
+            Primitive::Linestrips(ref linestrips) => &linestrips.inputs,
+            Primitive::Polygons(ref polygons) => &polygons.inputs,
+            Primitive::Polylist(ref polylist) => &polylist.inputs,
+            Primitive::Triangles(ref triangles) => &triangles.inputs,
+            Primitive::Trifans(ref trifans) => &trifans.inputs,
+            Primitive::Tristrips(ref tristrips) => &tristrips.inputs,
+        }
+    }
+
+    /// Returns an iterator over all of the vertices of the primitive, in document order.
+    ///
+    /// The vertices of each line, polygon, or strip are yielded one after the other. The holes
+    /// of a [`Polygons`](./struct.Polygons.html) aren't included.
+    pub fn vertices<'a>(&'a self) -> Box<Iterator<Item = Vertex<'a>> + 'a> {
+        match *self {
+            Primitive::Lines(ref lines) => Box::new(lines.iter().flatten()),
+            Primitive::Linestrips(ref linestrips) => Box::new(linestrips.iter().flatten()),
+            Primitive::Polygons(ref polygons) => Box::new(polygons.iter().flatten()),
+            Primitive::Polylist(ref polylist) => Box::new(polylist.iter().flatten()),
+            Primitive::Triangles(ref triangles) => Box::new(triangles.iter().flatten()),
+            Primitive::Trifans(ref trifans) => Box::new(trifans.iter().flatten()),
+            Primitive::Tristrips(ref tristrips) => Box::new(tristrips.iter().flatten()),
+        }
+    }
+}
+
+/// An iterator over the `<p>` elements of a primitive, each yielded as a single
+/// [`Polygon`](./struct.Polygon.html), e.g. the strips of a [`Tristrips`](./struct.Tristrips.html).
+pub struct PrimitivesIter<'a> {
+    iter: ::std::slice::Iter<'a, Primitives>,
+    stride: usize,
+}
+
+impl<'a> ::std::iter::Iterator for PrimitivesIter<'a> {
+    type Item = Polygon<'a>;
+
+    fn next(&mut self) -> Option<Polygon<'a>> {
+        let stride = self.stride;
+        self.iter.next().map(|primitives| Polygon::from_indices(primitives, stride))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "p"]
+pub struct Primitives {
+    #[text]
+    data: Vec<usize>,
+}
+
+impl ::std::ops::Deref for Primitives {
+    type Target = [usize];
+
+    fn deref(&self) -> &[usize] { &*self.data }
+}
+
+/// A platform-specific description of an [`Effect`](./struct.Effect.html).
+///
+/// Each variant wraps a single value containing the profile data. `<profile_GLES>` isn't currently
+/// supported, and is skipped when parsing.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum Profile {
+    Cg(ProfileCg),
+    Common(ProfileCommon),
+    Gles(ProfileGles),
+    Glsl(ProfileGlsl),
+}
+
+impl Profile {
+    /// Returns the inner [`ProfileCg`](./struct.ProfileCg.html) if this is a `<profile_CG>`.
+    pub fn as_cg(&self) -> Option<&ProfileCg> {
+        match *self {
+            Profile::Cg(ref profile) => Some(profile),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner [`ProfileCommon`](./struct.ProfileCommon.html) if this is a
+    /// `<profile_COMMON>`.
+    pub fn as_common(&self) -> Option<&ProfileCommon> {
+        match *self {
+            Profile::Common(ref profile) => Some(profile),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner [`ProfileGlsl`](./struct.ProfileGlsl.html) if this is a
+    /// `<profile_GLSL>`.
+    pub fn as_glsl(&self) -> Option<&ProfileGlsl> {
+        match *self {
+            Profile::Glsl(ref profile) => Some(profile),
+            _ => None,
+        }
+    }
+}
+
+/// Describes an effect with shaders written in NVIDIA's Cg language.
+///
+/// Cg shaders are compiled for a specific platform, given by `platform`.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "profile_CG"]
+pub struct ProfileCg {
+    /// A unique identifier for the profile.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The platform the shaders are compiled for. Defaults to `"PC"`.
+    #[attribute]
+    #[optional_with_default = r#""PC".into()"#]
+    pub platform: String,
+
+    /// Metadata about the profile and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The shader source code shared by all of the profile's techniques.
+    #[child]
+    pub code: Vec<ShaderCode>,
+
+    /// The images and parameters shared by all of the profile's techniques, in document order.
+    #[child]
+    pub params: Vec<ProfileParam>,
+
+    /// The techniques describing how to render the effect. There will always be at least one
+    /// technique.
+    #[child]
+    #[required]
+    pub techniques: Vec<ShaderTechnique>,
+
+    /// Arbitrary additional information about this profile and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl ProfileCg {
+    /// Returns the technique with the given sid, if the profile has one.
+    pub fn technique(&self, sid: &str) -> Option<&ShaderTechnique> {
+        self.techniques.iter().find(|technique| technique.sid.as_deref() == Some(sid))
+    }
+
+    /// Returns the parameter declared with the given sid, if the profile declares one.
+    pub fn new_param(&self, sid: &str) -> Option<&ShaderNewParam> {
+        self.params.iter()
+            .filter_map(ProfileParam::as_new_param)
+            .find(|param| param.sid == sid)
+    }
+}
+
+/// Describes an effect using one of the fixed-function shading models that every application is
+/// expected to support.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "profile_COMMON"]
+pub struct ProfileCommon {
+    /// A unique identifier for the profile.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// Metadata about the profile and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    #[child]
+    pub images: Vec<Image>,
+
+    #[child]
+    pub new_params: Vec<NewParam>,
+
+    /// The technique describing how to render the effect.
+    #[child]
+    pub technique: ProfileCommonTechnique,
+
+    /// Arbitrary additional information about this profile and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// The `<technique>` of a [`ProfileCommon`], which selects the shading model used to render the
+/// effect.
+///
+/// This is unrelated to the [`Technique`] elements in an [`Extra`], which hold
+/// application-specific data.
+///
+/// [`ProfileCommon`]: ./struct.ProfileCommon.html
+/// [`Technique`]: ../common/struct.Technique.html
+/// [`Extra`]: ./struct.Extra.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "technique"]
+pub struct ProfileCommonTechnique {
+    /// A unique identifier for the technique.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// An identifier for the technique that's unique within the effect.
+    #[attribute]
+    pub sid: String,
+
+    /// Metadata about the technique and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    #[child]
+    pub images: Vec<Image>,
+
+    /// The shading model, along with the inputs to it.
+    #[child]
+    pub shading: ShadingModel,
+
+    /// Arbitrary additional information about this technique and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "profile_GLES"]
+pub struct ProfileGles;
+
+/// Describes an effect with shaders written in the OpenGL Shading Language.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "profile_GLSL"]
+pub struct ProfileGlsl {
+    /// A unique identifier for the profile.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// Metadata about the profile and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The shader source code shared by all of the profile's techniques.
+    #[child]
+    pub code: Vec<ShaderCode>,
+
+    /// The images and parameters shared by all of the profile's techniques, in document order.
+    #[child]
+    pub params: Vec<ProfileParam>,
+
+    /// The techniques describing how to render the effect. There will always be at least one
+    /// technique.
+    #[child]
+    #[required]
+    pub techniques: Vec<ShaderTechnique>,
+
+    /// Arbitrary additional information about this profile and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl ProfileGlsl {
+    /// Returns the technique with the given sid, if the profile has one.
+    pub fn technique(&self, sid: &str) -> Option<&ShaderTechnique> {
+        self.techniques.iter().find(|technique| technique.sid.as_deref() == Some(sid))
+    }
+
+    /// Returns the parameter declared with the given sid, if the profile declares one.
+    pub fn new_param(&self, sid: &str) -> Option<&ShaderNewParam> {
+        self.params.iter()
+            .filter_map(ProfileParam::as_new_param)
+            .find(|param| param.sid == sid)
+    }
+}
+
+/// An image or parameter declared by a [`ProfileGlsl`] or [`ProfileCg`].
+///
+/// [`ProfileGlsl`]: ./struct.ProfileGlsl.html
+/// [`ProfileCg`]: ./struct.ProfileCg.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum ProfileParam {
+    Image(Image),
+    NewParam(ShaderNewParam),
+}
+
+impl ProfileParam {
+    /// Returns the inner [`ShaderNewParam`](./struct.ShaderNewParam.html) if this is a parameter.
+    pub fn as_new_param(&self) -> Option<&ShaderNewParam> {
+        match *self {
+            ProfileParam::NewParam(ref param) => Some(param),
+            _ => None,
+        }
+    }
+}
+
+/// The projection of a [`Camera`](./struct.Camera.html).
+///
+/// Each variant wraps a single value containing the parameters of a given type of projection.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum Projection {
+    Orthographic(Orthographic),
+    Perspective(Perspective),
+}
+
+impl Projection {
+    /// Returns the inner [`Orthographic`](./struct.Orthographic.html) if this is an orthographic
+    /// projection.
+    pub fn as_orthographic(&self) -> Option<&Orthographic> {
+        match *self {
+            Projection::Orthographic(ref orthographic) => Some(orthographic),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner [`Perspective`](./struct.Perspective.html) if this is a perspective
+    /// projection.
+    pub fn as_perspective(&self) -> Option<&Perspective> {
+        match *self {
+            Projection::Perspective(ref perspective) => Some(perspective),
+            _ => None,
+        }
+    }
+
+    /// Returns the distances to the near and far clipping planes.
+    pub fn clipping_planes(&self) -> (f32, f32) {
+        match *self {
+            Projection::Orthographic(ref orthographic) => (orthographic.znear.value, orthographic.zfar.value),
+            Projection::Perspective(ref perspective) => (perspective.znear.value, perspective.zfar.value),
+        }
+    }
+}
+
+targetable_float! {
+    /// The quadratic factor in the attenuation of a [`Point`](./struct.Point.html) or
+    /// [`Spot`](./struct.Spot.html) light.
+    pub struct QuadraticAttenuation, "quadratic_attenuation"
+}
+
+targetable_vector! {
+    /// The radii of a [`Cylinder`](./struct.Cylinder.html) or [`Capsule`](./struct.Capsule.html)
+    /// along its local x and z axes.
+    pub struct Radius, "radius"
+}
+
+targetable_vector! {
+    /// The radii of the bottom of a tapered cylinder or capsule along its local x and z axes.
+    pub struct Radius1, "radius1"
+}
+
+targetable_vector! {
+    /// The radii of the top of a tapered cylinder or capsule along its local x and z axes.
+    pub struct Radius2, "radius2"
+}
+
+/// A parameter value of a type that isn't otherwise modeled, e.g. `<half3>`, `<bool2x2>`, or a Cg
+/// `<usertype>`.
+///
+/// The value is kept as a [`RawElement`], whose numeric contents can be read with
+/// [`float_values`](#method.float_values).
+///
+/// [`RawElement`]: ../raw/struct.RawElement.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawParamValue {
+    /// The element, e.g. `<half3>0.5 0.5 1.0</half3>`.
+    pub element: RawElement,
+}
+
+impl RawParamValue {
+    const NAMES: &'static [&'static str] = &[
+        "bool1", "bool1x1", "bool1x2", "bool1x3", "bool1x4", "bool2x1", "bool2x2", "bool2x3",
+        "bool2x4", "bool3x1", "bool3x2", "bool3x3", "bool3x4", "bool4x1", "bool4x2", "bool4x3",
+        "bool4x4", "int1", "int1x1", "int1x2", "int1x3", "int1x4", "int2x1", "int2x2", "int2x3",
+        "int2x4", "int3x1", "int3x2", "int3x3", "int3x4", "int4x1", "int4x2", "int4x3", "int4x4",
+        "float1", "half", "half1", "half2", "half3", "half4", "half1x1", "half1x2", "half1x3",
+        "half1x4", "half2x1", "half2x2", "half2x3", "half2x4", "half3x1", "half3x2", "half3x3",
+        "half3x4", "half4x1", "half4x2", "half4x3", "half4x4", "fixed", "fixed1", "fixed2",
+        "fixed3", "fixed4", "fixed1x1", "fixed1x2", "fixed1x3", "fixed1x4", "fixed2x1", "fixed2x2",
+        "fixed2x3", "fixed2x4", "fixed3x1", "fixed3x2", "fixed3x3", "fixed3x4", "fixed4x1",
+        "fixed4x2", "fixed4x3", "fixed4x4", "string", "usertype", "array",
+    ];
+
+    /// Returns the name of the value's type, e.g. `"half3"`.
+    pub fn type_name(&self) -> &str {
+        &self.element.name.local_name
+    }
+
+    /// Interprets the value as a list of numbers, e.g. for a `half3` or `int2x2`.
+    ///
+    /// Returns `None` if the value has child elements, or isn't a valid list of numbers.
+    pub fn float_values(&self) -> Option<Vec<f32>> {
+        if !self.element.children.is_empty() {
+            return None;
+        }
+
+        utils::parse_float_list(self.element.text.trim()).ok()
+    }
+}
+
+impl ColladaElement for RawParamValue {
+    fn name_test(name: &str) -> bool {
+        RawParamValue::NAMES.contains(&name)
+    }
+
+    fn parse_element<R>(
+        reader: &mut DocumentReader<R>,
+        element_start: ElementStart,
+    ) -> Result<RawParamValue>
+    where
+        R: XmlPull,
+    {
+        utils::parse_raw_element(reader, element_start).map(|element| RawParamValue { element })
+    }
+
+    fn add_names(names: &mut Vec<&'static str>) {
+        names.extend_from_slice(RawParamValue::NAMES);
+    }
+
+    fn write_element<W: Write>(&self, writer: &mut XmlWriter<W>) -> writer::Result<()> {
+        writer.raw_element(&self.element)
+    }
+}
+
+/// The reference frame of a [`RigidConstraint`], which the frame of its `attachment` is
+/// constrained relative to.
+///
+/// [`RigidConstraint`]: ./struct.RigidConstraint.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "ref_attachment"]
+pub struct RefAttachment {
+    /// The location of the rigid body, or of a node, that the frame is relative to.
+    #[attribute]
+    pub rigid_body: AnyUri,
+
+    /// The transforms positioning the frame, in the order they appear in the document.
+    #[child]
+    pub transforms: Vec<RigidTransform>,
+
+    /// Arbitrary additional information about this attachment and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// A single rendering pass of an [`EvaluateScene`].
+///
+/// [`EvaluateScene`]: ./struct.EvaluateScene.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "render"]
+pub struct Render {
+    /// The location of the node containing the camera to render from.
+    #[attribute]
+    pub camera_node: AnyUri,
+
+    /// The layers of the scene to render. If there are no layers, the whole scene is rendered.
+    #[child]
+    #[name = "layer"]
+    pub layers: Vec<String>,
+
+    /// The effect used to render the pass, if any. Without an effect, the scene is rendered with
+    /// the materials bound to its geometry.
+    #[child]
+    pub instance_effect: Option<InstanceEffect>,
+}
+
+impl Render {
+    /// Returns `true` if `node` is rendered by this pass, based on the node's layers.
+    pub fn renders_node(&self, node: &Node) -> bool {
+        if self.layers.is_empty() {
+            return true;
+        }
+
+        node.layers().any(|layer| self.layers.iter().any(|rendered| rendered == layer))
+    }
+}
+
+/// A render state set by a [`Pass`], e.g. `<blend_enable value="true" />`.
+///
+/// The OpenGL pipeline has over a hundred render states, each with its own set of values, so
+/// they aren't modeled individually. Most states have a single `value` (or a `param` to read the
+/// value from), which can be interpreted with [`bool_value`](#method.bool_value) or
+/// [`float_values`](#method.float_values). The few states with several values, e.g.
+/// `<blend_func>`, keep them as `children`.
+///
+/// [`Pass`]: ./struct.Pass.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderState {
+    /// The name of the state, e.g. `"depth_func"`.
+    pub name: String,
+
+    /// The value of the state, if it's given directly, e.g. `"LEQUAL"`.
+    pub value: Option<String>,
+
+    /// The sid of the parameter to read the value from, if the value isn't given directly.
+    pub param: Option<String>,
+
+    /// The index of the light, texture unit, or clip plane that the state applies to, if any.
+    pub index: Option<usize>,
+
+    /// The elements inside of the state, e.g. the `<src>` and `<dest>` of a `<blend_func>`.
+    pub children: Vec<RawElement>,
+}
+
+impl RenderState {
+    const NAMES: &'static [&'static str] = &[
+        "alpha_func", "blend_func", "blend_func_separate", "blend_equation",
+        "blend_equation_separate", "color_material", "cull_face", "depth_func", "fog_mode",
+        "fog_coord_src", "front_face", "light_model_color_control", "logic_op", "polygon_mode",
+        "shade_model", "stencil_func", "stencil_op", "stencil_func_separate",
+        "stencil_op_separate", "stencil_mask_separate", "light_enable", "light_ambient",
+        "light_diffuse", "light_specular", "light_position", "light_constant_attenuation",
+        "light_linear_attenuation", "light_quadratic_attenuation", "light_spot_cutoff",
+        "light_spot_direction", "light_spot_exponent", "texture1D", "texture2D", "texture3D",
+        "textureCUBE", "textureRECT", "textureDEPTH", "texture1D_enable", "texture2D_enable",
+        "texture3D_enable", "textureCUBE_enable", "textureRECT_enable", "textureDEPTH_enable",
+        "texture_env_color", "texture_env_mode", "clip_plane", "clip_plane_enable", "blend_color",
+        "clear_color", "clear_stencil", "clear_depth", "color_mask", "depth_bounds", "depth_mask",
+        "depth_range", "fog_density", "fog_start", "fog_end", "fog_color", "light_model_ambient",
+        "lighting_enable", "line_stipple", "line_width", "material_ambient", "material_diffuse",
+        "material_emission", "material_shininess", "material_specular", "model_view_matrix",
+        "point_distance_attenuation", "point_fade_threshold_size", "point_size", "point_size_min",
+        "point_size_max", "polygon_offset", "projection_matrix", "scissor", "stencil_mask",
+        "alpha_test_enable", "auto_normal_enable", "blend_enable", "color_logic_op_enable",
+        "color_material_enable", "cull_face_enable", "depth_bounds_enable", "depth_clamp_enable",
+        "depth_test_enable", "dither_enable", "fog_enable", "light_model_local_viewer_enable",
+        "light_model_two_side_enable", "line_smooth_enable", "line_stipple_enable",
+        "logic_op_enable", "multisample_enable", "normalize_enable", "point_smooth_enable",
+        "polygon_offset_fill_enable", "polygon_offset_line_enable", "polygon_offset_point_enable",
+        "polygon_smooth_enable", "polygon_stipple_enable", "rescale_normal_enable",
+        "sample_alpha_to_coverage_enable", "sample_alpha_to_one_enable", "sample_coverage_enable",
+        "scissor_test_enable", "stencil_test_enable", "gl_hook_abstract",
+    ];
+
+    /// Interprets the value of the state as a boolean, e.g. for `<blend_enable>`.
+    pub fn bool_value(&self) -> Option<bool> {
+        self.value.as_ref().and_then(|value| value.parse().ok())
+    }
+
+    /// Interprets the value of the state as a list of numbers, e.g. for `<clear_color>`.
+    pub fn float_values(&self) -> Option<Vec<f32>> {
+        self.value.as_ref().and_then(|value| utils::parse_float_list(value).ok())
+    }
+
+    /// Returns the value of the child element `name`, e.g. the `<src>` of a `<blend_func>`.
+    pub fn child_value(&self, name: &str) -> Option<&str> {
+        self.children.iter()
+            .find(|child| child.name.local_name == name)
+            .and_then(|child| child.attribute("value"))
+    }
+}
+
+impl ColladaElement for RenderState {
+    fn name_test(name: &str) -> bool {
+        RenderState::NAMES.contains(&name)
+    }
+
+    fn parse_element<R>(
+        reader: &mut DocumentReader<R>,
+        element_start: ElementStart,
+    ) -> Result<RenderState>
+    where
+        R: XmlPull,
+    {
+        let element = utils::parse_raw_element(reader, element_start)?;
+        let index = match element.attribute("index") {
+            Some(index) => Some(utils::parse_value(reader, index)?),
+            None => None,
+        };
+
+        Ok(RenderState {
+            value: element.attribute("value").map(Into::into),
+            param: element.attribute("param").map(Into::into),
+            index,
+            name: element.name.local_name,
+            children: element.children,
+        })
+    }
+
+    fn add_names(names: &mut Vec<&'static str>) {
+        names.extend_from_slice(RenderState::NAMES);
+    }
+
+    fn write_element<W: Write>(&self, writer: &mut XmlWriter<W>) -> writer::Result<()> {
+        writer.start_element(&self.name)?;
+        if let Some(ref value) = self.value {
+            writer.attribute("value", value)?;
+        }
+        if let Some(ref param) = self.param {
+            writer.attribute("param", param)?;
+        }
+        if let Some(ref index) = self.index {
+            writer.attribute("index", index)?;
+        }
+        for child in &self.children {
+            writer.raw_element(child)?;
+        }
+        writer.end_element(&self.name)
+    }
+}
+
+/// An input of a primitive, after its `VERTEX` input has been replaced by the inputs of the mesh's
+/// [`Vertices`].
+///
+/// See [`Mesh::resolved_inputs`] for details.
+///
+/// [`Vertices`]: ./struct.Vertices.html
+/// [`Mesh::resolved_inputs`]: ./struct.Mesh.html#method.resolved_inputs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResolvedInput<'a> {
+    /// The meaning of the input, e.g. `Semantic::Normal`.
+    pub semantic: &'a Semantic,
+
+    /// The offset of the input's index within each vertex of the primitive.
+    pub offset: usize,
+
+    /// The set the input belongs to, if any.
+    ///
+    /// See [`SharedInput::set_index`](./struct.SharedInput.html#method.set_index).
+    pub set: Option<usize>,
+
+    /// The location of the source containing the input's data.
+    pub source: &'a UriFragment,
+
+    /// `true` if the input was declared by the mesh's `<vertices>` rather than by the primitive.
+    pub per_vertex: bool,
+}
+
+/// A value found by resolving a [`SidRef`] with
+/// [`Collada::resolve_sid_path`](./struct.Collada.html#method.resolve_sid_path).
+///
+/// [`SidRef`]: ../common/struct.SidRef.html
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResolvedSid<'a> {
+    /// The element targeted by the address.
+    pub target: SidTarget<'a>,
+
+    /// The index of the selected value within the target's values, if the address only selects
+    /// part of the target (e.g. the x value of a translation).
+    ///
+    /// For matrices, this is the index into the values in row-major order.
+    pub component: Option<usize>,
+}
+
+impl<'a> ResolvedSid<'a> {
+    /// Returns the targeted values, or just the selected value if there's a selector.
+    ///
+    /// Returns `None` if the target isn't a value, e.g. if it's a node.
+    pub fn values(&self) -> Option<&'a [f32]> {
+        let values = match self.target {
+            SidTarget::Color(color) => &*color.values,
+            SidTarget::Float(value) => ::std::slice::from_ref(value),
+            SidTarget::Transform(transform) => transform.values(),
+            _ => return None,
+        };
+
+        match self.component {
+            Some(index) => values.get(index..index + 1),
+            None => Some(values),
+        }
+    }
+}
+
+targetable_float! {
+    /// How much energy a [`PhysicsMaterial`](./struct.PhysicsMaterial.html) keeps when it
+    /// collides with another surface, i.e. how bouncy it is.
+    pub struct Restitution, "restitution"
+}
+
+/// Declares a solid object that's simulated by the physics engine.
+///
+/// A rigid body is made up of one or more [`Shape`]s, and is bound to the node it moves by an
+/// [`InstanceRigidBody`].
+///
+/// [`Shape`]: ./struct.Shape.html
+/// [`InstanceRigidBody`]: ./struct.InstanceRigidBody.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "rigid_body"]
+pub struct RigidBody {
+    /// An identifier for the body that's unique within its physics model.
+    #[attribute]
+    pub sid: String,
+
+    /// The human-friendly name for this rigid body.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The physical properties of the body, which every application is expected to support.
+    #[child]
+    pub technique_common: RigidBodyTechniqueCommon,
+
+    /// Application-specific physical properties.
+    #[child]
+    pub techniques: Vec<Technique>,
+
+    /// Arbitrary additional information about this rigid body and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl RigidBody {
+    /// Returns whether the body is moved by the simulation, which is the default if it doesn't
+    /// have a `<dynamic>`.
+    ///
+    /// Instances of the body can override this, see
+    /// [`InstanceRigidBody::is_dynamic`](./struct.InstanceRigidBody.html#method.is_dynamic).
+    pub fn is_dynamic(&self) -> bool {
+        match self.technique_common.dynamic {
+            Some(ref dynamic) => dynamic.value,
+            None => true,
+        }
+    }
+}
+
+/// The common technique of a [`RigidBody`](./struct.RigidBody.html).
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "technique_common"]
+pub struct RigidBodyTechniqueCommon {
+    /// Whether the body is moved by the simulation, which defaults to `true`.
+    #[child]
+    pub dynamic: Option<Dynamic>,
+
+    /// The total mass of the body.
+    ///
+    /// If `None`, the mass is worked out from the density and volume of the body's shapes.
+    #[child]
+    pub mass: Option<Mass>,
+
+    /// The center of mass and principal axes of inertia of the body, relative to the node it
+    /// simulates.
+    #[child]
+    pub mass_frame: Option<MassFrame>,
+
+    /// The inertia tensor of the body, in the frame given by `mass_frame`.
+    #[child]
+    pub inertia: Option<Inertia>,
+
+    /// The physics material of the body, used by any shapes that don't have their own.
+    #[child]
+    pub physics_material: Option<PhysicsMaterialSource>,
+
+    /// The shapes making up the body.
+    ///
+    /// There will always be at least one shape.
+    #[child]
+    #[required]
+    pub shapes: Vec<Shape>,
+}
+
+/// Connects two rigid bodies, limiting how they can move relative to each other, e.g. a hinge or
+/// a ball joint.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "rigid_constraint"]
+pub struct RigidConstraint {
+    /// An identifier for the constraint that's unique within its physics model.
+    #[attribute]
+    pub sid: String,
+
+    /// The human-friendly name for this rigid constraint.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The frame that the attachment is constrained relative to.
+    #[child]
+    pub ref_attachment: RefAttachment,
+
+    /// The frame being constrained.
+    #[child]
+    pub attachment: Attachment,
+
+    /// The limits and springs of the constraint, which every application is expected to support.
+    #[child]
+    pub technique_common: RigidConstraintTechniqueCommon,
+
+    /// Application-specific information about the constraint.
+    #[child]
+    pub techniques: Vec<Technique>,
+
+    /// Arbitrary additional information about this rigid constraint and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// The common technique of a [`RigidConstraint`](./struct.RigidConstraint.html).
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "technique_common"]
+pub struct RigidConstraintTechniqueCommon {
+    /// Whether the constraint is enabled, which defaults to `true`.
+    #[child]
+    pub enabled: Option<Enabled>,
+
+    /// Whether the connected bodies may pass through each other, which defaults to `false`.
+    #[child]
+    pub interpenetrate: Option<Interpenetrate>,
+
+    /// The limits of the attachment's movement relative to the reference frame.
+    #[child]
+    pub limits: Option<Limits>,
+
+    /// The springs pulling the attachment back towards the reference frame.
+    #[child]
+    pub spring: Option<Spring>,
+}
+
+/// A single transform of a physics element, e.g. the [`MassFrame`] of a rigid body.
+///
+/// Physics elements can only be translated and rotated.
+///
+/// [`MassFrame`]: ./struct.MassFrame.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum RigidTransform {
+    Rotate(Rotate),
+    Translate(Translate),
+}
+
+impl RigidTransform {
+    /// Returns the transform as a 4x4 matrix in row-major order.
+    ///
+    /// Returns `None` if the transform has the wrong number of values for its type.
+    pub fn matrix(&self) -> Option<[f32; 16]> {
+        match *self {
+            RigidTransform::Rotate(ref rotate) => rotate.matrix(),
+            RigidTransform::Translate(ref translate) => translate.matrix(),
+        }
+    }
+}
+
+/// Rotates a node around an axis.
+///
+/// Contains 4 values: the axis of rotation followed by the angle of rotation in degrees.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "rotate"]
+pub struct Rotate {
+    /// An identifier for the transform, so that it can be targeted by animations.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The axis and angle of the rotation.
+    #[text]
+    pub values: Vec<f32>,
+}
+
+impl Rotate {
+    /// Returns the axis of rotation and the angle of rotation in degrees.
+    ///
+    /// Returns `None` if the element doesn't contain exactly 4 values.
+    pub fn axis_angle(&self) -> Option<([f32; 3], f32)> {
+        match *self.values {
+            [x, y, z, angle] => Some(([x, y, z], angle)),
+            _ => None,
+        }
+    }
+
+    /// Returns the transform as a 4x4 matrix in row-major order.
+    pub fn matrix(&self) -> Option<[f32; 16]> {
+        let (axis, angle) = self.axis_angle()?;
+        let [x, y, z] = normalize(axis);
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let t = 1.0 - cos;
+        Some([
+            t * x * x + cos, t * x * y - sin * z, t * x * z + sin * y, 0.0,
+            t * x * y + sin * z, t * y * y + cos, t * y * z - sin * x, 0.0,
+            t * x * z - sin * y, t * y * z + sin * x, t * z * z + cos, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        ])
+    }
+}
+
+/// Declares an animation curve, built from the sources of an [`Animation`].
+///
+/// Each of the sampler's inputs connects a source to the curve, identified by its semantic:
+///
+/// | Semantic          | Description                                                  |
+/// |-------------------|--------------------------------------------------------------|
+/// | `"INPUT"`         | The keyframe times.                                          |
+/// | `"OUTPUT"`        | The values at each keyframe.                                 |
+/// | `"INTERPOLATION"` | How to interpolate after each keyframe, e.g. `"LINEAR"`.     |
+/// | `"IN_TANGENT"`    | The incoming tangents at each keyframe, for Bézier curves.   |
+/// | `"OUT_TANGENT"`   | The outgoing tangents at each keyframe, for Bézier curves.   |
+///
+/// [`Animation`]: ./struct.Animation.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "sampler"]
+pub struct Sampler {
+    /// A unique identifier for the sampler, which channels use to refer to it.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The sources that make up the curve.
+    ///
+    /// There will always be at least one input, and an `"INPUT"` input is required by the
+    /// specification.
+    #[child]
+    #[required]
+    pub inputs: Vec<UnsharedInput>,
+}
+
+impl Sampler {
+    /// Returns the input with the given semantic (e.g. `"OUTPUT"`), if the sampler has one.
+    pub fn input(&self, semantic: &str) -> Option<&UnsharedInput> {
+        self.inputs.iter().find(|input| input.semantic == semantic)
+    }
+}
+
+sampler! {
+    /// Declares how a one-dimensional texture is sampled from a surface.
+    pub struct Sampler1D, "sampler1D", [wrap_s]
+}
+
+/// Declares how a texture is sampled from a [`Surface`].
+///
+/// [`Surface`]: ./struct.Surface.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "sampler2D"]
+pub struct Sampler2D {
+    /// The sid of the [`Surface`] parameter to sample from.
+    ///
+    /// [`Surface`]: ./struct.Surface.html
+    #[child]
+    pub source: String,
+
+    /// How texture coordinates outside of `0.0` to `1.0` are handled horizontally.
+    #[child]
+    #[text_type]
+    #[optional_with_default]
+    pub wrap_s: WrapMode,
+
+    /// How texture coordinates outside of `0.0` to `1.0` are handled vertically.
+    #[child]
+    #[text_type]
+    #[optional_with_default]
+    pub wrap_t: WrapMode,
+
+    /// The filter used when the texture is minified.
+    #[child]
+    #[text_type]
+    #[optional_with_default]
+    pub minfilter: FilterMode,
+
+    /// The filter used when the texture is magnified.
+    #[child]
+    #[text_type]
+    #[optional_with_default]
+    pub magfilter: FilterMode,
+
+    /// The filter used between mipmap levels.
+    #[child]
+    #[text_type]
+    #[optional_with_default]
+    pub mipfilter: FilterMode,
+
+    #[child]
+    pub border_color: Option<BorderColor>,
+
+    /// The highest mipmap level to sample from.
+    #[child]
+    pub mipmap_maxlevel: Option<usize>,
+
+    /// The bias added to the mipmap level that's sampled from.
+    #[child]
+    pub mipmap_bias: Option<f32>,
+
+    /// Arbitrary additional information about this sampler.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+sampler! {
+    /// Declares how a three-dimensional texture is sampled from a surface.
+    pub struct Sampler3D, "sampler3D", [wrap_s, wrap_t, wrap_p]
+}
+
+sampler! {
+    /// Declares how a cube map is sampled from a surface.
+    pub struct SamplerCube, "samplerCUBE", [wrap_s, wrap_t, wrap_p]
+}
+
+sampler! {
+    /// Declares how a depth texture is sampled from a surface, e.g. for shadow mapping.
+    pub struct SamplerDepth, "samplerDEPTH", [wrap_s, wrap_t]
+}
+
+sampler! {
+    /// Declares how a rectangular texture, which is addressed in texels, is sampled from a surface.
+    pub struct SamplerRect, "samplerRECT", [wrap_s, wrap_t]
+}
+
+/// Scales a node along each axis.
+///
+/// Contains 3 values: the scale factors along the x, y, and z axes.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "scale"]
+pub struct Scale {
+    /// An identifier for the transform, so that it can be targeted by animations.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The scale factors.
+    #[text]
+    pub values: Vec<f32>,
+}
+
+impl Scale {
+    /// Returns the transform as a 4x4 matrix in row-major order.
+    ///
+    /// Returns `None` if the element doesn't contain exactly 3 values.
+    pub fn matrix(&self) -> Option<[f32; 16]> {
+        match *self.values {
+            [x, y, z] => Some([
+                x, 0.0, 0.0, 0.0,
+                0.0, y, 0.0, 0.0,
+                0.0, 0.0, z, 0.0,
+                0.0, 0.0, 0.0, 1.0,
+            ]),
+            _ => None,
+        }
+    }
+}
+
+/// The scene described by the document.
+///
+/// A document may contain any number of visual and physics scenes in its libraries, but only the
+/// ones instantiated here are part of the document's scene. Use [`Collada::main_scene`] to get
+/// the visual scene itself.
+///
+/// [`Collada::main_scene`]: ./struct.Collada.html#method.main_scene
+#[derive(Debug, Clone, Default, PartialEq, ColladaElement)]
+#[name = "scene"]
+pub struct Scene {
+    /// The physics scenes that simulate the visual scene, if any.
     #[child]
-    pub source_data: Option<AnyUri>,
+    pub instance_physics_scenes: Vec<InstancePhysicsScene>,
+
+    /// The visual scene to display, if any.
+    #[child]
+    pub instance_visual_scene: Option<InstanceVisualScene>,
+
+    /// The kinematics scene that animates the visual scene, which was added in COLLADA version
+    /// `1.5.0`.
+    #[child]
+    #[collada(version = "1.5")]
+    pub instance_kinematics_scene: Option<::v1_5::InstanceKinematicsScene>,
+
+    /// Arbitrary additional information about the scene.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
 }
 
+/// Overrides the value of an effect parameter for an [`InstanceEffect`].
+///
+/// [`InstanceEffect`]: ./struct.InstanceEffect.html
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "convex_mesh"]
-pub struct ConvexMesh;
+#[name = "setparam"]
+pub struct SetParam {
+    /// The sid of the parameter to override.
+    #[attribute]
+    #[name = "ref"]
+    pub reference: String,
 
-/// Provides arbitrary additional information about an element.
+    /// The new value of the parameter.
+    #[child]
+    pub value: ParamValue,
+}
+
+/// A shader program bound to one of the stages of a [`Pass`].
 ///
-/// COLLADA allows for applications to provide extra information about any given piece of data,
-/// including application-specific information that's not part of the COLLADA specification. This
-/// data can be any syntactically valid XML data, and is not parsed as part of this library, save
-/// for a few specific 3rd party applications that are directly supported.
+/// [`Pass`]: ./struct.Pass.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "shader"]
+pub struct Shader {
+    /// The pipeline stage that the shader runs in.
+    #[attribute]
+    pub stage: ShaderStage,
+
+    #[child]
+    pub annotations: Vec<Annotate>,
+
+    /// The profile to compile the shader for, e.g. `"ARBVP1"`.
+    #[child]
+    pub compiler_target: Option<String>,
+
+    /// Additional options to pass to the compiler.
+    #[child]
+    pub compiler_options: Option<String>,
+
+    /// The entry point of the shader.
+    #[child]
+    pub name: ShaderName,
+
+    /// The values bound to the shader's uniform parameters.
+    #[child]
+    pub binds: Vec<ShaderBind>,
+
+    /// Arbitrary additional information about this shader and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Shader {
+    /// Returns the value bound to the uniform parameter `symbol`, if there is one.
+    pub fn bind(&self, symbol: &str) -> Option<&ShaderBindValue> {
+        self.binds.iter().find(|bind| bind.symbol == symbol).map(|bind| &bind.value)
+    }
+}
+
+/// Binds a value to a uniform parameter of a [`Shader`](./struct.Shader.html).
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "bind"]
+pub struct ShaderBind {
+    /// The name of the uniform parameter in the shader's source code.
+    #[attribute]
+    pub symbol: String,
+
+    /// The value bound to the parameter.
+    #[child]
+    pub value: ShaderBindValue,
+}
+
+/// The value of a [`ShaderBind`](./struct.ShaderBind.html).
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum ShaderBindValue {
+    Param(ParamRef),
+    Value(ParamValue),
+}
+
+/// Shader source code, either embedded in the document or included from another file.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum ShaderCode {
+    Code(Code),
+    Include(Include),
+}
+
+impl ShaderCode {
+    /// Returns the sid that shaders use to refer to the code, if it has one.
+    pub fn sid(&self) -> Option<&str> {
+        match *self {
+            ShaderCode::Code(ref code) => code.sid.as_deref(),
+            ShaderCode::Include(ref include) => Some(&*include.sid),
+        }
+    }
+}
+
+/// The entry point of a [`Shader`](./struct.Shader.html).
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+#[name = "name"]
+pub struct ShaderName {
+    /// The sid of the [`Code`](./struct.Code.html) or [`Include`](./struct.Include.html)
+    /// containing the entry point, if it isn't in the shared code.
+    #[attribute]
+    pub source: Option<String>,
+
+    /// The name of the entry point function.
+    #[text]
+    pub entry_point: String,
+}
+
+/// Declares a parameter of a [`ProfileGlsl`], [`ProfileCg`], or one of their techniques.
 ///
-/// # Choosing a Technique
+/// [`ProfileGlsl`]: ./struct.ProfileGlsl.html
+/// [`ProfileCg`]: ./struct.ProfileCg.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "newparam"]
+pub struct ShaderNewParam {
+    /// The identifier of the parameter within the profile.
+    #[attribute]
+    pub sid: String,
+
+    #[child]
+    pub annotations: Vec<Annotate>,
+
+    /// A semantic for binding the parameter to a value provided by the application, e.g.
+    /// `"WORLDVIEWPROJECTION"`.
+    #[child]
+    pub semantic: Option<String>,
+
+    /// A storage modifier for the parameter, e.g. `"UNIFORM"`.
+    #[child]
+    pub modifier: Option<String>,
+
+    /// The type and value of the parameter.
+    #[child]
+    pub value: ParamValue,
+}
+
+/// Overrides the value of a parameter within a [`ShaderTechnique`].
 ///
-/// There may be more than one [`Technique`][Technique] provided in `techniques`, but generally
-/// only one is used by the consuming application. The application should pick a technique
-/// with a supported profile. If there are multiple techniques with supported profiles the
-/// application is free to pick whichever technique is preferred.
+/// [`ShaderTechnique`]: ./struct.ShaderTechnique.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "setparam"]
+pub struct ShaderSetParam {
+    /// The sid of the parameter to override.
+    #[attribute]
+    #[name = "ref"]
+    pub reference: String,
+
+    /// The Cg program the parameter belongs to, if any.
+    #[attribute]
+    pub program: Option<String>,
+
+    #[child]
+    pub annotations: Vec<Annotate>,
+
+    /// The new value of the parameter.
+    #[child]
+    pub value: ShaderSetParamValue,
+}
+
+/// The value of a [`ShaderSetParam`](./struct.ShaderSetParam.html).
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum ShaderSetParamValue {
+    ConnectParam(ConnectParam),
+    Value(ParamValue),
+}
+
+/// The pipeline stage that a [`Shader`](./struct.Shader.html) runs in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShaderStage {
+    /// The shader transforms vertices, written `VERTEXPROGRAM` for GLSL and `VERTEX` for Cg.
+    Vertex,
+
+    /// The shader colors fragments, written `FRAGMENTPROGRAM` for GLSL and `FRAGMENT` for Cg.
+    Fragment,
+}
+
+impl ::std::str::FromStr for ShaderStage {
+    type Err = ErrorKind;
+
+    fn from_str(source: &str) -> ::std::result::Result<ShaderStage, ErrorKind> {
+        match source {
+            "VERTEXPROGRAM" | "VERTEX" => Ok(ShaderStage::Vertex),
+            "FRAGMENTPROGRAM" | "FRAGMENT" => Ok(ShaderStage::Fragment),
+            _ => Err(ErrorKind::InvalidValue {
+                element: "shader",
+                value: source.into(),
+            }),
+        }
+    }
+}
+
+/// Stages are always written using the GLSL names, since the profile of the shader isn't known.
+impl WriteValue for ShaderStage {
+    fn write_value(&self, output: &mut String, _: &WriteOptions) {
+        output.push_str(match *self {
+            ShaderStage::Vertex => "VERTEXPROGRAM",
+            ShaderStage::Fragment => "FRAGMENTPROGRAM",
+        });
+    }
+}
+
+/// A technique of a [`ProfileGlsl`] or [`ProfileCg`], describing how to render the effect as a
+/// series of passes.
 ///
-/// [Technique]: struct.Technique.html
-#[derive(Debug, Clone, Default, PartialEq, ColladaElement)]
-#[name = "extra"]
-pub struct Extra {
-    /// The identifier of the element, if present. Will be unique within the document.
+/// [`ProfileGlsl`]: ./struct.ProfileGlsl.html
+/// [`ProfileCg`]: ./struct.ProfileCg.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "technique"]
+pub struct ShaderTechnique {
+    /// A unique identifier for the technique.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// An identifier for the technique that's unique within its profile.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// Metadata about the technique. Only Cg techniques have an asset.
+    #[child]
+    pub asset: Option<Asset>,
+
+    #[child]
+    pub annotations: Vec<Annotate>,
+
+    /// The shader source code used by the technique.
+    #[child]
+    pub code: Vec<ShaderCode>,
+
+    /// The images and parameters used by the technique, in document order.
+    #[child]
+    pub params: Vec<TechniqueParam>,
+
+    /// The rendering passes, in the order they should be rendered. There will always be at least
+    /// one pass.
+    #[child]
+    #[required]
+    pub passes: Vec<Pass>,
+
+    /// Arbitrary additional information about this technique and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl ShaderTechnique {
+    /// Returns the pass with the given sid, if the technique has one.
+    pub fn pass(&self, sid: &str) -> Option<&Pass> {
+        self.passes.iter().find(|pass| pass.sid.as_deref() == Some(sid))
+    }
+}
+
+/// A shading model of unknown type.
+///
+/// Each variant wraps a single value containing the inputs to a given shading model. The
+/// accessor methods return an input regardless of which model is used, or `None` if the model
+/// doesn't have that input.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum ShadingModel {
+    Blinn(Blinn),
+    Constant(Constant),
+    Lambert(Lambert),
+    Phong(Phong),
+}
+
+impl ShadingModel {
+    /// Returns the amount of light emitted from the surface.
+    pub fn emission(&self) -> Option<&ColorOrTexture> {
+        match *self {
+            ShadingModel::Blinn(ref blinn) => blinn.emission.as_ref(),
+            ShadingModel::Constant(ref constant) => constant.emission.as_ref(),
+            ShadingModel::Lambert(ref lambert) => lambert.emission.as_ref(),
+            ShadingModel::Phong(ref phong) => phong.emission.as_ref(),
+        }
+    }
+
+    /// Returns the amount of ambient light reflected from the surface.
+    pub fn ambient(&self) -> Option<&ColorOrTexture> {
+        match *self {
+            ShadingModel::Blinn(ref blinn) => blinn.ambient.as_ref(),
+            ShadingModel::Constant(_) => None,
+            ShadingModel::Lambert(ref lambert) => lambert.ambient.as_ref(),
+            ShadingModel::Phong(ref phong) => phong.ambient.as_ref(),
+        }
+    }
+
+    /// Returns the amount of light diffusely reflected from the surface.
+    pub fn diffuse(&self) -> Option<&ColorOrTexture> {
+        match *self {
+            ShadingModel::Blinn(ref blinn) => blinn.diffuse.as_ref(),
+            ShadingModel::Constant(_) => None,
+            ShadingModel::Lambert(ref lambert) => lambert.diffuse.as_ref(),
+            ShadingModel::Phong(ref phong) => phong.diffuse.as_ref(),
+        }
+    }
+
+    /// Returns the color of light reflected specularly from the surface.
+    pub fn specular(&self) -> Option<&ColorOrTexture> {
+        match *self {
+            ShadingModel::Blinn(ref blinn) => blinn.specular.as_ref(),
+            ShadingModel::Phong(ref phong) => phong.specular.as_ref(),
+            ShadingModel::Constant(_) | ShadingModel::Lambert(_) => None,
+        }
+    }
+
+    /// Returns the sharpness of the specular highlight.
+    pub fn shininess(&self) -> Option<&FloatOrParam> {
+        match *self {
+            ShadingModel::Blinn(ref blinn) => blinn.shininess.as_ref(),
+            ShadingModel::Phong(ref phong) => phong.shininess.as_ref(),
+            ShadingModel::Constant(_) | ShadingModel::Lambert(_) => None,
+        }
+    }
+
+    /// Returns the color of a perfect mirror reflection.
+    pub fn reflective(&self) -> Option<&ColorOrTexture> {
+        match *self {
+            ShadingModel::Blinn(ref blinn) => blinn.reflective.as_ref(),
+            ShadingModel::Constant(ref constant) => constant.reflective.as_ref(),
+            ShadingModel::Lambert(ref lambert) => lambert.reflective.as_ref(),
+            ShadingModel::Phong(ref phong) => phong.reflective.as_ref(),
+        }
+    }
+
+    /// Returns the amount of perfect mirror reflection to be added to the reflected light.
+    pub fn reflectivity(&self) -> Option<&FloatOrParam> {
+        match *self {
+            ShadingModel::Blinn(ref blinn) => blinn.reflectivity.as_ref(),
+            ShadingModel::Constant(ref constant) => constant.reflectivity.as_ref(),
+            ShadingModel::Lambert(ref lambert) => lambert.reflectivity.as_ref(),
+            ShadingModel::Phong(ref phong) => phong.reflectivity.as_ref(),
+        }
+    }
+
+    /// Returns the color of perfectly refracted light.
+    pub fn transparent(&self) -> Option<&Transparent> {
+        match *self {
+            ShadingModel::Blinn(ref blinn) => blinn.transparent.as_ref(),
+            ShadingModel::Constant(ref constant) => constant.transparent.as_ref(),
+            ShadingModel::Lambert(ref lambert) => lambert.transparent.as_ref(),
+            ShadingModel::Phong(ref phong) => phong.transparent.as_ref(),
+        }
+    }
+
+    /// Returns the amount of perfectly refracted light added to the reflected color.
+    pub fn transparency(&self) -> Option<&FloatOrParam> {
+        match *self {
+            ShadingModel::Blinn(ref blinn) => blinn.transparency.as_ref(),
+            ShadingModel::Constant(ref constant) => constant.transparency.as_ref(),
+            ShadingModel::Lambert(ref lambert) => lambert.transparency.as_ref(),
+            ShadingModel::Phong(ref phong) => phong.transparency.as_ref(),
+        }
+    }
+
+    /// Returns how the surface is blended with whatever is behind it.
+    ///
+    /// A missing `transparency` defaults to `1.0`, and a surface without a `transparent` input
+    /// is opaque. Returns `None` if either input refers to a parameter, since parameters can't
+    /// be resolved without the enclosing effect.
+    ///
+    /// Note that some exporters write a `transparency` of `0.0` for opaque surfaces, which
+    /// results in a fully transparent surface when interpreted according to the specification.
+    ///
+    /// ```
+    /// # use collaborate::v1_4::*;
+    /// # let collada = Collada::from_str(r#"
+    /// # <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+    /// #     <asset>
+    /// #         <created>2017-02-07T20:44:30Z</created>
+    /// #         <modified>2017-02-07T20:44:30Z</modified>
+    /// #     </asset>
+    /// #     <library_effects>
+    /// #         <effect id="glass">
+    /// #             <profile_COMMON>
+    /// #                 <technique sid="common">
+    /// #                     <lambert>
+    /// #                         <transparent opaque="A_ONE">
+    /// #                             <color>1 1 1 0.5</color>
+    /// #                         </transparent>
+    /// #                     </lambert>
+    /// #                 </technique>
+    /// #             </profile_COMMON>
+    /// #         </effect>
+    /// #     </library_effects>
+    /// # </COLLADA>
+    /// # "#).unwrap();
+    /// # let shading_model = collada.libraries[0].as_library_effects().unwrap()
+    /// #     .effect("glass").unwrap()
+    /// #     .common_shading().unwrap();
+    /// let blend = shading_model.blend().unwrap();
+    /// assert_eq!(Blend::Constant([0.5; 3]), blend);
+    /// assert_eq!(Some(0.5), blend.alpha());
+    /// ```
+    pub fn blend<'a>(&'a self) -> Option<Blend<'a>> {
+        let transparency = match self.transparency() {
+            Some(transparency) => transparency.as_float()?,
+            None => 1.0,
+        };
+        let transparent = match self.transparent() {
+            Some(transparent) => transparent,
+            None => return Some(Blend::Opaque),
+        };
+
+        match transparent.value {
+            ColorOrTexture::Color(ref color) => {
+                let opacity = transparent.opaque.opacity(color.rgba()?, transparency);
+                if opacity.iter().all(|&channel| channel >= 1.0) {
+                    Some(Blend::Opaque)
+                } else {
+                    Some(Blend::Constant(opacity))
+                }
+            }
+
+            ColorOrTexture::Texture(ref texture) => Some(Blend::Texture {
+                texture,
+                opaque: transparent.opaque,
+                transparency,
+            }),
+
+            ColorOrTexture::Param(_) => None,
+        }
+    }
+
+    /// Returns the index of refraction for perfectly refracted light.
+    pub fn index_of_refraction(&self) -> Option<&FloatOrParam> {
+        match *self {
+            ShadingModel::Blinn(ref blinn) => blinn.index_of_refraction.as_ref(),
+            ShadingModel::Constant(ref constant) => constant.index_of_refraction.as_ref(),
+            ShadingModel::Lambert(ref lambert) => lambert.index_of_refraction.as_ref(),
+            ShadingModel::Phong(ref phong) => phong.index_of_refraction.as_ref(),
+        }
+    }
+}
+
+/// A component of the collision volume of a rigid body.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "shape"]
+pub struct Shape {
+    /// Whether the shape is hollow, which defaults to `false`.
+    #[child]
+    pub hollow: Option<Hollow>,
+
+    /// The mass of the shape.
+    #[child]
+    pub mass: Option<Mass>,
+
+    /// The density of the shape.
+    #[child]
+    pub density: Option<Density>,
+
+    /// The physics material of the shape.
+    ///
+    /// If `None`, the shape uses the physics material of its rigid body.
+    #[child]
+    pub physics_material: Option<PhysicsMaterialSource>,
+
+    /// The geometry of the shape.
+    #[child]
+    pub geometry: ShapeGeometry,
+
+    /// The transforms positioning the shape within its rigid body, in the order they appear in
+    /// the document.
+    #[child]
+    pub transforms: Vec<RigidTransform>,
+
+    /// Arbitrary additional information about this shape and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// The geometry of a [`Shape`](./struct.Shape.html).
+///
+/// The shape is either one of the analytical shapes, or a geometry from the document, e.g. a
+/// [`ConvexMesh`](./struct.ConvexMesh.html).
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum ShapeGeometry {
+    Box(BoxShape),
+    Capsule(Capsule),
+    Cylinder(Cylinder),
+    InstanceGeometry(InstanceGeometry),
+    Plane(Plane),
+    Sphere(Sphere),
+    TaperedCapsule(TaperedCapsule),
+    TaperedCylinder(TaperedCylinder),
+}
+
+/// Declares the input semantic of a data source and connects a consumer of that source.
+///
+/// `SharedInput` declares the input connection to a data source that a consumer requires. A data
+/// source is a container of raw data that lacks semantic meaning, so that the data can be
+/// reused within the document. To use the data, a consumer declares a connection to it with the
+/// desired semantic information.
+///
+/// In COLLADA, all inputs are driven by index values. A consumer samples an input by supplying
+/// an index value to an input. Some consumers have multiple inputs that can share the same index
+/// values. Inputs that have the same `offset` value are driven by the same index value from the
+/// consumer. This is an optimization that reduces the total number of indexes that the consumer
+/// must store. These inputs are described in this section as shared inputs but otherwise
+/// operate in the same manner as unshared inputs.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "input"]
+pub struct SharedInput {
+    /// The offset into the list of indices provided by the parent object.
+    ///
+    /// If two `SharedInput` instances share the same `offset` value, they are indexed the same.
+    /// This is a simple form of compression for the list of indices and also defines the order
+    /// in which inputs are used.
     #[attribute]
-    pub id: Option<String>,
+    pub offset: usize,
 
-    /// The text string name of the element, if present.
+    /// The meaning of the input connnection, e.g. `Semantic::Position`.
+    ///
+    /// See [`Semantic`](../common/enum.Semantic.html) for the semantics defined by the COLLADA
+    /// specification.
     #[attribute]
-    pub name: Option<String>,
+    pub semantic: Semantic,
 
-    /// A hint as to the type of information this element represents, if present. Must be
-    /// must be understood by the consuming application.
+    /// The location of the data source.
     #[attribute]
-    #[name = "type"]
-    pub type_hint: Option<String>,
+    pub source: UriFragment,
 
-    /// Asset-management information for this element, if present.
+    /// Which inputs to group as a single set.
     ///
-    /// While this is technically allowed in all `<extra>` elements, it is likely only present in
-    /// elements that describe a new "asset" of some kind, rather than in `<extra>` elements that
-    /// provide application-specific information about an existing one.
-    #[child]
-    pub asset: Option<Asset>,
+    /// This is helpful when multiple inputs share the same semantic.
+    #[attribute]
+    pub set: Option<usize>,
+}
 
-    /// The arbitrary additional information, containing unprocessed XML events. There will always
-    /// be at least one item in `techniques`.
-    #[child]
-    #[required]
-    pub techniques: Vec<Technique>,
+impl SharedInput {
+    /// Returns the set the input belongs to.
+    ///
+    /// This is the `set` attribute if there is one, otherwise it's the set number written as part
+    /// of the semantic (e.g. `"TEXCOORD1"`), which some exporters use instead.
+    pub fn set_index(&self) -> Option<usize> {
+        self.set.or_else(|| self.semantic.set_index())
+    }
 }
 
+/// Skews a node by rotating one axis towards another.
+///
+/// Contains 7 values: the angle of the skew in degrees, the axis that's rotated, and the axis
+/// it's rotated towards, in that order.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "float_array"]
-pub struct FloatArray {
+#[name = "skew"]
+pub struct Skew {
+    /// An identifier for the transform, so that it can be targeted by animations.
     #[attribute]
-    pub count: usize,
+    pub sid: Option<String>,
 
-    #[attribute]
-    pub id: Option<String>,
+    /// The angle and axes of the skew.
+    #[text]
+    pub values: Vec<f32>,
+}
 
-    #[attribute]
-    pub name: Option<String>,
+impl Skew {
+    /// Returns the angle of the skew in degrees, the rotation axis, and the translation axis.
+    ///
+    /// Returns `None` if the element doesn't contain exactly 7 values.
+    pub fn parts(&self) -> Option<(f32, [f32; 3], [f32; 3])> {
+        match *self.values {
+            [angle, rx, ry, rz, tx, ty, tz] => Some((angle, [rx, ry, rz], [tx, ty, tz])),
+            _ => None,
+        }
+    }
 
-    #[attribute]
-    #[optional_with_default = "6"]
-    pub digits: usize,
+    /// Returns the transform as a 4x4 matrix in row-major order.
+    ///
+    /// Also returns `None` if the angle is too large for the rotation axis to be skewed that far
+    /// towards the translation axis.
+    pub fn matrix(&self) -> Option<[f32; 16]> {
+        let (angle, rotation, translation) = self.parts()?;
+
+        // Split the rotation axis into the components parallel and perpendicular to the
+        // translation axis, and work out how far the perpendicular axis needs to be sheared
+        // along the translation axis for the rotation axis to end up at `angle`.
+        let n2 = normalize(translation);
+        let parallel = dot(rotation, n2);
+        let perpendicular = sub(rotation, [n2[0] * parallel, n2[1] * parallel, n2[2] * parallel]);
+        if dot(perpendicular, perpendicular) == 0.0 {
+            return Some(IDENTITY);
+        }
 
-    #[attribute]
-    #[optional_with_default = "38"]
-    pub magnitude: usize,
+        let n1 = normalize(perpendicular);
+        let an1 = dot(rotation, n1);
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let rx = an1 * cos - parallel * sin;
+        let ry = an1 * sin + parallel * cos;
+        if rx <= 0.0 {
+            return None;
+        }
 
-    #[text]
-    pub data: Vec<f32>,
+        let alpha = ry / rx - parallel / an1;
+        let mut matrix = IDENTITY;
+        for row in 0..3 {
+            for column in 0..3 {
+                matrix[row * 4 + column] += alpha * n2[row] * n1[column];
+            }
+        }
+        Some(matrix)
+    }
 }
 
-/// A geometric element of unknown type.
+/// Binds a mesh to a skeleton, so that moving the skeleton's joints deforms the mesh.
 ///
-/// Each variant wraps a single value containing a given type of geometric data. See the
-/// documentation for each of the possible geometric types for more information.
+/// The skin's [`Joints`] name the joints and give each one's inverse bind matrix, and its
+/// [`VertexWeights`] give the joints influencing each vertex of the mesh. [`joint_names`],
+/// [`inverse_bind_matrices`], and [`influences`] look up the data for each in the skin's sources.
+///
+/// [`Joints`]: ./struct.Joints.html
+/// [`VertexWeights`]: ./struct.VertexWeights.html
+/// [`joint_names`]: #method.joint_names
+/// [`inverse_bind_matrices`]: #method.inverse_bind_matrices
+/// [`influences`]: #method.influences
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-pub enum GeometricElement {
-    ConvexMesh(ConvexMesh),
-    Mesh(Mesh),
-    Spline(Spline),
-}
+#[name = "skin"]
+pub struct Skin {
+    /// The mesh being skinned.
+    #[attribute]
+    pub source: AnyUri,
 
-impl GeometricElement {
-    /// Attempts to downcast the geometric element to a [`ConvexMesh`].
-    ///
-    /// Returns a reference to the inner [`ConvexMesh`] if there is one, returns `None` otherwise.
-    /// This is useful if you have a `GeometricElement` but only want to use it if it represents a
-    /// [`ConvexMesh`].
-    ///
-    /// # Examples
+    /// The transform applied to the mesh before it's bound to the joints, if it isn't the
+    /// identity.
+    #[child]
+    pub bind_shape_matrix: Option<BindShapeMatrix>,
+
+    /// The data for the joints and the vertex weights.
     ///
-    /// ```
-    /// # use ::collaborate::v1_4::*;
-    /// # static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
-    /// # let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
-    /// # let document = Collada::from_str(&*source).unwrap();
-    /// # let library_geometries = document.libraries[5].as_library_geometries().unwrap();
-    /// let geometry = &library_geometries.geometries[0];
-    /// if let Some(mesh) = geometry.geometric_element.as_convex_mesh() {
-    ///     // Do something with `mesh`.
-    /// }
-    /// ```
+    /// There will always be at least one source in a `Skin`.
+    #[child]
+    #[required]
+    pub sources: Vec<Source>,
+
+    /// The joints of the skeleton that the mesh is bound to.
+    #[child]
+    pub joints: Joints,
+
+    /// The joints influencing each vertex of the mesh.
+    #[child]
+    pub vertex_weights: VertexWeights,
+
+    /// Arbitrary additional information about this skin and the data it contains.
     ///
-    /// [`ConvexMesh`]: ./struct.ConvexMesh.html
-    pub fn as_convex_mesh(&self) -> Option<&ConvexMesh> {
-        match *self {
-            GeometricElement::ConvexMesh(ref mesh) => Some(mesh),
-            _ => None,
-        }
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Skin {
+    /// Returns the source in this skin with the given id, if there is one.
+    pub fn source(&self, id: &str) -> Option<&Source> {
+        self.sources.iter().find(|source| source.id == id)
     }
 
-    /// Attempts to downcast the geometric element to a [`Mesh`].
+    /// Returns the names of the joints, in the order that joints are indexed by the rest of the
+    /// skin.
     ///
-    /// Returns a reference to the inner [`Mesh`] if there is one, returns `None` otherwise. This
-    /// is useful if you have a `GeometricElement` but only want to use it if it represents a
-    /// [`Mesh`].
+    /// Returns `None` if the joints don't have a `"JOINT"` input, or if its source doesn't hold a
+    /// `<Name_array>`.
+    pub fn joint_names(&self) -> Option<&[String]> {
+        let input = self.joints.input("JOINT")?;
+        let array = self.source(input.source.id())?.array.as_ref()?;
+        array.as_name_array().map(|array| &*array.data)
+    }
+
+    /// Returns the inverse bind matrix for each joint, each as 16 values of a 4x4 matrix in
+    /// row-major order.
     ///
-    /// # Examples
+    /// Returns `None` if the joints don't have an `"INV_BIND_MATRIX"` input, or if its source
+    /// doesn't hold a `<float_array>`.
     ///
-    /// ```
-    /// # use ::collaborate::v1_4::*;
-    /// # static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
-    /// # let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
-    /// # let document = Collada::from_str(&*source).unwrap();
-    /// # let library_geometries = document.libraries[5].as_library_geometries().unwrap();
-    /// let geometry = &library_geometries.geometries[0];
-    /// if let Some(mesh) = geometry.geometric_element.as_mesh() {
-    ///     // Do something with `mesh`.
-    /// }
-    /// ```
+    /// # Errors
     ///
-    /// [`Mesh`]: ./struct.Mesh.html
-    pub fn as_mesh(&self) -> Option<&Mesh> {
-        match *self {
-            GeometricElement::Mesh(ref mesh) => Some(mesh),
-            _ => None,
-        }
+    /// Returns `Err` if the values in the array are malformed.
+    pub fn inverse_bind_matrices(&self) -> Option<Result<Vec<[f32; 16]>>> {
+        let input = self.joints.input("INV_BIND_MATRIX")?;
+        let array = self.source(input.source.id())?.array.as_ref()?.as_float_array()?;
+        Some(array.data.get().map(|values| {
+            values.chunks(16)
+                .filter(|chunk| chunk.len() == 16)
+                .map(|chunk| {
+                    let mut matrix = [0.0; 16];
+                    matrix.copy_from_slice(chunk);
+                    matrix
+                })
+                .collect()
+        }))
     }
 
-    /// Attempts to downcast the geometric element to a [`Spline`].
-    ///
-    /// Returns a reference to the inner [`Spline`] if there is one, returns `None` otherwise. This
-    /// is useful if you have a `GeometricElement` but only want to use it if it represents a
-    /// [`Spline`].
+    /// Returns the joints influencing each vertex of the mesh, with their weights looked up in
+    /// the skin's `"WEIGHT"` source.
     ///
-    /// # Examples
+    /// Returns `None` if the vertex weights don't have `"JOINT"` and `"WEIGHT"` inputs, if the
+    /// weight source doesn't hold a `<float_array>`, or if any of the indices are out of range.
     ///
-    /// ```
-    /// # use ::collaborate::v1_4::*;
-    /// # static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
-    /// # let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
-    /// # let document = Collada::from_str(&*source).unwrap();
-    /// # let library_geometries = document.libraries[5].as_library_geometries().unwrap();
-    /// let geometry = &library_geometries.geometries[0];
-    /// if let Some(spline) = geometry.geometric_element.as_spline() {
-    ///     // Do something with `spline`.
-    /// }
-    /// ```
+    /// # Errors
     ///
-    /// [`Spline`]: ./struct.Spline.html
-    pub fn as_spline(&self) -> Option<&Spline> {
-        match *self {
-            GeometricElement::Spline(ref mesh) => Some(mesh),
-            _ => None,
+    /// Returns `Err` if the values in the weight array are malformed.
+    pub fn influences(&self) -> Option<Result<Vec<Vec<Influence>>>> {
+        let vertex_weights = &self.vertex_weights;
+        let joint_offset = vertex_weights.input("JOINT")?.offset;
+        let weight_input = vertex_weights.input("WEIGHT")?;
+        let weights = self.source(weight_input.source.id())?.array.as_ref()?.as_float_array()?;
+        let weights = match weights.data.get() {
+            Ok(weights) => weights,
+            Err(error) => { return Some(Err(error)); }
+        };
+
+        let stride = vertex_weights.stride();
+        let mut influences = Vec::with_capacity(vertex_weights.count);
+        for indices in vertex_weights.iter() {
+            let vertex = indices.chunks(stride)
+                .map(|indices| {
+                    let joint = indices[joint_offset];
+                    let weight = indices[weight_input.offset];
+                    if weight < 0 {
+                        return None;
+                    }
+
+                    Some(Influence {
+                        joint: if joint < 0 { None } else { Some(joint as usize) },
+                        weight: *weights.get(weight as usize)?,
+                    })
+                })
+                .collect::<Option<Vec<_>>>()?;
+            influences.push(vertex);
+        }
+
+        // The iterator stops early if there aren't enough indices for every vertex.
+        if influences.len() != vertex_weights.vcount.as_ref().map_or(0, |vcount| vcount.len()) {
+            return None;
         }
+
+        Some(Ok(influences))
     }
 }
 
-/// Describes the visual shape and appearance of an object in a scene.
-///
-/// The primary purpose of `Geometry` is to provide access to a [`GeometricElement`], via its
-/// `geographic_element` member. It contains miscellaneous additional data, such as asset
-/// metadata, but otherwise does not directly contain any geometric data.
-///
-/// # Examples
-///
-/// ```
-/// # use ::collaborate::v1_4::*;
-/// # static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
-/// # let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
-/// # let document = Collada::from_str(&*source).unwrap();
-/// # let library_geometries = document.libraries[5].as_library_geometries().unwrap();
-/// let geometry = &library_geometries.geometries[0];
-/// match geometry.geometric_element {
-///     GeometricElement::ConvexMesh(ref mesh) => {
-///         // Do something with `mesh`.
-///     }
-///
-///     GeometricElement::Mesh(ref mesh) => {
-///         // Do something with `mesh`.
-///     }
-///
-///     GeometricElement::Spline(ref spline) => {
-///         // Do something with `spline`.
-///     }
-/// }
-/// ```
+/// An element that can be targeted by a [`SidRef`], e.g. by an animation channel.
 ///
-/// [`GeometricElement`]: ./enum.GeometricElement.html
+/// [`SidRef`]: ../common/struct.SidRef.html
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SidTarget<'a> {
+    Camera(&'a Camera),
+    Color(&'a Color),
+    Effect(&'a Effect),
+    Float(&'a f32),
+    Light(&'a Light),
+    Node(&'a Node),
+    Transform(&'a Transform),
+}
+
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "geometry"]
-pub struct Geometry {
-    /// A unique identifier for the geometry instance.
-    ///
-    /// Will be unique within the document.
+#[name = "size"]
+pub struct Size;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "source"]
+pub struct Source {
     #[attribute]
-    pub id: Option<String>,
+    pub id: String,
 
-    /// The human-friendly name for this geometry instance.
-    ///
-    /// Has no semantic meaning.
     #[attribute]
     pub name: Option<String>,
 
-    /// Metadata about this geometry instance and the data it contains.
     #[child]
     pub asset: Option<Asset>,
 
-    /// The actual data for the geometry instance.
     #[child]
-    pub geometric_element: GeometricElement,
+    pub array: Option<Array>,
 
-    /// Arbitrary additional information about this geometry instance and the data it contains.
+    /// The accessor from the source's `<technique_common>`, describing how to read the array.
+    #[child(flatten = "technique_common")]
+    pub accessor: Option<Accessor>,
+
+    #[child]
+    pub techniques: Vec<Technique>,
+}
+
+impl Source {
+    // Returns the [`Accessor`] in the source's `technique_common` member.
+    pub fn common_accessor(&self) -> Option<&Accessor> {
+        self.accessor.as_ref()
+    }
+}
+
+/// A sphere shape, centered on the origin.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "sphere"]
+pub struct Sphere {
+    /// The radius of the sphere.
+    #[child]
+    pub radius: SphereRadius,
+
+    /// Arbitrary additional information about this shape and the data it contains.
     ///
     /// For more information about 3rd-party extensions, see the
     /// [crate-level documentation](../index.html#3rd-party-extensions).
     #[child]
-    pub extra: Vec<Extra>,
+    pub extras: Vec<Extra>,
+}
+
+targetable_float! {
+    /// The radius of a [`Sphere`](./struct.Sphere.html).
+    pub struct SphereRadius, "radius"
 }
 
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "IDREF_array"]
-pub struct IdrefArray;
+#[name = "spline"]
+pub struct Spline;
+
+/// A light that shines in a cone from a single point, e.g. a flashlight.
+///
+/// The light shines down the local negative z axis of the node that instantiates it. It's
+/// attenuated with distance in the same way as a [`Point`](./struct.Point.html) light.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "spot"]
+pub struct Spot {
+    /// The color of the light.
+    #[child]
+    pub color: Color,
+
+    /// The constant attenuation factor, which defaults to 1.
+    #[child]
+    pub constant_attenuation: Option<ConstantAttenuation>,
+
+    /// The linear attenuation factor, which defaults to 0.
+    #[child]
+    pub linear_attenuation: Option<LinearAttenuation>,
+
+    /// The quadratic attenuation factor, which defaults to 0.
+    #[child]
+    pub quadratic_attenuation: Option<QuadraticAttenuation>,
+
+    /// The angle of the light's cone in degrees, which defaults to 180.
+    #[child]
+    pub falloff_angle: Option<FalloffAngle>,
 
-#[derive(Debug, Clone)]
-pub struct InputsForOffset<'a> {
-    inputs: ::std::slice::Iter<'a, SharedInput>,
-    offset: usize,
+    /// How the light falls off towards the edge of its cone, which defaults to 0.
+    #[child]
+    pub falloff_exponent: Option<FalloffExponent>,
 }
 
-impl<'a> Iterator for InputsForOffset<'a> {
-    type Item = &'a SharedInput;
-
-    fn next(&mut self) -> Option<&'a SharedInput> {
-        while let Some(input) = self.inputs.next() {
-            if input.offset == self.offset {
-                return Some(input);
-            }
-        }
+impl Spot {
+    /// Returns the constant, linear, and quadratic attenuation factors, filling in the defaults
+    /// for any that weren't given.
+    pub fn attenuation(&self) -> (f32, f32, f32) {
+        (
+            self.constant_attenuation.as_ref().map_or(1.0, |constant| constant.value),
+            self.linear_attenuation.as_ref().map_or(0.0, |linear| linear.value),
+            self.quadratic_attenuation.as_ref().map_or(0.0, |quadratic| quadratic.value),
+        )
+    }
 
-        None
+    /// Returns the falloff angle and exponent of the light's cone, filling in the defaults for
+    /// any that weren't given.
+    pub fn falloff(&self) -> (f32, f32) {
+        (
+            self.falloff_angle.as_ref().map_or(180.0, |angle| angle.value),
+            self.falloff_exponent.as_ref().map_or(0.0, |exponent| exponent.value),
+        )
     }
 }
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "int_array"]
-pub struct IntArray;
-
-/// A single library of unknown type.
+/// The springs of a [`RigidConstraint`].
 ///
-/// Each variant wraps a single value containing the library data. See the documentation for
-/// each of the possible library types for more information on what data each can contain.
+/// [`RigidConstraint`]: ./struct.RigidConstraint.html
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-pub enum Library {
-    Animations(LibraryAnimations),
-    AnimationClips(LibraryAnimationClips),
-    Cameras(LibraryCameras),
-    Controllers(LibraryControllers),
-    Effects(LibraryEffects),
-    ForceFields(LibraryForceFields),
-    Geometries(LibraryGeometries),
-    Images(LibraryImages),
-    Lights(LibraryLights),
-    Materials(LibraryMaterials),
-    Nodes(LibraryNodes),
-    PhysicsMaterials(LibraryPhysicsMaterials),
-    PhysicsModels(LibraryPhysicsModels),
-    PhysicsScenes(LibraryPhysicsScenes),
-    VisualScenes(LibraryVisualScenes),
+#[name = "spring"]
+pub struct Spring {
+    /// The spring acting on the rotation of the attachment.
+    #[child]
+    pub angular: Option<AngularSpring>,
+
+    /// The spring acting on the position of the attachment.
+    #[child]
+    pub linear: Option<LinearSpring>,
 }
 
-impl Library {
-    pub fn as_library_geometries(&self) -> Option<&LibraryGeometries> {
-        match *self {
-            Library::Geometries(ref library_geometries) => Some(library_geometries),
-            _ => None,
-        }
-    }
+targetable_float! {
+    /// The friction of a [`PhysicsMaterial`](./struct.PhysicsMaterial.html) that has to be
+    /// overcome before it starts sliding against another surface.
+    pub struct StaticFriction, "static_friction"
 }
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_animations"]
-pub struct LibraryAnimations;
+targetable_float! {
+    /// The stiffness of a spring, also known as the spring coefficient.
+    pub struct Stiffness, "stiffness"
+}
 
+/// Declares a texture surface, which is initialized from one or more [`Image`]s and read by a
+/// [`Sampler2D`].
+///
+/// [`Image`]: ./struct.Image.html
+/// [`Sampler2D`]: ./struct.Sampler2D.html
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_animation_clips"]
-pub struct LibraryAnimationClips;
+#[name = "surface"]
+pub struct Surface {
+    /// The type of the surface, e.g. `"2D"` or `"CUBE"`.
+    #[attribute]
+    #[name = "type"]
+    pub surface_type: String,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_cameras"]
-pub struct LibraryCameras;
+    #[child]
+    pub init_as_null: Option<InitAsNull>,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_controllers"]
-pub struct LibraryControllers;
+    #[child]
+    pub init_as_target: Option<InitAsTarget>,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_effects"]
-pub struct LibraryEffects;
+    #[child]
+    pub init_cube: Option<InitCube>,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_force_fields"]
-pub struct LibraryForceFields;
+    #[child]
+    pub init_volume: Option<InitVolume>,
 
-/// Contains geometric data for the document.
-///
-/// The geometric data is contained in `geometries` by one or more [`Geometry`] instances,
-/// `LibraryGeometries` is only a container and does not represent any geometric data itself.
-///
-/// [`Geometry`]: ./struct.Geometry.html
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_geometries"]
-pub struct LibraryGeometries {
-    /// A unique identifier for the library.
-    ///
-    /// Will be unique within the document.
-    #[attribute]
-    pub id: Option<String>,
+    #[child]
+    pub init_planar: Option<InitPlanar>,
 
-    /// The human-friendly name for this library.
-    ///
-    /// Has no semantic meaning.
-    #[attribute]
-    pub name: Option<String>,
+    /// The images that the surface is initialized from.
+    #[child]
+    pub init_from: Vec<InitFrom>,
 
-    /// Metada about the library and the data contained within it.
+    /// The texel format of the surface, e.g. `"A8R8G8B8"`.
     #[child]
-    pub asset: Option<Asset>,
+    pub format: Option<String>,
 
-    /// The geometric data contained within this library instance.
-    ///
-    /// There will always be at least one geometric element in a `LibraryGeometries`.
     #[child]
-    #[required]
-    pub geometries: Vec<Geometry>,
+    pub format_hint: Option<FormatHint>,
 
-    /// Arbitrary additional information about this library and the data it contains.
+    #[child]
+    pub size: Option<Size>,
+
+    #[child]
+    pub viewport_ratio: Option<ViewportRatio>,
+
+    /// The number of mipmap levels, where `0` means all of them.
+    #[child]
+    pub mip_levels: Option<usize>,
+
+    #[child]
+    pub mipmap_generate: Option<MipmapGenerate>,
+
+    /// Arbitrary additional information about this surface.
     ///
     /// For more information about 3rd-party extensions, see the
     /// [crate-level documentation](../index.html#3rd-party-extensions).
@@ -639,96 +9502,126 @@ pub struct LibraryGeometries {
     pub extras: Vec<Extra>,
 }
 
-impl LibraryGeometries {
-    /// Returns an iterator over all the [`Geometry`] objects contained in this library.
+impl Surface {
+    /// Returns the id of the image that the surface is initialized from, if it's initialized from
+    /// an image.
     ///
-    /// [`Geometry`]: ./struct.Geometry.html
-    pub fn geometries<'a>(&'a self) -> ::std::slice::Iter<'a, Geometry> {
-        self.geometries.iter()
+    /// If the surface is initialized from several images (e.g. one for each mipmap level or cube
+    /// face), this is the first of them.
+    pub fn image(&self) -> Option<&str> {
+        self.init_from.first().map(|init_from| &*init_from.image)
     }
 }
 
+/// The limits of how far the attachment of a rigid constraint can rotate around each axis, in
+/// degrees.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_images"]
-pub struct LibraryImages;
+#[name = "swing_cone_and_twist"]
+pub struct SwingConeAndTwist {
+    /// The lower limits, which default to 0.
+    #[child]
+    pub min: Option<Min>,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_lights"]
-pub struct LibraryLights;
+    /// The upper limits, which default to 0.
+    #[child]
+    pub max: Option<Max>,
+}
 
+/// A capsule shape with a different radius at each end.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_materials"]
-pub struct LibraryMaterials;
+#[name = "tapered_capsule"]
+pub struct TaperedCapsule {
+    /// The height of the capsule along its local y axis.
+    #[child]
+    pub height: Height,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_nodes"]
-pub struct LibraryNodes;
+    /// The radii of the bottom of the capsule.
+    #[child]
+    pub radius1: Radius1,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_physics_materials"]
-pub struct LibraryPhysicsMaterials;
+    /// The radii of the top of the capsule.
+    #[child]
+    pub radius2: Radius2,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_physics_models"]
-pub struct LibraryPhysicsModels;
+    /// Arbitrary additional information about this shape and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
 
+/// A cylinder shape with a different radius at each end.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_physics_scenes"]
-pub struct LibraryPhysicsScenes;
+#[name = "tapered_cylinder"]
+pub struct TaperedCylinder {
+    /// The height of the cylinder along its local y axis.
+    #[child]
+    pub height: Height,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_visual_scenes"]
-pub struct LibraryVisualScenes;
+    /// The radii of the bottom of the cylinder.
+    #[child]
+    pub radius1: Radius1,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "lines"]
-pub struct Lines;
+    /// The radii of the top of the cylinder.
+    #[child]
+    pub radius2: Radius2,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "linestrips"]
-pub struct Linestrips;
+    /// Arbitrary additional information about this shape and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
 
-/// Describes basic geometric meshes using vertex and primitive information.
-///
-/// Meshes embody a general form of geometric description that primarily includes vertex and
-/// primitive information. Vertex information is the set of attributes associated with a poin on
-/// the surface of the mesh. Each vertex includes data for attributes such as:
-///
-/// * Vertex position
-/// * Vertex color
-/// * Vertex normal
-/// * Vertex texture coordinate
+targetable_float! {
+    /// The resting position of a spring.
+    pub struct TargetValue, "target_value"
+}
+
+/// Suggests which technique of an effect to use for a given platform.
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+#[name = "technique_hint"]
+pub struct TechniqueHint {
+    /// The platform the hint applies to, e.g. `"PC-OGL"`.
+    #[attribute]
+    pub platform: Option<String>,
+
+    /// The profile containing the technique, e.g. `"GLSL"`.
+    #[attribute]
+    pub profile: Option<String>,
+
+    /// The sid of the technique to use.
+    #[attribute]
+    #[name = "ref"]
+    pub reference: String,
+}
+
+/// An image or parameter declared or overridden by a [`ShaderTechnique`].
 ///
-/// The mesh also includes a description of how the vertices are organized to form the geometric
-/// shape of the mesh. The mesh vertices are collated into geometric primitives such as polygons,
-/// triangles, or lines.
+/// [`ShaderTechnique`]: ./struct.ShaderTechnique.html
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "mesh"]
-pub struct Mesh {
-    /// One or more [`Source`] instances containing the raw mesh data.
-    ///
-    /// These contain the raw data used to specify the vertex attributes of the vertices in the
-    /// mesh. The primitives in `primitives` will index into these sources to specify the mesh.
-    ///
-    /// [`Source`]: ./struct.Source.html
-    #[child]
-    #[required]
-    pub sources: Vec<Source>,
+pub enum TechniqueParam {
+    Image(Image),
+    NewParam(ShaderNewParam),
+    SetParam(ShaderSetParam),
+}
 
-    /// Describes the mesh's vertex attributes.
-    ///
-    /// `vertices` will have the [`UnsharedInput`] which specifies the "POSITION" attribute for
-    /// the mesh's vertices. It may also specify other mesh attributes.
-    ///
-    /// [`UnsharedInput`]: ./struct.UnsharedInput.html
-    #[child]
-    pub vertices: Vertices,
+/// A texture sampled as the input to a shading model.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "texture"]
+pub struct Texture {
+    /// The sid of the sampler parameter used to sample the texture.
+    #[attribute]
+    pub texture: String,
 
-    /// Geometric primitives that assemble values from the inputs into vertex attribute data.
-    #[child]
-    pub primitives: Vec<Primitive>,
+    /// The texture coordinate semantic to sample with, which is bound to a set of texture
+    /// coordinates when the material is instantiated.
+    #[attribute]
+    pub texcoord: String,
 
-    /// Arbitrary additional information about this geometry instance and the data it contains.
+    /// Arbitrary additional information about this texture.
     ///
     /// For more information about 3rd-party extensions, see the
     /// [crate-level documentation](../index.html#3rd-party-extensions).
@@ -736,172 +9629,225 @@ pub struct Mesh {
     pub extras: Vec<Extra>,
 }
 
-impl Mesh {
-    /// Returns the source which matches `id`, or `None` if no sources match.
-    ///
-    /// # Examples
+targetable_float! {
+    /// The length of time simulated by each step of a
+    /// [`PhysicsScene`](./struct.PhysicsScene.html), in seconds.
+    pub struct TimeStep, "time_step"
+}
+
+/// A single transform of a [`Node`](./struct.Node.html).
+///
+/// Each variant wraps a single value containing the parameters of a given type of transform.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum Transform {
+    LookAt(LookAt),
+    Matrix(Matrix),
+    Rotate(Rotate),
+    Scale(Scale),
+    Skew(Skew),
+    Translate(Translate),
+}
+
+impl Transform {
+    /// Returns the sid of the transform, which animations use to target it.
+    pub fn sid(&self) -> Option<&str> {
+        match *self {
+            Transform::LookAt(ref look_at) => look_at.sid.as_deref(),
+            Transform::Matrix(ref matrix) => matrix.sid.as_deref(),
+            Transform::Rotate(ref rotate) => rotate.sid.as_deref(),
+            Transform::Scale(ref scale) => scale.sid.as_deref(),
+            Transform::Skew(ref skew) => skew.sid.as_deref(),
+            Transform::Translate(ref translate) => translate.sid.as_deref(),
+        }
+    }
+
+    /// Returns the values of the transform as they're written in the document.
+    pub fn values(&self) -> &[f32] {
+        match *self {
+            Transform::LookAt(ref look_at) => &look_at.values,
+            Transform::Matrix(ref matrix) => &matrix.values,
+            Transform::Rotate(ref rotate) => &rotate.values,
+            Transform::Scale(ref scale) => &scale.values,
+            Transform::Skew(ref skew) => &skew.values,
+            Transform::Translate(ref translate) => &translate.values,
+        }
+    }
+
+    /// Returns the transform as a 4x4 matrix in row-major order.
     ///
-    /// ```
-    /// # #![allow(unused_variables)]
-    /// # use std::fs::File;
-    /// # use collaborate::v1_4::Collada;
-    /// # let file = File::open("resources/blender_cube.dae").unwrap();
-    /// # let document = Collada::read(file).unwrap();
-    /// # let library = document.libraries[5].as_library_geometries().unwrap();
-    /// let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
-    /// let positions_source = mesh.find_source("Cube-mesh-positions");
-    /// assert!(positions_source.is_some());
-    /// ```
-    pub fn find_source<'a>(&'a self, id: &str) -> Option<&'a Source> {
-        self.sources.iter().find(|source| source.id == id)
+    /// Returns `None` if the transform has the wrong number of values for its type.
+    pub fn matrix(&self) -> Option<[f32; 16]> {
+        match *self {
+            Transform::LookAt(ref look_at) => look_at.matrix(),
+            Transform::Matrix(ref matrix) => matrix.matrix(),
+            Transform::Rotate(ref rotate) => rotate.matrix(),
+            Transform::Scale(ref scale) => scale.matrix(),
+            Transform::Skew(ref skew) => skew.matrix(),
+            Transform::Translate(ref translate) => translate.matrix(),
+        }
     }
+}
 
-    /// Returns an iterator over the primitives in the mesh.
-    pub fn primitives<'a>(&'a self) -> ::std::slice::Iter<'a, Primitive> {
-        self.primitives.iter()
+/// Moves a node along each axis.
+///
+/// Contains 3 values: the distances along the x, y, and z axes.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "translate"]
+pub struct Translate {
+    /// An identifier for the transform, so that it can be targeted by animations.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The translation distances.
+    #[text]
+    pub values: Vec<f32>,
+}
+
+impl Translate {
+    /// Returns the transform as a 4x4 matrix in row-major order.
+    ///
+    /// Returns `None` if the element doesn't contain exactly 3 values.
+    pub fn matrix(&self) -> Option<[f32; 16]> {
+        match *self.values {
+            [x, y, z] => Some([
+                1.0, 0.0, 0.0, x,
+                0.0, 1.0, 0.0, y,
+                0.0, 0.0, 1.0, z,
+                0.0, 0.0, 0.0, 1.0,
+            ]),
+            _ => None,
+        }
     }
 }
 
+/// The color of perfectly refracted light in a shading model, i.e. how see-through the surface
+/// is.
+///
+/// The transparency is combined with the `transparency` input of the shading model according to
+/// `opaque`.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "Name_array"]
-pub struct NameArray;
+#[name = "transparent"]
+pub struct Transparent {
+    /// How the transparency is encoded in the color or texture.
+    #[attribute]
+    #[optional_with_default]
+    pub opaque: Opaque,
+
+    /// The transparent color.
+    #[child]
+    pub value: ColorOrTexture,
+}
+
+impl Transparent {
+    /// Computes the opacity of the red, green, and blue channels for the given scalar
+    /// transparency.
+    ///
+    /// Returns `None` if the transparency comes from a texture or a parameter, or if the color
+    /// doesn't have three or four components.
+    pub fn opacity(&self, transparency: f32) -> Option<[f32; 3]> {
+        self.value.as_color()
+            .and_then(Color::rgba)
+            .map(|rgba| self.opaque.opacity(rgba, transparency))
+    }
+}
 
-/// Declares parametric information for its parent element.
-///
-/// A functional or programmatical format requires a means for users to specify parametric
-/// information. This information represents function parameter (argument) data.
+/// A list of individual triangles.
 ///
-/// Material shader programs may contain code representing vertex or pixel programs. These
-/// programs require parameters as part of their state information.
-///
-/// The basic declaration of a parameter describes the name, data type, and value data of the
-/// parameter. That parameter name identifies it to the function or program. The parameter type
-/// indicates the encoding of its value.
+/// Each triangle is made up of three vertices, which are stored one after the other in
+/// `primitives`.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "param"]
-pub struct Param {
-    /// The name of the parameter.
+#[name = "triangles"]
+pub struct Triangles {
+    /// A human-friendly name for this triangle list.
+    ///
+    /// Has no semantic meaning.
     #[attribute]
     pub name: Option<String>,
 
-    /// The subidentifier of this parameter.
-    ///
-    /// This value is unique within the scope of the parent element.
+    /// The number of triangle primitives in the triangle list.
     #[attribute]
-    pub sid: Option<String>,
+    pub count: usize,
 
-    /// The type of the value data.
+    /// The name of the material associated with this triangle list.
     ///
-    /// Must be understood by the application consuming the COLLADA document.
+    /// This name is bound to a material at the time of instantiaion. See [`InstanceGeometry`]
+    /// and [`BindMaterial`].
+    ///
+    /// If `None`, then the lighting and shading results are appplication-defined.
+    ///
+    /// [`InstanceGeometry`]: ./struct.InstanceGeometry.html
+    /// [`BindMaterial`]: ./struct.BindMaterial.html
     #[attribute]
-    #[name = "type"]
-    pub data_type: Option<String>,
+    pub material: Option<String>,
 
-    /// The user-defined meaning of the parameter.
-    #[attribute]
-    pub semantic: Option<String>,
-}
+    /// The input data for the triangle list.
+    #[child]
+    pub inputs: Vec<SharedInput>,
 
-#[derive(Debug, Clone)]
-pub struct Polygon<'a> {
-    len: usize,
-    chunks: ::std::slice::Chunks<'a, usize>,
+    /// A list of integers that specify the vertex attributes as indexes into the inputs.
+    #[child]
+    pub primitives: Option<Primitives>,
+
+    /// Arbitrary additional information about this triangle list and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
 }
 
-impl<'a> Polygon<'a> {
-    pub fn iter(&self) -> PolygonIter<'a> {
-        PolygonIter { chunks: self.chunks.clone() }
+impl Triangles {
+    /// Returns an iterator over the triangle primitives in the triangle list, each yielded as a
+    /// [`Polygon`](./struct.Polygon.html) with three vertices.
+    pub fn iter<'a>(&'a self) -> FixedPolygonIter<'a> {
+        FixedPolygonIter::new(self.primitives.as_ref(), 3, vertex_stride(&self.inputs))
     }
 
-    /// Returns the number of vertices in this polygon.
+    /// Returns the number of triangle primitives in the triangle list.
     pub fn len(&self) -> usize {
-        self.len
-    }
-}
-
-impl<'a> ::std::iter::IntoIterator for Polygon<'a> {
-    type Item = Vertex<'a>;
-    type IntoIter = PolygonIter<'a>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        PolygonIter { chunks: self.chunks }
+        self.count
     }
-}
-
-impl<'a> ::std::iter::IntoIterator for &'a Polygon<'a> {
-    type Item = Vertex<'a>;
-    type IntoIter = PolygonIter<'a>;
 
-    fn into_iter(self) -> Self::IntoIter {
-        PolygonIter { chunks: self.chunks.clone() }
+    /// Returns an iterator yielding all inputs that match `offset`.
+    ///
+    /// See [`Polylist::inputs_for_offset`](./struct.Polylist.html#method.inputs_for_offset) for
+    /// more information.
+    pub fn inputs_for_offset<'a>(&'a self, offset: usize) -> InputsForOffset<'a> {
+        InputsForOffset {
+            inputs: self.inputs.iter(),
+            offset,
+        }
     }
 }
 
-pub struct PolygonIter<'a> {
-    chunks: ::std::slice::Chunks<'a, usize>,
-}
-
-impl<'a> ::std::iter::Iterator for PolygonIter<'a> {
-    type Item = Vertex<'a>;
+impl<'a> ::std::iter::IntoIterator for &'a Triangles {
+    type Item = Polygon<'a>;
+    type IntoIter = FixedPolygonIter<'a>;
 
-    fn next(&mut self) -> Option<Vertex<'a>> {
-        self.chunks.next().map(|attributes| Vertex { attributes })
+    fn into_iter(self) -> FixedPolygonIter<'a> {
+        self.iter()
     }
 }
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "polygons"]
-pub struct Polygons;
-
-/// A list of polygons that are not necessarily triangles.
-///
-/// Provides the information needed for a mesh to bind vertex attributes together and then
-/// organize those vertices into individual polygons. `Polylist` provides functionality for
-/// iterating over the polygons it represents.
-///
-/// # Examples
-///
-/// Iterate over all of the polygons in a polylist, then iterate over each vertex in each polygon:
+/// A list of triangle fans.
 ///
-/// ```
-/// # #![allow(unused_variables)]
-/// # use std::fs::File;
-/// # use collaborate::v1_4::Collada;
-/// # let file = File::open("resources/blender_cube.dae").unwrap();
-/// # let document = Collada::read(file).unwrap();
-/// # let library = document.libraries[5].as_library_geometries().unwrap();
-/// # let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
-/// let polylist = mesh.primitives[0].as_polylist().unwrap();
-/// for polygon in polylist {
-///     println!("Vertices in polygon: {}", polygon.len());
-///     for vertex in polygon {
-///         println!("{:?}", vertex);
-///         for attribute in vertex {
-///             for input in polylist.inputs_for_offset(attribute.offset) {
-///                 println!(
-///                     "Attribute {:?} indexes into {:?}",
-///                     attribute,
-///                     input,
-///                 );
-///             }
-///         }
-///     }
-/// }
-/// ```
+/// Each fan is stored in its own `<p>` element. The first vertex of a fan is shared by all of
+/// its triangles, and each vertex after the second forms a triangle with the first vertex and the
+/// vertex before it.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "polylist"]
-pub struct Polylist {
-    /// A human-friendly name for this polylist.
+#[name = "trifans"]
+pub struct Trifans {
+    /// A human-friendly name for this triangle fan list.
     ///
     /// Has no semantic meaning.
     #[attribute]
     pub name: Option<String>,
 
-    /// The number of polygon primitives in the polylist.
+    /// The number of triangle fans in the triangle fan list.
     #[attribute]
     pub count: usize,
 
-    /// The name of the material associated with this polylist.
+    /// The name of the material associated with this triangle fan list.
     ///
     /// This name is bound to a material at the time of instantiaion. See [`InstanceGeometry`]
     /// and [`BindMaterial`].
@@ -913,19 +9859,15 @@ pub struct Polylist {
     #[attribute]
     pub material: Option<String>,
 
-    /// The input data for the polylist.
+    /// The input data for the triangle fan list.
     #[child]
     pub inputs: Vec<SharedInput>,
 
-    /// A list of integers, each specifying the number of vertices for one polygon in the polylist.
-    #[child]
-    pub vcount: Option<VCount>,
-
-    /// A list of integers that specify the vertex attributes as indexes into the inputs.
+    /// One list of vertex attribute indices for each triangle fan.
     #[child]
-    pub primitives: Option<Primitives>,
+    pub primitives: Vec<Primitives>,
 
-    /// Arbitrary additional information about this polylist and the data it contains.
+    /// Arbitrary additional information about this triangle fan list and the data it contains.
     ///
     /// For more information about 3rd-party extensions, see the
     /// [crate-level documentation](../index.html#3rd-party-extensions).
@@ -933,67 +9875,25 @@ pub struct Polylist {
     pub extras: Vec<Extra>,
 }
 
-impl Polylist {
-    /// Returns an iterator over the polygons in the polylist.
-    pub fn iter<'a>(&'a self) -> PolylistIter<'a> {
-        // Determine the number of indices that are used for each vertex. Generally, we expect this to
-        // be the same as the number of inputs (e.g. if there's an input for position and an input
-        // for normal, then we'd expect there to be 2 indices for each vertex), but the COLLADA spec
-        // allows multiple inputs to share an offset, effectively reducing the number of indices
-        // needed for each vertex. To account for this, we look for the largest offset used by the
-        // inputs, which should tell us consistently how many unique offsets there are.
-        // TODO: How do we handle a polylist with no inputs? Probably return no polygons.
-        let largest_offset = self.inputs.iter()
-            .map(|input| input.offset)
-            .max()
-            .unwrap();
-
-        PolylistIter {
-            polylist: self,
-            num_indices_per_vertex: largest_offset + 1,
-            vcount_iter: self.vcount.as_ref().unwrap().iter(),
-            verts_so_far: 0,
+impl Trifans {
+    /// Returns an iterator over the triangle fans, each yielded as a
+    /// [`Polygon`](./struct.Polygon.html) containing the vertices of the fan.
+    pub fn iter<'a>(&'a self) -> PrimitivesIter<'a> {
+        PrimitivesIter {
+            iter: self.primitives.iter(),
+            stride: vertex_stride(&self.inputs),
         }
     }
 
-    /// Returns the number of polygons in the polylist.
+    /// Returns the number of triangle fans in the triangle fan list.
     pub fn len(&self) -> usize {
         self.count
     }
 
     /// Returns an iterator yielding all inputs that match `offset`.
     ///
-    /// When matching a vertex attribute to an input, the attribute's offset is matched against
-    /// the input's offset. It's possible for multiple inputs to share the same offset, so this
-    /// method provides an easy way to iterate over all inputs with a given offset.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// # #![allow(unused_variables)]
-    /// # use std::fs::File;
-    /// # use collaborate::v1_4::Collada;
-    /// # let file = File::open("resources/blender_cube.dae").unwrap();
-    /// # let document = Collada::read(file).unwrap();
-    /// # let library = document.libraries[5].as_library_geometries().unwrap();
-    /// # let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
-    /// let polylist = mesh.primitives[0].as_polylist().unwrap();
-    /// for polygon in polylist {
-    ///     println!("Vertices in polygon: {}", polygon.len());
-    ///     for vertex in polygon {
-    ///         println!("{:?}", vertex);
-    ///         for attribute in vertex {
-    ///             for input in polylist.inputs_for_offset(attribute.offset) {
-    ///                 println!(
-    ///                     "Attribute {:?} indexes into {:?}",
-    ///                     attribute,
-    ///                     input,
-    ///                 );
-    ///             }
-    ///         }
-    ///     }
-    /// }
-    /// ```
+    /// See [`Polylist::inputs_for_offset`](./struct.Polylist.html#method.inputs_for_offset) for
+    /// more information.
     pub fn inputs_for_offset<'a>(&'a self, offset: usize) -> InputsForOffset<'a> {
         InputsForOffset {
             inputs: self.inputs.iter(),
@@ -1002,83 +9902,99 @@ impl Polylist {
     }
 }
 
-impl<'a> ::std::iter::IntoIterator for &'a Polylist {
+impl<'a> ::std::iter::IntoIterator for &'a Trifans {
     type Item = Polygon<'a>;
-    type IntoIter = PolylistIter<'a>;
+    type IntoIter = PrimitivesIter<'a>;
 
-    fn into_iter(self) -> PolylistIter<'a> {
+    fn into_iter(self) -> PrimitivesIter<'a> {
         self.iter()
     }
 }
 
-pub struct PolylistIter<'a> {
-    polylist: &'a Polylist,
-    num_indices_per_vertex: usize,
-    vcount_iter: ::std::slice::Iter<'a, usize>,
-    verts_so_far: usize,
-}
+/// A list of triangle strips.
+///
+/// Each strip is stored in its own `<p>` element. Each vertex after the second forms a triangle
+/// with the two vertices before it.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "tristrips"]
+pub struct Tristrips {
+    /// A human-friendly name for this triangle strip list.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
 
-impl<'a> ::std::iter::Iterator for PolylistIter<'a> {
-    type Item = Polygon<'a>;
+    /// The number of triangle strips in the triangle strip list.
+    #[attribute]
+    pub count: usize,
 
-    fn next(&mut self) -> Option<Polygon<'a>> {
-        let primitives = match self.polylist.primitives {
-            Some(ref primitives) => primitives,
-            None => return None,
-        };
+    /// The name of the material associated with this triangle strip list.
+    ///
+    /// This name is bound to a material at the time of instantiaion. See [`InstanceGeometry`]
+    /// and [`BindMaterial`].
+    ///
+    /// If `None`, then the lighting and shading results are appplication-defined.
+    ///
+    /// [`InstanceGeometry`]: ./struct.InstanceGeometry.html
+    /// [`BindMaterial`]: ./struct.BindMaterial.html
+    #[attribute]
+    pub material: Option<String>,
 
-        self.vcount_iter.next()
-            .map(|&num_verts| {
-                let indices = &primitives[self.verts_so_far * self.num_indices_per_vertex .. (self.verts_so_far + num_verts) * self.num_indices_per_vertex];
-                self.verts_so_far += num_verts;
-                Polygon {
-                    len: num_verts,
-                    chunks: indices.chunks(self.num_indices_per_vertex),
-                }
-            })
-    }
-}
+    /// The input data for the triangle strip list.
+    #[child]
+    pub inputs: Vec<SharedInput>,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-pub enum Primitive {
-    Lines(Lines),
-    Linestrips(Linestrips),
-    Polygons(Polygons),
-    Polylist(Polylist),
-    Triangles(Triangles),
-    Trifans(Trifans),
-    Tristrips(Tristrips),
+    /// One list of vertex attribute indices for each triangle strip.
+    #[child]
+    pub primitives: Vec<Primitives>,
+
+    /// Arbitrary additional information about this triangle strip list and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
 }
 
-impl Primitive {
-    pub fn as_polylist(&self) -> Option<&Polylist> {
-        match *self {
-            Primitive::Polylist(ref polylist) => Some(polylist),
-            _ => None,
+impl Tristrips {
+    /// Returns an iterator over the triangle strips, each yielded as a
+    /// [`Polygon`](./struct.Polygon.html) containing the vertices of the strip.
+    pub fn iter<'a>(&'a self) -> PrimitivesIter<'a> {
+        PrimitivesIter {
+            iter: self.primitives.iter(),
+            stride: vertex_stride(&self.inputs),
         }
     }
-}
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "p"]
-pub struct Primitives {
-    #[text]
-    data: Vec<usize>,
+    /// Returns the number of triangle strips in the triangle strip list.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns an iterator yielding all inputs that match `offset`.
+    ///
+    /// See [`Polylist::inputs_for_offset`](./struct.Polylist.html#method.inputs_for_offset) for
+    /// more information.
+    pub fn inputs_for_offset<'a>(&'a self, offset: usize) -> InputsForOffset<'a> {
+        InputsForOffset {
+            inputs: self.inputs.iter(),
+            offset,
+        }
+    }
 }
 
-impl ::std::ops::Deref for Primitives {
-    type Target = [usize];
+impl<'a> ::std::iter::IntoIterator for &'a Tristrips {
+    type Item = Polygon<'a>;
+    type IntoIter = PrimitivesIter<'a>;
 
-    fn deref(&self) -> &[usize] { &*self.data }
+    fn into_iter(self) -> PrimitivesIter<'a> {
+        self.iter()
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "scene"]
-pub struct Scene;
-
 /// Declares the input semantic of a data source and connects a consumer of that source.
 ///
-/// `SharedInput` declares the input connection to a data source that a consumer requires. A data
+/// Declares the input connection to a data source that a consumer requires. A data
 /// source is a container of raw data that lacks semantic meaning, so that the data can be
 /// reused within the document. To use the data, a consumer declares a connection to it with the
 /// desired semantic information.
@@ -1089,156 +10005,172 @@ pub struct Scene;
 /// consumer. This is an optimization that reduces the total number of indexes that the consumer
 /// must store. These inputs are described in this section as shared inputs but otherwise
 /// operate in the same manner as unshared inputs.
-///
-/// # Common Semantics
-///
-/// | Value of `semantic` | Description                                                |
-/// | ------------------- | ---------------------------------------------------------- |
-/// | `"BINORMAL"`        | Geometric binormal (bitangent) vector.                     |
-/// | `"COLOR"`           | Color coordinate vector. Color inputs are RGB.             |
-/// | `"CONTINUITY"`      | Continuity constraint at the control vertex (CV). See also "Curve Interpolation" in Chapter 4 of the COLLADA spec.    |
-/// | `"IMAGE"`           | Raster or MIP-level input.                                 |
-/// | `"INPUT"`           | Sampler input. See also "Curve Interpolation" in Chapter 4 of the COLLADA spec. |
-/// | `"IN_TANGENT"`      | Tangent vector for preceding control point. See also "Curve Interpolation" in Chapter 4 of the COLLADA spec. |
-/// | `"INTERPOLATION"`   | Sampler interpolation type. See also "Curve Interpolation" in Chapter 4 of the COLLADA spec. |
-/// | `"INV_BIND_MATRIX"` | Inverse of location-to-world matrix.                       |
-/// | `"JOIN"`            | Skin influence identifier.                                 |
-/// | `"LINEAR_STEPS"`    | Number of piece-wise linear approximation steps to use for the spline segment that follows this CV. See also "Curve Interpolation" in Chapter 4 of the COLLADA spec. |
-/// | `"MORPH_TARGET"`    | Morph targets for mesh morphing.                           |
-/// | `"MORPH_WEIGHT"`    | Weights for mesh morphing.                                 |
-/// | `"NORMAL"`          | Normal vector.                                             |
-/// | `"OUTPUT"`          | Sampler output. See also "Curve Interpolation" in Chapter 4 of the COLLADA spec. |
-/// | `"OUT_TANGENT"`     | Tangent vector for succeeding control point. See also "Curve Interpolation" in Chapter 4 fo the COLLADA spec. |
-/// | `"POSITION"`        | Geometric coordinate vector. See also "Curve Interpolation" in Chapter 4 of the COLLADA spec. |
-/// | `"TANGENT"`         | Geometric tangent vector.                                  |
-/// | `"TEXBINORMAL"`     | Texture binormal (bitangent) vector.                       |
-/// | `"TEXCOORD"`        | Texture coordinate vector.                                 |
-/// | `"TEXTANGENT"`      | Texture tangent vector.                                    |
-/// | `"UV"`              | Generic parameter vector.                                  |
-/// | `"VERTEX"`          | Mesh vertex.                                               |
-/// | `"WEIGHT"`          | Skin influence weighting value.                            |
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
 #[name = "input"]
-pub struct SharedInput {
-    /// The offset into the list of indices provided by the parent object.
-    ///
-    /// If two `SharedInput` instances share the same `offset` value, they are indexed the same.
-    /// This is a simple form of compression for the list of indices and also defines the order
-    /// in which inputs are used.
-    #[attribute]
-    pub offset: usize,
-
-    /// The user-defined meaning of the input connnection.
+pub struct UnsharedInput {
+    /// The meaning of the input connnection, e.g. `Semantic::Position`.
     ///
-    /// See the type-level documentation for a [list of common semantic values](#common-semantics).
+    /// See [`Semantic`](../common/enum.Semantic.html) for the semantics defined by the COLLADA
+    /// specification.
     #[attribute]
-    pub semantic: String,
+    pub semantic: Semantic,
 
     /// The location of the data source.
     #[attribute]
     pub source: UriFragment,
-
-    /// Which inputs to group as a single set.
-    ///
-    /// This is helpful when multiple inputs share the same semantic.
-    #[attribute]
-    pub set: Option<usize>,
 }
 
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "source"]
-pub struct Source {
-    #[attribute]
-    pub id: String,
+#[name = "vcount"]
+pub struct VCount {
+    #[text]
+    data: Vec<usize>,
+}
+
+impl ::std::ops::Deref for VCount {
+    type Target = [usize];
+
+    fn deref(&self) -> &[usize] { &*self.data }
+}
 
+/// The joints influencing each vertex of a skinned mesh, as part of a [`Skin`].
+///
+/// The influences are stored as indices, see [`Skin::influences`] for looking up their values.
+///
+/// [`Skin`]: ./struct.Skin.html
+/// [`Skin::influences`]: ./struct.Skin.html#method.influences
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "vertex_weights"]
+pub struct VertexWeights {
+    /// The number of vertices in the mesh.
     #[attribute]
-    pub name: Option<String>,
+    pub count: usize,
 
+    /// The sources indexed by `v`, generally with the semantics `"JOINT"` and `"WEIGHT"`.
+    ///
+    /// There will always be at least one input.
     #[child]
-    pub asset: Option<Asset>,
+    #[required]
+    pub inputs: Vec<SharedInput>,
 
+    /// The number of joints influencing each vertex.
     #[child]
-    pub array: Option<Array>,
+    pub vcount: Option<VCount>,
 
+    /// The indices for each influence, for each vertex in turn.
     #[child]
-    pub technique_common: Option<SourceTechniqueCommon>,
+    pub v: Option<VertexWeightIndices>,
 
+    /// Arbitrary additional information about the vertex weights.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
     #[child]
-    pub techniques: Vec<Technique>,
+    pub extras: Vec<Extra>,
 }
 
-impl Source {
-    // Returns the [`Accessor`] in the source's `technique_common` member.
-    pub fn common_accessor(&self) -> Option<&Accessor> {
-        self.technique_common
-            .as_ref()
-            .map(|technique| &technique.accessor)
+impl VertexWeights {
+    /// Returns the input with the given semantic, if there is one.
+    pub fn input(&self, semantic: &str) -> Option<&SharedInput> {
+        self.inputs.iter().find(|input| input.semantic == semantic)
+    }
+
+    /// Returns an iterator over the indices for each vertex.
+    ///
+    /// Each item holds one index for each input offset, for each of the joints influencing the
+    /// vertex. Iteration stops early if `v` doesn't hold as many indices as `vcount` declares.
+    pub fn iter<'a>(&'a self) -> VertexWeightsIter<'a> {
+        VertexWeightsIter {
+            vcount: self.vcount.as_deref().unwrap_or(&[]).iter(),
+            indices: self.v.as_deref().unwrap_or(&[]),
+            stride: self.stride(),
+        }
+    }
+
+    /// The number of indices used for each influence.
+    fn stride(&self) -> usize {
+        // As with polylists, inputs may share an offset, so the largest offset determines how
+        // many indices there are.
+        self.inputs.iter()
+            .map(|input| input.offset + 1)
+            .max()
+            .unwrap_or(1)
     }
 }
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "technique_common"]
-pub struct SourceTechniqueCommon {
-    #[child]
-    pub accessor: Accessor,
+impl<'a> ::std::iter::IntoIterator for &'a VertexWeights {
+    type Item = &'a [i64];
+    type IntoIter = VertexWeightsIter<'a>;
+
+    fn into_iter(self) -> VertexWeightsIter<'a> {
+        self.iter()
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "spline"]
-pub struct Spline;
+pub struct VertexWeightsIter<'a> {
+    vcount: ::std::slice::Iter<'a, usize>,
+    indices: &'a [i64],
+    stride: usize,
+}
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "triangles"]
-pub struct Triangles;
+impl<'a> ::std::iter::Iterator for VertexWeightsIter<'a> {
+    type Item = &'a [i64];
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "trifans"]
-pub struct Trifans;
+    fn next(&mut self) -> Option<&'a [i64]> {
+        let len = self.vcount.next()? * self.stride;
+        if len > self.indices.len() {
+            return None;
+        }
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "tristrips"]
-pub struct Tristrips;
+        let (vertex, rest) = self.indices.split_at(len);
+        self.indices = rest;
+        Some(vertex)
+    }
+}
 
-/// Declares the input semantic of a data source and connects a consumer of that source.
-///
-/// Declares the input connection to a data source that a consumer requires. A data
-/// source is a container of raw data that lacks semantic meaning, so that the data can be
-/// reused within the document. To use the data, a consumer declares a connection to it with the
-/// desired semantic information.
+/// The values of a single input for every vertex of a primitive.
 ///
-/// In COLLADA, all inputs are driven by index values. A consumer samples an input by supplying
-/// an index value to an input. Some consumers have multiple inputs that can share the same index
-/// values. Inputs that have the same `offset` value are driven by the same index value from the
-/// consumer. This is an optimization that reduces the total number of indexes that the consumer
-/// must store. These inputs are described in this section as shared inputs but otherwise
-/// operate in the same manner as unshared inputs.
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "input"]
-pub struct UnsharedInput {
-    /// The user-defined meaning of the input connnection.
-    ///
-    /// See [`SharedInput`] for a list of common semantic values.
-    ///
-    /// [`SharedInput`]: ./struct.SharedInput.html
-    #[attribute]
-    pub semantic: String,
+/// See [`Mesh::vertex_streams`](./struct.Mesh.html#method.vertex_streams).
+#[derive(Debug, Clone, PartialEq)]
+pub struct VertexStream<'a> {
+    /// The input the values were read for.
+    pub input: ResolvedInput<'a>,
 
-    /// The location of the data source.
-    #[attribute]
-    pub source: UriFragment,
+    /// The number of values for each vertex, e.g. 3 for positions.
+    pub width: usize,
+
+    /// The values of each vertex one after the other, with `width` values for each vertex.
+    pub values: Vec<f32>,
+}
+
+impl<'a> VertexStream<'a> {
+    /// Returns the values of the vertex at `index`, in the order of
+    /// [`Primitive::vertices`](./enum.Primitive.html#method.vertices).
+    pub fn get(&self, index: usize) -> Option<&[f32]> {
+        let start = index * self.width;
+        self.values.get(start..start + self.width)
+    }
 }
 
+/// The indices for the joint influences of a [`VertexWeights`].
+///
+/// [`VertexWeights`]: ./struct.VertexWeights.html
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "vcount"]
-pub struct VCount {
+#[name = "v"]
+pub struct VertexWeightIndices {
     #[text]
-    data: Vec<usize>,
+    data: Vec<i64>,
 }
 
-impl ::std::ops::Deref for VCount {
-    type Target = [usize];
+impl ::std::ops::Deref for VertexWeightIndices {
+    type Target = [i64];
 
-    fn deref(&self) -> &[usize] { &*self.data }
+    fn deref(&self) -> &[i64] { &*self.data }
+}
+
+targetable_vector! {
+    /// The initial linear velocity of an [`InstanceRigidBody`](./struct.InstanceRigidBody.html).
+    pub struct Velocity, "velocity"
 }
 
 /// A single vertex in a polygon.
@@ -1302,6 +10234,12 @@ impl<'a> Vertex<'a> {
             offset: 0,
         }
     }
+
+    /// Returns the index of the attribute at `offset`, or `None` if the vertex doesn't have that
+    /// many attributes.
+    pub fn index(&self, offset: usize) -> Option<usize> {
+        self.attributes.get(offset).cloned()
+    }
 }
 
 impl<'a> ::std::iter::IntoIterator for Vertex<'a> {
@@ -1376,7 +10314,8 @@ pub struct Vertices {
     /// The input data for the vertices.
     ///
     /// There will be at least one element in `inputs`, and one input will specify the
-    /// `"POSITION"` semantic.
+    /// `"POSITION"` semantic. Other attributes that don't change between the primitives sharing
+    /// a vertex (e.g. `"NORMAL"` or `"COLOR"`) may be bound here as well.
     #[child]
     #[required]
     pub inputs: Vec<UnsharedInput>,
@@ -1385,3 +10324,255 @@ pub struct Vertices {
     #[child]
     pub extras: Vec<Extra>,
 }
+
+impl Vertices {
+    /// Returns the input with the given semantic, if there is one.
+    pub fn input(&self, semantic: &str) -> Option<&UnsharedInput> {
+        self.inputs.iter().find(|input| input.semantic == semantic)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "viewport_ratio"]
+pub struct ViewportRatio;
+
+/// Declares the scene graph for a renderable scene, which can be instantiated to make it the
+/// document's scene.
+///
+/// The scene graph is made up of the hierarchy of [`Node`]s in `nodes`.
+///
+/// [`Node`]: ./struct.Node.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "visual_scene"]
+pub struct VisualScene {
+    /// A unique identifier for the visual scene.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this visual scene.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about the visual scene and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The root nodes of the scene graph.
+    #[child]
+    #[required]
+    pub nodes: Vec<Node>,
+
+    /// The rendering passes used to render the scene, if any.
+    #[child]
+    pub evaluate_scenes: Vec<EvaluateScene>,
+
+    /// Arbitrary additional information about this visual scene and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl VisualScene {
+    /// Returns the node with the given id, searching the whole hierarchy of the scene.
+    pub fn node(&self, id: &str) -> Option<&Node> {
+        self.nodes.iter().filter_map(|node| node.find(id)).next()
+    }
+
+    /// Returns an iterator over every node in the scene along with its ancestors.
+    ///
+    /// The nodes are visited depth-first in document order, so each node is visited before its
+    /// children. Nodes instantiated with `<instance_node>` are not followed. The [`Ancestry`]
+    /// of each node gives its parent chain, and combines the ancestors' transforms on request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::v1_4::Collada;
+    /// static DOCUMENT: &'static str = r#"
+    ///     <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+    ///         <asset>
+    ///             <created>2017-02-07T20:44:30Z</created>
+    ///             <modified>2017-02-07T20:44:30Z</modified>
+    ///         </asset>
+    ///         <library_visual_scenes>
+    ///             <visual_scene id="Scene">
+    ///                 <node id="Arm">
+    ///                     <translate>0 1 0</translate>
+    ///                     <node id="Hand">
+    ///                         <translate>2 0 0</translate>
+    ///                     </node>
+    ///                 </node>
+    ///             </visual_scene>
+    ///         </library_visual_scenes>
+    ///     </COLLADA>
+    /// "#;
+    ///
+    /// let document = Collada::from_str(DOCUMENT).unwrap();
+    /// let scene = document.visual_scene("Scene").unwrap();
+    /// for (node, ancestry) in scene.iter_nodes() {
+    ///     let world = ancestry.world_matrix(node).unwrap();
+    ///     println!("{:?} is at ({}, {}, {})", node.id, world[3], world[7], world[11]);
+    /// }
+    /// ```
+    ///
+    /// [`Ancestry`]: ./struct.Ancestry.html
+    pub fn iter_nodes<'a>(&'a self) -> NodeIter<'a> {
+        NodeIter {
+            levels: vec![self.nodes.iter()],
+            ancestors: Vec::new(),
+        }
+    }
+}
+
+/// How a [`Sampler2D`] handles texture coordinates outside of the texture.
+///
+/// [`Sampler2D`]: ./struct.Sampler2D.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WrapMode {
+    /// The texture repeats.
+    Wrap,
+
+    /// The texture repeats, flipping every other repetition.
+    Mirror,
+
+    /// The texture's edge texels are repeated.
+    Clamp,
+
+    /// The sampler's border color is used.
+    Border,
+
+    /// No wrap mode is specified.
+    None,
+}
+
+impl TextValue for WrapMode {
+    fn parse_text(text: &str) -> ::std::result::Result<WrapMode, ErrorKind> {
+        match text {
+            "WRAP" => Ok(WrapMode::Wrap),
+            "MIRROR" => Ok(WrapMode::Mirror),
+            "CLAMP" => Ok(WrapMode::Clamp),
+            "BORDER" => Ok(WrapMode::Border),
+            "NONE" => Ok(WrapMode::None),
+            _ => Err(ErrorKind::InvalidValue {
+                element: "sampler2D",
+                value: text.into(),
+            }),
+        }
+    }
+}
+
+impl WriteValue for WrapMode {
+    fn write_value(&self, output: &mut String, _: &WriteOptions) {
+        output.push_str(match *self {
+            WrapMode::Wrap => "WRAP",
+            WrapMode::Mirror => "MIRROR",
+            WrapMode::Clamp => "CLAMP",
+            WrapMode::Border => "BORDER",
+            WrapMode::None => "NONE",
+        });
+    }
+}
+
+impl Default for WrapMode {
+    fn default() -> WrapMode { WrapMode::Wrap }
+}
+
+targetable_float! {
+    /// The horizontal field of view of a [`Perspective`](./struct.Perspective.html) camera in
+    /// degrees.
+    pub struct Xfov, "xfov"
+}
+
+targetable_float! {
+    /// The horizontal magnification of an [`Orthographic`](./struct.Orthographic.html) camera.
+    pub struct Xmag, "xmag"
+}
+
+targetable_float! {
+    /// The vertical field of view of a [`Perspective`](./struct.Perspective.html) camera in
+    /// degrees.
+    pub struct Yfov, "yfov"
+}
+
+targetable_float! {
+    /// The vertical magnification of an [`Orthographic`](./struct.Orthographic.html) camera.
+    pub struct Ymag, "ymag"
+}
+
+targetable_float! {
+    /// The distance from a camera to its far clipping plane.
+    pub struct Zfar, "zfar"
+}
+
+targetable_float! {
+    /// The distance from a camera to its near clipping plane.
+    pub struct Znear, "znear"
+}
+
+/// Applies the unit and up axis overrides of each asset in turn, starting with the outermost.
+fn nested_coordinate_system<'a, I>(enclosing: CoordinateSystem, assets: I) -> CoordinateSystem
+    where
+    I: IntoIterator<Item = Option<&'a Asset>>,
+{
+    assets.into_iter()
+        .flatten()
+        .fold(enclosing, |system, asset| asset.coordinate_system(&system))
+}
+
+/// Returns the number of indices used for each vertex of a primitive with the given inputs.
+///
+/// Inputs may share an offset, so this is one more than the largest offset rather than the number
+/// of inputs.
+fn vertex_stride(inputs: &[SharedInput]) -> usize {
+    inputs.iter().map(|input| input.offset).max().map_or(1, |offset| offset + 1)
+}
+
+/// Returns the relative luminance of a linear RGB color, using the Rec. 709 coefficients.
+fn luminance(rgb: [f32; 3]) -> f32 {
+    0.2126 * rgb[0] + 0.7152 * rgb[1] + 0.0722 * rgb[2]
+}
+
+/// The 4x4 identity matrix in row-major order.
+const IDENTITY: [f32; 16] = [
+    1.0, 0.0, 0.0, 0.0,
+    0.0, 1.0, 0.0, 0.0,
+    0.0, 0.0, 1.0, 0.0,
+    0.0, 0.0, 0.0, 1.0,
+];
+
+fn multiply(left: &[f32; 16], right: &[f32; 16]) -> [f32; 16] {
+    let mut result = [0.0; 16];
+    for row in 0..4 {
+        for column in 0..4 {
+            result[row * 4 + column] = (0..4).map(|i| left[row * 4 + i] * right[i * 4 + column]).sum();
+        }
+    }
+    result
+}
+
+fn sub(left: [f32; 3], right: [f32; 3]) -> [f32; 3] {
+    [left[0] - right[0], left[1] - right[1], left[2] - right[2]]
+}
+
+fn dot(left: [f32; 3], right: [f32; 3]) -> f32 {
+    left[0] * right[0] + left[1] * right[1] + left[2] * right[2]
+}
+
+fn cross(left: [f32; 3], right: [f32; 3]) -> [f32; 3] {
+    [
+        left[1] * right[2] - left[2] * right[1],
+        left[2] * right[0] - left[0] * right[2],
+        left[0] * right[1] - left[1] * right[0],
+    ]
+}
+
+fn normalize(vector: [f32; 3]) -> [f32; 3] {
+    let length = dot(vector, vector).sqrt();
+    [vector[0] / length, vector[1] / length, vector[2] / length]
+}
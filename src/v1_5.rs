@@ -1,12 +1,102 @@
 //! Type definitions matching the COLLADA `1.5.0` specification.
 
-use {Result, Error, ErrorKind};
+use {Result, Error, ErrorKind, MultiError, ParseOptions, ParseReport, Strictness, Version, Warning};
+use backend::{self, XmlPull};
 use common::*;
-use std::io::Read;
+use image::{self, ImageInfo};
+#[cfg(feature = "rayon")]
+use parallel;
+use raw::RawElement;
+use profiles::ExtraProfile;
+use std::io::{Read, Write};
 use utils;
 use utils::*;
+use writer::{self, WriteOptions, XmlWriter};
 use xml::common::Position;
-use xml::reader::EventReader;
+use xml::reader::{EventReader, XmlEvent};
+
+/// Declares an element containing a fixed number of floats, e.g. `<origin>0 0 1</origin>`.
+macro_rules! float_values {
+    ($(#[$attr:meta])* pub struct $ty:ident, $name:tt) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, ColladaElement)]
+        #[name = $name]
+        pub struct $ty {
+            #[text]
+            pub values: Vec<f32>,
+        }
+    }
+}
+
+/// Declares one of the elements of a [`Brep`](./struct.Brep.html) that assemble lower-level
+/// topology into higher-level topology, e.g. `<faces>`.
+macro_rules! brep_topology {
+    ($(#[$attr:meta])* pub struct $ty:ident, $name:tt, $what:tt) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, ColladaElement)]
+        #[name = $name]
+        pub struct $ty {
+            /// A unique identifier for the element.
+            ///
+            /// Will be unique within the document.
+            #[attribute]
+            pub id: String,
+
+            /// The human-friendly name for the element.
+            ///
+            /// Has no semantic meaning.
+            #[attribute]
+            pub name: Option<String>,
+
+            #[doc = "The number of "]
+            #[doc = $what]
+            #[doc = " described by the element."]
+            #[attribute]
+            pub count: usize,
+
+            /// The inputs that `p` indexes into.
+            #[child]
+            #[required]
+            pub inputs: Vec<SharedInput>,
+
+            #[doc = "The number of input tuples in each of the "]
+            #[doc = $what]
+            #[doc = "."]
+            #[child]
+            pub vcount: VCount,
+
+            /// The indices into the inputs.
+            #[child]
+            pub p: Primitives,
+
+            /// Arbitrary additional information about the element.
+            ///
+            /// For more information about 3rd-party extensions, see the
+            /// [crate-level documentation](../index.html#3rd-party-extensions).
+            #[child]
+            pub extras: Vec<Extra>,
+        }
+
+        impl $ty {
+            /// Returns the input with the given semantic, if there is one.
+            pub fn input(&self, semantic: &str) -> Option<&SharedInput> {
+                self.inputs.iter().find(|input| input.semantic == semantic)
+            }
+
+            #[doc = "Returns an iterator over the indices of each of the "]
+            #[doc = $what]
+            #[doc = ".\n\nEach item holds `vcount` tuples of indices, with one index for each input \
+                     offset."]
+            pub fn iter<'a>(&'a self) -> TopologyIter<'a> {
+                TopologyIter {
+                    counts: self.vcount.iter(),
+                    indices: &*self.p,
+                    stride: input_stride(&self.inputs),
+                }
+            }
+        }
+    }
+}
 
 /// Represents a parsed COLLADA document.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
@@ -84,8 +174,37 @@ impl Collada {
     ///
     /// [crate]: index.html
     pub fn from_str(source: &str) -> Result<Collada> {
-        let reader = EventReader::new_with_config(source.as_bytes(), utils::PARSER_CONFIG.clone());
-        Self::parse(reader)
+        backend::parse_utf8(source.as_bytes(), ParseOptions::default(), |reader, options| {
+            Self::parse_events(reader, options)
+        }).map(|(document, _)| document)
+    }
+
+    /// Read a COLLADA document from a byte slice.
+    ///
+    /// The document's encoding is detected the same as with [`read`](#method.read), but the
+    /// document is parsed directly from the slice rather than through `io::Read`. See
+    /// [`VersionedDocument::from_bytes`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![allow(unused_variables)]
+    /// use collaborate::v1_5::Collada;
+    ///
+    /// let bytes = std::fs::read("resources/v1_5_minimal.dae").unwrap();
+    /// let collada = Collada::from_bytes(&bytes).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the document is invalid or malformed in some way, the same as
+    /// [`read`](#method.read).
+    ///
+    /// [`VersionedDocument::from_bytes`]: ../enum.VersionedDocument.html#method.from_bytes
+    pub fn from_bytes(source: &[u8]) -> Result<Collada> {
+        backend::parse_bytes(source, ParseOptions::default(), |reader, options| {
+            Self::parse_events(reader, options)
+        }).map(|(document, _)| document)
     }
 
     /// Attempts to parse the contents of a COLLADA document.
@@ -109,34 +228,276 @@ impl Collada {
     ///
     /// [crate]: index.html
     pub fn read<R: Read>(reader: R) -> Result<Collada> {
-        let reader = EventReader::new_with_config(reader, utils::PARSER_CONFIG.clone());
-        Self::parse(reader)
+        let options = ParseOptions::default();
+        let reader = backend::open_encoded(reader, &options)?;
+        Self::parse_events(reader, options).map(|(document, _)| document)
+    }
+
+    /// Attempts to parse the contents of a COLLADA document using the given options.
+    ///
+    /// Returns the parsed document along with a report of any workarounds that were needed to
+    /// parse it, including any problems that were tolerated because of `options.strictness`. See
+    /// the [`options`](../options/index.html) module for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the document is invalid or malformed in a way that `options` doesn't
+    /// allow, or if it exceeds one of the limits set in `options`.
+    pub fn read_with<R: Read>(reader: R, options: ParseOptions) -> Result<(Collada, ParseReport)> {
+        #[cfg(feature = "rayon")]
+        {
+            if options.parallel {
+                let source = parallel::read_source(reader, &options)?;
+                let (document, report) = parallel::parse::<Collada>(&source, options)?;
+                return Ok((document?, report));
+            }
+        }
+
+        let reader = backend::open_encoded(reader, &options)?;
+        Self::parse_events(reader, options)
+    }
+
+    /// Attempts to parse the contents of a COLLADA document, collecting any recoverable problems
+    /// as warnings instead of failing.
+    ///
+    /// The document is parsed with [`Strictness::Lenient`], and the resulting report is flattened
+    /// into a list of [`Warning`]s. Use [`read_with`](#method.read_with) for more control over how
+    /// the document is parsed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the document is invalid or malformed in a way that can't be recovered
+    /// from.
+    ///
+    /// [`Strictness::Lenient`]: ../options/enum.Strictness.html#variant.Lenient
+    /// [`Warning`]: ../report/enum.Warning.html
+    pub fn read_with_warnings<R: Read>(reader: R) -> Result<(Collada, Vec<Warning>)> {
+        let options = ParseOptions {
+            strictness: Strictness::Lenient,
+            ..ParseOptions::default()
+        };
+        let (document, report) = Self::read_with(reader, options)?;
+        Ok((document, report.into_warnings()))
+    }
+
+    /// Attempts to parse the contents of a COLLADA document, collecting every error found instead
+    /// of stopping at the first one.
+    ///
+    /// The document is parsed with [`ParseOptions::recover`] set, so elements that fail to parse
+    /// are skipped and parsing continues with the rest of the document. This is useful for
+    /// triaging broken exports, where fixing one error at a time would be tedious.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` with all of the errors found, in the order they were found, if any part of
+    /// the document failed to parse. The document itself is discarded in that case.
+    ///
+    /// [`ParseOptions::recover`]: ../options/struct.ParseOptions.html#structfield.recover
+    pub fn read_recovering<R: Read>(reader: R) -> ::std::result::Result<Collada, MultiError> {
+        let options = ParseOptions {
+            recover: true,
+            ..ParseOptions::default()
+        };
+        let (document, report) = backend::open_encoded(reader, &options)
+            .and_then(|reader| Self::parse_document(reader, options))?;
+        MultiError::collect(document, report.errors)
+    }
+
+    /// Writes the document to `writer` as COLLADA XML.
+    ///
+    /// See the [`writer`](../writer/index.html) module for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use collaborate::v1_5::Collada;
+    /// use collaborate::writer::WriteOptions;
+    ///
+    /// let document = Collada::read(File::open("resources/v1_5_minimal.dae").unwrap()).unwrap();
+    /// let mut output = Vec::new();
+    /// document.write(&mut output, WriteOptions::default()).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if writing to `writer` fails, or if the document can't be represented in
+    /// the version requested by `options`.
+    pub fn write<W: Write>(&self, writer: W, options: WriteOptions) -> writer::Result<()> {
+        writer::write_document(self, writer, options, Version::V1_5_0)
+    }
+
+    /// Returns the geometry with the given id, if it's in one of the document's
+    /// `<library_geometries>` elements.
+    pub fn geometry(&self, id: &str) -> Option<&Geometry> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_geometries)
+            .filter_map(|library| library.geometry(id))
+            .next()
+    }
+
+    /// Returns the formula with the given id, if it's in one of the document's
+    /// `<library_formulas>` elements.
+    pub fn formula(&self, id: &str) -> Option<&Formula> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_formulas)
+            .filter_map(|library| library.formula(id))
+            .next()
+    }
+
+    /// Returns the unit of distance and up axis declared by the document's root `<asset>`.
+    pub fn coordinate_system(&self) -> CoordinateSystem {
+        self.asset.coordinate_system(&CoordinateSystem::default())
+    }
+
+    /// Returns the coordinate system of `geometry`, taking into account the `<asset>` of the
+    /// geometry and the library containing it.
+    ///
+    /// Returns `None` if `geometry` isn't borrowed from this document's
+    /// `<library_geometries>`.
+    pub fn geometry_coordinate_system(&self, geometry: &Geometry) -> Option<CoordinateSystem> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_geometries)
+            .find(|library| library.geometries.iter().any(|other| ::std::ptr::eq(other, geometry)))
+            .map(|library| {
+                let system = match library.asset {
+                    Some(ref asset) => asset.coordinate_system(&self.coordinate_system()),
+                    None => self.coordinate_system(),
+                };
+                match geometry.asset {
+                    Some(ref asset) => asset.coordinate_system(&system),
+                    None => system,
+                }
+            })
+    }
+
+    pub fn parse<R: Read>(reader: EventReader<R>) -> Result<Collada> {
+        Self::parse_events(reader, ParseOptions::default()).map(|(document, _)| document)
+    }
+
+    fn parse_events<P: XmlPull>(reader: P, options: ParseOptions) -> Result<(Collada, ParseReport)> {
+        let (document, report) = Self::parse_document(reader, options)?;
+        Ok((document?, report))
     }
 
-    pub fn parse<R: Read>(mut reader: EventReader<R>) -> Result<Collada> {
+    /// Parses a document, returning the report for it even if the document fails to parse.
+    ///
+    /// The outer `Result` is `Err` if the document can't be parsed at all, e.g. because it's for
+    /// a different version of the specification.
+    fn parse_document<P: XmlPull>(mut reader: P, options: ParseOptions) -> Result<(Result<Collada>, ParseReport)> {
         // Get the opening `<COLLADA>` tag and find the "version" attribute.
         let element_start = utils::get_document_start(&mut reader)?;
-        let version = element_start.attributes.iter()
-            .find(|attrib| attrib.name.local_name == "version")
-            .map(|attrib| attrib.value.clone())
-            .ok_or(Error {
-                position: reader.position(),
-                kind: ErrorKind::MissingAttribute {
-                    element: "COLLADA",
-                    attribute: "version",
-                },
-            })?;
-
-        if version != "1.5.0" {
+        let version = utils::get_document_version(&reader, &element_start)?;
+        if !version.is_compatible("1.5") {
             return Err(Error {
                 position: reader.position(),
                 kind: ErrorKind::UnsupportedVersion {
-                    version: version,
+                    version: version.to_string(),
                 },
             });
         }
 
-        Collada::parse_element(&mut reader, element_start)
+        let mut reader = DocumentReader::new(reader, ParseContext::with_options(version, options));
+        let document = Collada::parse_element(&mut reader, element_start);
+        Ok((document, reader.into_context().report))
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl parallel::SplitDocument for Collada {
+    type Library = Library;
+
+    fn parse_document<P: XmlPull>(reader: P, options: ParseOptions) -> Result<(Result<Collada>, ParseReport)> {
+        Collada::parse_document(reader, options)
+    }
+
+    fn set_libraries(&mut self, libraries: Vec<Library>) {
+        self.libraries = libraries;
+    }
+}
+
+/// Describes a stream of values from an array data source.
+///
+/// An accessor declares an access pattern into an array of source data. The arrays can be
+/// arranged in either an interleaved or noninterleaved manner, depending on the `offset` and
+/// `stride` values.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "accessor"]
+pub struct Accessor {
+    /// The number of times the array is accessed.
+    #[attribute]
+    pub count: usize,
+
+    /// The index of the first value to be read from the array.
+    #[attribute]
+    #[optional_with_default = "0"]
+    pub offset: usize,
+
+    /// The location of the array to access.
+    #[attribute]
+    pub source: AnyUri,
+
+    /// The number of values that are to be considered a unit during each access to the array.
+    #[attribute]
+    #[optional_with_default = "1"]
+    pub stride: usize,
+
+    /// Describes each of the values in a unit.
+    #[child]
+    pub params: Vec<Param>,
+}
+
+impl Accessor {
+    /// Access a source array using the accessor.
+    ///
+    /// Returns a sub-slice of `array` containing the values of the unit at `index`.
+    pub fn access<'a, 'b, T>(&'a self, array: &'b [T], index: usize) -> &'b [T] {
+        let start = self.offset + self.stride * index;
+        let end = start + self.stride;
+        &array[start..end]
+    }
+}
+
+/// An array of raw values in a [`Source`](./struct.Source.html).
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum Array {
+    Bool(BoolArray),
+    Float(FloatArray),
+    Idref(IdrefArray),
+    Int(IntArray),
+    Name(NameArray),
+    Sidref(SidrefArray),
+    Token(TokenArray),
+}
+
+impl Array {
+    /// Returns the id of the array, if it has one.
+    pub fn id(&self) -> Option<&str> {
+        match *self {
+            Array::Bool(ref array) => array.id.as_deref(),
+            Array::Float(ref array) => array.id.as_deref(),
+            Array::Idref(ref array) => array.id.as_deref(),
+            Array::Int(ref array) => array.id.as_deref(),
+            Array::Name(ref array) => array.id.as_deref(),
+            Array::Sidref(ref array) => array.id.as_deref(),
+            Array::Token(ref array) => array.id.as_deref(),
+        }
+    }
+
+    /// Returns the inner [`FloatArray`](./struct.FloatArray.html) if this is a float array.
+    pub fn as_float_array(&self) -> Option<&FloatArray> {
+        match *self {
+            Array::Float(ref array) => Some(array),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner [`IntArray`](./struct.IntArray.html) if this is an int array.
+    pub fn as_int_array(&self) -> Option<&IntArray> {
+        match *self {
+            Array::Int(ref array) => Some(array),
+            _ => None,
+        }
     }
 }
 
@@ -157,6 +518,7 @@ pub struct Asset {
 
     /// Specifies the location of the visual scene in physical space.
     #[child]
+    #[collada(version = "1.5")]
     pub coverage: Option<Coverage>,
 
     /// Specifies the date and time that the asset was created.
@@ -165,7 +527,7 @@ pub struct Asset {
 
     /// A list of keywords used as search criteria for the asset.
     #[child]
-    pub keywords: Option<String>,
+    pub keywords: MaybeEmpty<String>,
 
     /// Contains the date and time that the parent element was last modified.
     #[child]
@@ -175,36 +537,38 @@ pub struct Asset {
     ///
     /// This field is free-form, with no formatting required by the COLLADA specification.
     #[child]
-    pub revision: Option<String>,
+    pub revision: MaybeEmpty<String>,
 
     /// Contains a description of the topical subject of the asset.
     ///
     /// This field is free-form, with no formatting required by the COLLADA specification.
     #[child]
-    pub subject: Option<String>,
+    pub subject: MaybeEmpty<String>,
 
     /// Contains title information for the asset.
     ///
     /// This field is free-form, with no formatting required by the COLLADA specification.
     #[child]
-    pub title: Option<String>,
+    pub title: MaybeEmpty<String>,
 
     /// Defines the unit of distance for this asset.
     ///
     /// This unit is used by the asset and all of its children, unless overridden by a more
-    /// local `Unit`.
+    /// local `Unit`. `None` if the asset doesn't override the unit of the element containing its
+    /// parent, use [`unit`](#method.unit) to get the unit with the default applied.
     #[child]
-    #[optional_with_default]
-    pub unit: Unit,
+    pub unit: Option<Unit>,
 
     /// Describes the coordinate system of the asset.
     ///
-    /// See the documentation for [`UpAxis`] for more details.
+    /// See the documentation for [`UpAxis`] for more details. `None` if the asset doesn't
+    /// override the up axis of the element containing its parent, use
+    /// [`up_axis`](#method.up_axis) to get the up axis with the default applied.
     ///
-    /// [`UpAxis`]: ../struct.UpAxis.html
+    /// [`UpAxis`]: ../common/enum.UpAxis.html
     #[child]
-    #[optional_with_default]
-    pub up_axis: UpAxis,
+    #[text_type]
+    pub up_axis: Option<UpAxis>,
 
     /// Provides arbitrary additional data about the asset.
     ///
@@ -212,240 +576,2620 @@ pub struct Asset {
     ///
     /// [`Extra`]: ./struct.Extra.html
     #[child]
+    #[collada(version = "1.5")]
+    pub extras: Vec<Extra>,
+}
+
+impl Asset {
+    /// Returns an iterator over the individual keywords in `keywords`.
+    ///
+    /// Keywords are separated by whitespace.
+    pub fn keywords<'a>(&'a self) -> Box<Iterator<Item = &'a str> + 'a> {
+        match self.keywords.value() {
+            Some(keywords) => Box::new(keywords.split_whitespace()),
+            None => Box::new(::std::iter::empty()),
+        }
+    }
+
+    /// Returns the geographic location of the asset, if its coverage specifies one.
+    ///
+    /// ```
+    /// # use collaborate::v1_5::*;
+    /// # let collada = Collada::from_str(r#"
+    /// # <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.5.0">
+    /// #     <asset>
+    /// #         <coverage>
+    /// #             <geographic_location>
+    /// #                 <longitude>-105.2830</longitude>
+    /// #                 <latitude>40.0170</latitude>
+    /// #                 <altitude mode="absolute">1655</altitude>
+    /// #             </geographic_location>
+    /// #         </coverage>
+    /// #         <created>2017-02-07T20:44:30Z</created>
+    /// #         <modified>2017-02-07T20:44:30Z</modified>
+    /// #     </asset>
+    /// # </COLLADA>
+    /// # "#).unwrap();
+    /// let location = collada.asset.geographic_location().unwrap();
+    /// assert_eq!(40.0170, location.latitude);
+    /// assert_eq!(1655.0, location.altitude.value());
+    /// assert!(!location.altitude.is_relative_to_ground());
+    /// ```
+    pub fn geographic_location(&self) -> Option<&GeographicLocation> {
+        self.coverage.as_ref().and_then(|coverage| coverage.geographic_location.as_ref())
+    }
+
+    /// Returns the unit of distance declared by the asset, or one meter if it doesn't declare
+    /// one.
+    pub fn unit(&self) -> Unit {
+        self.unit.clone().unwrap_or_default()
+    }
+
+    /// Returns the up axis declared by the asset, or the Y axis if it doesn't declare one.
+    pub fn up_axis(&self) -> UpAxis {
+        self.up_axis.unwrap_or_default()
+    }
+
+    /// Returns the coordinate system of the asset's parent element, given the coordinate system
+    /// of the element containing it.
+    pub fn coordinate_system(&self, enclosing: &CoordinateSystem) -> CoordinateSystem {
+        enclosing.with_overrides(self.unit.as_ref(), self.up_axis)
+    }
+}
+
+/// Connects the sources of a [`Nurbs`] curve or [`NurbsSurface`] to their roles.
+///
+/// [`Nurbs`]: ./struct.Nurbs.html
+/// [`NurbsSurface`]: ./struct.NurbsSurface.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "control_vertices"]
+pub struct ControlVertices {
+    /// The inputs connecting the sources.
+    ///
+    /// There will always be at least one input.
+    #[child]
+    #[required]
+    pub inputs: Vec<UnsharedInput>,
+
+    /// Arbitrary additional information about the control vertices.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
     pub extras: Vec<Extra>,
 }
 
+impl ControlVertices {
+    /// Returns the input with the given semantic, if there is one.
+    pub fn input(&self, semantic: &str) -> Option<&UnsharedInput> {
+        self.inputs.iter().find(|input| input.semantic == semantic)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "convex_mesh"]
+pub struct ConvexMesh;
+
+/// Describes the physical area covered by an [`Asset`][Asset].
+///
+/// [Asset]: struct.Asset.html
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
 #[name = "coverage"]
 pub struct Coverage {
+    /// The location of the asset on the surface of the Earth.
     #[child]
     pub geographic_location: Option<GeographicLocation>,
 }
 
-/// Information about a contributor to an asset.
-///
-/// Contributor data is largely free-form text data meant to informally describe either the author
-/// or the author's work on the asset. The exceptions are `author_email`, `author_website`, and
-/// `source_data`, which are strictly formatted data (be it a URI or email address).
-///
-/// # COLLADA Versions
+float_values! {
+    /// The axis of revolution of a swept surface.
+    pub struct Axis, "axis"
+}
+
+/// Binds a parameter of the material used by a [`Render`] to a value in the scene.
 ///
-/// `author_email` and `author_website` were added in COLLADA version `1.5.0`.
-#[derive(Debug, Clone, Default, PartialEq, Eq, ColladaElement)]
-#[name = "contributor"]
-pub struct Contributor {
-    /// The author's name, if present.
-    #[child]
-    pub author: Option<String>,
+/// [`Render`]: ./struct.Render.html
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+#[name = "bind"]
+pub struct Bind {
+    /// The semantic of the parameter being bound.
+    #[attribute]
+    pub semantic: String,
 
-    /// The author's full email address, if present.
-    // TODO: Should we use some `Email` type? The 1.5.0 COLLADA spec provides an RFC defining the
-    // exact format this data follows (I assume it's just the RFC that defines valid email
-    // addresses).
-    #[child]
-    pub author_email: Option<String>,
+    /// The location of the value the parameter is bound to.
+    #[attribute]
+    pub target: String,
+}
 
-    /// The URL for the author's website, if present.
-    #[child]
-    pub author_website: Option<AnyUri>,
+/// Binds an axis of a joint in a kinematics scene to the node that it moves in the visual
+/// scene.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "bind_joint_axis"]
+pub struct BindJointAxis {
+    /// The sid path of the node's transform that the axis drives, e.g. `"Arm/rotateX"`.
+    #[attribute]
+    pub target: String,
 
-    /// The name of the authoring tool.
-    #[child]
-    pub authoring_tool: Option<String>,
+    /// The joint axis being bound.
+    #[child(flatten = "axis")]
+    pub axis: SidrefOrParam,
 
-    /// Free-form comments from the author.
-    #[child]
-    pub comments: Option<String>,
+    /// The current value of the joint axis.
+    #[child(flatten = "value")]
+    pub value: FloatOrParam,
+}
 
-    /// Copyright information about the asset. Does not adhere to a formatting standard.
-    #[child]
-    pub copyright: Option<String>,
+/// Binds a kinematics model in a kinematics scene to the node that it moves in the visual scene.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "bind_kinematics_model"]
+pub struct BindKinematicsModel {
+    /// The sid path of the node being bound.
+    #[attribute]
+    pub node: String,
 
-    /// A URI reference to the source data for the asset.
-    ///
-    /// For example, if the asset based off a file `tank.s3d`, the value might be
-    /// `c:/models/tank.s3d`.
+    /// The kinematics model being bound.
     #[child]
-    pub source_data: Option<AnyUri>,
+    pub model: SidrefOrParam,
 }
 
-/// Provides arbitrary additional information about an element.
-///
-/// COLLADA allows for applications to provide extra information about any given piece of data,
-/// including application-specific information that's not part of the COLLADA specification. This
-/// data can be any syntactically valid XML data, and is not parsed as part of this library, save
-/// for a few specific 3rd party applications that are directly supported.
-///
-/// # Choosing a Technique
-///
-/// There may be more than one [`Technique`][Technique] provided in `techniques`, but generally
-/// only one is used by the consuming application. The application should pick a technique
-/// with a supported profile. If there are multiple techniques with supported profiles the
-/// application is free to pick whichever technique is preferred.
-///
-/// [Technique]: struct.Technique.html
-#[derive(Debug, Clone, Default, PartialEq, ColladaElement)]
-#[name = "extra"]
-pub struct Extra {
-    /// The identifier of the element, if present. Will be unique within the document.
+/// A literal boolean parameter value.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "bool"]
+pub struct Bool {
+    #[text]
+    pub value: bool,
+}
+
+/// An array of boolean values.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "bool_array"]
+pub struct BoolArray {
     #[attribute]
-    pub id: Option<String>,
+    pub count: usize,
 
-    /// The text string name of the element, if present.
     #[attribute]
-    pub name: Option<String>,
+    pub id: Option<String>,
 
-    /// A hint as to the type of information this element represents, if present. Must be
-    /// must be understood by the consuming application.
     #[attribute]
-    #[name = "type"]
-    pub type_hint: Option<String>,
+    pub name: Option<String>,
 
-    /// Asset-management information for this element, if present.
+    /// The values in the array.
     ///
-    /// While this is technically allowed in all `<extra>` elements, it is likely only present in
-    /// elements that describe a new "asset" of some kind, rather than in `<extra>` elements that
-    /// provide application-specific information about an existing one.
-    #[child]
-    pub asset: Option<Asset>,
-
-    /// The arbitrary additional information, containing unprocessed XML events. There will always
-    /// be at least one item in `techniques`.
-    #[child]
-    #[required]
-    pub techniques: Vec<Technique>,
+    /// Decoding the values can be deferred until they're first accessed, see [`RawArray`] for
+    /// details.
+    ///
+    /// [`RawArray`]: ../common/struct.RawArray.html
+    #[text(count = "count")]
+    pub data: RawArray<bool>,
 }
 
-/// Defines geographic location information for an [`Asset`][Asset].
+/// A boundary representation (B-rep) of a solid, as produced by CAD applications.
 ///
-/// A geographic location is given in latitude, longitude, and altitude coordinates as defined by
-/// [WGS 84][WGS 84] world geodetic system.
+/// A B-rep describes a shape exactly rather than as a tessellated mesh. The geometric
+/// description is made up of `curves` and `surfaces`, and the topology of the shape is built up
+/// in layers that index into the layer below: `vertices` are joined by `edges` (which follow
+/// curves), edges are joined into `wires`, wires bound `faces` (which lie on surfaces), faces
+/// make up `shells`, and shells enclose `solids`.
 ///
-/// [Asset]: struct.Asset.html
-/// [WGS 84]: https://en.wikipedia.org/wiki/World_Geodetic_System#A_new_World_Geodetic_System:_WGS_84
+/// Tessellating a B-rep into triangles is left up to the application.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "geographic_location"]
-pub struct GeographicLocation {
-    /// The longitude of the location. Will be in the range -180.0 to 180.0.
+#[name = "brep"]
+pub struct Brep {
+    /// The curves followed by the edges of the B-rep.
     #[child]
-    pub longitude: f64,
+    pub curves: Option<Curves>,
 
-    /// The latitude of the location. Will be in the range -180.0 to 180.0.
+    /// The curves in the parameter space of the surfaces, used by `pcurves`.
     #[child]
-    pub latitude: f64,
+    pub surface_curves: Option<SurfaceCurves>,
 
-    /// Specifies the altitude, either relative to global sea level or relative to ground level.
+    /// The surfaces that the faces of the B-rep lie on.
     #[child]
-    pub altitude: Altitude,
-}
+    pub surfaces: Option<Surfaces>,
 
+    /// The sources holding the data for the topology elements.
+    ///
+    /// There will always be at least one source.
+    #[child]
+    #[required]
+    pub sources: Vec<Source>,
+
+    /// The vertices of the B-rep.
+    #[child]
+    pub vertices: Vertices,
+
+    /// The edges of the B-rep, which join pairs of vertices along a curve.
+    #[child]
+    pub edges: Option<Edges>,
+
+    /// The wires of the B-rep, which are chains of edges.
+    #[child]
+    pub wires: Option<Wires>,
+
+    /// The faces of the B-rep, which are areas of a surface bounded by wires.
+    #[child]
+    pub faces: Option<Faces>,
+
+    /// Maps edges onto the surfaces of the faces they bound.
+    #[child]
+    pub pcurves: Option<Pcurves>,
+
+    /// The shells of the B-rep, which are connected sets of faces.
+    #[child]
+    pub shells: Option<Shells>,
+
+    /// The solids of the B-rep, which are volumes enclosed by shells.
+    #[child]
+    pub solids: Option<Solids>,
+
+    /// Arbitrary additional information about the B-rep.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Brep {
+    /// Returns the source which matches `id`, or `None` if no sources match.
+    pub fn find_source<'a>(&'a self, id: &str) -> Option<&'a Source> {
+        self.sources.iter().find(|source| source.id == id)
+    }
+
+    /// Returns the curve with the given sid, if it's in `curves`.
+    pub fn curve(&self, sid: &str) -> Option<&Curve> {
+        self.curves.as_ref()
+            .and_then(|curves| curves.curves.iter().find(|curve| curve.sid.as_deref() == Some(sid)))
+    }
+
+    /// Returns the surface with the given sid, if it's in `surfaces`.
+    pub fn surface(&self, sid: &str) -> Option<&Surface> {
+        self.surfaces.as_ref()
+            .and_then(|surfaces| surfaces.surfaces.iter().find(|surface| surface.sid.as_deref() == Some(sid)))
+    }
+}
+
+/// A circle around the origin in the local xy plane.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "circle"]
+pub struct Circle {
+    /// The radius of the circle.
+    #[child]
+    pub radius: f32,
+
+    /// Arbitrary additional information about the circle.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// An infinite cone around the local z axis.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "cone"]
+pub struct Cone {
+    /// The radius of the cone where it crosses the local xy plane.
+    #[child]
+    pub radius: f32,
+
+    /// The half-angle of the cone, in degrees.
+    #[child]
+    pub angle: f32,
+
+    /// Arbitrary additional information about the cone.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Connects a parameter to another parameter, so that setting one sets the other.
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+#[name = "connect_param"]
+pub struct ConnectParam {
+    /// The sid of the parameter to connect to.
+    #[attribute]
+    #[name = "ref"]
+    pub reference: String,
+}
+
+/// Information about a contributor to an asset.
+///
+/// Contributor data is largely free-form text data meant to informally describe either the author
+/// or the author's work on the asset. The exceptions are `author_email`, `author_website`, and
+/// `source_data`, which are strictly formatted data (be it a URI or email address).
+///
+/// # COLLADA Versions
+///
+/// `author_email` and `author_website` were added in COLLADA version `1.5.0`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, ColladaElement)]
+#[name = "contributor"]
+pub struct Contributor {
+    /// The author's name, if present.
+    #[child]
+    pub author: MaybeEmpty<String>,
+
+    /// The author's full email address, if present.
+    // TODO: Should we use some `Email` type? The 1.5.0 COLLADA spec provides an RFC defining the
+    // exact format this data follows (I assume it's just the RFC that defines valid email
+    // addresses).
+    #[child]
+    #[collada(version = "1.5")]
+    pub author_email: MaybeEmpty<String>,
+
+    /// The URL for the author's website, if present.
+    #[child]
+    #[collada(version = "1.5")]
+    pub author_website: Option<AnyUri>,
+
+    /// The name of the authoring tool.
+    #[child]
+    pub authoring_tool: MaybeEmpty<String>,
+
+    /// Free-form comments from the author.
+    #[child]
+    pub comments: MaybeEmpty<String>,
+
+    /// Copyright information about the asset. Does not adhere to a formatting standard.
+    #[child]
+    pub copyright: MaybeEmpty<String>,
+
+    /// A URI reference to the source data for the asset.
+    ///
+    /// For example, if the asset based off a file `tank.s3d`, the value might be
+    /// `c:/models/tank.s3d`.
+    #[child]
+    pub source_data: Option<AnyUri>,
+}
+
+/// Creates a 2D image, optionally initializing it from other images.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "create_2d"]
+pub struct Create2d {
+    /// The size of the image in pixels.
+    #[child]
+    pub size_exact: Option<SizeExact>,
+
+    /// The size of the image relative to the size of the viewport.
+    #[child]
+    pub size_ratio: Option<SizeRatio>,
+
+    /// The number of mipmap levels in the image.
+    #[child]
+    pub mips: Option<Mips>,
+
+    #[child]
+    pub unnormalized: Option<Unnormalized>,
+
+    /// The number of images in the image array, if the image is an array.
+    #[child]
+    pub array: Option<ImageArray>,
+
+    /// The texel format of the image.
+    #[child]
+    pub format: Option<ImageFormat>,
+
+    /// The images that the image is initialized from.
+    #[child]
+    pub init_from: Vec<ImageInitFrom>,
+}
+
+/// Creates a 3D image, optionally initializing it from other images.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "create_3d"]
+pub struct Create3d {
+    /// The size of the image in pixels.
+    #[child]
+    pub size: Size3d,
+
+    /// The number of mipmap levels in the image.
+    #[child]
+    pub mips: Mips,
+
+    /// The number of images in the image array, if the image is an array.
+    #[child]
+    pub array: Option<ImageArray>,
+
+    /// The texel format of the image.
+    #[child]
+    pub format: Option<ImageFormat>,
+
+    /// The images that the image is initialized from.
+    #[child]
+    pub init_from: Vec<ImageInitFrom>,
+}
+
+/// Creates a cube map image, optionally initializing its faces from other images.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "create_cube"]
+pub struct CreateCube {
+    /// The size of each face of the cube map in pixels.
+    #[child]
+    pub size: SizeCube,
+
+    /// The number of mipmap levels in the image.
+    #[child]
+    pub mips: Mips,
+
+    /// The number of images in the image array, if the image is an array.
+    #[child]
+    pub array: Option<ImageArray>,
+
+    /// The texel format of the image.
+    #[child]
+    pub format: Option<ImageFormat>,
+
+    /// The images that the faces are initialized from.
+    #[child]
+    pub init_from: Vec<ImageInitFrom>,
+}
+
+/// A curve in a [`Brep`](./struct.Brep.html).
+///
+/// The curve is described in its own local frame, which is positioned by `orients` and `origin`.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "curve"]
+pub struct Curve {
+    /// An identifier for the curve that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this curve.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The shape of the curve.
+    #[child]
+    pub geometry: CurveGeometry,
+
+    /// Rotations positioning the curve, as axis-angle rotations in the order they appear.
+    #[child]
+    pub orients: Vec<Orient>,
+
+    /// The position of the curve's local origin.
+    #[child]
+    pub origin: Option<Origin>,
+}
+
+/// The shape of a [`Curve`](./struct.Curve.html).
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum CurveGeometry {
+    Circle(Circle),
+    Ellipse(Ellipse),
+    Hyperbola(Hyperbola),
+    Line(Line),
+    Nurbs(Nurbs),
+    Parabola(Parabola),
+}
+
+/// The curves of a [`Brep`](./struct.Brep.html) that its edges follow.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "curves"]
+pub struct Curves {
+    /// The curves in the list.
+    ///
+    /// There will always be at least one curve.
+    #[child]
+    #[required]
+    pub curves: Vec<Curve>,
+
+    /// Arbitrary additional information about the curves.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// An infinite cylinder around the local z axis.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "cylinder"]
+pub struct Cylinder {
+    /// The radii of the cylinder along the local x and y axes.
+    #[child]
+    pub radius: Radii,
+
+    /// Arbitrary additional information about the cylinder.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+float_values! {
+    /// A direction in space, e.g. the direction of a line.
+    pub struct Direction, "direction"
+}
+
+/// The edges of a [`Brep`](./struct.Brep.html).
+///
+/// Each edge joins two vertices along a curve. Its indices are usually the index of the curve,
+/// followed by the indices of the start and end vertices.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "edges"]
+pub struct Edges {
+    /// A unique identifier for the edges.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: String,
+
+    /// The human-friendly name for the edges.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The number of edges.
+    #[attribute]
+    pub count: usize,
+
+    /// The inputs that `p` indexes into, usually `"CURVE"` and `"VERTEX"`.
+    #[child]
+    #[required]
+    pub inputs: Vec<SharedInput>,
+
+    /// The indices into the inputs.
+    #[child]
+    pub p: Primitives,
+
+    /// Arbitrary additional information about the edges.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Edges {
+    /// Returns the input with the given semantic, if there is one.
+    pub fn input(&self, semantic: &str) -> Option<&SharedInput> {
+        self.inputs.iter().find(|input| input.semantic == semantic)
+    }
+
+    /// Returns an iterator over the indices of each edge.
+    ///
+    /// The indices in `p` are split evenly between the `count` edges.
+    pub fn iter<'a>(&'a self) -> ::std::slice::Chunks<'a, usize> {
+        let stride = self.p.len().checked_div(self.count).unwrap_or(self.p.len());
+        self.p.chunks(stride.max(1))
+    }
+}
+
+/// An ellipse around the origin in the local xy plane.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "ellipse"]
+pub struct Ellipse {
+    /// The radii of the ellipse along the local x and y axes.
+    #[child]
+    pub radius: Radii,
+
+    /// Arbitrary additional information about the ellipse.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+float_values! {
+    /// The coefficients of a plane equation.
+    pub struct Equation, "equation"
+}
+
+/// Describes how to render the scene, as a series of rendering passes.
+///
+/// Each [`Render`] is one pass, rendered in order.
+///
+/// [`Render`]: ./struct.Render.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "evaluate_scene"]
+pub struct EvaluateScene {
+    /// A unique identifier for the evaluation.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// An identifier for the evaluation that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this evaluation.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Whether the scene should be rendered this way. Defaults to `true`.
+    #[attribute]
+    #[optional_with_default = "true"]
+    pub enable: bool,
+
+    /// Metadata about this evaluation.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The rendering passes, in the order they should be rendered. There will always be at least
+    /// one render.
+    #[child]
+    #[required]
+    pub renders: Vec<Render>,
+
+    /// Arbitrary additional information about this evaluation.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Provides arbitrary additional information about an element.
+///
+/// COLLADA allows for applications to provide extra information about any given piece of data,
+/// including application-specific information that's not part of the COLLADA specification. This
+/// data can be any syntactically valid XML data, and is not parsed as part of this library, save
+/// for a few specific 3rd party applications that are directly supported.
+///
+/// # Choosing a Technique
+///
+/// There may be more than one [`Technique`][Technique] provided in `techniques`, but generally
+/// only one is used by the consuming application. The application should pick a technique
+/// with a supported profile. If there are multiple techniques with supported profiles the
+/// application is free to pick whichever technique is preferred.
+///
+/// [Technique]: struct.Technique.html
+#[derive(Debug, Clone, Default, PartialEq, ColladaElement)]
+#[name = "extra"]
+pub struct Extra {
+    /// The identifier of the element, if present. Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// A hint as to the type of information this element represents, if present. Must be
+    /// must be understood by the consuming application.
+    #[attribute]
+    #[name = "type"]
+    pub type_hint: Option<String>,
+
+    /// Asset-management information for this element, if present.
+    ///
+    /// While this is technically allowed in all `<extra>` elements, it is likely only present in
+    /// elements that describe a new "asset" of some kind, rather than in `<extra>` elements that
+    /// provide application-specific information about an existing one.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The arbitrary additional information, containing unprocessed XML events. There will always
+    /// be at least one item in `techniques`.
+    #[child]
+    #[required]
+    pub techniques: Vec<Technique>,
+}
+
+impl Extra {
+    /// Returns the technique with the specified profile, if present.
+    ///
+    /// Profile names are case-sensitive, e.g. `"blender"` or `"FCOLLADA"`.
+    pub fn technique(&self, profile: &str) -> Option<&Technique> {
+        self.techniques.iter().find(|technique| technique.profile == profile)
+    }
+
+    /// Searches a list of `<extra>` elements for the first technique with the specified profile.
+    ///
+    /// Every element that supports 3rd party extensions has an `extras` field, so this can be
+    /// used to look up application-specific data on any of them.
+    pub fn find_technique<'a>(extras: &'a [Extra], profile: &str) -> Option<&'a Technique> {
+        extras.iter().filter_map(|extra| extra.technique(profile)).next()
+    }
+
+    /// Decodes the technique for a well-known vendor profile, if present.
+    ///
+    /// See the [`profiles`](../profiles/index.html) module for the supported profiles.
+    pub fn decode<P: ExtraProfile>(&self) -> Option<P> {
+        self.technique(P::profile()).map(|technique| P::decode(&technique.elements()))
+    }
+}
+
+brep_topology! {
+    /// The faces of a [`Brep`](./struct.Brep.html), each of which is the area of a surface bounded
+    /// by one or more wires.
+    ///
+    /// The inputs are usually `"SURFACE"`, `"WIRE"`, and `"ORIENTATION"`.
+    pub struct Faces, "faces", "faces"
+}
+
+/// A literal scalar value, e.g. the target value of a [`Formula`].
+///
+/// [`Formula`]: ./struct.Formula.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "float"]
+pub struct Float {
+    /// An identifier for the value, so that it can be targeted by animations.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub value: f32,
+}
+
+/// An array of floating point values.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "float_array"]
+pub struct FloatArray {
+    #[attribute]
+    pub count: usize,
+
+    #[attribute]
+    pub id: Option<String>,
+
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The number of significant decimal digits of the values.
+    #[attribute]
+    #[optional_with_default = "6"]
+    pub digits: usize,
+
+    /// The largest exponent of the values.
+    #[attribute]
+    #[optional_with_default = "38"]
+    pub magnitude: usize,
+
+    /// The values in the array.
+    ///
+    /// Decoding the values can be deferred until they're first accessed, see [`RawArray`] for
+    /// details.
+    ///
+    /// [`RawArray`]: ../common/struct.RawArray.html
+    #[text(count = "count")]
+    pub data: RawArray<f32>,
+}
+
+/// A scalar value that's either given directly or read from a parameter.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum FloatOrParam {
+    Float(Float),
+    Param(ParamRef),
+}
+
+/// Describes the texel format of an image in a platform-independent way, so that an application
+/// can pick the closest format that it supports.
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+#[name = "hint"]
+pub struct FormatHint {
+    /// The channels in each texel, e.g. `"RGBA"`.
+    #[attribute]
+    pub channels: String,
+
+    /// The range of values in each channel, e.g. `"UNORM"` or `"FLOAT"`.
+    #[attribute]
+    pub range: String,
+
+    /// The precision of each channel, e.g. `"HIGH"`. The default is `"DEFAULT"`.
+    #[attribute]
+    pub precision: Option<String>,
+
+    /// The color space of the image, e.g. `"sRGB"`.
+    #[attribute]
+    pub space: Option<String>,
+}
+
+/// Defines a value as a mathematical expression of other values, e.g. to couple the position of
+/// one kinematics joint to another.
+///
+/// The expression itself is written in [MathML] in the formula's `technique_common`. With the
+/// `mathml` feature enabled it can be interpreted with
+/// [`FormulaTechniqueCommon::expression`].
+///
+/// [MathML]: https://www.w3.org/Math/
+/// [`FormulaTechniqueCommon::expression`]: ./struct.FormulaTechniqueCommon.html#method.expression
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "formula"]
+pub struct Formula {
+    /// A unique identifier for the formula.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this formula.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// An identifier for the formula that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The parameters that the formula's expression can refer to.
+    #[child]
+    pub newparams: Vec<FormulaNewparam>,
+
+    /// The value that the result of the formula is assigned to.
+    #[child(flatten = "target")]
+    pub target: FloatOrParam,
+
+    /// The expression defining the formula.
+    #[child]
+    pub technique_common: FormulaTechniqueCommon,
+
+    /// Application-specific definitions of the formula.
+    #[child]
+    pub techniques: Vec<Technique>,
+}
+
+impl Formula {
+    /// Returns the parameter with the given sid, if the formula declares one.
+    pub fn newparam(&self, sid: &str) -> Option<&FormulaNewparam> {
+        self.newparams.iter().find(|newparam| newparam.sid == sid)
+    }
+}
+
+/// Declares a parameter of a [`Formula`](./struct.Formula.html) or an
+/// [`InstanceKinematicsScene`](./struct.InstanceKinematicsScene.html).
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "newparam"]
+pub struct FormulaNewparam {
+    /// The identifier that the formula's expression uses to refer to the parameter.
+    #[attribute]
+    pub sid: String,
+
+    /// The value of the parameter.
+    #[child]
+    pub value: ParamValue,
+}
+
+/// The common technique of a [`Formula`], which holds the formula's MathML expression.
+///
+/// The MathML is kept as a tree of [`RawElement`]s rather than being interpreted, since the
+/// specification allows any MathML here. With the `mathml` feature enabled,
+/// [`expression`](#method.expression) interprets content MathML as an [`Expression`].
+///
+/// [`Formula`]: ./struct.Formula.html
+/// [`RawElement`]: ../raw/struct.RawElement.html
+/// [`Expression`]: ../mathml/enum.Expression.html
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FormulaTechniqueCommon {
+    /// The elements directly inside of the technique, usually a single `<math>` element.
+    pub contents: Vec<RawElement>,
+}
+
+impl FormulaTechniqueCommon {
+    /// Returns the `<math>` element containing the formula's expression, if there is one.
+    pub fn math(&self) -> Option<&RawElement> {
+        self.contents.iter().find(|element| element.name.local_name == "math")
+    }
+
+    /// Interprets the formula's expression.
+    ///
+    /// Returns `None` if there's no `<math>` element, or if it doesn't contain a single content
+    /// MathML expression.
+    ///
+    /// Only available with the `mathml` feature enabled.
+    #[cfg(feature = "mathml")]
+    pub fn expression(&self) -> Option<::mathml::Expression> {
+        self.math().and_then(::mathml::Expression::from_math)
+    }
+}
+
+impl ColladaElement for FormulaTechniqueCommon {
+    fn name_test(name: &str) -> bool {
+        name == "technique_common"
+    }
+
+    fn parse_element<R>(
+        reader: &mut DocumentReader<R>,
+        element_start: ElementStart,
+    ) -> Result<FormulaTechniqueCommon>
+    where
+        R: XmlPull,
+    {
+        for attribute in element_start.attributes {
+            let error = Error {
+                position: reader.position(),
+                kind: ErrorKind::UnexpectedAttribute {
+                    element: "technique_common",
+                    attribute: attribute.name.local_name.clone(),
+                    expected: vec![],
+                },
+            };
+            reader.tolerate(error, Strictness::Lenient)?;
+            reader.preserve_attribute(attribute);
+        }
+
+        // Everything up to the matching end tag is captured, whatever the elements are named.
+        let mut events = Vec::new();
+        let mut depth = 0;
+        loop {
+            let event = reader.next()?;
+            match event {
+                XmlEvent::StartElement { .. } => { depth += 1; }
+
+                XmlEvent::EndElement { .. } => {
+                    if depth == 0 {
+                        break;
+                    } else {
+                        depth -= 1;
+                    }
+                }
+
+                _ => {}
+            }
+
+            events.push(event);
+        }
+
+        Ok(FormulaTechniqueCommon {
+            contents: RawElement::from_events(&events),
+        })
+    }
+
+    fn add_names(names: &mut Vec<&'static str>) {
+        names.push("technique_common");
+    }
+
+    fn write_element<W: Write>(&self, writer: &mut XmlWriter<W>) -> writer::Result<()> {
+        writer.start_element("technique_common")?;
+        for element in &self.contents {
+            writer.raw_element(element)?;
+        }
+        writer.end_element("technique_common")
+    }
+}
+
+/// Defines geographic location information for an [`Asset`][Asset].
+///
+/// A geographic location is given in latitude, longitude, and altitude coordinates as defined by
+/// [WGS 84][WGS 84] world geodetic system.
+///
+/// [Asset]: struct.Asset.html
+/// [WGS 84]: https://en.wikipedia.org/wiki/World_Geodetic_System#A_new_World_Geodetic_System:_WGS_84
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "geographic_location"]
+pub struct GeographicLocation {
+    /// The longitude of the location in degrees, which should be in the range -180.0 to 180.0.
+    #[child]
+    pub longitude: f64,
+
+    /// The latitude of the location in degrees, which should be in the range -90.0 to 90.0.
+    #[child]
+    pub latitude: f64,
+
+    /// Specifies the altitude, either relative to global sea level or relative to ground level.
+    #[child]
+    pub altitude: Altitude,
+}
+
+/// A geometric element of unknown type.
+///
+/// Each variant wraps a single value containing a given type of geometric data.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum GeometricElement {
+    #[collada(version = "1.5")]
+    Brep(Brep),
+    ConvexMesh(ConvexMesh),
+    Mesh(Mesh),
+    Spline(Spline),
+}
+
+impl GeometricElement {
+    /// Returns the inner [`Brep`](./struct.Brep.html) if the geometry is a boundary
+    /// representation.
+    pub fn as_brep(&self) -> Option<&Brep> {
+        match *self {
+            GeometricElement::Brep(ref brep) => Some(brep),
+            _ => None,
+        }
+    }
+}
+
+/// Describes the visual shape and appearance of an object in a scene.
+///
+/// The geometric data itself is held in `geometric_element`. Only boundary representations
+/// ([`Brep`]) are currently parsed for `1.5.0` documents, the other kinds of geometry are
+/// accepted but their contents are skipped.
+///
+/// [`Brep`]: ./struct.Brep.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "geometry"]
+pub struct Geometry {
+    /// A unique identifier for the geometry instance.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this geometry instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about this geometry instance and the data it contains.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The actual data for the geometry instance.
+    #[child]
+    pub geometric_element: GeometricElement,
+
+    /// Arbitrary additional information about this geometry instance and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Image data embedded in a document, e.g. the contents of a PNG file.
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+#[name = "hex"]
+pub struct Hex {
+    /// The format of the data, e.g. `"PNG"`.
+    #[attribute]
+    pub format: String,
+
+    /// The bytes of the image.
+    #[text]
+    pub data: HexBinary,
+}
+
+/// A hyperbola in the local xy plane, opening along the local x axis.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "hyperbola"]
+pub struct Hyperbola {
+    /// The major and minor radii of the hyperbola.
+    #[child]
+    pub radius: Radii,
+
+    /// Arbitrary additional information about the hyperbola.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// An array of ids referring to other elements in the document.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "IDREF_array"]
+pub struct IdrefArray {
+    #[attribute]
+    pub count: usize,
+
+    #[attribute]
+    pub id: Option<String>,
+
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The ids in the array.
+    #[text(count = "count", split_with = "::utils::split_xml_list")]
+    pub data: Vec<String>,
+}
+
+/// Declares an image used by an effect, e.g. as the source of a texture.
+///
+/// An image is either initialized directly from a file or embedded data using `init_from`, or
+/// created with a given size and format using one of `create_2d`, `create_3d`, and
+/// `create_cube`. A created image may itself be initialized from other images.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "image"]
+#[write_with = "write_image"]
+pub struct Image {
+    /// A unique identifier for the image.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// An identifier for the image that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this image.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about the image and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// Whether the image can be rendered to, if present.
+    #[child]
+    pub renderable: Option<Renderable>,
+
+    /// The file or data that the image is initialized from.
+    #[child]
+    pub init_from: Option<ImageInitFrom>,
+
+    #[child]
+    pub create_2d: Option<Create2d>,
+
+    #[child]
+    pub create_3d: Option<Create3d>,
+
+    #[child]
+    pub create_cube: Option<CreateCube>,
+
+    /// Arbitrary additional information about this image and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Image {
+    /// Returns an iterator over everything that the image is initialized from, including the
+    /// images that a created image is initialized from.
+    pub fn init_froms<'a>(&'a self) -> Box<Iterator<Item = &'a ImageInitFrom> + 'a> {
+        let created = self.create_2d.iter().flat_map(|create| &create.init_from)
+            .chain(self.create_3d.iter().flat_map(|create| &create.init_from))
+            .chain(self.create_cube.iter().flat_map(|create| &create.init_from));
+        Box::new(self.init_from.iter().chain(created))
+    }
+
+    /// Returns the location of the image data, if it's stored separately from the document.
+    ///
+    /// For images initialized from several files (e.g. the faces of a cube map), this is the
+    /// first of them.
+    pub fn uri(&self) -> Option<&AnyUri> {
+        self.init_froms().filter_map(|init_from| init_from.source.uri()).next()
+    }
+
+    /// Returns the image data, if it's embedded in the document.
+    ///
+    /// For images initialized from several blocks of data (e.g. the faces of a cube map), this is
+    /// the first of them.
+    pub fn embedded_data(&self) -> Option<&[u8]> {
+        self.init_froms().filter_map(|init_from| init_from.source.data()).next()
+    }
+
+    /// Returns the format, dimensions, and channel count of the image data embedded in the
+    /// document, read from the data's header.
+    ///
+    /// For images initialized from several blocks of data, this is the information for the first
+    /// of them.
+    ///
+    /// Returns `None` if the image data isn't embedded, or if it isn't in one of the formats
+    /// recognized by [`image::sniff`](../image/fn.sniff.html). For image data stored in a separate
+    /// file, load the file at [`uri`](#method.uri) and pass it to `image::sniff` instead.
+    pub fn info(&self) -> Option<ImageInfo> {
+        self.embedded_data().and_then(image::sniff)
+    }
+}
+
+/// Writes an [`Image`], translating it to the 1.4 form of `<image>` when writing 1.4.
+///
+/// In 1.4 the source of an image is written directly in `<init_from>` or `<data>`, with the format
+/// of embedded data given by the image itself. Created images and the other additions to
+/// `<image>` and `<init_from>` in 1.5 have no equivalent in 1.4, so they can't be written as 1.4.
+///
+/// [`Image`]: ./struct.Image.html
+fn write_image<W: Write>(image: &Image, writer: &mut XmlWriter<W>) -> writer::Result<()> {
+    let as_v1_4 = writer.version().is_some_and(|version| version.is_compatible("1.4"));
+
+    writer.start_element("image")?;
+    if let Some(ref id) = image.id {
+        writer.attribute("id", id)?;
+    }
+
+    if as_v1_4 {
+        if image.sid.is_some() {
+            writer.check_version("sid", "1.5")?;
+        }
+        if let Some(ref name) = image.name {
+            writer.attribute("name", name)?;
+        }
+        if let Some(ImageInitFrom { source: ImageSource::Hex(ref hex), .. }) = image.init_from {
+            writer.attribute("format", &hex.format)?;
+        }
+
+        if let Some(ref asset) = image.asset {
+            asset.write_element(writer)?;
+        }
+
+        if image.renderable.is_some() {
+            writer.check_version("renderable", "1.5")?;
+        }
+        if image.create_2d.is_some() {
+            writer.check_version("create_2d", "1.5")?;
+        }
+        if image.create_3d.is_some() {
+            writer.check_version("create_3d", "1.5")?;
+        }
+        if image.create_cube.is_some() {
+            writer.check_version("create_cube", "1.5")?;
+        }
+
+        if let Some(ref init_from) = image.init_from {
+            // Only the source of the image can be written, the attributes of `<init_from>` are
+            // new in 1.5.
+            if !init_from.mips_generate {
+                writer.check_version("mips_generate", "1.5")?;
+            }
+            if init_from.array_index != 0 {
+                writer.check_version("array_index", "1.5")?;
+            }
+            if init_from.mip_index != 0 {
+                writer.check_version("mip_index", "1.5")?;
+            }
+            if init_from.depth != 0 {
+                writer.check_version("depth", "1.5")?;
+            }
+            if init_from.face.is_some() {
+                writer.check_version("face", "1.5")?;
+            }
+
+            match init_from.source {
+                ImageSource::Ref(ref image_ref) => writer.text_element("init_from", &image_ref.uri)?,
+                ImageSource::Hex(ref hex) => writer.text_element("data", &hex.data)?,
+            }
+        }
+    } else {
+        if let Some(ref sid) = image.sid {
+            writer.attribute("sid", sid)?;
+        }
+        if let Some(ref name) = image.name {
+            writer.attribute("name", name)?;
+        }
+
+        if let Some(ref asset) = image.asset {
+            asset.write_element(writer)?;
+        }
+        if let Some(ref renderable) = image.renderable {
+            renderable.write_element(writer)?;
+        }
+        if let Some(ref init_from) = image.init_from {
+            init_from.write_element(writer)?;
+        }
+        if let Some(ref create_2d) = image.create_2d {
+            create_2d.write_element(writer)?;
+        }
+        if let Some(ref create_3d) = image.create_3d {
+            create_3d.write_element(writer)?;
+        }
+        if let Some(ref create_cube) = image.create_cube {
+            create_cube.write_element(writer)?;
+        }
+    }
+
+    for extra in &image.extras {
+        extra.write_element(writer)?;
+    }
+    writer.end_element("image")
+}
+
+/// The number of images in an image array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ColladaElement)]
+#[name = "array"]
+pub struct ImageArray {
+    #[attribute]
+    pub length: usize,
+}
+
+/// The texel format of a created image.
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+#[name = "format"]
+pub struct ImageFormat {
+    /// A description of the format, for picking the closest format the application supports.
+    #[child]
+    pub hint: Option<FormatHint>,
+
+    /// The exact format to use if the application supports it, e.g. `"R8G8B8A8_UNORM"`.
+    #[child]
+    pub exact: Option<String>,
+}
+
+/// The file or data that an [`Image`][Image] (or part of one) is initialized from.
+///
+/// [Image]: struct.Image.html
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+#[name = "init_from"]
+pub struct ImageInitFrom {
+    /// Whether mipmaps should be generated from the image.
+    #[attribute]
+    #[optional_with_default = "true"]
+    pub mips_generate: bool,
+
+    /// The index in the image array to initialize.
+    #[attribute]
+    #[optional_with_default = "0"]
+    pub array_index: usize,
+
+    /// The mipmap level to initialize.
+    #[attribute]
+    #[optional_with_default = "0"]
+    pub mip_index: usize,
+
+    /// The slice of a 3D image to initialize.
+    #[attribute]
+    #[optional_with_default = "0"]
+    pub depth: usize,
+
+    /// The face of a cube map to initialize, e.g. `"POSITIVE_X"`.
+    #[attribute]
+    pub face: Option<String>,
+
+    /// The file or data to initialize from.
+    #[child]
+    pub source: ImageSource,
+}
+
+/// The location of image data that's stored separately from the document.
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+#[name = "ref"]
+pub struct ImageRef {
+    #[text]
+    pub uri: AnyUri,
+}
+
+/// The source of the data for an [`ImageInitFrom`][ImageInitFrom].
+///
+/// [ImageInitFrom]: struct.ImageInitFrom.html
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+pub enum ImageSource {
+    Hex(Hex),
+    Ref(ImageRef),
+}
+
+impl ImageSource {
+    /// Returns the location of the data, if it's stored separately from the document.
+    pub fn uri(&self) -> Option<&AnyUri> {
+        match *self {
+            ImageSource::Ref(ref image_ref) => Some(&image_ref.uri),
+            _ => None,
+        }
+    }
+
+    /// Returns the data, if it's embedded in the document.
+    pub fn data(&self) -> Option<&[u8]> {
+        match *self {
+            ImageSource::Hex(ref hex) => Some(hex.data.as_bytes()),
+            _ => None,
+        }
+    }
+}
+
+/// Instantiates a kinematics scene within the document's [`Scene`], binding its kinematics
+/// models and joint axes to nodes in the visual scene.
+///
+/// [`Scene`]: ./struct.Scene.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_kinematics_scene"]
+pub struct InstanceKinematicsScene {
+    /// The location of the kinematics scene to instantiate.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// An identifier for the instance that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about this instance.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The parameters declared by this instance.
+    #[child]
+    pub newparams: Vec<FormulaNewparam>,
+
+    /// Overrides for the parameters of the kinematics scene.
+    #[child]
+    pub setparams: Vec<KinematicsSetparam>,
+
+    /// The nodes moved by each of the scene's kinematics models.
+    #[child]
+    pub bind_kinematics_models: Vec<BindKinematicsModel>,
+
+    /// The nodes moved by each of the scene's joint axes.
+    #[child]
+    pub bind_joint_axes: Vec<BindJointAxis>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl InstanceKinematicsScene {
+    /// Returns the parameter declared with the given sid, if there is one.
+    pub fn newparam(&self, sid: &str) -> Option<&FormulaNewparam> {
+        self.newparams.iter().find(|param| param.sid == sid)
+    }
+
+    /// Returns the `<setparam>` that overrides the parameter `sid`, if there is one.
+    pub fn setparam(&self, sid: &str) -> Option<&KinematicsSetparam> {
+        self.setparams.iter().find(|param| param.reference == sid)
+    }
+}
+
+/// Instantiates a physics scene within the document's [`Scene`].
+///
+/// [`Scene`]: ./struct.Scene.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_physics_scene"]
+pub struct InstancePhysicsScene {
+    /// The location of the physics scene to instantiate.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// An identifier for the instance that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Instantiates a visual scene as the document's [`Scene`].
+///
+/// [`Scene`]: ./struct.Scene.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_visual_scene"]
+pub struct InstanceVisualScene {
+    /// The location of the visual scene to instantiate.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// An identifier for the instance that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// A literal integer parameter value.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "int"]
+pub struct Int {
+    #[text]
+    pub value: i64,
+}
+
+/// An array of integer values.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "int_array"]
+pub struct IntArray {
+    #[attribute]
+    pub count: usize,
+
+    #[attribute]
+    pub id: Option<String>,
+
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The smallest value that can appear in the array.
+    #[attribute]
+    #[name = "minInclusive"]
+    #[optional_with_default = "-2147483648"]
+    pub min_inclusive: i64,
+
+    /// The largest value that can appear in the array.
+    #[attribute]
+    #[name = "maxInclusive"]
+    #[optional_with_default = "2147483647"]
+    pub max_inclusive: i64,
+
+    /// The values in the array.
+    ///
+    /// Decoding the values can be deferred until they're first accessed, see [`RawArray`] for
+    /// details.
+    ///
+    /// [`RawArray`]: ../common/struct.RawArray.html
+    #[text(count = "count")]
+    pub data: RawArray<i64>,
+}
+
+/// Overrides the value of a parameter for an
+/// [`InstanceKinematicsScene`](./struct.InstanceKinematicsScene.html).
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "setparam"]
+pub struct KinematicsSetparam {
+    /// The sid of the parameter to override.
+    #[attribute]
+    #[name = "ref"]
+    pub reference: String,
+
+    /// The new value of the parameter.
+    #[child]
+    pub value: ParamValue,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum Library {
+    Animations(LibraryAnimations),
+    AnimationClips(LibraryAnimationClips),
+    #[collada(version = "1.5")]
+    ArticulatedSystmes(LibraryArticulatedSystems),
+    Cameras(LibraryCameras),
+    Controllers(LibraryControllers),
+    Effects(LibraryEffects),
+    ForceFields(LibraryForceFields),
+    #[collada(version = "1.5")]
+    Formulas(LibraryFormulas),
+    Geometries(LibraryGeometries),
+    Images(LibraryImages),
+    #[collada(version = "1.5")]
+    Joints(LibraryJoints),
+    #[collada(version = "1.5")]
+    KinematicsModels(LibraryKinematicsModels),
+    #[collada(version = "1.5")]
+    KinematicsScenes(LibraryKinematicsScenes),
+    Lights(LibraryLights),
+    Materials(LibraryMaterials),
+    Nodes(LibraryNodes),
+    PhysicsMaterials(LibraryPhysicsMaterials),
+    PhysicsModels(LibraryPhysicsModels),
+    PhysicsScenes(LibraryPhysicsScenes),
+    VisualScenes(LibraryVisualScenes),
+}
+
+impl Library {
+    pub fn as_library_formulas(&self) -> Option<&LibraryFormulas> {
+        match *self {
+            Library::Formulas(ref library_formulas) => Some(library_formulas),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_geometries(&self) -> Option<&LibraryGeometries> {
+        match *self {
+            Library::Geometries(ref library_geometries) => Some(library_geometries),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_images(&self) -> Option<&LibraryImages> {
+        match *self {
+            Library::Images(ref library_images) => Some(library_images),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_animations"]
+pub struct LibraryAnimations;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_animation_clips"]
+pub struct LibraryAnimationClips;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_articulated_systems"]
+pub struct LibraryArticulatedSystems;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_cameras"]
+pub struct LibraryCameras;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_controllers"]
+pub struct LibraryControllers;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_effects"]
+pub struct LibraryEffects;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_force_fields"]
+pub struct LibraryForceFields;
+
+/// Contains formulas for the document.
+///
+/// The formulas are contained in `formulas` by one or more [`Formula`][Formula] instances,
+/// `LibraryFormulas` is only a container and does not represent any formula data itself.
+///
+/// [Formula]: struct.Formula.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_formulas"]
+pub struct LibraryFormulas {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The formulas contained within this library instance.
+    ///
+    /// There will always be at least one formula.
+    #[child]
+    #[required]
+    pub formulas: Vec<Formula>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl LibraryFormulas {
+    /// Returns the formula with the given id, if it's in this library.
+    pub fn formula(&self, id: &str) -> Option<&Formula> {
+        self.formulas.iter().find(|formula| formula.id.as_deref() == Some(id))
+    }
+}
+
+/// Contains geometries for the document.
+///
+/// The geometries are contained in `geometries` by one or more [`Geometry`][Geometry]
+/// instances, `LibraryGeometries` is only a container and does not represent any geometric data
+/// itself.
+///
+/// [Geometry]: struct.Geometry.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_geometries"]
+pub struct LibraryGeometries {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The geometries contained within this library instance.
+    ///
+    /// An empty `<library_geometries>` is accepted even though the specification requires at
+    /// least one geometry, so this may be empty.
+    #[child]
+    pub geometries: Vec<Geometry>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl LibraryGeometries {
+    /// Returns the geometry with the given id, if it's in this library.
+    pub fn geometry(&self, id: &str) -> Option<&Geometry> {
+        self.geometries.iter().find(|geometry| geometry.id.as_deref() == Some(id))
+    }
+}
+
+/// Contains images for the document.
+///
+/// The images are contained in `images` by zero or more [`Image`][Image] instances,
+/// `LibraryImages` is only a container and does not represent any image data itself.
+///
+/// [Image]: struct.Image.html
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-pub enum Library {
-    Animations(LibraryAnimations),
-    AnimationClips(LibraryAnimationClips),
-    ArticulatedSystmes(LibraryArticulatedSystems),
-    Cameras(LibraryCameras),
-    Controllers(LibraryControllers),
-    Effects(LibraryEffects),
-    ForceFields(LibraryForceFields),
-    Formulas(LibraryFormulas),
-    Geometries(LibraryGeometries),
-    Images(LibraryImages),
-    Joints(LibraryJoints),
-    KinematicsModels(LibraryKinematicsModels),
-    KinematicsScenes(LibraryKinematicsScenes),
-    Lights(LibraryLights),
-    Materials(LibraryMaterials),
-    Nodes(LibraryNodes),
-    PhysicsMaterials(LibraryPhysicsMaterials),
-    PhysicsModels(LibraryPhysicsModels),
-    PhysicsScenes(LibraryPhysicsScenes),
-    VisualScenes(LibraryVisualScenes),
+#[name = "library_images"]
+pub struct LibraryImages {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The images contained within this library instance.
+    ///
+    /// Documents without any textures often still have an empty `<library_images>`, so this may
+    /// be empty.
+    #[child]
+    pub images: Vec<Image>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_joints"]
+pub struct LibraryJoints;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_kinematics_models"]
+pub struct LibraryKinematicsModels;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_kinematics_scenes"]
+pub struct LibraryKinematicsScenes;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_lights"]
+pub struct LibraryLights;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_materials"]
+pub struct LibraryMaterials;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_nodes"]
+pub struct LibraryNodes;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_physics_materials"]
+pub struct LibraryPhysicsMaterials;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_physics_models"]
+pub struct LibraryPhysicsModels;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_physics_scenes"]
+pub struct LibraryPhysicsScenes;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_visual_scenes"]
+pub struct LibraryVisualScenes;
+
+/// An infinite straight line.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "line"]
+pub struct Line {
+    /// A point on the line.
+    #[child]
+    pub origin: Origin,
+
+    /// The direction of the line.
+    #[child]
+    pub direction: Direction,
+
+    /// Arbitrary additional information about the line.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "mesh"]
+pub struct Mesh;
+
+/// The number of mipmap levels in a created image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ColladaElement)]
+#[name = "mips"]
+pub struct Mips {
+    /// The number of mipmap levels, where `0` means all of them.
+    #[attribute]
+    pub levels: usize,
+
+    /// Whether the mipmaps should be generated after the image is initialized.
+    #[attribute]
+    pub auto_generate: bool,
+}
+
+/// An array of names, e.g. the names of joints.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "Name_array"]
+pub struct NameArray {
+    #[attribute]
+    pub count: usize,
+
+    #[attribute]
+    pub id: Option<String>,
+
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The names in the array.
+    #[text(count = "count", split_with = "::utils::split_xml_list")]
+    pub data: Vec<String>,
+}
+
+/// A non-uniform rational B-spline curve.
+///
+/// The control vertices, and the knots and weights, are read from the sources through
+/// `control_vertices`.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "nurbs"]
+pub struct Nurbs {
+    /// The degree of the curve.
+    #[attribute]
+    pub degree: usize,
+
+    /// Whether the curve is closed.
+    #[attribute]
+    #[optional_with_default = "false"]
+    pub closed: bool,
+
+    /// The sources holding the control vertices, knots, and weights.
+    #[child]
+    #[required]
+    pub sources: Vec<Source>,
+
+    /// Connects the sources to their roles in the curve, e.g. `"POSITION"` or `"KNOT"`.
+    #[child]
+    pub control_vertices: ControlVertices,
+
+    /// Arbitrary additional information about the curve.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// A non-uniform rational B-spline surface.
+///
+/// The control vertices, and the knots and weights in each direction, are read from the sources
+/// through `control_vertices`.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "nurbs_surface"]
+pub struct NurbsSurface {
+    /// The degree of the surface in the u direction.
+    #[attribute]
+    pub degree_u: usize,
+
+    /// Whether the surface is closed in the u direction.
+    #[attribute]
+    #[optional_with_default = "false"]
+    pub closed_u: bool,
+
+    /// The degree of the surface in the v direction.
+    #[attribute]
+    pub degree_v: usize,
+
+    /// Whether the surface is closed in the v direction.
+    #[attribute]
+    #[optional_with_default = "false"]
+    pub closed_v: bool,
+
+    /// The sources holding the control vertices, knots, and weights.
+    #[child]
+    #[required]
+    pub sources: Vec<Source>,
+
+    /// Connects the sources to their roles in the surface, e.g. `"POSITION"` or `"UKNOT"`.
+    #[child]
+    pub control_vertices: ControlVertices,
+
+    /// Arbitrary additional information about the surface.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+float_values! {
+    /// A rotation of the local frame of a curve or surface, as an axis followed by an angle in
+    /// degrees.
+    pub struct Orient, "orient"
+}
+
+float_values! {
+    /// A point in space, e.g. the origin of the local frame of a curve or surface.
+    pub struct Origin, "origin"
+}
+
+/// A parabola in the local xy plane, opening along the local x axis.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "parabola"]
+pub struct Parabola {
+    /// The distance from the vertex of the parabola to its focus.
+    #[child]
+    pub focal: f32,
+
+    /// Arbitrary additional information about the parabola.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Describes one of the values read by an [`Accessor`](./struct.Accessor.html).
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "param"]
+pub struct Param {
+    /// The name of the value, e.g. `"X"`.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The subidentifier of this parameter.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The type of the value, e.g. `"float"`.
+    #[attribute]
+    #[name = "type"]
+    pub data_type: String,
+
+    /// The user-defined meaning of the parameter.
+    #[attribute]
+    pub semantic: Option<String>,
+}
+
+/// A reference to a parameter, used in place of a literal value.
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+#[name = "param"]
+pub struct ParamRef {
+    /// The sid of the parameter being referenced.
+    #[attribute]
+    #[name = "ref"]
+    pub reference: String,
+}
+
+/// The type and value of a parameter, e.g. of a [`FormulaNewparam`] or a [`KinematicsSetparam`].
+///
+/// Each variant wraps a single value of a given parameter type. Only a [`KinematicsSetparam`]
+/// may connect to another parameter.
+///
+/// [`FormulaNewparam`]: ./struct.FormulaNewparam.html
+/// [`KinematicsSetparam`]: ./struct.KinematicsSetparam.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum ParamValue {
+    Bool(Bool),
+    ConnectParam(ConnectParam),
+    Float(Float),
+    Int(Int),
+    Sidref(Sidref),
+}
+
+brep_topology! {
+    /// Maps the edges of a [`Brep`](./struct.Brep.html) onto the surfaces of the faces they bound.
+    ///
+    /// The inputs are usually `"EDGE"`, `"FACE"`, and `"CURVE2D"`.
+    pub struct Pcurves, "pcurves", "pcurves"
+}
+
+/// An infinite plane.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "plane"]
+pub struct Plane {
+    /// The coefficients `a`, `b`, `c`, and `d` of the plane equation `ax + by + cz + d = 0`.
+    #[child]
+    pub equation: Equation,
+
+    /// Arbitrary additional information about the plane.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// The indices of a topology element, i.e. a `<p>` element.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "p"]
+pub struct Primitives {
+    #[text]
+    data: Vec<usize>,
+}
+
+impl ::std::ops::Deref for Primitives {
+    type Target = [usize];
+
+    fn deref(&self) -> &[usize] { &*self.data }
+}
+
+float_values! {
+    /// A pair of radii, e.g. the major and minor radii of an ellipse or torus.
+    pub struct Radii, "radius"
+}
+
+/// A single rendering pass of an [`EvaluateScene`].
+///
+/// [`EvaluateScene`]: ./struct.EvaluateScene.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "render"]
+pub struct Render {
+    /// The human-friendly name for this pass.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// An identifier for the pass that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The location of the node containing the camera to render from. If there's no camera, the
+    /// pass renders in screen space, e.g. a full-screen post-processing effect.
+    #[attribute]
+    pub camera_node: Option<AnyUri>,
+
+    /// The layers of the scene to render. If there are no layers, the whole scene is rendered.
+    #[child]
+    #[name = "layer"]
+    pub layers: Vec<String>,
+
+    /// The material used to render the pass, if any. Without a material, the scene is rendered
+    /// with the materials bound to its geometry.
+    #[child]
+    pub instance_material: Option<RenderInstanceMaterial>,
+
+    /// Arbitrary additional information about this pass.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Instantiates the material used by a [`Render`], which overrides the materials in the scene
+/// for that pass.
+///
+/// [`Render`]: ./struct.Render.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_material"]
+pub struct RenderInstanceMaterial {
+    /// The location of the material to instantiate.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// An identifier for the instance that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Selects which technique and pass of the material's effect to use, if any.
+    #[child]
+    pub technique_override: Option<TechniqueOverride>,
+
+    /// Binds the parameters of the material's effect to values in the scene.
+    #[child]
+    pub binds: Vec<Bind>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
 }
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_animations"]
-pub struct LibraryAnimations;
+/// Whether an image can be rendered to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ColladaElement)]
+#[name = "renderable"]
+pub struct Renderable {
+    /// Whether the image is shared between all of the effects that render to it, rather than each
+    /// effect rendering to its own copy.
+    #[attribute]
+    pub share: bool,
+}
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_animation_clips"]
-pub struct LibraryAnimationClips;
+/// The scene described by the document.
+///
+/// A document may contain any number of visual, physics, and kinematics scenes in its libraries,
+/// but only the ones instantiated here are part of the document's scene.
+#[derive(Debug, Clone, Default, PartialEq, ColladaElement)]
+#[name = "scene"]
+pub struct Scene {
+    /// The physics scenes that simulate the visual scene, if any.
+    #[child]
+    pub instance_physics_scenes: Vec<InstancePhysicsScene>,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_articulated_systems"]
-pub struct LibraryArticulatedSystems;
+    /// The visual scene to display, if any.
+    #[child]
+    pub instance_visual_scene: Option<InstanceVisualScene>,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_cameras"]
-pub struct LibraryCameras;
+    /// The kinematics scene that animates the visual scene, if any.
+    #[child]
+    #[collada(version = "1.5")]
+    pub instance_kinematics_scene: Option<InstanceKinematicsScene>,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_controllers"]
-pub struct LibraryControllers;
+    /// The rendering passes used to render the scene, if any.
+    #[child]
+    pub evaluate_scenes: Vec<EvaluateScene>,
+
+    /// Arbitrary additional information about the scene.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
 
+/// Declares the input semantic of a data source and connects a consumer of that source, along
+/// with the offset of the input's indices in the consumer's index tuples.
+///
+/// Inputs that have the same `offset` value are driven by the same index value from the
+/// consumer.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_effects"]
-pub struct LibraryEffects;
+#[name = "input"]
+pub struct SharedInput {
+    /// The offset into the tuples of indices provided by the parent element.
+    #[attribute]
+    pub offset: usize,
+
+    /// The meaning of the input connnection, e.g. `Semantic::Vertex` or
+    /// `Semantic::Other("EDGE".into())`.
+    #[attribute]
+    pub semantic: Semantic,
+
+    /// The location of the data source.
+    #[attribute]
+    pub source: UriFragment,
+
+    /// Which inputs to group as a single set.
+    ///
+    /// This is helpful when multiple inputs share the same semantic.
+    #[attribute]
+    pub set: Option<usize>,
+}
 
+brep_topology! {
+    /// The shells of a [`Brep`](./struct.Brep.html), each of which is a connected set of faces.
+    ///
+    /// The inputs are usually `"FACE"` and `"ORIENTATION"`.
+    pub struct Shells, "shells", "shells"
+}
+
+/// A parameter value that addresses an element in the document by its chain of sids, e.g.
+/// `"kinematics_model/joint0"`.
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+#[name = "SIDREF"]
+pub struct Sidref {
+    #[text]
+    pub value: String,
+}
+
+/// An array of sid references addressing other elements in the document.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_force_fields"]
-pub struct LibraryForceFields;
+#[name = "SIDREF_array"]
+pub struct SidrefArray {
+    #[attribute]
+    pub count: usize,
+
+    #[attribute]
+    pub id: Option<String>,
+
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The sid references in the array.
+    #[text(count = "count", split_with = "::utils::split_xml_list")]
+    pub data: Vec<String>,
+}
 
+/// A sid reference that's either given directly or read from a parameter.
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+pub enum SidrefOrParam {
+    Param(ParamRef),
+    Sidref(Sidref),
+}
+
+/// The size of a 3D image in pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ColladaElement)]
+#[name = "size"]
+pub struct Size3d {
+    #[attribute]
+    pub width: usize,
+
+    #[attribute]
+    pub height: usize,
+
+    #[attribute]
+    pub depth: usize,
+}
+
+/// The size of each face of a cube map in pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ColladaElement)]
+#[name = "size"]
+pub struct SizeCube {
+    #[attribute]
+    pub width: usize,
+}
+
+/// The size of a 2D image in pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ColladaElement)]
+#[name = "size_exact"]
+pub struct SizeExact {
+    #[attribute]
+    pub width: usize,
+
+    #[attribute]
+    pub height: usize,
+}
+
+/// The size of a 2D image relative to the size of the viewport.
+#[derive(Debug, Clone, Copy, PartialEq, ColladaElement)]
+#[name = "size_ratio"]
+pub struct SizeRatio {
+    #[attribute]
+    pub width: f32,
+
+    #[attribute]
+    pub height: f32,
+}
+
+brep_topology! {
+    /// The solids of a [`Brep`](./struct.Brep.html), each of which is a volume enclosed by one or
+    /// more shells.
+    ///
+    /// The inputs are usually `"SHELL"` and `"ORIENTATION"`.
+    pub struct Solids, "solids", "solids"
+}
+
+/// Declares a data repository that provides values according to the semantics of an
+/// [`UnsharedInput`] or [`SharedInput`] that refers to it.
+///
+/// [`UnsharedInput`]: ./struct.UnsharedInput.html
+/// [`SharedInput`]: ./struct.SharedInput.html
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_formulas"]
-pub struct LibraryFormulas;
+#[name = "source"]
+pub struct Source {
+    /// A unique identifier for the source.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: String,
+
+    /// The human-friendly name for this source.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about the source and the data it contains.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The raw values of the source.
+    #[child]
+    pub array: Option<Array>,
+
+    /// Describes how the values in `array` are read.
+    #[child(flatten = "technique_common")]
+    pub accessor: Option<Accessor>,
+
+    /// Application-specific descriptions of the source.
+    #[child]
+    pub techniques: Vec<Technique>,
+}
 
+/// A sphere around the origin.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_geometries"]
-pub struct LibraryGeometries;
+#[name = "sphere"]
+pub struct Sphere {
+    /// The radius of the sphere.
+    #[child]
+    pub radius: f32,
+
+    /// Arbitrary additional information about the sphere.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
 
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_images"]
-pub struct LibraryImages;
+#[name = "spline"]
+pub struct Spline;
 
+/// A surface in a [`Brep`](./struct.Brep.html).
+///
+/// The surface is described in its own local frame, which is positioned by `orients` and
+/// `origin`.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_joints"]
-pub struct LibraryJoints;
+#[name = "surface"]
+pub struct Surface {
+    /// An identifier for the surface that's unique within its parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this surface.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The shape of the surface.
+    #[child]
+    pub geometry: SurfaceGeometry,
+
+    /// Rotations positioning the surface, as axis-angle rotations in the order they appear.
+    #[child]
+    pub orients: Vec<Orient>,
+
+    /// The position of the surface's local origin.
+    #[child]
+    pub origin: Option<Origin>,
+}
 
+/// The shape of a [`Surface`](./struct.Surface.html).
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_kinematics_models"]
-pub struct LibraryKinematicsModels;
+pub enum SurfaceGeometry {
+    Cone(Cone),
+    Cylinder(Cylinder),
+    NurbsSurface(NurbsSurface),
+    Plane(Plane),
+    Sphere(Sphere),
+    SweptSurface(SweptSurface),
+    Torus(Torus),
+}
 
+/// The curves of a [`Brep`](./struct.Brep.html) in the two-dimensional parameter space of its
+/// surfaces.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_kinematics_scenes"]
-pub struct LibraryKinematicsScenes;
+#[name = "surface_curves"]
+pub struct SurfaceCurves {
+    /// The curves in the list.
+    ///
+    /// There will always be at least one curve.
+    #[child]
+    #[required]
+    pub curves: Vec<Curve>,
+
+    /// Arbitrary additional information about the curves.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
 
+/// The surfaces of a [`Brep`](./struct.Brep.html) that its faces lie on.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_lights"]
-pub struct LibraryLights;
+#[name = "surfaces"]
+pub struct Surfaces {
+    /// The surfaces in the list.
+    ///
+    /// There will always be at least one surface.
+    #[child]
+    #[required]
+    pub surfaces: Vec<Surface>,
 
+    /// Arbitrary additional information about the surfaces.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// A surface made by sweeping a curve, either along a direction (an extrusion) or around an axis
+/// (a revolution).
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_materials"]
-pub struct LibraryMaterials;
+#[name = "swept_surface"]
+pub struct SweptSurface {
+    /// The curve being swept.
+    #[child]
+    pub curve: Curve,
+
+    /// The direction that the curve is extruded along.
+    ///
+    /// Will be `None` if the surface is a revolution.
+    #[child]
+    pub direction: Option<Direction>,
+
+    /// A point on the axis that the curve is revolved around.
+    ///
+    /// Will be `None` if the surface is an extrusion.
+    #[child]
+    pub origin: Option<Origin>,
+
+    /// The axis that the curve is revolved around.
+    ///
+    /// Will be `None` if the surface is an extrusion.
+    #[child]
+    pub axis: Option<Axis>,
+
+    /// Arbitrary additional information about the surface.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Selects the technique and pass of an effect used by a
+/// [`RenderInstanceMaterial`](./struct.RenderInstanceMaterial.html).
+#[derive(Debug, Clone, PartialEq, Eq, ColladaElement)]
+#[name = "technique_override"]
+pub struct TechniqueOverride {
+    /// The sid of the technique to use.
+    #[attribute]
+    #[name = "ref"]
+    pub reference: String,
 
+    /// The sid of the pass to use. If there's no pass, all of the technique's passes are used.
+    #[attribute]
+    pub pass: Option<String>,
+}
+
+/// An array of arbitrary tokens, e.g. enumerated values.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_nodes"]
-pub struct LibraryNodes;
+#[name = "token_array"]
+pub struct TokenArray {
+    #[attribute]
+    pub count: usize,
+
+    #[attribute]
+    pub id: Option<String>,
+
+    #[attribute]
+    pub name: Option<String>,
 
+    /// The tokens in the array.
+    #[text(count = "count", split_with = "::utils::split_xml_list")]
+    pub data: Vec<String>,
+}
+
+/// An iterator over the entries of a B-rep topology element, e.g. each face of a
+/// [`Faces`](./struct.Faces.html).
+///
+/// Each item holds the indices of one entry.
+#[derive(Debug, Clone)]
+pub struct TopologyIter<'a> {
+    counts: ::std::slice::Iter<'a, usize>,
+    indices: &'a [usize],
+    stride: usize,
+}
+
+impl<'a> Iterator for TopologyIter<'a> {
+    type Item = &'a [usize];
+
+    fn next(&mut self) -> Option<&'a [usize]> {
+        let count = *self.counts.next()?;
+        let len = ::std::cmp::min(count * self.stride, self.indices.len());
+        let (entry, rest) = self.indices.split_at(len);
+        self.indices = rest;
+        Some(entry)
+    }
+}
+
+/// A torus around the origin, lying in the local xy plane.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_physics_materials"]
-pub struct LibraryPhysicsMaterials;
+#[name = "torus"]
+pub struct Torus {
+    /// The major and minor radii of the torus.
+    #[child]
+    pub radius: Radii,
+
+    /// Arbitrary additional information about the torus.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
 
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_physics_models"]
-pub struct LibraryPhysicsModels;
+#[name = "unnormalized"]
+pub struct Unnormalized;
 
+/// Declares the input semantic of a data source and connects a consumer of that source.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_physics_scenes"]
-pub struct LibraryPhysicsScenes;
+#[name = "input"]
+pub struct UnsharedInput {
+    /// The meaning of the input connnection, e.g. `Semantic::Position`.
+    #[attribute]
+    pub semantic: Semantic,
 
+    /// The location of the data source.
+    #[attribute]
+    pub source: UriFragment,
+}
+
+/// The number of index tuples in each entry of a topology element, i.e. a `<vcount>` element.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_visual_scenes"]
-pub struct LibraryVisualScenes;
+#[name = "vcount"]
+pub struct VCount {
+    #[text]
+    data: Vec<usize>,
+}
+
+impl ::std::ops::Deref for VCount {
+    type Target = [usize];
+
+    fn deref(&self) -> &[usize] { &*self.data }
+}
 
+/// Declares the attributes and identity of the vertices of a geometry.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "scene"]
-pub struct Scene;
+#[name = "vertices"]
+pub struct Vertices {
+    /// A unique identifier of the vertices instance.
+    ///
+    /// This value is unique within the document.
+    #[attribute]
+    pub id: String,
+
+    /// The name of the vertices instance.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The input data for the vertices.
+    ///
+    /// There will be at least one element in `inputs`, and one input will specify the
+    /// `"POSITION"` semantic.
+    #[child]
+    #[required]
+    pub inputs: Vec<UnsharedInput>,
+
+    /// Arbitrary additional data about the vertices.
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+brep_topology! {
+    /// The wires of a [`Brep`](./struct.Brep.html), each of which is a chain of edges.
+    ///
+    /// The inputs are usually `"EDGE"` and `"ORIENTATION"`.
+    pub struct Wires, "wires", "wires"
+}
 
 /// Specifies the altitude of a [`GeographicLocation`][GeographicLocation].
 ///
@@ -459,17 +3203,42 @@ pub enum Altitude {
     RelativeToGround(f64),
 }
 
+impl Altitude {
+    /// Returns the altitude in meters, regardless of what it's relative to.
+    pub fn value(&self) -> f64 {
+        match *self {
+            Altitude::Absolute(value) | Altitude::RelativeToGround(value) => value,
+        }
+    }
+
+    /// Returns `true` if the altitude is relative to ground level rather than sea level.
+    pub fn is_relative_to_ground(&self) -> bool {
+        match *self {
+            Altitude::Absolute(_) => false,
+            Altitude::RelativeToGround(_) => true,
+        }
+    }
+
+    /// Returns the string used to represent the altitude mode in a COLLADA document.
+    pub fn mode(&self) -> &'static str {
+        match *self {
+            Altitude::Absolute(_) => "absolute",
+            Altitude::RelativeToGround(_) => "relativeToGround",
+        }
+    }
+}
+
 impl ColladaElement for Altitude {
     fn name_test(name: &str) -> bool {
         name == "altitude"
     }
 
     fn parse_element<R>(
-        reader: &mut EventReader<R>,
+        reader: &mut DocumentReader<R>,
         element_start: ElementStart,
     ) -> Result<Self>
     where
-        R: Read,
+        R: XmlPull,
     {
         let mut mode = None;
         for attribute in element_start.attributes {
@@ -479,14 +3248,16 @@ impl ColladaElement for Altitude {
                 }
 
                 attrib_name @ _ => {
-                    return Err(Error {
+                    let error = Error {
                         position: reader.position(),
                         kind: ErrorKind::UnexpectedAttribute {
                             element: "altitude",
                             attribute: attrib_name.into(),
                             expected: vec!["mode"],
                         },
-                    });
+                    };
+                    reader.tolerate(error, Strictness::Lenient)?;
+                    reader.preserve_attribute(attribute);
                 }
             }
         }
@@ -530,4 +3301,16 @@ impl ColladaElement for Altitude {
     fn add_names(names: &mut Vec<&'static str>) {
         names.push("altitude");
     }
+
+    fn write_element<W: Write>(&self, writer: &mut XmlWriter<W>) -> writer::Result<()> {
+        writer.start_element("altitude")?;
+        writer.attribute("mode", self.mode())?;
+        writer.text(&self.value())?;
+        writer.end_element("altitude")
+    }
+}
+
+/// Returns the number of indices in each index tuple of an element with the given inputs.
+fn input_stride(inputs: &[SharedInput]) -> usize {
+    inputs.iter().map(|input| input.offset + 1).max().unwrap_or(0)
 }
@@ -0,0 +1,1017 @@
+//! Writing documents back out as COLLADA XML.
+//!
+//! Any parsed document can be written with [`v1_4::Collada::write`], [`v1_5::Collada::write`], or
+//! [`VersionedDocument::write`]. Together with parsing, this allows documents to be loaded,
+//! modified, and saved again.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::fs::File;
+//! use collaborate::v1_4::Collada;
+//! use collaborate::writer::WriteOptions;
+//!
+//! let file = File::open("resources/blender_cube.dae").unwrap();
+//! let document = Collada::read(file).unwrap();
+//!
+//! let mut output = Vec::new();
+//! document.write(&mut output, WriteOptions::default()).unwrap();
+//!
+//! // The written document parses back to the same document.
+//! assert_eq!(document, Collada::from_str(&String::from_utf8(output).unwrap()).unwrap());
+//! ```
+//!
+//! A document can also be written as a different version of the specification than the one it
+//! was parsed with, see [`WriteOptions::version`].
+//!
+//! Elements that are only stubbed out by this library (see the
+//! [`coverage`](../coverage/index.html) module) are written as empty elements, since their
+//! contents aren't kept when the document is parsed, unless the content was preserved (see the
+//! [`preserve`](../preserve/index.html) module).
+//!
+//! [`v1_4::Collada::write`]: ../v1_4/struct.Collada.html#method.write
+//! [`v1_5::Collada::write`]: ../v1_5/struct.Collada.html#method.write
+//! [`VersionedDocument::write`]: ../enum.VersionedDocument.html#method.write
+//! [`WriteOptions::version`]: struct.WriteOptions.html#structfield.version
+
+use common::{AnyUri, ArrayValue, DateTime, HexBinary, MaybeEmpty, RawArray, Semantic, UpAxis, UriFragment};
+use preserve::{ElementPath, PreservedContent};
+use raw::RawElement;
+use std::fmt::{self, Display, Formatter, Write as FmtWrite};
+use std::io::{self, Write};
+use Version;
+use utils::ColladaElement;
+use xml::name::OwnedName;
+use xml::namespace::{NamespaceStack, NS_NO_PREFIX};
+use xml::reader::XmlEvent;
+
+/// The result of writing a document.
+pub type Result<T> = ::std::result::Result<T, WriteError>;
+
+/// Options for writing a document.
+///
+/// New options may be added in the future, so construct `WriteOptions` using
+/// `..WriteOptions::default()` to fill in any options you don't set explicitly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// Whether to begin the document with an XML declaration
+    /// (`<?xml version="1.0" encoding="utf-8"?>`).
+    ///
+    /// Defaults to `true`.
+    pub xml_declaration: bool,
+
+    /// How nested elements are indented.
+    ///
+    /// Defaults to [`Indent::Spaces(2)`](enum.Indent.html).
+    pub indent: Indent,
+
+    /// The line ending written between elements.
+    ///
+    /// Defaults to [`Newline::Lf`](enum.Newline.html).
+    pub newline: Newline,
+
+    /// The order in which the attributes of an element are written.
+    ///
+    /// Defaults to [`AttributeOrder::Declared`](enum.AttributeOrder.html).
+    pub attribute_order: AttributeOrder,
+
+    /// How floating point values are formatted.
+    ///
+    /// Defaults to [`FloatFormat::Shortest`](enum.FloatFormat.html).
+    pub float_format: FloatFormat,
+
+    /// The version of the specification to write the document as, or `None` to use the version
+    /// the document was parsed with.
+    ///
+    /// Documents can be written as either 1.4 or 1.5 regardless of which version they were parsed
+    /// with. Constructs that are spelled differently in the two versions are translated, e.g.
+    /// the `<init_from>` of an `<image>` is written as `<init_from>uri</init_from>` in 1.4 and as
+    /// `<init_from><ref>uri</ref></init_from>` in 1.5. Content that can't be represented in the
+    /// requested version, e.g. a `<coverage>` in the asset of a 1.5 document written as 1.4,
+    /// causes a [`WriteError::VersionMismatch`] error.
+    ///
+    /// Defaults to `None`.
+    ///
+    /// [`WriteError::VersionMismatch`]: enum.WriteError.html#variant.VersionMismatch
+    pub version: Option<Version>,
+
+    /// Content to put back into the document that isn't part of the document model, as kept by
+    /// [`ParseOptions::preserve_unknown`] when the document was parsed.
+    ///
+    /// See the [`preserve`](../preserve/index.html) module for where the content is written.
+    /// Preserved content is written as-is, even if it belongs to a different version of the
+    /// specification than the one being written.
+    ///
+    /// Defaults to `None`.
+    ///
+    /// [`ParseOptions::preserve_unknown`]: ../options/struct.ParseOptions.html#structfield.preserve_unknown
+    pub preserved: Option<PreservedContent>,
+}
+
+impl Default for WriteOptions {
+    fn default() -> WriteOptions {
+        WriteOptions {
+            xml_declaration: true,
+            indent: Indent::Spaces(2),
+            newline: Newline::Lf,
+            attribute_order: AttributeOrder::Declared,
+            float_format: FloatFormat::Shortest,
+            version: None,
+            preserved: None,
+        }
+    }
+}
+
+/// How nested elements are indented, see [`WriteOptions::indent`].
+///
+/// [`WriteOptions::indent`]: struct.WriteOptions.html#structfield.indent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    /// Each level of nesting is indented by the given number of spaces.
+    Spaces(usize),
+
+    /// Each level of nesting is indented by a single tab.
+    Tabs,
+
+    /// Elements are written at the start of each line without any indentation.
+    None,
+}
+
+/// The line ending written between elements, see [`WriteOptions::newline`].
+///
+/// [`WriteOptions::newline`]: struct.WriteOptions.html#structfield.newline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Newline {
+    /// Lines end with `\n`.
+    Lf,
+
+    /// Lines end with `\r\n`.
+    CrLf,
+
+    /// The whole document is written on a single line, with no whitespace between elements.
+    ///
+    /// [`WriteOptions::indent`] is ignored in this case.
+    ///
+    /// [`WriteOptions::indent`]: struct.WriteOptions.html#structfield.indent
+    None,
+}
+
+impl Newline {
+    fn as_str(&self) -> &'static str {
+        match *self {
+            Newline::Lf => "\n",
+            Newline::CrLf => "\r\n",
+            Newline::None => "",
+        }
+    }
+}
+
+/// The order in which attributes are written, see [`WriteOptions::attribute_order`].
+///
+/// [`WriteOptions::attribute_order`]: struct.WriteOptions.html#structfield.attribute_order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeOrder {
+    /// Attributes are written in the order they're declared in, i.e. the order of the fields of
+    /// the element's struct, or the original order for elements that are kept as raw XML.
+    Declared,
+
+    /// Attributes are sorted by name.
+    ///
+    /// Namespace declarations (`xmlns` and `xmlns:*`) are always written before any other
+    /// attributes.
+    Alphabetical,
+}
+
+/// How floating point values are formatted, see [`WriteOptions::float_format`].
+///
+/// [`WriteOptions::float_format`]: struct.WriteOptions.html#structfield.float_format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatFormat {
+    /// The shortest representation that parses back to the same value, e.g. `0.5` or `1`.
+    ///
+    /// Arrays of values (e.g. `<float_array>`) are written using their original text.
+    Shortest,
+
+    /// A fixed number of digits after the decimal point, like C's `%.6f` for `Fixed(6)`.
+    ///
+    /// Arrays of values are decoded and reformatted, which is slower than writing them as-is. If
+    /// an array can't be decoded, its original text is written instead.
+    Fixed(usize),
+}
+
+/// An error when writing a document.
+#[derive(Debug)]
+pub enum WriteError {
+    /// Writing to the output failed.
+    Io(io::Error),
+
+    /// The document contains an element or attribute that doesn't exist in the version of the
+    /// specification it's being written as.
+    ///
+    /// See [`WriteOptions::version`] for which constructs are translated between versions.
+    ///
+    /// [`WriteOptions::version`]: struct.WriteOptions.html#structfield.version
+    VersionMismatch {
+        /// The path to the element containing the version-specific member, e.g.
+        /// `"COLLADA/asset"`.
+        element: String,
+
+        /// The name of the child element or attribute.
+        member: String,
+
+        /// The version(s) of the specification that allow the member.
+        valid_in: &'static str,
+
+        /// The version the document is being written as.
+        version: Version,
+    },
+}
+
+impl From<io::Error> for WriteError {
+    fn from(from: io::Error) -> WriteError {
+        WriteError::Io(from)
+    }
+}
+
+impl Display for WriteError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match *self {
+            WriteError::Io(ref error) => write!(formatter, "Failed to write document: {}", error),
+
+            WriteError::VersionMismatch { ref element, ref member, ref valid_in, ref version } => {
+                write!(
+                    formatter,
+                    "<{}> has \"{}\" which is only allowed in COLLADA {}, but the document is being written as version {}",
+                    element,
+                    member,
+                    valid_in,
+                    version,
+                )
+            }
+        }
+    }
+}
+
+impl ::std::error::Error for WriteError {
+    fn source(&self) -> Option<&(::std::error::Error + 'static)> {
+        match *self {
+            WriteError::Io(ref error) => Some(error),
+            WriteError::VersionMismatch { .. } => None,
+        }
+    }
+}
+
+/// Writes `document` to `output` as a complete XML document.
+///
+/// `version` is the version the document was parsed with, which is used unless the options
+/// request a different one.
+pub(crate) fn write_document<T, W>(document: &T, output: W, options: WriteOptions, version: Version) -> Result<()>
+where
+    T: ColladaElement,
+    W: Write,
+{
+    let version = options.version.unwrap_or(version);
+    let mut writer = XmlWriter::new(output, options);
+    writer.version = Some(version);
+    writer.declaration()?;
+    document.write_element(&mut writer)?;
+    writer.finish()
+}
+
+/// The size at which [`XmlWriter::text_list`] writes its buffer to the output.
+///
+/// [`XmlWriter::text_list`]: struct.XmlWriter.html#method.text_list
+const TEXT_CHUNK_SIZE: usize = 8 * 1024;
+
+/// What has been written inside of the innermost open element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Content {
+    /// The start tag is still open, so attributes may be added.
+    StartTag,
+
+    /// The element contains text.
+    Text,
+
+    /// The element contains other elements.
+    Children,
+}
+
+/// Writes XML to an output, keeping track of indentation and escaping.
+///
+/// Elements are written with [`ColladaElement::write_element`], which calls the methods here to
+/// write their tags, attributes, and contents.
+pub struct XmlWriter<W: Write> {
+    output: W,
+    options: WriteOptions,
+    depth: usize,
+    content: Option<Content>,
+
+    /// The version being written, if a whole document is being written.
+    ///
+    /// Namespace declarations aren't kept when a document is parsed, so the version and namespace
+    /// of the document are declared on the root element by the writer.
+    version: Option<Version>,
+
+    /// The names of the open elements, separated by `/`.
+    path: String,
+
+    /// The attributes of the element whose start tag is open, which are written once the start
+    /// tag is closed so that they can be reordered.
+    attributes: Vec<(String, String)>,
+
+    /// Reused for formatting values, so that each value doesn't need its own allocation.
+    buffer: String,
+
+    /// The namespace prefixes declared by the open elements, so that elements written with their
+    /// full names only declare the prefixes that aren't already in scope.
+    namespaces: NamespaceStack,
+
+    /// The content to put back into the document, taken from the options.
+    preserved: Option<PreservedContent>,
+
+    /// The path to the current element, used to look up preserved content.
+    preserve_path: ElementPath,
+
+    /// Which of the preserved children of each open element have been written.
+    preserve_written: Vec<Vec<bool>>,
+}
+
+impl<W: Write> XmlWriter<W> {
+    /// Creates a writer that writes to `output`.
+    pub fn new(output: W, mut options: WriteOptions) -> XmlWriter<W> {
+        let preserved = options.preserved.take();
+        XmlWriter {
+            output,
+            options,
+            depth: 0,
+            content: None,
+            version: None,
+            path: String::new(),
+            attributes: Vec::new(),
+            buffer: String::new(),
+            namespaces: NamespaceStack::default(),
+            preserved,
+            preserve_path: ElementPath::new(),
+            preserve_written: Vec::new(),
+        }
+    }
+
+    /// Returns the options used by the writer.
+    pub fn options(&self) -> &WriteOptions {
+        &self.options
+    }
+
+    /// Returns the version of the specification being written, if a whole document is being
+    /// written.
+    ///
+    /// Elements that are spelled differently between versions use this to decide how to write
+    /// themselves.
+    pub fn version(&self) -> Option<Version> {
+        self.version
+    }
+
+    /// Checks that `member` (a child or attribute of the current element) is allowed in the
+    /// version being written.
+    ///
+    /// `valid_in` may be a full version (e.g. `"1.4.1"`) or a prefix of one (e.g. `"1.5"`).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`WriteError::VersionMismatch`] error if the version being written isn't
+    /// compatible with `valid_in`.
+    ///
+    /// [`WriteError::VersionMismatch`]: enum.WriteError.html#variant.VersionMismatch
+    pub fn check_version(&self, member: &str, valid_in: &'static str) -> Result<()> {
+        match self.version {
+            Some(version) if !version.is_compatible(valid_in) => Err(WriteError::VersionMismatch {
+                element: self.path.clone(),
+                member: member.into(),
+                valid_in,
+                version,
+            }),
+
+            _ => Ok(()),
+        }
+    }
+
+    /// Writes the XML declaration, if the options ask for one.
+    pub fn declaration(&mut self) -> Result<()> {
+        if self.options.xml_declaration {
+            self.output.write_all(b"<?xml version=\"1.0\" encoding=\"utf-8\"?>")?;
+            self.content = Some(Content::Children);
+        }
+
+        Ok(())
+    }
+
+    /// Begins a new element inside of the current one.
+    pub fn start_element(&mut self, name: &str) -> Result<()> {
+        // Preserved elements that came before any of the parent's other children go first.
+        if self.preserved.is_some() {
+            self.preserved_children(None, false)?;
+        }
+
+        self.begin_child()?;
+        if self.preserved.is_some() {
+            // The path is made of local names, like the one used when reading.
+            self.preserve_path.start(name.rsplit(':').next().unwrap_or(name));
+            self.preserve_written.push(Vec::new());
+        }
+
+        self.output.write_all(b"<")?;
+        self.output.write_all(name.as_bytes())?;
+        self.namespaces.push_empty();
+        self.depth += 1;
+        self.content = Some(Content::StartTag);
+        if !self.path.is_empty() {
+            self.path.push('/');
+        }
+        self.path.push_str(name);
+
+        if self.depth == 1 {
+            if let Some(version) = self.version {
+                self.namespaces.put(NS_NO_PREFIX, version.namespace());
+                self.attributes.push(("xmlns".into(), version.namespace().into()));
+                self.attributes.push(("version".into(), version.as_str().into()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds an attribute to the element that was just started.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element already has contents.
+    pub fn attribute<T: WriteValue + ?Sized>(&mut self, name: &str, value: &T) -> Result<()> {
+        assert_eq!(Some(Content::StartTag), self.content, "Attribute written after element contents");
+
+        // The root element's namespace and version have already been declared.
+        if self.depth == 1 && (name == "xmlns" || name == "version") && self.version.is_some() {
+            return Ok(());
+        }
+
+        let mut text = String::new();
+        value.write_value(&mut text, &self.options);
+
+        // Declarations are tracked, so that elements inside of this one don't repeat them.
+        if name == "xmlns" {
+            self.namespaces.peek_mut().force_put(NS_NO_PREFIX, &*text);
+        } else if let Some(prefix) = name.strip_prefix("xmlns:") {
+            self.namespaces.peek_mut().force_put(prefix, &*text);
+        }
+
+        self.attributes.push((name.into(), text));
+        Ok(())
+    }
+
+    /// Adds an attribute with a full name to the element that was just started, declaring the
+    /// namespace of its prefix if it isn't already in scope.
+    pub fn qualified_attribute(&mut self, name: &OwnedName, value: &str) -> Result<()> {
+        self.namespace_declaration(name)?;
+        self.attribute(&name.borrow().to_repr(), value)
+    }
+
+    /// Writes a value as the text contents of the current element.
+    ///
+    /// Lists of values are streamed to the output in chunks, see
+    /// [`text_list`](#method.text_list).
+    pub fn text<T: WriteValue + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.write_text(self)
+    }
+
+    /// Writes a list of values separated by spaces as the text contents of the current element.
+    ///
+    /// The values are formatted into a reused buffer that's written to the output whenever it
+    /// fills up, so that huge arrays (e.g. the positions of a mesh with millions of vertices) can
+    /// be written without formatting their whole text in memory first.
+    pub fn text_list<I>(&mut self, values: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: WriteValue,
+    {
+        self.buffer.clear();
+        let mut first = true;
+        for value in values {
+            if !first {
+                self.buffer.push(' ');
+            }
+            first = false;
+
+            value.write_value(&mut self.buffer, &self.options);
+            if self.buffer.len() >= TEXT_CHUNK_SIZE {
+                self.flush_text()?;
+            }
+        }
+
+        self.flush_text()
+    }
+
+    /// Writes text that has already been formatted as the text contents of the current element,
+    /// escaping it as needed.
+    pub fn raw_text(&mut self, text: &str) -> Result<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        self.close_start_tag()?;
+        write_escaped(&mut self.output, text, false)?;
+        self.content = Some(Content::Text);
+        Ok(())
+    }
+
+    /// Formats `value` into the buffer and writes it as text all at once.
+    fn formatted_text<T: WriteValue + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.buffer.clear();
+        value.write_value(&mut self.buffer, &self.options);
+        self.flush_text()
+    }
+
+    /// Writes the contents of the buffer as text and clears it.
+    fn flush_text(&mut self) -> Result<()> {
+        let buffer = ::std::mem::take(&mut self.buffer);
+        let result = self.raw_text(&buffer);
+        self.buffer = buffer;
+        self.buffer.clear();
+        result
+    }
+
+    /// Finishes the current element.
+    pub fn end_element(&mut self, name: &str) -> Result<()> {
+        if self.preserved.is_some() {
+            self.preserved_children(None, true)?;
+        }
+
+        self.namespaces.pop();
+        self.depth -= 1;
+        let parent_len = self.path.rfind('/').unwrap_or(0);
+        self.path.truncate(parent_len);
+        match self.content {
+            Some(Content::StartTag) => {
+                self.write_attributes()?;
+                self.output.write_all(b"/>")?;
+            }
+
+            Some(Content::Children) => {
+                self.newline()?;
+                self.write_end_tag(name)?;
+            }
+
+            Some(Content::Text) | None => {
+                self.write_end_tag(name)?;
+            }
+        }
+
+        self.content = Some(Content::Children);
+
+        // Preserved elements that followed this one in the original document go right after it.
+        if self.preserved.is_some() {
+            self.preserve_written.pop();
+            if let Some(segment) = self.preserve_path.end() {
+                self.preserved_children(Some(&segment), false)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes an element containing only `value` as its text, e.g. `<created>...</created>`.
+    pub fn text_element<T: WriteValue + ?Sized>(&mut self, name: &str, value: &T) -> Result<()> {
+        self.start_element(name)?;
+        self.text(value)?;
+        self.end_element(name)
+    }
+
+    /// Writes an element with no attributes or contents, e.g. `<title/>`.
+    pub fn empty_element(&mut self, name: &str) -> Result<()> {
+        self.start_element(name)?;
+        self.end_element(name)
+    }
+
+    /// Writes a [`RawElement`](../raw/struct.RawElement.html) and everything inside of it.
+    ///
+    /// The element's text is written before its children.
+    pub fn raw_element(&mut self, element: &RawElement) -> Result<()> {
+        let attributes = element.attributes.iter()
+            .map(|(name, value)| (name, &**value))
+            .collect::<Vec<_>>();
+        self.start_qualified_element(&element.name, &attributes)?;
+        self.raw_text(&element.text)?;
+        for child in &element.children {
+            self.raw_element(child)?;
+        }
+        self.end_element(&element.name.borrow().to_repr())
+    }
+
+    /// Writes a single XML event, as kept by a [`Technique`](../common/struct.Technique.html).
+    ///
+    /// Start and end tags must be balanced. Namespace declarations are written for any prefixes
+    /// used by an element or its attributes.
+    pub fn event(&mut self, event: &XmlEvent) -> Result<()> {
+        match *event {
+            XmlEvent::StartElement { ref name, ref attributes, .. } => {
+                let attributes = attributes.iter()
+                    .map(|attribute| (&attribute.name, &*attribute.value))
+                    .collect::<Vec<_>>();
+                self.start_qualified_element(name, &attributes)?;
+            }
+
+            XmlEvent::EndElement { ref name } => {
+                self.end_element(&name.borrow().to_repr())?;
+            }
+
+            XmlEvent::Characters(ref text) | XmlEvent::CData(ref text) => {
+                self.raw_text(text)?;
+            }
+
+            XmlEvent::Comment(ref comment) => {
+                self.begin_child()?;
+                write!(self.output, "<!--{}-->", comment)?;
+                self.content = Some(Content::Children);
+            }
+
+            XmlEvent::ProcessingInstruction { ref name, ref data } => {
+                self.begin_child()?;
+                match *data {
+                    Some(ref data) => write!(self.output, "<?{} {}?>", name, data)?,
+                    None => write!(self.output, "<?{}?>", name)?,
+                }
+                self.content = Some(Content::Children);
+            }
+
+            XmlEvent::StartDocument { .. } | XmlEvent::EndDocument | XmlEvent::Whitespace(_) => {}
+        }
+
+        Ok(())
+    }
+
+    /// Finishes the document and flushes the output.
+    pub fn finish(mut self) -> Result<()> {
+        self.output.write_all(self.options.newline.as_str().as_bytes())?;
+        self.output.flush()?;
+        Ok(())
+    }
+
+    /// Writes the preserved children of the current element that haven't been written yet and
+    /// follow the sibling `after`, or all of them if `all` is set.
+    fn preserved_children(&mut self, after: Option<&str>, all: bool) -> Result<()> {
+        let pending: Vec<RawElement> = match (self.preserved.as_ref(), self.preserve_written.last_mut()) {
+            (Some(preserved), Some(written)) => {
+                let children = preserved.children(self.preserve_path.path());
+                written.resize(children.len(), false);
+                children.iter()
+                    .zip(written.iter_mut())
+                    .filter(|&(child, ref written)| !**written && (all || child.after.as_deref() == after))
+                    .map(|(child, written)| {
+                        *written = true;
+                        child.element.clone()
+                    })
+                    .collect()
+            }
+
+            _ => return Ok(()),
+        };
+
+        for element in &pending {
+            self.raw_element(element)?;
+        }
+
+        Ok(())
+    }
+
+    /// Begins an element with a full name and adds its attributes, declaring the namespaces of
+    /// any prefixes they use.
+    fn start_qualified_element(
+        &mut self,
+        name: &OwnedName,
+        attributes: &[(&OwnedName, &str)],
+    ) -> Result<()> {
+        self.start_element(&name.borrow().to_repr())?;
+
+        self.default_namespace_declaration(name)?;
+        self.namespace_declaration(name)?;
+        for &(name, _) in attributes {
+            self.namespace_declaration(name)?;
+        }
+        for &(name, value) in attributes {
+            self.attribute(&name.borrow().to_repr(), value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the attributes of the open start tag.
+    fn write_attributes(&mut self) -> Result<()> {
+        // Preserved attributes go after the element's own, unless the element already has them.
+        if let Some(ref preserved) = self.preserved {
+            for (name, value) in preserved.attributes(self.preserve_path.path()) {
+                let mut declarations = Vec::new();
+                if let (Some(prefix), Some(namespace)) = (name.prefix.as_ref(), name.namespace.as_ref()) {
+                    if prefix != "xml" && prefix != "xmlns" {
+                        declarations.push((format!("xmlns:{}", prefix), namespace.clone()));
+                    }
+                }
+                declarations.push((name.borrow().to_repr(), value.clone()));
+
+                for attribute in declarations {
+                    if !self.attributes.iter().any(|existing| existing.0 == attribute.0) {
+                        self.attributes.push(attribute);
+                    }
+                }
+            }
+        }
+
+        if self.options.attribute_order == AttributeOrder::Alphabetical {
+            self.attributes.sort_by(|left, right| {
+                (!is_namespace_declaration(&left.0), &left.0).cmp(&(!is_namespace_declaration(&right.0), &right.0))
+            });
+        }
+
+        for (name, value) in self.attributes.drain(..) {
+            self.output.write_all(b" ")?;
+            self.output.write_all(name.as_bytes())?;
+            self.output.write_all(b"=\"")?;
+            write_escaped(&mut self.output, &value, true)?;
+            self.output.write_all(b"\"")?;
+        }
+
+        Ok(())
+    }
+
+    /// Declares the namespace of a prefixed name on the element that was just started, since the
+    /// declarations themselves aren't kept. Nothing is written if the prefix is already bound to
+    /// the same namespace, e.g. by a parent element.
+    fn namespace_declaration(&mut self, name: &OwnedName) -> Result<()> {
+        if let (Some(prefix), Some(namespace)) = (name.prefix.as_ref(), name.namespace.as_ref()) {
+            if self.namespaces.get(prefix) != Some(&**namespace) {
+                self.attribute(&format!("xmlns:{}", prefix), &**namespace)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Declares the namespace of an unprefixed element name on the element that was just started,
+    /// if it differs from the default namespace in scope, e.g. for the `<math>` inside of a
+    /// technique.
+    ///
+    /// Names without a namespace are taken to be in the default namespace. Names in the namespace
+    /// of any version of the specification are written in the namespace of the version being
+    /// written, so that converting a document also converts the contents of its techniques.
+    fn default_namespace_declaration(&mut self, name: &OwnedName) -> Result<()> {
+        let namespace = match (name.prefix.as_ref(), name.namespace.as_ref()) {
+            (None, Some(namespace)) => &**namespace,
+            _ => return Ok(()),
+        };
+
+        let schema_namespaces = [Version::V1_4_1.namespace(), Version::V1_5_0.namespace()];
+        let namespace = match self.version {
+            Some(version) if schema_namespaces.contains(&namespace) => version.namespace(),
+            _ => namespace,
+        };
+
+        if self.namespaces.get(NS_NO_PREFIX) != Some(namespace) {
+            self.attribute("xmlns", namespace)?;
+        }
+
+        Ok(())
+    }
+
+    /// Prepares to write a child of the current element, closing its start tag and moving to a
+    /// new line.
+    fn begin_child(&mut self) -> Result<()> {
+        if self.content.is_some() {
+            self.close_start_tag()?;
+            self.newline()?;
+        }
+        Ok(())
+    }
+
+    fn close_start_tag(&mut self) -> Result<()> {
+        if self.content == Some(Content::StartTag) {
+            self.write_attributes()?;
+            self.output.write_all(b">")?;
+        }
+        self.content = Some(Content::Children);
+        Ok(())
+    }
+
+    fn newline(&mut self) -> Result<()> {
+        if self.options.newline == Newline::None {
+            return Ok(());
+        }
+
+        self.output.write_all(self.options.newline.as_str().as_bytes())?;
+        for _ in 0..self.depth {
+            match self.options.indent {
+                Indent::Spaces(count) => {
+                    for _ in 0..count {
+                        self.output.write_all(b" ")?;
+                    }
+                }
+                Indent::Tabs => self.output.write_all(b"\t")?,
+                Indent::None => break,
+            }
+        }
+        Ok(())
+    }
+
+    fn write_end_tag(&mut self, name: &str) -> Result<()> {
+        self.output.write_all(b"</")?;
+        self.output.write_all(name.as_bytes())?;
+        self.output.write_all(b">")?;
+        Ok(())
+    }
+}
+
+fn is_namespace_declaration(name: &str) -> bool {
+    name == "xmlns" || name.starts_with("xmlns:")
+}
+
+/// Writes `text` with the characters that are special in XML escaped.
+fn write_escaped<W: Write>(output: &mut W, text: &str, attribute: bool) -> io::Result<()> {
+    let mut start = 0;
+    for (index, character) in text.char_indices() {
+        let escaped = match character {
+            '&' => "&amp;",
+            '<' => "&lt;",
+            '>' => "&gt;",
+            '"' if attribute => "&quot;",
+            '\n' if attribute => "&#10;",
+            '\r' => "&#13;",
+            '\t' if attribute => "&#9;",
+            _ => continue,
+        };
+
+        output.write_all(&text.as_bytes()[start..index])?;
+        output.write_all(escaped.as_bytes())?;
+        start = index + character.len_utf8();
+    }
+
+    output.write_all(&text.as_bytes()[start..])
+}
+
+/// A value that can be written as the text of an attribute or element.
+///
+/// This is the counterpart of parsing a value from text, and is implemented for every type used
+/// as an attribute or as text data in the document model.
+pub trait WriteValue {
+    /// Appends the value as it should appear in the document to `output`.
+    fn write_value(&self, output: &mut String, options: &WriteOptions);
+
+    /// Writes the value as the text contents of the current element of `writer`.
+    ///
+    /// By default the value is formatted with [`write_value`](#tymethod.write_value) and written
+    /// all at once. Lists of values instead stream their values to the output a chunk at a time.
+    fn write_text<W: Write>(&self, writer: &mut XmlWriter<W>) -> Result<()> {
+        writer.formatted_text(self)
+    }
+}
+
+macro_rules! display_write_value {
+    ($($ty:ty),*) => {
+        $(
+            impl WriteValue for $ty {
+                fn write_value(&self, output: &mut String, _: &WriteOptions) {
+                    write!(output, "{}", self).expect("Formatting a value failed");
+                }
+            }
+        )*
+    }
+}
+
+display_write_value!(str, String, bool, i32, i64, u32, u64, usize, Semantic, UpAxis);
+
+macro_rules! float_write_value {
+    ($($ty:ty),*) => {
+        $(
+            impl WriteValue for $ty {
+                fn write_value(&self, output: &mut String, options: &WriteOptions) {
+                    // `xs:float` and `xs:double` spell infinity differently than Rust does.
+                    if self.is_infinite() {
+                        output.push_str(if *self > 0.0 { "INF" } else { "-INF" });
+                        return;
+                    }
+
+                    match options.float_format {
+                        FloatFormat::Shortest => write!(output, "{}", self),
+                        FloatFormat::Fixed(precision) => write!(output, "{:.*}", precision, self),
+                    }.expect("Formatting a value failed");
+                }
+            }
+        )*
+    }
+}
+
+float_write_value!(f32, f64);
+
+impl<T: WriteValue + ?Sized> WriteValue for &T {
+    fn write_value(&self, output: &mut String, options: &WriteOptions) {
+        (**self).write_value(output, options);
+    }
+
+    fn write_text<W: Write>(&self, writer: &mut XmlWriter<W>) -> Result<()> {
+        (**self).write_text(writer)
+    }
+}
+
+/// Lists are written with their values separated by spaces.
+impl<T: WriteValue> WriteValue for Vec<T> {
+    fn write_value(&self, output: &mut String, options: &WriteOptions) {
+        for (index, value) in self.iter().enumerate() {
+            if index != 0 {
+                output.push(' ');
+            }
+            value.write_value(output, options);
+        }
+    }
+
+    fn write_text<W: Write>(&self, writer: &mut XmlWriter<W>) -> Result<()> {
+        writer.text_list(self)
+    }
+}
+
+/// Deferred arrays are written using their original text, so they don't need to be decoded,
+/// unless the values need to be reformatted for [`FloatFormat::Fixed`](enum.FloatFormat.html).
+impl<T: ArrayValue + WriteValue> WriteValue for RawArray<T> {
+    fn write_value(&self, output: &mut String, options: &WriteOptions) {
+        match original_text(self, options) {
+            Some(text) => output.push_str(text),
+            None => {
+                for (index, value) in self.iter().enumerate() {
+                    if index != 0 {
+                        output.push(' ');
+                    }
+                    value.write_value(output, options);
+                }
+            }
+        }
+    }
+
+    fn write_text<W: Write>(&self, writer: &mut XmlWriter<W>) -> Result<()> {
+        match original_text(self, writer.options()) {
+            Some(text) => writer.raw_text(text),
+            None => writer.text_list(&**self),
+        }
+    }
+}
+
+/// Returns the text to write for an array in place of its values, if any.
+///
+/// Arrays that fail to decode are always written as their original text.
+fn original_text<'a, T: ArrayValue>(array: &'a RawArray<T>, options: &WriteOptions) -> Option<&'a str> {
+    let text = array.raw_text()?;
+    match options.float_format {
+        FloatFormat::Fixed(_) if array.get().is_ok() => None,
+        _ => Some(text.trim()),
+    }
+}
+
+impl<T: WriteValue> WriteValue for MaybeEmpty<T> {
+    fn write_value(&self, output: &mut String, options: &WriteOptions) {
+        if let MaybeEmpty::Value(ref value) = *self {
+            value.write_value(output, options);
+        }
+    }
+
+    fn write_text<W: Write>(&self, writer: &mut XmlWriter<W>) -> Result<()> {
+        match *self {
+            MaybeEmpty::Value(ref value) => value.write_text(writer),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl WriteValue for AnyUri {
+    fn write_value(&self, output: &mut String, _: &WriteOptions) {
+        output.push_str(self.as_str());
+    }
+}
+
+impl WriteValue for UriFragment {
+    fn write_value(&self, output: &mut String, _: &WriteOptions) {
+        output.push('#');
+        output.push_str(self.id());
+    }
+}
+
+impl WriteValue for DateTime {
+    fn write_value(&self, output: &mut String, _: &WriteOptions) {
+        match *self {
+            DateTime::Utc(ref datetime) => output.push_str(&datetime.to_rfc3339()),
+            DateTime::Naive(ref datetime) => {
+                write!(output, "{}", datetime.format("%Y-%m-%dT%H:%M:%S%.f"))
+                    .expect("Formatting a value failed");
+            }
+        }
+    }
+}
+
+impl WriteValue for HexBinary {
+    fn write_value(&self, output: &mut String, _: &WriteOptions) {
+        for byte in self.as_bytes() {
+            write!(output, "{:02x}", byte).expect("Formatting a value failed");
+        }
+    }
+}
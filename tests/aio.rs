@@ -0,0 +1,94 @@
+#![cfg(feature = "futures-io")]
+
+extern crate collaborate;
+extern crate futures_io;
+
+use ::collaborate::{ParseOptions, Strictness, VersionedDocument};
+use ::collaborate::aio;
+use ::collaborate::v1_4::*;
+use ::futures_io::AsyncRead;
+use ::std::future::Future;
+use ::std::io;
+use ::std::pin::Pin;
+use ::std::sync::Arc;
+use ::std::task::{Context, Poll, Wake, Waker};
+
+static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+
+/// An async reader that returns a few bytes at a time, and isn't ready every other poll.
+struct TrickleReader {
+    source: &'static [u8],
+    ready: bool,
+}
+
+impl AsyncRead for TrickleReader {
+    fn poll_read(mut self: Pin<&mut Self>, context: &mut Context, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        if !self.ready {
+            self.ready = true;
+            context.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        self.ready = false;
+        let len = buf.len().min(self.source.len()).min(100);
+        buf[..len].copy_from_slice(&self.source[..len]);
+        self.source = &self.source[len..];
+        Poll::Ready(Ok(len))
+    }
+}
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut context = Context::from_waker(&waker);
+    let mut future = ::std::pin::pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+#[test]
+fn read_async_matches_read() {
+    let reader = TrickleReader { source: TEST_DOCUMENT, ready: false };
+    let document = block_on(Collada::read_async(reader)).unwrap();
+    assert_eq!(Collada::read(TEST_DOCUMENT).unwrap(), document);
+}
+
+#[test]
+fn read_async_reports_parse_errors() {
+    let reader = TrickleReader { source: b"<COLLADA version=\"1.4.1\"></COLLADA>", ready: false };
+    let error = block_on(Collada::read_async(reader)).unwrap_err();
+    assert_eq!(Collada::from_str("<COLLADA version=\"1.4.1\"></COLLADA>").unwrap_err(), error);
+}
+
+#[test]
+fn read_async_with_options() {
+    static DOCUMENT: &'static [u8] = br#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2008/03/COLLADASchema" version="1.5.0">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+            <gadget />
+        </asset>
+    </COLLADA>
+    "#;
+
+    let options = ParseOptions {
+        strictness: Strictness::Lenient,
+        ..ParseOptions::default()
+    };
+    let reader = TrickleReader { source: DOCUMENT, ready: false };
+    let (document, report) = block_on(aio::read_async_with(reader, options.clone())).unwrap();
+    assert_eq!(VersionedDocument::read_with(DOCUMENT, options).unwrap(), (document, report));
+
+    let reader = TrickleReader { source: DOCUMENT, ready: false };
+    assert!(block_on(aio::read_async_with(reader, ParseOptions::default())).is_err());
+}
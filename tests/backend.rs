@@ -0,0 +1,132 @@
+//! Tests for the XML reader backends, which run against whichever backend the enabled features
+//! select, so that both report the same positions.
+
+extern crate collaborate;
+
+use ::collaborate::*;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+#[test]
+fn positions_count_characters() {
+    static DOCUMENT: &'static str = r##"<COLLADA version="1.4.1">
+    <asset>
+        <contributor><author>Zoë Ångström</author></contributor>
+        <created>2017-02-07T20:44:30Z</created>
+        <modified>2017-02-07T20:44:30Z</modified>
+        <subject>ü</subject> <title>日本語</title>
+    </asset>
+    <library_geometries>
+        <geometry id="first"><mesh>
+            <source id="positions"><float_array id="positions-array" count="3">0 1 2</float_array></source>
+            <vertices id="vertices"><input semantic="POSITION" source="#positions" /></vertices>
+        </mesh></geometry>
+    </library_geometries>
+</COLLADA>"##;
+
+    let options = ParseOptions {
+        track_spans: true,
+        ..ParseOptions::default()
+    };
+    let (_, report) = VersionedDocument::read_with(DOCUMENT.as_bytes(), options).unwrap();
+
+    let span = report.span("first").unwrap();
+    assert_eq!((8, 8), (span.start.row, span.start.column));
+    assert_eq!((11, 15), (span.end.row, span.end.column));
+
+    let span = report.span("positions-array").unwrap();
+    assert_eq!((9, 35), (span.start.row, span.start.column));
+    assert_eq!((9, 84), (span.end.row, span.end.column));
+}
+
+#[test]
+fn error_positions() {
+    static DOCUMENT: &'static str = r##"<COLLADA version="1.4.1">
+    <asset>
+        <created>2017-02-07T20:44:30Z</created>
+        <modified>2017-02-07T20:44:30Z</modified>
+        <title>Ångström</title> <up_axis>SIDEWAYS</up_axis>
+    </asset>
+</COLLADA>"##;
+
+    // The invalid value is reported at the end of its text, which comes after the multibyte
+    // characters in the title.
+    let error = VersionedDocument::from_str(DOCUMENT).unwrap_err();
+    assert_eq!(TextPosition { row: 4, column: 41 }, error.position);
+}
+
+#[test]
+fn large_documents_parse_in_linear_time() {
+    let mut document = String::from(r#"<COLLADA version="1.4.1">
+    <asset>
+        <created>2017-02-07T20:44:30Z</created>
+        <modified>2017-02-07T20:44:30Z</modified>
+    </asset>
+    <library_geometries>
+"#);
+    for index in 0..2000 {
+        write!(
+            document,
+            r##"        <geometry id="geometry-{0}"><mesh>
+            <source id="positions-{0}"><float_array id="positions-array-{0}" count="3">0 1 2</float_array></source>
+            <vertices id="vertices-{0}"><input semantic="POSITION" source="#positions-{0}" /></vertices>
+        </mesh></geometry>
+"##,
+            index,
+        ).unwrap();
+    }
+    document.push_str("    </library_geometries>\n</COLLADA>\n");
+
+    // Computing each element's position by scanning from the start of the document takes minutes
+    // for a document this size.
+    let start = Instant::now();
+    let document = v1_4::Collada::from_str(&document).unwrap();
+    assert!(start.elapsed() < Duration::from_secs(30), "Parsing took {:?}", start.elapsed());
+    assert!(document.geometry("geometry-1999").is_some());
+}
+
+#[test]
+fn truncated_documents() {
+    static DOCUMENT: &'static str = r##"<COLLADA version="1.4.1">
+    <asset>
+        <created>2017-02-07T20:44:30Z</created>
+        <modified>2017-02-07T20:44:30Z</modified>
+    </asset>
+    <library_cameras>
+        <camera id="camera"><optics><technique_common>
+            <perspective><yfov>45</yfov><aspect_ratio>1.5</aspect_ratio><znear>0.1</znear><zfar>100</zfar></perspective>
+        </technique_common></optics></camera>
+    </library_cameras>
+    <library_geometries>
+        <geometry id="first"><mesh>
+            <source id="positions"><float_array id="positions-array" count="3">0 1 2</float_array></source>
+            <vertices id="vertices"><input semantic="POSITION" source="#positions" /></vertices>
+        </mesh></geometry>
+    </library_geometries>
+    <extra><technique profile="x"><foo><bar>1</bar></foo></technique></extra>
+</COLLADA>"##;
+
+    let cut_after = [
+        "",
+        "<created>",
+        "<library_cameras>",
+        "<library_geometries>",
+        "<mesh>",
+        "<technique profile=\"x\"><foo>",
+        "</extra>",
+    ];
+    for cut_after in &cut_after {
+        let end = DOCUMENT.find(cut_after).unwrap() + cut_after.len();
+        let error = VersionedDocument::from_str(&DOCUMENT[..end]).unwrap_err();
+        match error.kind {
+            ErrorKind::XmlError(_) => {}
+            ref kind => panic!("Unexpected error after {:?}: {:?}", cut_after, kind),
+        }
+
+        // The error is reported at the end of the document.
+        let truncated = &DOCUMENT[..end];
+        let row = truncated.matches('\n').count() as u64;
+        let column = truncated.rsplit('\n').next().unwrap().chars().count() as u64;
+        assert_eq!(TextPosition { row, column }, error.position, "Truncated after {:?}", cut_after);
+    }
+}
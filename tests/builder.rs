@@ -0,0 +1,125 @@
+extern crate collaborate;
+
+use ::collaborate::*;
+use ::collaborate::common::MaybeEmpty;
+use ::collaborate::v1_4::{Asset, Collada, Geometry, Library, Mesh};
+use ::collaborate::writer::WriteOptions;
+
+static BLENDER_CUBE: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+
+fn cube_mesh() -> Mesh {
+    let document = Collada::read(BLENDER_CUBE).unwrap();
+    document.geometry("Cube-mesh").unwrap().geometric_element.as_mesh().unwrap().clone()
+}
+
+fn asset() -> Asset {
+    Asset {
+        contributors: Vec::new(),
+        coverage: None,
+        created: "2017-02-07T20:44:30Z".parse().unwrap(),
+        keywords: MaybeEmpty::Absent,
+        modified: "2017-02-07T20:44:30Z".parse().unwrap(),
+        revision: MaybeEmpty::Absent,
+        subject: MaybeEmpty::Absent,
+        title: MaybeEmpty::Value("Builder".into()),
+        unit: None,
+        up_axis: None,
+        extras: Vec::new(),
+    }
+}
+
+#[test]
+fn build_scene_for_geometries() {
+    let document = DocumentBuilder::new()
+        .set_asset(asset())
+        .add_geometry(cube_mesh())
+        .build();
+
+    assert_eq!("1.4.1", document.version);
+    assert_eq!(Some("Builder"), document.asset.title.as_deref());
+    assert_eq!(2, document.libraries.len());
+    assert!(document.geometry("geometry-0").is_some());
+
+    let scene = document.main_scene().unwrap();
+    assert_eq!(Some("visual_scene-0"), scene.id.as_deref());
+    assert_eq!(1, scene.nodes.len());
+    assert_eq!(Some("node-0"), scene.nodes[0].id.as_deref());
+    assert_eq!(Some("geometry-0"), scene.nodes[0].instance_geometries[0].url.local_id());
+
+    // The built document is a valid document.
+    let mut output = Vec::new();
+    document.write(&mut output, WriteOptions::default()).unwrap();
+    assert_eq!(document, Collada::read(&*output).unwrap());
+}
+
+#[test]
+fn explicit_ids_are_kept() {
+    let cube = Collada::read(BLENDER_CUBE).unwrap();
+    let mut visual_scene = cube.visual_scene("Scene").unwrap().clone();
+    visual_scene.id = None;
+    visual_scene.nodes[0].id = None;
+
+    let geometry = Geometry {
+        id: Some("visual_scene-0".into()),
+        ..Geometry::from(cube_mesh())
+    };
+
+    let document = DocumentBuilder::new()
+        .add_geometry(geometry)
+        .add_visual_scene(visual_scene)
+        .build();
+
+    // The geometry's id is kept, and the generated ids avoid it.
+    assert!(document.geometry("visual_scene-0").is_some());
+    let scene = document.main_scene().unwrap();
+    assert_eq!(Some("visual_scene-1"), scene.id.as_deref());
+    assert_eq!(Some("node-0"), scene.nodes[0].id.as_deref());
+    assert_eq!(Some("Lamp"), scene.nodes[1].id.as_deref());
+
+    // No scene is generated when one is given.
+    let scenes = document.libraries.iter()
+        .filter_map(Library::as_library_visual_scenes)
+        .flat_map(|library| library.visual_scenes.iter())
+        .count();
+    assert_eq!(1, scenes);
+}
+
+#[test]
+fn build_empty_document() {
+    let document = DocumentBuilder::new().build();
+    assert!(document.libraries.is_empty());
+    assert!(document.scene.is_none());
+
+    let mut output = Vec::new();
+    document.write(&mut output, WriteOptions::default()).unwrap();
+    assert_eq!(document, Collada::read(&*output).unwrap());
+}
+
+#[test]
+fn generated_ids_avoid_nested_ids() {
+    use ::collaborate::v1_4::{Array, Extra};
+
+    let mut mesh = cube_mesh();
+    match mesh.sources[0].array {
+        Some(Array::Float(ref mut array)) => array.id = Some("geometry-0".into()),
+        ref array => panic!("Expected a float array, got {:?}", array),
+    }
+    mesh.sources[1].id = "visual_scene-0".into();
+    mesh.vertices.id = "node-0".into();
+    mesh.extras.push(Extra {
+        id: Some("geometry-1".into()),
+        name: None,
+        type_hint: None,
+        asset: None,
+        techniques: Vec::new(),
+    });
+
+    let document = DocumentBuilder::new()
+        .add_geometry(mesh)
+        .build();
+
+    assert!(document.geometry("geometry-2").is_some());
+    let scene = document.main_scene().unwrap();
+    assert_eq!(Some("visual_scene-1"), scene.id.as_deref());
+    assert_eq!(Some("node-1"), scene.nodes[0].id.as_deref());
+}
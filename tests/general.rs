@@ -1,4 +1,5 @@
 extern crate collaborate;
+extern crate xml;
 
 use ::collaborate::*;
 
@@ -88,3 +89,901 @@ fn float_array_text_contents() {
 
     assert_eq!(EXPECTED, &*array.data, "`<float_array>` contents were not parsed correctly");
 }
+
+#[test]
+fn schema_coverage() {
+    use ::collaborate::coverage::*;
+
+    let coverage = schema_coverage();
+
+    // Text-only children are reported along with the elements that have their own types.
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("COLLADA"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("created"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("polylist"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("library_animations"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("sampler"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("library_animation_clips"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("instance_animation"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("phong"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("emission"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("profile_GLSL"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("float4x4"));
+    assert_eq!(Some(Support::Stubbed), coverage.v1_4.support("profile_GLES"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("sampler2D"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("spot"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("node"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("bind_material"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("tristrips"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("library_nodes"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("rigid_body"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("tapered_capsule"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("force_field"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("convex_mesh"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("instance_visual_scene"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_4.support("evaluate_scene"));
+    assert_eq!(None, coverage.v1_4.support("coverage"));
+
+    // `<coverage>` was added in 1.5.0, and is recursive through `<extra>` and `<asset>`.
+    assert_eq!(Some(Support::Implemented), coverage.v1_5.support("coverage"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_5.support("altitude"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_5.support("formula"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_5.support("brep"));
+    assert_eq!(Some(Support::Stubbed), coverage.v1_5.support("mesh"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_5.support("bind_joint_axis"));
+    assert_eq!(Some(Support::Implemented), coverage.v1_5.support("technique_override"));
+    assert!(coverage.v1_5.stubbed().any(|name| name == "library_kinematics_models"));
+}
+
+#[test]
+fn render_unexpected_attribute() {
+    static DOCUMENT: &'static str = r#"<COLLADA version="1.4.1">
+    <asset>
+        <contributor foo="bar" />
+        <created>2017-02-07T20:44:30Z</created>
+        <modified>2017-02-07T20:44:30Z</modified>
+    </asset>
+</COLLADA>"#;
+
+    static EXPECTED: &'static str = r#"error: <contributor> had an an attribute "foo" that is not allowed, only the following attributes are allowed for <contributor>: 
+ --> 3:9
+  |
+3 |         <contributor foo="bar" />
+  |                      ^^^
+  |
+  = note: <contributor> doesn't allow any attributes
+"#;
+
+    let error = VersionedDocument::from_str(DOCUMENT).unwrap_err();
+    assert_eq!(EXPECTED, error.render(DOCUMENT));
+}
+
+#[test]
+fn render_unexpected_element() {
+    static DOCUMENT: &'static str = r#"<COLLADA version="1.4.1">
+    <asset>
+        <created>2017-02-07T20:44:30Z</created>
+        <modified>2017-02-07T20:44:30Z</modified>
+        <gadget />
+    </asset>
+</COLLADA>"#;
+
+    static EXPECTED: &'static str = r#"error: <asset> had a child <gadget> which is not allowed, <asset> may only have the following children: contributor, created, keywords, modified, revision, subject, title, unit, up_axis
+ --> 5:9
+  |
+5 |         <gadget />
+  |         ^^^^^^^
+  |
+  = note: expected one of contributor, created, keywords, modified, revision, subject, title, unit, up_axis
+"#;
+
+    let error = VersionedDocument::from_str(DOCUMENT).unwrap_err();
+    assert_eq!(EXPECTED, error.render(DOCUMENT));
+}
+
+#[test]
+fn parse_report() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.0">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_effects>
+            <effect id="first">
+                <profile_GLES />
+            </effect>
+            <effect id="second">
+                <profile_GLES />
+            </effect>
+        </library_effects>
+    </COLLADA>
+    "#;
+
+    let (_, report) = VersionedDocument::from_str_with_report(DOCUMENT).unwrap();
+    assert!(!report.is_clean());
+    assert_eq!(Some(&2), report.stubbed_elements.get("profile_GLES"));
+    assert_eq!(Some(&1), report.quirks.get("1.4.0 document parsed as 1.4.1"));
+    assert!(report.fallbacks.is_empty());
+
+    let mut total = report.clone();
+    total.merge(&report);
+    assert_eq!(Some(&4), total.stubbed_elements.get("profile_GLES"));
+}
+
+#[test]
+fn lenient_parsing() {
+    static DOCUMENT: &'static str = r#"<COLLADA version="1.4.1">
+    <asset>
+        <contributor foo="bar" />
+        <created>2017-02-07T20:44:30Z</created>
+        <gadget><nested /></gadget>
+        <modified>2017-02-07T20:44:30Z</modified>
+    </asset>
+</COLLADA>"#;
+
+    let strict = VersionedDocument::read_with(DOCUMENT.as_bytes(), ParseOptions::default()).unwrap_err();
+    assert_eq!(VersionedDocument::from_str(DOCUMENT).unwrap_err(), strict);
+
+    let options = ParseOptions {
+        strictness: Strictness::Lenient,
+        ..ParseOptions::default()
+    };
+    let (document, report) = VersionedDocument::read_with(DOCUMENT.as_bytes(), options).unwrap();
+    match document {
+        VersionedDocument::V1_4(document) => {
+            assert_eq!(1, document.asset.contributors.len());
+        }
+        _ => panic!("Expected a 1.4 document"),
+    }
+
+    assert_eq!(2, report.warnings.len());
+    match report.warnings[0].kind {
+        ErrorKind::UnexpectedAttribute { ref attribute, .. } => assert_eq!("foo", attribute),
+        ref kind => panic!("Unexpected warning: {:?}", kind),
+    }
+    match report.warnings[1].kind {
+        ErrorKind::UnexpectedElement { ref element, .. } => assert_eq!("gadget", element),
+        ref kind => panic!("Unexpected warning: {:?}", kind),
+    }
+    assert!(!report.is_clean());
+}
+
+#[test]
+fn parse_limits() {
+    static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+
+    let options = ParseOptions {
+        max_depth: Some(3),
+        ..ParseOptions::default()
+    };
+    match VersionedDocument::read_with(TEST_DOCUMENT, options) {
+        Err(Error { kind: ErrorKind::LimitExceeded { limit: "max_depth", max: 3 }, .. }) => {}
+        result => panic!("Expected the depth limit to be exceeded, got {:?}", result.map(|_| ())),
+    }
+
+    let options = ParseOptions {
+        max_array_len: Some(10),
+        ..ParseOptions::default()
+    };
+    match VersionedDocument::read_with(TEST_DOCUMENT, options) {
+        Err(Error { kind: ErrorKind::LimitExceeded { limit: "max_array_len", max: 10 }, .. }) => {}
+        result => panic!("Expected the array limit to be exceeded, got {:?}", result.map(|_| ())),
+    }
+
+    let options = ParseOptions {
+        max_depth: Some(64),
+        max_array_len: Some(1000),
+        ..ParseOptions::default()
+    };
+    assert!(VersionedDocument::read_with(TEST_DOCUMENT, options).is_ok());
+}
+
+#[test]
+fn validate_counts() {
+    static DOCUMENT: &'static str = r##"<COLLADA version="1.4.1">
+    <asset>
+        <created>2017-02-07T20:44:30Z</created>
+        <modified>2017-02-07T20:44:30Z</modified>
+    </asset>
+    <library_geometries>
+        <geometry>
+            <mesh>
+                <source id="positions">
+                    <float_array count="4">0 1 2</float_array>
+                </source>
+                <vertices id="vertices">
+                    <input semantic="POSITION" source="#positions" />
+                </vertices>
+            </mesh>
+        </geometry>
+    </library_geometries>
+</COLLADA>"##;
+
+    // Counts are only checked when asked for.
+    assert!(VersionedDocument::from_str(DOCUMENT).is_ok());
+
+    let options = ParseOptions {
+        validate_counts: true,
+        ..ParseOptions::default()
+    };
+    match VersionedDocument::read_with(DOCUMENT.as_bytes(), options.clone()) {
+        Err(Error { kind: ErrorKind::CountMismatch { element: "float_array", expected: 4, actual: 3 }, .. }) => {}
+        result => panic!("Expected a count mismatch, got {:?}", result.map(|_| ())),
+    }
+
+    let options = ParseOptions {
+        strictness: Strictness::Lenient,
+        ..options
+    };
+    let (_, report) = VersionedDocument::read_with(DOCUMENT.as_bytes(), options).unwrap();
+    assert_eq!(1, report.warnings.len());
+}
+
+#[test]
+fn resource_limits() {
+    static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+
+    let options = ParseOptions {
+        max_text_len: Some(16),
+        ..ParseOptions::default()
+    };
+    match VersionedDocument::read_with(TEST_DOCUMENT, options) {
+        Err(Error { kind: ErrorKind::LimitExceeded { limit: "max_text_len", max: 16 }, .. }) => {}
+        result => panic!("Expected the text limit to be exceeded, got {:?}", result.map(|_| ())),
+    }
+
+    let options = ParseOptions {
+        max_children: Some(2),
+        ..ParseOptions::default()
+    };
+    match VersionedDocument::read_with(TEST_DOCUMENT, options) {
+        Err(Error { kind: ErrorKind::LimitExceeded { limit: "max_children", max: 2 }, .. }) => {}
+        result => panic!("Expected the child limit to be exceeded, got {:?}", result.map(|_| ())),
+    }
+
+    let options = ParseOptions {
+        max_array_bytes: Some(64),
+        ..ParseOptions::default()
+    };
+    match VersionedDocument::read_with(TEST_DOCUMENT, options) {
+        Err(Error { kind: ErrorKind::LimitExceeded { limit: "max_array_bytes", max: 64 }, .. }) => {}
+        result => panic!("Expected the array size limit to be exceeded, got {:?}", result.map(|_| ())),
+    }
+
+    let options = ParseOptions {
+        max_depth: Some(64),
+        max_array_len: Some(1000),
+        max_text_len: Some(64 * 1024),
+        max_children: Some(1000),
+        max_array_bytes: Some(1024 * 1024),
+        ..ParseOptions::default()
+    };
+    assert!(VersionedDocument::read_with(TEST_DOCUMENT, options).is_ok());
+}
+
+#[test]
+fn skip_nested_element() {
+    static DOCUMENT: &'static str = r#"<root>
+        <extra>
+            <extra><technique profile="a">text</technique></extra>
+            <![CDATA[<extra>]]>
+            <?instruction data?>
+            <extra />
+        </extra>
+        <after />
+    </root>"#;
+
+    let mut events = xml::ParserConfig::new()
+        .trim_whitespace(true)
+        .create_reader(DOCUMENT.as_bytes());
+    loop {
+        match events.next().unwrap() {
+            XmlEvent::StartElement { ref name, .. } if name.local_name == "extra" => { break; }
+            _ => {}
+        }
+    }
+
+    skip_element(&mut events).unwrap();
+    match events.next().unwrap() {
+        XmlEvent::StartElement { name, .. } => assert_eq!(name.local_name, "after"),
+        event => panic!("Expected <after>, got {:?}", event),
+    }
+}
+
+#[test]
+fn skip_truncated_element() {
+    static DOCUMENT: &'static str = "<root><extra><technique>";
+
+    let mut events = xml::EventReader::from_str(DOCUMENT);
+    for _ in 0..3 {
+        events.next().unwrap();
+    }
+
+    assert!(skip_element(&mut events).is_err());
+}
+
+#[test]
+fn leaf_element_children() {
+    static DOCUMENT: &'static str = r##"<COLLADA version="1.4.1">
+    <asset>
+        <created>2017-02-07T20:44:30Z</created>
+        <modified>2017-02-07T20:44:30Z</modified>
+    </asset>
+    <library_geometries>
+        <geometry>
+            <mesh>
+                <source id="positions">
+                    <float_array count="3">0 1 2</float_array>
+                </source>
+                <vertices id="vertices">
+                    <input semantic="POSITION" source="#positions"><gadget /></input>
+                </vertices>
+            </mesh>
+        </geometry>
+    </library_geometries>
+</COLLADA>"##;
+
+    match VersionedDocument::from_str(DOCUMENT) {
+        Err(Error { kind: ErrorKind::UnexpectedElement { parent: "input", ref element, .. }, .. }) => {
+            assert_eq!("gadget", element);
+        }
+        result => panic!("Expected an unexpected element error, got {:?}", result.map(|_| ())),
+    }
+
+    let options = ParseOptions {
+        strictness: Strictness::Lenient,
+        ..ParseOptions::default()
+    };
+    let (_, report) = VersionedDocument::read_with(DOCUMENT.as_bytes(), options).unwrap();
+    assert_eq!(1, report.warnings.len());
+}
+
+#[test]
+fn read_with_warnings() {
+    static DOCUMENT: &'static str = r#"<COLLADA version="1.4.1">
+    <asset>
+        <created>2017-02-07T20:44:30Z</created>
+        <gadget />
+        <modified>2017-02-07T20:44:30Z</modified>
+    </asset>
+    <library_effects>
+        <effect id="effect">
+            <profile_GLES />
+        </effect>
+    </library_effects>
+</COLLADA>"#;
+
+    let (_, warnings) = VersionedDocument::read_with_warnings(DOCUMENT.as_bytes()).unwrap();
+    assert_eq!(2, warnings.len());
+    match warnings[0] {
+        Warning::Tolerated(Error { kind: ErrorKind::UnexpectedElement { ref element, .. }, .. }) => {
+            assert_eq!("gadget", element);
+        }
+        ref warning => panic!("Unexpected warning: {:?}", warning),
+    }
+    match warnings[1] {
+        Warning::Stubbed { ref element, count: 1 } => assert_eq!("profile_GLES", element),
+        ref warning => panic!("Unexpected warning: {:?}", warning),
+    }
+}
+
+#[test]
+fn track_spans() {
+    static DOCUMENT: &'static str = r##"<COLLADA version="1.4.1">
+    <asset>
+        <created>2017-02-07T20:44:30Z</created>
+        <modified>2017-02-07T20:44:30Z</modified>
+    </asset>
+    <library_geometries>
+        <geometry id="cube">
+            <mesh>
+                <source id="positions">
+                    <float_array id="positions-array" count="3">0 1 2</float_array>
+                </source>
+                <vertices id="vertices">
+                    <input semantic="POSITION" source="#positions" />
+                </vertices>
+            </mesh>
+        </geometry>
+    </library_geometries>
+</COLLADA>"##;
+
+    let (_, report) = VersionedDocument::from_str_with_report(DOCUMENT).unwrap();
+    assert!(report.spans.is_empty());
+
+    let options = ParseOptions {
+        track_spans: true,
+        ..ParseOptions::default()
+    };
+    let (_, report) = VersionedDocument::read_with(DOCUMENT.as_bytes(), options).unwrap();
+    assert_eq!(4, report.spans.len());
+
+    let span = report.span("cube").unwrap();
+    assert_eq!((6, 8), (span.start.row, span.start.column));
+    assert_eq!((15, 8), (span.end.row, span.end.column));
+
+    let span = report.span("positions-array").unwrap();
+    assert_eq!((9, 20), (span.start.row, span.start.column));
+    assert_eq!((9, 69), (span.end.row, span.end.column));
+
+    assert!(report.span("missing").is_none());
+}
+
+#[test]
+fn track_order() {
+    static DOCUMENT: &'static str = r##"<COLLADA version="1.4.1">
+    <asset>
+        <created>2017-02-07T20:44:30Z</created>
+        <modified>2017-02-07T20:44:30Z</modified>
+    </asset>
+    <library_effects>
+        <effect id="effect">
+            <profile_GLES id="gles">
+                <technique id="skipped" />
+            </profile_GLES>
+        </effect>
+    </library_effects>
+    <library_geometries>
+        <geometry id="second">
+            <convex_mesh />
+        </geometry>
+        <geometry id="first">
+            <convex_mesh />
+        </geometry>
+    </library_geometries>
+</COLLADA>"##;
+
+    let (_, report) = VersionedDocument::from_str_with_report(DOCUMENT).unwrap();
+    assert!(report.document_order.is_empty());
+
+    let options = ParseOptions {
+        track_order: true,
+        ..ParseOptions::default()
+    };
+    let (_, report) = VersionedDocument::read_with(DOCUMENT.as_bytes(), options).unwrap();
+
+    // Elements inside stubbed libraries aren't parsed, but they're still counted.
+    assert_eq!(Some(6), report.document_index("gles"));
+    assert_eq!(None, report.document_index("skipped"));
+    assert_eq!(Some(9), report.document_index("second"));
+    assert_eq!(Some(11), report.document_index("first"));
+    assert_eq!(vec!["effect", "gles", "second", "first"], report.ids_in_document_order());
+}
+
+#[test]
+fn recover_from_errors() {
+    static DOCUMENT: &'static str = r##"<COLLADA version="1.4.1">
+    <asset>
+        <created>2017-02-07T20:44:30Z</created>
+        <modified>2017-02-07T20:44:30Z</modified>
+    </asset>
+    <library_geometries>
+        <geometry id="first">
+            <mesh>
+                <source id="first-positions">
+                    <float_array count="three">0 1 2</float_array>
+                </source>
+                <vertices id="first-vertices">
+                    <input semantic="POSITION" source="#first-positions" />
+                </vertices>
+            </mesh>
+        </geometry>
+        <geometry id="second">
+            <mesh>
+                <source id="second-positions">
+                    <float_array count="3">0 1 2</float_array>
+                </source>
+                <vertices id="second-vertices">
+                    <input semantic="POSITION" source="#second-positions" />
+                </vertices>
+            </mesh>
+        </geometry>
+        <geometry id="third">
+            <mesh>
+                <vertices id="third-vertices">
+                    <input semantic="POSITION" source="#third-positions" />
+                </vertices>
+            </mesh>
+        </geometry>
+    </library_geometries>
+</COLLADA>"##;
+
+    // Without recovery only the first error is found.
+    match VersionedDocument::from_str(DOCUMENT) {
+        Err(Error { kind: ErrorKind::ParseIntError(_), .. }) => {}
+        result => panic!("Expected an integer error, got {:?}", result.map(|_| ())),
+    }
+
+    let options = ParseOptions {
+        recover: true,
+        ..ParseOptions::default()
+    };
+    let (document, report) = VersionedDocument::read_with(DOCUMENT.as_bytes(), options).unwrap();
+    match document {
+        VersionedDocument::V1_4(document) => {
+            let geometries = match document.libraries[0] {
+                v1_4::Library::Geometries(ref library) => &library.geometries,
+                ref library => panic!("Expected a geometry library, got {:?}", library),
+            };
+            // The first geometry is kept, since only its (optional) array is skipped.
+            let ids = geometries.iter()
+                .map(|geometry| geometry.id.as_deref())
+                .collect::<Vec<_>>();
+            assert_eq!(vec![Some("first"), Some("second")], ids);
+        }
+        _ => panic!("Expected a 1.4 document"),
+    }
+    assert_eq!(2, report.errors.len());
+    assert_eq!(9, report.errors[0].position.row);
+    match report.errors[1].kind {
+        ErrorKind::UnexpectedElement { parent: "mesh", ref element, .. } => assert_eq!("vertices", element),
+        ref kind => panic!("Unexpected error: {:?}", kind),
+    }
+
+    let errors = VersionedDocument::read_recovering(DOCUMENT.as_bytes()).unwrap_err().errors;
+    assert_eq!(report.errors, errors);
+}
+
+#[test]
+fn non_utf8_encodings() {
+    use ::collaborate::v1_4::Collada;
+
+    static DOCUMENT: &'static str = r#"<?xml version="1.0" encoding="ENCODING"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <contributor>
+                <author>José Müller</author>
+            </contributor>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+    </COLLADA>
+    "#;
+
+    fn author(document: &Collada) -> Option<&str> {
+        document.asset.contributors[0].author.as_deref()
+    }
+
+    // ISO-8859-1, detected from the XML declaration.
+    let latin1 = DOCUMENT.replace("ENCODING", "ISO-8859-1")
+        .chars()
+        .map(|c| c as u8)
+        .collect::<Vec<_>>();
+    let document = Collada::read(&*latin1).unwrap();
+    assert_eq!(Some("José Müller"), author(&document));
+
+    // UTF-16, detected from the byte order mark.
+    let utf16 = DOCUMENT.replace("ENCODING", "UTF-16");
+    let mut bytes = vec![0xFF, 0xFE];
+    for unit in utf16.encode_utf16() {
+        bytes.push(unit as u8);
+        bytes.push((unit >> 8) as u8);
+    }
+    let document = Collada::read(&*bytes).unwrap();
+    assert_eq!(Some("José Müller"), author(&document));
+    let document = match VersionedDocument::read(&*bytes).unwrap() {
+        VersionedDocument::V1_4(document) => document,
+        _ => panic!("Expected a 1.4 document"),
+    };
+    assert_eq!(Some("José Müller"), author(&document));
+
+    // Text that has already been decoded isn't decoded again.
+    let decoded = DOCUMENT.replace("ENCODING", "ISO-8859-1");
+    let document = Collada::from_str(&decoded).unwrap();
+    assert_eq!(Some("José Müller"), author(&document));
+}
+
+#[test]
+fn dtd_internal_subset() {
+    static DOCUMENT: &'static str = r#"<?xml version="1.0" encoding="utf-8"?>
+    <!DOCTYPE COLLADA [
+        <!ENTITY author "Blender User">
+        <!-- A comment with a > in it -->
+    ]>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+    </COLLADA>
+    "#;
+
+    let _ = VersionedDocument::from_str(DOCUMENT).unwrap();
+    let _ = VersionedDocument::read(DOCUMENT.as_bytes()).unwrap();
+    let _ = v1_4::Collada::read(DOCUMENT.as_bytes()).unwrap();
+    let _ = ::collaborate::stream::DocumentReader::new(DOCUMENT.as_bytes()).unwrap();
+    let _ = ::collaborate::lazy::LazyDocument::from_reader(DOCUMENT.as_bytes()).unwrap();
+
+    let options = ParseOptions {
+        dtd: DtdHandling::Reject,
+        ..ParseOptions::default()
+    };
+    let error = VersionedDocument::read_with(DOCUMENT.as_bytes(), options).unwrap_err();
+    assert_eq!(ErrorKind::DtdNotAllowed, error.kind);
+    assert_eq!(TextPosition { row: 1, column: 4 }, error.position);
+}
+
+#[test]
+fn from_bytes() {
+    use std::fs;
+
+    for path in &["resources/blender_cube.dae", "resources/v1_5_minimal.dae"] {
+        let bytes = fs::read(path).unwrap();
+        let expected = VersionedDocument::read(&*bytes).unwrap();
+        assert_eq!(expected, VersionedDocument::from_bytes(&bytes).unwrap());
+        assert_eq!(expected, VersionedDocument::from_str(::std::str::from_utf8(&bytes).unwrap()).unwrap());
+    }
+
+    let bytes = fs::read("resources/blender_cube.dae").unwrap();
+    assert_eq!(v1_4::Collada::read(&*bytes).unwrap(), v1_4::Collada::from_bytes(&bytes).unwrap());
+    let bytes = fs::read("resources/v1_5_minimal.dae").unwrap();
+    assert_eq!(v1_5::Collada::read(&*bytes).unwrap(), v1_5::Collada::from_bytes(&bytes).unwrap());
+
+    // The encoding is still detected, and a DTD is still skipped.
+    static DOCUMENT: &'static str = r#"<?xml version="1.0" encoding="UTF-16"?>
+    <!DOCTYPE COLLADA [ <!ENTITY tool "Exporter"> ]>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <contributor>
+                <author>José Müller</author>
+            </contributor>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+    </COLLADA>
+    "#;
+    let mut utf16 = vec![0xFE, 0xFF];
+    for unit in DOCUMENT.encode_utf16() {
+        utf16.push((unit >> 8) as u8);
+        utf16.push(unit as u8);
+    }
+    let document = v1_4::Collada::from_bytes(&utf16).unwrap();
+    assert_eq!(Some("José Müller"), document.asset.contributors[0].author.as_deref());
+    assert_eq!(document, v1_4::Collada::from_str(DOCUMENT).unwrap());
+
+    // Errors are reported at the same position as when reading.
+    let broken = DOCUMENT.replace("<created>", "<created>oops");
+    let expected = VersionedDocument::read(broken.as_bytes()).unwrap_err();
+    assert_eq!(expected, VersionedDocument::from_bytes(broken.as_bytes()).unwrap_err());
+    assert_eq!(expected, VersionedDocument::from_str(&broken).unwrap_err());
+}
+
+#[test]
+fn custom_entities() {
+    use std::collections::BTreeMap;
+
+    static DOCUMENT: &'static str = r#"<?xml version="1.0" encoding="utf-8"?>
+    <!DOCTYPE COLLADA [ <!ENTITY tool "Exporter"> ]>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <contributor>
+                <!-- &tool; isn't expanded in comments -->
+                <author>&author; &amp; &tool;</author>
+                <authoring_tool><![CDATA[&tool;]]></authoring_tool>
+            </contributor>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+            <unit name="&unit;" meter="1" />
+        </asset>
+    </COLLADA>
+    "#;
+
+    // Entities declared in the DTD aren't expanded without being provided.
+    let error = v1_4::Collada::read(DOCUMENT.as_bytes()).unwrap_err();
+    match error.kind {
+        ErrorKind::XmlError(_) => {}
+        ref kind => panic!("Unexpected error: {:?}", kind),
+    }
+
+    let mut entities = BTreeMap::new();
+    entities.insert("author".to_string(), "<Someone>".to_string());
+    entities.insert("tool".to_string(), "Exporter".to_string());
+    entities.insert("unit".to_string(), "\"metre\"".to_string());
+    let options = ParseOptions {
+        entities,
+        ..ParseOptions::default()
+    };
+    let (document, _) = v1_4::Collada::read_with(DOCUMENT.as_bytes(), options).unwrap();
+    let contributor = &document.asset.contributors[0];
+    assert_eq!(Some("<Someone> & Exporter"), contributor.author.as_deref());
+    assert_eq!(Some("&tool;"), contributor.authoring_tool.as_deref());
+    assert_eq!("\"metre\"", document.asset.unit().name);
+}
+
+#[test]
+fn custom_entities_large_document() {
+    use std::collections::BTreeMap;
+
+    // Long enough to be read in several chunks, so that references get split between them.
+    let mut geometries = String::new();
+    for index in 0..2000 {
+        geometries.push_str(&format!("<geometry id=\"&prefix;{}\"><convex_mesh /></geometry>\n", index));
+    }
+    let document = format!(r#"<?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>{}</library_geometries>
+    </COLLADA>
+    "#, geometries);
+
+    let mut entities = BTreeMap::new();
+    entities.insert("prefix".to_string(), "mesh-".to_string());
+    let options = ParseOptions {
+        entities,
+        ..ParseOptions::default()
+    };
+    let (document, _) = v1_4::Collada::read_with(document.as_bytes(), options).unwrap();
+    let geometries = match document.libraries[0] {
+        v1_4::Library::Geometries(ref library) => &library.geometries,
+        ref library => panic!("Expected a geometry library, got {:?}", library),
+    };
+    assert_eq!(2000, geometries.len());
+    for (index, geometry) in geometries.iter().enumerate() {
+        assert_eq!(Some(format!("mesh-{}", index)), geometry.id);
+    }
+}
+
+#[test]
+fn progress_callback() {
+    use std::sync::{Arc, Mutex};
+
+    let mut geometries = String::new();
+    for index in 0..500 {
+        let name = "x".repeat(200);
+        geometries.push_str(&format!("<geometry id=\"mesh-{}\" name=\"{}\"><convex_mesh /></geometry>\n", index, name));
+    }
+    let document = format!(r#"<?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>{}</library_geometries>
+        <library_cameras />
+    </COLLADA>
+    "#, geometries);
+
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let callback = {
+        let reports = reports.clone();
+        ProgressCallback::new(move |progress| reports.lock().unwrap().push(progress))
+    };
+    let options = ParseOptions {
+        progress: Some(callback.with_total_bytes(document.len() as u64)),
+        ..ParseOptions::default()
+    };
+    let _ = VersionedDocument::read_with(document.as_bytes(), options).unwrap();
+
+    let reports = reports.lock().unwrap();
+    let mut libraries = reports.iter()
+        .filter(|progress| progress.path.len() >= 2 && progress.path[1].starts_with("library_"))
+        .map(|progress| &*progress.path[1])
+        .collect::<Vec<_>>();
+    libraries.dedup();
+    assert_eq!(vec!["library_geometries", "library_cameras"], libraries);
+
+    // The document is large enough to also be reported on after part of it has been read.
+    assert!(reports.len() > 2);
+    assert!(reports.iter().all(|progress| progress.total_bytes == Some(document.len() as u64)));
+    assert!(reports.windows(2).all(|pair| pair[0].bytes_read <= pair[1].bytes_read));
+    assert!(reports.last().unwrap().bytes_read <= document.len() as u64);
+}
+
+#[test]
+fn parser_reuse() {
+    use std::fs::File;
+    use std::io::Read;
+
+    let mut cube = Vec::new();
+    File::open("resources/blender_cube.dae").unwrap().read_to_end(&mut cube).unwrap();
+    let mut minimal = Vec::new();
+    File::open("resources/v1_5_minimal.dae").unwrap().read_to_end(&mut minimal).unwrap();
+
+    // A smaller document after a larger one, so that stale buffer contents would show up.
+    let latin1 = r#"<?xml version="1.0" encoding="ISO-8859-1"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <contributor><author>Jos&#233;</author></contributor>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+    </COLLADA>
+    "#.replace("&#233;", "\u{e9}").chars().map(|c| c as u8).collect::<Vec<_>>();
+
+    let mut parser = Parser::new();
+    for _ in 0..2 {
+        assert_eq!(VersionedDocument::read(&*cube).unwrap(), parser.parse(&*cube).unwrap());
+        assert!(parser.parse(&b"<COLLADA version=\"9.9\" />"[..]).is_err());
+        assert!(parser.parse(&b"<COLLADA version=\"1.4.1\"><bogus /></COLLADA>"[..]).is_err());
+        assert_eq!(VersionedDocument::read(&*minimal).unwrap(), parser.parse(&*minimal).unwrap());
+        assert_eq!(VersionedDocument::read(&*latin1).unwrap(), parser.parse(&*latin1).unwrap());
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_parsing() {
+    static DOCUMENT: &'static str = r##"<COLLADA version="1.4.0">
+    <asset>
+        <created>2017-02-07T20:44:30Z</created>
+        <modified>2017-02-07T20:44:30Z</modified>
+    </asset>
+    <library_geometries>
+        <geometry id="cube" color="red">
+            <mesh>
+                <source id="positions">
+                    <float_array id="positions-array" count="3">0 1 2</float_array>
+                </source>
+                <vertices id="vertices">
+                    <input semantic="POSITION" source="#positions" />
+                </vertices>
+            </mesh>
+        </geometry>
+    </library_geometries>
+    <library_visual_scenes id="scenes">
+        <visual_scene id="scene" shiny="yes">
+            <node id="node" />
+        </visual_scene>
+    </library_visual_scenes>
+    <scene>
+        <instance_visual_scene url="#scene" />
+    </scene>
+</COLLADA>"##;
+
+    fn parse(source: &str, options: ParseOptions) -> Result<(VersionedDocument, ParseReport)> {
+        VersionedDocument::read_with(source.as_bytes(), options)
+    }
+
+    let v1_5 = DOCUMENT.replace("1.4.0", "1.5.0");
+    for document in &[DOCUMENT, &*v1_5] {
+        let options = ParseOptions {
+            strictness: Strictness::Lenient,
+            track_spans: true,
+            ..ParseOptions::default()
+        };
+        let parallel = ParseOptions { parallel: true, ..options.clone() };
+        let expected = parse(document, options).unwrap();
+        assert_eq!(expected, parse(document, parallel).unwrap());
+        assert!(!expected.1.warnings.is_empty());
+        assert!(expected.1.span("scenes").is_some());
+
+        // Strict parsing fails at the first problem, even though the libraries are parsed
+        // separately.
+        let expected = parse(document, ParseOptions::default()).unwrap_err();
+        let parallel = ParseOptions { parallel: true, ..ParseOptions::default() };
+        assert_eq!(expected, parse(document, parallel).unwrap_err());
+        match expected.kind {
+            ErrorKind::UnexpectedAttribute { ref attribute, .. } => assert_eq!("color", attribute),
+            ref kind => panic!("Unexpected error: {:?}", kind),
+        }
+    }
+
+    let options = ParseOptions {
+        strictness: Strictness::Lenient,
+        parallel: true,
+        ..ParseOptions::default()
+    };
+    let (_, report) = parse(DOCUMENT, options).unwrap();
+    assert_eq!(Some(&1), report.quirks.get("1.4.0 document parsed as 1.4.1"));
+
+    // Every library that fails to parse is recovered from.
+    let broken = DOCUMENT.replace(r#" color="red""#, "").replace(r#" shiny="yes""#, "")
+        .replace(r#"count="3""#, r#"count="three""#)
+        .replace(r#"<node id="node" />"#, r#"<node id="node"><gadget /></node>"#);
+    let options = ParseOptions { recover: true, ..ParseOptions::default() };
+    let (expected, expected_report) = parse(&broken, options.clone()).unwrap();
+    let (document, report) = parse(&broken, ParseOptions { parallel: true, ..options }).unwrap();
+    assert_eq!(expected, document);
+    assert_eq!(expected_report.errors, report.errors);
+    assert_eq!(3, report.errors.len());
+
+    // The libraries are only parsed separately when they're in their usual place.
+    let misplaced = DOCUMENT.replace("</COLLADA>", "<library_nodes><node /></library_nodes></COLLADA>");
+    let expected = parse(&misplaced, ParseOptions::default()).unwrap_err();
+    let parallel = ParseOptions { parallel: true, ..ParseOptions::default() };
+    assert_eq!(expected, parse(&misplaced, parallel).unwrap_err());
+}
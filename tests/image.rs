@@ -0,0 +1,174 @@
+extern crate collaborate;
+
+use ::collaborate::image::{self, ImageFileFormat, ImageInfo};
+use ::collaborate::v1_4::*;
+
+fn png(width: u32, height: u32, color_type: u8) -> Vec<u8> {
+    let mut bytes = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    bytes.extend_from_slice(&width.to_be_bytes());
+    bytes.extend_from_slice(&height.to_be_bytes());
+    bytes.extend_from_slice(&[8, color_type, 0, 0, 0]);
+    bytes
+}
+
+fn dds(width: u32, height: u32, flags: u32, four_cc: &[u8; 4]) -> Vec<u8> {
+    let mut bytes = vec![0; 128];
+    bytes[..4].copy_from_slice(b"DDS ");
+    bytes[4..8].copy_from_slice(&124u32.to_le_bytes());
+    bytes[12..16].copy_from_slice(&height.to_le_bytes());
+    bytes[16..20].copy_from_slice(&width.to_le_bytes());
+    bytes[80..84].copy_from_slice(&flags.to_le_bytes());
+    bytes[84..88].copy_from_slice(four_cc);
+    bytes
+}
+
+fn exr_attribute(bytes: &mut Vec<u8>, name: &str, attribute_type: &str, value: &[u8]) {
+    bytes.extend_from_slice(name.as_bytes());
+    bytes.push(0);
+    bytes.extend_from_slice(attribute_type.as_bytes());
+    bytes.push(0);
+    bytes.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(value);
+}
+
+#[test]
+fn sniff_png() {
+    let expected = ImageInfo {
+        format: ImageFileFormat::Png,
+        width: 640,
+        height: 480,
+        channels: Some(3),
+    };
+    assert_eq!(Some(expected), image::sniff(&png(640, 480, 2)));
+    assert_eq!(Some(1), image::sniff(&png(1, 1, 0)).unwrap().channels);
+    assert_eq!(Some(2), image::sniff(&png(1, 1, 4)).unwrap().channels);
+    assert_eq!(Some(4), image::sniff(&png(1, 1, 6)).unwrap().channels);
+
+    // Truncated headers aren't recognized.
+    assert_eq!(None, image::sniff(&png(640, 480, 2)[..20]));
+    assert_eq!(None, image::sniff(&png(640, 480, 7)));
+}
+
+#[test]
+fn sniff_jpeg() {
+    let mut bytes = b"\xff\xd8".to_vec();
+
+    // A JFIF segment, followed by fill bytes and the baseline frame header.
+    bytes.extend_from_slice(b"\xff\xe0\x00\x10JFIF\0\x01\x01\0\0\x01\0\x01\0\0");
+    bytes.extend_from_slice(b"\xff\xff\xc0\x00\x11\x08\x01\xe0\x02\x80\x03");
+    let expected = ImageInfo {
+        format: ImageFileFormat::Jpeg,
+        width: 640,
+        height: 480,
+        channels: Some(3),
+    };
+    assert_eq!(Some(expected), image::sniff(&bytes));
+    assert_eq!("image/jpeg", expected.format.mime_type());
+
+    // The image data starts before there's a frame header.
+    assert_eq!(None, image::sniff(b"\xff\xd8\xff\xda\x00\x02"));
+}
+
+#[test]
+fn sniff_dds() {
+    let expected = ImageInfo {
+        format: ImageFileFormat::Dds,
+        width: 256,
+        height: 128,
+        channels: Some(4),
+    };
+    assert_eq!(Some(expected), image::sniff(&dds(256, 128, 0x4, b"DXT5")));
+    assert_eq!(Some(3), image::sniff(&dds(1, 1, 0x4, b"DXT1")).unwrap().channels);
+    assert_eq!(None, image::sniff(&dds(1, 1, 0x4, b"DX10")).unwrap().channels);
+    assert_eq!(Some(4), image::sniff(&dds(1, 1, 0x41, b"\0\0\0\0")).unwrap().channels);
+    assert_eq!(Some(1), image::sniff(&dds(1, 1, 0x20000, b"\0\0\0\0")).unwrap().channels);
+}
+
+#[test]
+fn sniff_tga() {
+    let mut bytes = vec![0; 18];
+    bytes[2] = 2;
+    bytes[12..14].copy_from_slice(&64u16.to_le_bytes());
+    bytes[14..16].copy_from_slice(&32u16.to_le_bytes());
+    bytes[16] = 32;
+    bytes[17] = 8;
+    let expected = ImageInfo {
+        format: ImageFileFormat::Tga,
+        width: 64,
+        height: 32,
+        channels: Some(4),
+    };
+    assert_eq!(Some(expected), image::sniff(&bytes));
+
+    bytes[16] = 24;
+    bytes[17] = 0;
+    assert_eq!(Some(3), image::sniff(&bytes).unwrap().channels);
+
+    // Headers that can't be TGA aren't recognized.
+    bytes[16] = 12;
+    assert_eq!(None, image::sniff(&bytes));
+    assert_eq!(None, image::sniff(b"not an image at all"));
+}
+
+#[test]
+fn sniff_exr() {
+    let mut bytes = b"\x76\x2f\x31\x01\x02\0\0\0".to_vec();
+    let mut channels = Vec::new();
+    for name in &["A", "B", "G", "R"] {
+        channels.extend_from_slice(name.as_bytes());
+        channels.extend_from_slice(&[0; 17]);
+    }
+    channels.push(0);
+    exr_attribute(&mut bytes, "channels", "chlist", &channels);
+    exr_attribute(&mut bytes, "compression", "compression", &[0]);
+    let mut window = Vec::new();
+    for value in &[10i32, -5, 109, 44] {
+        window.extend_from_slice(&value.to_le_bytes());
+    }
+    exr_attribute(&mut bytes, "dataWindow", "box2i", &window);
+    bytes.push(0);
+
+    let expected = ImageInfo {
+        format: ImageFileFormat::Exr,
+        width: 100,
+        height: 50,
+        channels: Some(4),
+    };
+    assert_eq!(Some(expected), image::sniff(&bytes));
+
+    // The header has to be complete.
+    assert_eq!(None, image::sniff(&bytes[..bytes.len() - 1]));
+}
+
+#[test]
+fn embedded_image_info() {
+    let data = png(16, 8, 6).iter().map(|byte| format!("{:02X}", byte)).collect::<String>();
+    let document = format!(r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_images>
+            <image id="embedded">
+                <data>{}</data>
+            </image>
+            <image id="external">
+                <init_from>texture.png</init_from>
+            </image>
+        </library_images>
+    </COLLADA>
+    "#, data);
+
+    let document = Collada::from_str(&document).unwrap();
+    let library = document.libraries[0].as_library_images().unwrap();
+    let expected = ImageInfo {
+        format: ImageFileFormat::Png,
+        width: 16,
+        height: 8,
+        channels: Some(4),
+    };
+    assert_eq!(Some(expected), library.images[0].info());
+    assert_eq!(None, library.images[1].info());
+}
@@ -0,0 +1,113 @@
+extern crate collaborate;
+
+use ::collaborate::*;
+use ::collaborate::incremental::{IncrementalDocument, Reparsed};
+use ::collaborate::v1_4::Collada;
+
+static DOCUMENT: &'static str = r##"
+<?xml version="1.0" encoding="utf-8"?>
+<COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+    <asset>
+        <created>2017-02-07T20:44:30Z</created>
+        <modified>2017-02-07T20:44:30Z</modified>
+    </asset>
+    <library_geometries name="geometries">
+        <geometry id="first" name="First">
+            <mesh>
+                <source id="first-positions">
+                    <float_array id="first-array" count="3">0 1 2</float_array>
+                </source>
+                <vertices id="first-vertices">
+                    <input semantic="POSITION" source="#first-positions" />
+                </vertices>
+            </mesh>
+        </geometry>
+        <geometry id="second" name="Second">
+            <mesh>
+                <source id="second-positions">
+                    <float_array id="second-array" count="3">3 4 5</float_array>
+                </source>
+                <vertices id="second-vertices">
+                    <input semantic="POSITION" source="#second-positions" />
+                </vertices>
+            </mesh>
+        </geometry>
+    </library_geometries>
+</COLLADA>
+"##;
+
+/// Replaces the first occurrence of `from` in the document's source with `to`.
+fn edit(document: &mut IncrementalDocument, from: &str, to: &str) -> Result<Reparsed> {
+    let start = document.source().find(from).unwrap();
+    document.edit(start..start + from.len(), to)
+}
+
+fn assert_matches_source(document: &IncrementalDocument) {
+    assert_eq!(&Collada::from_str(document.source()).unwrap(), document.document());
+}
+
+#[test]
+fn edit_geometry() {
+    let mut document = IncrementalDocument::from_str(DOCUMENT).unwrap();
+
+    let reparsed = edit(&mut document, "3 4 5", "6 7 8").unwrap();
+    assert_eq!(Reparsed::Geometry { library: 0, index: 1 }, reparsed);
+    assert_matches_source(&document);
+
+    // Edits that change the length of the source shift everything after them.
+    let reparsed = edit(&mut document, r#"name="First""#, r#"name="The first geometry""#).unwrap();
+    assert_eq!(Reparsed::Geometry { library: 0, index: 0 }, reparsed);
+    let reparsed = edit(&mut document, "6 7 8", "9 10 11").unwrap();
+    assert_eq!(Reparsed::Geometry { library: 0, index: 1 }, reparsed);
+    assert_matches_source(&document);
+
+    let library = document.document().libraries[0].as_library_geometries().unwrap();
+    assert_eq!(Some("The first geometry"), library.geometries[0].name.as_deref());
+}
+
+#[test]
+fn edit_library() {
+    let mut document = IncrementalDocument::from_str(DOCUMENT).unwrap();
+
+    let reparsed = edit(&mut document, r#"name="geometries""#, r#"name="meshes""#).unwrap();
+    assert_eq!(Reparsed::Library(0), reparsed);
+    assert_matches_source(&document);
+
+    // Removing a geometry's id means geometries can't be matched up with their spans anymore.
+    let reparsed = edit(&mut document, r#"id="second" "#, "").unwrap();
+    assert_eq!(Reparsed::Document, reparsed);
+    let reparsed = edit(&mut document, "3 4 5", "6 7 8").unwrap();
+    assert_eq!(Reparsed::Library(0), reparsed);
+    assert_matches_source(&document);
+}
+
+#[test]
+fn edit_document() {
+    let mut document = IncrementalDocument::from_str(DOCUMENT).unwrap();
+
+    let reparsed = edit(&mut document, "2017-02-07T20:44:30Z</modified>", "2018-01-01T00:00:00Z</modified>").unwrap();
+    assert_eq!(Reparsed::Document, reparsed);
+    assert_matches_source(&document);
+
+    // Adding another geometry changes the structure of the library.
+    let start = document.source().find("<geometry id=\"second\"").unwrap();
+    let end = document.source().find("</library_geometries>").unwrap();
+    let third = document.source()[start..end].replace("second", "third");
+    let reparsed = document.edit(end..end, &third).unwrap();
+    assert_eq!(Reparsed::Document, reparsed);
+    assert_matches_source(&document);
+
+    let library = document.document().libraries[0].as_library_geometries().unwrap();
+    assert_eq!(3, library.geometries.len());
+}
+
+#[test]
+fn failed_edit_is_discarded() {
+    let mut document = IncrementalDocument::from_str(DOCUMENT).unwrap();
+    let before = document.clone();
+
+    let expected = Collada::from_str(&DOCUMENT.replacen("</vertices>", "</vertices><gadget />", 1)).unwrap_err();
+    assert_eq!(expected, edit(&mut document, "</vertices>", "</vertices><gadget />").unwrap_err());
+    assert_eq!(before.source(), document.source());
+    assert_eq!(before.document(), document.document());
+}
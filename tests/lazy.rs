@@ -0,0 +1,252 @@
+extern crate collaborate;
+
+use ::collaborate::{ErrorKind, ParseOptions, Strictness, TextPosition};
+use ::collaborate::lazy::LazyDocument;
+use ::collaborate::v1_4::*;
+
+static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+
+#[test]
+fn libraries_match_document() {
+    let document = Collada::read(TEST_DOCUMENT).unwrap();
+    let lazy = LazyDocument::from_reader(TEST_DOCUMENT).unwrap();
+
+    assert_eq!(&document.asset, lazy.asset());
+    assert_eq!(document.libraries, lazy.libraries().unwrap());
+
+    let expected = document.libraries()
+        .filter_map(Library::as_library_geometries)
+        .cloned()
+        .collect::<Vec<_>>();
+    assert_eq!(expected, lazy.geometries().unwrap());
+}
+
+#[test]
+fn library_names() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <!-- <library_lights /> -->
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_cameras name="a > b" />
+        <library_geometries>
+            <![CDATA[ </library_geometries> ]]>
+        </library_geometries>
+        <library_visual_scenes>
+            <!-- </library_visual_scenes> -->
+        </library_visual_scenes>
+    </COLLADA>
+    "#;
+
+    let lazy = LazyDocument::from_reader(DOCUMENT.as_bytes()).unwrap();
+    let names = lazy.library_names().collect::<Vec<_>>();
+    assert_eq!(vec!["library_cameras", "library_geometries", "library_visual_scenes"], names);
+}
+
+#[test]
+fn error_positions_match_document() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_cameras />
+        <library_geometries>
+            <gadget />
+        </library_geometries>
+    </COLLADA>
+    "#;
+
+    let lazy = LazyDocument::from_reader(DOCUMENT.as_bytes()).unwrap();
+    assert_eq!(1, lazy.libraries_named("library_cameras").unwrap().len());
+
+    let expected = Collada::from_str(DOCUMENT).unwrap_err();
+    assert_eq!(expected, lazy.geometries().unwrap_err());
+}
+
+#[test]
+fn malformed_documents() {
+    static TRUNCATED: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry id="g">
+                <mesh>"#;
+
+    static STRAY_END_TAG: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        </library_geometries></foo>
+        <library_cameras />
+    </COLLADA>
+    "#;
+
+    // The truncated document is reported at the end of the text, the same as a full parse.
+    let expected = Collada::from_str(TRUNCATED).unwrap_err();
+    let actual = LazyDocument::from_reader(TRUNCATED.as_bytes()).unwrap_err();
+    match actual.kind {
+        ErrorKind::XmlError(_) => {}
+        ref kind => panic!("Unexpected error: {:?}", kind),
+    }
+    assert_eq!(expected.position, actual.position);
+
+    // Otherwise the libraries after the stray end tag would silently go missing.
+    let actual = LazyDocument::from_reader(STRAY_END_TAG.as_bytes()).unwrap_err();
+    match actual.kind {
+        ErrorKind::XmlError(_) => {}
+        ref kind => panic!("Unexpected error: {:?}", kind),
+    }
+    assert_eq!(TextPosition { row: 7, column: 29 }, actual.position);
+}
+
+#[test]
+fn parse_options() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <gadget />
+            <geometry id="g">
+                <convex_mesh convex_hull_of="hull.dae" />
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "#;
+
+    let lazy = LazyDocument::from_reader(DOCUMENT.as_bytes()).unwrap();
+    assert!(lazy.geometries().is_err());
+
+    let options = ParseOptions {
+        strictness: Strictness::Lenient,
+        ..ParseOptions::default()
+    };
+    let lazy = LazyDocument::from_reader_with(DOCUMENT.as_bytes(), options).unwrap();
+    assert_eq!(1, lazy.geometries().unwrap().len());
+}
+
+#[test]
+fn geometry_by_id() {
+    let document = Collada::read(TEST_DOCUMENT).unwrap();
+    let lazy = LazyDocument::from_reader(TEST_DOCUMENT).unwrap();
+
+    let expected = document.libraries()
+        .filter_map(Library::as_library_geometries)
+        .flat_map(|library| library.geometries.iter())
+        .find(|geometry| geometry.id.as_deref() == Some("Cube-mesh"))
+        .cloned();
+    assert!(expected.is_some());
+    assert_eq!(expected, lazy.geometry("Cube-mesh").unwrap());
+
+    assert_eq!(None, lazy.geometry("Missing-mesh").unwrap());
+}
+
+#[test]
+fn element_ids() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_cameras>
+            <camera sid="not-an-id" name='a > b' id='camera' />
+        </library_cameras>
+        <library_geometries id="library">
+            <geometry id="first"><mesh><source id="nested" /></mesh></geometry>
+            <geometry name="anonymous" />
+            <geometry id = "second" />
+        </library_geometries>
+    </COLLADA>
+    "#;
+
+    let lazy = LazyDocument::from_reader(DOCUMENT.as_bytes()).unwrap();
+    let ids = lazy.element_ids().collect::<Vec<_>>();
+    assert_eq!(vec!["camera", "first", "second"], ids);
+
+    // The element is found by id, but it's not a geometry.
+    assert_eq!(None, lazy.geometry("camera").unwrap());
+}
+
+#[test]
+fn geometry_error_positions_match_document() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry id="broken">
+                <gadget />
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "#;
+
+    let lazy = LazyDocument::from_reader(DOCUMENT.as_bytes()).unwrap();
+    let expected = Collada::from_str(DOCUMENT).unwrap_err();
+    assert_eq!(expected, lazy.geometry("broken").unwrap_err());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_matches_sequential() {
+    use ::collaborate::VersionedDocument;
+
+    let expected = match VersionedDocument::read(TEST_DOCUMENT).unwrap() {
+        VersionedDocument::V1_4(document) => document,
+        _ => panic!("Expected a 1.4 document"),
+    };
+
+    let lazy = LazyDocument::from_reader(TEST_DOCUMENT).unwrap();
+    assert_eq!(expected, lazy.parse_parallel().unwrap());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_reports_every_error() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry />
+        </library_geometries>
+        <library_visual_scenes>
+            <gadget />
+        </library_visual_scenes>
+        <scene>
+            <gizmo />
+        </scene>
+    </COLLADA>
+    "#;
+
+    let lazy = LazyDocument::from_reader(DOCUMENT.as_bytes()).unwrap();
+    let errors = lazy.parse_parallel().unwrap_err().errors;
+    let sequential = ::collaborate::VersionedDocument::from_str(DOCUMENT).unwrap_err();
+    assert_eq!(sequential, errors[0]);
+    let rows = errors.iter().map(|error| error.position.row).collect::<Vec<_>>();
+    assert_eq!(vec![8, 11, 14], rows);
+}
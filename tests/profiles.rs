@@ -0,0 +1,131 @@
+extern crate collaborate;
+
+use ::collaborate::profiles::*;
+use ::collaborate::v1_4::{Collada, Extra};
+
+#[test]
+fn effect_texture_extensions() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_effects>
+            <effect id="Wall-effect">
+                <profile_COMMON>
+                    <technique sid="common">
+                        <lambert>
+                            <diffuse>
+                                <color>0.8 0.8 0.8 1</color>
+                            </diffuse>
+                        </lambert>
+                        <extra>
+                            <technique profile="MAX3D">
+                                <ambient_diffuse_lock>1</ambient_diffuse_lock>
+                                <ambient_diffuse_texture_lock>maybe</ambient_diffuse_texture_lock>
+                                <bump>
+                                    <texture texture="Wall-normal-sampler" texcoord="CHANNEL1" />
+                                </bump>
+                            </technique>
+                            <technique profile="FCOLLADA">
+                                <ambient>
+                                    <texture texture="Wall-occlusion-sampler" texcoord="CHANNEL2" />
+                                </ambient>
+                                <bump>
+                                    <texture texcoord="CHANNEL1" />
+                                </bump>
+                            </technique>
+                            <technique profile="GOOGLEEARTH">
+                                <double_sided>false</double_sided>
+                            </technique>
+                        </extra>
+                    </technique>
+                </profile_COMMON>
+            </effect>
+        </library_effects>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_effects().unwrap();
+    let technique = &library.effects[0].profile_common().unwrap().technique;
+    let extra = &technique.extras[0];
+
+    let expected = Max3dProfile {
+        ambient_diffuse_lock: Some(true),
+        ambient_diffuse_texture_lock: None,
+        ambient: None,
+        bump: Some(ExtensionTexture {
+            texture: "Wall-normal-sampler".into(),
+            texcoord: "CHANNEL1".into(),
+        }),
+    };
+    assert_eq!(Some(expected), extra.decode::<Max3dProfile>());
+
+    // A texture without a sampler is ignored rather than causing an error.
+    let expected = FColladaProfile {
+        ambient: Some(ExtensionTexture {
+            texture: "Wall-occlusion-sampler".into(),
+            texcoord: "CHANNEL2".into(),
+        }),
+        bump: None,
+    };
+    assert_eq!(Some(expected), extra.decode::<FColladaProfile>());
+
+    let google_earth = extra.decode::<GoogleEarthProfile>().unwrap();
+    assert_eq!(Some(false), google_earth.double_sided);
+
+    assert_eq!(None, extra.decode::<OpenColladaProfile>());
+    assert_eq!(None, extra.decode::<BlenderProfile>());
+}
+
+#[test]
+fn double_sided_geometry() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry id="Leaf-mesh">
+                <mesh>
+                    <source id="Leaf-positions">
+                        <float_array id="Leaf-positions-array" count="3">0 0 0</float_array>
+                    </source>
+                    <vertices id="Leaf-vertices">
+                        <input semantic="POSITION" source="#Leaf-positions" />
+                    </vertices>
+                </mesh>
+                <extra>
+                    <technique profile="OpenCOLLADA">
+                        <double_sided>1</double_sided>
+                    </technique>
+                </extra>
+                <extra>
+                    <technique profile="blender">
+                        <double_sided> 0 </double_sided>
+                    </technique>
+                </extra>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let geometry = document.geometry("Leaf-mesh").unwrap();
+
+    let open_collada = geometry.extras[0].decode::<OpenColladaProfile>().unwrap();
+    assert_eq!(Some(true), open_collada.double_sided);
+
+    let blender = geometry.extras[1].decode::<BlenderProfile>().unwrap();
+    assert_eq!(Some(false), blender.double_sided);
+    assert_eq!("blender", BlenderProfile::profile());
+
+    // Decoding a technique found by searching all of the extras works the same way.
+    let technique = Extra::find_technique(&geometry.extras, "blender").unwrap();
+    assert_eq!(blender, BlenderProfile::decode(&technique.elements()));
+}
@@ -0,0 +1,130 @@
+extern crate collaborate;
+extern crate xml;
+
+use ::collaborate::raw::RawElement;
+use ::collaborate::v1_4::Collada;
+use ::collaborate::writer::{WriteOptions, XmlWriter};
+use xml::name::OwnedName;
+use xml::reader::EventReader;
+
+#[test]
+fn parse_subtree() {
+    static SOURCE: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <!-- A comment before the element. -->
+    <layer name="Base" visible="true">
+        Before <![CDATA[<children>]]>
+        <color sid="tint">1 0 0</color>
+        <color>0 1 0</color>
+        After
+        <empty />
+    </layer>
+    "#;
+
+    let element = RawElement::parse(&mut EventReader::from_str(SOURCE)).unwrap();
+    assert_eq!(OwnedName::local("layer"), element.name);
+    assert_eq!(
+        vec![(OwnedName::local("name"), "Base".to_string()), (OwnedName::local("visible"), "true".to_string())],
+        element.attributes,
+    );
+    assert_eq!(Some("true"), element.attribute("visible"));
+    assert_eq!(None, element.attribute("hidden"));
+
+    let names = element.children.iter().map(|child| &*child.name.local_name).collect::<Vec<_>>();
+    assert_eq!(vec!["color", "color", "empty"], names);
+    assert_eq!(2, element.children_named("color").count());
+    assert_eq!(Some("tint"), element.child("color").unwrap().attribute("sid"));
+    assert_eq!("1 0 0", element.children[0].text);
+    assert!(element.text.contains("Before <children>"));
+    assert!(element.text.contains("After"));
+}
+
+#[test]
+fn parse_stops_after_element() {
+    let mut events = EventReader::from_str("<root><first><inner /></first><second /></root>");
+    events.next().unwrap();
+    events.next().unwrap();
+
+    let first = RawElement::parse(&mut events).unwrap();
+    assert_eq!("first", first.name.local_name);
+    assert_eq!("inner", first.children[0].name.local_name);
+
+    let second = RawElement::parse(&mut events).unwrap();
+    assert_eq!("second", second.name.local_name);
+
+    // There are no more elements inside of `<root>`.
+    assert!(RawElement::parse(&mut events).is_err());
+}
+
+#[test]
+fn prefixed_names() {
+    static SOURCE: &'static str = r#"
+    <fx:layer xmlns:fx="http://example.com/fx" xmlns:ui="http://example.com/ui" fx:name="Base" ui:hidden="false">
+        <fx:color>1 0 0</fx:color>
+    </fx:layer>
+    "#;
+
+    let element = RawElement::parse(&mut EventReader::from_str(SOURCE)).unwrap();
+    assert_eq!(Some("fx"), element.name.prefix.as_deref());
+    assert_eq!(Some("http://example.com/fx"), element.name.namespace.as_deref());
+    assert_eq!(Some("ui"), element.attributes[1].0.prefix.as_deref());
+
+    // Lookups ignore the prefix.
+    assert_eq!(Some("Base"), element.attribute("name"));
+    assert_eq!("1 0 0", element.child("color").unwrap().text);
+
+    // The names are written back as they were read.
+    let mut output = Vec::new();
+    {
+        let mut writer = XmlWriter::new(&mut output, WriteOptions::default());
+        writer.raw_element(&element).unwrap();
+        writer.finish().unwrap();
+    }
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.starts_with("<fx:layer "), "{}", output);
+    assert!(output.contains(r#"ui:hidden="false""#), "{}", output);
+    assert!(output.contains("<fx:color>1 0 0</fx:color>"), "{}", output);
+
+    let written = RawElement::parse(&mut EventReader::from_str(&output)).unwrap();
+    assert_eq!(element.name, written.name);
+    assert_eq!(element.attributes, written.attributes);
+    assert_eq!(element.children, written.children);
+}
+
+#[test]
+fn unclosed_element() {
+    let mut events = EventReader::from_str("<root><first>");
+    assert!(RawElement::parse(&mut events).is_err());
+}
+
+#[test]
+fn technique_elements() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <extra>
+            <technique profile="blender">
+                <layer name="Base">
+                    <visible>1</visible>
+                </layer>
+                <layer name="Detail" />
+            </technique>
+        </extra>
+    </COLLADA>
+    "#;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let technique = &document.extras[0].techniques[0];
+    assert_eq!("blender", technique.profile);
+
+    let elements = technique.elements();
+    assert_eq!(2, elements.len());
+    assert_eq!(Some("Base"), elements[0].attribute("name"));
+    assert_eq!("1", elements[0].child("visible").unwrap().text);
+    assert_eq!(Some("Detail"), elements[1].attribute("name"));
+    assert!(elements[1].children.is_empty());
+}
@@ -0,0 +1,51 @@
+extern crate collaborate;
+
+use ::collaborate::*;
+use ::collaborate::shared::SharedDocument;
+use ::collaborate::v1_4::Collada;
+use std::thread;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn documents_are_send_and_sync() {
+    assert_send_sync::<VersionedDocument>();
+    assert_send_sync::<v1_4::Collada>();
+    assert_send_sync::<v1_5::Collada>();
+    assert_send_sync::<SharedDocument>();
+    assert_send_sync::<shared::SharedLibrary>();
+    assert_send_sync::<lazy::LazyDocument>();
+    assert_send_sync::<ParseReport>();
+    assert_send_sync::<Error>();
+}
+
+#[test]
+fn shared_libraries() {
+    static DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+
+    let expected = Collada::read(DOCUMENT).unwrap();
+    let document = SharedDocument::new(expected.clone());
+    assert_eq!(expected.libraries.len(), document.libraries().count());
+    assert!(document.library(expected.libraries.len()).is_none());
+
+    let workers = document.geometries()
+        .map(|library| thread::spawn(move || {
+            let geometries = library.as_library_geometries().unwrap();
+            (library.index(), geometries.geometries.len())
+        }))
+        .collect::<Vec<_>>();
+    assert!(!workers.is_empty());
+
+    for worker in workers {
+        let (index, count) = worker.join().unwrap();
+        let library = expected.libraries[index].as_library_geometries().unwrap();
+        assert_eq!(library.geometries.len(), count);
+    }
+
+    // Once every other handle is gone, the document can be taken back.
+    let library = document.library(0).unwrap();
+    let document = document.try_unwrap().unwrap_err();
+    assert_eq!(expected.libraries[0], *library);
+    drop(library);
+    assert_eq!(expected, document.try_unwrap().unwrap());
+}
@@ -0,0 +1,81 @@
+extern crate collaborate;
+
+use ::collaborate::*;
+use ::collaborate::stream::DocumentReader;
+use ::collaborate::v1_4::*;
+
+static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+
+#[test]
+fn libraries_match_document() {
+    let document = Collada::read(TEST_DOCUMENT).unwrap();
+
+    let mut reader = DocumentReader::new(TEST_DOCUMENT).unwrap();
+    assert_eq!(&document.asset, reader.asset());
+
+    let mut libraries = Vec::new();
+    while let Some(library) = reader.next_library().unwrap() {
+        libraries.push(library);
+    }
+    assert_eq!(document.libraries, libraries);
+
+    // The reader stays exhausted.
+    assert_eq!(None, reader.next_library().unwrap());
+}
+
+#[test]
+fn geometries_match_document() {
+    let document = Collada::read(TEST_DOCUMENT).unwrap();
+    let expected = document.libraries()
+        .filter_map(Library::as_library_geometries)
+        .flat_map(|library| library.geometries.iter().cloned())
+        .collect::<Vec<_>>();
+
+    let geometries = DocumentReader::new(TEST_DOCUMENT).unwrap()
+        .into_geometries()
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(expected, geometries);
+}
+
+#[test]
+fn unsupported_version() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.5.0">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+    </COLLADA>
+    "#;
+
+    let error = DocumentReader::new(DOCUMENT.as_bytes()).err().unwrap();
+    assert_eq!(ErrorKind::UnsupportedVersion { version: "1.5.0".into() }, error.kind);
+}
+
+#[test]
+fn unexpected_element_stops_iteration() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_cameras />
+        <gadget />
+        <library_cameras />
+    </COLLADA>
+    "#;
+
+    let mut geometries = DocumentReader::new(DOCUMENT.as_bytes()).unwrap().into_geometries();
+    match geometries.next() {
+        Some(Err(Error { kind: ErrorKind::UnexpectedElement { ref element, .. }, .. })) => {
+            assert_eq!("gadget", element);
+        }
+
+        result => panic!("Expected an unexpected element error, got {:?}", result),
+    }
+    assert!(geometries.next().is_none());
+}
@@ -3,6 +3,8 @@ extern crate collaborate;
 use ::collaborate::*;
 use ::collaborate::common::*;
 use ::collaborate::v1_4::*;
+use ::collaborate::writer::WriteOptions;
+use std::collections::HashMap;
 
 #[test]
 fn blender_cube() {
@@ -31,17 +33,16 @@ fn collada_asset_minimal() {
         base_uri: None,
         asset: Asset {
             contributors: vec![],
+            coverage: None,
             created: "2017-02-07T20:44:30Z".parse().unwrap(),
-            keywords: None,
+            keywords: MaybeEmpty::Absent,
             modified: "2017-02-07T20:44:30Z".parse().unwrap(),
-            revision: None,
-            subject: None,
-            title: None,
-            unit: Unit {
-                meter: 1.0,
-                name: "meter".into(),
-            },
-            up_axis: UpAxis::Y,
+            revision: MaybeEmpty::Absent,
+            subject: MaybeEmpty::Absent,
+            title: MaybeEmpty::Absent,
+            unit: None,
+            up_axis: None,
+            extras: Vec::new(),
         },
         libraries: Vec::new(),
         scene: None,
@@ -50,6 +51,7 @@ fn collada_asset_minimal() {
 
     let actual = Collada::from_str(DOCUMENT).unwrap();
     assert_eq!(expected, actual);
+    assert_eq!(CoordinateSystem::default(), actual.coordinate_system());
 }
 
 #[test]
@@ -95,17 +97,19 @@ fn asset_full() {
 
     let expected = Asset {
         contributors: vec![Contributor::default(), Contributor::default(), Contributor::default()],
+        coverage: None,
         created: "2017-02-07T20:44:30Z".parse().unwrap(),
-        keywords: Some("foo bar baz".into()),
+        keywords: MaybeEmpty::Value("foo bar baz".into()),
         modified: "2017-02-07T20:44:30Z".parse().unwrap(),
-        revision: Some("7".into()),
-        subject: Some("A thing".into()),
-        title: Some("Model of a thing".into()),
-        unit: Unit {
+        revision: MaybeEmpty::Value("7".into()),
+        subject: MaybeEmpty::Value("A thing".into()),
+        title: MaybeEmpty::Value("Model of a thing".into()),
+        unit: Some(Unit {
             meter: 7.0,
             name: "septimeter".into(),
-        },
-        up_axis: UpAxis::Z,
+        }),
+        up_axis: Some(UpAxis::Z),
+        extras: Vec::new(),
     };
 
     let collada = Collada::from_str(DOCUMENT).unwrap();
@@ -133,28 +137,64 @@ fn asset_blender() {
     let expected = Asset {
         contributors: vec![
             Contributor {
-                author: Some("Blender User".into()),
-                authoring_tool: Some("Blender 2.78.0 commit date:2016-10-24, commit time:12:20, hash:e8299c8".into()),
+                author: MaybeEmpty::Value("Blender User".into()),
+                authoring_tool: MaybeEmpty::Value("Blender 2.78.0 commit date:2016-10-24, commit time:12:20, hash:e8299c8".into()),
                 .. Contributor::default()
             },
         ],
+        coverage: None,
         created: "2017-02-01T09:29:54".parse().unwrap(),
-        keywords: None,
+        keywords: MaybeEmpty::Absent,
         modified: "2017-02-01T09:29:54".parse().unwrap(),
-        revision: None,
-        subject: None,
-        title: None,
-        unit: Unit {
+        revision: MaybeEmpty::Absent,
+        subject: MaybeEmpty::Absent,
+        title: MaybeEmpty::Absent,
+        unit: Some(Unit {
             meter: 1.0,
             name: "meter".into(),
-        },
-        up_axis: UpAxis::Z,
+        }),
+        up_axis: Some(UpAxis::Z),
+        extras: Vec::new(),
     };
 
     let collada = Collada::from_str(DOCUMENT).unwrap();
     assert_eq!(expected, collada.asset);
 }
 
+#[test]
+fn asset_empty_text() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <contributor>
+                <author />
+                <comments></comments>
+                <copyright>Public domain</copyright>
+            </contributor>
+            <created>2017-02-07T20:44:30Z</created>
+            <keywords />
+            <modified>2017-02-07T20:44:30Z</modified>
+            <title></title>
+        </asset>
+    </COLLADA>
+    "#;
+
+    let collada = Collada::from_str(DOCUMENT).unwrap();
+    let asset = &collada.asset;
+    assert_eq!(MaybeEmpty::Empty, asset.keywords);
+    assert_eq!(MaybeEmpty::Absent, asset.revision);
+    assert_eq!(MaybeEmpty::Absent, asset.subject);
+    assert_eq!(MaybeEmpty::Empty, asset.title);
+    assert_eq!(0, asset.keywords().count());
+
+    let contributor = &asset.contributors[0];
+    assert_eq!(MaybeEmpty::Empty, contributor.author);
+    assert_eq!(MaybeEmpty::Absent, contributor.authoring_tool);
+    assert_eq!(MaybeEmpty::Empty, contributor.comments);
+    assert_eq!(Some("Public domain"), contributor.copyright.as_deref());
+}
+
 #[test]
 fn asset_wrong_version() {
     static DOCUMENT: &'static str = r#"
@@ -185,10 +225,148 @@ fn asset_wrong_version() {
 
     let expected = Error {
         position: TextPosition { row: 7, column: 12 },
-        kind: ErrorKind::UnexpectedElement {
-            parent: "asset",
-            element: "coverage".into(),
-            expected: vec!["contributor", "created", "keywords", "modified", "revision", "subject", "title", "unit", "up_axis"],
+        kind: ErrorKind::VersionMismatch {
+            element: "asset",
+            member: "coverage".into(),
+            valid_in: "1.5",
+            version: Version::V1_4_1,
+        },
+    };
+
+    let actual = Collada::from_str(DOCUMENT).unwrap_err();
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn library_wrong_version() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_kinematics_models />
+        <library_gadgets />
+    </COLLADA>
+    "#;
+
+    let expected = Error {
+        position: TextPosition { row: 7, column: 8 },
+        kind: ErrorKind::VersionMismatch {
+            element: "COLLADA",
+            member: "library_kinematics_models".into(),
+            valid_in: "1.5",
+            version: Version::V1_4_1,
+        },
+    };
+
+    let actual = Collada::from_str(DOCUMENT).unwrap_err();
+    assert_eq!(expected, actual);
+
+    // Elements from the wrong version are skipped in lenient mode, the same as any other
+    // unexpected element.
+    let options = ParseOptions {
+        strictness: Strictness::Lenient,
+        ..ParseOptions::default()
+    };
+    let (_, report) = Collada::read_with(DOCUMENT.as_bytes(), options).unwrap();
+    assert_eq!(2, report.warnings.len());
+    assert_eq!(expected, report.warnings[0]);
+    match report.warnings[1].kind {
+        ErrorKind::UnexpectedElement { ref element, .. } => assert_eq!("library_gadgets", element),
+        ref kind => panic!("Unexpected error: {:?}", kind),
+    }
+}
+
+#[test]
+fn permissive_wrong_version() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <contributor>
+                <author_email>someone@example.com</author_email>
+            </contributor>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_kinematics_models />
+    </COLLADA>
+    "#;
+
+    // Elements from the wrong version are still reported, but they're kept in the document.
+    let options = ParseOptions {
+        strictness: Strictness::Permissive,
+        ..ParseOptions::default()
+    };
+    let (collada, report) = Collada::read_with(DOCUMENT.as_bytes(), options).unwrap();
+    assert_eq!(2, report.warnings.len());
+    assert_eq!(
+        MaybeEmpty::Value("someone@example.com".into()),
+        collada.asset.contributors[0].author_email,
+    );
+    match collada.libraries[..] {
+        [Library::KinematicsModels(_)] => {}
+        ref libraries => panic!("Unexpected libraries: {:?}", libraries),
+    }
+
+    // They can't be written back out as the document's version.
+    let options = WriteOptions {
+        version: Some(Version::V1_4_1),
+        ..WriteOptions::default()
+    };
+    let error = collada.write(Vec::new(), options).unwrap_err();
+    assert_eq!(
+        "<COLLADA/asset/contributor> has \"author_email\" which is only allowed in COLLADA 1.5, but the document is being written as version 1.4.1",
+        error.to_string(),
+    );
+}
+
+#[test]
+fn missing_element_current_version() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry />
+        </library_geometries>
+    </COLLADA>
+    "#;
+
+    // `<brep>` is only allowed in COLLADA 1.5, so it isn't one of the expected elements.
+    let expected = Error {
+        position: TextPosition { row: 8, column: 12 },
+        kind: ErrorKind::MissingElement {
+            parent: "geometry",
+            expected: vec!["convex_mesh", "mesh", "spline"],
+        },
+    };
+    assert_eq!(expected, Collada::from_str(DOCUMENT).unwrap_err());
+}
+
+#[test]
+fn asset_invalid_up_axis() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+            <up_axis>W_UP</up_axis>
+        </asset>
+    </COLLADA>
+    "#;
+
+    let expected = Error {
+        position: TextPosition { row: 6, column: 21 },
+        kind: ErrorKind::InvalidValue {
+            element: "up_axis",
+            value: "W_UP".into(),
         },
     };
 
@@ -196,6 +374,168 @@ fn asset_wrong_version() {
     assert_eq!(expected, actual);
 }
 
+#[test]
+fn asset_unit_defaults() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <keywords>  cube
+                example </keywords>
+            <modified>2017-02-07T20:44:30Z</modified>
+            <unit meter="0.01" />
+            <up_axis>Z_UP</up_axis>
+        </asset>
+    </COLLADA>
+    "#;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let asset = &document.asset;
+    assert_eq!(Unit { meter: 0.01, name: "meter".into() }, asset.unit());
+    assert_eq!(UpAxis::Z, asset.up_axis());
+    assert_eq!(vec!["cube", "example"], asset.keywords().collect::<Vec<_>>());
+
+    let no_meter = DOCUMENT.replace(r#"<unit meter="0.01" />"#, r#"<unit name="inch" />"#);
+    let document = Collada::from_str(&no_meter).unwrap();
+    assert_eq!(Unit { meter: 1.0, name: "inch".into() }, document.asset.unit());
+}
+
+#[test]
+fn unit_and_up_axis_conversion() {
+    let inch = Unit { meter: 0.0254, name: "inch".into() };
+    let meter = Unit { meter: 1.0, name: "meter".into() };
+    assert!((inch.to_meters(100.0) - 2.54).abs() < 1e-9);
+    assert!((meter.scale_to(&inch) - 1.0 / 0.0254).abs() < 1e-9);
+    assert_eq!(1.0, inch.scale_to(&inch));
+
+    assert_eq!("Z_UP", UpAxis::Z.as_str());
+    assert_eq!("X_UP", UpAxis::X.to_string());
+
+    let transform = |matrix: [f32; 16], vector: [f32; 3]| {
+        let mut result = [0.0; 3];
+        for row in 0..3 {
+            result[row] = (0..3).map(|column| matrix[row * 4 + column] * vector[column]).sum();
+        }
+        result
+    };
+
+    // Every conversion maps the source up vector onto the target up vector.
+    let axes = [UpAxis::X, UpAxis::Y, UpAxis::Z];
+    for &from in &axes {
+        for &to in &axes {
+            let matrix = from.rotation_to(to);
+            assert_eq!(to.up_vector(), transform(matrix, from.up_vector()), "{:?} to {:?}", from, to);
+            assert_eq!([0.0, 0.0, 0.0, 1.0], [matrix[12], matrix[13], matrix[14], matrix[15]]);
+        }
+    }
+
+    // Converting back undoes the conversion.
+    let point = [1.0, 2.0, 3.0];
+    assert_eq!([2.0, -1.0, 3.0], transform(UpAxis::Y.rotation_to(UpAxis::X), point));
+    assert_eq!(point, transform(UpAxis::X.rotation_to(UpAxis::Y), [2.0, -1.0, 3.0]));
+    assert_eq!([1.0, -3.0, 2.0], transform(UpAxis::Y.rotation_to(UpAxis::Z), point));
+    assert_eq!(UpAxis::Y.rotation_to(UpAxis::Y), UpAxis::Z.rotation_to(UpAxis::Z));
+}
+
+#[test]
+fn per_element_asset() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+            <unit meter="0.01" name="centimeter" />
+            <up_axis>Z_UP</up_axis>
+        </asset>
+        <library_geometries>
+            <asset>
+                <created>2017-02-07T20:44:30Z</created>
+                <modified>2017-02-07T20:44:30Z</modified>
+                <unit meter="1" name="meter" />
+            </asset>
+            <geometry id="Imported">
+                <asset>
+                    <created>2017-02-07T20:44:30Z</created>
+                    <modified>2017-02-07T20:44:30Z</modified>
+                    <up_axis>Y_UP</up_axis>
+                </asset>
+                <mesh>
+                    <source id="positions">
+                        <float_array id="positions-array" count="3">0 1 0</float_array>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#positions" />
+                    </vertices>
+                </mesh>
+            </geometry>
+            <geometry id="Local">
+                <mesh>
+                    <source id="local-positions">
+                        <float_array id="local-positions-array" count="3">0 0 1</float_array>
+                    </source>
+                    <vertices id="local-vertices">
+                        <input semantic="POSITION" source="#local-positions" />
+                    </vertices>
+                </mesh>
+            </geometry>
+        </library_geometries>
+        <library_visual_scenes>
+            <visual_scene id="Scene">
+                <node id="Root">
+                    <node id="Child">
+                        <asset>
+                            <created>2017-02-07T20:44:30Z</created>
+                            <modified>2017-02-07T20:44:30Z</modified>
+                            <unit meter="0.0254" name="inch" />
+                        </asset>
+                        <node id="Grandchild" />
+                    </node>
+                </node>
+            </visual_scene>
+        </library_visual_scenes>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let centimeter = Unit { meter: 0.01, name: "centimeter".into() };
+    let meter = Unit { meter: 1.0, name: "meter".into() };
+    let inch = Unit { meter: 0.0254, name: "inch".into() };
+    assert_eq!(CoordinateSystem { unit: centimeter.clone(), up_axis: UpAxis::Z }, document.coordinate_system());
+
+    let library = &document.libraries[0];
+    assert!(library.asset().unwrap().up_axis.is_none());
+    assert_eq!(
+        CoordinateSystem { unit: meter.clone(), up_axis: UpAxis::Z },
+        document.library_coordinate_system(library),
+    );
+
+    let imported = document.geometry("Imported").unwrap();
+    let imported_system = document.geometry_coordinate_system(imported).unwrap();
+    assert_eq!(CoordinateSystem { unit: meter.clone(), up_axis: UpAxis::Y }, imported_system);
+    assert_eq!(
+        CoordinateSystem { unit: meter.clone(), up_axis: UpAxis::Z },
+        document.geometry_coordinate_system(document.geometry("Local").unwrap()).unwrap(),
+    );
+
+    // A geometry that isn't part of the document has no coordinate system.
+    assert_eq!(None, document.geometry_coordinate_system(&imported.clone()));
+
+    assert_eq!(
+        CoordinateSystem { unit: centimeter.clone(), up_axis: UpAxis::Z },
+        document.node_coordinate_system(document.node("Root").unwrap()).unwrap(),
+    );
+    let grandchild = document.node_coordinate_system(document.node("Grandchild").unwrap()).unwrap();
+    assert_eq!(CoordinateSystem { unit: inch, up_axis: UpAxis::Z }, grandchild);
+
+    // Converting the imported geometry into the document's coordinate system scales meters to
+    // centimeters and turns Y-up into Z-up.
+    let matrix = imported_system.conversion_to(&document.coordinate_system());
+    let up = [matrix[1], matrix[5], matrix[9]];
+    assert_eq!([0.0, 0.0, 100.0], up);
+}
+
 #[test]
 fn contributor_minimal() {
     static DOCUMENT: &'static str = r#"
@@ -233,10 +573,12 @@ fn contributor_full() {
     "#;
 
     let expected = Contributor {
-        author: Some("David LeGare".into()),
-        authoring_tool: Some("Atom".into()),
-        comments: Some("This is a sample COLLADA document.".into()),
-        copyright: Some("David LeGare, free for public use".into()),
+        author: MaybeEmpty::Value("David LeGare".into()),
+        author_email: MaybeEmpty::Absent,
+        author_website: None,
+        authoring_tool: MaybeEmpty::Value("Atom".into()),
+        comments: MaybeEmpty::Value("This is a sample COLLADA document.".into()),
+        copyright: MaybeEmpty::Value("David LeGare, free for public use".into()),
         source_data: Some("C:/models/tank.s3d".parse().unwrap()),
     };
 
@@ -331,10 +673,11 @@ fn contributor_wrong_version() {
 
     let expected = Error {
         position: TextPosition { row: 6, column: 16 },
-        kind: ErrorKind::UnexpectedElement {
-            parent: "contributor".into(),
-            element: "author_email".into(),
-            expected: vec!["author", "authoring_tool", "comments", "copyright", "source_data"],
+        kind: ErrorKind::VersionMismatch {
+            element: "contributor",
+            member: "author_email".into(),
+            valid_in: "1.5",
+            version: Version::V1_4_1,
         },
     };
 
@@ -484,3 +827,2955 @@ fn polylist_iter() {
 
     assert!(polygons.next().is_none());
 }
+
+#[test]
+fn mesh_interleaved_arrays() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry>
+                <mesh>
+                    <source id="positions">
+                        <float_array id="interleaved" count="12">0 0 0 0 0 1 1 1 1 0 0 1</float_array>
+                        <technique_common>
+                            <accessor source="#interleaved" count="2" stride="6">
+                                <param name="X" type="float" />
+                                <param name="Y" type="float" />
+                                <param name="Z" type="float" />
+                            </accessor>
+                        </technique_common>
+                    </source>
+                    <source id="normals">
+                        <technique_common>
+                            <accessor source="#interleaved" count="2" offset="3" stride="6">
+                                <param name="X" type="float" />
+                                <param name="Y" type="float" />
+                                <param name="Z" type="float" />
+                            </accessor>
+                        </technique_common>
+                    </source>
+                    <source id="uvs">
+                        <float_array id="uvs-array" count="4">0 0 1 1</float_array>
+                        <technique_common>
+                            <accessor source="#uvs-array" count="2" stride="2">
+                                <param name="S" type="float" />
+                                <param name="T" type="float" />
+                            </accessor>
+                        </technique_common>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#positions" />
+                    </vertices>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    let layouts = mesh.array_layouts();
+    assert_eq!(2, layouts.len());
+
+    let interleaved = &layouts[0];
+    assert_eq!("#interleaved", interleaved.array_uri.as_str());
+    assert_eq!(Some("interleaved"), interleaved.array.and_then(Array::id));
+    assert!(interleaved.is_shared());
+    assert_eq!(Some(6), interleaved.interleaved_stride());
+    assert_eq!("positions", interleaved.views[0].source.id);
+    assert_eq!("normals", interleaved.views[1].source.id);
+
+    let uvs = &layouts[1];
+    assert_eq!(Some("uvs-array"), uvs.array.and_then(Array::id));
+    assert!(!uvs.is_shared());
+    assert_eq!(None, uvs.interleaved_stride());
+}
+
+#[test]
+fn mesh_primitive_types() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry>
+                <mesh>
+                    <source id="positions">
+                        <float_array id="positions-array" count="3">0 1 2</float_array>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#positions" />
+                    </vertices>
+                    <lines count="2" material="Wire">
+                        <input semantic="VERTEX" source="#vertices" offset="0" />
+                        <p>0 1 1 2</p>
+                    </lines>
+                    <linestrips count="2">
+                        <input semantic="VERTEX" source="#vertices" offset="0" />
+                        <p>0 1 2</p>
+                        <p>2 0</p>
+                    </linestrips>
+                    <polygons count="2" material="Surface">
+                        <input semantic="VERTEX" source="#vertices" offset="0" />
+                        <input semantic="TEXCOORD" source="#uvs" offset="1" set="1" />
+                        <ph>
+                            <p>0 0 1 1 2 2 3 3</p>
+                            <h>4 4 5 5 6 6</h>
+                        </ph>
+                        <p>0 0 1 1 2 2</p>
+                    </polygons>
+                    <triangles count="2">
+                        <input semantic="VERTEX" source="#vertices" offset="0" />
+                        <input semantic="NORMAL" source="#normals" offset="1" />
+                        <p>0 0 1 0 2 0 2 1 1 1 0 1</p>
+                    </triangles>
+                    <trifans count="1">
+                        <input semantic="VERTEX" source="#vertices" offset="0" />
+                        <p>0 1 2 3</p>
+                    </trifans>
+                    <tristrips count="1">
+                        <input semantic="VERTEX" source="#vertices" offset="0" />
+                        <p>0 1 2 3 4</p>
+                    </tristrips>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    fn indices(polygon: Polygon) -> Vec<usize> {
+        polygon.iter().flat_map(|vertex| vertex.iter()).map(|attribute| attribute.index).collect()
+    }
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    let materials = mesh.primitives().map(Primitive::material).collect::<Vec<_>>();
+    assert_eq!(vec![Some("Wire"), None, Some("Surface"), None, None, None], materials);
+
+    let lines = mesh.primitives[0].as_lines().unwrap();
+    assert_eq!(2, lines.len());
+    assert_eq!(vec![vec![0, 1], vec![1, 2]], lines.iter().map(indices).collect::<Vec<_>>());
+
+    let linestrips = mesh.primitives[1].as_linestrips().unwrap();
+    assert_eq!(vec![vec![0, 1, 2], vec![2, 0]], linestrips.iter().map(indices).collect::<Vec<_>>());
+
+    // Polygons with holes keep their place among the other polygons.
+    let polygons = mesh.primitives[2].as_polygons().unwrap();
+    assert_eq!(Some(1), mesh.primitives[2].inputs()[1].set);
+    assert_eq!(vec![4, 3], polygons.iter().map(|polygon| polygon.len()).collect::<Vec<_>>());
+    assert_eq!(1, polygons.polygons[0].holes().len());
+    assert_eq!([4, 4, 5, 5, 6, 6], *polygons.polygons[0].holes()[0]);
+    assert!(polygons.polygons[1].holes().is_empty());
+    assert_eq!(1, polygons.inputs_for_offset(1).count());
+
+    // Each vertex of a triangle has one index for each offset.
+    let triangles = mesh.primitives[3].as_triangles().unwrap();
+    let triangles = triangles.iter().map(indices).collect::<Vec<_>>();
+    assert_eq!(vec![vec![0, 0, 1, 0, 2, 0], vec![2, 1, 1, 1, 0, 1]], triangles);
+
+    let trifans = mesh.primitives[4].as_trifans().unwrap();
+    assert_eq!(vec![vec![0, 1, 2, 3]], trifans.iter().map(indices).collect::<Vec<_>>());
+
+    let tristrips = mesh.primitives[5].as_tristrips().unwrap();
+    assert_eq!(vec![5], tristrips.iter().map(|strip| strip.len()).collect::<Vec<_>>());
+    assert_eq!(None, mesh.primitives[5].as_triangles());
+
+    // A polygon with holes needs at least one hole.
+    match Collada::from_str(&DOCUMENT.replace("<h>4 4 5 5 6 6</h>", "")) {
+        Err(Error { kind: ErrorKind::MissingRequiredChild { parent: "ph", ref expected, .. }, .. }) => {
+            assert_eq!(vec!["h"], *expected);
+        }
+        result => panic!("Expected a missing element error, got {:?}", result.map(|_| ())),
+    }
+}
+
+#[test]
+fn line_segments() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry id="Annotation">
+                <mesh>
+                    <source id="positions">
+                        <float_array id="positions-array" count="3">0 1 2</float_array>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#positions" />
+                    </vertices>
+                    <lines count="2">
+                        <input semantic="VERTEX" source="#vertices" offset="0" />
+                        <input semantic="COLOR" source="#colors" offset="1" />
+                        <p>0 5 1 5 2 6 3 6</p>
+                    </lines>
+                    <linestrips count="3">
+                        <input semantic="VERTEX" source="#vertices" offset="0" />
+                        <p>0 1 2 3</p>
+                        <p>7</p>
+                        <p>4 5</p>
+                    </linestrips>
+                    <lines count="0">
+                        <input semantic="VERTEX" source="#vertices" offset="0" />
+                    </lines>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let mesh = document.geometry("Annotation").unwrap().geometric_element.as_mesh().unwrap();
+
+    let lines = mesh.primitives[0].as_lines().unwrap();
+    let segments = lines.segments().collect::<Vec<_>>();
+    assert_eq!(2, segments.len());
+    assert_eq!(Some([0, 1]), segments[0].indices(0));
+    assert_eq!(Some([6, 6]), segments[1].indices(1));
+    assert_eq!(None, segments[1].indices(2));
+    assert_eq!(Some(2), segments[1].start.index(0));
+    assert_eq!(
+        vec![0, 1, 2, 3],
+        lines.vertices().filter_map(|vertex| vertex.index(0)).collect::<Vec<_>>(),
+    );
+
+    // Strips are expanded into their segments, and a strip with a single vertex has none.
+    let linestrips = mesh.primitives[1].as_linestrips().unwrap();
+    let pairs = linestrips.segments().filter_map(|segment| segment.indices(0)).collect::<Vec<_>>();
+    assert_eq!(vec![[0, 1], [1, 2], [2, 3], [4, 5]], pairs);
+    assert_eq!(7, linestrips.vertices().count());
+
+    let empty = mesh.primitives[2].as_lines().unwrap();
+    assert_eq!(0, empty.segments().count());
+    assert_eq!(0, empty.vertices().count());
+}
+
+#[test]
+fn vertex_level_inputs() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry id="Quad">
+                <mesh>
+                    <source id="positions">
+                        <float_array id="positions-array" count="12">0 0 0 1 0 0 1 1 0 0 1 0</float_array>
+                        <technique_common>
+                            <accessor source="#positions-array" count="4" stride="3">
+                                <param name="X" type="float" />
+                                <param name="Y" type="float" />
+                                <param name="Z" type="float" />
+                            </accessor>
+                        </technique_common>
+                    </source>
+                    <source id="normals">
+                        <float_array id="normals-array" count="3">0 0 1</float_array>
+                        <technique_common>
+                            <accessor source="#normals-array" count="1" stride="3">
+                                <param name="X" type="float" />
+                                <param name="Y" type="float" />
+                                <param name="Z" type="float" />
+                            </accessor>
+                        </technique_common>
+                    </source>
+                    <source id="colors">
+                        <float_array id="colors-array" count="8">1 0 0 1 0 0 1 1</float_array>
+                        <technique_common>
+                            <accessor source="#colors-array" count="4" stride="2">
+                                <param name="R" type="float" />
+                                <param name="G" type="float" />
+                            </accessor>
+                        </technique_common>
+                    </source>
+                    <source id="flat-normals">
+                        <float_array id="flat-normals-array" count="3">0 0 -1</float_array>
+                        <technique_common>
+                            <accessor source="#flat-normals-array" count="1" stride="3" />
+                        </technique_common>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#positions" />
+                        <input semantic="COLOR" source="#colors" />
+                    </vertices>
+                    <triangles count="2">
+                        <input semantic="VERTEX" source="#vertices" offset="0" />
+                        <input semantic="NORMAL" source="#normals" offset="1" />
+                        <p>0 0 1 0 2 0 2 0 3 0 0 0</p>
+                    </triangles>
+                    <lines count="1">
+                        <input semantic="NORMAL" source="#flat-normals" offset="1" />
+                        <input semantic="VERTEX" source="#vertices" offset="0" />
+                        <input semantic="COLOR" source="#normals" offset="1" />
+                        <p>1 0 3 0</p>
+                    </lines>
+                    <lines count="1">
+                        <input semantic="VERTEX" source="#vertices" offset="0" />
+                        <p>0 4</p>
+                    </lines>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let mesh = document.geometry("Quad").unwrap().geometric_element.as_mesh().unwrap();
+    assert_eq!("colors", mesh.vertices.input("COLOR").unwrap().source.id());
+    assert_eq!(None, mesh.vertices.input("NORMAL"));
+
+    // The vertex-level inputs take the place of the `VERTEX` input.
+    let triangles = &mesh.primitives[0];
+    let inputs = mesh.resolved_inputs(triangles);
+    let semantics = inputs.iter().map(|input| input.semantic.clone()).collect::<Vec<_>>();
+    assert_eq!(vec![Semantic::Position, Semantic::Color(None), Semantic::Normal], semantics);
+    assert_eq!(vec![0, 0, 1], inputs.iter().map(|input| input.offset).collect::<Vec<_>>());
+    assert_eq!(vec![true, true, false], inputs.iter().map(|input| input.per_vertex).collect::<Vec<_>>());
+
+    assert_eq!(6, triangles.vertices().count());
+    let streams = mesh.vertex_streams(triangles).unwrap();
+    assert_eq!(3, streams.len());
+    assert_eq!(Some(&[1.0, 1.0, 0.0][..]), streams[0].get(2));
+    assert_eq!(2, streams[1].width);
+    assert_eq!(Some(&[0.0, 0.0][..]), streams[1].get(3));
+    assert_eq!([0.0, 0.0, 1.0].repeat(6), streams[2].values);
+    assert_eq!(None, streams[2].get(6));
+
+    // An input declared by the primitive replaces the vertex-level input with the same semantic.
+    let lines = &mesh.primitives[1];
+    let inputs = mesh.resolved_inputs(lines);
+    let semantics = inputs.iter().map(|input| input.semantic.clone()).collect::<Vec<_>>();
+    assert_eq!(vec![Semantic::Normal, Semantic::Position, Semantic::Color(None)], semantics);
+    assert!(!inputs[2].per_vertex);
+    let streams = mesh.vertex_streams(lines).unwrap();
+    assert_eq!(vec![0.0, 0.0, -1.0, 0.0, 0.0, -1.0], streams[0].values);
+    assert_eq!(vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0], streams[1].values);
+    assert_eq!(3, streams[2].width);
+
+    // Indices past the end of a source can't be read.
+    assert_eq!(None, mesh.vertex_streams(&mesh.primitives[2]));
+}
+
+#[test]
+fn input_semantics() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry>
+                <mesh>
+                    <source id="positions">
+                        <float_array id="positions-array" count="3">0 1 2</float_array>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#positions" />
+                        <input semantic="TEXTANGENT" source="#tangents" />
+                    </vertices>
+                    <triangles count="1">
+                        <input semantic="VERTEX" source="#vertices" offset="0" />
+                        <input semantic="TEXCOORD" source="#uvs" offset="1" set="0" />
+                        <input semantic="TEXCOORD1" source="#lightmap-uvs" offset="1" />
+                        <input semantic="COLOR" source="#colors" offset="1" set="2" />
+                        <input semantic="BAKED_AO" source="#occlusion" offset="1" />
+                        <p>0 0 1 1 2 2</p>
+                    </triangles>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    assert_eq!(Semantic::Position, mesh.vertices.inputs[0].semantic);
+    assert_eq!(Semantic::TexTangent, mesh.vertices.inputs[1].semantic);
+
+    let inputs = mesh.primitives[0].inputs();
+    let semantics = inputs.iter().map(|input| input.semantic.clone()).collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            Semantic::Vertex,
+            Semantic::TexCoord(None),
+            Semantic::TexCoord(Some(1)),
+            Semantic::Color(None),
+            Semantic::Other("BAKED_AO".into()),
+        ],
+        semantics,
+    );
+
+    // The set can be given either by the `set` attribute or as part of the semantic.
+    let sets = inputs.iter().map(SharedInput::set_index).collect::<Vec<_>>();
+    assert_eq!(vec![None, Some(0), Some(1), Some(2), None], sets);
+
+    // Semantics compare against the text as it's written in the document.
+    assert!(inputs[2].semantic == "TEXCOORD1");
+    assert_eq!("TEXCOORD1", inputs[2].semantic.to_string());
+    assert_eq!("TEXCOORD", inputs[2].semantic.name());
+    assert_eq!("BAKED_AO", inputs[4].semantic.to_string());
+    assert_eq!(Some(&inputs[1]), inputs.iter().find(|input| input.semantic == "TEXCOORD"));
+
+    // Numbers that wouldn't be written back out the same way aren't treated as sets.
+    assert_eq!(Semantic::Other("COLOR01".into()), Semantic::from("COLOR01"));
+    assert_eq!(Semantic::Other("TEXCOORD-1".into()), Semantic::from("TEXCOORD-1"));
+}
+
+#[test]
+fn convex_mesh() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry id="Rock-mesh">
+                <mesh>
+                    <source id="Rock-positions">
+                        <float_array id="Rock-positions-array" count="3">0 0 0</float_array>
+                    </source>
+                    <vertices id="Rock-vertices">
+                        <input semantic="POSITION" source="#Rock-positions" />
+                    </vertices>
+                </mesh>
+            </geometry>
+            <geometry id="Rock-hull">
+                <convex_mesh convex_hull_of="#Rock-mesh" />
+            </geometry>
+            <geometry id="Wedge-hull">
+                <convex_mesh>
+                    <source id="Wedge-positions">
+                        <float_array id="Wedge-positions-array" count="9">0 0 0 1 0 0 0 1 0</float_array>
+                    </source>
+                    <vertices id="Wedge-vertices">
+                        <input semantic="POSITION" source="#Wedge-positions" />
+                    </vertices>
+                    <triangles count="1">
+                        <input semantic="VERTEX" source="#Wedge-vertices" offset="0" />
+                        <p>0 1 2</p>
+                    </triangles>
+                </convex_mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+
+    // The hull of another geometry only names it.
+    let rock = document.geometry("Rock-hull").unwrap().geometric_element.as_convex_mesh().unwrap();
+    assert_eq!(Some("#Rock-mesh"), rock.convex_hull_of.as_ref().map(AnyUri::as_str));
+    assert!(rock.sources.is_empty());
+    assert_eq!(None, rock.vertices);
+    let hull_of = document.convex_hull_of(rock).unwrap();
+    assert_eq!(Some("Rock-mesh"), hull_of.id.as_deref());
+    assert!(hull_of.geometric_element.as_mesh().is_some());
+
+    // A hull can also be described in full, like a regular mesh.
+    let wedge = document.geometry("Wedge-hull").unwrap().geometric_element.as_convex_mesh().unwrap();
+    assert_eq!(None, wedge.convex_hull_of);
+    assert_eq!(None, document.convex_hull_of(wedge));
+    assert!(wedge.find_source("Wedge-positions").is_some());
+    assert_eq!("Wedge-vertices", wedge.vertices.as_ref().unwrap().id);
+    let triangles = wedge.primitives().next().and_then(Primitive::as_triangles).unwrap();
+    assert_eq!(1, triangles.len());
+
+    // The mesh data still has to be in mesh order.
+    let invalid = DOCUMENT.replace(
+        r#"<float_array id="Wedge-positions-array" count="9">0 0 0 1 0 0 0 1 0</float_array>
+                    </source>"#,
+        r#"<float_array id="Wedge-positions-array" count="9">0 0 0 1 0 0 0 1 0</float_array>
+                    </source>
+                    <triangles count="0" />"#,
+    );
+    match Collada::from_str(&invalid).unwrap_err().kind {
+        ErrorKind::UnexpectedElement { parent: "convex_mesh", ref element, .. } => assert_eq!("vertices", element),
+        kind => panic!("Expected an error for the out of order vertices, got {:?}", kind),
+    }
+}
+
+#[test]
+fn source_technique_common() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry>
+                <mesh>
+                    <source id="positions">
+                        <float_array id="positions-array" count="3">0 1 2</float_array>
+                        <technique_common>
+                            <accessor source="#positions-array" count="1" stride="3" />
+                        </technique_common>
+                    </source>
+                    <source id="normals">
+                        <float_array id="normals-array" count="1">0</float_array>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#positions" />
+                    </vertices>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    // The `<technique_common>` wrapper is flattened away, leaving just the accessor.
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    let accessor = mesh.sources[0].accessor.as_ref().unwrap();
+    assert_eq!("#positions-array", accessor.source.as_str());
+    assert_eq!(Some(accessor), mesh.sources[0].common_accessor());
+    assert_eq!(None, mesh.sources[1].accessor);
+
+    // The wrapper still has to contain the accessor.
+    let document = DOCUMENT.replace(r##"<accessor source="#positions-array" count="1" stride="3" />"##, "");
+    let expected = Error {
+        position: TextPosition { row: 12, column: 24 },
+        kind: ErrorKind::MissingElement {
+            parent: "technique_common",
+            expected: vec!["accessor"],
+        },
+    };
+    assert_eq!(expected, Collada::from_str(&document).unwrap_err());
+}
+
+#[test]
+fn source_array_types() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry>
+                <mesh>
+                    <source id="flags">
+                        <bool_array id="flags-array" count="4">true false 1 0</bool_array>
+                        <technique_common>
+                            <accessor source="#flags-array" count="2" offset="1" stride="2">
+                                <param type="bool" />
+                                <param name="VISIBLE" sid="visible" type="bool" semantic="VISIBILITY" />
+                            </accessor>
+                        </technique_common>
+                    </source>
+                    <source id="joints">
+                        <IDREF_array id="joints-array" count="2">Root Spine</IDREF_array>
+                        <technique_common>
+                            <accessor source="#joints-array" count="2">
+                                <param name="JOINT" type="IDREF" />
+                            </accessor>
+                        </technique_common>
+                    </source>
+                    <source id="names">
+                        <Name_array id="names-array" count="2">Hip Knee</Name_array>
+                    </source>
+                    <source id="indices">
+                        <int_array count="3" minInclusive="0" maxInclusive="8">0 4 8</int_array>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#positions" />
+                    </vertices>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    let arrays = mesh.sources.iter().map(|source| source.array.as_ref().unwrap()).collect::<Vec<_>>();
+    let ids = arrays.iter().map(|array| array.id()).collect::<Vec<_>>();
+    assert_eq!(vec![Some("flags-array"), Some("joints-array"), Some("names-array"), None], ids);
+    assert_eq!(vec![4, 2, 2, 3], arrays.iter().map(|array| array.count()).collect::<Vec<_>>());
+
+    // Booleans may be written as words or digits.
+    let flags = arrays[0].as_bool_array().unwrap();
+    assert_eq!(&[true, false, true, false], flags.data.get().unwrap());
+    let accessor = mesh.sources[0].common_accessor().unwrap();
+    assert_eq!((2, 1, 2), (accessor.count, accessor.offset, accessor.stride));
+    assert_eq!(&[false, true], accessor.access(flags.data.get().unwrap(), 0));
+    assert_eq!(None, accessor.params[0].name);
+    assert_eq!(Some("VISIBILITY"), accessor.params[1].semantic.as_deref());
+    assert_eq!(Some("visible"), accessor.params[1].sid.as_deref());
+
+    let joints = arrays[1].as_idref_array().unwrap();
+    assert_eq!(vec!["Root", "Spine"], joints.data);
+    assert_eq!(Some("IDREF"), mesh.sources[1].common_accessor().unwrap().params[0].data_type.as_deref());
+    assert_eq!(None, arrays[1].as_bool_array());
+
+    assert_eq!(vec!["Hip", "Knee"], arrays[2].as_name_array().unwrap().data);
+
+    // Names are only separated by XML whitespace, so other whitespace stays part of the name.
+    let document = Collada::from_str(&DOCUMENT.replace("Hip Knee", "Left\u{a0}Hip\n\tKnee")).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    let names = mesh.sources[2].array.as_ref().and_then(Array::as_name_array).unwrap();
+    assert_eq!(vec!["Left\u{a0}Hip", "Knee"], names.data);
+
+    let indices = arrays[3].as_int_array().unwrap();
+    assert_eq!((0, 8), (indices.min_inclusive, indices.max_inclusive));
+
+    match Collada::from_str(&DOCUMENT.replace("true false 1 0", "true false yes 0")) {
+        Err(Error { kind: ErrorKind::ParseBoolError(_), .. }) => {}
+        result => panic!("Expected a bool parse error, got {:?}", result.map(|_| ())),
+    }
+
+    let options = ParseOptions {
+        validate_counts: true,
+        ..ParseOptions::default()
+    };
+    match Collada::read_with(DOCUMENT.replace("Root Spine", "Root").as_bytes(), options) {
+        Err(Error { kind: ErrorKind::CountMismatch { element: "IDREF_array", expected: 2, actual: 1 }, .. }) => {}
+        result => panic!("Expected a count mismatch error, got {:?}", result.map(|_| ())),
+    }
+}
+
+#[test]
+fn library_animations() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_animations>
+            <animation id="Armature">
+                <animation id="Bone-location-X" name="Bone location X">
+                    <source id="Bone-location-X-input">
+                        <float_array id="Bone-location-X-input-array" count="2">0 1.5</float_array>
+                        <technique_common>
+                            <accessor source="#Bone-location-X-input-array" count="2" stride="1">
+                                <param name="TIME" type="float" />
+                            </accessor>
+                        </technique_common>
+                    </source>
+                    <source id="Bone-location-X-output">
+                        <float_array id="Bone-location-X-output-array" count="2">0 2</float_array>
+                    </source>
+                    <source id="Bone-location-X-interpolation">
+                        <Name_array id="Bone-location-X-interpolation-array" count="2">LINEAR BEZIER</Name_array>
+                    </source>
+                    <sampler id="Bone-location-X-sampler">
+                        <input semantic="INPUT" source="#Bone-location-X-input" />
+                        <input semantic="OUTPUT" source="#Bone-location-X-output" />
+                        <input semantic="INTERPOLATION" source="#Bone-location-X-interpolation" />
+                    </sampler>
+                    <channel source="#Bone-location-X-sampler" target="Bone/location.X" />
+                </animation>
+            </animation>
+        </library_animations>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_animations().unwrap();
+    assert_eq!(1, library.animations.len());
+
+    let armature = &library.animations[0];
+    assert_eq!(Some("Armature"), armature.id.as_deref());
+    assert!(armature.sources.is_empty());
+    assert_eq!(1, armature.animations.len());
+
+    let animation = &armature.animations[0];
+    assert_eq!(Some("Bone location X"), animation.name.as_deref());
+    assert_eq!(3, animation.sources.len());
+    assert_eq!(
+        vec![Channel {
+            source: "#Bone-location-X-sampler".parse().unwrap(),
+            target: "Bone/location.X".into(),
+        }],
+        animation.channels,
+    );
+
+    // Follow the channel back to its keyframe data.
+    let channel = &animation.channels[0];
+    let sampler = animation.channel_sampler(channel).unwrap();
+    assert_eq!(3, sampler.inputs.len());
+    assert_eq!(None, sampler.input("IN_TANGENT"));
+
+    let times = animation.sampler_source(sampler, "INPUT").unwrap();
+    let times = times.array.as_ref().unwrap().as_float_array().unwrap();
+    assert_eq!(&[0.0, 1.5], times.data.get().unwrap());
+
+    let values = animation.sampler_source(sampler, "OUTPUT").unwrap();
+    let values = values.array.as_ref().unwrap().as_float_array().unwrap();
+    assert_eq!(&[0.0, 2.0], values.data.get().unwrap());
+
+    let interpolation = animation.sampler_source(sampler, "INTERPOLATION").unwrap();
+    let interpolation = interpolation.array.as_ref().unwrap();
+    assert_eq!(Some("Bone-location-X-interpolation-array"), interpolation.id());
+    assert_eq!(vec!["LINEAR", "BEZIER"], interpolation.as_name_array().unwrap().data);
+
+    // A sampler needs at least one input.
+    let document = DOCUMENT
+        .replace(r##"<input semantic="INPUT" source="#Bone-location-X-input" />"##, "")
+        .replace(r##"<input semantic="OUTPUT" source="#Bone-location-X-output" />"##, "")
+        .replace(r##"<input semantic="INTERPOLATION" source="#Bone-location-X-interpolation" />"##, "");
+    match Collada::from_str(&document).unwrap_err().kind {
+        ErrorKind::MissingRequiredChild { parent: "sampler", .. } => {}
+        kind => panic!("Unexpected error: {:?}", kind),
+    }
+}
+
+#[test]
+fn library_animation_clips() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_animations>
+            <animation id="Character">
+                <animation id="Walk-legs" />
+                <animation id="Walk-arms" />
+            </animation>
+            <animation id="Idle" />
+        </library_animations>
+        <library_animation_clips>
+            <animation_clip id="walk" name="Walk" start="0.5" end="1.75">
+                <instance_animation url="#Walk-legs" />
+                <instance_animation url="#Walk-arms" sid="arms" />
+            </animation_clip>
+            <animation_clip id="idle">
+                <instance_animation url="#Idle" />
+                <instance_animation url="other.dae#Idle-face" />
+            </animation_clip>
+        </library_animation_clips>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[1].as_library_animation_clips().unwrap();
+    assert_eq!(2, library.animation_clips.len());
+    assert_eq!(2, document.animation_clips().count());
+
+    let walk = document.animation_clip("walk").unwrap();
+    assert_eq!(Some("Walk"), walk.name.as_deref());
+    assert_eq!(0.5, walk.start);
+    assert_eq!(Some(1.75), walk.end);
+    assert_eq!(Some(1.25), walk.duration());
+    assert_eq!(Some("arms"), walk.instances[1].sid.as_deref());
+
+    // Nested animations can be instantiated directly.
+    let ids: Vec<_> = document.clip_animations(walk).map(|animation| animation.id.as_deref()).collect();
+    assert_eq!(vec![Some("Walk-legs"), Some("Walk-arms")], ids);
+
+    // The start time defaults to zero, and animations in other documents aren't resolved.
+    let idle = library.animation_clip("idle").unwrap();
+    assert_eq!(0.0, idle.start);
+    assert_eq!(None, idle.duration());
+    assert_eq!(2, idle.instances.len());
+    assert_eq!(1, document.clip_animations(idle).count());
+    assert_eq!(None, document.instance_animation(&idle.instances[1]));
+
+    // A clip needs at least one animation.
+    let document = DOCUMENT.replace(r##"<instance_animation url="#Idle" />"##, "")
+        .replace(r##"<instance_animation url="other.dae#Idle-face" />"##, "");
+    match Collada::from_str(&document).unwrap_err().kind {
+        ErrorKind::MissingRequiredChild { parent: "animation_clip", .. } => {}
+        kind => panic!("Unexpected error: {:?}", kind),
+    }
+}
+
+#[test]
+fn sid_addressing() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_animations>
+            <animation>
+                <channel source="#Bone-sampler" target="Armature/Bone/rotateY.ANGLE" />
+                <channel source="#Lamp-sampler" target="Lamp-light/color.G" />
+                <channel source="#Broken-sampler" target="Armature//rotateY" />
+            </animation>
+        </library_animations>
+        <library_cameras>
+            <camera id="Camera-camera">
+                <optics>
+                    <technique_common>
+                        <perspective>
+                            <xfov sid="xfov">49</xfov>
+                            <znear>0.1</znear>
+                            <zfar sid="zfar">100</zfar>
+                        </perspective>
+                    </technique_common>
+                </optics>
+            </camera>
+        </library_cameras>
+        <library_lights>
+            <light id="Lamp-light">
+                <technique_common>
+                    <spot>
+                        <color sid="color">1 0.5 0.25</color>
+                        <falloff_angle sid="falloff">45</falloff_angle>
+                    </spot>
+                </technique_common>
+            </light>
+        </library_lights>
+        <library_effects>
+            <effect id="Metal-effect">
+                <profile_COMMON>
+                    <technique sid="common">
+                        <phong>
+                            <diffuse>
+                                <color sid="diffuse">0.8 0.8 0.8 1</color>
+                            </diffuse>
+                            <shininess>
+                                <float sid="shininess">50</float>
+                            </shininess>
+                        </phong>
+                    </technique>
+                </profile_COMMON>
+            </effect>
+        </library_effects>
+        <library_visual_scenes>
+            <visual_scene id="Scene">
+                <node id="Armature">
+                    <translate sid="location">0 1 2</translate>
+                    <node id="Armature-Root" sid="Root">
+                        <node id="Armature-Bone" sid="Bone">
+                            <rotate sid="rotateY">0 1 0 90</rotate>
+                        </node>
+                    </node>
+                    <node id="Armature-Other" sid="Other">
+                        <node id="Armature-Shadowed" sid="Bone">
+                            <rotate sid="rotateY">0 1 0 45</rotate>
+                        </node>
+                    </node>
+                    <node id="Armature-Tip" sid="Tip">
+                        <matrix sid="transform">1 0 0 4 0 1 0 5 0 0 1 6 0 0 0 1</matrix>
+                    </node>
+                </node>
+            </visual_scene>
+        </library_visual_scenes>
+    </COLLADA>
+    "##;
+
+    // Parsing addresses.
+    let address: SidRef = "./translate".parse().unwrap();
+    assert_eq!(None, address.root);
+    assert_eq!(vec!["translate"], address.sids);
+    assert_eq!(None, address.selector);
+    assert_eq!("./translate", address.to_string());
+
+    let address: SidRef = "Box/transform(3)(2)".parse().unwrap();
+    assert_eq!(Some(SidSelector::Index2(3, 2)), address.selector);
+    assert_eq!("Box/transform(3)(2)", address.to_string());
+    assert_eq!(Some(SidSelector::Index(1)), "Box/values(1)".parse::<SidRef>().unwrap().selector);
+
+    for invalid in &["", ".", "/location", "Box//location", "Box/", "Box/transform(x)", "Box/transform(1", "Box/transform(1)(2)(3)", "Box/location."] {
+        assert_eq!(Err(SidRefParseError), invalid.parse::<SidRef>(), "{:?}", invalid);
+    }
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let resolve = |path: &str| document.resolve_sid_path(None, &path.parse().unwrap());
+
+    // Nodes are searched breadth-first, so the bone directly under `Root` is found before the
+    // one under `Other`.
+    let bone = document.node("Armature-Bone").unwrap();
+    assert_eq!(Some(SidTarget::Node(bone)), resolve("Armature/Bone").map(|resolved| resolved.target));
+    let angle = resolve("Armature/Bone/rotateY.ANGLE").unwrap();
+    assert_eq!(SidTarget::Transform(&bone.transforms[0]), angle.target);
+    assert_eq!(Some(3), angle.component);
+    assert_eq!(Some(&[90.0][..]), angle.values());
+
+    // Transforms are found before nodes deeper in the hierarchy.
+    let location = resolve("Armature/location").unwrap();
+    assert_eq!(Some(&[0.0, 1.0, 2.0][..]), location.values());
+    assert_eq!(Some(&[2.0][..]), resolve("Armature/location.Z").unwrap().values());
+    assert_eq!(Some(&[1.0][..]), resolve("Armature/location(1)").unwrap().values());
+
+    // Matrices are indexed by row, then column.
+    assert_eq!(Some(&[5.0][..]), resolve("Armature/transform(1)(3)").unwrap().values());
+
+    // Relative addresses start from the given node.
+    let armature = document.node("Armature").unwrap();
+    let relative = document.resolve_sid_path(Some(armature), &"./Tip/transform".parse().unwrap()).unwrap();
+    assert_eq!(16, relative.values().unwrap().len());
+    assert_eq!(None, resolve("./Tip"));
+
+    // Cameras, lights, and effects can be targeted too.
+    assert_eq!(Some(&[49.0][..]), resolve("Camera-camera/xfov").unwrap().values());
+    assert_eq!(Some(&[0.5][..]), resolve("Lamp-light/color.G").unwrap().values());
+    assert_eq!(Some(&[45.0][..]), resolve("Lamp-light/falloff").unwrap().values());
+    assert_eq!(Some(&[1.0][..]), resolve("Metal-effect/diffuse.A").unwrap().values());
+    assert_eq!(Some(&[50.0][..]), resolve("Metal-effect/shininess").unwrap().values());
+    assert_eq!(None, resolve("Camera-camera").unwrap().values());
+
+    // Anything that can't be found, or a selector that doesn't fit, resolves to nothing.
+    assert_eq!(None, resolve("Missing/location"));
+    assert_eq!(None, resolve("Armature/scale"));
+    assert_eq!(None, resolve("Armature/location.W"));
+    assert_eq!(None, resolve("Armature/location(3)"));
+    assert_eq!(None, resolve("Armature/location.TIME"));
+    assert_eq!(None, resolve("Lamp-light/color.A"));
+    assert_eq!(None, resolve("Camera-camera/xfov.X"));
+    assert_eq!(None, resolve("Armature/location/X"));
+
+    // Channels resolve their targets directly.
+    let library = document.libraries[0].as_library_animations().unwrap();
+    let channels = &library.animations[0].channels;
+    assert_eq!(Some(angle), document.resolve_channel(&channels[0]));
+    let color = document.resolve_channel(&channels[1]).unwrap();
+    assert_eq!(Some(1), color.component);
+    assert_eq!(Err(SidRefParseError), channels[2].target_ref());
+    assert_eq!(None, document.resolve_channel(&channels[2]));
+}
+
+#[test]
+fn library_cameras() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_cameras>
+            <camera id="Camera-camera" name="Camera">
+                <optics>
+                    <technique_common>
+                        <perspective>
+                            <xfov sid="xfov">90</xfov>
+                            <aspect_ratio>2</aspect_ratio>
+                            <znear sid="znear">0.1</znear>
+                            <zfar sid="zfar">100</zfar>
+                        </perspective>
+                    </technique_common>
+                    <technique profile="blender">
+                        <shiftx>0</shiftx>
+                    </technique>
+                </optics>
+            </camera>
+            <camera id="Top-camera">
+                <optics>
+                    <technique_common>
+                        <orthographic>
+                            <ymag>5</ymag>
+                            <aspect_ratio>1.5</aspect_ratio>
+                            <znear>1</znear>
+                            <zfar>50</zfar>
+                        </orthographic>
+                    </technique_common>
+                </optics>
+            </camera>
+        </library_cameras>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_cameras().unwrap();
+    assert_eq!(2, library.cameras.len());
+
+    let camera = library.camera("Camera-camera").unwrap();
+    assert_eq!(Some("Camera"), camera.name.as_deref());
+    assert_eq!("blender", camera.optics.techniques[0].profile);
+
+    let perspective = camera.optics.projection.as_perspective().unwrap();
+    let xfov = perspective.xfov.as_ref().unwrap();
+    assert_eq!(Some("xfov"), xfov.sid.as_deref());
+    assert_eq!(90.0, xfov.value);
+    assert_eq!(None, perspective.yfov);
+    assert_eq!((0.1, 100.0), camera.optics.projection.clipping_planes());
+
+    // The vertical field of view is worked out from the tangent of the horizontal one.
+    let (x, y) = perspective.field_of_view().unwrap();
+    assert_eq!(90.0, x);
+    assert!((y - 53.130_1).abs() < 0.001, "Unexpected vertical field of view {}", y);
+
+    let orthographic = library.camera("Top-camera").unwrap().optics.projection.as_orthographic().unwrap();
+    assert_eq!(None, orthographic.xmag);
+    assert_eq!(Some((7.5, 5.0)), orthographic.magnification());
+    assert_eq!(None, library.camera("Missing-camera"));
+
+    // The horizontal magnification is used as-is when it's given.
+    let document = DOCUMENT.replace("<orthographic>", "<orthographic><xmag>1</xmag>")
+        .replace("<aspect_ratio>1.5</aspect_ratio>", "");
+    let document = Collada::from_str(&document).unwrap();
+    let library = document.libraries[0].as_library_cameras().unwrap();
+    let orthographic = library.cameras[1].optics.projection.as_orthographic().unwrap();
+    assert_eq!(Some((1.0, 5.0)), orthographic.magnification());
+
+    // The clipping planes are required.
+    match Collada::from_str(&DOCUMENT.replace("<znear>1</znear>", "")) {
+        Err(Error { kind: ErrorKind::UnexpectedElement { parent: "orthographic", ref element, .. }, .. }) => {
+            assert_eq!("zfar", element);
+        }
+        result => panic!("Expected an unexpected element error, got {:?}", result.map(|_| ())),
+    }
+}
+
+#[test]
+fn library_controllers() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_controllers>
+            <controller id="Armature-skin" name="Armature">
+                <skin source="#Cube-mesh">
+                    <bind_shape_matrix>1 0 0 0 0 1 0 0 0 0 1 0 0 0 0 1</bind_shape_matrix>
+                    <source id="Armature-joints">
+                        <Name_array id="Armature-joints-array" count="2">Root Tip</Name_array>
+                    </source>
+                    <source id="Armature-bind-poses">
+                        <float_array id="Armature-bind-poses-array" count="32">
+                            1 0 0 0 0 1 0 0 0 0 1 0 0 0 0 1
+                            1 0 0 0 0 1 0 -1 0 0 1 0 0 0 0 1
+                        </float_array>
+                    </source>
+                    <source id="Armature-weights">
+                        <float_array id="Armature-weights-array" count="3">1 0.25 0.75</float_array>
+                    </source>
+                    <joints>
+                        <input semantic="JOINT" source="#Armature-joints" />
+                        <input semantic="INV_BIND_MATRIX" source="#Armature-bind-poses" />
+                    </joints>
+                    <vertex_weights count="3">
+                        <input semantic="JOINT" source="#Armature-joints" offset="0" />
+                        <input semantic="WEIGHT" source="#Armature-weights" offset="1" />
+                        <vcount>1 2 1</vcount>
+                        <v>0 0 0 1 1 2 -1 0</v>
+                    </vertex_weights>
+                </skin>
+            </controller>
+        </library_controllers>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_controllers().unwrap();
+    let controller = &library.controllers[0];
+    assert_eq!(Some("Armature-skin"), controller.id.as_deref());
+
+    let skin = controller.control_element.as_skin().unwrap();
+    assert_eq!("#Cube-mesh", skin.source.as_str());
+    assert_eq!(16, skin.bind_shape_matrix.as_ref().unwrap().len());
+    assert_eq!(Some(&["Root".to_string(), "Tip".to_string()][..]), skin.joint_names());
+
+    let matrices = skin.inverse_bind_matrices().unwrap().unwrap();
+    assert_eq!(2, matrices.len());
+    assert_eq!(-1.0, matrices[1][7]);
+
+    let indices = skin.vertex_weights.iter().collect::<Vec<_>>();
+    assert_eq!(vec![&[0, 0][..], &[0, 1, 1, 2][..], &[-1, 0][..]], indices);
+
+    let influences = skin.influences().unwrap().unwrap();
+    assert_eq!(
+        vec![
+            vec![Influence { joint: Some(0), weight: 1.0 }],
+            vec![Influence { joint: Some(0), weight: 0.25 }, Influence { joint: Some(1), weight: 0.75 }],
+            vec![Influence { joint: None, weight: 1.0 }],
+        ],
+        influences,
+    );
+
+    // Indices past the end of the weights can't be looked up.
+    let document = Collada::from_str(&DOCUMENT.replace("-1 0</v>", "-1 3</v>")).unwrap();
+    let library = document.libraries[0].as_library_controllers().unwrap();
+    let skin = library.controllers[0].control_element.as_skin().unwrap();
+    assert_eq!(None, skin.influences());
+}
+
+#[test]
+fn remap_joint_names() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_animations>
+            <animation id="Walk">
+                <channel source="#Hips-sampler" target="mixamorig_Hips/transform" />
+                <channel source="#Spine-sampler" target="Armature/mixamorig_Hips/mixamorig_Spine/rotateY.ANGLE" />
+                <animation>
+                    <channel source="#Cube-sampler" target="mixamorig_Cube/location.X" />
+                    <channel source="#Broken-sampler" target="mixamorig_Hips//rotateY" />
+                </animation>
+            </animation>
+        </library_animations>
+        <library_controllers>
+            <controller id="Armature-skin">
+                <skin source="#Body-mesh">
+                    <source id="Armature-joints">
+                        <Name_array id="Armature-joints-array" count="2">mixamorig_Hips mixamorig_Spine</Name_array>
+                    </source>
+                    <joints>
+                        <input semantic="JOINT" source="#Armature-joints" />
+                    </joints>
+                    <vertex_weights count="0">
+                        <input semantic="JOINT" source="#Armature-joints" offset="0" />
+                    </vertex_weights>
+                </skin>
+            </controller>
+        </library_controllers>
+        <library_visual_scenes>
+            <visual_scene id="Scene">
+                <node id="Armature">
+                    <node id="mixamorig_Hips" sid="mixamorig_Hips" name="mixamorig_Hips">
+                        <node sid="mixamorig_Spine" type="JOINT" />
+                    </node>
+                </node>
+                <node id="mixamorig_Cube" />
+                <node id="Body">
+                    <instance_controller url="#Armature-skin">
+                        <skeleton>#mixamorig_Hips</skeleton>
+                    </instance_controller>
+                </node>
+            </visual_scene>
+        </library_visual_scenes>
+    </COLLADA>
+    "##;
+
+    let mut document = Collada::from_str(DOCUMENT).unwrap();
+    document.remap_joint_names_with(|name| name.strip_prefix("mixamorig_").map(String::from));
+
+    let animation = document.animation("Walk").unwrap();
+    let targets = animation.channels.iter()
+        .chain(&animation.animations[0].channels)
+        .map(|channel| channel.target.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec!["Hips/transform", "Armature/Hips/Spine/rotateY.ANGLE", "mixamorig_Cube/location.X", "mixamorig_Hips//rotateY"],
+        targets,
+    );
+
+    let library = document.libraries[1].as_library_controllers().unwrap();
+    let skin = library.controllers[0].control_element.as_skin().unwrap();
+    assert_eq!(Some(&["Hips".to_string(), "Spine".to_string()][..]), skin.joint_names());
+
+    // Only joints are renamed, whether they're marked as joints or listed by a skin.
+    let hips = document.node("Hips").unwrap();
+    assert_eq!(Some("Hips"), hips.sid.as_deref());
+    assert_eq!(Some("Hips"), hips.name.as_deref());
+    assert_eq!(Some("Spine"), hips.nodes[0].sid.as_deref());
+    assert!(document.node("mixamorig_Cube").is_some());
+
+    let body = document.node("Body").unwrap();
+    assert_eq!("#Hips", body.instance_controllers[0].skeletons[0].as_str());
+
+    // Names can also be mapped one to one.
+    let mut names = HashMap::new();
+    names.insert("Hips".to_string(), "Pelvis".to_string());
+    document.remap_joint_names(&names);
+    assert!(document.node("Pelvis").is_some());
+    assert_eq!("Pelvis/transform", document.animation("Walk").unwrap().channels[0].target);
+}
+
+#[test]
+fn library_effects() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_effects>
+            <effect id="Metal-effect">
+                <profile_GLSL>
+                    <technique sid="glsl">
+                        <pass />
+                    </technique>
+                </profile_GLSL>
+                <profile_COMMON>
+                    <technique sid="common">
+                        <blinn>
+                            <emission>
+                                <color sid="emission">0 0 0 1</color>
+                            </emission>
+                            <diffuse>
+                                <texture texture="Metal-sampler" texcoord="UVMap" />
+                            </diffuse>
+                            <specular>
+                                <param ref="specular-color" />
+                            </specular>
+                            <shininess>
+                                <float sid="shininess">50</float>
+                            </shininess>
+                            <transparent opaque="RGB_ZERO">
+                                <color>0.5 0.5 0.5</color>
+                            </transparent>
+                        </blinn>
+                    </technique>
+                </profile_COMMON>
+            </effect>
+            <effect id="Glow-effect">
+                <profile_COMMON>
+                    <technique sid="common">
+                        <constant>
+                            <emission>
+                                <color>1 0.5 0 1</color>
+                            </emission>
+                            <transparent>
+                                <color>0 0 0 1</color>
+                            </transparent>
+                        </constant>
+                    </technique>
+                </profile_COMMON>
+            </effect>
+        </library_effects>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_effects().unwrap();
+    assert_eq!(2, library.effects.len());
+
+    let metal = library.effect("Metal-effect").unwrap();
+    assert_eq!(2, metal.profiles.len());
+    assert_eq!("common", metal.profile_common().unwrap().technique.sid);
+
+    let shading = metal.common_shading().unwrap();
+    let emission = shading.emission().and_then(ColorOrTexture::as_color).unwrap();
+    assert_eq!(Some("emission"), emission.sid.as_deref());
+    assert_eq!(Some([0.0, 0.0, 0.0, 1.0]), emission.rgba());
+
+    let diffuse = shading.diffuse().and_then(ColorOrTexture::as_texture).unwrap();
+    assert_eq!("Metal-sampler", diffuse.texture);
+    assert_eq!("UVMap", diffuse.texcoord);
+
+    match *shading.specular().unwrap() {
+        ColorOrTexture::Param(ref param) => assert_eq!("specular-color", param.reference),
+        ref specular => panic!("Expected a param, got {:?}", specular),
+    }
+
+    assert_eq!(Some(50.0), shading.shininess().and_then(FloatOrParam::as_float));
+    assert_eq!(None, shading.ambient());
+
+    let transparent = shading.transparent().unwrap();
+    assert_eq!(Opaque::RgbZero, transparent.opaque);
+    assert_eq!(Some([0.5, 0.5, 0.5, 1.0]), transparent.value.as_color().and_then(Color::rgba));
+
+    let glow = library.effect("Glow-effect").unwrap().common_shading().unwrap();
+    match *glow {
+        ShadingModel::Constant(_) => {}
+        ref shading => panic!("Expected a constant shading model, got {:?}", shading),
+    }
+    assert_eq!(None, glow.diffuse());
+    assert_eq!(None, glow.specular());
+    assert_eq!(Opaque::AOne, glow.transparent().unwrap().opaque);
+
+    match Collada::from_str(&DOCUMENT.replace("RGB_ZERO", "RGB_HALF")) {
+        Err(Error { kind: ErrorKind::InvalidValue { element: "transparent", .. }, .. }) => {}
+        result => panic!("Expected an invalid value error, got {:?}", result.map(|_| ())),
+    }
+}
+
+#[test]
+fn transparency_modes() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_effects>
+            <effect id="Solid-effect">
+                <profile_COMMON>
+                    <technique sid="common">
+                        <lambert>
+                            <diffuse><color>1 0 0 1</color></diffuse>
+                        </lambert>
+                    </technique>
+                </profile_COMMON>
+            </effect>
+            <effect id="Glass-effect">
+                <profile_COMMON>
+                    <technique sid="common">
+                        <phong>
+                            <transparent opaque="A_ZERO"><color>0 0 0 0.25</color></transparent>
+                            <transparency><float>0.5</float></transparency>
+                        </phong>
+                    </technique>
+                </profile_COMMON>
+            </effect>
+            <effect id="Tinted-effect">
+                <profile_COMMON>
+                    <technique sid="common">
+                        <blinn>
+                            <transparent opaque="RGB_ONE"><color>1 0.5 0 1</color></transparent>
+                        </blinn>
+                    </technique>
+                </profile_COMMON>
+            </effect>
+            <effect id="Leaves-effect">
+                <profile_COMMON>
+                    <technique sid="common">
+                        <lambert>
+                            <transparent opaque="A_ONE">
+                                <texture texture="Leaves-sampler" texcoord="UVMap"/>
+                            </transparent>
+                            <transparency><float>0.8</float></transparency>
+                        </lambert>
+                    </technique>
+                </profile_COMMON>
+            </effect>
+            <effect id="Inverted-effect">
+                <profile_COMMON>
+                    <technique sid="common">
+                        <constant>
+                            <transparent><color>0 0 0 1</color></transparent>
+                            <transparency><float>0</float></transparency>
+                        </constant>
+                    </technique>
+                </profile_COMMON>
+            </effect>
+            <effect id="Param-effect">
+                <profile_COMMON>
+                    <technique sid="common">
+                        <constant>
+                            <transparent><color>0 0 0 1</color></transparent>
+                            <transparency><param ref="transparency"/></transparency>
+                        </constant>
+                    </technique>
+                </profile_COMMON>
+            </effect>
+        </library_effects>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_effects().unwrap();
+    let shading = |id: &str| library.effect(id).unwrap().common_shading().unwrap();
+
+    let solid = shading("Solid-effect").blend().unwrap();
+    assert!(solid.is_opaque());
+    assert_eq!(Some(1.0), solid.alpha());
+
+    let glass = shading("Glass-effect");
+    let transparent = glass.transparent().unwrap();
+    assert_eq!(Opaque::AZero, transparent.opaque);
+    assert_eq!("A_ZERO", transparent.opaque.as_str());
+    assert!(transparent.opaque.uses_alpha());
+    assert_eq!(Some([0.875; 3]), transparent.opacity(0.5));
+    assert_eq!(Some(Blend::Constant([0.875; 3])), glass.blend());
+
+    let tinted = shading("Tinted-effect");
+    assert_eq!(Opaque::RgbOne, tinted.transparent().unwrap().opaque);
+    assert!(!tinted.transparent().unwrap().opaque.uses_alpha());
+    let blend = tinted.blend().unwrap();
+    assert_eq!(Blend::Constant([1.0, 0.5, 0.0]), blend);
+    assert!((blend.alpha().unwrap() - 0.5702).abs() < 1e-6);
+
+    match shading("Leaves-effect").blend().unwrap() {
+        Blend::Texture { texture, opaque, transparency } => {
+            assert_eq!("Leaves-sampler", texture.texture);
+            assert_eq!(Opaque::AOne, opaque);
+            assert_eq!(0.8, transparency);
+            assert_eq!([0.4; 3], opaque.opacity([1.0, 1.0, 1.0, 0.5], transparency));
+        }
+        blend => panic!("Expected a texture blend, got {:?}", blend),
+    }
+    assert_eq!(None, shading("Leaves-effect").transparent().unwrap().opacity(1.0));
+
+    // A transparency of zero makes the surface fully transparent, even though some exporters
+    // use it to mean opaque.
+    assert_eq!(Some(Blend::Constant([0.0; 3])), shading("Inverted-effect").blend());
+
+    assert_eq!(None, shading("Param-effect").blend());
+}
+
+#[test]
+fn profile_glsl_and_cg() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_effects>
+            <effect id="Toon-effect">
+                <profile_GLSL>
+                    <code sid="toon">void main() { gl_FragColor = tint; }</code>
+                    <include sid="lighting" url="lighting.glsl" />
+                    <newparam sid="tint">
+                        <semantic>DIFFUSE</semantic>
+                        <float4>1 0.5 0 1</float4>
+                    </newparam>
+                    <newparam sid="wvp">
+                        <semantic>WORLDVIEWPROJECTION</semantic>
+                        <float4x4>1 0 0 0 0 1 0 0 0 0 1 0 0 0 0 1</float4x4>
+                    </newparam>
+                    <technique sid="default">
+                        <setparam ref="tint">
+                            <float4>0 1 0 1</float4>
+                        </setparam>
+                        <pass sid="outline">
+                            <color_target>Outline-surface</color_target>
+                            <draw>SCENE_GEOMETRY</draw>
+                            <blend_enable value="true" />
+                            <blend_func>
+                                <src value="SRC_ALPHA" />
+                                <dest value="ONE_MINUS_SRC_ALPHA" />
+                            </blend_func>
+                            <clear_color value="0 0 0 1" />
+                            <light_enable index="1" param="light-on" />
+                            <shader stage="VERTEXPROGRAM">
+                                <name source="lighting">transform</name>
+                                <bind symbol="worldViewProjection">
+                                    <param ref="wvp" />
+                                </bind>
+                            </shader>
+                            <shader stage="FRAGMENTPROGRAM">
+                                <name source="toon">main</name>
+                                <bind symbol="scale">
+                                    <float>2</float>
+                                </bind>
+                            </shader>
+                        </pass>
+                    </technique>
+                </profile_GLSL>
+            </effect>
+            <effect id="Cg-effect">
+                <profile_CG>
+                    <technique sid="cg">
+                        <pass>
+                            <shader stage="FRAGMENT">
+                                <compiler_target>ARBFP1</compiler_target>
+                                <name>main</name>
+                            </shader>
+                        </pass>
+                    </technique>
+                </profile_CG>
+                <profile_CG platform="PS3">
+                    <technique sid="cg">
+                        <pass />
+                    </technique>
+                </profile_CG>
+            </effect>
+        </library_effects>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_effects().unwrap();
+
+    let glsl = library.effect("Toon-effect").unwrap().profile_glsl().unwrap();
+    assert_eq!(2, glsl.code.len());
+    match glsl.code[0] {
+        ShaderCode::Code(ref code) => assert_eq!("void main() { gl_FragColor = tint; }", code.source),
+        ref code => panic!("Expected code, got {:?}", code),
+    }
+    assert_eq!(Some("lighting"), glsl.code[1].sid());
+
+    let tint = glsl.new_param("tint").unwrap();
+    assert_eq!(Some("DIFFUSE".into()), tint.semantic);
+    assert_eq!("float4", tint.value.type_name());
+    assert_eq!(Some(vec![1.0, 0.5, 0.0, 1.0]), tint.value.float_values());
+    assert_eq!(Some(16), glsl.new_param("wvp").unwrap().value.float_values().map(|values| values.len()));
+
+    let technique = glsl.technique("default").unwrap();
+    match technique.params[0] {
+        TechniqueParam::SetParam(ref param) => {
+            assert_eq!("tint", param.reference);
+            match param.value {
+                ShaderSetParamValue::Value(ref value) => {
+                    assert_eq!(Some(vec![0.0, 1.0, 0.0, 1.0]), value.float_values());
+                }
+                ref value => panic!("Expected a value, got {:?}", value),
+            }
+        }
+        ref param => panic!("Expected a setparam, got {:?}", param),
+    }
+
+    let pass = technique.pass("outline").unwrap();
+    assert_eq!(2, pass.setup.len());
+    assert_eq!("color_target", pass.setup[0].name());
+    assert_eq!("Outline-surface", pass.setup[0].value());
+    assert_eq!("SCENE_GEOMETRY", pass.setup[1].value());
+
+    assert_eq!(4, pass.render_states().count());
+    assert_eq!(Some(true), pass.render_state("blend_enable").and_then(RenderState::bool_value));
+    let blend_func = pass.render_state("blend_func").unwrap();
+    assert_eq!(Some("SRC_ALPHA"), blend_func.child_value("src"));
+    assert_eq!(Some("ONE_MINUS_SRC_ALPHA"), blend_func.child_value("dest"));
+    assert_eq!(
+        Some(vec![0.0, 0.0, 0.0, 1.0]),
+        pass.render_state("clear_color").and_then(RenderState::float_values),
+    );
+    let light = pass.render_state("light_enable").unwrap();
+    assert_eq!(Some(1), light.index);
+    assert_eq!(Some("light-on".into()), light.param);
+    assert_eq!(None, light.value);
+
+    let vertex = pass.shader(ShaderStage::Vertex).unwrap();
+    assert_eq!(Some("lighting".into()), vertex.name.source);
+    assert_eq!("transform", vertex.name.entry_point);
+    match *vertex.bind("worldViewProjection").unwrap() {
+        ShaderBindValue::Param(ref param) => assert_eq!("wvp", param.reference),
+        ref value => panic!("Expected a param, got {:?}", value),
+    }
+
+    let fragment = pass.shader(ShaderStage::Fragment).unwrap();
+    match *fragment.bind("scale").unwrap() {
+        ShaderBindValue::Value(ref value) => assert_eq!(Some(vec![2.0]), value.float_values()),
+        ref value => panic!("Expected a value, got {:?}", value),
+    }
+
+    let cg = library.effect("Cg-effect").unwrap();
+    let profiles: Vec<_> = cg.profiles.iter().filter_map(Profile::as_cg).collect();
+    assert_eq!("PC", profiles[0].platform);
+    assert_eq!("PS3", profiles[1].platform);
+
+    let shader = profiles[0].technique("cg").unwrap().passes[0].shader(ShaderStage::Fragment).unwrap();
+    assert_eq!(Some("ARBFP1".into()), shader.compiler_target);
+    assert_eq!(None, shader.name.source);
+    assert_eq!("main", shader.name.entry_point);
+}
+
+#[test]
+fn param_value_types() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_effects>
+            <effect id="Params-effect">
+                <profile_GLSL>
+                    <newparam sid="enabled"><bool>true</bool></newparam>
+                    <newparam sid="mask"><bool3>true false true</bool3></newparam>
+                    <newparam sid="count"><int>-3</int></newparam>
+                    <newparam sid="size"><int2>640 480</int2></newparam>
+                    <newparam sid="basis"><float2x3>1 2 3 4 5 6</float2x3></newparam>
+                    <newparam sid="filter"><enum>LINEAR_MIPMAP_LINEAR</enum></newparam>
+                    <newparam sid="environment">
+                        <samplerCUBE>
+                            <source>Environment-surface</source>
+                            <wrap_p>CLAMP</wrap_p>
+                            <minfilter>LINEAR</minfilter>
+                        </samplerCUBE>
+                    </newparam>
+                    <newparam sid="shadow">
+                        <samplerDEPTH>
+                            <source>Shadow-surface</source>
+                        </samplerDEPTH>
+                    </newparam>
+                    <technique sid="default">
+                        <pass />
+                    </technique>
+                </profile_GLSL>
+                <profile_CG>
+                    <newparam sid="half-tint"><half3>0.5 0.5 1</half3></newparam>
+                    <technique sid="cg">
+                        <pass />
+                    </technique>
+                </profile_CG>
+            </effect>
+        </library_effects>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let effect = document.libraries[0].as_library_effects().unwrap().effect("Params-effect").unwrap();
+    let glsl = effect.profile_glsl().unwrap();
+    let value = |sid| &glsl.new_param(sid).unwrap().value;
+
+    assert_eq!(ParamValue::Bool(Bool { value: true }), *value("enabled"));
+    assert_eq!(ParamValue::Bool3(Bool3 { values: vec![true, false, true] }), *value("mask"));
+    assert_eq!(None, value("mask").float_values());
+    assert_eq!(ParamValue::Int(Int { value: -3 }), *value("count"));
+    assert_eq!(Some(vec![640.0, 480.0]), value("size").float_values());
+    assert_eq!(ParamValue::Enum(Enum { value: "LINEAR_MIPMAP_LINEAR".into() }), *value("filter"));
+
+    match *value("basis") {
+        ParamValue::FloatMatrix(ref matrix) => {
+            assert_eq!("float2x3", matrix.type_name());
+            assert_eq!((2, 3), (matrix.rows, matrix.columns));
+            assert_eq!(Some(6.0), matrix.get(1, 2));
+            assert_eq!(None, matrix.get(2, 0));
+        }
+        ref value => panic!("Expected a float matrix, got {:?}", value),
+    }
+
+    match *value("environment") {
+        ParamValue::SamplerCube(ref sampler) => {
+            assert_eq!("Environment-surface", sampler.source);
+            assert_eq!(WrapMode::Clamp, sampler.wrap_p);
+            assert_eq!(WrapMode::Wrap, sampler.wrap_s);
+            assert_eq!(FilterMode::Linear, sampler.minfilter);
+        }
+        ref value => panic!("Expected a cube sampler, got {:?}", value),
+    }
+    assert_eq!("samplerDEPTH", value("shadow").type_name());
+
+    let cg = effect.profile_cg().unwrap();
+    let half_tint = &cg.new_param("half-tint").unwrap().value;
+    assert_eq!("half3", half_tint.type_name());
+    assert_eq!(Some(vec![0.5, 0.5, 1.0]), half_tint.float_values());
+}
+
+#[test]
+fn effect_textures() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_images>
+            <image id="Brick-image" name="Brick" width="256" height="256">
+                <init_from>textures/brick.png</init_from>
+            </image>
+        </library_images>
+        <library_effects>
+            <effect id="Brick-effect">
+                <image id="Brick-normal-image">
+                    <init_from>textures/brick_normal.png</init_from>
+                </image>
+                <newparam sid="Brick-surface">
+                    <surface type="2D">
+                        <init_from mip="0">Brick-image</init_from>
+                        <format>A8R8G8B8</format>
+                    </surface>
+                </newparam>
+                <profile_COMMON>
+                    <newparam sid="Brick-sampler">
+                        <sampler2D>
+                            <source>Brick-surface</source>
+                            <wrap_s>MIRROR</wrap_s>
+                            <minfilter>LINEAR_MIPMAP_LINEAR</minfilter>
+                            <magfilter>LINEAR</magfilter>
+                        </sampler2D>
+                    </newparam>
+                    <newparam sid="Brick-normal-surface">
+                        <surface type="2D">
+                            <init_from>Brick-normal-image</init_from>
+                        </surface>
+                    </newparam>
+                    <newparam sid="Brick-normal-sampler">
+                        <sampler2D>
+                            <source>Brick-normal-surface</source>
+                        </sampler2D>
+                    </newparam>
+                    <newparam sid="Brick-tint">
+                        <float4>1 0.9 0.9 1</float4>
+                    </newparam>
+                    <technique sid="common">
+                        <lambert>
+                            <ambient>
+                                <texture texture="Brick-normal-sampler" texcoord="UVMap" />
+                            </ambient>
+                            <diffuse>
+                                <texture texture="Brick-sampler" texcoord="UVMap" />
+                            </diffuse>
+                            <reflective>
+                                <texture texture="Brick-tint" texcoord="UVMap" />
+                            </reflective>
+                        </lambert>
+                    </technique>
+                </profile_COMMON>
+            </effect>
+        </library_effects>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let image = document.image("Brick-image").unwrap();
+    assert_eq!(Some(256), image.width);
+    assert_eq!(1, image.depth);
+
+    let library = document.libraries[1].as_library_effects().unwrap();
+    let effect = &library.effects[0];
+    let shading = effect.common_shading().unwrap();
+    let diffuse = shading.diffuse().and_then(ColorOrTexture::as_texture).unwrap();
+
+    let sampler = effect.texture_sampler(diffuse).unwrap();
+    assert_eq!("Brick-surface", sampler.source);
+    assert_eq!(WrapMode::Mirror, sampler.wrap_s);
+    assert_eq!(WrapMode::Wrap, sampler.wrap_t);
+    assert_eq!(FilterMode::LinearMipmapLinear, sampler.minfilter);
+    assert_eq!(FilterMode::Linear, sampler.magfilter);
+    assert_eq!(FilterMode::None, sampler.mipfilter);
+
+    // The surface is declared on the effect rather than the profile.
+    let surface = effect.texture_surface(diffuse).unwrap();
+    assert_eq!("2D", surface.surface_type);
+    assert_eq!(Some("A8R8G8B8"), surface.format.as_deref());
+    assert_eq!(Some("Brick-image"), surface.image());
+    assert_eq!(Some("textures/brick.png"), document.texture_image_uri(effect, diffuse).map(AnyUri::as_str));
+
+    // Images declared in the effect are found before the document's libraries are searched.
+    let ambient = shading.ambient().and_then(ColorOrTexture::as_texture).unwrap();
+    assert_eq!(Some("textures/brick_normal.png"), document.texture_image_uri(effect, ambient).map(AnyUri::as_str));
+
+    // A texture has to reference a sampler.
+    let reflective = shading.reflective().and_then(ColorOrTexture::as_texture).unwrap();
+    assert!(effect.new_param("Brick-tint").is_some());
+    assert_eq!(None, effect.texture_sampler(reflective));
+    assert_eq!(None, document.texture_image_uri(effect, reflective));
+
+    match Collada::from_str(&DOCUMENT.replace("MIRROR", "REPEAT")) {
+        Err(Error { kind: ErrorKind::InvalidValue { element: "sampler2D", .. }, .. }) => {}
+        result => panic!("Expected an invalid value error, got {:?}", result.map(|_| ())),
+    }
+}
+
+#[test]
+fn image_data() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_images>
+            <image id="Pixel-image" format="RAW" width="1" height="1">
+                <data>ff00 80FF</data>
+            </image>
+        </library_images>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let image = document.image("Pixel-image").unwrap();
+    assert_eq!(Some("RAW"), image.format.as_deref());
+    assert_eq!(None, image.uri());
+    assert_eq!(Some(&[0xff, 0x00, 0x80, 0xff][..]), image.embedded_data());
+
+    match Collada::from_str(&DOCUMENT.replace("80FF", "80FG")) {
+        Err(Error { kind: ErrorKind::HexBinaryParseError(_), .. }) => {}
+        result => panic!("Expected a hex binary error, got {:?}", result.map(|_| ())),
+    }
+}
+
+#[test]
+fn library_lights() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_lights>
+            <light id="Ambient-light">
+                <technique_common>
+                    <ambient>
+                        <color>0.1 0.1 0.1</color>
+                    </ambient>
+                </technique_common>
+            </light>
+            <light id="Sun-light" name="Sun">
+                <technique_common>
+                    <directional>
+                        <color sid="color">1 0.9 0.8</color>
+                    </directional>
+                </technique_common>
+            </light>
+            <light id="Lamp-light">
+                <technique_common>
+                    <point>
+                        <color sid="color">1 1 1</color>
+                        <constant_attenuation>1</constant_attenuation>
+                        <linear_attenuation>0</linear_attenuation>
+                        <quadratic_attenuation>0.00111109</quadratic_attenuation>
+                    </point>
+                </technique_common>
+                <technique profile="blender">
+                    <energy sid="blender_energy">1</energy>
+                </technique>
+            </light>
+            <light id="Spot-light">
+                <technique_common>
+                    <spot>
+                        <color>1 1 1</color>
+                        <linear_attenuation sid="linear">0.5</linear_attenuation>
+                        <falloff_angle sid="falloff">45</falloff_angle>
+                    </spot>
+                </technique_common>
+            </light>
+        </library_lights>
+    </COLLADA>
+    "#;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_lights().unwrap();
+    assert_eq!(4, library.lights.len());
+
+    let ambient = library.light("Ambient-light").unwrap();
+    assert_eq!([0.1, 0.1, 0.1, 1.0], ambient.kind.as_ambient().unwrap().color.rgba().unwrap());
+    assert_eq!(None, ambient.kind.attenuation());
+
+    let sun = library.light("Sun-light").unwrap();
+    assert_eq!(Some("Sun"), sun.name.as_deref());
+    assert_eq!(Some("color"), sun.kind.color().sid.as_deref());
+    assert_eq!(vec![1.0, 0.9, 0.8], sun.kind.as_directional().unwrap().color.values);
+
+    let lamp = library.light("Lamp-light").unwrap();
+    assert_eq!("blender", lamp.techniques[0].profile);
+    let point = lamp.kind.as_point().unwrap();
+    assert_eq!((1.0, 0.0, 0.001_111_09), point.attenuation());
+    assert_eq!(None, lamp.kind.as_spot());
+
+    // Attenuation and falloff parameters that aren't given fall back to their defaults.
+    let spot = library.light("Spot-light").unwrap().kind.as_spot().unwrap();
+    assert_eq!(Some("linear"), spot.linear_attenuation.as_ref().unwrap().sid.as_deref());
+    assert_eq!((1.0, 0.5, 0.0), spot.attenuation());
+    assert_eq!((45.0, 0.0), spot.falloff());
+    assert_eq!(None, library.light("Missing-light"));
+
+    // Every light has a color.
+    match Collada::from_str(&DOCUMENT.replace("<color>0.1 0.1 0.1</color>", "")) {
+        Err(Error { kind: ErrorKind::MissingElement { parent: "ambient", ref expected, .. }, .. }) => {
+            assert_eq!(vec!["color"], *expected);
+        }
+        result => panic!("Expected a missing element error, got {:?}", result.map(|_| ())),
+    }
+}
+
+#[test]
+fn library_materials() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_effects>
+            <effect id="Paint-effect">
+                <newparam sid="Paint-shininess">
+                    <float>20</float>
+                </newparam>
+                <newparam sid="Paint-color">
+                    <float4>1 0 0 1</float4>
+                </newparam>
+                <profile_COMMON>
+                    <technique sid="common">
+                        <phong>
+                            <diffuse>
+                                <param ref="Paint-color" />
+                            </diffuse>
+                            <shininess>
+                                <param ref="Paint-shininess" />
+                            </shininess>
+                        </phong>
+                    </technique>
+                </profile_COMMON>
+            </effect>
+        </library_effects>
+        <library_materials>
+            <material id="Red-material" name="Red">
+                <instance_effect url="#Paint-effect" />
+            </material>
+            <material id="Blue-material" name="Blue">
+                <instance_effect url="#Paint-effect">
+                    <technique_hint platform="PC-OGL" profile="COMMON" ref="common" />
+                    <setparam ref="Paint-color">
+                        <float4>0 0 1 1</float4>
+                    </setparam>
+                </instance_effect>
+            </material>
+            <material id="Missing-material">
+                <instance_effect url="#Missing-effect" />
+            </material>
+        </library_materials>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[1].as_library_materials().unwrap();
+    assert_eq!(3, library.materials.len());
+
+    let red = document.material("Red-material").unwrap();
+    assert_eq!(Some("Red"), red.name.as_deref());
+    assert_eq!("Paint-effect", document.material_effect(red).unwrap().id);
+    match *document.material_param(red, "Paint-color").unwrap() {
+        ParamValue::Float4(ref color) => assert_eq!(vec![1.0, 0.0, 0.0, 1.0], color.values),
+        ref value => panic!("Expected a float4, got {:?}", value),
+    }
+
+    // Parameters that aren't set by the material keep the effect's value.
+    let blue = document.material("Blue-material").unwrap();
+    let hint = &blue.instance_effect.technique_hints[0];
+    assert_eq!(Some("PC-OGL"), hint.platform.as_deref());
+    assert_eq!(Some("COMMON"), hint.profile.as_deref());
+    assert_eq!("common", hint.reference);
+    match *document.material_param(blue, "Paint-color").unwrap() {
+        ParamValue::Float4(ref color) => assert_eq!(vec![0.0, 0.0, 1.0, 1.0], color.values),
+        ref value => panic!("Expected a float4, got {:?}", value),
+    }
+    match *document.material_param(blue, "Paint-shininess").unwrap() {
+        ParamValue::Float(ref shininess) => assert_eq!(20.0, shininess.value),
+        ref value => panic!("Expected a float, got {:?}", value),
+    }
+    assert_eq!(None, document.material_param(blue, "Paint-roughness"));
+
+    let missing = document.material("Missing-material").unwrap();
+    assert_eq!(None, document.material_effect(missing));
+    assert_eq!(None, document.material("Green-material"));
+}
+
+#[test]
+fn library_visual_scenes() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_visual_scenes>
+            <visual_scene id="Scene" name="Scene">
+                <node id="Camera" name="Camera" type="NODE">
+                    <lookat sid="view">0 0 5 0 0 0 0 1 0</lookat>
+                    <instance_camera url="#Camera-camera"/>
+                </node>
+                <node id="Armature" layer="characters rigs">
+                    <translate sid="location">1 2 3</translate>
+                    <rotate sid="rotationZ">0 0 1 90</rotate>
+                    <scale sid="scale">2 2 2</scale>
+                    <instance_controller url="#Armature-skin">
+                        <skeleton>#Root</skeleton>
+                    </instance_controller>
+                    <instance_light url="#Lamp-light"/>
+                    <node id="Root" sid="Root" type="JOINT">
+                        <matrix sid="transform">1 0 0 0 0 1 0 0 0 0 1 4 0 0 0 1</matrix>
+                        <skew>45 0 1 0 1 0 0</skew>
+                        <instance_geometry url="#Cube-mesh" name="Cube"/>
+                        <instance_node url="#Shared"/>
+                    </node>
+                </node>
+            </visual_scene>
+        </library_visual_scenes>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let scene = document.visual_scene("Scene").unwrap();
+    assert_eq!(2, scene.nodes.len());
+    assert_eq!(None, document.visual_scene("Missing"));
+
+    let camera = &scene.nodes[0];
+    assert_eq!(NodeType::Node, camera.node_type);
+    assert_eq!("#Camera-camera", camera.instance_cameras[0].url.as_str());
+    let expected = [
+        1.0, 0.0, 0.0, 0.0,
+        0.0, 1.0, 0.0, 0.0,
+        0.0, 0.0, 1.0, 5.0,
+        0.0, 0.0, 0.0, 1.0,
+    ];
+    assert_eq!(Some(expected), camera.local_matrix());
+
+    // Transforms keep the order they have in the document.
+    let armature = scene.node("Armature").unwrap();
+    assert_eq!(vec!["characters", "rigs"], armature.layers().collect::<Vec<_>>());
+    let sids = armature.transforms.iter().map(Transform::sid).collect::<Vec<_>>();
+    assert_eq!(vec![Some("location"), Some("rotationZ"), Some("scale")], sids);
+    match armature.transforms[1] {
+        Transform::Rotate(ref rotate) => assert_eq!(Some(([0.0, 0.0, 1.0], 90.0)), rotate.axis_angle()),
+        ref transform => panic!("Expected a rotate transform, got {:?}", transform),
+    }
+    let matrix = armature.local_matrix().unwrap();
+    let expected = [
+        0.0, -2.0, 0.0, 1.0,
+        2.0, 0.0, 0.0, 2.0,
+        0.0, 0.0, 2.0, 3.0,
+        0.0, 0.0, 0.0, 1.0,
+    ];
+    for (actual, expected) in matrix.iter().zip(expected.iter()) {
+        assert!((actual - expected).abs() < 1e-6, "Unexpected local matrix {:?}", matrix);
+    }
+    assert_eq!("#Root", armature.instance_controllers[0].skeletons[0].as_str());
+    assert_eq!("#Lamp-light", armature.instance_lights[0].url.as_str());
+
+    // Nodes are found anywhere in the hierarchy.
+    let root = scene.node("Root").unwrap();
+    assert_eq!(NodeType::Joint, root.node_type);
+    assert_eq!(Some("Root"), root.sid.as_deref());
+    assert_eq!(Some("Cube"), root.instance_geometries[0].name.as_deref());
+    assert_eq!("#Shared", root.instance_nodes[0].url.as_str());
+    let expected = [
+        1.0, 1.0, 0.0, 0.0,
+        0.0, 1.0, 0.0, 0.0,
+        0.0, 0.0, 1.0, 4.0,
+        0.0, 0.0, 0.0, 1.0,
+    ];
+    let matrix = root.local_matrix().unwrap();
+    for (actual, expected) in matrix.iter().zip(expected.iter()) {
+        assert!((actual - expected).abs() < 1e-6, "Unexpected local matrix {:?}", matrix);
+    }
+
+    // Transforms with the wrong number of values can't be combined.
+    let document = Collada::from_str(&DOCUMENT.replace("1 2 3", "1 2")).unwrap();
+    let armature = document.visual_scene("Scene").unwrap().node("Armature").unwrap();
+    assert_eq!(None, armature.local_matrix());
+
+    match Collada::from_str(&DOCUMENT.replace("JOINT", "BONE")) {
+        Err(Error { kind: ErrorKind::InvalidValue { element: "node", ref value }, .. }) => assert_eq!("BONE", value),
+        result => panic!("Expected an invalid value error, got {:?}", result.map(|_| ())),
+    }
+
+    // Transforms come before anything the node instantiates.
+    let document = DOCUMENT.replace(
+        r##"<instance_camera url="#Camera-camera"/>"##,
+        r##"<instance_camera url="#Camera-camera"/><translate>1 0 0</translate>"##,
+    );
+    match Collada::from_str(&document) {
+        Err(Error { kind: ErrorKind::UnexpectedElement { parent: "node", ref element, .. }, .. }) => {
+            assert_eq!("translate", element);
+        }
+        result => panic!("Expected an unexpected element error, got {:?}", result.map(|_| ())),
+    }
+}
+
+#[test]
+fn visual_scene_iter_nodes() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_visual_scenes>
+            <visual_scene id="Scene">
+                <node id="Armature">
+                    <translate>1 0 0</translate>
+                    <node id="Hip">
+                        <scale>2 2 2</scale>
+                        <node id="Knee">
+                            <translate>0 1 0</translate>
+                        </node>
+                    </node>
+                    <node id="Spine" />
+                </node>
+                <node id="Camera" />
+            </visual_scene>
+        </library_visual_scenes>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let scene = document.visual_scene("Scene").unwrap();
+
+    // Nodes are visited depth-first, each before its children.
+    let visited = scene.iter_nodes()
+        .map(|(node, ancestry)| {
+            let ancestors = ancestry.nodes().iter().map(|node| node.id.as_deref().unwrap()).collect::<Vec<_>>();
+            (node.id.as_deref().unwrap(), ancestors)
+        })
+        .collect::<Vec<_>>();
+    let expected = vec![
+        ("Armature", vec![]),
+        ("Hip", vec!["Armature"]),
+        ("Knee", vec!["Armature", "Hip"]),
+        ("Spine", vec!["Armature"]),
+        ("Camera", vec![]),
+    ];
+    assert_eq!(expected, visited);
+
+    // Transforms accumulate down the hierarchy.
+    let (knee, ancestry) = scene.iter_nodes().nth(2).unwrap();
+    assert_eq!(2, ancestry.depth());
+    assert_eq!(Some("Hip"), ancestry.parent().and_then(|parent| parent.id.as_deref()));
+    let expected = [
+        2.0, 0.0, 0.0, 1.0,
+        0.0, 2.0, 0.0, 0.0,
+        0.0, 0.0, 2.0, 0.0,
+        0.0, 0.0, 0.0, 1.0,
+    ];
+    assert_eq!(Some(expected), ancestry.matrix());
+    let expected = [
+        2.0, 0.0, 0.0, 1.0,
+        0.0, 2.0, 0.0, 2.0,
+        0.0, 0.0, 2.0, 0.0,
+        0.0, 0.0, 0.0, 1.0,
+    ];
+    assert_eq!(Some(expected), ancestry.world_matrix(knee));
+
+    let (camera, ancestry) = scene.iter_nodes().last().unwrap();
+    assert_eq!(None, ancestry.parent());
+    assert_eq!(camera.local_matrix(), ancestry.world_matrix(camera));
+}
+
+#[test]
+fn node_other_attributes() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_visual_scenes>
+            <visual_scene id="Scene">
+                <node id="Box" xmlns:max="http://example.com/max" max:handle="12" frozen="true">
+                    <node id="Lid" />
+                </node>
+            </visual_scene>
+        </library_visual_scenes>
+    </COLLADA>
+    "##;
+
+    // Unknown attributes are collected instead of being rejected.
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let scene = document.visual_scene("Scene").unwrap();
+    let attributes = scene.nodes[0].other_attributes.iter()
+        .map(|(name, value)| (name.borrow().to_repr(), &**value))
+        .collect::<Vec<_>>();
+    assert_eq!(vec![("max:handle".to_string(), "12"), ("frozen".to_string(), "true")], attributes);
+    assert_eq!(Some("http://example.com/max"), scene.nodes[0].other_attributes[0].0.namespace.as_deref());
+    assert!(scene.node("Lid").unwrap().other_attributes.is_empty());
+
+    // They're written back out with their namespaces declared.
+    let mut written = Vec::new();
+    document.write(&mut written, WriteOptions::default()).unwrap();
+    let written = String::from_utf8(written).unwrap();
+    assert!(written.contains(r#"xmlns:max="http://example.com/max" max:handle="12" frozen="true""#), "{}", written);
+    assert_eq!(document, Collada::from_str(&written).unwrap());
+}
+
+#[test]
+fn library_nodes() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_nodes id="components">
+            <node id="Chair" name="Chair">
+                <instance_geometry url="#Seat-mesh"/>
+                <node id="Chair-leg">
+                    <translate>0.5 0 0</translate>
+                    <instance_geometry url="#Leg-mesh"/>
+                </node>
+            </node>
+        </library_nodes>
+        <library_visual_scenes>
+            <visual_scene id="Scene">
+                <node id="Kitchen">
+                    <node id="Chair-1">
+                        <translate>1 0 0</translate>
+                        <instance_node url="#Chair"/>
+                    </node>
+                    <node id="Chair-2">
+                        <translate>2 0 0</translate>
+                        <instance_node url="#Chair-leg"/>
+                        <instance_node url="#Kitchen"/>
+                    </node>
+                </node>
+            </visual_scene>
+        </library_visual_scenes>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_nodes().unwrap();
+    assert_eq!(Some("components"), library.id.as_deref());
+    assert_eq!(1, library.nodes.len());
+
+    // Instances resolve into the shared hierarchy, including nested nodes.
+    let scene = document.visual_scene("Scene").unwrap();
+    let first = scene.node("Chair-1").unwrap();
+    let chair = document.instance_node(&first.instance_nodes[0]).unwrap();
+    assert_eq!(Some("Chair"), chair.name.as_deref());
+    assert_eq!("#Leg-mesh", chair.nodes[0].instance_geometries[0].url.as_str());
+
+    let second = scene.node("Chair-2").unwrap();
+    let leg = document.instance_node(&second.instance_nodes[0]).unwrap();
+    assert_eq!(Some("Chair-leg"), leg.id.as_deref());
+    assert_eq!(Some(&chair.nodes[0]), library.node("Chair-leg"));
+
+    // Nodes in the visual scenes can be instantiated too.
+    let kitchen = document.instance_node(&second.instance_nodes[1]).unwrap();
+    assert_eq!(2, kitchen.nodes.len());
+    assert_eq!(None, document.node("Table"));
+}
+
+#[test]
+fn physics() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_physics_materials>
+            <physics_material id="Rubber">
+                <technique_common>
+                    <dynamic_friction>0.8</dynamic_friction>
+                    <restitution sid="bounce">0.9</restitution>
+                </technique_common>
+            </physics_material>
+        </library_physics_materials>
+        <library_physics_models>
+            <physics_model id="Ragdoll">
+                <rigid_body sid="torso">
+                    <technique_common>
+                        <dynamic>true</dynamic>
+                        <mass>40</mass>
+                        <mass_frame>
+                            <translate>0 1 0</translate>
+                            <rotate>0 1 0 90</rotate>
+                        </mass_frame>
+                        <instance_physics_material url="#Rubber"/>
+                        <shape>
+                            <box>
+                                <half_extents>0.5 1 0.25</half_extents>
+                            </box>
+                        </shape>
+                        <shape>
+                            <hollow>false</hollow>
+                            <density>2</density>
+                            <physics_material>
+                                <technique_common>
+                                    <static_friction>0.5</static_friction>
+                                </technique_common>
+                            </physics_material>
+                            <capsule>
+                                <height>2</height>
+                                <radius>0.5 0.5</radius>
+                            </capsule>
+                            <translate>0 2 0</translate>
+                        </shape>
+                    </technique_common>
+                </rigid_body>
+                <rigid_body sid="head">
+                    <technique_common>
+                        <shape><sphere><radius>0.3</radius></sphere></shape>
+                        <shape><instance_geometry url="#Head-hull"/></shape>
+                        <shape><plane><equation>0 1 0 0</equation></plane></shape>
+                        <shape>
+                            <tapered_cylinder>
+                                <height>1</height>
+                                <radius1>1 1</radius1>
+                                <radius2>0.5 0.5</radius2>
+                            </tapered_cylinder>
+                        </shape>
+                    </technique_common>
+                </rigid_body>
+                <rigid_body sid="ghost">
+                    <technique_common>
+                        <shape><sphere><radius>1</radius></sphere></shape>
+                    </technique_common>
+                </rigid_body>
+                <rigid_constraint sid="neck">
+                    <ref_attachment rigid_body="#torso">
+                        <translate>0 2 0</translate>
+                    </ref_attachment>
+                    <attachment rigid_body="#head"/>
+                    <technique_common>
+                        <enabled>true</enabled>
+                        <interpenetrate>false</interpenetrate>
+                        <limits>
+                            <swing_cone_and_twist>
+                                <min>-45 -45 -10</min>
+                                <max>45 45 10</max>
+                            </swing_cone_and_twist>
+                        </limits>
+                        <spring>
+                            <angular>
+                                <stiffness>10</stiffness>
+                            </angular>
+                        </spring>
+                    </technique_common>
+                </rigid_constraint>
+            </physics_model>
+        </library_physics_models>
+        <library_physics_scenes>
+            <physics_scene id="World">
+                <instance_physics_model url="#Ragdoll" parent="#Root">
+                    <instance_rigid_body body="torso" target="#Torso-node">
+                        <technique_common>
+                            <velocity>0 0 1</velocity>
+                        </technique_common>
+                    </instance_rigid_body>
+                    <instance_rigid_constraint constraint="neck"/>
+                </instance_physics_model>
+                <technique_common>
+                    <gravity>0 -9.8 0</gravity>
+                    <time_step>0.016</time_step>
+                </technique_common>
+            </physics_scene>
+        </library_physics_scenes>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+
+    let rubber = document.physics_material("Rubber").unwrap();
+    assert_eq!(0.8, rubber.dynamic_friction());
+    assert_eq!(0.9, rubber.restitution());
+    assert_eq!(0.0, rubber.static_friction());
+    assert_eq!(
+        Some("bounce"),
+        rubber.technique_common.restitution.as_ref().and_then(|value| value.sid.as_deref()),
+    );
+
+    let model = document.physics_model("Ragdoll").unwrap();
+    let torso = &model.rigid_body("torso").unwrap().technique_common;
+    assert_eq!(Some(true), torso.dynamic.as_ref().map(|dynamic| dynamic.value));
+    assert_eq!(Some(40.0), torso.mass.as_ref().map(|mass| mass.value));
+    let mass_frame = torso.mass_frame.as_ref().unwrap();
+    assert_eq!(2, mass_frame.transforms.len());
+    assert_eq!(1.0, mass_frame.transforms[0].matrix().unwrap()[7]);
+    match torso.physics_material {
+        Some(PhysicsMaterialSource::Instance(ref instance)) => assert_eq!("#Rubber", instance.url.as_str()),
+        ref material => panic!("Expected an instanced physics material, got {:?}", material),
+    }
+
+    assert_eq!(2, torso.shapes.len());
+    match torso.shapes[0].geometry {
+        ShapeGeometry::Box(ref shape) => assert_eq!(vec![0.5, 1.0, 0.25], shape.half_extents.values),
+        ref geometry => panic!("Expected a box, got {:?}", geometry),
+    }
+    let capsule = &torso.shapes[1];
+    assert_eq!(Some(false), capsule.hollow.as_ref().map(|hollow| hollow.value));
+    assert_eq!(Some(2.0), capsule.density.as_ref().map(|density| density.value));
+    assert_eq!(1, capsule.transforms.len());
+    match capsule.physics_material {
+        Some(PhysicsMaterialSource::Declared(ref material)) => assert_eq!(0.5, material.static_friction()),
+        ref material => panic!("Expected a declared physics material, got {:?}", material),
+    }
+    match capsule.geometry {
+        ShapeGeometry::Capsule(ref shape) => {
+            assert_eq!(2.0, shape.height.value);
+            assert_eq!(vec![0.5, 0.5], shape.radius.values);
+        }
+        ref geometry => panic!("Expected a capsule, got {:?}", geometry),
+    }
+
+    let head = &model.rigid_body("head").unwrap().technique_common;
+    match head.shapes[0].geometry {
+        ShapeGeometry::Sphere(ref shape) => assert_eq!(0.3, shape.radius.value),
+        ref geometry => panic!("Expected a sphere, got {:?}", geometry),
+    }
+    match head.shapes[1].geometry {
+        ShapeGeometry::InstanceGeometry(ref instance) => assert_eq!("#Head-hull", instance.url.as_str()),
+        ref geometry => panic!("Expected a geometry instance, got {:?}", geometry),
+    }
+    match head.shapes[2].geometry {
+        ShapeGeometry::Plane(ref shape) => assert_eq!(vec![0.0, 1.0, 0.0, 0.0], shape.equation.values),
+        ref geometry => panic!("Expected a plane, got {:?}", geometry),
+    }
+    match head.shapes[3].geometry {
+        ShapeGeometry::TaperedCylinder(ref shape) => assert_eq!(vec![0.5, 0.5], shape.radius2.values),
+        ref geometry => panic!("Expected a tapered cylinder, got {:?}", geometry),
+    }
+
+    let neck = model.rigid_constraint("neck").unwrap();
+    assert_eq!("#torso", neck.ref_attachment.rigid_body.as_str());
+    assert_eq!(1, neck.ref_attachment.transforms.len());
+    assert_eq!("#head", neck.attachment.rigid_body.as_str());
+    let technique = &neck.technique_common;
+    assert_eq!(Some(true), technique.enabled.as_ref().map(|enabled| enabled.value));
+    assert_eq!(Some(false), technique.interpenetrate.as_ref().map(|value| value.value));
+    let swing = technique.limits.as_ref().and_then(|limits| limits.swing_cone_and_twist.as_ref()).unwrap();
+    assert_eq!(Some(&vec![45.0, 45.0, 10.0]), swing.max.as_ref().map(|max| &max.values));
+    let angular = technique.spring.as_ref().and_then(|spring| spring.angular.as_ref()).unwrap();
+    assert_eq!(Some(10.0), angular.stiffness.as_ref().map(|stiffness| stiffness.value));
+    assert_eq!(None, angular.damping);
+
+    let scene = document.physics_scene("World").unwrap();
+    assert_eq!(Some([0.0, -9.8, 0.0]), scene.gravity());
+    assert_eq!(Some(0.016), scene.time_step());
+    let instance = &scene.instance_physics_models[0];
+    assert_eq!(Some("#Root"), instance.parent.as_ref().map(AnyUri::as_str));
+    let body = instance.instance_rigid_body("torso").unwrap();
+    assert_eq!("#Torso-node", body.target.as_str());
+    assert_eq!(Some(&vec![0.0, 0.0, 1.0]), body.technique_common.velocity.as_ref().map(|velocity| &velocity.values));
+    assert_eq!(Some([0.0, 0.0, 1.0]), body.initial_velocity());
+    assert_eq!(Some([0.0; 3]), body.initial_angular_velocity());
+    assert!(body.is_dynamic(model.rigid_body("torso").unwrap()));
+    assert_eq!("neck", instance.instance_rigid_constraints[0].constraint);
+
+    // An instance can make a dynamic body static.
+    let fixed = DOCUMENT.replace("<velocity>0 0 1</velocity>", "<dynamic>false</dynamic>");
+    let fixed = Collada::from_str(&fixed).unwrap();
+    let scene = fixed.physics_scene("World").unwrap();
+    let body = scene.instance_physics_models[0].instance_rigid_body("torso").unwrap();
+    let torso = fixed.physics_model("Ragdoll").unwrap().rigid_body("torso").unwrap();
+    assert!(torso.is_dynamic());
+    assert!(!body.is_dynamic(torso));
+
+    // Every rigid body needs at least one shape.
+    let invalid = DOCUMENT.replace("<shape><sphere><radius>1</radius></sphere></shape>", "");
+    assert!(Collada::from_str(&invalid).is_err());
+}
+
+#[test]
+fn library_force_fields() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_force_fields id="forces">
+            <force_field id="Wind" name="Wind">
+                <technique profile="PhysX">
+                    <wind strength="2">1 0 0</wind>
+                </technique>
+            </force_field>
+        </library_force_fields>
+        <library_physics_scenes>
+            <physics_scene id="World">
+                <instance_force_field url="#Wind" sid="wind"/>
+                <instance_physics_model url="#Kite">
+                    <instance_force_field url="#Gust"/>
+                </instance_physics_model>
+                <technique_common/>
+            </physics_scene>
+        </library_physics_scenes>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_force_fields().unwrap();
+    assert_eq!(Some("forces"), library.id.as_deref());
+    let wind = library.force_field("Wind").unwrap();
+    assert_eq!("PhysX", wind.techniques[0].profile);
+
+    let scene = document.physics_scene("World").unwrap();
+    assert_eq!(Some("wind"), scene.instance_force_fields[0].sid.as_deref());
+    assert_eq!(Some(wind), document.instance_force_field(&scene.instance_force_fields[0]));
+
+    // Force fields that aren't in the document don't resolve.
+    let model = &scene.instance_physics_models[0];
+    assert_eq!(None, document.instance_force_field(&model.instance_force_fields[0]));
+
+    // A force field must have at least one technique.
+    let invalid = DOCUMENT.replace(r#"<wind strength="2">1 0 0</wind>"#, "")
+        .replace(r#"<technique profile="PhysX">"#, "")
+        .replace("</technique>", "");
+    match Collada::from_str(&invalid).unwrap_err().kind {
+        ErrorKind::MissingRequiredChild { parent: "force_field", ref expected } => {
+            assert_eq!(&["technique"], &**expected);
+        }
+        kind => panic!("Expected a missing technique error, got {:?}", kind),
+    }
+}
+
+#[test]
+fn bind_material() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_materials>
+            <material id="Red-material" name="Red">
+                <instance_effect url="#Red-effect"/>
+            </material>
+            <material id="Blue-material" name="Blue">
+                <instance_effect url="#Blue-effect"/>
+            </material>
+        </library_materials>
+        <library_visual_scenes>
+            <visual_scene id="Scene">
+                <node id="Left">
+                    <instance_geometry url="#Cube-mesh">
+                        <bind_material>
+                            <param sid="color" type="float3"/>
+                            <technique_common>
+                                <instance_material symbol="Surface" target="#Red-material">
+                                    <bind semantic="LIGHT_POS" target="Lamp/translate"/>
+                                    <bind_vertex_input semantic="UVMap" input_semantic="TEXCOORD" input_set="1"/>
+                                </instance_material>
+                            </technique_common>
+                        </bind_material>
+                    </instance_geometry>
+                </node>
+                <node id="Right">
+                    <instance_controller url="#Cube-skin">
+                        <skeleton>#Root</skeleton>
+                        <bind_material>
+                            <technique_common>
+                                <instance_material symbol="Surface" target="#Blue-material"/>
+                            </technique_common>
+                        </bind_material>
+                    </instance_controller>
+                </node>
+            </visual_scene>
+        </library_visual_scenes>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let scene = document.visual_scene("Scene").unwrap();
+
+    // The same symbol is bound to a different material by each instance.
+    let left = &scene.node("Left").unwrap().instance_geometries[0];
+    let instance = left.instance_material("Surface").unwrap();
+    assert_eq!("#Red-material", instance.target.as_str());
+    assert_eq!(Some("Red"), document.bound_material(instance).unwrap().name.as_deref());
+    assert_eq!(Some("color"), left.bind_material.as_ref().unwrap().params[0].sid.as_deref());
+    assert_eq!("Lamp/translate", instance.binds[0].target);
+
+    let input = instance.bind_vertex_input("UVMap").unwrap();
+    assert_eq!(Semantic::TexCoord(None), input.input_semantic);
+    assert_eq!(Some(1), input.input_set);
+    assert_eq!(None, instance.bind_vertex_input("Missing"));
+
+    let right = &scene.node("Right").unwrap().instance_controllers[0];
+    let instance = right.instance_material("Surface").unwrap();
+    assert_eq!(Some("Blue"), document.bound_material(instance).unwrap().name.as_deref());
+    assert_eq!(None, right.instance_material("Missing"));
+
+    // Every instance material needs a symbol to bind.
+    match Collada::from_str(&DOCUMENT.replace(r##"symbol="Surface" target="#Blue-material""##, r##"target="#Blue-material""##)) {
+        Err(Error { kind: ErrorKind::MissingAttribute { element: "instance_material", attribute }, .. }) => {
+            assert_eq!("symbol", attribute);
+        }
+        result => panic!("Expected a missing attribute error, got {:?}", result.map(|_| ())),
+    }
+}
+
+#[test]
+fn float_array_number_formats() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry>
+                <mesh>
+                    <source id="positions">
+                        <float_array id="positions-array" count="6">
+                            -1 .5 2.
+                            1e3	-2.5E-1 +4
+                        </float_array>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#positions" />
+                    </vertices>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    let array = mesh.sources[0].array.as_ref().and_then(Array::as_float_array).unwrap();
+    assert_eq!(&[-1.0, 0.5, 2.0, 1000.0, -0.25, 4.0], &*array.data);
+
+    let invalid = DOCUMENT.replace("+4", "4x");
+    match Collada::from_str(&invalid) {
+        Err(Error { kind: ErrorKind::ParseFloatError(_), .. }) => {}
+        result => panic!("Expected a float parsing error, got {:?}", result),
+    }
+}
+
+#[test]
+fn locale_numbers() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+            <unit meter="0,01" name="centimeter" />
+        </asset>
+        <library_geometries>
+            <geometry>
+                <mesh>
+                    <source id="positions">
+                        <float_array id="positions-array" count="4">0,5 -1.234,5 1,234.5 2</float_array>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#positions" />
+                    </vertices>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    match Collada::from_str(DOCUMENT) {
+        Err(Error { kind: ErrorKind::ParseFloatError(_), .. }) => {}
+        result => panic!("Expected a float parsing error, got {:?}", result.map(|_| ())),
+    }
+
+    let options = ParseOptions {
+        locale_numbers: true,
+        ..ParseOptions::default()
+    };
+    let (document, report) = Collada::read_with(DOCUMENT.as_bytes(), options).unwrap();
+    assert_eq!(0.01, document.asset.unit().meter);
+    assert_eq!(Some(&2), report.fallbacks.get("number formatted for another locale"));
+
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    let array = mesh.sources[0].array.as_ref().and_then(Array::as_float_array).unwrap();
+    assert_eq!(&[0.5, -1234.5, 1234.5, 2.0], &*array.data);
+
+    // Lenient parsing accepts them too.
+    let options = ParseOptions {
+        strictness: Strictness::Lenient,
+        ..ParseOptions::default()
+    };
+    assert_eq!(document, Collada::read_with(DOCUMENT.as_bytes(), options).unwrap().0);
+
+    // Commas that can't be separators in any locale are still rejected.
+    let invalid = DOCUMENT.replace("0,01", "0,0,1");
+    let options = ParseOptions {
+        locale_numbers: true,
+        ..ParseOptions::default()
+    };
+    assert!(Collada::read_with(invalid.as_bytes(), options).is_err());
+}
+
+#[test]
+fn raw_array_deferred_decoding() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry>
+                <mesh>
+                    <source id="indices">
+                        <int_array id="indices-array" count="4">0 -1 2 bogus</int_array>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#indices" />
+                    </vertices>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    // By default the malformed value fails the parse.
+    match Collada::from_str(DOCUMENT) {
+        Err(Error { kind: ErrorKind::ParseIntError(_), .. }) => {}
+        result => panic!("Expected an int parsing error, got {:?}", result),
+    }
+
+    // When decoding is deferred, it doesn't prevent the document from loading.
+    let options = ParseOptions {
+        defer_arrays: true,
+        ..ParseOptions::default()
+    };
+    let (document, _) = Collada::read_with(DOCUMENT.as_bytes(), options.clone()).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    let array = mesh.sources[0].array.as_ref().and_then(Array::as_int_array).unwrap();
+    assert_eq!(Some("indices-array"), mesh.sources[0].array.as_ref().and_then(Array::id));
+    assert_eq!(-2147483648, array.min_inclusive);
+    assert_eq!(Some("0 -1 2 bogus"), array.data.raw_text());
+    assert!(!array.data.is_decoded());
+
+    // Instead the error is reported once the array is accessed, pointing at the array.
+    match array.data.get() {
+        Err(Error { kind: ErrorKind::ParseIntError(_), position }) => {
+            assert_eq!(11, position.row);
+        }
+        result => panic!("Expected an int parsing error, got {:?}", result),
+    }
+    assert!(!array.data.is_decoded());
+
+    let valid = DOCUMENT.replace("bogus", "3");
+    let (deferred, _) = Collada::read_with(valid.as_bytes(), options).unwrap();
+    let library = deferred.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    let array = mesh.sources[0].array.as_ref().and_then(Array::as_int_array).unwrap();
+    assert_eq!(&[0, -1, 2, 3], &*array.data);
+    assert!(array.data.is_decoded());
+
+    // Deferred arrays are equal to decoded ones with the same values.
+    assert_eq!(Collada::from_str(&valid).unwrap(), deferred);
+}
+
+#[test]
+fn raw_array_equality() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry>
+                <mesh>
+                    <source id="positions">
+                        <float_array id="positions-array" count="3">1 2 0.5</float_array>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#positions" />
+                    </vertices>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let options = ParseOptions {
+        defer_arrays: true,
+        ..ParseOptions::default()
+    };
+    let parse = |document: &str| Collada::read_with(document.as_bytes(), options.clone()).unwrap().0;
+
+    // Arrays are compared by their values, not by how they're formatted.
+    let expected = parse(DOCUMENT);
+    assert_eq!(expected, parse(&DOCUMENT.replace("1 2 0.5", "1.0  2\n.5")));
+    assert_ne!(expected, parse(&DOCUMENT.replace("1 2 0.5", "1 2 0.25")));
+
+    // Arrays that fail to decode are compared by their text.
+    let invalid = parse(&DOCUMENT.replace("0.5", "bogus"));
+    assert_eq!(invalid, parse(&DOCUMENT.replace("0.5", "bogus")));
+    assert_ne!(invalid, parse(&DOCUMENT.replace("0.5", "other")));
+    assert_ne!(invalid, expected);
+
+    // Arrays can also be built from decoded values.
+    let library = expected.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    let array = mesh.sources[0].array.as_ref().and_then(Array::as_float_array).unwrap();
+    assert_eq!(RawArray::from(vec![1.0, 2.0, 0.5]), array.data);
+}
+
+#[test]
+fn vendor_extras() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry id="Plane-mesh">
+                <mesh>
+                    <source id="Plane-positions">
+                        <float_array id="Plane-positions-array" count="3">0 0 0</float_array>
+                    </source>
+                    <vertices id="Plane-vertices">
+                        <input semantic="POSITION" source="#Plane-positions" />
+                    </vertices>
+                </mesh>
+                <extra>
+                    <technique profile="MAYA">
+                        <double_sided>1</double_sided>
+                    </technique>
+                    <technique profile="OpenCOLLADA">
+                        <technique profile="nested"><flag /></technique>
+                    </technique>
+                </extra>
+                <extra type="import">
+                    <technique profile="blender">
+                        <smooth_shading>1</smooth_shading>
+                    </technique>
+                </extra>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let geometry = document.geometry("Plane-mesh").unwrap();
+    assert_eq!(2, geometry.extras.len());
+    assert_eq!(Some("import".into()), geometry.extras[1].type_hint);
+
+    let maya = geometry.extras[0].technique("MAYA").unwrap();
+    assert_eq!("double_sided", maya.elements()[0].name.local_name);
+    assert!(geometry.extras[0].technique("blender").is_none());
+
+    // Techniques can be found across all of an element's extras.
+    let blender = Extra::find_technique(&geometry.extras, "blender").unwrap();
+    assert_eq!("1", blender.elements()[0].text);
+    assert!(Extra::find_technique(&geometry.extras, "FCOLLADA").is_none());
+
+    // Nested techniques with the same name are kept intact.
+    let open_collada = Extra::find_technique(&geometry.extras, "OpenCOLLADA").unwrap();
+    let elements = open_collada.elements();
+    assert_eq!(1, elements.len());
+    assert_eq!("technique", elements[0].name.local_name);
+    assert_eq!("flag", elements[0].children[0].name.local_name);
+}
+
+#[test]
+fn scene() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_physics_scenes>
+            <physics_scene id="World">
+                <technique_common />
+            </physics_scene>
+        </library_physics_scenes>
+        <library_visual_scenes>
+            <visual_scene id="Draft">
+                <node id="Placeholder" />
+            </visual_scene>
+            <visual_scene id="Final">
+                <node id="Root" />
+            </visual_scene>
+        </library_visual_scenes>
+        <scene>
+            <instance_physics_scene url="#World" sid="physics" />
+            <instance_physics_scene url="external.dae#Wind" />
+            <instance_visual_scene url="#Final" name="Final scene" />
+            <extra>
+                <technique profile="blender" />
+            </extra>
+        </scene>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let scene = document.scene.as_ref().unwrap();
+    assert_eq!(1, scene.extras.len());
+
+    let main_scene = document.main_scene().unwrap();
+    assert_eq!(Some("Final".into()), main_scene.id);
+    assert!(main_scene.node("Root").is_some());
+
+    let instances = &scene.instance_physics_scenes;
+    assert_eq!(2, instances.len());
+    assert_eq!(Some("physics".into()), instances[0].sid);
+    assert!(document.instance_physics_scene(&instances[0]).is_some());
+    assert!(document.instance_physics_scene(&instances[1]).is_none());
+
+    // The scene doesn't have to instantiate a visual scene.
+    let no_visual_scene = DOCUMENT.replace(r##"<instance_visual_scene url="#Final" name="Final scene" />"##, "");
+    let document = Collada::from_str(&no_visual_scene).unwrap();
+    assert!(document.scene.is_some());
+    assert!(document.main_scene().is_none());
+
+    // The visual scene instance must come after the physics scene instances.
+    let wrong_order = DOCUMENT.replace(
+        r##"<instance_physics_scene url="external.dae#Wind" />"##,
+        r##"<instance_visual_scene url="#Draft" /><instance_physics_scene url="external.dae#Wind" />"##,
+    );
+    match Collada::from_str(&wrong_order) {
+        Err(Error { kind: ErrorKind::UnexpectedElement { parent: "scene", .. }, .. }) => {}
+        result => panic!("Expected an unexpected element error, got {:?}", result),
+    }
+}
+
+#[test]
+fn evaluate_scene() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_visual_scenes>
+            <visual_scene id="Scene">
+                <node id="Camera" />
+                <node id="Ground" layer="background" />
+                <node id="Player" layer="characters highlighted" />
+                <node id="Hud" layer="overlay" />
+                <evaluate_scene name="passes">
+                    <render camera_node="#Camera">
+                        <layer>background</layer>
+                        <layer>characters</layer>
+                    </render>
+                    <render camera_node="#Camera">
+                        <layer>highlighted</layer>
+                        <instance_effect url="#Outline-effect" />
+                    </render>
+                </evaluate_scene>
+                <evaluate_scene>
+                    <render camera_node="#Missing" />
+                </evaluate_scene>
+            </visual_scene>
+        </library_visual_scenes>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let scene = document.visual_scene("Scene").unwrap();
+    assert_eq!(2, scene.evaluate_scenes.len());
+
+    let passes = &scene.evaluate_scenes[0];
+    assert_eq!(Some("passes".into()), passes.name);
+    assert_eq!(2, passes.renders.len());
+
+    let main = &passes.renders[0];
+    assert_eq!(vec!["background", "characters"], main.layers);
+    assert!(main.instance_effect.is_none());
+    assert_eq!(Some("Camera".into()), document.render_camera(main).unwrap().id);
+    assert!(main.renders_node(scene.node("Ground").unwrap()));
+    assert!(main.renders_node(scene.node("Player").unwrap()));
+    assert!(!main.renders_node(scene.node("Hud").unwrap()));
+
+    let outline = &passes.renders[1];
+    assert_eq!(Some("Outline-effect"), outline.instance_effect.as_ref().unwrap().url.local_id());
+    assert!(outline.renders_node(scene.node("Player").unwrap()));
+    assert!(!outline.renders_node(scene.node("Ground").unwrap()));
+
+    // A render without layers renders everything.
+    let everything = &scene.evaluate_scenes[1].renders[0];
+    assert!(everything.renders_node(scene.node("Hud").unwrap()));
+    assert!(document.render_camera(everything).is_none());
+
+    // Every evaluation needs at least one render.
+    let empty = DOCUMENT.replace(r##"<render camera_node="#Missing" />"##, "");
+    match Collada::from_str(&empty) {
+        Err(Error { kind: ErrorKind::MissingRequiredChild { parent: "evaluate_scene", .. }, .. }) => {}
+        result => panic!("Expected a missing child error, got {:?}", result),
+    }
+}
@@ -25,16 +25,13 @@ fn collada_asset_minimal() {
             contributors: vec![],
             coverage: None,
             created: "2017-02-07T20:44:30Z".parse().unwrap(),
-            keywords: None,
+            keywords: MaybeEmpty::Absent,
             modified: "2017-02-07T20:44:30Z".parse().unwrap(),
-            revision: None,
-            subject: None,
-            title: None,
-            unit: Unit {
-                meter: 1.0,
-                name: "meter".into(),
-            },
-            up_axis: UpAxis::Y,
+            revision: MaybeEmpty::Absent,
+            subject: MaybeEmpty::Absent,
+            title: MaybeEmpty::Absent,
+            unit: None,
+            up_axis: None,
             extras: vec![],
         },
         libraries: Vec::new(),
@@ -44,6 +41,7 @@ fn collada_asset_minimal() {
 
     let actual = Collada::from_str(DOCUMENT).unwrap();
     assert_eq!(expected, actual);
+    assert_eq!(CoordinateSystem::default(), actual.coordinate_system());
 }
 
 #[test]
@@ -139,8 +137,12 @@ fn asset_full() {
             <title>Model of a thing</title>
             <unit meter="7" name="septimeter" />
             <up_axis>Z_UP</up_axis>
-            <extra />
-            <extra />
+            <extra>
+                <technique profile="foo" />
+            </extra>
+            <extra>
+                <technique profile="bar" />
+            </extra>
         </asset>
     </COLLADA>
     "#;
@@ -155,21 +157,83 @@ fn asset_full() {
             }),
         }),
         created: "2017-02-07T20:44:30Z".parse().unwrap(),
-        keywords: Some("foo bar baz".into()),
+        keywords: MaybeEmpty::Value("foo bar baz".into()),
         modified: "2017-02-07T20:44:30Z".parse().unwrap(),
-        revision: Some("7".into()),
-        subject: Some("A thing".into()),
-        title: Some("Model of a thing".into()),
-        unit: Unit {
+        revision: MaybeEmpty::Value("7".into()),
+        subject: MaybeEmpty::Value("A thing".into()),
+        title: MaybeEmpty::Value("Model of a thing".into()),
+        unit: Some(Unit {
             meter: 7.0,
             name: "septimeter".into(),
-        },
-        up_axis: UpAxis::Z,
-        extras: vec![Extra::default(), Extra::default()],
+        }),
+        up_axis: Some(UpAxis::Z),
+        extras: vec![
+            Extra {
+                techniques: vec![Technique { profile: "foo".into(), xmlns: None, data: Vec::default() }],
+                .. Extra::default()
+            },
+            Extra {
+                techniques: vec![Technique { profile: "bar".into(), xmlns: None, data: Vec::default() }],
+                .. Extra::default()
+            },
+        ],
     };
 
     let collada = Collada::from_str(DOCUMENT).unwrap();
     assert_eq!(expected, collada.asset);
+    assert_eq!(vec!["foo", "bar", "baz"], collada.asset.keywords().collect::<Vec<_>>());
+}
+
+#[test]
+fn asset_geographic_location() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.5.0">
+        <asset>
+            <coverage>
+                <geographic_location>
+                    <longitude>-122.4194</longitude>
+                    <latitude>37.7749</latitude>
+                    <altitude mode="absolute">16.5</altitude>
+                </geographic_location>
+            </coverage>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+    </COLLADA>
+    "#;
+
+    let collada = Collada::from_str(DOCUMENT).unwrap();
+    let location = collada.asset.geographic_location().unwrap();
+    assert_eq!(-122.4194, location.longitude);
+    assert_eq!(37.7749, location.latitude);
+    assert_eq!(Altitude::Absolute(16.5), location.altitude);
+    assert_eq!(16.5, location.altitude.value());
+    assert_eq!("absolute", location.altitude.mode());
+    assert!(!location.altitude.is_relative_to_ground());
+
+    let relative = Collada::from_str(&DOCUMENT.replace("\"absolute\"", "\"relativeToGround\"")).unwrap();
+    let altitude = &relative.asset.geographic_location().unwrap().altitude;
+    assert_eq!(Altitude::RelativeToGround(16.5), *altitude);
+    assert_eq!("relativeToGround", altitude.mode());
+    assert!(altitude.is_relative_to_ground());
+
+    let empty = Collada::from_str(&DOCUMENT.replace(
+        &DOCUMENT[DOCUMENT.find("<geographic_location>").unwrap()..DOCUMENT.find("</coverage>").unwrap()],
+        "",
+    )).unwrap();
+    assert!(empty.asset.coverage.is_some());
+    assert_eq!(None, empty.asset.geographic_location());
+
+    match Collada::from_str(&DOCUMENT.replace("\"absolute\"", "\"clampToGround\"")) {
+        Err(Error { kind: ErrorKind::InvalidValue { element: "altitude", .. }, .. }) => {}
+        result => panic!("Expected an invalid value error, got {:?}", result.map(|_| ())),
+    }
+
+    match Collada::from_str(&DOCUMENT.replace(" mode=\"absolute\"", "")) {
+        Err(Error { kind: ErrorKind::MissingAttribute { element: "altitude", attribute: "mode" }, .. }) => {}
+        result => panic!("Expected a missing attribute error, got {:?}", result.map(|_| ())),
+    }
 }
 
 #[test]
@@ -211,12 +275,12 @@ fn contributor_full() {
     "#;
 
     let expected = Contributor {
-        author: Some("David LeGare".into()),
-        author_email: Some("dl@email.com".into()),
+        author: MaybeEmpty::Value("David LeGare".into()),
+        author_email: MaybeEmpty::Value("dl@email.com".into()),
         author_website: Some("david.com".parse().unwrap()),
-        authoring_tool: Some("Atom".into()),
-        comments: Some("This is a sample COLLADA document.".into()),
-        copyright: Some("David LeGare, free for public use".into()),
+        authoring_tool: MaybeEmpty::Value("Atom".into()),
+        comments: MaybeEmpty::Value("This is a sample COLLADA document.".into()),
+        copyright: MaybeEmpty::Value("David LeGare, free for public use".into()),
         source_data: Some("C:/models/tank.s3d".parse().unwrap()),
     };
 
@@ -372,6 +436,31 @@ fn extra_minimal() {
     assert_eq!(expected, actual.asset.extras[0]);
 }
 
+#[test]
+fn extra_missing_technique() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.5.0">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+            <extra />
+        </asset>
+    </COLLADA>
+    "#;
+
+    let expected = Error {
+        position: TextPosition { row: 6, column: 12 },
+        kind: ErrorKind::MissingRequiredChild {
+            parent: "extra",
+            expected: vec!["technique"],
+        },
+    };
+
+    let actual = Collada::from_str(DOCUMENT).unwrap_err();
+    assert_eq!(expected, actual);
+}
+
 #[test]
 fn extra_full() {
     static DOCUMENT: &'static str = r#"
@@ -401,13 +490,13 @@ fn extra_full() {
             contributors: vec![],
             coverage: None,
             created: "2017-02-07T20:44:30Z".parse().unwrap(),
-            keywords: None,
+            keywords: MaybeEmpty::Absent,
             modified: "2017-02-07T20:44:30Z".parse().unwrap(),
-            revision: None,
-            subject: None,
-            title: None,
-            unit: Unit::default(),
-            up_axis: UpAxis::default(),
+            revision: MaybeEmpty::Absent,
+            subject: MaybeEmpty::Absent,
+            title: MaybeEmpty::Absent,
+            unit: None,
+            up_axis: None,
             extras: Vec::default(),
         }),
         techniques: vec![
@@ -420,3 +509,512 @@ fn extra_full() {
     let actual = Collada::from_str(DOCUMENT).unwrap();
     assert_eq!(expected, actual.asset.extras[0]);
 }
+
+#[test]
+fn library_images() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.5.0">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_images>
+            <image id="Brick-image" name="Brick">
+                <renderable share="true" />
+                <init_from mips_generate="false">
+                    <ref>textures/brick.png</ref>
+                </init_from>
+            </image>
+            <image id="Pixel-image">
+                <init_from>
+                    <hex format="RAW">ff 00 80 ff</hex>
+                </init_from>
+            </image>
+            <image id="Target-image">
+                <create_2d>
+                    <size_exact width="512" height="256" />
+                    <mips levels="0" auto_generate="true" />
+                    <format>
+                        <hint channels="RGBA" range="UNORM" space="sRGB" />
+                        <exact>R8G8B8A8_UNORM</exact>
+                    </format>
+                </create_2d>
+            </image>
+            <image id="Sky-image">
+                <create_cube>
+                    <size width="1024" />
+                    <mips levels="1" auto_generate="false" />
+                    <init_from face="POSITIVE_X"><ref>sky_px.png</ref></init_from>
+                    <init_from face="NEGATIVE_X"><ref>sky_nx.png</ref></init_from>
+                </create_cube>
+            </image>
+        </library_images>
+    </COLLADA>
+    "#;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_images().unwrap();
+    assert_eq!(4, library.images.len());
+
+    let brick = &library.images[0];
+    assert_eq!(Some(Renderable { share: true }), brick.renderable);
+    assert!(!brick.init_from.as_ref().unwrap().mips_generate);
+    assert_eq!(Some("textures/brick.png"), brick.uri().map(AnyUri::as_str));
+    assert_eq!(None, brick.embedded_data());
+
+    let pixel = &library.images[1];
+    assert!(pixel.init_from.as_ref().unwrap().mips_generate);
+    assert_eq!(None, pixel.uri());
+    assert_eq!(Some(&[0xff, 0x00, 0x80, 0xff][..]), pixel.embedded_data());
+    assert_eq!(None, pixel.info());
+
+    let target = library.images[2].create_2d.as_ref().unwrap();
+    assert_eq!(Some(SizeExact { width: 512, height: 256 }), target.size_exact);
+    assert_eq!(Some(Mips { levels: 0, auto_generate: true }), target.mips);
+    let format = target.format.as_ref().unwrap();
+    assert_eq!("RGBA", format.hint.as_ref().unwrap().channels);
+    assert_eq!(Some("sRGB"), format.hint.as_ref().unwrap().space.as_deref());
+    assert_eq!(Some("R8G8B8A8_UNORM"), format.exact.as_deref());
+    assert_eq!(None, library.images[2].uri());
+
+    let sky = &library.images[3];
+    assert_eq!(1024, sky.create_cube.as_ref().unwrap().size.width);
+    let faces = sky.init_froms().map(|init_from| init_from.face.as_deref()).collect::<Vec<_>>();
+    assert_eq!(vec![Some("POSITIVE_X"), Some("NEGATIVE_X")], faces);
+    assert_eq!(Some("sky_px.png"), sky.uri().map(AnyUri::as_str));
+}
+
+#[test]
+fn image_invalid_data() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.5.0">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_images>
+            <image id="Pixel-image">
+                <renderable share="true" />
+                <init_from>
+                    <hex format="RAW">ff 00 80 ff</hex>
+                </init_from>
+            </image>
+        </library_images>
+    </COLLADA>
+    "#;
+
+    match Collada::from_str(&DOCUMENT.replace("80 ff", "80 f")) {
+        Err(Error { kind: ErrorKind::HexBinaryParseError(_), .. }) => {}
+        result => panic!("Expected a hex binary error, got {:?}", result.map(|_| ())),
+    }
+
+    match Collada::from_str(&DOCUMENT.replace(r#"share="true""#, r#"share="yes""#)) {
+        Err(Error { kind: ErrorKind::ParseBoolError(_), .. }) => {}
+        result => panic!("Expected a bool parsing error, got {:?}", result.map(|_| ())),
+    }
+}
+
+static FORMULA_DOCUMENT: &'static str = r##"
+<?xml version="1.0" encoding="utf-8"?>
+<COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.5.0">
+    <asset>
+        <created>2017-02-07T20:44:30Z</created>
+        <modified>2017-02-07T20:44:30Z</modified>
+    </asset>
+    <library_formulas id="formulas">
+        <formula id="coupling" sid="coupling">
+            <newparam sid="ratio"><float>0.5</float></newparam>
+            <newparam sid="driver"><SIDREF>kinematics/joint0</SIDREF></newparam>
+            <newparam sid="inverted"><bool>true</bool></newparam>
+            <newparam sid="teeth"><int>24</int></newparam>
+            <target><param ref="joint1.value"/></target>
+            <technique_common>
+                <math xmlns="http://www.w3.org/1998/Math/MathML">
+                    <apply>
+                        <times/>
+                        <ci>ratio</ci>
+                        <apply><minus/><csymbol encoding="COLLADA">driver</csymbol></apply>
+                    </apply>
+                </math>
+            </technique_common>
+            <technique profile="Maya">
+                <expression>joint1 = -0.5 * joint0</expression>
+            </technique>
+        </formula>
+        <formula id="constant">
+            <target><float sid="value">3</float></target>
+            <technique_common/>
+        </formula>
+    </library_formulas>
+</COLLADA>
+"##;
+
+#[test]
+fn library_formulas() {
+    let document = Collada::from_str(FORMULA_DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_formulas().unwrap();
+    assert_eq!(Some("formulas"), library.id.as_deref());
+    assert_eq!(2, library.formulas.len());
+
+    let coupling = document.formula("coupling").unwrap();
+    assert_eq!(Some("coupling"), coupling.sid.as_deref());
+    assert_eq!(4, coupling.newparams.len());
+    assert_eq!(
+        ParamValue::Float(Float { sid: None, value: 0.5 }),
+        coupling.newparam("ratio").unwrap().value,
+    );
+    assert_eq!(
+        ParamValue::Sidref(Sidref { value: "kinematics/joint0".into() }),
+        coupling.newparam("driver").unwrap().value,
+    );
+    assert_eq!(ParamValue::Bool(Bool { value: true }), coupling.newparam("inverted").unwrap().value);
+    assert_eq!(ParamValue::Int(Int { value: 24 }), coupling.newparam("teeth").unwrap().value);
+    assert_eq!(FloatOrParam::Param(ParamRef { reference: "joint1.value".into() }), coupling.target);
+    assert_eq!("Maya", coupling.techniques[0].profile);
+
+    // The MathML is kept as a raw tree.
+    let math = coupling.technique_common.math().unwrap();
+    let apply = &math.children[0];
+    assert_eq!("apply", apply.name.local_name);
+    assert_eq!("times", apply.children[0].name.local_name);
+    assert_eq!("ratio", apply.children[1].text);
+    let symbol = &apply.children[2].children[1];
+    assert_eq!(Some("COLLADA"), symbol.attribute("encoding"));
+
+    let constant = library.formula("constant").unwrap();
+    assert_eq!(FloatOrParam::Float(Float { sid: Some("value".into()), value: 3.0 }), constant.target);
+    assert_eq!(None, constant.technique_common.math());
+
+    // The target and technique_common are required.
+    let invalid = FORMULA_DOCUMENT.replace(r#"<target><float sid="value">3</float></target>"#, "");
+    match Collada::from_str(&invalid).unwrap_err().kind {
+        ErrorKind::UnexpectedElement { parent: "formula", ref element, .. } => assert_eq!("technique_common", element),
+        kind => panic!("Expected an error for the missing target, got {:?}", kind),
+    }
+}
+
+#[cfg(feature = "mathml")]
+#[test]
+fn formula_mathml() {
+    use ::collaborate::mathml::Expression;
+
+    let document = Collada::from_str(FORMULA_DOCUMENT).unwrap();
+    let coupling = document.formula("coupling").unwrap();
+    let expression = coupling.technique_common.expression().unwrap();
+    assert_eq!(
+        Expression::Apply("times".into(), vec![
+            Expression::Identifier("ratio".into()),
+            Expression::Apply("minus".into(), vec![Expression::Symbol("driver".into())]),
+        ]),
+        expression,
+    );
+
+    let lookup = |name: &str| match name {
+        "ratio" => Some(0.5),
+        "driver" => Some(30.0),
+        _ => None,
+    };
+    assert_eq!(Some(-15.0), expression.evaluate(&lookup));
+    assert_eq!(None, expression.evaluate(&|_| None));
+
+    let constant = document.formula("constant").unwrap();
+    assert_eq!(None, constant.technique_common.expression());
+}
+
+#[test]
+fn brep_geometry() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.5.0">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry id="Disc-brep" name="Disc">
+                <brep>
+                    <curves>
+                        <curve sid="rim">
+                            <circle><radius>2</radius></circle>
+                            <orient>0 0 1 90</orient>
+                            <origin>0 0 1</origin>
+                        </curve>
+                        <curve sid="spline">
+                            <nurbs degree="2" closed="true">
+                                <source id="spline-cvs">
+                                    <float_array id="spline-cvs-array" count="6">0 0 0 1 1 0</float_array>
+                                    <technique_common>
+                                        <accessor source="#spline-cvs-array" count="2" stride="3">
+                                            <param name="X" type="float"/>
+                                            <param name="Y" type="float"/>
+                                            <param name="Z" type="float"/>
+                                        </accessor>
+                                    </technique_common>
+                                </source>
+                                <control_vertices>
+                                    <input semantic="POSITION" source="#spline-cvs"/>
+                                </control_vertices>
+                            </nurbs>
+                        </curve>
+                    </curves>
+                    <surfaces>
+                        <surface sid="top">
+                            <plane><equation>0 0 1 -1</equation></plane>
+                        </surface>
+                        <surface sid="extrusion">
+                            <swept_surface>
+                                <curve><line><origin>0 0 0</origin><direction>1 0 0</direction></line></curve>
+                                <direction>0 0 1</direction>
+                            </swept_surface>
+                        </surface>
+                    </surfaces>
+                    <source id="Disc-positions">
+                        <float_array id="Disc-positions-array" count="3">2 0 1</float_array>
+                    </source>
+                    <source id="Disc-curves">
+                        <SIDREF_array id="Disc-curves-array" count="1">Disc-brep/rim</SIDREF_array>
+                    </source>
+                    <source id="Disc-orientations">
+                        <token_array id="Disc-orientations-array" count="2">FORWARD REVERSED</token_array>
+                    </source>
+                    <vertices id="Disc-vertices">
+                        <input semantic="POSITION" source="#Disc-positions"/>
+                    </vertices>
+                    <edges id="Disc-edges" count="1">
+                        <input semantic="CURVE" source="#Disc-curves" offset="0"/>
+                        <input semantic="VERTEX" source="#Disc-vertices" offset="1"/>
+                        <p>0 0 0</p>
+                    </edges>
+                    <wires id="Disc-wires" count="1">
+                        <input semantic="EDGE" source="#Disc-edges" offset="0"/>
+                        <input semantic="ORIENTATION" source="#Disc-orientations" offset="1"/>
+                        <vcount>1</vcount>
+                        <p>0 0</p>
+                    </wires>
+                    <faces id="Disc-faces" count="2">
+                        <input semantic="SURFACE" source="#Disc-surfaces" offset="0"/>
+                        <input semantic="WIRE" source="#Disc-wires" offset="1"/>
+                        <input semantic="ORIENTATION" source="#Disc-orientations" offset="2"/>
+                        <vcount>1 2</vcount>
+                        <p>0 0 0 1 0 1 1 0 0</p>
+                    </faces>
+                </brep>
+            </geometry>
+            <geometry id="Cube-mesh">
+                <mesh/>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    assert_eq!(2, library.geometries.len());
+    assert_eq!(None, library.geometries[1].geometric_element.as_brep());
+
+    let brep = document.geometry("Disc-brep").unwrap().geometric_element.as_brep().unwrap();
+
+    // Geometry.
+    let rim = brep.curve("rim").unwrap();
+    match rim.geometry {
+        CurveGeometry::Circle(ref circle) => assert_eq!(2.0, circle.radius),
+        ref geometry => panic!("Expected a circle, got {:?}", geometry),
+    }
+    assert_eq!(vec![0.0, 0.0, 1.0, 90.0], rim.orients[0].values);
+    assert_eq!(Some(vec![0.0, 0.0, 1.0]), rim.origin.as_ref().map(|origin| origin.values.clone()));
+    match brep.curve("spline").unwrap().geometry {
+        CurveGeometry::Nurbs(ref nurbs) => {
+            assert_eq!(2, nurbs.degree);
+            assert!(nurbs.closed);
+            assert_eq!(Some(3), nurbs.sources[0].accessor.as_ref().map(|accessor| accessor.stride));
+            assert_eq!("spline-cvs", nurbs.control_vertices.input("POSITION").unwrap().source.id());
+        }
+        ref geometry => panic!("Expected a NURBS curve, got {:?}", geometry),
+    }
+    match brep.surface("top").unwrap().geometry {
+        SurfaceGeometry::Plane(ref plane) => assert_eq!(vec![0.0, 0.0, 1.0, -1.0], plane.equation.values),
+        ref geometry => panic!("Expected a plane, got {:?}", geometry),
+    }
+    match brep.surface("extrusion").unwrap().geometry {
+        SurfaceGeometry::SweptSurface(ref swept) => {
+            match swept.curve.geometry {
+                CurveGeometry::Line(ref line) => assert_eq!(vec![1.0, 0.0, 0.0], line.direction.values),
+                ref geometry => panic!("Expected a line, got {:?}", geometry),
+            }
+            assert_eq!(Some(vec![0.0, 0.0, 1.0]), swept.direction.as_ref().map(|direction| direction.values.clone()));
+            assert_eq!(None, swept.axis);
+        }
+        ref geometry => panic!("Expected a swept surface, got {:?}", geometry),
+    }
+
+    // Sources, including the array types added in 1.5.
+    assert_eq!(3, brep.sources.len());
+    let curves = brep.find_source("Disc-curves").unwrap();
+    assert_eq!(Some("Disc-curves-array"), curves.array.as_ref().and_then(Array::id));
+    match brep.find_source("Disc-orientations").unwrap().array {
+        Some(Array::Token(ref array)) => assert_eq!(vec!["FORWARD", "REVERSED"], array.data),
+        ref array => panic!("Expected a token array, got {:?}", array),
+    }
+    let positions = brep.find_source("Disc-positions").and_then(|source| source.array.as_ref()).unwrap();
+    assert_eq!(&[2.0, 0.0, 1.0], positions.as_float_array().unwrap().data.get().unwrap());
+
+    // Topology.
+    assert_eq!("Disc-vertices", brep.vertices.id);
+    let edges = brep.edges.as_ref().unwrap();
+    assert_eq!(vec![&[0, 0, 0][..]], edges.iter().collect::<Vec<_>>());
+    let wires = brep.wires.as_ref().unwrap();
+    assert_eq!(Some(1), wires.input("ORIENTATION").map(|input| input.offset));
+    assert_eq!(vec![&[0, 0][..]], wires.iter().collect::<Vec<_>>());
+    let faces = brep.faces.as_ref().unwrap();
+    assert_eq!(2, faces.count);
+    assert_eq!(vec![&[0, 0, 0][..], &[1, 0, 1, 1, 0, 0][..]], faces.iter().collect::<Vec<_>>());
+    assert_eq!(None, brep.solids);
+
+    // The vertices are required.
+    let invalid = DOCUMENT.replace(r##"<input semantic="POSITION" source="#Disc-positions"/>"##, "")
+        .replace(r#"<vertices id="Disc-vertices">"#, "")
+        .replace("</vertices>", "");
+    match Collada::from_str(&invalid).unwrap_err().kind {
+        ErrorKind::UnexpectedElement { parent: "brep", ref element, .. } => assert_eq!("edges", element),
+        kind => panic!("Expected an error for the missing vertices, got {:?}", kind),
+    }
+}
+
+#[test]
+fn scene_kinematics() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2008/03/COLLADASchema" version="1.5.0">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <scene>
+            <instance_physics_scene url="#World" />
+            <instance_visual_scene url="#Robot-scene" />
+            <instance_kinematics_scene url="#Robot-kinematics" sid="kinematics">
+                <newparam sid="arm-model">
+                    <SIDREF>Robot-kinematics/arm</SIDREF>
+                </newparam>
+                <newparam sid="shoulder-value">
+                    <float>30</float>
+                </newparam>
+                <setparam ref="kinematics.speed">
+                    <float>2.5</float>
+                </setparam>
+                <setparam ref="kinematics.shoulder">
+                    <connect_param ref="shoulder-value" />
+                </setparam>
+                <bind_kinematics_model node="Arm">
+                    <param ref="arm-model" />
+                </bind_kinematics_model>
+                <bind_joint_axis target="Arm/Shoulder/rotateZ">
+                    <axis>
+                        <SIDREF>Robot-kinematics/arm/shoulder/axis0</SIDREF>
+                    </axis>
+                    <value>
+                        <param ref="shoulder-value" />
+                    </value>
+                </bind_joint_axis>
+            </instance_kinematics_scene>
+        </scene>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let scene = document.scene.unwrap();
+    assert_eq!(Some("World"), scene.instance_physics_scenes[0].url.local_id());
+    assert_eq!(Some("Robot-scene"), scene.instance_visual_scene.unwrap().url.local_id());
+
+    let kinematics = scene.instance_kinematics_scene.unwrap();
+    assert_eq!(Some("kinematics"), kinematics.sid.as_deref());
+    assert_eq!(
+        ParamValue::Sidref(Sidref { value: "Robot-kinematics/arm".into() }),
+        kinematics.newparam("arm-model").unwrap().value,
+    );
+    assert_eq!(
+        ParamValue::Float(Float { sid: None, value: 2.5 }),
+        kinematics.setparam("kinematics.speed").unwrap().value,
+    );
+    assert_eq!(
+        ParamValue::ConnectParam(ConnectParam { reference: "shoulder-value".into() }),
+        kinematics.setparam("kinematics.shoulder").unwrap().value,
+    );
+
+    let model = &kinematics.bind_kinematics_models[0];
+    assert_eq!("Arm", model.node);
+    assert_eq!(SidrefOrParam::Param(ParamRef { reference: "arm-model".into() }), model.model);
+
+    let axis = &kinematics.bind_joint_axes[0];
+    assert_eq!("Arm/Shoulder/rotateZ", axis.target);
+    assert_eq!(
+        SidrefOrParam::Sidref(Sidref { value: "Robot-kinematics/arm/shoulder/axis0".into() }),
+        axis.axis,
+    );
+    assert_eq!(FloatOrParam::Param(ParamRef { reference: "shoulder-value".into() }), axis.value);
+}
+
+#[test]
+fn scene_evaluate_scene() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2008/03/COLLADASchema" version="1.5.0">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <scene>
+            <instance_visual_scene url="#Scene" />
+            <evaluate_scene id="Passes" sid="passes">
+                <render name="Main" camera_node="#Camera">
+                    <layer>world</layer>
+                </render>
+                <render sid="bloom">
+                    <instance_material url="#Bloom-material">
+                        <technique_override ref="glsl" pass="blur" />
+                        <bind semantic="SCENE_COLOR" target="Passes/Main" />
+                        <extra>
+                            <technique profile="blender" />
+                        </extra>
+                    </instance_material>
+                </render>
+            </evaluate_scene>
+            <evaluate_scene enable="false">
+                <render />
+            </evaluate_scene>
+        </scene>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let scene = document.scene.unwrap();
+    assert_eq!(2, scene.evaluate_scenes.len());
+
+    let passes = &scene.evaluate_scenes[0];
+    assert_eq!(Some("Passes"), passes.id.as_deref());
+    assert!(passes.enable);
+    assert!(!scene.evaluate_scenes[1].enable);
+
+    let main = &passes.renders[0];
+    assert_eq!(Some("Main"), main.name.as_deref());
+    assert_eq!(Some("Camera"), main.camera_node.as_ref().and_then(AnyUri::local_id));
+    assert_eq!(vec!["world"], main.layers);
+    assert!(main.instance_material.is_none());
+
+    // A render without a camera is a screen-space pass.
+    let bloom = &passes.renders[1];
+    assert!(bloom.camera_node.is_none());
+    let material = bloom.instance_material.as_ref().unwrap();
+    assert_eq!(Some("Bloom-material"), material.url.local_id());
+    assert_eq!(
+        Some(TechniqueOverride { reference: "glsl".into(), pass: Some("blur".into()) }),
+        material.technique_override,
+    );
+    assert_eq!(
+        vec![Bind { semantic: "SCENE_COLOR".into(), target: "Passes/Main".into() }],
+        material.binds,
+    );
+    assert_eq!(1, material.extras.len());
+}
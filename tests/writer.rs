@@ -0,0 +1,614 @@
+extern crate collaborate;
+
+use ::collaborate::*;
+use ::collaborate::writer::{AttributeOrder, FloatFormat, Indent, Newline, WriteError, WriteOptions};
+use std::fs::File;
+use std::io::{self, Write};
+
+fn write_to_string(document: &VersionedDocument, options: WriteOptions) -> String {
+    let mut output = Vec::new();
+    document.write(&mut output, options).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn round_trip_blender_cube() {
+    let document = VersionedDocument::read(File::open("resources/blender_cube.dae").unwrap()).unwrap();
+    let written = write_to_string(&document, WriteOptions::default());
+    assert_eq!(document, VersionedDocument::from_str(&written).unwrap());
+}
+
+#[test]
+fn round_trip_v1_5() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2008/03/COLLADASchema" version="1.5.0">
+        <asset>
+            <coverage>
+                <geographic_location>
+                    <longitude>-105.283</longitude>
+                    <latitude>40.017</latitude>
+                    <altitude mode="relativeToGround">12.5</altitude>
+                </geographic_location>
+            </coverage>
+            <created>2017-02-07T20:44:30Z</created>
+            <keywords>foo bar baz</keywords>
+            <modified>2017-02-07T20:44:30Z</modified>
+            <unit meter="0.01" name="centimeter" />
+            <up_axis>Z_UP</up_axis>
+        </asset>
+    </COLLADA>
+    "#;
+
+    let document = VersionedDocument::from_str(DOCUMENT).unwrap();
+    let written = write_to_string(&document, WriteOptions::default());
+    assert_eq!(document, VersionedDocument::from_str(&written).unwrap());
+}
+
+#[test]
+fn empty_text_elements() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2008/03/COLLADASchema" version="1.5.0">
+        <asset>
+            <contributor><author /><author_email></author_email></contributor>
+            <created>2017-02-07T20:44:30Z</created>
+            <keywords />
+            <modified>2017-02-07T20:44:30Z</modified>
+            <title>Title</title>
+        </asset>
+    </COLLADA>
+    "#;
+
+    // Empty elements are written back, while missing ones stay missing.
+    let document = VersionedDocument::from_str(DOCUMENT).unwrap();
+    let written = write_to_string(&document, WriteOptions::default());
+    assert!(written.contains("<author/>"), "{}", written);
+    assert!(written.contains("<author_email/>"), "{}", written);
+    assert!(written.contains("<keywords/>"), "{}", written);
+    assert!(written.contains("<title>Title</title>"), "{}", written);
+    assert!(!written.contains("subject"), "{}", written);
+    assert_eq!(document, VersionedDocument::from_str(&written).unwrap());
+}
+
+#[test]
+fn technique_namespaces() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <extra>
+            <technique profile="formulas">
+                <math xmlns="http://www.w3.org/1998/Math/MathML">
+                    <apply><times /><ci>ratio</ci><cn>2</cn></apply>
+                </math>
+                <flag>1</flag>
+            </technique>
+        </extra>
+    </COLLADA>
+    "#;
+
+    let document = VersionedDocument::from_str(DOCUMENT).unwrap();
+    let written = write_to_string(&document, WriteOptions::default());
+    assert_eq!(document, VersionedDocument::from_str(&written).unwrap());
+
+    // The MathML namespace is declared where it's used, and the elements after it are back in the
+    // document's namespace.
+    assert!(written.contains(r#"<math xmlns="http://www.w3.org/1998/Math/MathML">"#), "{}", written);
+    assert!(written.contains("<apply>"), "{}", written);
+    assert!(written.contains("<flag>1</flag>"), "{}", written);
+
+    // Elements in the document's namespace move to the namespace of the version being written.
+    let options = WriteOptions {
+        version: Some(Version::V1_5_0),
+        ..WriteOptions::default()
+    };
+    let written = write_to_string(&document, options);
+    assert!(written.contains(r#"<math xmlns="http://www.w3.org/1998/Math/MathML">"#), "{}", written);
+    assert!(written.contains("<flag>1</flag>"), "{}", written);
+}
+
+#[test]
+fn written_format() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <contributor>
+                <author>Tom &amp; Jerry</author>
+            </contributor>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+            <title>&lt;Untitled&gt;</title>
+            <unit name="&quot;inch&quot;" meter="0.0254" />
+        </asset>
+        <library_geometries>
+            <geometry id="line">
+                <mesh>
+                    <source id="positions">
+                        <float_array id="positions-array" count="6">0 0 0 1 0.5 -2</float_array>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#positions" />
+                    </vertices>
+                    <lines count="1">
+                        <input semantic="VERTEX" source="#vertices" offset="0" />
+                        <p>0 1</p>
+                    </lines>
+                </mesh>
+            </geometry>
+        </library_geometries>
+        <extra>
+            <technique profile="example">
+                <note kind="a &lt; b">Hello</note>
+            </technique>
+        </extra>
+    </COLLADA>
+    "##;
+
+    static EXPECTED: &'static str = r##"<?xml version="1.0" encoding="utf-8"?>
+<COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+  <asset>
+    <contributor>
+      <author>Tom &amp; Jerry</author>
+    </contributor>
+    <created>2017-02-07T20:44:30+00:00</created>
+    <modified>2017-02-07T20:44:30+00:00</modified>
+    <title>&lt;Untitled&gt;</title>
+    <unit meter="0.0254" name="&quot;inch&quot;"/>
+  </asset>
+  <library_geometries>
+    <geometry id="line">
+      <mesh>
+        <source id="positions">
+          <float_array count="6" id="positions-array" digits="6" magnitude="38">0 0 0 1 0.5 -2</float_array>
+        </source>
+        <vertices id="vertices">
+          <input semantic="POSITION" source="#positions"/>
+        </vertices>
+        <lines count="1">
+          <input offset="0" semantic="VERTEX" source="#vertices"/>
+          <p>0 1</p>
+        </lines>
+      </mesh>
+    </geometry>
+  </library_geometries>
+  <extra>
+    <technique profile="example">
+      <note kind="a &lt; b">Hello</note>
+    </technique>
+  </extra>
+</COLLADA>
+"##;
+
+    let document = VersionedDocument::from_str(DOCUMENT).unwrap();
+    let written = write_to_string(&document, WriteOptions::default());
+    assert_eq!(EXPECTED, written);
+    assert_eq!(document, VersionedDocument::from_str(&written).unwrap());
+
+    let options = WriteOptions {
+        xml_declaration: false,
+        ..WriteOptions::default()
+    };
+    let written = write_to_string(&document, options);
+    assert!(written.starts_with("<COLLADA "));
+}
+
+#[test]
+fn formatting_options() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+            <unit name="inch" meter="0.0254" />
+        </asset>
+        <library_geometries>
+            <geometry id="point">
+                <mesh>
+                    <source id="positions">
+                        <float_array id="positions-array" count="3">0 0.5 -2</float_array>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#positions" />
+                    </vertices>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = VersionedDocument::from_str(DOCUMENT).unwrap();
+
+    let options = WriteOptions {
+        xml_declaration: false,
+        indent: Indent::Tabs,
+        newline: Newline::CrLf,
+        ..WriteOptions::default()
+    };
+    let written = write_to_string(&document, options);
+    assert!(written.starts_with("<COLLADA xmlns=\"http://www.collada.org/2005/11/COLLADASchema\" version=\"1.4.1\">\r\n\t<asset>\r\n\t\t<created>"));
+    assert!(written.ends_with("\t</library_geometries>\r\n</COLLADA>\r\n"));
+    assert_eq!(document, VersionedDocument::from_str(&written).unwrap());
+
+    let options = WriteOptions {
+        xml_declaration: false,
+        indent: Indent::None,
+        ..WriteOptions::default()
+    };
+    let written = write_to_string(&document, options);
+    assert!(written.contains("\n<asset>\n<created>"));
+
+    let options = WriteOptions {
+        xml_declaration: false,
+        newline: Newline::None,
+        ..WriteOptions::default()
+    };
+    let written = write_to_string(&document, options);
+    assert!(!written.contains('\n'));
+    assert!(written.contains("<asset><created>"));
+    assert_eq!(document, VersionedDocument::from_str(&written).unwrap());
+
+    let options = WriteOptions {
+        attribute_order: AttributeOrder::Alphabetical,
+        ..WriteOptions::default()
+    };
+    let written = write_to_string(&document, options);
+    assert!(written.contains(r#"<COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">"#));
+    assert!(written.contains(r#"<unit meter="0.0254" name="inch"/>"#));
+    assert!(written.contains(r#"<float_array count="3" digits="6" id="positions-array" magnitude="38">"#));
+
+    let options = WriteOptions {
+        float_format: FloatFormat::Fixed(6),
+        ..WriteOptions::default()
+    };
+    let written = write_to_string(&document, options.clone());
+    assert!(written.contains(r#"<unit meter="0.025400" name="inch"/>"#));
+    assert!(written.contains(">0.000000 0.500000 -2.000000</float_array>"));
+
+    // Reformatted arrays keep their new text, so compare by writing the document again.
+    let reparsed = VersionedDocument::from_str(&written).unwrap();
+    assert_eq!(written, write_to_string(&reparsed, options));
+}
+
+
+#[test]
+fn write_as_v1_5() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_images>
+            <image id="file" name="File">
+                <init_from>textures/file.png</init_from>
+            </image>
+            <image id="embedded" format="PNG">
+                <data>89504e47</data>
+            </image>
+        </library_images>
+    </COLLADA>
+    "#;
+
+    let document = VersionedDocument::from_str(DOCUMENT).unwrap();
+    let options = WriteOptions {
+        version: Some(Version::V1_5_0),
+        ..WriteOptions::default()
+    };
+    let written = write_to_string(&document, options);
+    assert!(written.contains(r#"<COLLADA xmlns="http://www.collada.org/2008/03/COLLADASchema" version="1.5.0">"#));
+
+    let written = match VersionedDocument::from_str(&written).unwrap() {
+        VersionedDocument::V1_5(written) => written,
+        written => panic!("Expected a 1.5 document, got {:?}", written),
+    };
+    let images = written.libraries[0].as_library_images().unwrap();
+    assert_eq!(2, images.images.len());
+    assert_eq!(Some("File"), images.images[0].name.as_deref());
+    assert_eq!("textures/file.png", images.images[0].uri().unwrap().as_str());
+    assert_eq!(Some(&[0x89, 0x50, 0x4e, 0x47][..]), images.images[1].embedded_data());
+    match images.images[1].init_from.as_ref().unwrap().source {
+        v1_5::ImageSource::Hex(ref hex) => assert_eq!("PNG", hex.format),
+        ref source => panic!("Expected hex data, got {:?}", source),
+    }
+}
+
+#[test]
+fn write_as_v1_4() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2008/03/COLLADASchema" version="1.5.0">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_images>
+            <image id="file">
+                <init_from>
+                    <ref>textures/file.png</ref>
+                </init_from>
+            </image>
+            <image id="embedded">
+                <init_from>
+                    <hex format="PNG">89504e47</hex>
+                </init_from>
+            </image>
+        </library_images>
+    </COLLADA>
+    "#;
+
+    let document = VersionedDocument::from_str(DOCUMENT).unwrap();
+    let options = WriteOptions {
+        version: Some(Version::V1_4_1),
+        ..WriteOptions::default()
+    };
+    let written = write_to_string(&document, options);
+    assert!(written.contains(r#"<COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">"#));
+
+    let written = match VersionedDocument::from_str(&written).unwrap() {
+        VersionedDocument::V1_4(written) => written,
+        written => panic!("Expected a 1.4 document, got {:?}", written),
+    };
+    let images: Vec<_> = written.libraries.iter()
+        .filter_map(|library| match *library {
+            v1_4::Library::Images(ref images) => Some(images),
+            _ => None,
+        })
+        .flat_map(|images| &images.images)
+        .collect();
+    assert_eq!(2, images.len());
+    assert_eq!("textures/file.png", images[0].uri().unwrap().as_str());
+    assert_eq!(Some("PNG"), images[1].format.as_deref());
+    assert_eq!(Some(&[0x89, 0x50, 0x4e, 0x47][..]), images[1].embedded_data());
+}
+
+#[test]
+fn version_mismatch() {
+    static V1_5_DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2008/03/COLLADASchema" version="1.5.0">
+        <asset>
+            <coverage>
+                <geographic_location>
+                    <longitude>-105.283</longitude>
+                    <latitude>40.017</latitude>
+                    <altitude mode="absolute">0</altitude>
+                </geographic_location>
+            </coverage>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+    </COLLADA>
+    "#;
+
+    static V1_4_DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_images>
+            <image id="file" width="256" height="256">
+                <init_from>textures/file.png</init_from>
+            </image>
+        </library_images>
+    </COLLADA>
+    "#;
+
+    let document = VersionedDocument::from_str(V1_5_DOCUMENT).unwrap();
+    let options = WriteOptions {
+        version: Some(Version::V1_4_1),
+        ..WriteOptions::default()
+    };
+    match document.write(Vec::new(), options) {
+        Err(WriteError::VersionMismatch { ref element, ref member, valid_in, version }) => {
+            assert_eq!("COLLADA/asset", element);
+            assert_eq!("coverage", member);
+            assert_eq!("1.5", valid_in);
+            assert_eq!(Version::V1_4_1, version);
+        }
+        result => panic!("Expected a version mismatch, got {:?}", result),
+    }
+
+    let document = VersionedDocument::from_str(V1_4_DOCUMENT).unwrap();
+    let options = WriteOptions {
+        version: Some(Version::V1_5_0),
+        ..WriteOptions::default()
+    };
+    let error = document.write(Vec::new(), options).unwrap_err();
+    assert_eq!(
+        "<COLLADA/library_images/image> has \"height\" which is only allowed in COLLADA 1.4, but the document is being written as version 1.5.0",
+        error.to_string(),
+    );
+}
+
+
+#[test]
+fn large_arrays_are_streamed() {
+    /// Records the largest single write, to check that arrays aren't written all at once.
+    struct LargestWrite {
+        output: Vec<u8>,
+        largest: usize,
+    }
+
+    impl Write for LargestWrite {
+        fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+            self.largest = self.largest.max(buffer.len());
+            self.output.extend_from_slice(buffer);
+            Ok(buffer.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> { Ok(()) }
+    }
+
+    const VERTICES: usize = 100_000;
+    let positions: Vec<String> = (0..VERTICES * 3).map(|index| format!("{}.5", index)).collect();
+    let indices: Vec<String> = (0..VERTICES).map(|index| index.to_string()).collect();
+    let document = format!(r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry id="points">
+                <mesh>
+                    <source id="positions">
+                        <float_array id="positions-array" count="{}">{}</float_array>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#positions" />
+                    </vertices>
+                    <lines count="{}">
+                        <input semantic="VERTEX" source="#vertices" offset="0" />
+                        <p>{}</p>
+                    </lines>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##, VERTICES * 3, positions.join(" "), VERTICES / 2, indices.join(" "));
+    let document = v1_4::Collada::from_str(&document).unwrap();
+
+    let options = WriteOptions {
+        float_format: FloatFormat::Fixed(1),
+        ..WriteOptions::default()
+    };
+    let mut output = LargestWrite { output: Vec::new(), largest: 0 };
+    document.write(&mut output, options).unwrap();
+
+    // The arrays are over a megabyte of text, but are written a few kilobytes at a time.
+    assert!(output.output.len() > 1_000_000);
+    assert!(output.largest <= 16 * 1024, "Largest write was {} bytes", output.largest);
+
+    let written = String::from_utf8(output.output).unwrap();
+    assert!(written.contains(">0.5 1.5 2.5 "));
+    assert!(written.contains(" 299999.5</float_array>"));
+
+    let written = v1_4::Collada::from_str(&written).unwrap();
+    let mesh = written.geometry("points").unwrap().geometric_element.as_mesh().unwrap();
+    assert_eq!(mesh.primitives, document.geometry("points").unwrap().geometric_element.as_mesh().unwrap().primitives);
+}
+
+
+#[test]
+fn write_error() {
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::WriteZero, "disk full"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> { Ok(()) }
+    }
+
+    let document = v1_4::Collada::read(File::open("resources/blender_cube.dae").unwrap()).unwrap();
+    match document.write(FailingWriter, WriteOptions::default()) {
+        Err(WriteError::Io(ref error)) => assert_eq!(io::ErrorKind::WriteZero, error.kind()),
+        result => panic!("Expected an IO error, got {:?}", result),
+    }
+}
+
+#[test]
+fn preserve_unknown_content() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" version="1.4.1" xsi:schemaLocation="http://www.collada.org/2005/11/COLLADASchema collada.xsd">
+        <asset>
+            <exporter_notes>First</exporter_notes>
+            <created>2017-02-07T20:44:30Z</created>
+            <revision_notes draft="true">Not in the schema</revision_notes>
+            <modified>2017-02-07T20:44:30Z</modified>
+            <ext:notes xmlns:ext="http://example.com/ext" ext:author="Someone">Prefixed</ext:notes>
+        </asset>
+        <library_cameras>
+            <camera id="camera">
+                <optics>
+                    <technique_common>
+                        <perspective>
+                            <yfov>37</yfov>
+                            <aspect_ratio>1.5</aspect_ratio>
+                            <znear>0.1</znear>
+                            <zfar>100</zfar>
+                        </perspective>
+                    </technique_common>
+                </optics>
+                <imager>
+                    <technique profile="test">
+                        <exposure>2</exposure>
+                    </technique>
+                </imager>
+            </camera>
+        </library_cameras>
+    </COLLADA>
+    "##;
+
+    static EXPECTED: &'static str = r##"<?xml version="1.0" encoding="utf-8"?>
+<COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://www.collada.org/2005/11/COLLADASchema collada.xsd">
+  <asset>
+    <exporter_notes>First</exporter_notes>
+    <created>2017-02-07T20:44:30+00:00</created>
+    <revision_notes draft="true">Not in the schema</revision_notes>
+    <modified>2017-02-07T20:44:30+00:00</modified>
+    <ext:notes xmlns:ext="http://example.com/ext" ext:author="Someone">Prefixed</ext:notes>
+  </asset>
+  <library_cameras>
+    <camera id="camera">
+      <optics>
+        <technique_common>
+          <perspective>
+            <yfov>37</yfov>
+            <aspect_ratio>1.5</aspect_ratio>
+            <znear>0.1</znear>
+            <zfar>100</zfar>
+          </perspective>
+        </technique_common>
+      </optics>
+      <imager>
+        <technique profile="test">
+          <exposure>2</exposure>
+        </technique>
+      </imager>
+    </camera>
+  </library_cameras>
+</COLLADA>
+"##;
+
+    let parse_options = ParseOptions {
+        strictness: Strictness::Lenient,
+        preserve_unknown: true,
+        ..ParseOptions::default()
+    };
+    let (document, report) = VersionedDocument::read_with(DOCUMENT.as_bytes(), parse_options.clone()).unwrap();
+
+    // The root's attribute, the three elements in the asset, and the contents of the stubbed
+    // `<imager>`.
+    assert_eq!(5, report.preserved.len());
+
+    let options = WriteOptions {
+        preserved: Some(report.preserved.clone()),
+        ..WriteOptions::default()
+    };
+    let written = write_to_string(&document, options);
+    assert_eq!(EXPECTED, written);
+
+    // Parsing the written document keeps the same content.
+    let (reparsed, reparsed_report) = VersionedDocument::read_with(written.as_bytes(), parse_options).unwrap();
+    assert_eq!(document, reparsed);
+    assert_eq!(report.preserved, reparsed_report.preserved);
+
+    // Without the preserved content, only the document model is written.
+    let written = write_to_string(&document, WriteOptions::default());
+    assert!(!written.contains("exporter_notes"));
+    assert!(!written.contains("schemaLocation"));
+    assert!(written.contains("<imager/>"));
+}